@@ -0,0 +1,95 @@
+//! Per-packet decode dump (`pcap analyse --dump-packets`).
+//!
+//! Every packet of the capture is decoded on its own, like
+//! `DecodedPacketIter` does, and written as one line: timestamp, endpoints,
+//! protocol, then the universe, sequence, and other key fields, or the
+//! parser's error for malformed packets. Filters keep the packets matching
+//! all of them.
+
+use std::io::Write;
+use std::str::FromStr;
+
+use liveshark_core::{DecodedPacket, DecodedPacketIter, PacketSource, Protocol};
+
+/// Condition a dumped packet must match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PacketFilter {
+    /// Decoded (or rejected) as this protocol: `artnet`, `sacn`, `udp`.
+    Protocol(Protocol),
+    /// Without a UDP layer: `non-udp`.
+    NonUdp,
+    /// Rejected by a parser: `malformed`.
+    Malformed,
+    /// DMX or ArtNzs data of this universe: `universe=N`.
+    Universe(u16),
+}
+
+impl PacketFilter {
+    fn matches(self, packet: &DecodedPacket) -> bool {
+        match self {
+            PacketFilter::Protocol(protocol) => packet.protocol() == Some(protocol),
+            PacketFilter::NonUdp => matches!(packet, DecodedPacket::NonUdp { .. }),
+            PacketFilter::Malformed => matches!(packet, DecodedPacket::Malformed { .. }),
+            PacketFilter::Universe(universe) => packet.universe() == Some(universe),
+        }
+    }
+}
+
+impl FromStr for PacketFilter {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "artnet" => Ok(PacketFilter::Protocol(Protocol::ArtNet)),
+            "sacn" => Ok(PacketFilter::Protocol(Protocol::Sacn)),
+            "udp" => Ok(PacketFilter::Protocol(Protocol::Udp)),
+            "non-udp" => Ok(PacketFilter::NonUdp),
+            "malformed" => Ok(PacketFilter::Malformed),
+            _ => value
+                .strip_prefix("universe=")
+                .and_then(|universe| universe.parse().ok())
+                .map(PacketFilter::Universe)
+                .ok_or_else(|| {
+                    format!(
+                        "unknown packet filter: {value} (expected artnet, sacn, udp, non-udp, malformed, or universe=N)"
+                    )
+                }),
+        }
+    }
+}
+
+/// Write one line per packet of `source` matching every filter.
+pub(crate) fn write_packets<S: PacketSource>(
+    source: S,
+    filters: &[PacketFilter],
+    out: &mut dyn Write,
+) -> anyhow::Result<()> {
+    for packet in DecodedPacketIter::new(source) {
+        let packet = packet?;
+        if filters.iter().all(|filter| filter.matches(&packet)) {
+            writeln!(out, "{packet}")?;
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PacketFilter;
+    use liveshark_core::Protocol;
+
+    #[test]
+    fn filters_parse() {
+        assert_eq!(
+            "sacn".parse::<PacketFilter>(),
+            Ok(PacketFilter::Protocol(Protocol::Sacn))
+        );
+        assert_eq!(
+            "universe=7".parse::<PacketFilter>(),
+            Ok(PacketFilter::Universe(7))
+        );
+        assert!("universe=x".parse::<PacketFilter>().is_err());
+        assert!("tcp".parse::<PacketFilter>().is_err());
+    }
+}
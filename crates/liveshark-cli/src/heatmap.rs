@@ -0,0 +1,175 @@
+//! Rendering of channel activity matrices (`pcap heatmap`).
+//!
+//! The CSV matrix has one row per universe and one column per channel. The
+//! PNG draws the same matrix 512 pixels wide, one band per universe, with a
+//! black-red-yellow-white scale relative to the busiest channel. The PNG
+//! encoder stores the image uncompressed (deflate "stored" blocks), which
+//! keeps it dependency-free; files stay small at this size.
+
+use liveshark_core::{ActivityMetric, ChannelActivity};
+
+/// Pixel rows drawn per universe.
+const BAND_HEIGHT: usize = 8;
+/// Largest payload of a deflate stored block.
+const STORED_BLOCK_LEN: usize = 65_535;
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+/// CSV matrix: `universe,proto,1,...,512`, then one row per universe.
+pub(crate) fn render_csv(activity: &ChannelActivity, metric: ActivityMetric) -> String {
+    let mut csv = String::from("universe,proto");
+    for channel in 1..=512 {
+        csv.push_str(&format!(",{channel}"));
+    }
+    csv.push('\n');
+    for universe in &activity.universes {
+        csv.push_str(&format!("{},{}", universe.universe, universe.proto));
+        for value in universe.values(metric) {
+            csv.push_str(&format!(",{value}"));
+        }
+        csv.push('\n');
+    }
+    csv
+}
+
+/// RGB PNG, 512 pixels wide and `BAND_HEIGHT` rows per universe.
+pub(crate) fn render_png(activity: &ChannelActivity, metric: ActivityMetric) -> Vec<u8> {
+    let max = activity.max(metric);
+    let width = 512usize;
+    let height = activity.universes.len().max(1) * BAND_HEIGHT;
+
+    // Each scanline starts with filter type 0 (none).
+    let mut raw = Vec::with_capacity(height * (1 + width * 3));
+    if activity.universes.is_empty() {
+        raw.resize(height * (1 + width * 3), 0);
+    }
+    for universe in &activity.universes {
+        let mut line = Vec::with_capacity(1 + width * 3);
+        line.push(0);
+        for value in universe.values(metric) {
+            let scale = if max > 0.0 { value / max } else { 0.0 };
+            line.extend_from_slice(&heat_color(scale));
+        }
+        for _ in 0..BAND_HEIGHT {
+            raw.extend_from_slice(&line);
+        }
+    }
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&(width as u32).to_be_bytes());
+    header.extend_from_slice(&(height as u32).to_be_bytes());
+    // 8-bit depth, truecolor, deflate, adaptive filtering, no interlace.
+    header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+    let mut png = PNG_SIGNATURE.to_vec();
+    push_chunk(&mut png, b"IHDR", &header);
+    push_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+    push_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+/// Black at 0, through red and yellow, to white at 1.
+fn heat_color(scale: f64) -> [u8; 3] {
+    let channel = |offset: f64| ((scale * 3.0 - offset).clamp(0.0, 1.0) * 255.0).round() as u8;
+    [channel(0.0), channel(1.0), channel(2.0)]
+}
+
+fn push_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(STORED_BLOCK_LEN).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        out.push(u8::from(blocks.peek().is_none()));
+        let len = block.len() as u16;
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in data {
+        a = (a + u32::from(*byte)) % 65_521;
+        b = (b + a) % 65_521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{adler32, crc32, heat_color, render_csv, render_png};
+    use liveshark_core::{ActivityMetric, ChannelActivity, Protocol, UniverseActivity};
+
+    fn activity() -> ChannelActivity {
+        let mut changes = vec![0; 512];
+        changes[0] = 4;
+        changes[1] = 2;
+        ChannelActivity {
+            universes: vec![UniverseActivity {
+                universe: 1,
+                proto: Protocol::ArtNet,
+                changes,
+                nonzero_s: vec![0.0; 512],
+            }],
+        }
+    }
+
+    #[test]
+    fn csv_has_a_column_per_channel() {
+        let csv = render_csv(&activity(), ActivityMetric::Changes);
+        let mut lines = csv.lines();
+        let header = lines.next().expect("header");
+        assert!(header.starts_with("universe,proto,1,2,"));
+        assert!(header.ends_with(",512"));
+        let row = lines.next().expect("row");
+        assert!(row.starts_with("1,artnet,4,2,0,"));
+        assert_eq!(row.split(',').count(), 514);
+    }
+
+    #[test]
+    fn png_has_a_band_per_universe() {
+        let png = render_png(&activity(), ActivityMetric::Changes);
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..20], &512u32.to_be_bytes());
+        assert_eq!(&png[20..24], &8u32.to_be_bytes());
+        assert!(png.ends_with(b"IEND\xae\x42\x60\x82"));
+    }
+
+    #[test]
+    fn checksums_match_reference_values() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+        assert_eq!(heat_color(0.0), [0, 0, 0]);
+        assert_eq!(heat_color(1.0), [255, 255, 255]);
+    }
+}
@@ -1,13 +1,16 @@
 //! LiveShark command-line interface.
 //!
-//! This binary runs offline analysis for PCAP/PCAPNG captures and emits a
-//! deterministic JSON report. It is a thin wrapper over `liveshark-core` and
-//! follows the contract in `spec/en/LiveShark_Spec.tex`.
+//! This binary runs offline analysis for PCAP/PCAPNG captures, or live
+//! analysis on a network interface, and emits a deterministic JSON report.
+//! It is a thin wrapper over `liveshark-core` and follows the contract in
+//! `spec/en/LiveShark_Spec.tex`.
 //!
 //! Typical usage:
 //! - `liveshark analyze capture.pcapng --report report.json`
 //! - `liveshark pcap analyse capture.pcapng --report report.json`
 //! - `liveshark pcap follow capture.pcapng --report report.json`
+//! - `liveshark pcap heatmap capture.pcapng -o activity.png`
+//! - `liveshark live eth0 --report report.json` (Linux)
 //!
 //! Errors are reported to stderr; a non-zero exit code indicates failure to
 //! read the input capture or to write the report.
@@ -22,10 +25,22 @@ use std::time::{Duration, Instant, SystemTime};
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 use glob::glob;
-use liveshark_core::PacketSource;
+use liveshark_core::{
+    ActivityMetric, Analyzer, AnalyzerConfig, CaptureLatency, CompareOptions,
+    DEFAULT_FLOAT_DECIMALS, DmxComparison, FixtureMap, JsonOptions, LatencyOptions, PacketSource,
+    Profile, RigPatch, UniversePresence, channel_activity_pcap_file, compare_pcap_files,
+    latency_pcap_files, to_json_string,
+};
 use serde::Serialize;
 use time::{OffsetDateTime, format_description::well_known::Rfc3339};
 
+mod dump;
+mod heatmap;
+#[cfg(target_os = "linux")]
+mod window;
+
+use dump::PacketFilter;
+
 #[derive(Parser, Debug)]
 #[command(name = "liveshark")]
 #[command(
@@ -48,6 +63,8 @@ struct Cli {
     command: Commands,
 }
 
+// Parsed once per run, so the size of the `Pcap` variant does not matter.
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand, Debug)]
 enum Commands {
     /// Operations on PCAP/PCAPNG inputs (offline-first).
@@ -55,6 +72,105 @@ enum Commands {
         #[command(subcommand)]
         command: PcapCommands,
     },
+    /// Capture from a network interface and rewrite a report of the latest traffic as it arrives (Linux).
+    #[command(
+        after_help = "Examples:\n  liveshark live eth0 --report report.json\n  liveshark live eth0 --stdout --interval-ms 5000 --window-s 300"
+    )]
+    Live {
+        /// Network interface to capture on, e.g. eth0 (needs root or CAP_NET_RAW)
+        interface: String,
+
+        /// Output report path (JSON)
+        #[arg(short = 'o', long, required_unless_present = "stdout")]
+        report: Option<PathBuf>,
+
+        /// Write JSON report to stdout
+        #[arg(long, conflicts_with = "report")]
+        stdout: bool,
+
+        /// Pretty-print JSON output
+        #[arg(long, conflicts_with = "compact")]
+        pretty: bool,
+
+        /// Compact JSON output (default)
+        #[arg(long)]
+        compact: bool,
+
+        /// Write floats at full precision instead of rounding them to 6 decimals
+        #[arg(long)]
+        full_precision: bool,
+
+        /// Suppress non-error output
+        #[arg(long)]
+        quiet: bool,
+
+        /// Exit with a non-zero code if compliance violations are present
+        #[arg(long)]
+        strict: bool,
+
+        /// List compliance violations after analysis
+        #[arg(long)]
+        list_violations: bool,
+
+        /// Analyzer configuration file (JSON: windows, filters, rule overrides, custom rules)
+        #[arg(long)]
+        config: Option<PathBuf>,
+
+        /// Compliance profile applied before the config file (strict, touring, install)
+        #[arg(long)]
+        profile: Option<Profile>,
+
+        /// Fixture map (CSV personalities and patch) used to label channels
+        #[arg(long)]
+        fixtures: Option<PathBuf>,
+
+        /// Rig patch (CSV universe/channel ranges) used to label findings
+        #[arg(long)]
+        patch: Option<PathBuf>,
+
+        /// Operator note embedded in the report, e.g. "desk swapped at 21:30" (repeatable)
+        #[arg(long, value_name = "TEXT")]
+        note: Vec<String>,
+
+        /// File of operator notes, one per non-empty line, embedded before any --note
+        #[arg(long, value_name = "FILE")]
+        notes_file: Option<PathBuf>,
+
+        /// Report interval in milliseconds
+        #[arg(long, default_value_t = 1000)]
+        interval_ms: u64,
+
+        /// Seconds of traffic each report covers (at most 100000 packets)
+        #[arg(long, default_value_t = 60.0)]
+        window_s: f64,
+
+        /// Stop after N iterations (tests only).
+        #[arg(long, hide = true)]
+        max_iterations: Option<u64>,
+    },
+    /// Compliance rules checked during analysis.
+    Rules {
+        #[command(subcommand)]
+        command: RulesCommands,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum RulesCommands {
+    /// List every compliance rule with its code, severity, and reference.
+    List {
+        /// Output JSON to stdout
+        #[arg(long)]
+        json: bool,
+
+        /// Pretty-print JSON output
+        #[arg(long, conflicts_with = "compact")]
+        pretty: bool,
+
+        /// Compact JSON output (default)
+        #[arg(long)]
+        compact: bool,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -84,6 +200,10 @@ enum PcapCommands {
         #[arg(long)]
         compact: bool,
 
+        /// Write floats at full precision instead of rounding them to 6 decimals
+        #[arg(long)]
+        full_precision: bool,
+
         /// Suppress non-error output
         #[arg(long)]
         quiet: bool,
@@ -95,6 +215,42 @@ enum PcapCommands {
         /// List compliance violations after analysis
         #[arg(long)]
         list_violations: bool,
+
+        /// Analyzer configuration file (JSON: windows, filters, rule overrides, custom rules)
+        #[arg(long)]
+        config: Option<PathBuf>,
+
+        /// Compliance profile applied before the config file (strict, touring, install)
+        #[arg(long)]
+        profile: Option<Profile>,
+
+        /// Fixture map (CSV personalities and patch) used to label channels
+        #[arg(long)]
+        fixtures: Option<PathBuf>,
+
+        /// Rig patch (CSV universe/channel ranges) used to label findings
+        #[arg(long)]
+        patch: Option<PathBuf>,
+
+        /// Another capture of the same network to merge in, after aligning its clock (repeatable)
+        #[arg(long, value_name = "FILE")]
+        merge: Vec<PathBuf>,
+
+        /// Operator note embedded in the report, e.g. "desk swapped at 21:30" (repeatable)
+        #[arg(long, value_name = "TEXT")]
+        note: Vec<String>,
+
+        /// File of operator notes, one per non-empty line, embedded before any --note
+        #[arg(long, value_name = "FILE")]
+        notes_file: Option<PathBuf>,
+
+        /// Write one decoded line per packet of the input to FILE (stdout when omitted)
+        #[arg(long, value_name = "FILE", num_args = 0..=1, default_missing_value = "-")]
+        dump_packets: Option<PathBuf>,
+
+        /// Dump only packets matching FILTER: artnet, sacn, udp, non-udp, malformed, universe=N (repeatable, all must match)
+        #[arg(long, value_name = "FILTER", requires = "dump_packets")]
+        dump_filter: Vec<PacketFilter>,
     },
     /// Follow a capture file that is still growing and rewrite full reports.
     Follow {
@@ -117,6 +273,10 @@ enum PcapCommands {
         #[arg(long)]
         compact: bool,
 
+        /// Write floats at full precision instead of rounding them to 6 decimals
+        #[arg(long)]
+        full_precision: bool,
+
         /// Suppress non-error output
         #[arg(long)]
         quiet: bool,
@@ -129,6 +289,30 @@ enum PcapCommands {
         #[arg(long)]
         list_violations: bool,
 
+        /// Analyzer configuration file (JSON: windows, filters, rule overrides, custom rules)
+        #[arg(long)]
+        config: Option<PathBuf>,
+
+        /// Compliance profile applied before the config file (strict, touring, install)
+        #[arg(long)]
+        profile: Option<Profile>,
+
+        /// Fixture map (CSV personalities and patch) used to label channels
+        #[arg(long)]
+        fixtures: Option<PathBuf>,
+
+        /// Rig patch (CSV universe/channel ranges) used to label findings
+        #[arg(long)]
+        patch: Option<PathBuf>,
+
+        /// Operator note embedded in the report, e.g. "desk swapped at 21:30" (repeatable)
+        #[arg(long, value_name = "TEXT")]
+        note: Vec<String>,
+
+        /// File of operator notes, one per non-empty line, embedded before any --note
+        #[arg(long, value_name = "FILE")]
+        notes_file: Option<PathBuf>,
+
         /// Loop interval in milliseconds
         #[arg(long, default_value_t = 1000)]
         interval_ms: u64,
@@ -150,6 +334,88 @@ enum PcapCommands {
         #[arg(long, conflicts_with = "compact")]
         pretty: bool,
 
+        /// Compact JSON output (default)
+        #[arg(long)]
+        compact: bool,
+    },
+    /// Compare the DMX output of a capture against a reference capture.
+    #[command(
+        after_help = "Examples:\n  liveshark pcap compare reference.pcapng show.pcapng\n  liveshark pcap compare reference.pcapng show.pcapng --tolerance 2 --strict"
+    )]
+    Compare {
+        /// Reference capture (the intended output)
+        reference: PathBuf,
+
+        /// Capture to check against the reference
+        input: PathBuf,
+
+        /// Sampling interval in milliseconds
+        #[arg(long, default_value_t = 100)]
+        interval_ms: u64,
+
+        /// Largest slot difference still counted as a match
+        #[arg(long, default_value_t = 0)]
+        tolerance: u8,
+
+        /// Exit with a non-zero code when the output diverges
+        #[arg(long)]
+        strict: bool,
+
+        /// Output JSON to stdout
+        #[arg(long)]
+        json: bool,
+
+        /// Pretty-print JSON output
+        #[arg(long, conflicts_with = "compact")]
+        pretty: bool,
+
+        /// Compact JSON output (default)
+        #[arg(long)]
+        compact: bool,
+    },
+    /// Export per-channel activity of each universe as a CSV matrix or PNG heatmap.
+    #[command(
+        after_help = "Examples:\n  liveshark pcap heatmap capture.pcapng -o activity.csv\n  liveshark pcap heatmap capture.pcapng -o activity.png --metric nonzero"
+    )]
+    Heatmap {
+        /// Path to a .pcap or .pcapng file
+        input: PathBuf,
+
+        /// Output path; a .png extension renders an image, anything else a CSV matrix
+        #[arg(short = 'o', long)]
+        output: PathBuf,
+
+        /// Channel score: changes (level changes) or nonzero (seconds at a non-zero level)
+        #[arg(long, default_value = "changes")]
+        metric: ActivityMetric,
+
+        /// Suppress non-error output
+        #[arg(long)]
+        quiet: bool,
+    },
+    /// Measure one-way latency and loss between two captures of the same traffic.
+    #[command(
+        after_help = "Examples:\n  liveshark pcap latency console-port.pcapng node-port.pcapng\n  liveshark pcap latency console-port.pcapng node-port.pcapng --window-ms 200 --pretty"
+    )]
+    Latency {
+        /// Capture taken closer to the sender
+        upstream: PathBuf,
+
+        /// Capture taken further along the path
+        downstream: PathBuf,
+
+        /// Largest time difference between two matching packets, in milliseconds
+        #[arg(long, default_value_t = 1000)]
+        window_ms: u64,
+
+        /// Output JSON to stdout
+        #[arg(long)]
+        json: bool,
+
+        /// Pretty-print JSON output
+        #[arg(long, conflicts_with = "compact")]
+        pretty: bool,
+
         /// Compact JSON output (default)
         #[arg(long)]
         compact: bool,
@@ -167,18 +433,38 @@ fn main() -> ExitCode {
                 stdout,
                 pretty,
                 compact,
+                full_precision,
                 quiet,
                 strict,
                 list_violations,
+                config,
+                profile,
+                fixtures,
+                patch,
+                merge,
+                note,
+                notes_file,
+                dump_packets,
+                dump_filter,
             } => cmd_pcap_analyse(
                 input,
                 report,
                 stdout,
                 pretty,
                 compact,
+                full_precision,
                 quiet,
                 strict,
                 list_violations,
+                config,
+                profile,
+                fixtures,
+                patch,
+                merge,
+                note,
+                notes_file,
+                dump_packets,
+                dump_filter,
             ),
             PcapCommands::Info {
                 input,
@@ -186,15 +472,55 @@ fn main() -> ExitCode {
                 pretty,
                 compact,
             } => cmd_pcap_info(input, json, pretty, compact),
+            PcapCommands::Compare {
+                reference,
+                input,
+                interval_ms,
+                tolerance,
+                strict,
+                json,
+                pretty,
+                compact,
+            } => cmd_pcap_compare(
+                reference,
+                input,
+                interval_ms,
+                tolerance,
+                strict,
+                json,
+                pretty,
+                compact,
+            ),
+            PcapCommands::Heatmap {
+                input,
+                output,
+                metric,
+                quiet,
+            } => cmd_pcap_heatmap(input, output, metric, quiet),
+            PcapCommands::Latency {
+                upstream,
+                downstream,
+                window_ms,
+                json,
+                pretty,
+                compact,
+            } => cmd_pcap_latency(upstream, downstream, window_ms, json, pretty, compact),
             PcapCommands::Follow {
                 input,
                 report,
                 stdout,
                 pretty,
                 compact,
+                full_precision,
                 quiet,
                 strict,
                 list_violations,
+                config,
+                profile,
+                fixtures,
+                patch,
+                note,
+                notes_file,
                 interval_ms,
                 max_iterations,
             } => cmd_pcap_follow(
@@ -203,13 +529,66 @@ fn main() -> ExitCode {
                 stdout,
                 pretty,
                 compact,
+                full_precision,
                 quiet,
                 strict,
                 list_violations,
+                config,
+                profile,
+                fixtures,
+                patch,
+                note,
+                notes_file,
                 interval_ms,
                 max_iterations,
             ),
         },
+        Commands::Live {
+            interface,
+            report,
+            stdout,
+            pretty,
+            compact,
+            full_precision,
+            quiet,
+            strict,
+            list_violations,
+            config,
+            profile,
+            fixtures,
+            patch,
+            note,
+            notes_file,
+            interval_ms,
+            window_s,
+            max_iterations,
+        } => cmd_live(
+            interface,
+            report,
+            stdout,
+            pretty,
+            compact,
+            full_precision,
+            quiet,
+            strict,
+            list_violations,
+            config,
+            profile,
+            fixtures,
+            patch,
+            note,
+            notes_file,
+            interval_ms,
+            window_s,
+            max_iterations,
+        ),
+        Commands::Rules { command } => match command {
+            RulesCommands::List {
+                json,
+                pretty,
+                compact,
+            } => cmd_rules_list(json, pretty, compact),
+        },
     };
 
     match result {
@@ -260,10 +639,27 @@ fn cmd_pcap_analyse(
     stdout: bool,
     pretty: bool,
     compact: bool,
+    full_precision: bool,
     quiet: bool,
     strict: bool,
     list_violations: bool,
+    config: Option<PathBuf>,
+    profile: Option<Profile>,
+    fixtures: Option<PathBuf>,
+    patch: Option<PathBuf>,
+    merge: Vec<PathBuf>,
+    note: Vec<String>,
+    notes_file: Option<PathBuf>,
+    dump_packets: Option<PathBuf>,
+    dump_filter: Vec<PacketFilter>,
 ) -> Result<(), CliError> {
+    let analyzer = load_analyzer(
+        config.as_deref(),
+        profile,
+        fixtures.as_deref(),
+        patch.as_deref(),
+    )?;
+    let notes = load_notes(notes_file.as_deref(), note)?;
     let resolved_input = resolve_input_path(&input)?;
     validate_input_file(&resolved_input)?;
     let input_abs = fs::canonicalize(&resolved_input)
@@ -319,9 +715,21 @@ fn cmd_pcap_analyse(
         ));
     }
 
-    let rep = liveshark_core::analyze_pcap_file(&resolved_input)
+    if let Some(target) = dump_packets.as_deref() {
+        write_packet_dump(&resolved_input, target, &dump_filter, stdout)?;
+    }
+
+    let mut inputs = vec![resolved_input];
+    for path in &merge {
+        let resolved = resolve_input_path(path)?;
+        validate_input_file(&resolved)?;
+        inputs.push(resolved);
+    }
+    let mut rep = analyzer
+        .analyze_files(&inputs)
         .context("PCAP/PCAPNG analysis failed")?;
-    let json = serialize_json(&rep, pretty, compact)?;
+    rep.notes = notes;
+    let json = serialize_report(&rep, pretty, compact, full_precision)?;
 
     if stdout {
         print!("{}", json);
@@ -361,6 +769,7 @@ fn cmd_pcap_analyse(
     }
     if !quiet {
         eprintln!("OK: report written -> {}", report.display());
+        print_health(&rep);
     }
     if strict && has_violations(&rep) {
         return Err(CliError::new(
@@ -371,6 +780,24 @@ fn cmd_pcap_analyse(
     Ok(())
 }
 
+/// Operator notes for the report: the lines of `notes_file` (trimmed,
+/// blank lines skipped), then the `--note` values.
+fn load_notes(notes_file: Option<&Path>, note: Vec<String>) -> Result<Vec<String>, CliError> {
+    let mut notes = Vec::new();
+    if let Some(path) = notes_file {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read notes file: {}", path.display()))?;
+        notes.extend(
+            text.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string),
+        );
+    }
+    notes.extend(note);
+    Ok(notes)
+}
+
 #[allow(clippy::too_many_arguments)]
 fn cmd_pcap_follow(
     input: PathBuf,
@@ -378,12 +805,26 @@ fn cmd_pcap_follow(
     stdout: bool,
     pretty: bool,
     compact: bool,
+    full_precision: bool,
     quiet: bool,
     strict: bool,
     list_violations: bool,
+    config: Option<PathBuf>,
+    profile: Option<Profile>,
+    fixtures: Option<PathBuf>,
+    patch: Option<PathBuf>,
+    note: Vec<String>,
+    notes_file: Option<PathBuf>,
     interval_ms: u64,
     max_iterations: Option<u64>,
 ) -> Result<(), CliError> {
+    let analyzer = load_analyzer(
+        config.as_deref(),
+        profile,
+        fixtures.as_deref(),
+        patch.as_deref(),
+    )?;
+    let notes = load_notes(notes_file.as_deref(), note)?;
     let resolved_input = resolve_input_path(&input)?;
     validate_input_file(&resolved_input)?;
     let input_abs = fs::canonicalize(&resolved_input)
@@ -497,40 +938,22 @@ fn cmd_pcap_follow(
             eprintln!("follow: analyzing {}", resolved_input.display());
         }
 
-        match liveshark_core::analyze_pcap_file(&resolved_input) {
-            Ok(rep) => {
+        match analyzer.analyze_file(&resolved_input) {
+            Ok(mut rep) => {
+                rep.notes = notes.clone();
                 force_retry = false;
                 last_seen = Some(current);
-                let json = serialize_json(&rep, pretty, compact)?;
-                if stdout {
-                    println!("{}", json);
-                } else if let Some(report_path) = report.as_ref() {
-                    write_report_atomic(report_path, &json)?;
-                }
-
-                if list_violations && !quiet {
-                    let summary = violations_summary(&rep);
-                    if summary.is_empty() {
-                        last_violations = Some(summary);
-                    } else if last_violations.as_ref() != Some(&summary) {
-                        print_violations_summary(&summary);
-                        last_violations = Some(summary);
-                    }
-                }
-
-                if !quiet {
-                    if let Some(report_path) = report.as_ref() {
-                        eprintln!("OK: report written -> {}", report_path.display());
-                    } else {
-                        eprintln!("OK: report emitted");
-                    }
-                }
-                if strict && has_violations(&rep) {
-                    return Err(CliError::new(
-                        "compliance violations detected",
-                        Some("use --list-violations to inspect".to_string()),
-                    ));
-                }
+                emit_periodic_report(
+                    &rep,
+                    report.as_deref(),
+                    pretty,
+                    compact,
+                    full_precision,
+                    quiet,
+                    strict,
+                    list_violations,
+                    &mut last_violations,
+                )?;
             }
             Err(err) => {
                 if is_transient_error(&err) {
@@ -555,6 +978,234 @@ fn cmd_pcap_follow(
     Ok(())
 }
 
+/// Capture on `interface` for one interval at a time and, after each, emit
+/// the report of the packets captured in the last `window_s` seconds.
+#[cfg(target_os = "linux")]
+#[allow(clippy::too_many_arguments)]
+fn cmd_live(
+    interface: String,
+    report: Option<PathBuf>,
+    stdout: bool,
+    pretty: bool,
+    compact: bool,
+    full_precision: bool,
+    quiet: bool,
+    strict: bool,
+    list_violations: bool,
+    config: Option<PathBuf>,
+    profile: Option<Profile>,
+    fixtures: Option<PathBuf>,
+    patch: Option<PathBuf>,
+    note: Vec<String>,
+    notes_file: Option<PathBuf>,
+    interval_ms: u64,
+    window_s: f64,
+    max_iterations: Option<u64>,
+) -> Result<(), CliError> {
+    if !(window_s.is_finite() && window_s > 0.0) {
+        return Err(CliError::new(
+            "invalid --window-s",
+            Some("pass a positive number of seconds".to_string()),
+        ));
+    }
+    let analyzer = load_analyzer(
+        config.as_deref(),
+        profile,
+        fixtures.as_deref(),
+        patch.as_deref(),
+    )?;
+    let notes = load_notes(notes_file.as_deref(), note)?;
+    let report = if stdout {
+        None
+    } else {
+        Some(report.ok_or_else(|| {
+            CliError::new(
+                "missing report output",
+                Some("pass --report <FILE> or use --stdout".to_string()),
+            )
+        })?)
+    };
+
+    let mut source = liveshark_core::LiveCaptureSource::open(&interface).map_err(|err| {
+        CliError::new(
+            format!("cannot capture on {interface}: {err}"),
+            Some(
+                "live capture needs root or CAP_NET_RAW; list interfaces with `ip link`"
+                    .to_string(),
+            ),
+        )
+    })?;
+    let input = format!("live:{interface}");
+    let mut packets = window::PacketWindow::new(window_s, window::MAX_WINDOW_PACKETS);
+    let mut last_violations: Option<Vec<ViolationSummary>> = None;
+    let mut iterations = 0u64;
+    let interval = Duration::from_millis(interval_ms);
+    if !quiet {
+        eprintln!("live: capturing on {interface}");
+    }
+
+    loop {
+        if let Some(max) = max_iterations {
+            if iterations >= max {
+                break;
+            }
+        }
+        iterations += 1;
+
+        source.stop_at(Some(Instant::now() + interval));
+        while let Some(packet) = source
+            .next_packet()
+            .map_err(|err| CliError::new(format!("live capture failed: {err}"), None))?
+        {
+            packets.push(packet, |data| source.recycle(data));
+        }
+
+        let mut rep = analyzer
+            .analyze_packets(&input, packets.packets())
+            .context("live analysis failed")?;
+        rep.notes = notes.clone();
+        emit_periodic_report(
+            &rep,
+            report.as_deref(),
+            pretty,
+            compact,
+            full_precision,
+            quiet,
+            strict,
+            list_violations,
+            &mut last_violations,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+#[allow(clippy::too_many_arguments, unused_variables)]
+fn cmd_live(
+    interface: String,
+    report: Option<PathBuf>,
+    stdout: bool,
+    pretty: bool,
+    compact: bool,
+    full_precision: bool,
+    quiet: bool,
+    strict: bool,
+    list_violations: bool,
+    config: Option<PathBuf>,
+    profile: Option<Profile>,
+    fixtures: Option<PathBuf>,
+    patch: Option<PathBuf>,
+    note: Vec<String>,
+    notes_file: Option<PathBuf>,
+    interval_ms: u64,
+    window_s: f64,
+    max_iterations: Option<u64>,
+) -> Result<(), CliError> {
+    Err(CliError::new(
+        "live capture is only supported on Linux",
+        Some("record with tcpdump or Wireshark and run `liveshark pcap follow`".to_string()),
+    ))
+}
+
+/// Emit one report of a periodic run (`follow`, `live`): to stdout, one JSON
+/// document per line, or atomically over `report`. Violations are listed only
+/// when they differ from `last_violations`.
+#[allow(clippy::too_many_arguments)]
+fn emit_periodic_report(
+    rep: &liveshark_core::Report,
+    report: Option<&Path>,
+    pretty: bool,
+    compact: bool,
+    full_precision: bool,
+    quiet: bool,
+    strict: bool,
+    list_violations: bool,
+    last_violations: &mut Option<Vec<ViolationSummary>>,
+) -> Result<(), CliError> {
+    let json = serialize_report(rep, pretty, compact, full_precision)?;
+    match report {
+        Some(report_path) => write_report_atomic(report_path, &json)?,
+        None => println!("{}", json),
+    }
+
+    if list_violations && !quiet {
+        let summary = violations_summary(rep);
+        if summary.is_empty() {
+            *last_violations = Some(summary);
+        } else if last_violations.as_ref() != Some(&summary) {
+            print_violations_summary(&summary);
+            *last_violations = Some(summary);
+        }
+    }
+
+    if !quiet {
+        if let Some(report_path) = report {
+            eprintln!("OK: report written -> {}", report_path.display());
+        } else {
+            eprintln!("OK: report emitted");
+        }
+        print_health(rep);
+    }
+    if strict && has_violations(rep) {
+        return Err(CliError::new(
+            "compliance violations detected",
+            Some("use --list-violations to inspect".to_string()),
+        ));
+    }
+    Ok(())
+}
+
+/// Dump the decoded packets of `input` to `target` (`-` for stdout).
+fn write_packet_dump(
+    input: &Path,
+    target: &Path,
+    filters: &[PacketFilter],
+    report_to_stdout: bool,
+) -> Result<(), CliError> {
+    let source = liveshark_core::PcapFileSource::open(input)
+        .map_err(|err| CliError::new(err.to_string(), None))?;
+    if target == Path::new("-") {
+        if report_to_stdout {
+            return Err(CliError::new(
+                "cannot write the packet dump and the report to stdout",
+                Some("pass a file to --dump-packets".to_string()),
+            ));
+        }
+        let mut out = io::BufWriter::new(io::stdout().lock());
+        dump::write_packets(source, filters, &mut out).context("packet dump failed")?;
+    } else {
+        let file = fs::File::create(target)
+            .with_context(|| format!("Failed to write packet dump: {}", target.display()))?;
+        let mut out = io::BufWriter::new(file);
+        dump::write_packets(source, filters, &mut out).context("packet dump failed")?;
+    }
+    Ok(())
+}
+
+/// Serialize a report with its floats rounded to
+/// [`DEFAULT_FLOAT_DECIMALS`] unless `full_precision` is set.
+fn serialize_report(
+    rep: &liveshark_core::Report,
+    pretty: bool,
+    compact: bool,
+    full_precision: bool,
+) -> Result<String, CliError> {
+    if pretty && compact {
+        return Err(CliError::new(
+            "cannot use --pretty and --compact together",
+            Some("choose one output format".to_string()),
+        ));
+    }
+    let options = JsonOptions {
+        pretty,
+        float_decimals: (!full_precision).then_some(DEFAULT_FLOAT_DECIMALS),
+    };
+    to_json_string(rep, options)
+        .context("JSON serialization failed")
+        .map_err(Into::into)
+}
+
 fn serialize_json<T: Serialize>(
     value: &T,
     pretty: bool,
@@ -585,9 +1236,10 @@ fn has_violations(rep: &liveshark_core::Report) -> bool {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct ViolationSummary {
-    protocol: String,
+    protocol: liveshark_core::Protocol,
     id: String,
     count: u64,
+    spec_reference: Option<String>,
 }
 
 fn violations_summary(rep: &liveshark_core::Report) -> Vec<ViolationSummary> {
@@ -595,9 +1247,10 @@ fn violations_summary(rep: &liveshark_core::Report) -> Vec<ViolationSummary> {
     for entry in &rep.compliance {
         for violation in &entry.violations {
             summary.push(ViolationSummary {
-                protocol: entry.protocol.clone(),
+                protocol: entry.protocol,
                 id: violation.id.clone(),
                 count: violation.count,
+                spec_reference: violation.spec_reference.clone(),
             });
         }
     }
@@ -611,7 +1264,31 @@ fn print_violations_summary(summary: &[ViolationSummary]) {
     }
     eprintln!("Compliance violations:");
     for item in summary {
-        eprintln!("  {} {} ({})", item.protocol, item.id, item.count);
+        match &item.spec_reference {
+            Some(reference) => eprintln!(
+                "  {} {} ({}) see: {}",
+                item.protocol, item.id, item.count, reference
+            ),
+            None => eprintln!("  {} {} ({})", item.protocol, item.id, item.count),
+        }
+    }
+}
+
+/// One-line health summary: the capture score and its weakest universe.
+fn print_health(rep: &liveshark_core::Report) {
+    let Some(health) = &rep.health else {
+        return;
+    };
+    let weakest = health
+        .universes
+        .iter()
+        .min_by(|a, b| a.score.total_cmp(&b.score));
+    match weakest {
+        Some(universe) => eprintln!(
+            "Health: {:.1}/100 (lowest: {} universe {} at {:.1})",
+            health.score, universe.proto, universe.universe, universe.score
+        ),
+        None => eprintln!("Health: {:.1}/100", health.score),
     }
 }
 
@@ -742,6 +1419,57 @@ fn sleep_interval(interval: Duration) {
     thread::sleep(interval);
 }
 
+fn load_analyzer(
+    config: Option<&Path>,
+    profile: Option<Profile>,
+    fixtures: Option<&Path>,
+    patch: Option<&Path>,
+) -> Result<Analyzer, CliError> {
+    let config = match (config, profile) {
+        (None, profile) => profile.map(AnalyzerConfig::for_profile).unwrap_or_default(),
+        (Some(path), profile) => match profile {
+            Some(profile) => AnalyzerConfig::from_file_with_profile(path, profile),
+            None => AnalyzerConfig::from_file(path),
+        }
+        .map_err(|err| {
+            CliError::new(
+                format!("invalid config file {}: {}", path.display(), err),
+                Some("see `liveshark rules list` for rule codes".to_string()),
+            )
+        })?,
+    };
+    // The CLI analyzes whole files, where reusing packet buffers pays off.
+    let mut builder = Analyzer::builder().config(config).buffer_pool(true);
+    if let Some(path) = fixtures {
+        let map = FixtureMap::from_file(path).map_err(|err| {
+            CliError::new(
+                format!("invalid fixture map {}: {}", path.display(), err),
+                Some(
+                    "expected `personality,<name>,<offset>,<parameter>` and \
+                     `patch,<fixture>,<personality>,<universe>,<address>` lines"
+                        .to_string(),
+                ),
+            )
+        })?;
+        builder = builder.fixtures(map);
+    }
+    if let Some(path) = patch {
+        let patch = RigPatch::from_file(path).map_err(|err| {
+            CliError::new(
+                format!("invalid patch {}: {}", path.display(), err),
+                Some(
+                    "expected `<universe>,<start>,<end>,<label>[,<position>[,<fixture type>]]` lines"
+                        .to_string(),
+                ),
+            )
+        })?;
+        builder = builder.patch(patch);
+    }
+    builder
+        .build()
+        .map_err(|err| CliError::new(err.to_string(), None))
+}
+
 fn validate_input_file(input: &Path) -> Result<(), CliError> {
     if !input.exists() {
         return Err(CliError::new(
@@ -794,6 +1522,241 @@ fn cmd_pcap_info(input: PathBuf, json: bool, pretty: bool, compact: bool) -> Res
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
+fn cmd_pcap_compare(
+    reference: PathBuf,
+    input: PathBuf,
+    interval_ms: u64,
+    tolerance: u8,
+    strict: bool,
+    json: bool,
+    pretty: bool,
+    compact: bool,
+) -> Result<(), CliError> {
+    let reference = resolve_input_path(&reference)?;
+    validate_input_file(&reference)?;
+    let input = resolve_input_path(&input)?;
+    validate_input_file(&input)?;
+    let options = CompareOptions {
+        interval_s: interval_ms as f64 / 1000.0,
+        tolerance,
+    };
+    let comparison = compare_pcap_files(&reference, &input, &options)
+        .map_err(|err| CliError::new(err.to_string(), None))?;
+
+    if json || pretty || compact {
+        let json = serialize_json(&comparison, pretty, compact)?;
+        print!("{}", json);
+    } else {
+        print_comparison(&comparison);
+    }
+    if strict && !comparison.matches() {
+        return Err(CliError::new(
+            "DMX output diverges from the reference",
+            Some("run without --strict to list the divergences".to_string()),
+        ));
+    }
+    Ok(())
+}
+
+fn cmd_pcap_heatmap(
+    input: PathBuf,
+    output: PathBuf,
+    metric: ActivityMetric,
+    quiet: bool,
+) -> Result<(), CliError> {
+    let input = resolve_input_path(&input)?;
+    validate_input_file(&input)?;
+    let activity =
+        channel_activity_pcap_file(&input).map_err(|err| CliError::new(err.to_string(), None))?;
+    let is_png = output
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("png"));
+    let bytes = if is_png {
+        heatmap::render_png(&activity, metric)
+    } else {
+        heatmap::render_csv(&activity, metric).into_bytes()
+    };
+    if let Some(parent) = output.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create output directory: {}", parent.display())
+            })?;
+        }
+    }
+    fs::write(&output, bytes)
+        .with_context(|| format!("Failed to write heatmap: {}", output.display()))?;
+    if !quiet {
+        eprintln!(
+            "OK: {} universe(s) written -> {}",
+            activity.universes.len(),
+            output.display()
+        );
+    }
+    Ok(())
+}
+
+fn cmd_pcap_latency(
+    upstream: PathBuf,
+    downstream: PathBuf,
+    window_ms: u64,
+    json: bool,
+    pretty: bool,
+    compact: bool,
+) -> Result<(), CliError> {
+    let upstream = resolve_input_path(&upstream)?;
+    validate_input_file(&upstream)?;
+    let downstream = resolve_input_path(&downstream)?;
+    validate_input_file(&downstream)?;
+    let options = LatencyOptions {
+        match_window_s: window_ms as f64 / 1000.0,
+    };
+    let latency = latency_pcap_files(&upstream, &downstream, &options)
+        .map_err(|err| CliError::new(err.to_string(), None))?;
+
+    if json || pretty || compact {
+        let json = serialize_json(&latency, pretty, compact)?;
+        print!("{}", json);
+    } else {
+        print_latency(&latency);
+    }
+    Ok(())
+}
+
+fn print_latency(latency: &CaptureLatency) {
+    for universe in &latency.universes {
+        println!(
+            "universe {} ({}): {} of {} upstream packets matched, {} lost between captures, {} downstream only",
+            universe.universe,
+            universe.proto,
+            universe.matched,
+            universe.upstream_packets,
+            universe.lost_between,
+            universe.downstream_only
+        );
+        if let Some(lost) = universe.lost_before_upstream {
+            println!("  lost before upstream capture: {lost}");
+        }
+        if let Some(distribution) = &universe.latency {
+            println!(
+                "  latency ms: min {:.3}, p50 {:.3}, p95 {:.3}, p99 {:.3}, max {:.3}",
+                distribution.min_ms,
+                distribution.p50_ms,
+                distribution.p95_ms,
+                distribution.p99_ms,
+                distribution.max_ms
+            );
+        }
+    }
+}
+
+fn print_comparison(comparison: &DmxComparison) {
+    for universe in &comparison.universes {
+        match universe.presence {
+            UniversePresence::ReferenceOnly => {
+                println!("universe {}: missing (reference only)", universe.universe);
+                continue;
+            }
+            UniversePresence::IncidentOnly => {
+                println!(
+                    "universe {}: unexpected (not in reference)",
+                    universe.universe
+                );
+                continue;
+            }
+            UniversePresence::Both => {}
+        }
+        println!(
+            "universe {}: {} of {} samples diverge",
+            universe.universe, universe.divergent_samples, universe.samples
+        );
+        for divergence in &universe.divergences {
+            println!(
+                "  {:.3}-{:.3} s: channels {} (max delta {})",
+                divergence.start_s,
+                divergence.end_s,
+                format_channel_ranges(&divergence.channels),
+                divergence.max_delta
+            );
+        }
+    }
+    if comparison.matches() {
+        println!("OK: output matches the reference");
+    }
+}
+
+/// Sorted channels as ranges, e.g. `1-4, 9`.
+fn format_channel_ranges(channels: &[u16]) -> String {
+    let mut ranges: Vec<(u16, u16)> = Vec::new();
+    for &channel in channels {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == channel => *end = channel,
+            _ => ranges.push((channel, channel)),
+        }
+    }
+    ranges
+        .iter()
+        .map(|(start, end)| {
+            if start == end {
+                start.to_string()
+            } else {
+                format!("{start}-{end}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[derive(Debug, Serialize)]
+struct RuleInfo {
+    code: &'static str,
+    protocol: liveshark_core::Protocol,
+    severity: liveshark_core::Severity,
+    enabled_by_default: bool,
+    message: &'static str,
+    description: &'static str,
+    spec_reference: &'static str,
+}
+
+fn cmd_rules_list(json: bool, pretty: bool, compact: bool) -> Result<(), CliError> {
+    let rules: Vec<RuleInfo> = liveshark_core::RuleId::ALL
+        .iter()
+        .map(|rule| RuleInfo {
+            code: rule.code(),
+            protocol: rule.protocol(),
+            severity: rule.default_severity(),
+            enabled_by_default: rule.enabled_by_default(),
+            message: rule.message(),
+            description: rule.description(),
+            spec_reference: rule.spec_reference(),
+        })
+        .collect();
+    if json || pretty || compact {
+        let json = serialize_json(&rules, pretty, compact)?;
+        print!("{}", json);
+        return Ok(());
+    }
+
+    let code_width = rules.iter().map(|rule| rule.code.len()).max().unwrap_or(0);
+    for rule in &rules {
+        let optional = if rule.enabled_by_default {
+            ""
+        } else {
+            " (off by default)"
+        };
+        println!(
+            "{:<code_width$}  {:<6}  {:<7}  {}{}",
+            rule.code,
+            rule.protocol.as_str(),
+            rule.severity.as_str(),
+            rule.description,
+            optional
+        );
+        println!("{:<code_width$}  see: {}", "", rule.spec_reference);
+    }
+    Ok(())
+}
+
 #[derive(Debug, Serialize)]
 struct PcapInfo {
     path: String,
@@ -943,7 +1906,7 @@ fn is_glob_pattern(input: &str) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::cmd_pcap_analyse;
+    use super::{cmd_pcap_analyse, format_channel_ranges};
     use std::path::PathBuf;
     use tempfile::TempDir;
 
@@ -959,9 +1922,19 @@ mod tests {
             false,
             false,
             false,
+            false,
             true,
             false,
             false,
+            None,
+            None,
+            None,
+            None,
+            Vec::new(),
+            Vec::new(),
+            None,
+            None,
+            Vec::new(),
         )
         .expect_err("missing report should error");
 
@@ -971,4 +1944,13 @@ mod tests {
             Some("pass --report <FILE> or use --stdout")
         );
     }
+
+    #[test]
+    fn channel_ranges_are_collapsed() {
+        assert_eq!(
+            format_channel_ranges(&[1, 2, 3, 4, 9, 11, 12]),
+            "1-4, 9, 11-12"
+        );
+        assert_eq!(format_channel_ranges(&[]), "");
+    }
 }
@@ -0,0 +1,103 @@
+//! Rolling window of captured packets (`live`).
+//!
+//! A live run reports on the traffic of the last few seconds rather than on
+//! everything since it started: the window keeps the packets captured within
+//! `span_s` of the newest one, and never more than `max_packets` of them, so
+//! memory and the work of each report stay bounded however long the capture
+//! runs. Buffers of packets leaving the window go back to the capture source.
+
+use std::collections::VecDeque;
+
+use liveshark_core::PacketEvent;
+
+/// Packets kept at most, whatever their timestamps.
+pub(crate) const MAX_WINDOW_PACKETS: usize = 100_000;
+
+/// Packets captured within the last `span_s` seconds.
+#[derive(Debug)]
+pub(crate) struct PacketWindow {
+    packets: VecDeque<PacketEvent>,
+    span_s: f64,
+    max_packets: usize,
+}
+
+impl PacketWindow {
+    pub(crate) fn new(span_s: f64, max_packets: usize) -> Self {
+        Self {
+            packets: VecDeque::new(),
+            span_s,
+            max_packets,
+        }
+    }
+
+    /// Add `packet`, handing the buffers of the packets it pushes out of the
+    /// window to `recycle`. Packets without a timestamp leave by count only.
+    pub(crate) fn push(&mut self, packet: PacketEvent, mut recycle: impl FnMut(Vec<u8>)) {
+        let oldest_kept = packet.ts.map(|ts| ts - self.span_s);
+        self.packets.push_back(packet);
+        while let Some(front) = self.packets.front() {
+            let expired =
+                matches!((front.ts, oldest_kept), (Some(ts), Some(oldest)) if ts < oldest);
+            if !expired && self.packets.len() <= self.max_packets {
+                break;
+            }
+            if let Some(evicted) = self.packets.pop_front() {
+                recycle(evicted.data);
+            }
+        }
+    }
+
+    /// Packets in the window, oldest first.
+    pub(crate) fn packets(&self) -> impl Iterator<Item = &PacketEvent> {
+        self.packets.iter()
+    }
+
+    /// Number of packets in the window.
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.packets.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PacketWindow;
+    use liveshark_core::PacketEvent;
+    use pcap_parser::Linktype;
+
+    fn packet(ts: Option<f64>) -> PacketEvent {
+        PacketEvent {
+            ts,
+            linktype: Linktype::ETHERNET,
+            data: vec![0; 64],
+            orig_len: None,
+        }
+    }
+
+    #[test]
+    fn retained_packets_stay_within_the_span() {
+        let mut window = PacketWindow::new(2.0, usize::MAX);
+        let mut recycled = 0;
+        // One packet every 0.25 s: the window holds the newest 2 s of them.
+        for i in 0..1000 {
+            window.push(packet(Some(f64::from(i) * 0.25)), |_| recycled += 1);
+            assert!(window.len() <= 9);
+        }
+        assert_eq!(window.len(), 9);
+        assert_eq!(recycled, 1000 - 9);
+        assert_eq!(window.packets().next().and_then(|p| p.ts), Some(247.75));
+    }
+
+    #[test]
+    fn retained_packets_stay_within_the_count() {
+        let mut window = PacketWindow::new(60.0, 50);
+        let mut recycled = 0;
+        for i in 0..500 {
+            let ts = (i % 2 == 0).then_some(0.0);
+            window.push(packet(ts), |_| recycled += 1);
+            assert!(window.len() <= 50);
+        }
+        assert_eq!(window.len(), 50);
+        assert_eq!(recycled, 450);
+    }
+}
@@ -209,6 +209,144 @@ fn pretty_and_compact_conflict() {
         .stderr(contains("error:"));
 }
 
+#[test]
+fn report_floats_are_rounded_unless_full_precision() {
+    let input = repo_root()
+        .join("tests")
+        .join("golden")
+        .join("flow_peak_and_maxgap")
+        .join("input.pcapng");
+
+    let rounded = cmd()
+        .arg("pcap")
+        .arg("analyze")
+        .arg(&input)
+        .arg("--stdout")
+        .output()
+        .expect("run analyze");
+    assert!(rounded.status.success());
+    let rounded = String::from_utf8(rounded.stdout).expect("utf-8");
+    assert!(rounded.contains("\"iat_jitter_ms\":700.0,"));
+
+    let full = cmd()
+        .arg("pcap")
+        .arg("analyze")
+        .arg(&input)
+        .arg("--stdout")
+        .arg("--full-precision")
+        .output()
+        .expect("run analyze");
+    assert!(full.status.success());
+    let full = String::from_utf8(full.stdout).expect("utf-8");
+    assert!(full.contains("\"iat_jitter_ms\":700.0000000000001,"));
+}
+
+#[test]
+fn notes_are_embedded_in_order() {
+    let temp = TempDir::new().expect("tempdir");
+    let notes_file = temp.path().join("notes.txt");
+    std::fs::write(&notes_file, "  doors at 19:00  \n\nsoundcheck done\n").expect("write notes");
+
+    let output = cmd()
+        .arg("pcap")
+        .arg("analyze")
+        .arg(sample_capture())
+        .arg("--stdout")
+        .arg("--notes-file")
+        .arg(&notes_file)
+        .arg("--note")
+        .arg("desk swapped at 21:30")
+        .output()
+        .expect("run analyze");
+    assert!(output.status.success());
+    let report: Value = serde_json::from_slice(&output.stdout).expect("report json");
+    assert_eq!(
+        report["notes"],
+        serde_json::json!(["doors at 19:00", "soundcheck done", "desk swapped at 21:30"])
+    );
+}
+
+#[test]
+fn dump_packets_streams_filtered_lines() {
+    let temp = TempDir::new().expect("tempdir");
+    let input = repo_root()
+        .join("tests")
+        .join("golden")
+        .join("artnet_invalid_length")
+        .join("input.pcapng");
+    let report = temp.path().join("report.json");
+
+    cmd()
+        .arg("pcap")
+        .arg("analyze")
+        .arg(&input)
+        .arg("-o")
+        .arg(&report)
+        .arg("--quiet")
+        .arg("--dump-packets")
+        .arg("--dump-filter")
+        .arg("malformed")
+        .assert()
+        .success()
+        .stdout(contains(
+            "0.000000 192.168.0.10:6454 -> 192.168.0.20:6454 artnet malformed: invalid DMX length: 513",
+        ));
+    assert!(report.exists());
+
+    let dump = temp.path().join("packets.txt");
+    cmd()
+        .arg("pcap")
+        .arg("analyze")
+        .arg(&input)
+        .arg("--stdout")
+        .arg("--dump-packets")
+        .arg(&dump)
+        .arg("--dump-filter")
+        .arg("sacn")
+        .assert()
+        .success();
+    assert_eq!(std::fs::read_to_string(&dump).expect("read dump"), "");
+
+    cmd()
+        .arg("pcap")
+        .arg("analyze")
+        .arg(&input)
+        .arg("--stdout")
+        .arg("--dump-packets")
+        .assert()
+        .failure()
+        .stderr(contains(
+            "cannot write the packet dump and the report to stdout",
+        ));
+}
+
+#[test]
+fn health_score_is_reported_and_summarised() {
+    let temp = TempDir::new().expect("tempdir");
+    let input = repo_root()
+        .join("tests")
+        .join("golden")
+        .join("artnet_conflict")
+        .join("input.pcapng");
+    let report = temp.path().join("report.json");
+
+    cmd()
+        .arg("pcap")
+        .arg("analyze")
+        .arg(&input)
+        .arg("-o")
+        .arg(&report)
+        .assert()
+        .success()
+        .stderr(contains(
+            "Health: 85.0/100 (lowest: artnet universe 1 at 85.0)",
+        ));
+    let json: Value = serde_json::from_str(&std::fs::read_to_string(&report).expect("read report"))
+        .expect("report json");
+    assert_eq!(json["health"]["score"], 85.0);
+    assert_eq!(json["health"]["penalties"]["conflicts"], 10.0);
+}
+
 #[test]
 fn quiet_suppresses_ok_message() {
     let temp = TempDir::new().expect("tempdir");
@@ -242,7 +380,11 @@ fn list_violations_outputs_ids() {
         .arg("--list-violations")
         .assert()
         .success()
-        .stderr(contains("Compliance violations:").and(contains("LS-SACN-TOO-SHORT")));
+        .stderr(
+            contains("Compliance violations:")
+                .and(contains("LS-SACN-TOO-SHORT"))
+                .and(contains("see: ANSI E1.31-2018, section 4.1")),
+        );
 }
 
 #[test]
@@ -368,6 +510,37 @@ fn follow_writes_report_in_two_iterations() {
     assert!(json.get("flows").is_some() || json.get("universes").is_some());
 }
 
+#[test]
+fn live_rejects_unknown_interfaces() {
+    cmd()
+        .arg("live")
+        .arg("liveshark-none0")
+        .arg("--stdout")
+        .arg("--max-iterations")
+        .arg("1")
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(contains("error:").and(contains("hint:")));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn live_rejects_an_empty_window() {
+    cmd()
+        .arg("live")
+        .arg("lo")
+        .arg("--stdout")
+        .arg("--window-s")
+        .arg("0")
+        .arg("--max-iterations")
+        .arg("1")
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(contains("invalid --window-s"));
+}
+
 #[test]
 fn follow_glob_errors_match_analyze_semantics() {
     let temp = TempDir::new().expect("tempdir");
@@ -612,3 +785,318 @@ fn pcap_info_rejects_invalid_extension() {
         .failure()
         .stderr(contains("error: unsupported input format").and(contains("hint: expected")));
 }
+
+#[test]
+fn rules_list_outputs_every_rule() {
+    cmd()
+        .arg("rules")
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(contains("LS-SACN-START-CODE").and(contains("ANSI E1.31-2018")));
+
+    let assert = cmd()
+        .arg("rules")
+        .arg("list")
+        .arg("--json")
+        .assert()
+        .success();
+    let value: Value = serde_json::from_slice(&assert.get_output().stdout).expect("json stdout");
+    let rules = value.as_array().expect("rules array");
+    assert_eq!(rules.len(), liveshark_core::RuleId::ALL.len());
+    let port = rules
+        .iter()
+        .find(|rule| rule["code"] == "LS-ARTNET-PORT")
+        .expect("artnet port rule");
+    assert_eq!(port["protocol"], "artnet");
+    assert_eq!(port["severity"], "warning");
+    assert_eq!(port["enabled_by_default"], true);
+    let gap = rules
+        .iter()
+        .find(|rule| rule["code"] == "LS-SACN-SEQ-GAP")
+        .expect("sacn sequence gap rule");
+    assert_eq!(gap["enabled_by_default"], false);
+}
+
+#[test]
+fn merged_captures_record_clock_corrections() {
+    let assert = cmd()
+        .arg("pcap")
+        .arg("analyze")
+        .arg(sample_capture())
+        .arg("--merge")
+        .arg(sample_capture())
+        .arg("--stdout")
+        .assert()
+        .success();
+    let report: Value = serde_json::from_slice(&assert.get_output().stdout).expect("json stdout");
+    assert_eq!(report["capture_summary"]["packets_total"], 2);
+    let correction = &report["clock_corrections"][0];
+    assert_eq!(correction["offset_ms"], 0.0);
+    assert_eq!(correction["matched_packets"], 1);
+
+    cmd()
+        .arg("pcap")
+        .arg("analyze")
+        .arg(sample_capture())
+        .arg("--merge")
+        .arg("missing.pcapng")
+        .arg("--stdout")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn profile_is_applied_and_recorded_in_report() {
+    let assert = cmd()
+        .arg("pcap")
+        .arg("analyze")
+        .arg(sample_capture())
+        .arg("--stdout")
+        .arg("--profile")
+        .arg("strict")
+        .assert()
+        .success();
+    let report: Value = serde_json::from_slice(&assert.get_output().stdout).expect("json stdout");
+    assert_eq!(report["profile"], "strict");
+    let rules = report["rules"].as_array().expect("rules array");
+    assert!(rules.iter().any(|rule| rule["id"] == "LS-SACN-SEQ-GAP"));
+
+    let assert = cmd()
+        .arg("pcap")
+        .arg("analyze")
+        .arg(sample_capture())
+        .arg("--stdout")
+        .assert()
+        .success();
+    let report: Value = serde_json::from_slice(&assert.get_output().stdout).expect("json stdout");
+    assert!(report.get("profile").is_none());
+
+    cmd()
+        .arg("pcap")
+        .arg("analyze")
+        .arg(sample_capture())
+        .arg("--stdout")
+        .arg("--profile")
+        .arg("relaxed")
+        .assert()
+        .failure()
+        .stderr(contains("unknown profile"));
+}
+
+#[test]
+fn fixture_map_is_loaded_and_validated() {
+    let temp = TempDir::new().expect("tempdir");
+    let fixtures = temp.path().join("rig.csv");
+    std::fs::write(
+        &fixtures,
+        "personality,Spot,1,Dimmer\npatch,Spot 1,Spot,1,1\n",
+    )
+    .expect("write fixtures");
+    cmd()
+        .arg("pcap")
+        .arg("analyze")
+        .arg(sample_capture())
+        .arg("--stdout")
+        .arg("--fixtures")
+        .arg(&fixtures)
+        .assert()
+        .success();
+
+    std::fs::write(&fixtures, "patch,Spot 1,Spot,1,1\n").expect("write fixtures");
+    cmd()
+        .arg("pcap")
+        .arg("analyze")
+        .arg(sample_capture())
+        .arg("--stdout")
+        .arg("--fixtures")
+        .arg(&fixtures)
+        .assert()
+        .failure()
+        .stderr(contains("invalid fixture map"))
+        .stderr(contains("unknown personality: Spot"));
+}
+
+#[test]
+fn rig_patch_is_embedded_in_the_report() {
+    let temp = TempDir::new().expect("tempdir");
+    let patch = temp.path().join("patch.csv");
+    std::fs::write(&patch, "1,1,512,FOH truss,FOH,Wash\n").expect("write patch");
+    let output = cmd()
+        .arg("pcap")
+        .arg("analyze")
+        .arg(sample_capture())
+        .arg("--stdout")
+        .arg("--patch")
+        .arg(&patch)
+        .output()
+        .expect("run analyze");
+    assert!(output.status.success());
+    let report: Value = serde_json::from_slice(&output.stdout).expect("report json");
+    assert_eq!(report["patch"][0]["label"], "FOH truss");
+    assert_eq!(report["patch"][0]["fixture_type"], "Wash");
+
+    let mvr = temp.path().join("show.mvr");
+    std::fs::write(&mvr, b"PK").expect("write mvr");
+    cmd()
+        .arg("pcap")
+        .arg("analyze")
+        .arg(sample_capture())
+        .arg("--stdout")
+        .arg("--patch")
+        .arg(&mvr)
+        .assert()
+        .failure()
+        .stderr(contains("invalid patch"))
+        .stderr(contains("MVR files are not supported"));
+}
+
+#[test]
+fn compare_reports_divergent_dmx_output() {
+    let golden = repo_root().join("tests").join("golden");
+    let output = cmd()
+        .arg("pcap")
+        .arg("compare")
+        .arg(sample_capture())
+        .arg(sample_capture())
+        .arg("--json")
+        .output()
+        .expect("run compare");
+    assert!(output.status.success());
+    let comparison: Value = serde_json::from_slice(&output.stdout).expect("comparison json");
+    assert_eq!(comparison["universes"][0]["presence"], "both");
+    assert_eq!(comparison["universes"][0]["divergent_samples"], 0);
+
+    cmd()
+        .arg("pcap")
+        .arg("compare")
+        .arg(sample_capture())
+        .arg(golden.join("artnet_gap").join("input.pcapng"))
+        .arg("--strict")
+        .assert()
+        .failure()
+        .stdout(contains("channels 2 (max delta 2)"))
+        .stderr(contains("DMX output diverges from the reference"));
+}
+
+#[test]
+fn latency_matches_packets_between_captures() {
+    let output = cmd()
+        .arg("pcap")
+        .arg("latency")
+        .arg(sample_capture())
+        .arg(sample_capture())
+        .arg("--json")
+        .output()
+        .expect("run latency");
+    assert!(output.status.success());
+    let latency: Value = serde_json::from_slice(&output.stdout).expect("latency json");
+    let universe = &latency["universes"][0];
+    assert_eq!(universe["lost_between"], 0);
+    assert_eq!(universe["downstream_only"], 0);
+    assert_eq!(universe["matched"], universe["upstream_packets"]);
+    assert_eq!(universe["latency"]["max_ms"], 0.0);
+
+    cmd()
+        .arg("pcap")
+        .arg("latency")
+        .arg(sample_capture())
+        .arg(sample_capture())
+        .assert()
+        .success()
+        .stdout(contains("0 lost between captures"));
+}
+
+#[test]
+fn heatmap_exports_csv_and_png() {
+    let temp = TempDir::new().expect("tempdir");
+    let csv = temp.path().join("activity.csv");
+    cmd()
+        .arg("pcap")
+        .arg("heatmap")
+        .arg(sample_capture())
+        .arg("-o")
+        .arg(&csv)
+        .assert()
+        .success()
+        .stderr(contains("OK: 1 universe(s) written"));
+    let matrix = std::fs::read_to_string(&csv).expect("read csv");
+    let mut lines = matrix.lines();
+    assert!(lines.next().expect("header").ends_with(",511,512"));
+    assert!(lines.next().expect("row").starts_with("1,artnet,"));
+
+    let png = temp.path().join("activity.png");
+    cmd()
+        .arg("pcap")
+        .arg("heatmap")
+        .arg(sample_capture())
+        .arg("-o")
+        .arg(&png)
+        .arg("--metric")
+        .arg("nonzero")
+        .arg("--quiet")
+        .assert()
+        .success();
+    let image = std::fs::read(&png).expect("read png");
+    assert!(image.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+    cmd()
+        .arg("pcap")
+        .arg("heatmap")
+        .arg(sample_capture())
+        .arg("-o")
+        .arg(&csv)
+        .arg("--metric")
+        .arg("busy")
+        .assert()
+        .failure()
+        .stderr(contains("unknown activity metric 'busy'"));
+}
+
+#[test]
+fn config_file_disables_rules_and_overrides_severity() {
+    let temp = TempDir::new().expect("tempdir");
+    let config = temp.path().join("liveshark.json");
+    std::fs::write(
+        &config,
+        r#"{"rules": {"LS-SACN-TOO-SHORT": {"enabled": false}, "LS-ARTNET-PORT": {"severity": "error"}}}"#,
+    )
+    .expect("write config");
+
+    let assert = cmd()
+        .arg("pcap")
+        .arg("analyze")
+        .arg(sample_capture())
+        .arg("--stdout")
+        .arg("--config")
+        .arg(&config)
+        .assert()
+        .success();
+    let report: Value = serde_json::from_slice(&assert.get_output().stdout).expect("json stdout");
+    let rules = report["rules"].as_array().expect("rules array");
+    assert!(rules.iter().all(|rule| rule["id"] != "LS-SACN-TOO-SHORT"));
+    let port = rules
+        .iter()
+        .find(|rule| rule["id"] == "LS-ARTNET-PORT")
+        .expect("artnet port rule");
+    assert_eq!(port["severity"], "error");
+    let violations: Vec<&Value> = report["compliance"]
+        .as_array()
+        .expect("compliance array")
+        .iter()
+        .flat_map(|entry| entry["violations"].as_array().expect("violations"))
+        .collect();
+    assert!(violations.iter().all(|v| v["id"] != "LS-SACN-TOO-SHORT"));
+
+    std::fs::write(&config, r#"{"rules": {"LS-NOPE": {}}}"#).expect("write config");
+    cmd()
+        .arg("pcap")
+        .arg("analyze")
+        .arg(sample_capture())
+        .arg("--stdout")
+        .arg("--config")
+        .arg(&config)
+        .assert()
+        .failure()
+        .stderr(contains("invalid config file"));
+}
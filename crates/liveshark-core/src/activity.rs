@@ -0,0 +1,258 @@
+//! Per-channel activity of each universe over a capture.
+//!
+//! Each universe is reduced to its output (the last frame received from any
+//! source) and every channel is scored twice: how often its level changed,
+//! and how long it sat at a non-zero level. Laid out as a universe by
+//! channel matrix, this shows at a glance which parts of the rig the
+//! captured traffic actually drove.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::analysis::round_to;
+use crate::{
+    DecodedPacket, DecodedPacketIter, DmxStateStore, PacketSource, PcapFileSource, Protocol,
+    SourceError,
+};
+
+/// Non-zero times are rounded to milliseconds.
+const TIME_SCALE: f64 = 1_000.0;
+
+/// Activity of a universe, its current output slots, and the time of its
+/// last timestamped frame.
+type OutputState = (UniverseActivity, [u8; 512], Option<f64>);
+
+/// Error returned when parsing an unknown activity metric name.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("unknown activity metric '{name}' (expected changes or nonzero)")]
+pub struct UnknownActivityMetricError {
+    pub name: String,
+}
+
+/// Score of a channel in an activity matrix.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::ActivityMetric;
+///
+/// let metric: ActivityMetric = "nonzero".parse()?;
+/// assert_eq!(metric, ActivityMetric::NonZeroTime);
+/// assert_eq!(ActivityMetric::Changes.as_str(), "changes");
+/// # Ok::<(), liveshark_core::UnknownActivityMetricError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ActivityMetric {
+    /// Frames in which the channel's level changed.
+    #[serde(rename = "changes")]
+    Changes,
+    /// Seconds the channel held a non-zero level.
+    #[serde(rename = "nonzero")]
+    NonZeroTime,
+}
+
+impl ActivityMetric {
+    /// Every metric, in declaration order.
+    pub const ALL: [ActivityMetric; 2] = [ActivityMetric::Changes, ActivityMetric::NonZeroTime];
+
+    /// Canonical lowercase name.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ActivityMetric::Changes => "changes",
+            ActivityMetric::NonZeroTime => "nonzero",
+        }
+    }
+}
+
+impl fmt::Display for ActivityMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ActivityMetric {
+    type Err = UnknownActivityMetricError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        ActivityMetric::ALL
+            .iter()
+            .copied()
+            .find(|metric| metric.as_str() == name)
+            .ok_or_else(|| UnknownActivityMetricError {
+                name: name.to_string(),
+            })
+    }
+}
+
+/// Activity of the 512 channels of one universe; index 0 is channel 1.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UniverseActivity {
+    /// Universe identifier.
+    pub universe: u16,
+    /// Protocol of the universe.
+    pub proto: Protocol,
+    /// Frames in which each channel's level changed.
+    pub changes: Vec<u64>,
+    /// Seconds each channel held a non-zero level (three decimals); the
+    /// last frame of the universe is not counted, as its duration is
+    /// unknown.
+    pub nonzero_s: Vec<f64>,
+}
+
+impl UniverseActivity {
+    /// Scores of the 512 channels for `metric`.
+    pub fn values(&self, metric: ActivityMetric) -> Vec<f64> {
+        match metric {
+            ActivityMetric::Changes => self.changes.iter().map(|count| *count as f64).collect(),
+            ActivityMetric::NonZeroTime => self.nonzero_s.clone(),
+        }
+    }
+}
+
+/// Activity matrix of a capture: one row per universe, sorted by universe
+/// and protocol.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{ActivityMetric, ChannelActivity};
+///
+/// let activity = ChannelActivity { universes: Vec::new() };
+/// assert_eq!(activity.max(ActivityMetric::Changes), 0.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChannelActivity {
+    /// Universes seen in the capture.
+    pub universes: Vec<UniverseActivity>,
+}
+
+impl ChannelActivity {
+    /// Highest score of any channel for `metric` (0 when empty).
+    pub fn max(&self, metric: ActivityMetric) -> f64 {
+        self.universes
+            .iter()
+            .flat_map(|universe| universe.values(metric))
+            .fold(0.0, f64::max)
+    }
+}
+
+/// Channel activity of a capture file.
+///
+/// # Errors
+/// Returns `SourceError` when the capture cannot be read.
+pub fn channel_activity_pcap_file(path: &Path) -> Result<ChannelActivity, SourceError> {
+    channel_activity_source(PcapFileSource::open(path)?)
+}
+
+/// Channel activity of a packet source. Frames without timestamps count as
+/// changes but add no time.
+///
+/// # Errors
+/// Returns `SourceError` when the source fails.
+pub fn channel_activity_source<S: PacketSource>(source: S) -> Result<ChannelActivity, SourceError> {
+    let mut state = DmxStateStore::new();
+    let mut universes: BTreeMap<(u16, Protocol), OutputState> = BTreeMap::new();
+    for packet in DecodedPacketIter::new(source) {
+        let frame = match packet? {
+            DecodedPacket::ArtDmx {
+                ts,
+                endpoints,
+                frame,
+            } => state.apply_artdmx(&endpoints, ts, &frame),
+            DecodedPacket::SacnDmx {
+                ts,
+                endpoints,
+                frame,
+            } => state.apply_sacn_dmx(&endpoints, ts, &frame),
+            _ => continue,
+        };
+        let (activity, output, last_ts) = universes
+            .entry((frame.universe, frame.protocol))
+            .or_insert_with(|| {
+                (
+                    UniverseActivity {
+                        universe: frame.universe,
+                        proto: frame.protocol,
+                        changes: vec![0; 512],
+                        nonzero_s: vec![0.0; 512],
+                    },
+                    [0u8; 512],
+                    None,
+                )
+            });
+        if let (Some(previous), Some(ts)) = (*last_ts, frame.timestamp) {
+            let elapsed = (ts - previous).max(0.0);
+            for (held, level) in activity.nonzero_s.iter_mut().zip(output.iter()) {
+                if *level != 0 {
+                    *held += elapsed;
+                }
+            }
+        }
+        for (count, (before, after)) in activity
+            .changes
+            .iter_mut()
+            .zip(output.iter().zip(frame.slots.iter()))
+        {
+            if before != after {
+                *count += 1;
+            }
+        }
+        *output = frame.slots;
+        if frame.timestamp.is_some() {
+            *last_ts = frame.timestamp;
+        }
+    }
+    Ok(ChannelActivity {
+        universes: universes
+            .into_values()
+            .map(|(mut activity, _, _)| {
+                for held in &mut activity.nonzero_s {
+                    *held = round_to(*held, TIME_SCALE);
+                }
+                activity
+            })
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ActivityMetric, channel_activity_source};
+    use crate::PacketEvent;
+    use crate::test_util::{ArtDmxBuilder, MemorySource, udp_event};
+
+    fn artdmx(ts: f64, slots: [u8; 2]) -> PacketEvent {
+        let payload = ArtDmxBuilder::new(1).slots(slots).build();
+        udp_event(ts, [10, 0, 0, 1], [10, 0, 0, 255], 6454, &payload)
+    }
+
+    #[test]
+    fn changes_and_nonzero_time_are_counted_per_channel() {
+        let source = MemorySource::from_iter([
+            artdmx(0.0, [255, 0]),
+            artdmx(1.0, [255, 10]),
+            artdmx(2.0, [0, 20]),
+            artdmx(3.5, [0, 20]),
+        ]);
+        let activity = channel_activity_source(source).expect("activity");
+        assert_eq!(activity.universes.len(), 1);
+        let universe = &activity.universes[0];
+        assert_eq!(universe.universe, 1);
+        // Channel 1 starts at 255 (a change from the blank output) and drops
+        // to 0 at 2.0; channel 2 rises twice.
+        assert_eq!(&universe.changes[..3], &[2, 2, 0]);
+        assert_eq!(&universe.nonzero_s[..3], &[2.0, 2.5, 0.0]);
+        assert_eq!(activity.max(ActivityMetric::Changes), 2.0);
+    }
+
+    #[test]
+    fn metric_names_round_trip() {
+        for metric in ActivityMetric::ALL {
+            assert_eq!(metric.as_str().parse::<ActivityMetric>(), Ok(metric));
+        }
+        assert!("busy".parse::<ActivityMetric>().is_err());
+    }
+}
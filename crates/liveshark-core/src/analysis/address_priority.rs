@@ -0,0 +1,106 @@
+//! sACN per-address priority (start code 0xdd).
+//!
+//! Sources that share a universe can claim slots individually by sending a
+//! priority per slot alongside their DMX data; a priority of 0 leaves the
+//! slot to other sources. The map kept per universe and source is the last
+//! one received, with a count of how often it changed.
+
+use std::collections::BTreeMap;
+
+use crate::{AddressPriorityMap, AddressPriorityRange};
+
+#[derive(Debug, Default)]
+struct Source {
+    packets: u64,
+    changes: u64,
+    priorities: Vec<u8>,
+}
+
+/// Latest per-address priorities per universe and source.
+#[derive(Debug, Default)]
+pub(crate) struct AddressPriorityTracker {
+    sources: BTreeMap<(u16, String), Source>,
+}
+
+impl AddressPriorityTracker {
+    /// Record a priority packet of `source_id` for `universe`.
+    pub(crate) fn add(&mut self, universe: u16, source_id: &str, priorities: Vec<u8>) {
+        let source = self
+            .sources
+            .entry((universe, source_id.to_string()))
+            .or_default();
+        if source.packets > 0 && source.priorities != priorities {
+            source.changes += 1;
+        }
+        source.packets += 1;
+        source.priorities = priorities;
+    }
+
+    /// Priority maps sorted by universe then source ID.
+    pub(crate) fn finish(self) -> Vec<AddressPriorityMap> {
+        self.sources
+            .into_iter()
+            .map(|((universe, source_id), source)| AddressPriorityMap {
+                universe,
+                source_id,
+                packets: source.packets,
+                changes: source.changes,
+                ranges: ranges(&source.priorities),
+            })
+            .collect()
+    }
+}
+
+/// Runs of equal priority, with 1-based slot numbers.
+fn ranges(priorities: &[u8]) -> Vec<AddressPriorityRange> {
+    let mut ranges: Vec<AddressPriorityRange> = Vec::new();
+    for (slot, &priority) in (1u16..).zip(priorities) {
+        match ranges.last_mut() {
+            Some(range) if range.priority == priority => range.last_slot = slot,
+            _ => ranges.push(AddressPriorityRange {
+                first_slot: slot,
+                last_slot: slot,
+                priority,
+            }),
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AddressPriorityTracker;
+    use crate::AddressPriorityRange;
+
+    #[test]
+    fn priorities_are_grouped_into_ranges() {
+        let mut tracker = AddressPriorityTracker::default();
+        tracker.add(1, "sacn:cid:a", vec![100; 4]);
+        tracker.add(1, "sacn:cid:a", vec![100; 4]);
+        tracker.add(1, "sacn:cid:a", vec![150, 150, 0, 100]);
+
+        let maps = tracker.finish();
+        assert_eq!(maps.len(), 1);
+        assert_eq!((maps[0].packets, maps[0].changes), (3, 1));
+        assert_eq!(
+            maps[0].ranges,
+            vec![
+                AddressPriorityRange {
+                    first_slot: 1,
+                    last_slot: 2,
+                    priority: 150,
+                },
+                AddressPriorityRange {
+                    first_slot: 3,
+                    last_slot: 3,
+                    priority: 0,
+                },
+                AddressPriorityRange {
+                    first_slot: 4,
+                    last_slot: 4,
+                    priority: 100,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,129 @@
+//! 16-bit channel pair detection.
+//!
+//! Moving lights drive pan, tilt, and some dimmers with a coarse/fine slot
+//! pair: the fine slot sweeps quickly and wraps around while the coarse slot
+//! steps by one at each wrap. Spotting that carry pattern between adjacent
+//! slots lets reports treat the pair as one 16-bit parameter instead of two
+//! unrelated channels.
+
+use super::dmx::DmxFrame;
+use crate::ChannelPair;
+
+/// Fewest carries (coarse step together with a fine wrap) before a pair is
+/// reported; one could be a coincidence.
+pub const MIN_PAIR_CARRIES: u64 = 2;
+/// Smallest fine-slot jump read as a wraparound rather than a move.
+const WRAP_MIN_DELTA: i16 = 128;
+/// Share of coarse changes that must be carries (percent).
+const MIN_CARRY_PERCENT: u64 = 80;
+
+#[derive(Debug, Default, Clone, Copy)]
+struct PairCounts {
+    carries: u64,
+    coarse_changes: u64,
+    fine_changes: u64,
+}
+
+/// Coarse/fine pairs across all sources of a universe; `sources` holds each
+/// source's frames in arrival order.
+pub(crate) fn detect_channel_pairs<'a>(
+    sources: impl IntoIterator<Item = &'a [DmxFrame]>,
+) -> Vec<ChannelPair> {
+    let mut counts = [PairCounts::default(); 511];
+    for frames in sources {
+        for pair in frames.windows(2) {
+            count_changes(&mut counts, &pair[0].slots, &pair[1].slots);
+        }
+    }
+
+    let mut pairs = Vec::new();
+    let mut index = 0;
+    while index < counts.len() {
+        let current = &counts[index];
+        // A slot belongs to at most one pair; an overlapping neighbor with
+        // more carries wins.
+        let next_is_better = counts
+            .get(index + 1)
+            .is_some_and(|next| is_pair(next) && next.carries > current.carries);
+        if is_pair(current) && !next_is_better {
+            pairs.push(ChannelPair {
+                coarse: index as u16 + 1,
+                fine: index as u16 + 2,
+                carries: current.carries,
+                label: None,
+            });
+            index += 2;
+        } else {
+            index += 1;
+        }
+    }
+    pairs
+}
+
+fn count_changes(counts: &mut [PairCounts; 511], prev: &[u8; 512], cur: &[u8; 512]) {
+    for (index, count) in counts.iter_mut().enumerate() {
+        let coarse = i16::from(cur[index]) - i16::from(prev[index]);
+        let fine = i16::from(cur[index + 1]) - i16::from(prev[index + 1]);
+        if coarse != 0 {
+            count.coarse_changes += 1;
+        }
+        if fine != 0 {
+            count.fine_changes += 1;
+        }
+        let wrapped_up = coarse == 1 && fine <= -WRAP_MIN_DELTA;
+        let wrapped_down = coarse == -1 && fine >= WRAP_MIN_DELTA;
+        if wrapped_up || wrapped_down {
+            count.carries += 1;
+        }
+    }
+}
+
+fn is_pair(count: &PairCounts) -> bool {
+    count.carries >= MIN_PAIR_CARRIES
+        && count.carries * 100 >= count.coarse_changes * MIN_CARRY_PERCENT
+        && count.fine_changes >= 2 * count.coarse_changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::detect_channel_pairs;
+    use crate::Protocol;
+    use crate::analysis::dmx::DmxFrame;
+
+    fn frames(values: impl Iterator<Item = [u8; 4]>) -> Vec<DmxFrame> {
+        values
+            .enumerate()
+            .map(|(index, head)| {
+                let mut slots = [0u8; 512];
+                slots[..4].copy_from_slice(&head);
+                DmxFrame {
+                    universe: 1,
+                    timestamp: Some(index as f64 * 0.025),
+                    source_id: "artnet:10.0.0.1:6454".to_string(),
+                    protocol: Protocol::ArtNet,
+                    slots,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sweeping_16_bit_value_is_detected() {
+        // Slots 2/3 carry a 16-bit pan sweeping upward; slot 1 is a dimmer
+        // fading independently.
+        let sweep = frames((0u16..40).map(|step| {
+            let value = 200 + step * 96;
+            [(step * 3) as u8, (value >> 8) as u8, value as u8, 0]
+        }));
+        let pairs = detect_channel_pairs([sweep.as_slice()]);
+        assert_eq!(pairs.len(), 1);
+        assert_eq!((pairs[0].coarse, pairs[0].fine), (2, 3));
+        assert!(pairs[0].carries >= 10);
+    }
+
+    #[test]
+    fn unrelated_channels_are_not_paired() {
+        let chase = frames((0u8..40).map(|step| [step.wrapping_mul(50), step, 255 - step, 0]));
+        assert!(detect_channel_pairs([chase.as_slice()]).is_empty());
+    }
+}
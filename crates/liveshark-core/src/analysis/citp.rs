@@ -0,0 +1,196 @@
+//! CITP peers: media servers, consoles, and visualizers seen over UDP.
+//!
+//! Peers announce themselves with PINF/PLoc; media servers also publish
+//! their layer status and stream thumbnails or previews (MSEX). A console
+//! that loses the thumbnails of a media server, or receives them at a
+//! crawl, shows it here before the operator notices stale previews.
+
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+
+use super::{AVERAGE_SCALE, round_to};
+use crate::{
+    CitpLayerSummary, CitpMessage, CitpPacket, CitpPeer, CitpPeerSummary, CitpStreamSummary,
+};
+
+#[derive(Debug)]
+struct Stream {
+    format: [u8; 4],
+    width: u16,
+    height: u16,
+    frames: u64,
+    first_seen: Option<f64>,
+    last_seen: Option<f64>,
+}
+
+#[derive(Debug, Default)]
+struct Peer {
+    packets: u64,
+    location: Option<CitpPeer>,
+    layers: BTreeMap<u8, CitpLayerSummary>,
+    streams: BTreeMap<u16, Stream>,
+}
+
+/// CITP peers, keyed by source IP.
+#[derive(Debug, Default)]
+pub(crate) struct CitpTracker {
+    peers: BTreeMap<String, Peer>,
+}
+
+impl CitpTracker {
+    /// Record a CITP packet sent from `src_ip`.
+    pub(crate) fn add(&mut self, ts: Option<f64>, src_ip: IpAddr, packet: &CitpPacket) {
+        let peer = self.peers.entry(src_ip.to_string()).or_default();
+        peer.packets += 1;
+        match &packet.message {
+            CitpMessage::PeerLocation(location) => peer.location = Some(location.clone()),
+            CitpMessage::LayerStatus(layers) => {
+                for layer in layers {
+                    peer.layers.insert(
+                        layer.layer,
+                        CitpLayerSummary {
+                            layer: layer.layer,
+                            physical_output: layer.physical_output,
+                            media_name: layer.media_name.clone(),
+                        },
+                    );
+                }
+            }
+            CitpMessage::StreamFrame(frame) => {
+                let stream = peer.streams.entry(frame.source_id).or_insert(Stream {
+                    format: frame.format,
+                    width: frame.width,
+                    height: frame.height,
+                    frames: 0,
+                    first_seen: None,
+                    last_seen: None,
+                });
+                stream.format = frame.format;
+                stream.width = frame.width;
+                stream.height = frame.height;
+                stream.frames += 1;
+                if let Some(ts) = ts {
+                    stream.first_seen.get_or_insert(ts);
+                    stream.last_seen = Some(ts);
+                }
+            }
+            CitpMessage::Other { .. } => {}
+        }
+    }
+
+    /// Peers sorted by source IP, with their layers sorted by layer number
+    /// and streams by source id.
+    pub(crate) fn finish(self) -> Vec<CitpPeerSummary> {
+        self.peers
+            .into_iter()
+            .map(|(source_ip, peer)| {
+                let location = peer.location;
+                CitpPeerSummary {
+                    source_ip,
+                    name: location.as_ref().map(|location| location.name.clone()),
+                    peer_type: location.as_ref().map(|location| location.peer_type.clone()),
+                    state: location.as_ref().map(|location| location.state.clone()),
+                    listening_port: location.as_ref().map(|location| location.listening_port),
+                    packets: peer.packets,
+                    layers: peer.layers.into_values().collect(),
+                    streams: peer
+                        .streams
+                        .into_iter()
+                        .map(|(source_id, stream)| CitpStreamSummary {
+                            source_id,
+                            format: String::from_utf8_lossy(&stream.format).into_owned(),
+                            width: stream.width,
+                            height: stream.height,
+                            frames: stream.frames,
+                            fps: match (stream.first_seen, stream.last_seen) {
+                                (Some(first), Some(last)) if last > first => Some(round_to(
+                                    (stream.frames - 1) as f64 / (last - first),
+                                    AVERAGE_SCALE,
+                                )),
+                                _ => None,
+                            },
+                        })
+                        .collect(),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use super::CitpTracker;
+    use crate::{CitpHeader, CitpLayerStatus, CitpMessage, CitpPacket, CitpPeer, CitpStreamFrame};
+
+    fn packet(message: CitpMessage) -> CitpPacket {
+        CitpPacket {
+            header: CitpHeader {
+                version_major: 1,
+                version_minor: 0,
+                message_size: 0,
+                message_part_count: 1,
+                message_part: 0,
+                layer: *b"MSEX",
+            },
+            message,
+        }
+    }
+
+    #[test]
+    fn peers_collect_location_layers_and_stream_rates() {
+        let mut tracker = CitpTracker::default();
+        let server = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 40));
+        tracker.add(
+            Some(0.0),
+            server,
+            &packet(CitpMessage::PeerLocation(CitpPeer {
+                listening_port: 4811,
+                peer_type: "MediaServer".to_string(),
+                name: "Server A".to_string(),
+                state: "Running".to_string(),
+            })),
+        );
+        tracker.add(
+            Some(0.1),
+            server,
+            &packet(CitpMessage::LayerStatus(vec![CitpLayerStatus {
+                layer: 2,
+                physical_output: 1,
+                media_name: "intro.mov".to_string(),
+                media_position: 0,
+                media_length: 100,
+            }])),
+        );
+        for frame in 0..5 {
+            tracker.add(
+                Some(1.0 + f64::from(frame) * 0.2),
+                server,
+                &packet(CitpMessage::StreamFrame(CitpStreamFrame {
+                    source_id: 1,
+                    format: *b"JPEG",
+                    width: 160,
+                    height: 90,
+                })),
+            );
+        }
+
+        let peers = tracker.finish();
+        assert_eq!(peers.len(), 1);
+        let peer = &peers[0];
+        assert_eq!(peer.name.as_deref(), Some("Server A"));
+        assert_eq!(peer.peer_type.as_deref(), Some("MediaServer"));
+        assert_eq!(peer.listening_port, Some(4811));
+        assert_eq!(peer.packets, 7);
+        assert_eq!(peer.layers.len(), 1);
+        assert_eq!(
+            (peer.layers[0].layer, peer.layers[0].media_name.as_str()),
+            (2, "intro.mov")
+        );
+        let stream = &peer.streams[0];
+        assert_eq!(stream.format, "JPEG");
+        assert_eq!((stream.width, stream.height, stream.frames), (160, 90, 5));
+        assert_eq!(stream.fps, Some(5.0));
+    }
+}
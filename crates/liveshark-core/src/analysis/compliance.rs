@@ -0,0 +1,1841 @@
+//! Compliance rules engine.
+//!
+//! Decoder errors and packet checks are mapped to registry rules (`RuleId`)
+//! here. The engine applies the configured `RuleSet`: disabled rules are
+//! neither reported nor forwarded to observers, and severity overrides replace
+//! the rule default. Violations are aggregated per protocol and rule, with
+//! deduplicated examples capped at `max_violation_examples`.
+//!
+//! Stateful checks (sequence gaps, rates over a sliding window, priority
+//! changes, ...) keep their state per key such as (protocol, universe, source)
+//! and, where a rule calls for it, record one violation per episode or per
+//! offending value rather than per packet; `RuleId::description` states what
+//! each rule checks. Checks that need the whole capture run once the last
+//! packet has been examined: a sACN CID seen from several IPs is reported once,
+//! as `LS-SACN-CID-COLLISION` when two of them sent at the same time and as
+//! `LS-SACN-CID-MULTI-IP` when it only moved between them.
+//!
+//! `compliance_percentage` is `100 * (1 - penalty / examined)` per protocol:
+//! `examined` counts packets identified as that protocol (every UDP datagram
+//! for `udp`), and each of them adds the weight of its most severe violation
+//! to `penalty` (error 1.0, warning 0.5, info 0.0). Payloads too short to be
+//! identified (`*-TOO-SHORT`) and violations found after the last packet
+//! (e.g. `LS-SACN-CID-MULTI-IP`) are reported but do not lower the percentage.
+//!
+//! Site-specific rules are evaluated alongside; see [`CustomRule`].
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::ops::RangeInclusive;
+
+use super::config::{AnalyzerConfig, MAX_SEQUENCE_GAP};
+use super::observer::{AnalysisObserver, ViolationEvent};
+use super::takeovers::SOURCE_TIMEOUT_S;
+use super::udp::UdpPacket;
+use super::udp::error::UdpError;
+use super::{ARTNET_PORT, SACN_PORT, ts_to_rfc3339};
+use crate::protocols::artnet::ArtAddress;
+use crate::protocols::artnet::error::ArtNetError;
+use crate::protocols::artnet::layout::PROTOCOL_VERSION_MIN;
+use crate::protocols::sacn::error::SacnError;
+use crate::protocols::sacn::layout::MAX_PRIORITY;
+use crate::rules::{CustomRule, PacketFields, RuleId, RuleSet};
+use crate::{
+    ComplianceSummary, ExampleBytes, Protocol, RigPatch, SacnUniverseDiscovery, Severity,
+    Violation, ViolationExample,
+};
+
+const MS_PER_S: f64 = 1000.0;
+const PERCENT: f64 = 100.0;
+/// Percentages are rounded to this many steps per unit (two decimals).
+const PERCENTAGE_ROUNDING: f64 = 100.0;
+const ERROR_WEIGHT: f64 = 1.0;
+const WARNING_WEIGHT: f64 = 0.5;
+const INFO_WEIGHT: f64 = 0.0;
+
+/// Compliance entries collected during analysis, keyed by protocol.
+pub(crate) struct RulesEngine<'a> {
+    rules: &'a RuleSet,
+    custom_rules: &'a [CustomRule],
+    patch: Option<&'a RigPatch>,
+    max_examples: usize,
+    example_bytes: usize,
+    min_sequence_gap: u8,
+    artnet_min_interval_s: f64,
+    entries: HashMap<Protocol, ComplianceSummary>,
+    last_sequence: HashMap<(Protocol, u16, String), u8>,
+    last_artnet_ts: HashMap<(u16, String), f64>,
+    last_priority: HashMap<(u16, String), u8>,
+    broadcast_window_s: f64,
+    max_broadcast_pps: f64,
+    broadcasts: HashMap<IpAddr, RateState>,
+    universe_window_s: f64,
+    max_universe_fps: f64,
+    universe_rates: HashMap<(Protocol, u16), RateState>,
+    max_source_fps: f64,
+    source_rates: HashMap<(u16, String), SourceRate>,
+    max_ip_datagram_len: usize,
+    multicast_ttl: RangeInclusive<u8>,
+    reported_ttls: HashSet<(String, u8)>,
+    reported_unicast: HashSet<(u16, SocketAddr)>,
+    multicast_universes: BTreeMap<u16, (IpAddr, u16, Option<f64>)>,
+    reported_protocol_versions: HashSet<(String, u16)>,
+    cid_addresses: BTreeMap<String, BTreeMap<IpAddr, AddressSpan>>,
+    examined: HashMap<Protocol, u64>,
+    penalty: HashMap<Protocol, f64>,
+    packet_penalty: HashMap<Protocol, f64>,
+    packet_examined: HashSet<Protocol>,
+    packet_vlan: Option<u16>,
+}
+
+/// Frame times of one sACN source within the rate window, and the episode
+/// above `max_source_fps` in progress.
+#[derive(Default)]
+struct SourceRate {
+    samples: VecDeque<f64>,
+    episode: Option<RateEpisode>,
+}
+
+/// Where a source's rate first went above the limit, and how high it got.
+struct RateEpisode {
+    ip: IpAddr,
+    port: u16,
+    start_ts: f64,
+    peak_fps: f64,
+}
+
+/// Port and time range of one source address seen with a CID.
+struct AddressSpan {
+    port: u16,
+    first_ts: Option<f64>,
+    last_ts: Option<f64>,
+    /// First..last timestamps of each run of packets less than the source
+    /// loss timeout apart.
+    active: Vec<(f64, f64)>,
+}
+
+/// Timestamps within a rate window, and whether the rate is above its limit.
+#[derive(Default)]
+struct RateState {
+    samples: VecDeque<f64>,
+    exceeded: bool,
+}
+
+impl RateState {
+    /// Add a sample; returns the rate only when it rises above `max_rate`,
+    /// so each episode is reported once.
+    fn rises_above(&mut self, ts: f64, window_s: f64, max_rate: f64) -> Option<f64> {
+        self.samples.push_back(ts);
+        while let Some(&oldest) = self.samples.front() {
+            if ts - oldest <= window_s {
+                break;
+            }
+            self.samples.pop_front();
+        }
+        let rate = self.samples.len() as f64 / window_s;
+        if rate <= max_rate {
+            self.exceeded = false;
+            return None;
+        }
+        if self.exceeded {
+            return None;
+        }
+        self.exceeded = true;
+        Some(rate)
+    }
+}
+
+impl<'a> RulesEngine<'a> {
+    pub(crate) fn new(config: &'a AnalyzerConfig) -> Self {
+        Self {
+            rules: &config.rules,
+            custom_rules: &config.custom_rules,
+            patch: config.patch.as_ref(),
+            max_examples: config.max_violation_examples,
+            example_bytes: config.violation_example_bytes,
+            min_sequence_gap: config.min_sequence_gap,
+            artnet_min_interval_s: config.artnet_min_interval_s,
+            entries: HashMap::new(),
+            last_sequence: HashMap::new(),
+            last_artnet_ts: HashMap::new(),
+            last_priority: HashMap::new(),
+            broadcast_window_s: config.flow_rate_window_s,
+            max_broadcast_pps: config.max_broadcast_pps,
+            broadcasts: HashMap::new(),
+            universe_window_s: config.fps_window_s,
+            max_universe_fps: config.max_universe_fps,
+            universe_rates: HashMap::new(),
+            max_source_fps: config.max_source_fps,
+            source_rates: HashMap::new(),
+            max_ip_datagram_len: config.max_ip_datagram_len,
+            multicast_ttl: config.min_multicast_ttl..=config.max_multicast_ttl,
+            reported_ttls: HashSet::new(),
+            reported_unicast: HashSet::new(),
+            multicast_universes: BTreeMap::new(),
+            reported_protocol_versions: HashSet::new(),
+            cid_addresses: BTreeMap::new(),
+            examined: HashMap::new(),
+            penalty: HashMap::new(),
+            packet_penalty: HashMap::new(),
+            packet_examined: HashSet::new(),
+            packet_vlan: None,
+        }
+    }
+
+    /// `universe=<n>` detail field, followed by `rig=<label>` when the rig
+    /// patch names the universe.
+    pub(crate) fn universe_key(&self, universe: u16) -> String {
+        match self.patch.and_then(|patch| patch.universe_label(universe)) {
+            Some(label) => format!("universe={universe}, rig={label}"),
+            None => format!("universe={universe}"),
+        }
+    }
+
+    /// Report sACN data multicast to a group other than the one of its universe.
+    pub(crate) fn check_multicast_group(
+        &mut self,
+        observer: &mut dyn AnalysisObserver,
+        universe: u16,
+        udp: &UdpPacket<'_>,
+        ts: Option<f64>,
+    ) {
+        if !udp.dst_ip.is_multicast() {
+            return;
+        }
+        let expected = sacn_multicast_group(universe, udp.dst_ip.is_ipv6());
+        if udp.dst_ip == expected {
+            if expected.is_ipv4() {
+                self.multicast_universes
+                    .entry(universe)
+                    .or_insert((udp.src_ip, udp.src_port, ts));
+            }
+            return;
+        }
+        let detail = format!(
+            "{}, dst={}, expected={}",
+            self.universe_key(universe),
+            udp.dst_ip,
+            expected
+        );
+        self.record_packet(observer, RuleId::SacnMulticastMismatch, detail, udp, ts);
+    }
+
+    /// Report each receiver that gets a universe by sACN unicast (once).
+    pub(crate) fn check_unicast(
+        &mut self,
+        observer: &mut dyn AnalysisObserver,
+        universe: u16,
+        receiver: SocketAddr,
+        udp: &UdpPacket<'_>,
+        ts: Option<f64>,
+    ) {
+        if !self.reported_unicast.insert((universe, receiver)) {
+            return;
+        }
+        let detail = format!("{}, receiver={}", self.universe_key(universe), receiver);
+        self.record_packet(observer, RuleId::SacnUnicast, detail, udp, ts);
+    }
+
+    /// Report sACN sources multicasting with a TTL outside the accepted range.
+    pub(crate) fn check_multicast_ttl(
+        &mut self,
+        observer: &mut dyn AnalysisObserver,
+        source_id: &str,
+        udp: &UdpPacket<'_>,
+        ts: Option<f64>,
+    ) {
+        if !udp.dst_ip.is_multicast() || self.multicast_ttl.contains(&udp.ttl) {
+            return;
+        }
+        if !self.reported_ttls.insert((source_id.to_string(), udp.ttl)) {
+            return;
+        }
+        let detail = format!(
+            "dst={}, ttl={}, accepted={}..={}",
+            udp.dst_ip,
+            udp.ttl,
+            self.multicast_ttl.start(),
+            self.multicast_ttl.end()
+        );
+        self.record_packet(observer, RuleId::SacnMulticastTtl, detail, udp, ts);
+    }
+
+    /// Tag the violations of the current packet with its VLAN ID.
+    pub(crate) fn set_vlan(&mut self, vlan: Option<u16>) {
+        self.packet_vlan = vlan;
+    }
+
+    /// Count the current packet as examined for `protocol`.
+    pub(crate) fn examine(&mut self, protocol: Protocol) {
+        if self.packet_examined.insert(protocol) {
+            *self.examined.entry(protocol).or_default() += 1;
+        }
+    }
+
+    /// Close the current packet: its worst violation per examined protocol
+    /// is added to that protocol's penalty.
+    pub(crate) fn end_packet(&mut self) {
+        for (protocol, weight) in self.packet_penalty.drain() {
+            if self.packet_examined.contains(&protocol) {
+                *self.penalty.entry(protocol).or_default() += weight;
+            }
+        }
+        self.packet_examined.clear();
+        self.packet_vlan = None;
+    }
+
+    /// Report lighting-protocol datagrams longer than the configured maximum.
+    pub(crate) fn check_datagram_size(
+        &mut self,
+        observer: &mut dyn AnalysisObserver,
+        udp: &UdpPacket<'_>,
+        ts: Option<f64>,
+    ) {
+        let lighting = [udp.src_port, udp.dst_port]
+            .iter()
+            .any(|port| *port == ARTNET_PORT || *port == SACN_PORT);
+        if !lighting || udp.ip_len <= self.max_ip_datagram_len {
+            return;
+        }
+        let detail = format!(
+            "ip_len={}, max_len={}",
+            udp.ip_len, self.max_ip_datagram_len
+        );
+        self.record_packet(observer, RuleId::UdpOversized, detail, udp, ts);
+    }
+
+    /// Report a UDP packet the capture cut short of its length on the wire,
+    /// with its source when its headers survived.
+    pub(crate) fn record_truncated(
+        &mut self,
+        observer: &mut dyn AnalysisObserver,
+        captured: usize,
+        original: usize,
+        source: Option<(&IpAddr, u16)>,
+        ts: Option<f64>,
+    ) {
+        let detail = format!("captured={}, original={}", captured, original);
+        self.record(
+            observer,
+            RuleId::CaptureTruncated,
+            format_violation_example(detail, source, ts),
+        );
+    }
+
+    /// Report sources whose broadcast rate rises above the configured maximum.
+    pub(crate) fn check_broadcast(
+        &mut self,
+        observer: &mut dyn AnalysisObserver,
+        udp: &UdpPacket<'_>,
+        ts: Option<f64>,
+    ) {
+        let rule = RuleId::UdpBroadcastStorm;
+        let Some(ts) = ts else {
+            return;
+        };
+        if !is_broadcast(&udp.dst_ip) || !self.rules.is_enabled(rule) {
+            return;
+        }
+        let state = self.broadcasts.entry(udp.src_ip).or_default();
+        let Some(pps) = state.rises_above(ts, self.broadcast_window_s, self.max_broadcast_pps)
+        else {
+            return;
+        };
+        let detail = format!(
+            "dst={}, pps={:.1}, max_pps={:.1}",
+            udp.dst_ip, pps, self.max_broadcast_pps
+        );
+        self.record_packet(observer, rule, detail, udp, Some(ts));
+    }
+
+    /// Report universes whose frame rate rises above the configured maximum.
+    pub(crate) fn check_universe_rate(
+        &mut self,
+        observer: &mut dyn AnalysisObserver,
+        protocol: Protocol,
+        universe: u16,
+        udp: &UdpPacket<'_>,
+        ts: Option<f64>,
+    ) {
+        let rule = match protocol {
+            Protocol::ArtNet => RuleId::ArtNetUniverseRate,
+            Protocol::Sacn => RuleId::SacnUniverseRate,
+            Protocol::Udp
+            | Protocol::Tcp
+            | Protocol::Osc
+            | Protocol::Citp
+            | Protocol::Ptp
+            | Protocol::Dhcp => {
+                return;
+            }
+        };
+        let Some(ts) = ts else {
+            return;
+        };
+        if !self.rules.is_enabled(rule) {
+            return;
+        }
+        let state = self.universe_rates.entry((protocol, universe)).or_default();
+        let Some(fps) = state.rises_above(ts, self.universe_window_s, self.max_universe_fps) else {
+            return;
+        };
+        let detail = format!(
+            "{}, fps={:.1}, max_fps={:.1}",
+            self.universe_key(universe),
+            fps,
+            self.max_universe_fps
+        );
+        self.record_packet(observer, rule, detail, udp, Some(ts));
+    }
+
+    /// Track the refresh rate of an sACN source; an episode above the
+    /// configured maximum is recorded with its peak once the rate falls back.
+    pub(crate) fn check_source_rate(
+        &mut self,
+        observer: &mut dyn AnalysisObserver,
+        universe: u16,
+        source_id: &str,
+        udp: &UdpPacket<'_>,
+        ts: Option<f64>,
+    ) {
+        let Some(ts) = ts else {
+            return;
+        };
+        if !self.rules.is_enabled(RuleId::SacnRate) {
+            return;
+        }
+        let rate = self
+            .source_rates
+            .entry((universe, source_id.to_string()))
+            .or_default();
+        rate.samples.push_back(ts);
+        while let Some(&oldest) = rate.samples.front() {
+            if ts - oldest <= self.universe_window_s {
+                break;
+            }
+            rate.samples.pop_front();
+        }
+        // Frame intervals within the window, so a steady 44 Hz source reads 44.
+        let fps = (rate.samples.len() - 1) as f64 / self.universe_window_s;
+        if fps > self.max_source_fps {
+            let episode = rate.episode.get_or_insert(RateEpisode {
+                ip: udp.src_ip,
+                port: udp.src_port,
+                start_ts: ts,
+                peak_fps: fps,
+            });
+            episode.peak_fps = episode.peak_fps.max(fps);
+            return;
+        }
+        if let Some(episode) = rate.episode.take() {
+            self.record_source_rate(observer, universe, source_id, episode);
+        }
+    }
+
+    /// Record the sACN rate episodes still in progress (end of capture).
+    pub(crate) fn check_source_rates(&mut self, observer: &mut dyn AnalysisObserver) {
+        let mut open: Vec<((u16, String), RateEpisode)> = self
+            .source_rates
+            .drain()
+            .filter_map(|(key, rate)| Some((key, rate.episode?)))
+            .collect();
+        open.sort_by(|a, b| a.0.cmp(&b.0));
+        for ((universe, source_id), episode) in open {
+            self.record_source_rate(observer, universe, &source_id, episode);
+        }
+    }
+
+    fn record_source_rate(
+        &mut self,
+        observer: &mut dyn AnalysisObserver,
+        universe: u16,
+        source_id: &str,
+        episode: RateEpisode,
+    ) {
+        let detail = format!(
+            "{}, source={}, peak_fps={:.1}, max_fps={:.1}",
+            self.universe_key(universe),
+            source_id,
+            episode.peak_fps,
+            self.max_source_fps
+        );
+        let example = format_violation_example(
+            detail,
+            Some((&episode.ip, episode.port)),
+            Some(episode.start_ts),
+        );
+        self.record(observer, RuleId::SacnRate, example);
+    }
+
+    /// Report ArtDMX packets spaced closer than the configured minimum.
+    pub(crate) fn check_artnet_interval(
+        &mut self,
+        observer: &mut dyn AnalysisObserver,
+        universe: u16,
+        source_id: &str,
+        udp: &UdpPacket<'_>,
+        ts: Option<f64>,
+    ) {
+        let rule = RuleId::ArtNetMinInterval;
+        let Some(ts) = ts else {
+            return;
+        };
+        if !self.rules.is_enabled(rule) {
+            return;
+        }
+        let key = (universe, source_id.to_string());
+        let Some(last) = self.last_artnet_ts.insert(key, ts) else {
+            return;
+        };
+        let interval = ts - last;
+        if interval < 0.0 || interval >= self.artnet_min_interval_s {
+            return;
+        }
+        let detail = format!(
+            "{}, interval_ms={:.3}, min_ms={:.3}",
+            self.universe_key(universe),
+            interval * MS_PER_S,
+            self.artnet_min_interval_s * MS_PER_S
+        );
+        self.record_packet(observer, rule, detail, udp, Some(ts));
+    }
+
+    /// Track a DMX sequence number and report skipped values.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn check_sequence(
+        &mut self,
+        observer: &mut dyn AnalysisObserver,
+        protocol: Protocol,
+        universe: u16,
+        source_id: &str,
+        sequence: Option<u8>,
+        udp: &UdpPacket<'_>,
+        ts: Option<f64>,
+    ) {
+        let rule = match protocol {
+            Protocol::ArtNet => RuleId::ArtNetSeqGap,
+            Protocol::Sacn => RuleId::SacnSeqGap,
+            Protocol::Udp
+            | Protocol::Tcp
+            | Protocol::Osc
+            | Protocol::Citp
+            | Protocol::Ptp
+            | Protocol::Dhcp => {
+                return;
+            }
+        };
+        let Some(sequence) = sequence else {
+            return;
+        };
+        if !self.rules.is_enabled(rule) {
+            return;
+        }
+        let key = (protocol, universe, source_id.to_string());
+        let Some(last) = self.last_sequence.insert(key, sequence) else {
+            return;
+        };
+        let Some((expected, gap)) = sequence_gap(protocol, last, sequence) else {
+            return;
+        };
+        if gap < self.min_sequence_gap {
+            return;
+        }
+        let detail = format!(
+            "{}, expected={}, actual={}, gap={}",
+            self.universe_key(universe),
+            expected,
+            sequence,
+            gap
+        );
+        self.record_packet(observer, rule, detail, udp, ts);
+    }
+
+    /// Remember the source address and time of an sACN packet for its CID.
+    pub(crate) fn track_cid_address(&mut self, cid: &str, udp: &UdpPacket<'_>, ts: Option<f64>) {
+        if !self.rules.is_enabled(RuleId::SacnCidMultiIp)
+            && !self.rules.is_enabled(RuleId::SacnCidCollision)
+        {
+            return;
+        }
+        let span = self
+            .cid_addresses
+            .entry(cid.to_string())
+            .or_default()
+            .entry(udp.src_ip)
+            .or_insert(AddressSpan {
+                port: udp.src_port,
+                first_ts: ts,
+                last_ts: ts,
+                active: Vec::new(),
+            });
+        span.first_ts = min_ts(span.first_ts, ts);
+        span.last_ts = max_ts(span.last_ts, ts);
+        if let Some(ts) = ts {
+            match span.active.last_mut() {
+                Some((start, end))
+                    if ts >= *start - SOURCE_TIMEOUT_S && ts <= *end + SOURCE_TIMEOUT_S =>
+                {
+                    *start = start.min(ts);
+                    *end = end.max(ts);
+                }
+                _ => span.active.push((ts, ts)),
+            }
+        }
+    }
+
+    /// Report CIDs active from several IP addresses at the same time as
+    /// collisions, and CIDs that only moved between addresses as multi-IP
+    /// (end of capture).
+    pub(crate) fn check_cid_addresses(&mut self, observer: &mut dyn AnalysisObserver) {
+        let cids = std::mem::take(&mut self.cid_addresses);
+        for (cid, addresses) in cids {
+            if addresses.len() < 2 {
+                continue;
+            }
+            let ranges: Vec<String> = addresses
+                .iter()
+                .map(|(ip, span)| {
+                    format!(
+                        "{} {}..{}",
+                        ip,
+                        ts_to_rfc3339(span.first_ts).unwrap_or_else(|| "unknown".to_string()),
+                        ts_to_rfc3339(span.last_ts).unwrap_or_else(|| "unknown".to_string())
+                    )
+                })
+                .collect();
+            // Attribute the finding to the address that appeared last.
+            let latest = addresses.iter().max_by(|a, b| {
+                let first = |span: &AddressSpan| span.first_ts.unwrap_or(f64::NEG_INFINITY);
+                first(a.1).total_cmp(&first(b.1))
+            });
+            let detail = format!("cid={}, ips={}", cid, ranges.join(", "));
+            let example = |detail: String| match latest {
+                Some((ip, span)) => {
+                    format_violation_example(detail, Some((ip, span.port)), span.first_ts)
+                }
+                None => detail_example(detail),
+            };
+            let rule = if self.rules.is_enabled(RuleId::SacnCidCollision)
+                && addresses_overlap(&addresses)
+            {
+                RuleId::SacnCidCollision
+            } else {
+                RuleId::SacnCidMultiIp
+            };
+            self.record(observer, rule, example(detail));
+        }
+    }
+
+    /// Report sACN universes multicast to their IPv4 group that no receiver
+    /// joined by IGMP (end of capture). `joined` is `None` when the capture
+    /// holds no membership report, so nothing can be concluded.
+    pub(crate) fn check_subscribers(
+        &mut self,
+        observer: &mut dyn AnalysisObserver,
+        joined: Option<&BTreeSet<u16>>,
+    ) {
+        let universes = std::mem::take(&mut self.multicast_universes);
+        let Some(joined) = joined else {
+            return;
+        };
+        for (universe, (ip, port, ts)) in universes {
+            if joined.contains(&universe) {
+                continue;
+            }
+            let detail = format!(
+                "{}, group={}",
+                self.universe_key(universe),
+                sacn_multicast_group(universe, false)
+            );
+            let example = format_violation_example(detail, Some((&ip, port)), ts);
+            self.record(observer, RuleId::SacnNoSubscriber, example);
+        }
+    }
+
+    /// Report sACN sources whose universe discovery differs from the
+    /// universes they sent (end of capture).
+    pub(crate) fn check_universe_discovery(
+        &mut self,
+        observer: &mut dyn AnalysisObserver,
+        sources: &[SacnUniverseDiscovery],
+    ) {
+        for source in sources {
+            if source.undeclared.is_empty() && source.not_sent.is_empty() {
+                continue;
+            }
+            let list = |universes: &[u16]| {
+                universes
+                    .iter()
+                    .map(u16::to_string)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            };
+            let detail = format!(
+                "cid={}, undeclared={}, not_sent={}",
+                source.cid,
+                list(&source.undeclared),
+                list(&source.not_sent)
+            );
+            let example = ViolationExample {
+                source_ip: Some(source.source_ip.clone()),
+                ..detail_example(detail)
+            };
+            self.record(observer, RuleId::SacnDiscovery, example);
+        }
+    }
+
+    /// Report reserved sACN priorities and priority changes within a stream.
+    pub(crate) fn check_priority(
+        &mut self,
+        observer: &mut dyn AnalysisObserver,
+        universe: u16,
+        source_id: &str,
+        priority: u8,
+        udp: &UdpPacket<'_>,
+        ts: Option<f64>,
+    ) {
+        if priority > MAX_PRIORITY {
+            let detail = format!(
+                "{}, priority={}, max={}",
+                self.universe_key(universe),
+                priority,
+                MAX_PRIORITY
+            );
+            self.record_packet(observer, RuleId::SacnPriority, detail, udp, ts);
+        }
+        let key = (universe, source_id.to_string());
+        let Some(previous) = self.last_priority.insert(key, priority) else {
+            return;
+        };
+        if previous != priority {
+            let detail = format!(
+                "{}, previous={}, actual={}",
+                self.universe_key(universe),
+                previous,
+                priority
+            );
+            self.record_packet(observer, RuleId::SacnPriorityChange, detail, udp, ts);
+        }
+    }
+
+    /// Report Art-Net packets advertising a protocol revision older than
+    /// Art-Net 4, once per source and revision.
+    pub(crate) fn check_protocol_version(
+        &mut self,
+        observer: &mut dyn AnalysisObserver,
+        packet: &str,
+        version: u16,
+        udp: &UdpPacket<'_>,
+        ts: Option<f64>,
+    ) {
+        if version >= PROTOCOL_VERSION_MIN
+            || !self
+                .reported_protocol_versions
+                .insert((udp.src_ip.to_string(), version))
+        {
+            return;
+        }
+        let detail =
+            format!("packet={packet}, protocol_version={version}, min={PROTOCOL_VERSION_MIN}");
+        self.record_per_source(observer, RuleId::ArtNetProtVer, detail, udp, ts);
+    }
+
+    /// Report IPv4 header and UDP checksum mismatches, counted per source IP.
+    pub(crate) fn check_checksums(
+        &mut self,
+        observer: &mut dyn AnalysisObserver,
+        udp: &UdpPacket<'_>,
+        ts: Option<f64>,
+    ) {
+        let checks = [
+            (RuleId::UdpIpChecksum, udp.ip_checksum),
+            (RuleId::UdpChecksum, udp.udp_checksum),
+        ];
+        for (rule, mismatch) in checks {
+            let Some(mismatch) = mismatch else {
+                continue;
+            };
+            let detail = format!(
+                "expected=0x{:04x}, actual=0x{:04x}",
+                mismatch.expected, mismatch.actual
+            );
+            self.record_per_source(observer, rule, detail, udp, ts);
+        }
+    }
+
+    /// Record a packet violation and count it against the source IP.
+    fn record_per_source(
+        &mut self,
+        observer: &mut dyn AnalysisObserver,
+        rule: RuleId,
+        detail: String,
+        udp: &UdpPacket<'_>,
+        ts: Option<f64>,
+    ) {
+        if !self.rules.is_enabled(rule) {
+            return;
+        }
+        self.record_packet(observer, rule, detail, udp, ts);
+        let violation = self
+            .entries
+            .get_mut(&rule.protocol())
+            .and_then(|entry| entry.violations.iter_mut().find(|v| v.id == rule.code()));
+        if let Some(violation) = violation {
+            *violation.sources.entry(udp.src_ip.to_string()).or_default() += 1;
+        }
+    }
+
+    /// Report the custom rules matched by a packet.
+    pub(crate) fn check_custom_rules(&mut self, fields: &PacketFields, ts: Option<f64>) {
+        let custom_rules = self.custom_rules;
+        for rule in custom_rules.iter().filter(|rule| rule.matches(fields)) {
+            let detail = format!(
+                "proto={}, dst={}:{}",
+                fields.proto, fields.dst_ip, fields.dst_port
+            );
+            let mut example =
+                format_violation_example(detail, Some((&fields.src_ip, fields.src_port)), ts);
+            example.vlan = self.packet_vlan;
+            self.aggregate(
+                fields.proto,
+                rule.id(),
+                rule.severity(),
+                &format!("Custom rule: {}", rule.condition()),
+                example,
+            );
+        }
+    }
+
+    /// Record a violation observed on a decoded UDP packet.
+    pub(crate) fn record_packet(
+        &mut self,
+        observer: &mut dyn AnalysisObserver,
+        rule: RuleId,
+        detail: String,
+        udp: &UdpPacket<'_>,
+        ts: Option<f64>,
+    ) {
+        if !self.rules.is_enabled(rule) {
+            return;
+        }
+        let example = format_violation_example(detail, Some((&udp.src_ip, udp.src_port)), ts);
+        self.record(observer, rule, example);
+    }
+
+    /// Record a decoding error of a UDP payload, attaching the payload bytes
+    /// from `offset` when configured.
+    pub(crate) fn record_payload_error(
+        &mut self,
+        observer: &mut dyn AnalysisObserver,
+        rule: RuleId,
+        detail: String,
+        udp: &UdpPacket<'_>,
+        offset: usize,
+        ts: Option<f64>,
+    ) {
+        if !self.rules.is_enabled(rule) {
+            return;
+        }
+        let mut example = format_violation_example(detail, Some((&udp.src_ip, udp.src_port)), ts);
+        example.bytes = ExampleBytes::from_payload(udp.payload, offset, self.example_bytes);
+        self.record(observer, rule, example);
+    }
+
+    /// Record a violation with a preformatted example.
+    pub(crate) fn record(
+        &mut self,
+        observer: &mut dyn AnalysisObserver,
+        rule: RuleId,
+        mut example: ViolationExample,
+    ) {
+        if !self.rules.is_enabled(rule) {
+            return;
+        }
+        example.vlan = example.vlan.or(self.packet_vlan);
+        let severity = self.rules.severity(rule);
+        observer.on_violation(&ViolationEvent {
+            rule,
+            severity,
+            example: &example,
+        });
+        self.aggregate(
+            rule.protocol(),
+            rule.code(),
+            severity,
+            rule.message(),
+            example,
+        );
+    }
+
+    /// Count one occurrence against the current packet and the report entry.
+    fn aggregate(
+        &mut self,
+        protocol: Protocol,
+        id: &str,
+        severity: Severity,
+        message: &str,
+        example: ViolationExample,
+    ) {
+        let weight = self.packet_penalty.entry(protocol).or_default();
+        *weight = weight.max(severity_weight(severity));
+        let max_examples = self.max_examples;
+        let entry = self
+            .entries
+            .entry(protocol)
+            .or_insert_with(|| ComplianceSummary {
+                protocol,
+                compliance_percentage: 100.0,
+                violations: Vec::new(),
+            });
+
+        if let Some(existing) = entry.violations.iter_mut().find(|v| v.id == id) {
+            existing.count += 1;
+            if existing.examples.len() < max_examples && !existing.examples.contains(&example) {
+                existing.examples.push(example);
+            }
+            return;
+        }
+
+        entry.violations.push(Violation {
+            id: id.to_string(),
+            severity,
+            message: message.to_string(),
+            count: 1,
+            examples: if max_examples > 0 {
+                vec![example]
+            } else {
+                Vec::new()
+            },
+            sources: BTreeMap::new(),
+            spec_reference: id
+                .parse::<RuleId>()
+                .ok()
+                .map(|rule| rule.spec_reference().to_string()),
+        });
+    }
+
+    /// Sorted compliance entries for the report.
+    pub(crate) fn finalize(self) -> Vec<ComplianceSummary> {
+        if self.entries.is_empty() {
+            return Vec::new();
+        }
+        let mut entries: Vec<ComplianceSummary> = self.entries.into_values().collect();
+        for entry in &mut entries {
+            entry.compliance_percentage = compliance_percentage(
+                self.examined.get(&entry.protocol).copied().unwrap_or(0),
+                self.penalty.get(&entry.protocol).copied().unwrap_or(0.0),
+            );
+            entry
+                .violations
+                .sort_by(|a, b| a.severity.cmp(&b.severity).then_with(|| a.id.cmp(&b.id)));
+            for violation in &mut entry.violations {
+                violation.examples.sort();
+            }
+        }
+        entries.sort_by_key(|entry| entry.protocol);
+        entries
+    }
+}
+
+/// Expected sequence number and number of skipped values, or `None` when the
+/// packet is in order, duplicated, or reordered.
+///
+/// sACN counts 0..=255; Art-Net counts 1..=255 (0 disables sequencing and
+/// never reaches this point).
+pub(crate) fn sequence_gap(protocol: Protocol, last: u8, sequence: u8) -> Option<(u8, u8)> {
+    let (expected, gap) = match protocol {
+        Protocol::ArtNet => {
+            let expected = if last == u8::MAX { 1 } else { last + 1 };
+            let span = u16::from(u8::MAX);
+            let gap = (u16::from(sequence) + span - u16::from(expected)) % span;
+            (expected, u8::try_from(gap).ok()?)
+        }
+        _ => {
+            let expected = last.wrapping_add(1);
+            (expected, sequence.wrapping_sub(expected))
+        }
+    };
+    (gap > 0 && gap <= MAX_SEQUENCE_GAP).then_some((expected, gap))
+}
+
+fn severity_weight(severity: Severity) -> f64 {
+    match severity {
+        Severity::Error => ERROR_WEIGHT,
+        Severity::Warning => WARNING_WEIGHT,
+        Severity::Info => INFO_WEIGHT,
+    }
+}
+
+/// Weighted share of compliant packets, rounded to two decimals.
+fn compliance_percentage(examined: u64, penalty: f64) -> f64 {
+    if examined == 0 {
+        return PERCENT;
+    }
+    let share = (1.0 - penalty / examined as f64).clamp(0.0, 1.0);
+    (share * PERCENT * PERCENTAGE_ROUNDING).round() / PERCENTAGE_ROUNDING
+}
+
+fn min_ts(current: Option<f64>, ts: Option<f64>) -> Option<f64> {
+    match (current, ts) {
+        (Some(current), Some(ts)) => Some(current.min(ts)),
+        (current, ts) => current.or(ts),
+    }
+}
+
+fn max_ts(current: Option<f64>, ts: Option<f64>) -> Option<f64> {
+    match (current, ts) {
+        (Some(current), Some(ts)) => Some(current.max(ts)),
+        (current, ts) => current.or(ts),
+    }
+}
+
+/// Multicast group of an sACN universe: 239.255.H.L, or ff18::8300:U over IPv6.
+fn sacn_multicast_group(universe: u16, ipv6: bool) -> IpAddr {
+    let [high, low] = universe.to_be_bytes();
+    if ipv6 {
+        IpAddr::V6(Ipv6Addr::new(0xff18, 0, 0, 0, 0, 0, 0x8300, universe))
+    } else {
+        IpAddr::V4(Ipv4Addr::new(239, 255, high, low))
+    }
+}
+
+/// True when two addresses of one CID were active at the same time, an
+/// address being active over each run of its packets less than the source
+/// loss timeout apart. Packets without timestamps cannot be ordered and
+/// never overlap.
+fn addresses_overlap(addresses: &BTreeMap<IpAddr, AddressSpan>) -> bool {
+    let mut runs: Vec<(f64, f64, &IpAddr)> = addresses
+        .iter()
+        .flat_map(|(ip, span)| {
+            span.active
+                .iter()
+                .map(move |(start, end)| (*start, *end, ip))
+        })
+        .collect();
+    runs.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut ends: BTreeMap<&IpAddr, f64> = BTreeMap::new();
+    for (start, end, ip) in runs {
+        if ends
+            .iter()
+            .any(|(other, other_end)| *other != ip && start <= *other_end)
+        {
+            return true;
+        }
+        let last = ends.entry(ip).or_insert(end);
+        *last = last.max(end);
+    }
+    false
+}
+
+/// Limited broadcast (255.255.255.255) or a directed broadcast with an
+/// all-ones host byte (x.x.x.255, as used by Art-Net 2.255.255.255 and
+/// 10.255.255.255). Subnet masks are not visible in captures.
+pub(crate) fn is_broadcast(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => ip.is_broadcast() || ip.octets()[3] == u8::MAX,
+        IpAddr::V6(_) => false,
+    }
+}
+
+/// Example detail of `LS-ARTNET-ADDRESS`: the addressed node and what the
+/// ArtAddress reprograms (switch values with bit 7 set, `reset` for 0, names,
+/// and a non-zero command).
+pub(crate) fn artaddress_detail(target: &IpAddr, address: &ArtAddress) -> String {
+    let switch = |name: String, value: u8| match (value, ArtAddress::programmed(value)) {
+        (0, _) => Some(format!("{name}=reset")),
+        (_, Some(value)) => Some(format!("{name}={value}")),
+        (_, None) => None,
+    };
+    let mut changes: Vec<String> = Vec::new();
+    changes.extend(switch("net".to_string(), address.net_switch));
+    changes.extend(switch("sub_net".to_string(), address.sub_switch));
+    for (port, value) in address.sw_in.iter().enumerate() {
+        changes.extend(switch(format!("in{}", port + 1), *value));
+    }
+    for (port, value) in address.sw_out.iter().enumerate() {
+        changes.extend(switch(format!("out{}", port + 1), *value));
+    }
+    if let Some(name) = &address.short_name {
+        changes.push(format!("short_name={name:?}"));
+    }
+    if let Some(name) = &address.long_name {
+        changes.push(format!("long_name={name:?}"));
+    }
+    if address.command != 0 {
+        changes.push(format!("command=0x{:02x}", address.command));
+    }
+    let changes = if changes.is_empty() {
+        "no change".to_string()
+    } else {
+        changes.join(", ")
+    };
+    format!(
+        "target={}, bind_index={}, {}",
+        target, address.bind_index, changes
+    )
+}
+
+/// Rule and example detail for an Art-Net decoding error.
+pub(crate) fn artnet_violation(err: &ArtNetError) -> (RuleId, String) {
+    match err {
+        ArtNetError::InvalidUniverseId { value } => {
+            (RuleId::ArtNetUniverseId, format!("value={}", value))
+        }
+        ArtNetError::InvalidDmxLength { len } => (RuleId::ArtNetLength, format!("length={}", len)),
+        ArtNetError::TooShort { needed, actual } => (
+            RuleId::ArtNetTooShort,
+            format!("needed={}, actual={}", needed, actual),
+        ),
+        ArtNetError::UnsupportedOpCode { opcode } => {
+            (RuleId::ArtNetOpcode, format!("opcode=0x{:04x}", opcode))
+        }
+    }
+}
+
+/// Rule and example detail for an sACN decoding error.
+pub(crate) fn sacn_violation(err: &SacnError) -> (RuleId, String) {
+    match err {
+        SacnError::InvalidStartCode { value } => {
+            (RuleId::SacnStartCode, format!("value={}", value))
+        }
+        SacnError::InvalidPropertyValueCount { count } => {
+            (RuleId::SacnPropertyCount, format!("count={}", count))
+        }
+        SacnError::InvalidDmxLength { length } => {
+            (RuleId::SacnDmxLength, format!("length={}", length))
+        }
+        SacnError::TooShort { needed, actual } => (
+            RuleId::SacnTooShort,
+            format!("needed={}, actual={}", needed, actual),
+        ),
+        SacnError::InvalidAcnPid => (RuleId::SacnAcnPid, "acn_pid=invalid".to_string()),
+        SacnError::InvalidRootVector { value } => {
+            (RuleId::SacnRootVector, format!("value=0x{:08x}", value))
+        }
+        SacnError::InvalidFramingVector { value } => {
+            (RuleId::SacnFramingVector, format!("value=0x{:08x}", value))
+        }
+        SacnError::InvalidDmpVector { value } => {
+            (RuleId::SacnDmpVector, format!("value=0x{:02x}", value))
+        }
+    }
+}
+
+/// Rule and example for a UDP decoding error (no UDP source port is known).
+pub(crate) fn udp_violation(err: UdpError) -> (RuleId, String) {
+    match err {
+        UdpError::Slice(message) => (RuleId::UdpSlice, message),
+        UdpError::MissingNetworkLayer => (
+            RuleId::UdpMissingNetwork,
+            "missing network layer".to_string(),
+        ),
+        UdpError::MissingIpPayload => (RuleId::UdpMissingPayload, "missing IP payload".to_string()),
+        UdpError::TooShort { needed, actual } => (
+            RuleId::UdpTooShort,
+            format!("needed={}, actual={}", needed, actual),
+        ),
+        UdpError::Fragmented { src, dst, ip_len } => (
+            RuleId::UdpFragmented,
+            format!("src={}, dst={}, ip_len={}", src, dst, ip_len),
+        ),
+    }
+}
+
+/// Fields of a UDP datagram for custom rules, before any DMX decoding.
+pub(crate) fn packet_fields(udp: &UdpPacket<'_>) -> PacketFields {
+    PacketFields {
+        proto: Protocol::Udp,
+        src_ip: udp.src_ip,
+        dst_ip: udp.dst_ip,
+        src_port: udp.src_port,
+        dst_port: udp.dst_port,
+        ttl: udp.ttl,
+        ip_len: udp.ip_len,
+        payload_len: udp.payload.len(),
+        universe: None,
+        sequence: None,
+        priority: None,
+    }
+}
+
+/// Example detail for a packet sent outside the protocol port.
+pub(crate) fn port_detail(udp: &UdpPacket<'_>) -> String {
+    format!(
+        "ports={}:{}->{}:{}",
+        udp.src_ip, udp.src_port, udp.dst_ip, udp.dst_port
+    )
+}
+
+fn format_violation_example(
+    detail: String,
+    source: Option<(&IpAddr, u16)>,
+    ts: Option<f64>,
+) -> ViolationExample {
+    let Some((ip, port)) = source else {
+        return detail_example(detail);
+    };
+    ViolationExample {
+        source_ip: Some(ip.to_string()),
+        source_port: Some(port),
+        timestamp: ts_to_rfc3339(ts),
+        detail: detail.trim().to_string(),
+        bytes: None,
+        vlan: None,
+    }
+}
+
+/// Example without a known source (e.g. frames that never decoded as UDP).
+pub(crate) fn detail_example(detail: String) -> ViolationExample {
+    ViolationExample {
+        detail: detail.trim().to_string(),
+        ..ViolationExample::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use super::{
+        RulesEngine, artaddress_detail, compliance_percentage, detail_example, is_broadcast,
+        sequence_gap,
+    };
+    use crate::analysis::config::AnalyzerConfig;
+    use crate::analysis::udp::UdpPacket;
+    use crate::rules::RuleId;
+    use crate::{ArtAddress, Protocol, Severity};
+    use liveshark_proto::ChecksumMismatch;
+
+    #[test]
+    fn compliance_aggregates_by_protocol_and_id() {
+        let config = AnalyzerConfig::default();
+        let mut compliance = RulesEngine::new(&config);
+
+        compliance.record(
+            &mut (),
+            RuleId::ArtNetUniverseId,
+            detail_example("value=32768".to_string()),
+        );
+        compliance.record(
+            &mut (),
+            RuleId::ArtNetUniverseId,
+            detail_example("value=40000".to_string()),
+        );
+        compliance.record(
+            &mut (),
+            RuleId::SacnStartCode,
+            detail_example("value=1".to_string()),
+        );
+
+        let artnet = compliance
+            .entries
+            .get(&Protocol::ArtNet)
+            .expect("artnet compliance");
+        assert_eq!(artnet.violations.len(), 1);
+        let violation = &artnet.violations[0];
+        assert_eq!(violation.count, 2);
+        assert_eq!(violation.examples.len(), 2);
+
+        let sacn = compliance
+            .entries
+            .get(&Protocol::Sacn)
+            .expect("sacn compliance");
+        assert_eq!(sacn.violations.len(), 1);
+        assert_eq!(sacn.violations[0].count, 1);
+        assert_eq!(
+            sacn.violations[0].spec_reference.as_deref(),
+            Some(RuleId::SacnStartCode.spec_reference())
+        );
+    }
+
+    #[test]
+    fn compliance_examples_are_deduplicated_and_capped() {
+        let config = AnalyzerConfig::default();
+        let mut compliance = RulesEngine::new(&config);
+
+        for example in ["slice-c", "slice-a", "slice-b", "slice-a", "slice-d"] {
+            compliance.record(
+                &mut (),
+                RuleId::UdpSlice,
+                detail_example(example.to_string()),
+            );
+        }
+
+        let entries = compliance.finalize();
+        let udp = &entries[0];
+        let violation = &udp.violations[0];
+        assert_eq!(violation.count, 5);
+        assert_eq!(violation.examples.len(), 3);
+        let details: Vec<&str> = violation
+            .examples
+            .iter()
+            .map(|example| example.detail.as_str())
+            .collect();
+        assert_eq!(details, vec!["slice-a", "slice-b", "slice-c"]);
+        assert_eq!(
+            violation.examples[0].to_string(),
+            "source unknown @ unknown; slice-a"
+        );
+    }
+
+    #[test]
+    fn compliance_entries_are_sorted_by_protocol_and_id() {
+        let config = AnalyzerConfig::default();
+        let mut compliance = RulesEngine::new(&config);
+
+        compliance.record(
+            &mut (),
+            RuleId::SacnStartCode,
+            detail_example("value=1".to_string()),
+        );
+        compliance.record(
+            &mut (),
+            RuleId::ArtNetUniverseId,
+            detail_example("value=32768".to_string()),
+        );
+        compliance.record(
+            &mut (),
+            RuleId::ArtNetLength,
+            detail_example("length=0".to_string()),
+        );
+
+        let entries = compliance.finalize();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].protocol, Protocol::ArtNet);
+        assert_eq!(entries[1].protocol, Protocol::Sacn);
+        assert_eq!(entries[0].violations.len(), 2);
+        assert_eq!(entries[0].violations[0].id, "LS-ARTNET-LENGTH");
+        assert_eq!(entries[0].violations[1].id, "LS-ARTNET-UNIVERSE-ID");
+    }
+
+    #[test]
+    fn disabled_rules_are_skipped_and_severity_is_overridden() {
+        let mut config = AnalyzerConfig::default();
+        config.rules.set_enabled(RuleId::ArtNetLength, false);
+        config
+            .rules
+            .set_severity(RuleId::SacnStartCode, Severity::Info);
+        let mut compliance = RulesEngine::new(&config);
+
+        compliance.record(
+            &mut (),
+            RuleId::ArtNetLength,
+            detail_example("length=0".to_string()),
+        );
+        compliance.record(
+            &mut (),
+            RuleId::SacnStartCode,
+            detail_example("value=1".to_string()),
+        );
+
+        let entries = compliance.finalize();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].protocol, Protocol::Sacn);
+        assert_eq!(entries[0].violations[0].severity, Severity::Info);
+    }
+
+    #[test]
+    fn sequence_gaps_skip_duplicates_and_reorders() {
+        assert_eq!(sequence_gap(Protocol::Sacn, 10, 11), None);
+        assert_eq!(sequence_gap(Protocol::Sacn, 10, 14), Some((11, 3)));
+        assert_eq!(sequence_gap(Protocol::Sacn, 255, 2), Some((0, 2)));
+        assert_eq!(sequence_gap(Protocol::Sacn, 10, 10), None);
+        assert_eq!(sequence_gap(Protocol::Sacn, 10, 9), None);
+
+        assert_eq!(sequence_gap(Protocol::ArtNet, 255, 1), None);
+        assert_eq!(sequence_gap(Protocol::ArtNet, 254, 2), Some((255, 2)));
+        assert_eq!(sequence_gap(Protocol::ArtNet, 5, 5), None);
+        assert_eq!(sequence_gap(Protocol::ArtNet, 5, 4), None);
+    }
+
+    #[test]
+    fn broadcast_storm_is_recorded_once_per_episode() {
+        let config = AnalyzerConfig {
+            max_broadcast_pps: 50.0,
+            ..AnalyzerConfig::default()
+        };
+        let mut compliance = RulesEngine::new(&config);
+        let udp = UdpPacket {
+            src_ip: "10.0.0.1".parse().unwrap(),
+            src_port: 6454,
+            dst_ip: "10.255.255.255".parse().unwrap(),
+            dst_port: 6454,
+            payload: &[],
+            ip_len: 28,
+            ttl: 64,
+            ip_checksum: None,
+            udp_checksum: None,
+            vlan: None,
+            outer_vlan: None,
+            wifi_retry: None,
+        };
+        // 100 pps for two seconds, a pause, then another burst.
+        for start in [0.0, 10.0] {
+            for step in 0..200 {
+                compliance.check_broadcast(&mut (), &udp, Some(start + f64::from(step) * 0.01));
+            }
+        }
+
+        let entries = compliance.finalize();
+        let violation = &entries[0].violations[0];
+        assert_eq!(violation.id, "LS-UDP-BROADCAST-STORM");
+        assert_eq!(violation.count, 2);
+        assert!(
+            violation.examples[0]
+                .detail
+                .contains("dst=10.255.255.255, pps=51.0, max_pps=50.0")
+        );
+    }
+
+    #[test]
+    fn oversized_datagrams_are_checked_on_lighting_ports_only() {
+        let config = AnalyzerConfig::default();
+        let mut compliance = RulesEngine::new(&config);
+        let mut udp = UdpPacket {
+            src_ip: "10.0.0.1".parse().unwrap(),
+            src_port: 50000,
+            dst_ip: "239.255.0.1".parse().unwrap(),
+            dst_port: 5568,
+            payload: &[],
+            ip_len: 1500,
+            ttl: 64,
+            ip_checksum: None,
+            udp_checksum: None,
+            vlan: None,
+            outer_vlan: None,
+            wifi_retry: None,
+        };
+        compliance.check_datagram_size(&mut (), &udp, Some(0.0));
+        udp.ip_len = 1600;
+        compliance.check_datagram_size(&mut (), &udp, Some(0.0));
+        udp.dst_port = 9000;
+        compliance.check_datagram_size(&mut (), &udp, Some(0.0));
+
+        let entries = compliance.finalize();
+        let violation = &entries[0].violations[0];
+        assert_eq!(violation.id, "LS-UDP-OVERSIZED");
+        assert_eq!(violation.count, 1);
+        assert!(
+            violation.examples[0]
+                .detail
+                .ends_with("ip_len=1600, max_len=1500")
+        );
+    }
+
+    #[test]
+    fn multicast_ttl_is_reported_once_per_source_and_value() {
+        let config = AnalyzerConfig {
+            min_multicast_ttl: 2,
+            ..AnalyzerConfig::default()
+        };
+        let mut compliance = RulesEngine::new(&config);
+        let mut udp = UdpPacket {
+            src_ip: "10.0.0.1".parse().unwrap(),
+            src_port: 5568,
+            dst_ip: "239.255.0.1".parse().unwrap(),
+            dst_port: 5568,
+            payload: &[],
+            ip_len: 28,
+            ttl: 1,
+            ip_checksum: None,
+            udp_checksum: None,
+            vlan: None,
+            outer_vlan: None,
+            wifi_retry: None,
+        };
+        compliance.check_multicast_ttl(&mut (), "sacn:a", &udp, Some(0.0));
+        compliance.check_multicast_ttl(&mut (), "sacn:a", &udp, Some(1.0));
+        compliance.check_multicast_ttl(&mut (), "sacn:b", &udp, Some(1.0));
+        udp.ttl = 255;
+        compliance.check_multicast_ttl(&mut (), "sacn:a", &udp, Some(2.0));
+        udp.ttl = 16;
+        compliance.check_multicast_ttl(&mut (), "sacn:a", &udp, Some(3.0));
+        udp.ttl = 1;
+        udp.dst_ip = "10.0.0.2".parse().unwrap();
+        compliance.check_multicast_ttl(&mut (), "sacn:c", &udp, Some(4.0));
+
+        let entries = compliance.finalize();
+        let violation = &entries[0].violations[0];
+        assert_eq!(violation.id, "LS-SACN-MULTICAST-TTL");
+        assert_eq!(violation.count, 3);
+        assert!(
+            violation.examples[0]
+                .detail
+                .ends_with("dst=239.255.0.1, ttl=1, accepted=2..=64")
+        );
+    }
+
+    #[test]
+    fn checksum_mismatches_are_counted_per_source() {
+        let config = AnalyzerConfig::default();
+        let mut compliance = RulesEngine::new(&config);
+        let mismatch = Some(ChecksumMismatch {
+            expected: 0x1234,
+            actual: 0xabcd,
+        });
+        let mut udp = UdpPacket {
+            src_ip: "10.0.0.1".parse().unwrap(),
+            src_port: 6454,
+            dst_ip: "10.0.0.255".parse().unwrap(),
+            dst_port: 6454,
+            payload: &[],
+            ip_len: 28,
+            ttl: 64,
+            ip_checksum: None,
+            udp_checksum: mismatch,
+            vlan: None,
+            outer_vlan: None,
+            wifi_retry: None,
+        };
+        compliance.check_checksums(&mut (), &udp, Some(0.0));
+        compliance.check_checksums(&mut (), &udp, Some(1.0));
+        udp.src_ip = "10.0.0.2".parse().unwrap();
+        udp.ip_checksum = mismatch;
+        compliance.check_checksums(&mut (), &udp, Some(2.0));
+
+        let entries = compliance.finalize();
+        let violations = &entries[0].violations;
+        let udp_checksum = violations
+            .iter()
+            .find(|v| v.id == "LS-UDP-CHECKSUM")
+            .unwrap();
+        assert_eq!(udp_checksum.count, 3);
+        assert_eq!(udp_checksum.sources["10.0.0.1"], 2);
+        assert_eq!(udp_checksum.sources["10.0.0.2"], 1);
+        assert!(
+            udp_checksum.examples[0]
+                .detail
+                .ends_with("expected=0x1234, actual=0xabcd")
+        );
+        let ip_checksum = violations
+            .iter()
+            .find(|v| v.id == "LS-UDP-IP-CHECKSUM")
+            .unwrap();
+        assert_eq!(ip_checksum.count, 1);
+        assert_eq!(ip_checksum.sources.len(), 1);
+    }
+
+    #[test]
+    fn sacn_priority_range_and_changes_are_reported() {
+        let config = AnalyzerConfig::default();
+        let mut compliance = RulesEngine::new(&config);
+        let udp = UdpPacket {
+            src_ip: "10.0.0.1".parse().unwrap(),
+            src_port: 5568,
+            dst_ip: "239.255.0.1".parse().unwrap(),
+            dst_port: 5568,
+            payload: &[],
+            ip_len: 28,
+            ttl: 1,
+            ip_checksum: None,
+            udp_checksum: None,
+            vlan: None,
+            outer_vlan: None,
+            wifi_retry: None,
+        };
+        for (universe, priority) in [(1, 100), (1, 100), (2, 150), (1, 201), (1, 201)] {
+            compliance.check_priority(&mut (), universe, "sacn:a", priority, &udp, None);
+        }
+
+        let entries = compliance.finalize();
+        let violations = &entries[0].violations;
+        let reserved = violations
+            .iter()
+            .find(|v| v.id == "LS-SACN-PRIORITY")
+            .unwrap();
+        assert_eq!(reserved.count, 2);
+        assert!(
+            reserved.examples[0]
+                .detail
+                .ends_with("universe=1, priority=201, max=200")
+        );
+        let change = violations
+            .iter()
+            .find(|v| v.id == "LS-SACN-PRIORITY-CHANGE")
+            .unwrap();
+        assert_eq!(change.count, 1);
+        assert!(
+            change.examples[0]
+                .detail
+                .ends_with("universe=1, previous=100, actual=201")
+        );
+    }
+
+    #[test]
+    fn cid_sent_from_several_ips_is_reported_with_time_ranges() {
+        let config = AnalyzerConfig::default();
+        let mut compliance = RulesEngine::new(&config);
+        let mut udp = UdpPacket {
+            src_ip: "10.0.0.1".parse().unwrap(),
+            src_port: 5568,
+            dst_ip: "239.255.0.1".parse().unwrap(),
+            dst_port: 5568,
+            payload: &[],
+            ip_len: 28,
+            ttl: 1,
+            ip_checksum: None,
+            udp_checksum: None,
+            vlan: None,
+            outer_vlan: None,
+            wifi_retry: None,
+        };
+        compliance.track_cid_address("aa", &udp, Some(1.0));
+        compliance.track_cid_address("bb", &udp, Some(1.0));
+        compliance.track_cid_address("aa", &udp, Some(2.0));
+        udp.src_ip = "10.0.0.2".parse().unwrap();
+        compliance.track_cid_address("aa", &udp, Some(5.0));
+        compliance.check_cid_addresses(&mut ());
+
+        let entries = compliance.finalize();
+        let violation = entries[0]
+            .violations
+            .iter()
+            .find(|v| v.id == "LS-SACN-CID-MULTI-IP")
+            .unwrap();
+        assert_eq!(violation.count, 1);
+        assert_eq!(
+            violation.examples[0].to_string(),
+            "source 10.0.0.2:5568 @ 1970-01-01T00:00:05Z; cid=aa, \
+             ips=10.0.0.1 1970-01-01T00:00:01Z..1970-01-01T00:00:02Z, \
+             10.0.0.2 1970-01-01T00:00:05Z..1970-01-01T00:00:05Z"
+        );
+    }
+
+    #[test]
+    fn cid_active_from_several_ips_at_once_is_a_collision() {
+        let config = AnalyzerConfig::default();
+        let mut compliance = RulesEngine::new(&config);
+        let mut udp = UdpPacket {
+            src_ip: "10.0.0.1".parse().unwrap(),
+            src_port: 5568,
+            dst_ip: "239.255.0.1".parse().unwrap(),
+            dst_port: 5568,
+            payload: &[],
+            ip_len: 28,
+            ttl: 1,
+            ip_checksum: None,
+            udp_checksum: None,
+            vlan: None,
+            outer_vlan: None,
+            wifi_retry: None,
+        };
+        let main: IpAddr = "10.0.0.1".parse().unwrap();
+        let backup: IpAddr = "10.0.0.2".parse().unwrap();
+        let mut send = |cid: &str, ip: IpAddr, from: u32, to: u32| {
+            udp.src_ip = ip;
+            for second in from..=to {
+                compliance.track_cid_address(cid, &udp, Some(f64::from(second)));
+            }
+        };
+        // "aa" moves from one IP to another; "bb" is cloned on two consoles;
+        // "cc" fails over to the backup and back, never sending from both.
+        send("aa", main, 1, 2);
+        send("aa", backup, 3, 3);
+        send("bb", main, 1, 4);
+        send("bb", backup, 2, 2);
+        send("cc", main, 0, 10);
+        send("cc", backup, 20, 30);
+        send("cc", main, 40, 50);
+        compliance.check_cid_addresses(&mut ());
+
+        let entries = compliance.finalize();
+        let collision = entries[0]
+            .violations
+            .iter()
+            .find(|v| v.id == "LS-SACN-CID-COLLISION")
+            .unwrap();
+        assert_eq!(collision.severity, Severity::Error);
+        assert_eq!(collision.count, 1);
+        assert!(collision.examples[0].to_string().contains("cid=bb, "));
+        let multi_ip = entries[0]
+            .violations
+            .iter()
+            .find(|v| v.id == "LS-SACN-CID-MULTI-IP")
+            .unwrap();
+        assert_eq!(multi_ip.count, 2);
+        assert!(
+            multi_ip
+                .examples
+                .iter()
+                .all(|example| !example.to_string().contains("cid=bb, "))
+        );
+    }
+
+    #[test]
+    fn universe_rate_is_checked_per_protocol_and_universe() {
+        let config = AnalyzerConfig {
+            max_universe_fps: 60.0,
+            fps_window_s: 1.0,
+            ..AnalyzerConfig::default()
+        };
+        let mut compliance = RulesEngine::new(&config);
+        let udp = UdpPacket {
+            src_ip: "10.0.0.1".parse().unwrap(),
+            src_port: 5568,
+            dst_ip: "239.255.0.1".parse().unwrap(),
+            dst_port: 5568,
+            payload: &[],
+            ip_len: 28,
+            ttl: 1,
+            ip_checksum: None,
+            udp_checksum: None,
+            vlan: None,
+            outer_vlan: None,
+            wifi_retry: None,
+        };
+        // 100 fps on sACN universe 1 and 40 fps on universe 2 for two seconds.
+        for step in 0..200 {
+            let ts = f64::from(step) * 0.01;
+            compliance.check_universe_rate(&mut (), Protocol::Sacn, 1, &udp, Some(ts));
+            if step % 5 == 0 {
+                compliance.check_universe_rate(&mut (), Protocol::Sacn, 2, &udp, Some(ts));
+            }
+        }
+
+        let entries = compliance.finalize();
+        let violations = &entries[0].violations;
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].id, "LS-SACN-UNIVERSE-RATE");
+        assert_eq!(violations[0].count, 1);
+        assert!(
+            violations[0].examples[0]
+                .detail
+                .ends_with("universe=1, fps=61.0, max_fps=60.0")
+        );
+    }
+
+    #[test]
+    fn sacn_source_rate_is_reported_with_its_peak() {
+        let config = AnalyzerConfig {
+            fps_window_s: 1.0,
+            ..AnalyzerConfig::default()
+        };
+        let mut compliance = RulesEngine::new(&config);
+        let udp = UdpPacket {
+            src_ip: "10.0.0.1".parse().unwrap(),
+            src_port: 5568,
+            dst_ip: "239.255.0.1".parse().unwrap(),
+            dst_port: 5568,
+            payload: &[],
+            ip_len: 28,
+            ttl: 1,
+            ip_checksum: None,
+            udp_checksum: None,
+            vlan: None,
+            outer_vlan: None,
+            wifi_retry: None,
+        };
+        // "a" sends 50 fps then 40 fps, "b" a steady 44 fps, "c" 100 fps to the end.
+        for step in 0..100 {
+            compliance.check_source_rate(&mut (), 1, "a", &udp, Some(f64::from(step) / 50.0));
+        }
+        for step in 1..=80 {
+            compliance.check_source_rate(&mut (), 1, "a", &udp, Some(2.0 + f64::from(step) / 40.0));
+        }
+        for step in 0..176 {
+            compliance.check_source_rate(&mut (), 2, "b", &udp, Some(f64::from(step) / 44.0));
+        }
+        for step in 0..200 {
+            compliance.check_source_rate(&mut (), 3, "c", &udp, Some(f64::from(step) / 100.0));
+        }
+        compliance.check_source_rates(&mut ());
+
+        let entries = compliance.finalize();
+        let violations = &entries[0].violations;
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].id, "LS-SACN-RATE");
+        assert_eq!(violations[0].count, 2);
+        let details: Vec<&str> = violations[0]
+            .examples
+            .iter()
+            .map(|example| example.detail.as_str())
+            .collect();
+        assert_eq!(
+            details,
+            vec![
+                "universe=3, source=c, peak_fps=100.0, max_fps=44.0",
+                "universe=1, source=a, peak_fps=50.0, max_fps=44.0",
+            ]
+        );
+    }
+
+    #[test]
+    fn compliance_percentage_weights_worst_violation_per_packet() {
+        let config = AnalyzerConfig::default();
+        let mut compliance = RulesEngine::new(&config);
+        // Packet 1: error and warning; packet 2: warning; packets 3-4: clean.
+        compliance.examine(Protocol::Sacn);
+        compliance.record(
+            &mut (),
+            RuleId::SacnStartCode,
+            detail_example("value=1".to_string()),
+        );
+        compliance.record(
+            &mut (),
+            RuleId::SacnPort,
+            detail_example("ports".to_string()),
+        );
+        compliance.end_packet();
+        compliance.examine(Protocol::Sacn);
+        compliance.record(
+            &mut (),
+            RuleId::SacnPort,
+            detail_example("ports".to_string()),
+        );
+        compliance.end_packet();
+        for _ in 0..2 {
+            compliance.examine(Protocol::Sacn);
+            compliance.end_packet();
+        }
+
+        let entries = compliance.finalize();
+        assert_eq!(entries[0].compliance_percentage, 62.5);
+
+        assert_eq!(compliance_percentage(0, 0.0), 100.0);
+        assert_eq!(compliance_percentage(3, 1.0), 66.67);
+        assert_eq!(compliance_percentage(1, 2.0), 0.0);
+    }
+
+    #[test]
+    fn broadcast_addresses_are_detected() {
+        let broadcast = |ip: &str| is_broadcast(&ip.parse::<IpAddr>().unwrap());
+        assert!(broadcast("255.255.255.255"));
+        assert!(broadcast("2.255.255.255"));
+        assert!(broadcast("192.168.1.255"));
+        assert!(!broadcast("192.168.1.10"));
+        assert!(!broadcast("239.255.0.1"));
+        assert!(!broadcast("ff02::1"));
+    }
+
+    #[test]
+    fn artaddress_detail_lists_what_is_reprogrammed() {
+        let target: IpAddr = "2.0.0.10".parse().unwrap();
+        let mut address = ArtAddress {
+            protocol_version: 14,
+            net_switch: 0x7f,
+            bind_index: 1,
+            short_name: None,
+            long_name: None,
+            sw_in: [0x7f; 4],
+            sw_out: [0x83, 0x7f, 0x7f, 0x7f],
+            sub_switch: 0,
+            command: 0,
+        };
+        assert_eq!(
+            artaddress_detail(&target, &address),
+            "target=2.0.0.10, bind_index=1, sub_net=reset, out1=3"
+        );
+        address.sub_switch = 0x7f;
+        address.sw_out = [0x7f; 4];
+        assert_eq!(
+            artaddress_detail(&target, &address),
+            "target=2.0.0.10, bind_index=1, no change"
+        );
+        address.short_name = Some("Truss".to_string());
+        address.command = 0x90;
+        assert_eq!(
+            artaddress_detail(&target, &address),
+            "target=2.0.0.10, bind_index=1, short_name=\"Truss\", command=0x90"
+        );
+    }
+}
@@ -0,0 +1,934 @@
+//! Analyzer configuration and builder.
+//!
+//! `AnalyzerConfig` gathers every tunable used by the analysis pipeline
+//! (sliding windows, thresholds, enabled protocols, filters, limits). The
+//! defaults reproduce the behavior documented in the specification, so reports
+//! produced with `Analyzer::default()` match the free functions exactly.
+//!
+//! The configuration can also be loaded from a JSON file; omitted fields keep
+//! their defaults and unknown fields are rejected. Site-specific rules are
+//! listed as strings under `custom_rules` (see [`CustomRule`]). A `profile`
+//! key (or an explicit [`Profile`]) replaces the defaults with that preset
+//! before the other fields are applied; `rules` overrides are merged per rule.
+
+use std::collections::BTreeSet;
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::rules::{CustomRule, RuleId, RuleSet};
+use crate::source::{MergedSource, PacketEvent, PacketSource, PcapFileSource};
+use crate::{ActiveRule, FixtureMap, Report, RigPatch, estimate_clock_correction};
+use crate::{Protocol, Severity};
+
+use super::AnalysisError;
+use super::observer::AnalysisObserver;
+use super::profile::Profile;
+
+/// Default sliding window for universe loss/burst/jitter metrics (seconds).
+pub const DEFAULT_METRICS_WINDOW_S: f64 = 10.0;
+/// Default sliding window for universe FPS (seconds).
+pub const DEFAULT_FPS_WINDOW_S: f64 = 5.0;
+/// Default minimum overlap before two sources are reported as a conflict (seconds).
+pub const DEFAULT_CONFLICT_MIN_OVERLAP_S: f64 = 1.0;
+/// Default sliding window for flow PPS/BPS peaks (seconds).
+pub const DEFAULT_FLOW_RATE_WINDOW_S: f64 = 1.0;
+/// Default sliding window for flow and universe microburst peaks (seconds):
+/// short enough to see bursts that overflow switch buffers.
+pub const DEFAULT_MICROBURST_WINDOW_S: f64 = 0.01;
+/// Default sliding window for flow inter-arrival jitter (seconds).
+pub const DEFAULT_FLOW_JITTER_WINDOW_S: f64 = 10.0;
+/// Default shortest interval without any frame of a universe reported as a
+/// silence (seconds).
+pub const DEFAULT_SILENCE_THRESHOLD_S: f64 = 1.0;
+/// Default maximum number of examples kept per violation.
+pub const DEFAULT_MAX_VIOLATION_EXAMPLES: usize = 3;
+/// Default number of payload bytes attached to examples of Art-Net and sACN
+/// decoding errors (0 attaches none).
+pub const DEFAULT_VIOLATION_EXAMPLE_BYTES: usize = 0;
+/// Largest number of payload bytes attached to one violation example.
+pub const MAX_VIOLATION_EXAMPLE_BYTES: usize = 64;
+/// Default smallest sequence gap reported by the `LS-*-SEQ-GAP` rules.
+pub const DEFAULT_MIN_SEQUENCE_GAP: u8 = 1;
+/// Default minimum spacing between ArtDMX packets of one universe and source
+/// (seconds): one DMX512 frame at the maximum refresh rate of 44 Hz.
+pub const DEFAULT_ARTNET_MIN_INTERVAL_S: f64 = 1.0 / 44.0;
+/// Default broadcast rate per source above which a storm is reported (packets per second).
+pub const DEFAULT_MAX_BROADCAST_PPS: f64 = 200.0;
+/// Default universe frame rate above which flooding is reported (frames per
+/// second): well above the 44 Hz DMX512 maximum that consoles send.
+pub const DEFAULT_MAX_UNIVERSE_FPS: f64 = 120.0;
+/// Default per-source sACN refresh rate above which `LS-SACN-RATE` is reported
+/// (frames per second): the 44 Hz DMX512 maximum that E1.31 sources follow.
+pub const DEFAULT_MAX_SOURCE_FPS: f64 = 44.0;
+/// Default largest IP datagram on the lighting ports before `LS-UDP-OVERSIZED`
+/// is reported (bytes): the standard Ethernet MTU.
+pub const DEFAULT_MAX_IP_DATAGRAM_LEN: usize = 1500;
+/// Default lowest sACN multicast TTL accepted (1 keeps traffic on the local subnet).
+pub const DEFAULT_MIN_MULTICAST_TTL: u8 = 1;
+/// Default highest sACN multicast TTL accepted before it is reported as abnormal.
+pub const DEFAULT_MAX_MULTICAST_TTL: u8 = 64;
+/// Default number of non-DMX flows listed in `other_traffic`.
+pub const DEFAULT_MAX_OTHER_FLOWS: usize = 10;
+/// Default gap between consecutive packets above which a timestamp jump is
+/// reported (seconds): far longer than any pause in a running show.
+pub const DEFAULT_MAX_TIMESTAMP_JUMP_S: f64 = 300.0;
+/// Default share of a source's active channels that must change in one frame
+/// for a scene change to be reported.
+pub const DEFAULT_SCENE_CHANGE_MIN_FRACTION: f64 = 0.5;
+/// Largest sequence gap told apart from a reordered packet (half the sequence space).
+pub const MAX_SEQUENCE_GAP: u8 = 127;
+
+/// Tunables for the analysis pipeline.
+///
+/// Build one through [`Analyzer::builder`]; `AnalyzerConfig::default()` matches
+/// the behavior of [`crate::analyze_source`].
+///
+/// # Examples
+/// ```
+/// use liveshark_core::AnalyzerConfig;
+///
+/// let config = AnalyzerConfig::default();
+/// assert_eq!(config.metrics_window_s, 10.0);
+/// assert!(config.universes.is_none());
+///
+/// let config = AnalyzerConfig::from_json(
+///     r#"{"fps_window_s": 2.0, "rules": {"LS-ARTNET-PORT": {"enabled": false}}}"#,
+/// )?;
+/// assert_eq!(config.fps_window_s, 2.0);
+/// assert!(!config.rules.is_enabled(liveshark_core::RuleId::ArtNetPort));
+/// # Ok::<(), liveshark_core::AnalysisError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AnalyzerConfig {
+    /// Sliding window for universe loss/burst/jitter metrics (seconds).
+    pub metrics_window_s: f64,
+    /// Sliding window for universe FPS (seconds).
+    pub fps_window_s: f64,
+    /// Minimum overlap before two sources are reported as a conflict (seconds).
+    pub conflict_min_overlap_s: f64,
+    /// Sliding window for flow PPS/BPS peaks (seconds).
+    pub flow_rate_window_s: f64,
+    /// Sliding window for flow inter-arrival jitter (seconds).
+    pub flow_jitter_window_s: f64,
+    /// Sliding window for flow and universe microburst peaks (seconds).
+    pub microburst_window_s: f64,
+    /// Shortest interval without any frame of a universe, from any source,
+    /// reported as a silence (seconds).
+    pub silence_threshold_s: f64,
+    /// Decode Art-Net payloads.
+    pub artnet_enabled: bool,
+    /// Decode sACN payloads.
+    pub sacn_enabled: bool,
+    /// Restrict DMX analysis to these universes (`None` keeps all).
+    pub universes: Option<BTreeSet<u16>>,
+    /// Stop reading after this many packets (`None` reads the whole source).
+    pub max_packets: Option<u64>,
+    /// Maximum number of examples kept per violation.
+    pub max_violation_examples: usize,
+    /// Payload bytes, from the offending field, attached to examples of
+    /// Art-Net and sACN decoding errors (0 attaches none).
+    pub violation_example_bytes: usize,
+    /// Compliance rule overrides (enable/disable, severity).
+    pub rules: RuleSet,
+    /// Smallest number of missing sequence values reported by the optional
+    /// `LS-SACN-SEQ-GAP` / `LS-ARTNET-SEQ-GAP` rules (1..=127).
+    pub min_sequence_gap: u8,
+    /// Minimum spacing between ArtDMX packets of one universe and source
+    /// checked by `LS-ARTNET-MIN-INTERVAL` (seconds).
+    pub artnet_min_interval_s: f64,
+    /// Broadcast rate per source, measured over `flow_rate_window_s`, above
+    /// which `LS-UDP-BROADCAST-STORM` is reported (packets per second).
+    pub max_broadcast_pps: f64,
+    /// Largest IP datagram (header included) on the Art-Net and sACN ports
+    /// before `LS-UDP-OVERSIZED` is reported (bytes).
+    pub max_ip_datagram_len: usize,
+    /// Universe frame rate, measured over `fps_window_s`, above which
+    /// `LS-ARTNET-UNIVERSE-RATE` / `LS-SACN-UNIVERSE-RATE` are reported.
+    pub max_universe_fps: f64,
+    /// Refresh rate of one sACN source for one universe, measured over
+    /// `fps_window_s`, above which `LS-SACN-RATE` is reported.
+    pub max_source_fps: f64,
+    /// Lowest sACN multicast TTL accepted by `LS-SACN-MULTICAST-TTL`; set to 2
+    /// or more when universes must cross a router.
+    pub min_multicast_ttl: u8,
+    /// Highest sACN multicast TTL accepted by `LS-SACN-MULTICAST-TTL`.
+    pub max_multicast_ttl: u8,
+    /// Site-specific rules evaluated on every decoded packet.
+    pub custom_rules: Vec<CustomRule>,
+    /// Preset this configuration starts from, recorded in the report.
+    pub profile: Option<Profile>,
+    /// Number of non-DMX flows listed in `other_traffic` (0 omits the section).
+    pub max_other_flows: usize,
+    /// Gap between consecutive packets above which a `timestamp_jump`
+    /// warning is reported (seconds).
+    pub max_timestamp_jump_s: f64,
+    /// Share of a source's active channels (0 to 1) that must change in one
+    /// frame for a scene change to be reported.
+    pub scene_change_min_fraction: f64,
+    /// Embed per-second universe and flow series in the report (off by
+    /// default to keep reports small).
+    pub series: bool,
+    /// Unwrap VXLAN, GRE, and ERSPAN tunnels before parsing, so traffic a
+    /// switch mirrors through a tunnel is analyzed as the inner frame.
+    pub decapsulate: bool,
+    /// Fixture labels for channel-level findings; set through
+    /// [`AnalyzerBuilder::fixtures`], never read from configuration files.
+    #[serde(skip)]
+    pub fixtures: Option<FixtureMap>,
+    /// Rig patch naming universes and channel ranges; set through
+    /// [`AnalyzerBuilder::patch`], never read from configuration files.
+    #[serde(skip)]
+    pub patch: Option<RigPatch>,
+}
+
+impl Default for AnalyzerConfig {
+    fn default() -> Self {
+        Self {
+            metrics_window_s: DEFAULT_METRICS_WINDOW_S,
+            fps_window_s: DEFAULT_FPS_WINDOW_S,
+            conflict_min_overlap_s: DEFAULT_CONFLICT_MIN_OVERLAP_S,
+            flow_rate_window_s: DEFAULT_FLOW_RATE_WINDOW_S,
+            flow_jitter_window_s: DEFAULT_FLOW_JITTER_WINDOW_S,
+            microburst_window_s: DEFAULT_MICROBURST_WINDOW_S,
+            silence_threshold_s: DEFAULT_SILENCE_THRESHOLD_S,
+            artnet_enabled: true,
+            sacn_enabled: true,
+            universes: None,
+            max_packets: None,
+            max_violation_examples: DEFAULT_MAX_VIOLATION_EXAMPLES,
+            violation_example_bytes: DEFAULT_VIOLATION_EXAMPLE_BYTES,
+            rules: RuleSet::default(),
+            min_sequence_gap: DEFAULT_MIN_SEQUENCE_GAP,
+            artnet_min_interval_s: DEFAULT_ARTNET_MIN_INTERVAL_S,
+            max_broadcast_pps: DEFAULT_MAX_BROADCAST_PPS,
+            max_ip_datagram_len: DEFAULT_MAX_IP_DATAGRAM_LEN,
+            max_universe_fps: DEFAULT_MAX_UNIVERSE_FPS,
+            max_source_fps: DEFAULT_MAX_SOURCE_FPS,
+            min_multicast_ttl: DEFAULT_MIN_MULTICAST_TTL,
+            max_multicast_ttl: DEFAULT_MAX_MULTICAST_TTL,
+            custom_rules: Vec::new(),
+            profile: None,
+            max_other_flows: DEFAULT_MAX_OTHER_FLOWS,
+            max_timestamp_jump_s: DEFAULT_MAX_TIMESTAMP_JUMP_S,
+            scene_change_min_fraction: DEFAULT_SCENE_CHANGE_MIN_FRACTION,
+            series: false,
+            decapsulate: false,
+            fixtures: None,
+            patch: None,
+        }
+    }
+}
+
+impl AnalyzerConfig {
+    /// Parse and validate a JSON configuration.
+    ///
+    /// # Errors
+    /// Returns `AnalysisError::InvalidConfig` for malformed JSON, unknown
+    /// fields or rule codes, and values rejected by [`AnalyzerBuilder::build`].
+    pub fn from_json(json: &str) -> Result<Self, AnalysisError> {
+        Self::parse(json, None)
+    }
+
+    /// Parse and validate a JSON configuration on top of `profile`, which
+    /// takes precedence over a `profile` key in the JSON.
+    ///
+    /// # Errors
+    /// Same as [`AnalyzerConfig::from_json`].
+    pub fn from_json_with_profile(json: &str, profile: Profile) -> Result<Self, AnalysisError> {
+        Self::parse(json, Some(profile))
+    }
+
+    /// Default configuration with a profile preset applied.
+    pub fn for_profile(profile: Profile) -> Self {
+        let mut config = Self::default();
+        profile.apply(&mut config);
+        config
+    }
+
+    /// Read and validate a JSON configuration file.
+    ///
+    /// # Errors
+    /// Returns `AnalysisError::Io` when the file cannot be read, and the
+    /// errors of [`AnalyzerConfig::from_json`] otherwise.
+    pub fn from_file(path: &Path) -> Result<Self, AnalysisError> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Read and validate a JSON configuration file on top of `profile`.
+    ///
+    /// # Errors
+    /// Same as [`AnalyzerConfig::from_file`].
+    pub fn from_file_with_profile(path: &Path, profile: Profile) -> Result<Self, AnalysisError> {
+        Self::from_json_with_profile(&std::fs::read_to_string(path)?, profile)
+    }
+
+    /// Returns true when the protocol is decoded by this configuration.
+    pub fn protocol_enabled(&self, protocol: Protocol) -> bool {
+        match protocol {
+            Protocol::ArtNet => self.artnet_enabled,
+            Protocol::Sacn => self.sacn_enabled,
+            Protocol::Udp
+            | Protocol::Tcp
+            | Protocol::Osc
+            | Protocol::Citp
+            | Protocol::Ptp
+            | Protocol::Dhcp => true,
+        }
+    }
+
+    /// Returns true when the universe passes the universe filter.
+    pub fn universe_selected(&self, universe: u16) -> bool {
+        self.universes
+            .as_ref()
+            .is_none_or(|selected| selected.contains(&universe))
+    }
+
+    /// Enabled registry rules and custom rules with their effective severity,
+    /// sorted by ID.
+    pub fn active_rules(&self) -> Vec<ActiveRule> {
+        let mut active = self.rules.active();
+        active.extend(self.custom_rules.iter().map(|rule| ActiveRule {
+            id: rule.id().to_string(),
+            severity: rule.severity(),
+        }));
+        active.sort_by(|a, b| a.id.cmp(&b.id));
+        active
+    }
+
+    fn parse(json: &str, profile: Option<Profile>) -> Result<Self, AnalysisError> {
+        let Value::Object(mut fields) = serde_json::from_str(json).map_err(invalid_config)? else {
+            return Err(AnalysisError::InvalidConfig(
+                "configuration must be a JSON object".to_string(),
+            ));
+        };
+        let named = fields
+            .remove("profile")
+            .map(serde_json::from_value::<Option<Profile>>)
+            .transpose()
+            .map_err(invalid_config)?
+            .flatten();
+        let base = profile.or(named).map(Self::for_profile).unwrap_or_default();
+        let mut merged = serde_json::to_value(base).map_err(invalid_config)?;
+        merge_json(&mut merged, Value::Object(fields));
+        let config: Self = serde_json::from_value(merged).map_err(invalid_config)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), AnalysisError> {
+        let windows = [
+            ("metrics_window_s", self.metrics_window_s),
+            ("fps_window_s", self.fps_window_s),
+            ("flow_rate_window_s", self.flow_rate_window_s),
+            ("flow_jitter_window_s", self.flow_jitter_window_s),
+            ("microburst_window_s", self.microburst_window_s),
+            ("silence_threshold_s", self.silence_threshold_s),
+            ("max_timestamp_jump_s", self.max_timestamp_jump_s),
+        ];
+        for (name, value) in windows {
+            if !value.is_finite() || value <= 0.0 {
+                return Err(AnalysisError::InvalidConfig(format!(
+                    "{name} must be a positive number of seconds (got {value})"
+                )));
+            }
+        }
+        let thresholds = [
+            ("conflict_min_overlap_s", self.conflict_min_overlap_s),
+            ("artnet_min_interval_s", self.artnet_min_interval_s),
+            ("max_broadcast_pps", self.max_broadcast_pps),
+            ("max_universe_fps", self.max_universe_fps),
+            ("max_source_fps", self.max_source_fps),
+        ];
+        for (name, value) in thresholds {
+            if !value.is_finite() || value < 0.0 {
+                return Err(AnalysisError::InvalidConfig(format!(
+                    "{name} must be zero or positive (got {value})"
+                )));
+            }
+        }
+        let fraction = self.scene_change_min_fraction;
+        if !(fraction > 0.0 && fraction <= 1.0) {
+            return Err(AnalysisError::InvalidConfig(format!(
+                "scene_change_min_fraction must be greater than 0 and at most 1 (got {fraction})"
+            )));
+        }
+        if !(1..=MAX_SEQUENCE_GAP).contains(&self.min_sequence_gap) {
+            return Err(AnalysisError::InvalidConfig(format!(
+                "min_sequence_gap must be between 1 and {MAX_SEQUENCE_GAP} (got {})",
+                self.min_sequence_gap
+            )));
+        }
+        if self.violation_example_bytes > MAX_VIOLATION_EXAMPLE_BYTES {
+            return Err(AnalysisError::InvalidConfig(format!(
+                "violation_example_bytes must be at most {MAX_VIOLATION_EXAMPLE_BYTES} (got {})",
+                self.violation_example_bytes
+            )));
+        }
+        if self.min_multicast_ttl > self.max_multicast_ttl {
+            return Err(AnalysisError::InvalidConfig(format!(
+                "min_multicast_ttl ({}) must not exceed max_multicast_ttl ({})",
+                self.min_multicast_ttl, self.max_multicast_ttl
+            )));
+        }
+        let mut ids = BTreeSet::new();
+        for rule in &self.custom_rules {
+            if !ids.insert(rule.id()) {
+                return Err(AnalysisError::InvalidConfig(format!(
+                    "custom rule ID {} is defined more than once",
+                    rule.id()
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn invalid_config(err: serde_json::Error) -> AnalysisError {
+    AnalysisError::InvalidConfig(err.to_string())
+}
+
+/// Overlay `overrides` onto `base`: objects are merged key by key (so a rule
+/// override keeps the profile's other settings), other values are replaced.
+fn merge_json(base: &mut Value, overrides: Value) {
+    match (base, overrides) {
+        (Value::Object(base), Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overrides) => *base = overrides,
+    }
+}
+
+/// Configured analysis pipeline.
+///
+/// # Examples
+/// ```no_run
+/// use liveshark_core::Analyzer;
+/// use std::path::Path;
+///
+/// let analyzer = Analyzer::builder()
+///     .fps_window_s(2.0)
+///     .sacn(false)
+///     .universes([1, 2])
+///     .build()?;
+/// let report = analyzer.analyze_file(Path::new("capture.pcapng"))?;
+/// assert!(report.report_version >= 1);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Analyzer {
+    config: AnalyzerConfig,
+    buffer_pool: bool,
+}
+
+impl Analyzer {
+    /// Start building an analyzer from the default configuration.
+    pub fn builder() -> AnalyzerBuilder {
+        AnalyzerBuilder::default()
+    }
+
+    /// Configuration used by this analyzer.
+    pub fn config(&self) -> &AnalyzerConfig {
+        &self.config
+    }
+
+    /// Whether file analysis reuses packet buffers (see
+    /// [`AnalyzerBuilder::buffer_pool`]).
+    pub fn buffer_pool(&self) -> bool {
+        self.buffer_pool
+    }
+
+    /// Analyze a PCAP/PCAPNG file from disk.
+    ///
+    /// # Errors
+    /// Returns `AnalysisError` when the file cannot be opened or parsed.
+    pub fn analyze_file(&self, path: &Path) -> Result<Report, AnalysisError> {
+        self.analyze_file_with_observer(path, &mut ())
+    }
+
+    /// Analyze a PCAP/PCAPNG file from disk, notifying `observer` as it runs.
+    ///
+    /// # Errors
+    /// Returns `AnalysisError` when the file cannot be opened or parsed.
+    pub fn analyze_file_with_observer(
+        &self,
+        path: &Path,
+        observer: &mut dyn AnalysisObserver,
+    ) -> Result<Report, AnalysisError> {
+        let source = PcapFileSource::open(path)?;
+        if self.buffer_pool {
+            self.analyze_source_with_observer(path, source.with_buffer_pool(), observer)
+        } else {
+            self.analyze_source_with_observer(path, source, observer)
+        }
+    }
+
+    /// Analyze several captures of the same network as one. The clock of
+    /// each capture after the first is aligned onto the first one's from
+    /// the UDP datagrams both captured, and the corrections are recorded
+    /// under `clock_corrections`; the report's `input` is the first capture.
+    ///
+    /// # Errors
+    /// Returns `AnalysisError` when no path is given or a file cannot be
+    /// opened or parsed.
+    pub fn analyze_files(&self, paths: &[PathBuf]) -> Result<Report, AnalysisError> {
+        let Some((reference, others)) = paths.split_first() else {
+            return Err(AnalysisError::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "no capture file to analyze",
+            )));
+        };
+        if others.is_empty() {
+            return self.analyze_file(reference);
+        }
+        let mut source = MergedSource::default().with_input(PcapFileSource::open(reference)?, None);
+        let mut corrections = Vec::with_capacity(others.len());
+        for path in others {
+            let correction = estimate_clock_correction(
+                &path.display().to_string(),
+                PcapFileSource::open(reference)?,
+                PcapFileSource::open(path)?,
+            )?;
+            source = source.with_input(PcapFileSource::open(path)?, Some(correction.clone()));
+            corrections.push(correction);
+        }
+        let mut report = self.analyze_source(reference, source)?;
+        report.clock_corrections = corrections;
+        Ok(report)
+    }
+
+    /// Analyze a packet source and produce a report.
+    ///
+    /// # Errors
+    /// Returns `AnalysisError` for I/O or parsing failures originating from the
+    /// packet source.
+    pub fn analyze_source<S: PacketSource>(
+        &self,
+        path: &Path,
+        source: S,
+    ) -> Result<Report, AnalysisError> {
+        self.analyze_source_with_observer(path, source, &mut ())
+    }
+
+    /// Analyze a packet source, notifying `observer` as it runs.
+    ///
+    /// # Errors
+    /// Returns `AnalysisError` for I/O or parsing failures originating from the
+    /// packet source.
+    pub fn analyze_source_with_observer<S: PacketSource>(
+        &self,
+        path: &Path,
+        source: S,
+        observer: &mut dyn AnalysisObserver,
+    ) -> Result<Report, AnalysisError> {
+        super::run_analysis(&self.config, path, source, observer)
+    }
+
+    /// Analyze packets held in memory, such as frames captured live, under
+    /// the input name `input`; the report's input size is the bytes of the
+    /// packets read. The packets are borrowed, not copied, so the caller can
+    /// analyze the same ones again later.
+    ///
+    /// # Errors
+    /// Returns `AnalysisError` when the report cannot be built.
+    pub fn analyze_packets<'a, I>(&self, input: &str, packets: I) -> Result<Report, AnalysisError>
+    where
+        I: IntoIterator<Item = &'a PacketEvent>,
+    {
+        super::analyze_borrowed(&self.config, input, packets.into_iter())
+    }
+}
+
+/// Builder for [`Analyzer`].
+///
+/// # Examples
+/// ```
+/// use liveshark_core::Analyzer;
+///
+/// let analyzer = Analyzer::builder().metrics_window_s(5.0).build().unwrap();
+/// assert_eq!(analyzer.config().metrics_window_s, 5.0);
+///
+/// assert!(Analyzer::builder().fps_window_s(0.0).build().is_err());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AnalyzerBuilder {
+    config: AnalyzerConfig,
+    buffer_pool: bool,
+}
+
+impl AnalyzerBuilder {
+    /// Replace the whole configuration.
+    pub fn config(mut self, config: AnalyzerConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Sliding window for universe loss/burst/jitter metrics (seconds).
+    pub fn metrics_window_s(mut self, seconds: f64) -> Self {
+        self.config.metrics_window_s = seconds;
+        self
+    }
+
+    /// Sliding window for universe FPS (seconds).
+    pub fn fps_window_s(mut self, seconds: f64) -> Self {
+        self.config.fps_window_s = seconds;
+        self
+    }
+
+    /// Minimum overlap before two sources are reported as a conflict (seconds).
+    pub fn conflict_min_overlap_s(mut self, seconds: f64) -> Self {
+        self.config.conflict_min_overlap_s = seconds;
+        self
+    }
+
+    /// Sliding window for flow PPS/BPS peaks (seconds).
+    pub fn flow_rate_window_s(mut self, seconds: f64) -> Self {
+        self.config.flow_rate_window_s = seconds;
+        self
+    }
+
+    /// Sliding window for flow inter-arrival jitter (seconds).
+    pub fn flow_jitter_window_s(mut self, seconds: f64) -> Self {
+        self.config.flow_jitter_window_s = seconds;
+        self
+    }
+
+    /// Sliding window for flow and universe microburst peaks (seconds).
+    pub fn microburst_window_s(mut self, seconds: f64) -> Self {
+        self.config.microburst_window_s = seconds;
+        self
+    }
+
+    /// Shortest interval without any frame of a universe reported as a
+    /// silence (seconds).
+    pub fn silence_threshold_s(mut self, seconds: f64) -> Self {
+        self.config.silence_threshold_s = seconds;
+        self
+    }
+
+    /// Enable or disable Art-Net decoding.
+    pub fn artnet(mut self, enabled: bool) -> Self {
+        self.config.artnet_enabled = enabled;
+        self
+    }
+
+    /// Enable or disable sACN decoding.
+    pub fn sacn(mut self, enabled: bool) -> Self {
+        self.config.sacn_enabled = enabled;
+        self
+    }
+
+    /// Restrict DMX analysis to the given universes.
+    pub fn universes<I: IntoIterator<Item = u16>>(mut self, universes: I) -> Self {
+        self.config.universes = Some(universes.into_iter().collect());
+        self
+    }
+
+    /// Stop reading after `limit` packets.
+    pub fn max_packets(mut self, limit: u64) -> Self {
+        self.config.max_packets = Some(limit);
+        self
+    }
+
+    /// Maximum number of examples kept per violation.
+    pub fn max_violation_examples(mut self, limit: usize) -> Self {
+        self.config.max_violation_examples = limit;
+        self
+    }
+
+    /// Payload bytes attached to examples of Art-Net and sACN decoding
+    /// errors, from the offending field (at most 64).
+    pub fn violation_example_bytes(mut self, len: usize) -> Self {
+        self.config.violation_example_bytes = len;
+        self
+    }
+
+    /// Number of non-DMX flows listed in `other_traffic`.
+    pub fn max_other_flows(mut self, limit: usize) -> Self {
+        self.config.max_other_flows = limit;
+        self
+    }
+
+    /// Label channel-level findings with fixture and parameter names.
+    pub fn fixtures(mut self, fixtures: FixtureMap) -> Self {
+        self.config.fixtures = Some(fixtures);
+        self
+    }
+
+    /// Label universes and channel ranges with the production's rig patch.
+    pub fn patch(mut self, patch: RigPatch) -> Self {
+        self.config.patch = Some(patch);
+        self
+    }
+
+    /// Embed per-second metric series in the report.
+    pub fn series(mut self, enabled: bool) -> Self {
+        self.config.series = enabled;
+        self
+    }
+
+    /// Unwrap tunneled (VXLAN, GRE, ERSPAN) frames before analysis.
+    pub fn decapsulate(mut self, enabled: bool) -> Self {
+        self.config.decapsulate = enabled;
+        self
+    }
+
+    /// Reuse packet buffers across packets when analyzing files, instead of
+    /// allocating one per packet; worth it on high packet-rate captures.
+    pub fn buffer_pool(mut self, enabled: bool) -> Self {
+        self.buffer_pool = enabled;
+        self
+    }
+
+    /// Gap between consecutive packets reported as a timestamp jump (seconds).
+    pub fn max_timestamp_jump_s(mut self, seconds: f64) -> Self {
+        self.config.max_timestamp_jump_s = seconds;
+        self
+    }
+
+    /// Share of active channels that must change together for a scene change.
+    pub fn scene_change_min_fraction(mut self, fraction: f64) -> Self {
+        self.config.scene_change_min_fraction = fraction;
+        self
+    }
+
+    /// Replace all compliance rule overrides.
+    pub fn rules(mut self, rules: RuleSet) -> Self {
+        self.config.rules = rules;
+        self
+    }
+
+    /// Enable or disable a compliance rule.
+    pub fn rule_enabled(mut self, rule: RuleId, enabled: bool) -> Self {
+        self.config.rules.set_enabled(rule, enabled);
+        self
+    }
+
+    /// Override the severity reported for a compliance rule.
+    pub fn rule_severity(mut self, rule: RuleId, severity: Severity) -> Self {
+        self.config.rules.set_severity(rule, severity);
+        self
+    }
+
+    /// Smallest sequence gap reported by the optional sequence-gap rules.
+    pub fn min_sequence_gap(mut self, gap: u8) -> Self {
+        self.config.min_sequence_gap = gap;
+        self
+    }
+
+    /// Minimum spacing between ArtDMX packets of one universe and source (seconds).
+    pub fn artnet_min_interval_s(mut self, seconds: f64) -> Self {
+        self.config.artnet_min_interval_s = seconds;
+        self
+    }
+
+    /// Broadcast rate per source above which a storm is reported (packets per second).
+    pub fn max_broadcast_pps(mut self, pps: f64) -> Self {
+        self.config.max_broadcast_pps = pps;
+        self
+    }
+
+    /// Largest IP datagram on the lighting ports before it is reported as oversized (bytes).
+    pub fn max_ip_datagram_len(mut self, len: usize) -> Self {
+        self.config.max_ip_datagram_len = len;
+        self
+    }
+
+    /// Universe frame rate above which flooding is reported (frames per second).
+    pub fn max_universe_fps(mut self, fps: f64) -> Self {
+        self.config.max_universe_fps = fps;
+        self
+    }
+
+    /// Per-source sACN refresh rate above which it is reported (frames per second).
+    pub fn max_source_fps(mut self, fps: f64) -> Self {
+        self.config.max_source_fps = fps;
+        self
+    }
+
+    /// Accepted sACN multicast TTL range (inclusive).
+    pub fn multicast_ttl(mut self, min: u8, max: u8) -> Self {
+        self.config.min_multicast_ttl = min;
+        self.config.max_multicast_ttl = max;
+        self
+    }
+
+    /// Apply a profile preset to the configuration built so far; later
+    /// setters refine it.
+    pub fn profile(mut self, profile: Profile) -> Self {
+        profile.apply(&mut self.config);
+        self
+    }
+
+    /// Add a site-specific rule.
+    pub fn custom_rule(mut self, rule: CustomRule) -> Self {
+        self.config.custom_rules.push(rule);
+        self
+    }
+
+    /// Validate the configuration and build the analyzer.
+    ///
+    /// # Errors
+    /// Returns `AnalysisError::InvalidConfig` when a window is not a positive
+    /// finite number, a minimum threshold is negative, the sequence gap
+    /// threshold is outside 1..=127, the scene change fraction is outside
+    /// (0, 1], or two custom rules share an ID.
+    pub fn build(self) -> Result<Analyzer, AnalysisError> {
+        self.config.validate()?;
+        Ok(Analyzer {
+            config: self.config,
+            buffer_pool: self.buffer_pool,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Analyzer, AnalyzerConfig};
+    use crate::analysis::profile::Profile;
+    use crate::rules::RuleId;
+    use crate::{Protocol, Severity};
+
+    #[test]
+    fn default_builder_matches_default_config() {
+        let analyzer = Analyzer::builder().build().unwrap();
+        assert_eq!(analyzer.config(), &AnalyzerConfig::default());
+    }
+
+    #[test]
+    fn builder_rejects_invalid_windows() {
+        assert!(Analyzer::builder().metrics_window_s(-1.0).build().is_err());
+        assert!(
+            Analyzer::builder()
+                .flow_rate_window_s(f64::NAN)
+                .build()
+                .is_err()
+        );
+        assert!(
+            Analyzer::builder()
+                .conflict_min_overlap_s(-0.5)
+                .build()
+                .is_err()
+        );
+        assert!(
+            Analyzer::builder()
+                .conflict_min_overlap_s(0.0)
+                .build()
+                .is_ok()
+        );
+        assert!(Analyzer::builder().min_sequence_gap(0).build().is_err());
+        assert!(
+            Analyzer::builder()
+                .artnet_min_interval_s(-0.01)
+                .build()
+                .is_err()
+        );
+        assert!(Analyzer::builder().min_sequence_gap(128).build().is_err());
+        assert!(Analyzer::builder().multicast_ttl(32, 16).build().is_err());
+        assert!(
+            Analyzer::builder()
+                .scene_change_min_fraction(0.0)
+                .build()
+                .is_err()
+        );
+        assert!(
+            Analyzer::builder()
+                .scene_change_min_fraction(1.0)
+                .build()
+                .is_ok()
+        );
+        assert!(
+            Analyzer::builder()
+                .microburst_window_s(0.0)
+                .build()
+                .is_err()
+        );
+        assert!(
+            Analyzer::builder()
+                .silence_threshold_s(-1.0)
+                .build()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn filters_select_protocols_and_universes() {
+        let analyzer = Analyzer::builder()
+            .artnet(false)
+            .universes([3, 1])
+            .build()
+            .unwrap();
+        let config = analyzer.config();
+        assert!(!config.protocol_enabled(Protocol::ArtNet));
+        assert!(config.protocol_enabled(Protocol::Sacn));
+        assert!(config.universe_selected(1));
+        assert!(!config.universe_selected(2));
+    }
+
+    #[test]
+    fn json_config_keeps_defaults_and_rejects_unknown_fields() {
+        let config = AnalyzerConfig::from_json(
+            r#"{"sacn_enabled": false, "rules": {"LS-SACN-PORT": {"enabled": false}}}"#,
+        )
+        .unwrap();
+        assert!(!config.sacn_enabled);
+        assert!(!config.rules.is_enabled(RuleId::SacnPort));
+        assert_eq!(config.fps_window_s, AnalyzerConfig::default().fps_window_s);
+
+        assert!(AnalyzerConfig::from_json(r#"{"fps_window": 2.0}"#).is_err());
+        assert!(AnalyzerConfig::from_json(r#"{"fps_window_s": 0.0}"#).is_err());
+        assert_eq!(
+            AnalyzerConfig::from_json("{}").unwrap(),
+            AnalyzerConfig::default()
+        );
+    }
+
+    #[test]
+    fn json_config_applies_on_top_of_profile() {
+        let config = AnalyzerConfig::from_json(
+            r#"{"profile": "strict", "max_universe_fps": 60.0,
+                "rules": {"LS-SACN-SEQ-GAP": {"severity": "error"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(config.profile, Some(Profile::Strict));
+        assert_eq!(config.max_universe_fps, 60.0);
+        assert!(config.rules.is_enabled(RuleId::SacnSeqGap));
+        assert_eq!(config.rules.severity(RuleId::SacnSeqGap), Severity::Error);
+
+        let config =
+            AnalyzerConfig::from_json_with_profile(r#"{"profile": "strict"}"#, Profile::Touring)
+                .unwrap();
+        assert_eq!(config, AnalyzerConfig::for_profile(Profile::Touring));
+        assert!(AnalyzerConfig::from_json(r#"{"profile": "relaxed"}"#).is_err());
+        assert!(AnalyzerConfig::from_json("[]").is_err());
+    }
+
+    #[test]
+    fn custom_rules_are_parsed_and_ids_must_be_unique() {
+        let config =
+            AnalyzerConfig::from_json(r#"{"custom_rules": ["ttl > 64 -> warning CUSTOM-TTL"]}"#)
+                .unwrap();
+        assert_eq!(config.custom_rules[0].id(), "CUSTOM-TTL");
+
+        assert!(AnalyzerConfig::from_json(r#"{"custom_rules": ["ttl > -> warning X"]}"#).is_err());
+        let duplicate = "ttl > 1 -> warning CUSTOM-TTL".parse().unwrap();
+        assert!(
+            Analyzer::builder()
+                .config(config)
+                .custom_rule(duplicate)
+                .build()
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,108 @@
+//! Concurrent controllers of a universe.
+//!
+//! Several sources on a universe are not necessarily a conflict: a backup
+//! console idling on the universe, or a desk writing a few channels now and
+//! then, may never overlap enough to be reported as one, yet they are still
+//! more than one controller on the wire. A source is active from a frame to
+//! its next one when the two are closer than the source loss timeout; the
+//! peak number of sources active at once is the universe's concurrency.
+
+use super::takeovers::SOURCE_TIMEOUT_S;
+
+/// Intervals during which one source kept sending.
+#[derive(Debug, Default)]
+pub(crate) struct ActivitySpans {
+    /// `(first, last)` frame timestamps, in arrival order; merged when the
+    /// concurrency is computed so out-of-order captures do not split spans.
+    spans: Vec<(f64, f64)>,
+}
+
+impl ActivitySpans {
+    /// Record a frame of the source at `ts`.
+    pub(crate) fn add(&mut self, ts: f64) {
+        match self.spans.last_mut() {
+            Some((start, end))
+                if ts >= *start - SOURCE_TIMEOUT_S && ts <= *end + SOURCE_TIMEOUT_S =>
+            {
+                *start = start.min(ts);
+                *end = end.max(ts);
+            }
+            _ => self.spans.push((ts, ts)),
+        }
+    }
+
+    fn merged(mut self) -> Vec<(f64, f64)> {
+        self.spans.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut merged: Vec<(f64, f64)> = Vec::with_capacity(self.spans.len());
+        for (start, end) in self.spans {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 + SOURCE_TIMEOUT_S => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+        merged
+    }
+}
+
+/// Most sources active at the same time; `None` when no frame carries a
+/// timestamp.
+pub(crate) fn max_concurrent(sources: impl IntoIterator<Item = ActivitySpans>) -> Option<u64> {
+    // Starts sort before ends at the same instant, so sources sending in the
+    // same instant count as concurrent.
+    let mut events: Vec<(f64, i64)> = Vec::new();
+    for spans in sources {
+        for (start, end) in spans.merged() {
+            events.push((start, 1));
+            events.push((end, -1));
+        }
+    }
+    if events.is_empty() {
+        return None;
+    }
+    events.sort_by(|a, b| a.0.total_cmp(&b.0).then(b.1.cmp(&a.1)));
+    let mut active = 0i64;
+    let mut peak = 0i64;
+    for (_, delta) in events {
+        active += delta;
+        peak = peak.max(active);
+    }
+    Some(peak as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ActivitySpans, max_concurrent};
+
+    /// 40 fps frames between `start` and `end` (seconds).
+    fn spans(start: f64, end: f64) -> ActivitySpans {
+        let mut spans = ActivitySpans::default();
+        let count = ((end - start) / 0.025).round() as usize;
+        for index in 0..count {
+            spans.add(start + index as f64 * 0.025);
+        }
+        spans
+    }
+
+    #[test]
+    fn overlapping_sources_are_concurrent() {
+        let peak = max_concurrent([spans(0.0, 10.0), spans(2.0, 4.0), spans(3.0, 12.0)]);
+        assert_eq!(peak, Some(3));
+    }
+
+    #[test]
+    fn backup_starting_after_main_stops_is_not_concurrent() {
+        let peak = max_concurrent([spans(0.0, 10.0), spans(11.0, 20.0)]);
+        assert_eq!(peak, Some(1));
+    }
+
+    #[test]
+    fn sparse_source_stays_active_between_close_frames() {
+        let mut sparse = ActivitySpans::default();
+        // Out of order, but never more than 2 s apart.
+        for ts in [1.0, 5.0, 3.0, 7.0] {
+            sparse.add(ts);
+        }
+        assert_eq!(max_concurrent([sparse, spans(6.0, 6.5)]), Some(2));
+        assert_eq!(max_concurrent([ActivitySpans::default()]), None);
+    }
+}
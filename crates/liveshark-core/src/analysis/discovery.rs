@@ -0,0 +1,127 @@
+//! sACN universe discovery: declared versus transmitted universes.
+//!
+//! E1.31 sources announce the universes they transmit in universe discovery
+//! packets, split over pages. Receivers and monitoring tools rely on that
+//! list, so a universe a source sends without announcing it, or announces
+//! without sending it, is worth reporting. The comparison spans the whole
+//! capture: every announced page adds to the declared universes.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::IpAddr;
+
+use crate::{SacnDiscovery, SacnUniverseDiscovery};
+
+#[derive(Debug, Default)]
+struct Source {
+    source_name: Option<String>,
+    source_ip: Option<IpAddr>,
+    discovery_packets: u64,
+    last_page: u8,
+    declared: BTreeSet<u16>,
+    sent: BTreeSet<u16>,
+}
+
+/// Declared and transmitted universes per source CID.
+#[derive(Debug, Default)]
+pub(crate) struct DiscoveryTracker {
+    sources: BTreeMap<String, Source>,
+}
+
+impl DiscoveryTracker {
+    /// Record a discovery page sent from `src_ip`, keeping the universes
+    /// `selected` accepts.
+    pub(crate) fn add_discovery(
+        &mut self,
+        src_ip: IpAddr,
+        discovery: SacnDiscovery,
+        selected: impl Fn(u16) -> bool,
+    ) {
+        let source = self.sources.entry(discovery.cid).or_default();
+        source.discovery_packets += 1;
+        source.source_ip = Some(src_ip);
+        source.last_page = source.last_page.max(discovery.last_page);
+        if discovery.source_name.is_some() {
+            source.source_name = discovery.source_name;
+        }
+        source.declared.extend(
+            discovery
+                .universes
+                .into_iter()
+                .filter(|&universe| selected(universe)),
+        );
+    }
+
+    /// Record a data packet of `cid` for `universe`.
+    pub(crate) fn add_data(&mut self, cid: &str, universe: u16) {
+        if let Some(source) = self.sources.get_mut(cid) {
+            source.sent.insert(universe);
+        } else {
+            let mut source = Source::default();
+            source.sent.insert(universe);
+            self.sources.insert(cid.to_string(), source);
+        }
+    }
+
+    /// Sources that sent universe discovery, sorted by CID.
+    pub(crate) fn finish(self) -> Vec<SacnUniverseDiscovery> {
+        self.sources
+            .into_iter()
+            .filter(|(_, source)| source.discovery_packets > 0)
+            .map(|(cid, source)| SacnUniverseDiscovery {
+                cid,
+                source_name: source.source_name,
+                source_ip: source
+                    .source_ip
+                    .map(|ip| ip.to_string())
+                    .unwrap_or_default(),
+                discovery_packets: source.discovery_packets,
+                pages: u16::from(source.last_page) + 1,
+                undeclared: source.sent.difference(&source.declared).copied().collect(),
+                not_sent: source.declared.difference(&source.sent).copied().collect(),
+                declared: source.declared.into_iter().collect(),
+                sent: source.sent.into_iter().collect(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use super::DiscoveryTracker;
+    use crate::SacnDiscovery;
+
+    fn page(cid: &str, page: u8, last_page: u8, universes: Vec<u16>) -> SacnDiscovery {
+        SacnDiscovery {
+            cid: cid.to_string(),
+            source_name: Some("console".to_string()),
+            page,
+            last_page,
+            universes,
+        }
+    }
+
+    #[test]
+    fn declared_universes_are_compared_with_the_universes_sent() {
+        let console = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let mut tracker = DiscoveryTracker::default();
+        tracker.add_discovery(console, page("a", 0, 1, vec![1, 2]), |_| true);
+        tracker.add_discovery(console, page("a", 1, 1, vec![3, 900]), |u| u < 900);
+        for universe in [1, 2, 4] {
+            tracker.add_data("a", universe);
+        }
+        // Sources without discovery are not compared.
+        tracker.add_data("b", 1);
+
+        let sources = tracker.finish();
+        assert_eq!(sources.len(), 1);
+        let source = &sources[0];
+        assert_eq!(source.source_ip, "10.0.0.1");
+        assert_eq!((source.discovery_packets, source.pages), (2, 2));
+        assert_eq!(source.declared, vec![1, 2, 3]);
+        assert_eq!(source.sent, vec![1, 2, 4]);
+        assert_eq!(source.undeclared, vec![4]);
+        assert_eq!(source.not_sent, vec![3]);
+    }
+}
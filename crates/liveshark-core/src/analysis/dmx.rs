@@ -1,35 +1,171 @@
-use std::collections::HashMap;
+//! Stateful DMX reconstruction.
+//!
+//! Art-Net and sACN packets may carry fewer than 512 slots. Reconstruction
+//! keeps the last known value of every slot per (universe, source, protocol),
+//! so each applied packet yields a full 512-slot frame. Slots never received
+//! are zero.
+//!
+//! Art-Net senders that emit ArtSync put their nodes in synchronous mode:
+//! ArtDMX is buffered and output on the next ArtSync, so synchronized
+//! frames are held per sender IP and released with the ArtSync timestamp.
+//! A node reverts to immediate output when no ArtSync arrives for 4 s; held
+//! frames then keep their own timestamps.
+//!
+//! sACN synchronization works the same way per source CID and sync address:
+//! data packets naming a sync address are output on the source's next
+//! synchronization packet for that address, and receivers stop waiting
+//! after the 2.5 s network data loss timeout.
+//!
+use std::collections::{BTreeMap, HashMap};
+use std::net::IpAddr;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub(crate) enum DmxProtocol {
-    ArtNet,
-    Sacn,
-}
+use crate::decode::Endpoints;
+use crate::protocols::artnet::ArtDmx;
+use crate::protocols::sacn::{SacnDmx, SacnSync};
+use crate::{DmxSnapshot, Protocol};
 
+/// Full 512-slot DMX frame reconstructed for one source.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{DmxFrame, Protocol};
+///
+/// let frame = DmxFrame {
+///     universe: 1,
+///     timestamp: Some(0.0),
+///     source_id: "artnet:10.0.0.1:6454".to_string(),
+///     protocol: Protocol::ArtNet,
+///     slots: [0u8; 512],
+/// };
+/// assert_eq!(frame.slots.len(), 512);
+/// ```
 #[derive(Debug, Clone, PartialEq)]
-pub(crate) struct DmxFrame {
+pub struct DmxFrame {
+    /// Universe identifier.
     pub universe: u16,
+    /// Packet timestamp in seconds (if available).
     pub timestamp: Option<f64>,
+    /// Source identifier (same format as `SourceSummary.source_id`).
     pub source_id: String,
-    pub protocol: DmxProtocol,
+    /// Protocol that carried the frame.
+    pub protocol: Protocol,
+    /// Slot values after applying the packet.
     pub slots: [u8; 512],
 }
 
 #[derive(Debug, Default)]
 pub(crate) struct DmxStore {
     frames_by_universe: HashMap<u16, HashMap<String, Vec<DmxFrame>>>,
+    /// What each stored frame wrote, parallel to `frames_by_universe`.
+    writes_by_universe: HashMap<u16, HashMap<String, Vec<FrameWrite>>>,
+}
+
+/// Slots a packet actually carried and the priority it was sent with; the
+/// reconstructed frame alone cannot tell which slots were written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FrameWrite {
+    /// Slots carried by the packet (the first `slots` of the frame).
+    pub slots: u16,
+    /// sACN priority of the packet (`None` for Art-Net).
+    pub priority: Option<u8>,
+}
+
+impl Default for FrameWrite {
+    fn default() -> Self {
+        Self {
+            slots: 512,
+            priority: None,
+        }
+    }
 }
 
+/// Per-(universe, source, protocol) DMX state used for reconstruction.
+///
+/// This is the reconstruction used by the analysis pipeline; feeding it the
+/// same packets (ArtDMX through `apply_artdmx_synced` and `apply_artsync`,
+/// sACN through `apply_sacn_dmx_synced` and `apply_sacn_sync`, then
+/// `flush_artsync` and `flush_sacn_sync`) yields the same frames.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{DmxStateStore, Protocol};
+///
+/// let mut state = DmxStateStore::new();
+/// state.apply_partial(1, "artnet:10.0.0.1:6454", Protocol::ArtNet, &[10, 20, 30]);
+/// let slots = state.apply_partial(1, "artnet:10.0.0.1:6454", Protocol::ArtNet, &[99]);
+/// assert_eq!(&slots[..3], &[99, 20, 30]);
+/// ```
 #[derive(Debug, Default)]
-pub(crate) struct DmxStateStore {
+pub struct DmxStateStore {
     states: HashMap<DmxStateKey, [u8; 512]>,
+    /// Senders in synchronous mode, by IP (ordered so flushes are stable).
+    art_sync: BTreeMap<IpAddr, SyncSender<HeldArtDmx>>,
+    /// Synchronized sACN sources, by CID and sync address.
+    sacn_sync: BTreeMap<(String, u16), SyncSender<HeldSacnDmx>>,
+}
+
+/// Art-Net 4: a node leaves synchronous mode after 4 s without ArtSync.
+const ART_SYNC_TIMEOUT_S: f64 = 4.0;
+
+/// E1.31: receivers stop waiting for synchronization after the 2.5 s
+/// network data loss timeout.
+const SACN_SYNC_TIMEOUT_S: f64 = 2.5;
+
+#[derive(Debug)]
+struct SyncSender<T> {
+    /// Timestamp of the last synchronization packet.
+    last_sync: Option<f64>,
+    /// Data received since the last synchronization packet, in arrival
+    /// order.
+    held: Vec<T>,
+}
+
+impl<T> Default for SyncSender<T> {
+    fn default() -> Self {
+        Self {
+            last_sync: None,
+            held: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct HeldArtDmx {
+    endpoints: Endpoints,
+    timestamp: Option<f64>,
+    packet: ArtDmx,
+}
+
+#[derive(Debug)]
+struct HeldSacnDmx {
+    endpoints: Endpoints,
+    timestamp: Option<f64>,
+    packet: SacnDmx,
+}
+
+/// sACN frame output by the synchronization gate, with what its packet
+/// wrote.
+#[derive(Debug)]
+pub(crate) struct ReleasedSacnDmx {
+    pub frame: DmxFrame,
+    pub write: FrameWrite,
+}
+
+/// ArtDMX frame output by the ArtSync gate, with what its packet carried.
+#[derive(Debug)]
+pub(crate) struct ReleasedArtDmx {
+    pub frame: DmxFrame,
+    pub endpoints: Endpoints,
+    pub sequence: Option<u8>,
+    /// Slots carried by the packet.
+    pub slots: u16,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct DmxStateKey {
     universe: u16,
     source_id: String,
-    protocol: DmxProtocol,
+    protocol: Protocol,
 }
 
 impl DmxStore {
@@ -37,7 +173,19 @@ impl DmxStore {
         Self::default()
     }
 
+    #[cfg(test)]
     pub(crate) fn push(&mut self, frame: DmxFrame) {
+        self.push_written(frame, FrameWrite::default());
+    }
+
+    /// Store a frame along with the slots and priority of its packet.
+    pub(crate) fn push_written(&mut self, frame: DmxFrame, write: FrameWrite) {
+        self.writes_by_universe
+            .entry(frame.universe)
+            .or_default()
+            .entry(frame.source_id.clone())
+            .or_default()
+            .push(write);
         let per_universe = self.frames_by_universe.entry(frame.universe).or_default();
         per_universe
             .entry(frame.source_id.clone())
@@ -45,11 +193,7 @@ impl DmxStore {
             .push(frame);
     }
 
-    pub(crate) fn frames_for_universe(
-        &self,
-        universe: u16,
-        protocol: DmxProtocol,
-    ) -> Vec<&DmxFrame> {
+    pub(crate) fn frames_for_universe(&self, universe: u16, protocol: Protocol) -> Vec<&DmxFrame> {
         let Some(per_source) = self.frames_by_universe.get(&universe) else {
             return Vec::new();
         };
@@ -61,29 +205,124 @@ impl DmxStore {
             .collect()
     }
 
+    /// Frames of each source of a universe, in arrival order.
+    pub(crate) fn sources_for_universe(
+        &self,
+        universe: u16,
+        protocol: Protocol,
+    ) -> impl Iterator<Item = &[DmxFrame]> {
+        self.frames_by_universe
+            .get(&universe)
+            .into_iter()
+            .flat_map(|per_source| per_source.values())
+            .filter(move |frames| {
+                frames
+                    .first()
+                    .is_some_and(|frame| frame.protocol == protocol)
+            })
+            .map(Vec::as_slice)
+    }
+
+    /// Frames of each source of a universe with what each frame wrote, in
+    /// arrival order per source.
+    pub(crate) fn written_frames(
+        &self,
+        universe: u16,
+        protocol: Protocol,
+    ) -> impl Iterator<Item = (&[DmxFrame], &[FrameWrite])> {
+        let writes = self.writes_by_universe.get(&universe);
+        self.frames_by_universe
+            .get(&universe)
+            .into_iter()
+            .flat_map(|per_source| per_source.iter())
+            .filter(move |(_, frames)| {
+                frames
+                    .first()
+                    .is_some_and(|frame| frame.protocol == protocol)
+            })
+            .filter_map(move |(source_id, frames)| {
+                let writes = writes?.get(source_id)?;
+                Some((frames.as_slice(), writes.as_slice()))
+            })
+    }
+
+    /// Frames of every source of every universe, in arrival order per source.
+    pub(crate) fn sources(&self) -> impl Iterator<Item = &[DmxFrame]> {
+        self.frames_by_universe
+            .values()
+            .flat_map(|per_source| per_source.values())
+            .map(Vec::as_slice)
+    }
+
+    /// Snapshots of the first and last frame of a universe across all its
+    /// sources; ties go to the lowest source id, and to arrival order within
+    /// a source. Frames without timestamps sort first.
+    pub(crate) fn snapshots(
+        &self,
+        universe: u16,
+        protocol: Protocol,
+    ) -> Option<(DmxSnapshot, DmxSnapshot)> {
+        let per_source = self.frames_by_universe.get(&universe)?;
+        let mut sources: Vec<(&String, &Vec<DmxFrame>)> = per_source.iter().collect();
+        sources.sort_by(|a, b| a.0.cmp(b.0));
+        let mut first: Option<&DmxFrame> = None;
+        let mut last: Option<&DmxFrame> = None;
+        for frame in sources
+            .into_iter()
+            .flat_map(|(_, frames)| frames.iter())
+            .filter(|frame| frame.protocol == protocol)
+        {
+            if first.is_none_or(|first| earlier(frame.timestamp, first.timestamp)) {
+                first = Some(frame);
+            }
+            if last.is_none_or(|last| {
+                earlier(last.timestamp, frame.timestamp)
+                    || (last.source_id == frame.source_id
+                        && !earlier(frame.timestamp, last.timestamp))
+            }) {
+                last = Some(frame);
+            }
+        }
+        let snapshot =
+            |frame: &DmxFrame| DmxSnapshot::new(frame.timestamp, &frame.source_id, &frame.slots);
+        Some((snapshot(first?), snapshot(last?)))
+    }
+
     #[allow(dead_code)]
     pub(crate) fn frames_for(&self, universe: u16, source_id: &str) -> Option<&[DmxFrame]> {
         self.frames_by_universe
             .get(&universe)
             .and_then(|per_source| per_source.get(source_id).map(|v| v.as_slice()))
     }
+
+    /// What each frame of `frames_for` wrote, in the same order.
+    pub(crate) fn writes_for(&self, universe: u16, source_id: &str) -> Option<&[FrameWrite]> {
+        self.writes_by_universe
+            .get(&universe)
+            .and_then(|per_source| per_source.get(source_id).map(Vec::as_slice))
+    }
 }
 
 impl DmxStateStore {
-    pub(crate) fn new() -> Self {
+    /// Create an empty state store.
+    pub fn new() -> Self {
         Self::default()
     }
 
-    pub(crate) fn apply_partial(
+    /// Apply partial slot data and return the full 512-slot state.
+    ///
+    /// Slots beyond `partial_slots` keep their last known value; extra bytes
+    /// beyond 512 are ignored.
+    pub fn apply_partial(
         &mut self,
         universe: u16,
-        source_id: String,
-        protocol: DmxProtocol,
+        source_id: impl Into<String>,
+        protocol: Protocol,
         partial_slots: &[u8],
     ) -> [u8; 512] {
         let key = DmxStateKey {
             universe,
-            source_id,
+            source_id: source_id.into(),
             protocol,
         };
         let entry = self.states.entry(key).or_insert([0u8; 512]);
@@ -93,11 +332,416 @@ impl DmxStateStore {
         }
         *entry
     }
+
+    /// Apply a parsed ArtDMX packet and return the reconstructed frame.
+    ///
+    /// The source id is derived from the sender endpoint, as in reports.
+    pub fn apply_artdmx(
+        &mut self,
+        endpoints: &Endpoints,
+        timestamp: Option<f64>,
+        packet: &ArtDmx,
+    ) -> DmxFrame {
+        let source_id = artnet_source_id(&endpoints.src_ip, endpoints.src_port);
+        let slots = self.apply_partial(
+            packet.universe,
+            source_id.clone(),
+            Protocol::ArtNet,
+            &packet.slots,
+        );
+        DmxFrame {
+            universe: packet.universe,
+            timestamp,
+            source_id,
+            protocol: Protocol::ArtNet,
+            slots,
+        }
+    }
+
+    /// Apply a parsed ArtDMX packet the way a node honouring ArtSync would.
+    ///
+    /// While the sender is in synchronous mode the packet is held and no
+    /// frame is returned; otherwise the frame is returned at once, after any
+    /// frames still held from a lapsed synchronous mode.
+    pub fn apply_artdmx_synced(
+        &mut self,
+        endpoints: &Endpoints,
+        timestamp: Option<f64>,
+        packet: &ArtDmx,
+    ) -> Vec<DmxFrame> {
+        frames(self.artdmx_released(*endpoints, timestamp, packet.clone()))
+    }
+
+    /// Apply an ArtSync from the sender of `endpoints`.
+    ///
+    /// Puts the sender in synchronous mode and returns the frames held since
+    /// its previous ArtSync, stamped with `timestamp`.
+    ///
+    /// # Examples
+    /// ```
+    /// use liveshark_core::{ArtDmx, DmxStateStore, Endpoints};
+    ///
+    /// let endpoints = Endpoints {
+    ///     src_ip: "10.0.0.1".parse().unwrap(),
+    ///     src_port: 6454,
+    ///     dst_ip: "10.0.0.255".parse().unwrap(),
+    ///     dst_port: 6454,
+    /// };
+    /// let packet = ArtDmx {
+    ///     protocol_version: 14,
+    ///     sequence: None,
+    ///     physical: 0,
+    ///     sub_uni: 1,
+    ///     net: 0,
+    ///     universe: 1,
+    ///     slots: vec![255, 0],
+    /// };
+    /// let mut state = DmxStateStore::new();
+    /// assert!(state.apply_artsync(&endpoints, Some(0.0)).is_empty());
+    /// assert!(state.apply_artdmx_synced(&endpoints, Some(0.01), &packet).is_empty());
+    /// let frames = state.apply_artsync(&endpoints, Some(0.02));
+    /// assert_eq!(frames[0].timestamp, Some(0.02));
+    /// assert_eq!(frames[0].slots[0], 255);
+    /// ```
+    pub fn apply_artsync(
+        &mut self,
+        endpoints: &Endpoints,
+        timestamp: Option<f64>,
+    ) -> Vec<DmxFrame> {
+        frames(self.artsync_released(endpoints, timestamp))
+    }
+
+    /// Release every held ArtDMX frame at its own timestamp, as at the end
+    /// of a capture.
+    pub fn flush_artsync(&mut self) -> Vec<DmxFrame> {
+        frames(self.flush_released())
+    }
+
+    pub(crate) fn artdmx_released(
+        &mut self,
+        endpoints: Endpoints,
+        timestamp: Option<f64>,
+        packet: ArtDmx,
+    ) -> Vec<ReleasedArtDmx> {
+        let held = HeldArtDmx {
+            endpoints,
+            timestamp,
+            packet,
+        };
+        if let Some(sender) = self.art_sync.get_mut(&endpoints.src_ip) {
+            if in_sync_mode(sender.last_sync, timestamp, ART_SYNC_TIMEOUT_S) {
+                sender.held.push(held);
+                return Vec::new();
+            }
+        }
+        let lapsed = self
+            .art_sync
+            .remove(&endpoints.src_ip)
+            .map(|sender| sender.held)
+            .unwrap_or_default();
+        lapsed
+            .into_iter()
+            .chain([held])
+            .map(|held| {
+                let timestamp = held.timestamp;
+                self.release(held, timestamp)
+            })
+            .collect()
+    }
+
+    pub(crate) fn artsync_released(
+        &mut self,
+        endpoints: &Endpoints,
+        timestamp: Option<f64>,
+    ) -> Vec<ReleasedArtDmx> {
+        let sender = self.art_sync.entry(endpoints.src_ip).or_default();
+        let on_time = in_sync_mode(sender.last_sync, timestamp, ART_SYNC_TIMEOUT_S);
+        sender.last_sync = timestamp.or(sender.last_sync);
+        let held = std::mem::take(&mut sender.held);
+        held.into_iter()
+            .map(|held| {
+                let released_at = if on_time { timestamp } else { held.timestamp };
+                self.release(held, released_at)
+            })
+            .collect()
+    }
+
+    pub(crate) fn flush_released(&mut self) -> Vec<ReleasedArtDmx> {
+        std::mem::take(&mut self.art_sync)
+            .into_values()
+            .flat_map(|sender| sender.held)
+            .map(|held| {
+                let timestamp = held.timestamp;
+                self.release(held, timestamp)
+            })
+            .collect()
+    }
+
+    fn release(&mut self, held: HeldArtDmx, timestamp: Option<f64>) -> ReleasedArtDmx {
+        let frame = self.apply_artdmx(&held.endpoints, timestamp, &held.packet);
+        ReleasedArtDmx {
+            frame,
+            endpoints: held.endpoints,
+            sequence: held.packet.sequence,
+            slots: held.packet.slots.len().min(512) as u16,
+        }
+    }
+
+    /// Apply a parsed sACN DMX packet and return the reconstructed frame.
+    ///
+    /// The source id is derived from the CID (or the sender endpoint when the
+    /// CID is empty), as in reports.
+    pub fn apply_sacn_dmx(
+        &mut self,
+        endpoints: &Endpoints,
+        timestamp: Option<f64>,
+        packet: &SacnDmx,
+    ) -> DmxFrame {
+        let source_id = sacn_source_id(&packet.cid, &endpoints.src_ip, endpoints.src_port);
+        let slots = self.apply_partial(
+            packet.universe,
+            source_id.clone(),
+            Protocol::Sacn,
+            &packet.slots,
+        );
+        DmxFrame {
+            universe: packet.universe,
+            timestamp,
+            source_id,
+            protocol: Protocol::Sacn,
+            slots,
+        }
+    }
+
+    /// Apply a parsed sACN DMX packet the way a receiver honouring E1.31
+    /// synchronization would.
+    ///
+    /// A packet naming a sync address is held while its source is
+    /// synchronizing that address and no frame is returned; otherwise the
+    /// frame is returned at once, after any frames still held from a lapsed
+    /// synchronization.
+    pub fn apply_sacn_dmx_synced(
+        &mut self,
+        endpoints: &Endpoints,
+        timestamp: Option<f64>,
+        packet: &SacnDmx,
+    ) -> Vec<DmxFrame> {
+        sacn_frames(self.sacn_dmx_released(*endpoints, timestamp, packet.clone()))
+    }
+
+    /// Apply an sACN synchronization packet.
+    ///
+    /// Starts synchronization of its source on its sync address and returns
+    /// the frames held since the previous synchronization packet, stamped
+    /// with `timestamp`.
+    ///
+    /// # Examples
+    /// ```
+    /// use liveshark_core::{DmxStateStore, Endpoints, SacnDmx, SacnSync};
+    ///
+    /// let endpoints = Endpoints {
+    ///     src_ip: "10.0.0.1".parse().unwrap(),
+    ///     src_port: 5568,
+    ///     dst_ip: "239.255.0.1".parse().unwrap(),
+    ///     dst_port: 5568,
+    /// };
+    /// let sync = SacnSync {
+    ///     cid: "console".to_string(),
+    ///     sequence: 0,
+    ///     sync_address: 7000,
+    /// };
+    /// let packet = SacnDmx {
+    ///     cid: "console".to_string(),
+    ///     source_name: None,
+    ///     source_name_issue: None,
+    ///     priority: 100,
+    ///     sync_address: Some(7000),
+    ///     sequence: None,
+    ///     options: 0,
+    ///     universe: 1,
+    ///     address_type: 0xa1,
+    ///     first_property_address: 0,
+    ///     address_increment: 1,
+    ///     start_code: 0,
+    ///     slots: vec![255, 0],
+    /// };
+    /// let mut state = DmxStateStore::new();
+    /// assert!(state.apply_sacn_sync(Some(0.0), &sync).is_empty());
+    /// assert!(state.apply_sacn_dmx_synced(&endpoints, Some(0.01), &packet).is_empty());
+    /// let frames = state.apply_sacn_sync(Some(0.02), &sync);
+    /// assert_eq!(frames[0].timestamp, Some(0.02));
+    /// assert_eq!(frames[0].slots[0], 255);
+    /// ```
+    pub fn apply_sacn_sync(&mut self, timestamp: Option<f64>, sync: &SacnSync) -> Vec<DmxFrame> {
+        sacn_frames(self.sacn_sync_released(timestamp, sync))
+    }
+
+    /// Release every held sACN frame at its own timestamp, as at the end of
+    /// a capture.
+    pub fn flush_sacn_sync(&mut self) -> Vec<DmxFrame> {
+        sacn_frames(self.flush_sacn_released())
+    }
+
+    pub(crate) fn sacn_dmx_released(
+        &mut self,
+        endpoints: Endpoints,
+        timestamp: Option<f64>,
+        packet: SacnDmx,
+    ) -> Vec<ReleasedSacnDmx> {
+        let Some(sync_address) = packet.sync_address else {
+            return vec![self.release_sacn(HeldSacnDmx {
+                endpoints,
+                timestamp,
+                packet,
+            })];
+        };
+        let key = (packet.cid.clone(), sync_address);
+        let held = HeldSacnDmx {
+            endpoints,
+            timestamp,
+            packet,
+        };
+        if let Some(sender) = self.sacn_sync.get_mut(&key) {
+            if in_sync_mode(sender.last_sync, timestamp, SACN_SYNC_TIMEOUT_S) {
+                sender.held.push(held);
+                return Vec::new();
+            }
+        }
+        let lapsed = self
+            .sacn_sync
+            .remove(&key)
+            .map(|sender| sender.held)
+            .unwrap_or_default();
+        lapsed
+            .into_iter()
+            .chain([held])
+            .map(|held| self.release_sacn(held))
+            .collect()
+    }
+
+    pub(crate) fn sacn_sync_released(
+        &mut self,
+        timestamp: Option<f64>,
+        sync: &SacnSync,
+    ) -> Vec<ReleasedSacnDmx> {
+        let sender = self
+            .sacn_sync
+            .entry((sync.cid.clone(), sync.sync_address))
+            .or_default();
+        let on_time = in_sync_mode(sender.last_sync, timestamp, SACN_SYNC_TIMEOUT_S);
+        sender.last_sync = timestamp.or(sender.last_sync);
+        let held = std::mem::take(&mut sender.held);
+        held.into_iter()
+            .map(|mut held| {
+                if on_time {
+                    held.timestamp = timestamp;
+                }
+                self.release_sacn(held)
+            })
+            .collect()
+    }
+
+    pub(crate) fn flush_sacn_released(&mut self) -> Vec<ReleasedSacnDmx> {
+        std::mem::take(&mut self.sacn_sync)
+            .into_values()
+            .flat_map(|sender| sender.held)
+            .map(|held| self.release_sacn(held))
+            .collect()
+    }
+
+    fn release_sacn(&mut self, held: HeldSacnDmx) -> ReleasedSacnDmx {
+        let frame = self.apply_sacn_dmx(&held.endpoints, held.timestamp, &held.packet);
+        ReleasedSacnDmx {
+            frame,
+            write: FrameWrite {
+                slots: held.packet.slots.len().min(512) as u16,
+                priority: Some(held.packet.priority),
+            },
+        }
+    }
+
+    /// Current state for a source, if any packet has been applied.
+    pub fn state(&self, universe: u16, source_id: &str, protocol: Protocol) -> Option<&[u8; 512]> {
+        let key = DmxStateKey {
+            universe,
+            source_id: source_id.to_string(),
+            protocol,
+        };
+        self.states.get(&key)
+    }
+}
+
+fn frames(released: Vec<ReleasedArtDmx>) -> Vec<DmxFrame> {
+    released
+        .into_iter()
+        .map(|released| released.frame)
+        .collect()
+}
+
+fn sacn_frames(released: Vec<ReleasedSacnDmx>) -> Vec<DmxFrame> {
+    released
+        .into_iter()
+        .map(|released| released.frame)
+        .collect()
+}
+
+/// Whether a sender synchronized at `last_sync` still is at `timestamp`,
+/// given the protocol's `timeout`; unknown times keep it synchronized.
+fn in_sync_mode(last_sync: Option<f64>, timestamp: Option<f64>, timeout: f64) -> bool {
+    match (last_sync, timestamp) {
+        (Some(last_sync), Some(timestamp)) => timestamp - last_sync <= timeout,
+        _ => true,
+    }
+}
+
+/// Strict timestamp order, with missing timestamps first.
+fn earlier(a: Option<f64>, b: Option<f64>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a < b,
+        (None, Some(_)) => true,
+        _ => false,
+    }
+}
+
+pub(crate) fn artnet_source_id(source_ip: &IpAddr, source_port: u16) -> String {
+    format!("artnet:{}:{}", source_ip, source_port)
+}
+
+pub(crate) fn sacn_source_id(cid: &str, source_ip: &IpAddr, source_port: u16) -> String {
+    if cid.is_empty() {
+        format!("sacn:{}:{}", source_ip, source_port)
+    } else {
+        format!("sacn:cid:{}", cid)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{DmxFrame, DmxProtocol, DmxStateStore, DmxStore};
+    use super::{DmxFrame, DmxStateStore, DmxStore};
+    use crate::protocols::artnet::ArtDmx;
+    use crate::protocols::sacn::{SacnDmx, SacnSync};
+    use crate::{Endpoints, Protocol};
+
+    fn endpoints(src_ip: &str) -> Endpoints {
+        Endpoints {
+            src_ip: src_ip.parse().unwrap(),
+            src_port: 6454,
+            dst_ip: "10.0.0.255".parse().unwrap(),
+            dst_port: 6454,
+        }
+    }
+
+    fn artdmx(universe: u16, value: u8) -> ArtDmx {
+        ArtDmx {
+            protocol_version: 14,
+            sequence: None,
+            physical: 0,
+            sub_uni: universe as u8,
+            net: 0,
+            universe,
+            slots: vec![value, value],
+        }
+    }
 
     #[test]
     fn stores_frames_by_universe_and_source() {
@@ -109,7 +753,7 @@ mod tests {
             universe: 1,
             timestamp: Some(1.0),
             source_id: "artnet:10.0.0.1:6454".to_string(),
-            protocol: DmxProtocol::ArtNet,
+            protocol: Protocol::ArtNet,
             slots,
         };
 
@@ -119,13 +763,164 @@ mod tests {
         assert_eq!(stored[0], frame);
     }
 
+    #[test]
+    fn artsync_holds_frames_per_sender_until_the_next_sync() {
+        let desk = endpoints("10.0.0.1");
+        let other = endpoints("10.0.0.2");
+        let mut state = DmxStateStore::new();
+        assert!(state.apply_artsync(&desk, Some(1.0)).is_empty());
+
+        assert!(
+            state
+                .apply_artdmx_synced(&desk, Some(1.01), &artdmx(1, 10))
+                .is_empty()
+        );
+        assert!(
+            state
+                .apply_artdmx_synced(&desk, Some(1.02), &artdmx(2, 20))
+                .is_empty()
+        );
+        // Senders that never sent ArtSync are output at once.
+        let frames = state.apply_artdmx_synced(&other, Some(1.03), &artdmx(1, 30));
+        assert_eq!(frames[0].timestamp, Some(1.03));
+        assert_eq!(
+            state.state(1, "artnet:10.0.0.1:6454", Protocol::ArtNet),
+            None
+        );
+
+        let frames = state.apply_artsync(&desk, Some(1.04));
+        let released: Vec<(u16, Option<f64>, u8)> = frames
+            .iter()
+            .map(|frame| (frame.universe, frame.timestamp, frame.slots[0]))
+            .collect();
+        assert_eq!(released, vec![(1, Some(1.04), 10), (2, Some(1.04), 20)]);
+    }
+
+    #[test]
+    fn artsync_mode_lapses_after_the_timeout() {
+        let desk = endpoints("10.0.0.1");
+        let mut state = DmxStateStore::new();
+        state.apply_artsync(&desk, Some(1.0));
+        assert!(
+            state
+                .apply_artdmx_synced(&desk, Some(2.0), &artdmx(1, 10))
+                .is_empty()
+        );
+        // No ArtSync for more than 4 s: held frames keep their own time.
+        let frames = state.apply_artdmx_synced(&desk, Some(5.5), &artdmx(1, 20));
+        let times: Vec<Option<f64>> = frames.iter().map(|frame| frame.timestamp).collect();
+        assert_eq!(times, vec![Some(2.0), Some(5.5)]);
+
+        state.apply_artsync(&desk, Some(6.0));
+        assert!(
+            state
+                .apply_artdmx_synced(&desk, Some(6.5), &artdmx(1, 30))
+                .is_empty()
+        );
+        let frames = state.flush_artsync();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].timestamp, Some(6.5));
+    }
+
+    fn sacn_dmx(universe: u16, sync_address: Option<u16>, value: u8) -> SacnDmx {
+        SacnDmx {
+            cid: "console".to_string(),
+            source_name: None,
+            source_name_issue: None,
+            priority: 100,
+            sync_address,
+            sequence: None,
+            options: 0,
+            universe,
+            address_type: 0xa1,
+            first_property_address: 0,
+            address_increment: 1,
+            start_code: 0,
+            slots: vec![value, value],
+        }
+    }
+
+    fn sacn_sync(sync_address: u16) -> SacnSync {
+        SacnSync {
+            cid: "console".to_string(),
+            sequence: 0,
+            sync_address,
+        }
+    }
+
+    #[test]
+    fn sacn_sync_holds_frames_per_source_and_address() {
+        let console = endpoints("10.0.0.1");
+        let mut state = DmxStateStore::new();
+        // Before any synchronization packet, data is output at once.
+        let frames = state.apply_sacn_dmx_synced(&console, Some(0.5), &sacn_dmx(1, Some(7000), 5));
+        assert_eq!(frames.len(), 1);
+        assert!(
+            state
+                .apply_sacn_sync(Some(1.0), &sacn_sync(7000))
+                .is_empty()
+        );
+
+        for (universe, ts) in [(1, 1.01), (2, 1.02)] {
+            let packet = sacn_dmx(universe, Some(7000), universe as u8 * 10);
+            assert!(
+                state
+                    .apply_sacn_dmx_synced(&console, Some(ts), &packet)
+                    .is_empty()
+            );
+        }
+        // Unsynchronized universes of the same source are not held.
+        let frames = state.apply_sacn_dmx_synced(&console, Some(1.03), &sacn_dmx(3, None, 30));
+        assert_eq!(frames[0].timestamp, Some(1.03));
+        // Another sync address releases nothing.
+        assert!(
+            state
+                .apply_sacn_sync(Some(1.035), &sacn_sync(7001))
+                .is_empty()
+        );
+
+        let frames = state.apply_sacn_sync(Some(1.04), &sacn_sync(7000));
+        let released: Vec<(u16, Option<f64>, u8)> = frames
+            .iter()
+            .map(|frame| (frame.universe, frame.timestamp, frame.slots[0]))
+            .collect();
+        assert_eq!(released, vec![(1, Some(1.04), 10), (2, Some(1.04), 20)]);
+    }
+
+    #[test]
+    fn sacn_sync_lapses_after_the_network_data_loss_timeout() {
+        let console = endpoints("10.0.0.1");
+        let mut state = DmxStateStore::new();
+        state.apply_sacn_sync(Some(1.0), &sacn_sync(7000));
+        let packet = sacn_dmx(1, Some(7000), 10);
+        assert!(
+            state
+                .apply_sacn_dmx_synced(&console, Some(2.0), &packet)
+                .is_empty()
+        );
+        // No synchronization packet for more than 2.5 s.
+        let frames = state.apply_sacn_dmx_synced(&console, Some(3.6), &packet);
+        let times: Vec<Option<f64>> = frames.iter().map(|frame| frame.timestamp).collect();
+        assert_eq!(times, vec![Some(2.0), Some(3.6)]);
+
+        state.apply_sacn_sync(Some(4.0), &sacn_sync(7000));
+        assert!(
+            state
+                .apply_sacn_dmx_synced(&console, Some(4.5), &packet)
+                .is_empty()
+        );
+        let frames = state.flush_sacn_sync();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].timestamp, Some(4.5));
+    }
+
     #[test]
     fn stateful_reconstruction_retains_last_known_values_artnet() {
         let mut state = DmxStateStore::new();
         let slots = state.apply_partial(
             1,
             "artnet:10.0.0.1:6454".to_string(),
-            DmxProtocol::ArtNet,
+            Protocol::ArtNet,
             &[10, 11, 12, 13, 14, 15, 16, 17, 18, 19],
         );
         assert_eq!(&slots[..10], &[10, 11, 12, 13, 14, 15, 16, 17, 18, 19]);
@@ -135,7 +930,7 @@ mod tests {
         let slots = state.apply_partial(
             1,
             "artnet:10.0.0.1:6454".to_string(),
-            DmxProtocol::ArtNet,
+            Protocol::ArtNet,
             &[42, 43, 44, 45, 46],
         );
         assert_eq!(&slots[..5], &[42, 43, 44, 45, 46]);
@@ -150,7 +945,7 @@ mod tests {
         let slots = state.apply_partial(
             1,
             "sacn:cid:00112233445566778899aabbccddeeff".to_string(),
-            DmxProtocol::Sacn,
+            Protocol::Sacn,
             &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10],
         );
         assert_eq!(&slots[..10], &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
@@ -160,7 +955,7 @@ mod tests {
         let slots = state.apply_partial(
             1,
             "sacn:cid:00112233445566778899aabbccddeeff".to_string(),
-            DmxProtocol::Sacn,
+            Protocol::Sacn,
             &[200, 201, 202, 203, 204],
         );
         assert_eq!(&slots[..5], &[200, 201, 202, 203, 204]);
@@ -174,15 +969,38 @@ mod tests {
         let mut state = DmxStateStore::new();
         let source_id = "source:example".to_string();
 
-        let artnet_slots = state.apply_partial(1, source_id.clone(), DmxProtocol::ArtNet, &[9, 8]);
-        let sacn_slots = state.apply_partial(1, source_id.clone(), DmxProtocol::Sacn, &[1, 2]);
-        let other_universe = state.apply_partial(2, source_id.clone(), DmxProtocol::ArtNet, &[7]);
+        let artnet_slots = state.apply_partial(1, source_id.clone(), Protocol::ArtNet, &[9, 8]);
+        let sacn_slots = state.apply_partial(1, source_id.clone(), Protocol::Sacn, &[1, 2]);
+        let other_universe = state.apply_partial(2, source_id.clone(), Protocol::ArtNet, &[7]);
 
         assert_eq!(&artnet_slots[..2], &[9, 8]);
         assert_eq!(&sacn_slots[..2], &[1, 2]);
         assert_eq!(other_universe[0], 7);
 
-        let artnet_again = state.apply_partial(1, source_id, DmxProtocol::ArtNet, &[]);
+        let artnet_again = state.apply_partial(1, source_id, Protocol::ArtNet, &[]);
         assert_eq!(&artnet_again[..2], &[9, 8]);
     }
+
+    #[test]
+    fn snapshots_take_the_first_and_last_frame_across_sources() {
+        let mut store = DmxStore::new();
+        let frame = |source: &str, ts: f64, value: u8| DmxFrame {
+            universe: 1,
+            timestamp: Some(ts),
+            source_id: source.to_string(),
+            protocol: Protocol::ArtNet,
+            slots: [value; 512],
+        };
+        store.push(frame("artnet:10.0.0.2:6454", 1.0, 10));
+        store.push(frame("artnet:10.0.0.1:6454", 2.0, 20));
+        store.push(frame("artnet:10.0.0.2:6454", 3.0, 30));
+        store.push(frame("artnet:10.0.0.2:6454", 3.0, 31));
+
+        let (first, last) = store.snapshots(1, Protocol::ArtNet).unwrap();
+        assert_eq!(first.source_id, "artnet:10.0.0.2:6454");
+        assert_eq!(first.runs, vec![(10, 512)]);
+        assert_eq!(last.timestamp, Some(3.0));
+        assert_eq!(last.runs, vec![(31, 512)]);
+        assert!(store.snapshots(1, Protocol::Sacn).is_none());
+    }
 }
@@ -0,0 +1,264 @@
+//! Fade detection and characterization.
+//!
+//! A fade is a channel ramping in one direction over several frames. How
+//! smooth it looks depends on how far the level jumps between frames: a
+//! console at 40 fps moves a full-scale two-second fade by about 3 per
+//! frame, while the same fade at 10 fps, or across a run of lost frames,
+//! jumps by 13 or more and reads as "chunky" on stage. Fades are reported
+//! with their largest step and the frame rate and gaps of the source while
+//! they ran, and flagged when the steps are large because of either.
+
+use std::collections::BTreeMap;
+
+use super::dmx::DmxFrame;
+use super::{AVERAGE_SCALE, round_to};
+use crate::{Fade, FadeDirection, SteppyCause};
+
+/// Smallest overall level change of a fade (about 10% of full scale).
+const MIN_FADE_DELTA: u8 = 26;
+/// Fewest level changes in a fade; one or two jumps are a snap, not a ramp.
+const MIN_FADE_STEPS: u32 = 3;
+/// Shortest fade (seconds).
+const MIN_FADE_S: f64 = 0.25;
+/// Longest hold inside a fade (seconds); a slow fade at a high frame rate
+/// repeats each level for a few frames, a longer hold ends the fade.
+const MAX_FADE_HOLD_S: f64 = 0.5;
+/// Largest step of a smooth fade (about 4% of full scale).
+const MAX_SMOOTH_STEP: u8 = 10;
+/// Frame rate below which large steps are blamed on the source rate.
+const MIN_SMOOTH_FPS: f64 = 20.0;
+/// Frame interval, as a multiple of the median interval of the fade, from
+/// which an interval counts as a gap (lost or late frames).
+const GAP_FACTOR: f64 = 3.0;
+
+/// Ramp of one channel, as frame indices into the timed frames.
+struct Ramp {
+    start: usize,
+    end: usize,
+    from: u8,
+    to: u8,
+    steps: u32,
+    max_step: u8,
+}
+
+impl Ramp {
+    fn rising(&self) -> bool {
+        self.to > self.from
+    }
+
+    fn is_fade(&self) -> bool {
+        self.steps >= MIN_FADE_STEPS && self.from.abs_diff(self.to) >= MIN_FADE_DELTA
+    }
+}
+
+/// Fades of one source, grouped into ranges of adjacent channels that faded
+/// over the same frames; sorted by start frame, then first channel. Frames
+/// without timestamps are ignored.
+pub(crate) fn detect_fades(frames: &[DmxFrame]) -> Vec<Fade> {
+    let timed: Vec<(f64, &[u8; 512])> = frames
+        .iter()
+        .filter_map(|frame| Some((frame.timestamp?, &frame.slots)))
+        .collect();
+    let Some(first) = frames.first() else {
+        return Vec::new();
+    };
+
+    // Channels (0-based) of each (start, end, rising) ramp, in channel order.
+    let mut groups: BTreeMap<(usize, usize, bool), Vec<(usize, Ramp)>> = BTreeMap::new();
+    for channel in 0..512 {
+        for ramp in channel_ramps(&timed, channel) {
+            groups
+                .entry((ramp.start, ramp.end, ramp.rising()))
+                .or_default()
+                .push((channel, ramp));
+        }
+    }
+
+    let mut fades = Vec::new();
+    for ((start, end, rising), ramps) in groups {
+        let mut ramps = ramps.into_iter().peekable();
+        while let Some((channel, ramp)) = ramps.next() {
+            let mut last_channel = channel;
+            let mut max_delta = ramp.from.abs_diff(ramp.to);
+            let mut steps = ramp.steps;
+            let mut max_step = ramp.max_step;
+            while let Some((next, next_ramp)) = ramps.next_if(|(next, _)| *next == last_channel + 1)
+            {
+                last_channel = next;
+                max_delta = max_delta.max(next_ramp.from.abs_diff(next_ramp.to));
+                steps = steps.max(next_ramp.steps);
+                max_step = max_step.max(next_ramp.max_step);
+            }
+            let Some((fps, max_gap_s, median_gap_s)) = frame_timing(&timed[start..=end]) else {
+                continue;
+            };
+            let duration_s = timed[end].0 - timed[start].0;
+            // Large steps at a healthy rate without gaps are the console's
+            // own fade curve, not a network symptom.
+            let steppy_cause = if max_step <= MAX_SMOOTH_STEP {
+                None
+            } else if max_gap_s > GAP_FACTOR * median_gap_s {
+                Some(SteppyCause::FrameGap)
+            } else if fps < MIN_SMOOTH_FPS {
+                Some(SteppyCause::LowFrameRate)
+            } else {
+                None
+            };
+            fades.push(Fade {
+                timestamp: timed[start].0,
+                duration_s: round_to(duration_s, AVERAGE_SCALE),
+                universe: first.universe,
+                proto: first.protocol,
+                source_id: first.source_id.clone(),
+                start_channel: channel as u16 + 1,
+                end_channel: last_channel as u16 + 1,
+                direction: if rising {
+                    FadeDirection::Up
+                } else {
+                    FadeDirection::Down
+                },
+                delta: max_delta,
+                steps,
+                max_step,
+                fps: round_to(fps, AVERAGE_SCALE),
+                max_gap_ms: round_to(max_gap_s * 1000.0, AVERAGE_SCALE),
+                steppy_cause,
+            });
+        }
+    }
+    fades.sort_by(|a, b| {
+        a.timestamp
+            .total_cmp(&b.timestamp)
+            .then(a.start_channel.cmp(&b.start_channel))
+    });
+    fades
+}
+
+/// Monotonic ramps of one channel that qualify as fades.
+fn channel_ramps(timed: &[(f64, &[u8; 512])], channel: usize) -> Vec<Ramp> {
+    let mut ramps = Vec::new();
+    let mut current: Option<Ramp> = None;
+    let mut finish = |ramp: Option<Ramp>| {
+        if let Some(ramp) = ramp
+            .filter(|ramp| ramp.is_fade() && timed[ramp.end].0 - timed[ramp.start].0 >= MIN_FADE_S)
+        {
+            ramps.push(ramp);
+        }
+    };
+    for index in 1..timed.len() {
+        let before = timed[index - 1].1[channel];
+        let after = timed[index].1[channel];
+        let held_too_long = |ramp: &Ramp| timed[index].0 - timed[ramp.end].0 > MAX_FADE_HOLD_S;
+        if before == after {
+            if current.as_ref().is_some_and(held_too_long) {
+                finish(current.take());
+            }
+            continue;
+        }
+        let step = before.abs_diff(after);
+        match current.as_mut() {
+            Some(ramp) if ramp.rising() == (after > before) && !held_too_long(ramp) => {
+                ramp.end = index;
+                ramp.to = after;
+                ramp.steps += 1;
+                ramp.max_step = ramp.max_step.max(step);
+            }
+            _ => {
+                finish(current.take());
+                current = Some(Ramp {
+                    start: index - 1,
+                    end: index,
+                    from: before,
+                    to: after,
+                    steps: 1,
+                    max_step: step,
+                });
+            }
+        }
+    }
+    finish(current);
+    ramps
+}
+
+/// Frame rate, largest and median frame interval (seconds) over a span of
+/// frames; `None` when the span has no duration.
+fn frame_timing(span: &[(f64, &[u8; 512])]) -> Option<(f64, f64, f64)> {
+    let mut intervals: Vec<f64> = span.windows(2).map(|pair| pair[1].0 - pair[0].0).collect();
+    let duration: f64 = intervals.iter().sum();
+    if duration <= 0.0 {
+        return None;
+    }
+    intervals.sort_by(f64::total_cmp);
+    let max = intervals.last().copied()?;
+    let median = intervals[intervals.len() / 2];
+    Some((intervals.len() as f64 / duration, max, median))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::detect_fades;
+    use crate::analysis::dmx::DmxFrame;
+    use crate::{FadeDirection, Protocol, SteppyCause};
+
+    /// Frames at `interval_s` where slots 1-3 follow `level(step)`.
+    fn frames(interval_s: f64, levels: impl Iterator<Item = u8>) -> Vec<DmxFrame> {
+        levels
+            .enumerate()
+            .map(|(step, level)| {
+                let mut slots = [0u8; 512];
+                slots[..3].fill(level);
+                DmxFrame {
+                    universe: 1,
+                    timestamp: Some(step as f64 * interval_s),
+                    source_id: "artnet:10.0.0.1:6454".to_string(),
+                    protocol: Protocol::ArtNet,
+                    slots,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn smooth_fade_is_grouped_and_not_flagged() {
+        // Two-second fade up at 40 fps, then a hold.
+        let levels = (0u32..=80)
+            .map(|step| (step * 255 / 80) as u8)
+            .chain(std::iter::repeat_n(255, 40));
+        let fades = detect_fades(&frames(0.025, levels));
+        assert_eq!(fades.len(), 1);
+        let fade = &fades[0];
+        assert_eq!((fade.start_channel, fade.end_channel), (1, 3));
+        assert_eq!(fade.direction, FadeDirection::Up);
+        assert_eq!(fade.delta, 255);
+        assert_eq!(fade.duration_s, 2.0);
+        assert_eq!(fade.fps, 40.0);
+        assert!(fade.max_step <= 4);
+        assert_eq!(fade.steppy_cause, None);
+    }
+
+    #[test]
+    fn fade_at_low_frame_rate_is_steppy() {
+        let levels = (0u32..=20).map(|step| 255 - (step * 255 / 20) as u8);
+        let fades = detect_fades(&frames(0.1, levels));
+        assert_eq!(fades.len(), 1);
+        assert_eq!(fades[0].direction, FadeDirection::Down);
+        assert_eq!(fades[0].steppy_cause, Some(SteppyCause::LowFrameRate));
+    }
+
+    #[test]
+    fn fade_across_lost_frames_is_steppy() {
+        let mut frames = frames(0.025, (0u32..=80).map(|step| (step * 255 / 80) as u8));
+        // Lose a quarter of a second in the middle of the fade.
+        frames.drain(30..40);
+        let fades = detect_fades(&frames);
+        assert_eq!(fades.len(), 1);
+        assert_eq!(fades[0].max_gap_ms, 275.0);
+        assert_eq!(fades[0].steppy_cause, Some(SteppyCause::FrameGap));
+    }
+
+    #[test]
+    fn snaps_are_not_fades() {
+        let levels = [0u8, 0, 255, 255, 0, 0].into_iter();
+        assert!(detect_fades(&frames(0.025, levels)).is_empty());
+    }
+}
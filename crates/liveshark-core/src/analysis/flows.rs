@@ -1,9 +1,13 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::net::IpAddr;
 
-use crate::FlowSummary;
+use crate::{FlowSeries, FlowSummary, Protocol, WifiRetries};
 
+use super::config::AnalyzerConfig;
+use super::microbursts::BurstWindow;
+use super::series::{FlowSecond, Timeline, second_of};
 use super::udp::UdpPacket;
+use super::{AVERAGE_SCALE, round_to};
 
 #[derive(Debug, Hash, PartialEq, Eq)]
 pub(crate) struct FlowKey {
@@ -11,6 +15,8 @@ pub(crate) struct FlowKey {
     pub src_port: u16,
     pub dst_ip: IpAddr,
     pub dst_port: u16,
+    pub vlan: Option<u16>,
+    pub outer_vlan: Option<u16>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -32,14 +38,25 @@ pub(crate) struct FlowStats {
     pub peak_bps: Option<f64>,
     pub peak_window_packets: u64,
     pub peak_window_bytes: u64,
+    /// Short-window peaks.
+    pub microburst: BurstWindow,
+    /// Packets per UDP payload size, for the size distribution.
+    pub payload_sizes: BTreeMap<u64, u64>,
+    /// Per-second buckets, filled only when series are requested.
+    pub seconds: BTreeMap<i64, FlowSecond>,
+    /// Application protocol recognized in the flow's datagrams; plain UDP
+    /// when none was.
+    pub app_proto: Option<Protocol>,
+    /// 802.11 data frames and retried ones, for flows captured over the air.
+    pub wifi_frames: u64,
+    pub wifi_retries: u64,
 }
 
-const PPS_BPS_WINDOW_S: f64 = 1.0;
-const JITTER_WINDOW_S: f64 = 10.0;
-
 pub(crate) fn add_flow_stats(
     stats: &mut HashMap<FlowKey, FlowStats>,
+    config: &AnalyzerConfig,
     packet: &UdpPacket<'_>,
+    app_proto: Option<Protocol>,
     ts: Option<f64>,
 ) {
     let key = FlowKey {
@@ -47,16 +64,43 @@ pub(crate) fn add_flow_stats(
         src_port: packet.src_port,
         dst_ip: packet.dst_ip,
         dst_port: packet.dst_port,
+        vlan: packet.vlan,
+        outer_vlan: packet.outer_vlan,
     };
     let entry = stats.entry(key).or_default();
     entry.packets += 1;
     entry.bytes += packet.payload.len() as u64;
-    update_flow_jitter(entry, ts);
-    update_flow_rates(entry, ts, packet.payload.len() as u64);
+    if app_proto.is_some() {
+        entry.app_proto = app_proto;
+    }
+    if let Some(retry) = packet.wifi_retry {
+        entry.wifi_frames += 1;
+        entry.wifi_retries += u64::from(retry);
+    }
+    *entry
+        .payload_sizes
+        .entry(packet.payload.len() as u64)
+        .or_default() += 1;
+    if let (true, Some(ts)) = (config.series, ts) {
+        let second = entry.seconds.entry(second_of(ts)).or_default();
+        second.packets += 1;
+        second.bytes += packet.payload.len() as u64;
+    }
+    update_flow_jitter(entry, ts, config.flow_jitter_window_s);
+    entry
+        .microburst
+        .add(ts, packet.payload.len() as u64, config.microburst_window_s);
+    update_flow_rates(
+        entry,
+        ts,
+        packet.payload.len() as u64,
+        config.flow_rate_window_s,
+    );
 }
 
 pub(crate) fn build_flow_summaries(
     stats: HashMap<FlowKey, FlowStats>,
+    config: &AnalyzerConfig,
     _duration_s: Option<f64>,
 ) -> Vec<FlowSummary> {
     let mut flows: Vec<FlowSummary> = stats
@@ -68,7 +112,7 @@ pub(crate) fn build_flow_summaries(
                 None
             };
             let (pps_peak_1s, bps_peak_1s) = match (stats.first_ts, stats.last_ts) {
-                (Some(start), Some(end)) if end - start >= PPS_BPS_WINDOW_S => (
+                (Some(start), Some(end)) if end - start >= config.flow_rate_window_s => (
                     Some(stats.peak_window_packets),
                     Some(stats.peak_window_bytes),
                 ),
@@ -85,33 +129,91 @@ pub(crate) fn build_flow_summaries(
                 _ => (None, None),
             };
             let iat_jitter_ms = stats.jitter_peak.map(|value| value * 1000.0);
+            let sizes = &stats.payload_sizes;
 
             FlowSummary {
-                app_proto: "udp".to_string(),
+                app_proto: stats.app_proto.unwrap_or(Protocol::Udp),
                 src: format_endpoint(key.src_ip, key.src_port),
                 dst: format_endpoint(key.dst_ip, key.dst_port),
+                vlan: key.vlan,
+                outer_vlan: key.outer_vlan,
                 pps,
                 bps,
                 iat_jitter_ms,
                 max_iat_ms,
                 pps_peak_1s,
                 bps_peak_1s,
+                payload_size_min: sizes.keys().next().copied(),
+                payload_size_avg: (stats.packets > 0)
+                    .then(|| round_to(stats.bytes as f64 / stats.packets as f64, AVERAGE_SCALE)),
+                payload_size_p50: size_percentile(sizes, stats.packets, 50),
+                payload_size_p95: size_percentile(sizes, stats.packets, 95),
+                payload_size_max: sizes.keys().next_back().copied(),
+                microburst: stats.microburst.peak(config.microburst_window_s),
+                wifi: (stats.wifi_frames > 0).then(|| WifiRetries {
+                    frames: stats.wifi_frames,
+                    retries: stats.wifi_retries,
+                    retry_rate: round_to(
+                        stats.wifi_retries as f64 / stats.wifi_frames as f64,
+                        AVERAGE_SCALE,
+                    ),
+                }),
             }
         })
         .collect();
 
-    flows.sort_by(|a, b| a.src.cmp(&b.src).then_with(|| a.dst.cmp(&b.dst)));
+    flows.sort_by(|a, b| {
+        a.src
+            .cmp(&b.src)
+            .then_with(|| a.dst.cmp(&b.dst))
+            .then_with(|| (a.vlan, a.outer_vlan).cmp(&(b.vlan, b.outer_vlan)))
+    });
     flows
 }
 
-fn format_endpoint(ip: IpAddr, port: u16) -> String {
+/// Per-second series of every flow in `stats`, sorted like the summaries.
+pub(crate) fn build_flow_series(
+    stats: &HashMap<FlowKey, FlowStats>,
+    timeline: Timeline,
+) -> Vec<FlowSeries> {
+    let mut series: Vec<FlowSeries> = stats
+        .iter()
+        .map(|(key, stats)| FlowSeries {
+            src: format_endpoint(key.src_ip, key.src_port),
+            dst: format_endpoint(key.dst_ip, key.dst_port),
+            vlan: key.vlan,
+            outer_vlan: key.outer_vlan,
+            pps: timeline.spread(&stats.seconds, |second| second.packets),
+            bps: timeline.spread(&stats.seconds, |second| second.bytes),
+        })
+        .collect();
+    series.sort_by(|a, b| {
+        a.src
+            .cmp(&b.src)
+            .then_with(|| a.dst.cmp(&b.dst))
+            .then_with(|| (a.vlan, a.outer_vlan).cmp(&(b.vlan, b.outer_vlan)))
+    });
+    series
+}
+
+/// Nearest-rank percentile of a size histogram holding `total` packets.
+fn size_percentile(sizes: &BTreeMap<u64, u64>, total: u64, percentile: u64) -> Option<u64> {
+    let rank = (total * percentile).div_ceil(100).max(1);
+    let mut seen = 0u64;
+    sizes.iter().find_map(|(size, count)| {
+        seen += count;
+        (seen >= rank).then_some(*size)
+    })
+}
+
+pub(crate) fn format_endpoint(ip: IpAddr, port: u16) -> String {
     match ip {
         IpAddr::V4(addr) => format!("{}:{}", addr, port),
         IpAddr::V6(addr) => format!("[{}]:{}", addr, port),
     }
 }
 
-fn update_flow_jitter(stats: &mut FlowStats, ts: Option<f64>) {
+fn update_flow_jitter(stats: &mut FlowStats, ts: Option<f64>, window_s: f64) {
     let ts = match ts {
         Some(ts) => ts,
         None => return,
@@ -135,7 +237,7 @@ fn update_flow_jitter(stats: &mut FlowStats, ts: Option<f64>) {
             stats.jitter_sum += diff;
             stats.jitter_samples.push_back((ts, diff));
             while let Some((sample_ts, sample)) = stats.jitter_samples.front().copied() {
-                if ts - sample_ts <= JITTER_WINDOW_S {
+                if ts - sample_ts <= window_s {
                     break;
                 }
                 stats.jitter_sum -= sample;
@@ -153,7 +255,7 @@ fn update_flow_jitter(stats: &mut FlowStats, ts: Option<f64>) {
     stats.last_ts = Some(ts);
 }
 
-fn update_flow_rates(stats: &mut FlowStats, ts: Option<f64>, bytes: u64) {
+fn update_flow_rates(stats: &mut FlowStats, ts: Option<f64>, bytes: u64, window_s: f64) {
     let ts = match ts {
         Some(ts) => ts,
         None => return,
@@ -162,15 +264,15 @@ fn update_flow_rates(stats: &mut FlowStats, ts: Option<f64>, bytes: u64) {
     stats.window_bytes += bytes;
     stats.window_samples.push_back((ts, bytes));
     while let Some((sample_ts, sample_bytes)) = stats.window_samples.front().copied() {
-        if ts - sample_ts <= PPS_BPS_WINDOW_S {
+        if ts - sample_ts <= window_s {
             break;
         }
         stats.window_packets = stats.window_packets.saturating_sub(1);
         stats.window_bytes = stats.window_bytes.saturating_sub(sample_bytes);
         stats.window_samples.pop_front();
     }
-    let pps = stats.window_packets as f64 / PPS_BPS_WINDOW_S;
-    let bps = stats.window_bytes as f64 / PPS_BPS_WINDOW_S;
+    let pps = stats.window_packets as f64 / window_s;
+    let bps = stats.window_bytes as f64 / window_s;
     stats.peak_pps = Some(stats.peak_pps.map_or(pps, |peak| peak.max(pps)));
     stats.peak_bps = Some(stats.peak_bps.map_or(bps, |peak| peak.max(bps)));
     stats.peak_window_packets = stats.peak_window_packets.max(stats.window_packets);
@@ -180,6 +282,7 @@ fn update_flow_rates(stats: &mut FlowStats, ts: Option<f64>, bytes: u64) {
 #[cfg(test)]
 mod tests {
     use super::{FlowKey, FlowStats, add_flow_stats, build_flow_summaries};
+    use crate::analysis::config::AnalyzerConfig;
     use crate::analysis::udp::UdpPacket;
     use std::collections::HashMap;
     use std::net::IpAddr;
@@ -197,6 +300,8 @@ mod tests {
                 src_port: 1000,
                 dst_ip: c,
                 dst_port: 2000,
+                vlan: None,
+                outer_vlan: None,
             },
             FlowStats {
                 packets: 10,
@@ -210,6 +315,8 @@ mod tests {
                 src_port: 1000,
                 dst_ip: c,
                 dst_port: 2000,
+                vlan: None,
+                outer_vlan: None,
             },
             FlowStats {
                 packets: 5,
@@ -218,13 +325,31 @@ mod tests {
             },
         );
 
-        let summaries = build_flow_summaries(stats, None);
-        assert_eq!(summaries.len(), 2);
-        assert!(summaries[0].src < summaries[1].src);
+        // The same endpoints on another VLAN are another flow.
+        stats.insert(
+            FlowKey {
+                src_ip: a,
+                src_port: 1000,
+                dst_ip: c,
+                dst_port: 2000,
+                vlan: Some(20),
+                outer_vlan: None,
+            },
+            FlowStats {
+                packets: 5,
+                bytes: 50,
+                ..Default::default()
+            },
+        );
+
+        let summaries = build_flow_summaries(stats, &AnalyzerConfig::default(), None);
+        assert_eq!(summaries.len(), 3);
+        assert!(summaries[0].src < summaries[2].src);
+        assert_eq!((summaries[0].vlan, summaries[1].vlan), (None, Some(20)));
         assert!(summaries[0].pps.is_none());
         assert!(summaries[0].bps.is_none());
-        assert!(summaries[1].pps.is_none());
-        assert!(summaries[1].bps.is_none());
+        assert!(summaries[2].pps.is_none());
+        assert!(summaries[2].bps.is_none());
     }
 
     #[test]
@@ -238,14 +363,45 @@ mod tests {
             dst_ip: b,
             dst_port: 2000,
             payload: &[0u8; 10],
+            ip_len: 38,
+            ttl: 64,
+            ip_checksum: None,
+            udp_checksum: None,
+            vlan: None,
+            outer_vlan: None,
+            wifi_retry: None,
         };
 
-        add_flow_stats(&mut stats, &packet, Some(0.0));
-        add_flow_stats(&mut stats, &packet, Some(0.2));
-        add_flow_stats(&mut stats, &packet, Some(0.4));
-        add_flow_stats(&mut stats, &packet, Some(2.0));
+        add_flow_stats(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            &packet,
+            None,
+            Some(0.0),
+        );
+        add_flow_stats(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            &packet,
+            None,
+            Some(0.2),
+        );
+        add_flow_stats(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            &packet,
+            None,
+            Some(0.4),
+        );
+        add_flow_stats(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            &packet,
+            None,
+            Some(2.0),
+        );
 
-        let summaries = build_flow_summaries(stats, Some(2.0));
+        let summaries = build_flow_summaries(stats, &AnalyzerConfig::default(), Some(2.0));
         let summary = &summaries[0];
         assert_eq!(summary.pps, Some(2.0));
         assert_eq!(summary.bps, Some(20.0));
@@ -260,13 +416,38 @@ mod tests {
             dst_ip: "10.0.0.2".parse().unwrap(),
             dst_port: 2000,
             payload: &[0u8; 4],
+            ip_len: 32,
+            ttl: 64,
+            ip_checksum: None,
+            udp_checksum: None,
+            vlan: None,
+            outer_vlan: None,
+            wifi_retry: None,
         };
 
-        add_flow_stats(&mut stats, &packet, Some(0.0));
-        add_flow_stats(&mut stats, &packet, Some(1.0));
-        add_flow_stats(&mut stats, &packet, Some(3.0));
+        add_flow_stats(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            &packet,
+            None,
+            Some(0.0),
+        );
+        add_flow_stats(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            &packet,
+            None,
+            Some(1.0),
+        );
+        add_flow_stats(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            &packet,
+            None,
+            Some(3.0),
+        );
 
-        let summaries = build_flow_summaries(stats, Some(3.0));
+        let summaries = build_flow_summaries(stats, &AnalyzerConfig::default(), Some(3.0));
         let summary = &summaries[0];
         let jitter = summary.iat_jitter_ms.unwrap_or(0.0);
         assert!((jitter - 1000.0).abs() < 0.1);
@@ -281,12 +462,19 @@ mod tests {
             dst_ip: "10.0.0.2".parse().unwrap(),
             dst_port: 2000,
             payload: &[0u8; 4],
+            ip_len: 32,
+            ttl: 64,
+            ip_checksum: None,
+            udp_checksum: None,
+            vlan: None,
+            outer_vlan: None,
+            wifi_retry: None,
         };
 
-        add_flow_stats(&mut stats, &packet, None);
-        add_flow_stats(&mut stats, &packet, None);
+        add_flow_stats(&mut stats, &AnalyzerConfig::default(), &packet, None, None);
+        add_flow_stats(&mut stats, &AnalyzerConfig::default(), &packet, None, None);
 
-        let summaries = build_flow_summaries(stats, None);
+        let summaries = build_flow_summaries(stats, &AnalyzerConfig::default(), None);
         let summary = &summaries[0];
         assert!(summary.iat_jitter_ms.is_none());
     }
@@ -300,13 +488,38 @@ mod tests {
             dst_ip: "10.0.0.2".parse().unwrap(),
             dst_port: 2000,
             payload: &[0u8; 10],
+            ip_len: 38,
+            ttl: 64,
+            ip_checksum: None,
+            udp_checksum: None,
+            vlan: None,
+            outer_vlan: None,
+            wifi_retry: None,
         };
 
-        add_flow_stats(&mut stats, &packet, Some(0.0));
-        add_flow_stats(&mut stats, &packet, Some(0.5));
-        add_flow_stats(&mut stats, &packet, Some(2.0));
+        add_flow_stats(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            &packet,
+            None,
+            Some(0.0),
+        );
+        add_flow_stats(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            &packet,
+            None,
+            Some(0.5),
+        );
+        add_flow_stats(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            &packet,
+            None,
+            Some(2.0),
+        );
 
-        let summaries = build_flow_summaries(stats, Some(2.0));
+        let summaries = build_flow_summaries(stats, &AnalyzerConfig::default(), Some(2.0));
         let summary = &summaries[0];
         assert_eq!(summary.max_iat_ms, Some(1500));
     }
@@ -320,16 +533,81 @@ mod tests {
             dst_ip: "10.0.0.2".parse().unwrap(),
             dst_port: 2000,
             payload: &[0u8; 10],
+            ip_len: 38,
+            ttl: 64,
+            ip_checksum: None,
+            udp_checksum: None,
+            vlan: None,
+            outer_vlan: None,
+            wifi_retry: None,
         };
 
-        add_flow_stats(&mut stats, &packet, Some(0.0));
-        add_flow_stats(&mut stats, &packet, Some(0.2));
-        add_flow_stats(&mut stats, &packet, Some(0.4));
-        add_flow_stats(&mut stats, &packet, Some(2.0));
+        add_flow_stats(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            &packet,
+            None,
+            Some(0.0),
+        );
+        add_flow_stats(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            &packet,
+            None,
+            Some(0.2),
+        );
+        add_flow_stats(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            &packet,
+            None,
+            Some(0.4),
+        );
+        add_flow_stats(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            &packet,
+            None,
+            Some(2.0),
+        );
 
-        let summaries = build_flow_summaries(stats, Some(2.0));
+        let summaries = build_flow_summaries(stats, &AnalyzerConfig::default(), Some(2.0));
         let summary = &summaries[0];
         assert_eq!(summary.pps_peak_1s, Some(3));
         assert_eq!(summary.bps_peak_1s, Some(30));
     }
+
+    #[test]
+    fn flow_payload_size_distribution_is_reported() {
+        let mut stats = HashMap::new();
+        let payload = [0u8; 530];
+        let config = AnalyzerConfig::default();
+        for (index, len) in [18usize, 18, 530, 530, 530, 530, 530, 530, 530, 530]
+            .into_iter()
+            .enumerate()
+        {
+            let packet = UdpPacket {
+                src_ip: "10.0.0.1".parse().unwrap(),
+                src_port: 6454,
+                dst_ip: "10.0.0.2".parse().unwrap(),
+                dst_port: 6454,
+                payload: &payload[..len],
+                ip_len: len + 28,
+                ttl: 64,
+                ip_checksum: None,
+                udp_checksum: None,
+                vlan: None,
+                outer_vlan: None,
+                wifi_retry: None,
+            };
+            add_flow_stats(&mut stats, &config, &packet, None, Some(index as f64));
+        }
+
+        let summary = &build_flow_summaries(stats, &config, None)[0];
+        assert_eq!(summary.payload_size_min, Some(18));
+        assert_eq!(summary.payload_size_avg, Some(427.6));
+        assert_eq!(summary.payload_size_p50, Some(530));
+        assert_eq!(summary.payload_size_p95, Some(530));
+        assert_eq!(summary.payload_size_max, Some(530));
+    }
 }
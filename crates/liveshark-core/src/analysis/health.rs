@@ -0,0 +1,209 @@
+//! Capture health score.
+//!
+//! One number per report so shows can be compared at a glance. Every
+//! universe starts at 100 and loses points per factor, each capped so no
+//! single factor sinks a universe on its own:
+//!
+//! - loss: 4 points per percent of lost packets, up to 40;
+//! - jitter: 1 point per millisecond of inter-arrival jitter, up to 20;
+//! - conflicts: 10 points per conflict on the universe, up to 20;
+//! - data loss: 5 points per silence of the universe, up to 20.
+//!
+//! The capture score averages the universe penalties and adds the
+//! violations penalty: 0.2 points per percent below 100 of the worst
+//! protocol compliance, whose percentage already weighs violations by
+//! severity (up to 20).
+
+use super::round_to;
+use crate::{HealthPenalties, HealthScore, Report, UniverseHealth, UniverseSummary};
+
+const MAX_SCORE: f64 = 100.0;
+const LOSS_POINTS_PER_RATE: f64 = 400.0;
+const MAX_LOSS_PENALTY: f64 = 40.0;
+const JITTER_POINTS_PER_MS: f64 = 1.0;
+const MAX_JITTER_PENALTY: f64 = 20.0;
+const CONFLICT_POINTS: f64 = 10.0;
+const MAX_CONFLICT_PENALTY: f64 = 20.0;
+const SILENCE_POINTS: f64 = 5.0;
+const MAX_DATA_LOSS_PENALTY: f64 = 20.0;
+const VIOLATION_POINTS_PER_PERCENT: f64 = 0.2;
+const MAX_VIOLATION_PENALTY: f64 = 20.0;
+/// Scores and penalties are rounded to this many steps per point (one decimal).
+const SCORE_ROUNDING: f64 = 10.0;
+
+/// Health score of a finished report.
+pub(crate) fn health_score(report: &Report) -> HealthScore {
+    let universes: Vec<UniverseHealth> = report
+        .universes
+        .iter()
+        .map(|universe| universe_health(report, universe))
+        .collect();
+
+    let mut penalties = HealthPenalties::default();
+    if !universes.is_empty() {
+        let count = universes.len() as f64;
+        let mean = |factor: fn(&HealthPenalties) -> f64| {
+            universes
+                .iter()
+                .map(|universe| factor(&universe.penalties))
+                .sum::<f64>()
+                / count
+        };
+        penalties.loss = mean(|p| p.loss);
+        penalties.jitter = mean(|p| p.jitter);
+        penalties.conflicts = mean(|p| p.conflicts);
+        penalties.data_loss = mean(|p| p.data_loss);
+    }
+    let worst_compliance = report
+        .compliance
+        .iter()
+        .map(|entry| entry.compliance_percentage)
+        .reduce(f64::min)
+        .unwrap_or(MAX_SCORE);
+    penalties.violations = ((MAX_SCORE - worst_compliance) * VIOLATION_POINTS_PER_PERCENT)
+        .clamp(0.0, MAX_VIOLATION_PENALTY);
+
+    let score = score_after(&penalties);
+    HealthScore {
+        score,
+        penalties: rounded(penalties),
+        universes,
+    }
+}
+
+fn universe_health(report: &Report, universe: &UniverseSummary) -> UniverseHealth {
+    let conflicts = report
+        .conflicts
+        .iter()
+        .filter(|conflict| {
+            conflict.universe == universe.universe
+                && conflict.proto.is_none_or(|proto| proto == universe.proto)
+        })
+        .count();
+    let silences = report
+        .silences
+        .iter()
+        .filter(|silence| silence.universe == universe.universe && silence.proto == universe.proto)
+        .count();
+    let penalties = HealthPenalties {
+        loss: (universe.loss_rate.unwrap_or(0.0) * LOSS_POINTS_PER_RATE).min(MAX_LOSS_PENALTY),
+        jitter: (universe.jitter_ms.unwrap_or(0.0) * JITTER_POINTS_PER_MS).min(MAX_JITTER_PENALTY),
+        conflicts: (conflicts as f64 * CONFLICT_POINTS).min(MAX_CONFLICT_PENALTY),
+        data_loss: (silences as f64 * SILENCE_POINTS).min(MAX_DATA_LOSS_PENALTY),
+        violations: 0.0,
+    };
+    UniverseHealth {
+        universe: universe.universe,
+        proto: universe.proto,
+        score: score_after(&penalties),
+        penalties: rounded(penalties),
+    }
+}
+
+fn score_after(penalties: &HealthPenalties) -> f64 {
+    round_to(
+        (MAX_SCORE - penalties.total()).clamp(0.0, MAX_SCORE),
+        SCORE_ROUNDING,
+    )
+}
+
+fn rounded(penalties: HealthPenalties) -> HealthPenalties {
+    HealthPenalties {
+        loss: round_to(penalties.loss, SCORE_ROUNDING),
+        jitter: round_to(penalties.jitter, SCORE_ROUNDING),
+        conflicts: round_to(penalties.conflicts, SCORE_ROUNDING),
+        data_loss: round_to(penalties.data_loss, SCORE_ROUNDING),
+        violations: round_to(penalties.violations, SCORE_ROUNDING),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::health_score;
+    use crate::{
+        ComplianceSummary, ConflictSummary, Protocol, ReportBuilder, Severity, UniverseSilence,
+        UniverseSummary,
+    };
+
+    fn universe(number: u16, loss_rate: Option<f64>, jitter_ms: Option<f64>) -> UniverseSummary {
+        UniverseSummary {
+            universe: number,
+            proto: Protocol::Sacn,
+            port_address: None,
+            sources: Vec::new(),
+            fps: None,
+            frames_count: 0,
+            loss_packets: None,
+            loss_rate,
+            burst_count: None,
+            max_burst_len: None,
+            jitter_ms,
+            dup_packets: None,
+            reordered_packets: None,
+            first_seen: None,
+            last_seen: None,
+            destinations: Vec::new(),
+            channel_pairs: Vec::new(),
+            first_snapshot: None,
+            last_snapshot: None,
+            ownership: Vec::new(),
+            microburst: None,
+            controllers_count: 0,
+            max_concurrent_controllers: None,
+            non_zero_start_codes: None,
+            preview_frames: None,
+            unicast_receivers: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn clean_capture_scores_100() {
+        let report = ReportBuilder::new("capture.pcapng", 1)
+            .universes(vec![universe(1, Some(0.0), Some(0.0))])
+            .into_report();
+        let health = health_score(&report);
+        assert_eq!(health.score, 100.0);
+        assert_eq!(health.universes[0].score, 100.0);
+    }
+
+    #[test]
+    fn penalties_are_capped_and_averaged() {
+        let report = ReportBuilder::new("capture.pcapng", 1)
+            .universes(vec![
+                universe(1, Some(0.5), Some(3.0)),
+                universe(2, Some(0.0), None),
+            ])
+            .conflicts(vec![ConflictSummary {
+                universe: 1,
+                sources: vec!["a".to_string(), "b".to_string()],
+                proto: Some(Protocol::Sacn),
+                overlap_duration_s: 1.0,
+                affected_channels: Vec::new(),
+                severity: Severity::Error,
+                conflict_score: 1.0,
+                first_seen: None,
+                channel_labels: Vec::new(),
+                rig_labels: Vec::new(),
+            }])
+            .silences(vec![UniverseSilence {
+                universe: 2,
+                proto: Protocol::Sacn,
+                start: 1.0,
+                duration_s: 2.0,
+            }])
+            .compliance(vec![ComplianceSummary {
+                protocol: Protocol::Sacn,
+                compliance_percentage: 75.0,
+                violations: Vec::new(),
+            }])
+            .into_report();
+        let health = health_score(&report);
+        // Universe 1: loss capped at 40, 3 ms jitter, one conflict.
+        assert_eq!(health.universes[0].score, 47.0);
+        // Universe 2: one silence.
+        assert_eq!(health.universes[1].score, 95.0);
+        assert_eq!(health.penalties.loss, 20.0);
+        assert_eq!(health.penalties.violations, 5.0);
+        assert_eq!(health.score, 66.0);
+    }
+}
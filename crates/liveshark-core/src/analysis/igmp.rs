@@ -0,0 +1,195 @@
+//! IGMP group membership, correlated with sACN universes.
+//!
+//! sACN receivers join the multicast group of each universe they patch
+//! (239.255.H.L for universe H * 256 + L); with IGMP snooping, switches only
+//! forward a universe to the ports that joined it. Membership reports and
+//! leaves are carried directly over IPv4, so they arrive here from frames
+//! that are not UDP. Queries and malformed messages are ignored.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::Ipv4Addr;
+
+use etherparse::{IpNumber, NetSlice, SlicedPacket};
+use pcap_parser::Linktype;
+
+use crate::protocols::decode_igmp;
+use crate::protocols::igmp::layout::IP_PROTOCOL;
+use crate::{IgmpGroupSummary, IgmpMemberSummary, IgmpMessage};
+
+/// Highest universe number an sACN multicast group can carry.
+const MAX_SACN_UNIVERSE: u16 = 63999;
+
+#[derive(Debug, Default)]
+struct Member {
+    joins: u64,
+    leaves: u64,
+    first_joined: Option<f64>,
+    last_left: Option<f64>,
+    member: bool,
+}
+
+#[derive(Debug, Default)]
+struct Group {
+    universe: Option<u16>,
+    members: BTreeMap<String, Member>,
+}
+
+/// Multicast groups joined or left, keyed by group address.
+#[derive(Debug, Default)]
+pub(crate) struct IgmpTracker {
+    groups: BTreeMap<String, Group>,
+    reports: u64,
+}
+
+impl IgmpTracker {
+    /// Record the IGMP membership message a non-UDP frame carries, if any.
+    pub(crate) fn add_frame(&mut self, ts: Option<f64>, linktype: Linktype, data: &[u8]) {
+        let sliced = match linktype {
+            Linktype::ETHERNET => SlicedPacket::from_ethernet(data),
+            Linktype::RAW => SlicedPacket::from_ip(data),
+            _ => return,
+        };
+        let Ok(sliced) = sliced else {
+            return;
+        };
+        let Some(NetSlice::Ipv4(ipv4)) = sliced.net else {
+            return;
+        };
+        let payload = ipv4.payload();
+        if payload.ip_number != IpNumber(IP_PROTOCOL) || payload.fragmented {
+            return;
+        }
+        if let Ok(Some(message)) = decode_igmp(payload.payload) {
+            self.add(ts, ipv4.header().source_addr(), &message);
+        }
+    }
+
+    /// Record an IGMP message sent by `receiver`.
+    pub(crate) fn add(&mut self, ts: Option<f64>, receiver: Ipv4Addr, message: &IgmpMessage) {
+        let IgmpMessage::Membership { changes, .. } = message else {
+            return;
+        };
+        self.reports += 1;
+        for change in changes {
+            let group = self
+                .groups
+                .entry(change.group.to_string())
+                .or_insert_with(|| Group {
+                    universe: sacn_universe(change.group),
+                    members: BTreeMap::new(),
+                });
+            let member = group.members.entry(receiver.to_string()).or_default();
+            if change.joined {
+                member.joins += 1;
+                if let Some(ts) = ts {
+                    member.first_joined.get_or_insert(ts);
+                }
+            } else {
+                member.leaves += 1;
+                if ts.is_some() {
+                    member.last_left = ts;
+                }
+            }
+            member.member = change.joined;
+        }
+    }
+
+    /// sACN universes whose group at least one receiver joined; `None` when
+    /// the capture holds no membership report at all, as a mirror port
+    /// often does not see them.
+    pub(crate) fn joined_universes(&self) -> Option<BTreeSet<u16>> {
+        (self.reports > 0).then(|| {
+            self.groups
+                .values()
+                .filter(|group| group.members.values().any(|member| member.joins > 0))
+                .filter_map(|group| group.universe)
+                .collect()
+        })
+    }
+
+    /// Groups named by a membership message, sorted by group address, each
+    /// with its members sorted by receiver address.
+    pub(crate) fn finish(self) -> Vec<IgmpGroupSummary> {
+        self.groups
+            .into_iter()
+            .map(|(group, state)| IgmpGroupSummary {
+                group,
+                universe: state.universe,
+                members: state
+                    .members
+                    .into_iter()
+                    .map(|(receiver, member)| IgmpMemberSummary {
+                        receiver,
+                        joins: member.joins,
+                        leaves: member.leaves,
+                        first_joined: member.first_joined,
+                        last_left: member.last_left,
+                        member_at_end: member.member,
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+/// Universe of an sACN multicast group (239.255.H.L).
+fn sacn_universe(group: Ipv4Addr) -> Option<u16> {
+    let [a, b, high, low] = group.octets();
+    let universe = u16::from_be_bytes([high, low]);
+    ((a, b) == (239, 255) && (1..=MAX_SACN_UNIVERSE).contains(&universe)).then_some(universe)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::IgmpTracker;
+    use crate::{IgmpGroupChange, IgmpMessage};
+
+    fn membership(group: [u8; 4], joined: bool) -> IgmpMessage {
+        IgmpMessage::Membership {
+            version: 2,
+            changes: vec![IgmpGroupChange {
+                group: Ipv4Addr::from(group),
+                joined,
+            }],
+        }
+    }
+
+    #[test]
+    fn members_are_listed_per_group_with_their_universe() {
+        let mut tracker = IgmpTracker::default();
+        assert_eq!(tracker.joined_universes(), None);
+        let node = Ipv4Addr::new(10, 0, 0, 20);
+        let desk = Ipv4Addr::new(10, 0, 0, 1);
+        tracker.add(Some(1.0), node, &membership([239, 255, 0, 1], true));
+        tracker.add(Some(2.0), node, &membership([239, 255, 0, 1], true));
+        tracker.add(Some(3.0), node, &membership([239, 255, 0, 1], false));
+        tracker.add(Some(1.5), desk, &membership([239, 255, 0, 2], false));
+        tracker.add(Some(1.5), desk, &membership([224, 0, 1, 129], true));
+        tracker.add(
+            Some(4.0),
+            desk,
+            &IgmpMessage::Query {
+                group: Ipv4Addr::UNSPECIFIED,
+            },
+        );
+
+        assert_eq!(tracker.joined_universes(), Some([1].into()));
+        let groups = tracker.finish();
+        let names: Vec<_> = groups.iter().map(|group| group.group.as_str()).collect();
+        assert_eq!(names, vec!["224.0.1.129", "239.255.0.1", "239.255.0.2"]);
+        assert_eq!(groups[0].universe, None);
+        let universe = &groups[1];
+        assert_eq!(universe.universe, Some(1));
+        let member = &universe.members[0];
+        assert_eq!(member.receiver, "10.0.0.20");
+        assert_eq!((member.joins, member.leaves), (2, 1));
+        assert_eq!(
+            (member.first_joined, member.last_left),
+            (Some(1.0), Some(3.0))
+        );
+        assert!(!member.member_at_end);
+        assert!(groups[0].members[0].member_at_end);
+    }
+}
@@ -0,0 +1,90 @@
+//! Microburst peaks over short sliding windows.
+//!
+//! A 1 s peak averages away bursts that last a few milliseconds, yet those
+//! are what overflow the small buffers of access switches and nodes. The
+//! same sliding-window count used for the 1 s peaks is kept over a much
+//! shorter window (10 ms by default) for every flow and universe.
+
+use std::collections::VecDeque;
+
+use super::{AVERAGE_SCALE, round_to};
+use crate::MicroburstPeak;
+
+/// Sliding window of packets, using the `[t - W, t]` inclusion rule.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct BurstWindow {
+    samples: VecDeque<(f64, u64)>,
+    packets: u64,
+    bytes: u64,
+    peak_packets: u64,
+    peak_bytes: u64,
+    first_ts: Option<f64>,
+    last_ts: Option<f64>,
+}
+
+impl BurstWindow {
+    /// Count a packet of `bytes`; packets without timestamps are ignored.
+    pub(crate) fn add(&mut self, ts: Option<f64>, bytes: u64, window_s: f64) {
+        let Some(ts) = ts else {
+            return;
+        };
+        self.first_ts.get_or_insert(ts);
+        self.last_ts = Some(ts);
+        self.packets += 1;
+        self.bytes += bytes;
+        self.samples.push_back((ts, bytes));
+        while let Some((sample_ts, sample_bytes)) = self.samples.front().copied() {
+            if ts - sample_ts <= window_s {
+                break;
+            }
+            self.packets -= 1;
+            self.bytes -= sample_bytes;
+            self.samples.pop_front();
+        }
+        self.peak_packets = self.peak_packets.max(self.packets);
+        self.peak_bytes = self.peak_bytes.max(self.bytes);
+    }
+
+    /// Peak counts and rates, once the packets span at least one window.
+    pub(crate) fn peak(&self, window_s: f64) -> Option<MicroburstPeak> {
+        let (first, last) = (self.first_ts?, self.last_ts?);
+        (last - first >= window_s).then(|| MicroburstPeak {
+            window_ms: round_to(window_s * 1000.0, AVERAGE_SCALE),
+            packets: self.peak_packets,
+            bytes: self.peak_bytes,
+            pps: round_to(self.peak_packets as f64 / window_s, AVERAGE_SCALE),
+            bps: round_to(self.peak_bytes as f64 / window_s, AVERAGE_SCALE),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BurstWindow;
+
+    #[test]
+    fn burst_within_the_window_sets_the_peak() {
+        let mut window = BurstWindow::default();
+        // A steady 40 fps stream with five packets bunched within 4 ms.
+        for step in 0..40 {
+            window.add(Some(f64::from(step) * 0.025), 530, 0.01);
+        }
+        for step in 0..5 {
+            window.add(Some(1.0 + f64::from(step) * 0.001), 530, 0.01);
+        }
+        let peak = window.peak(0.01).expect("peak");
+        assert_eq!(peak.window_ms, 10.0);
+        assert_eq!(peak.packets, 5);
+        assert_eq!(peak.bytes, 2650);
+        assert_eq!(peak.pps, 500.0);
+    }
+
+    #[test]
+    fn short_or_untimed_streams_have_no_peak() {
+        let mut window = BurstWindow::default();
+        window.add(None, 100, 0.01);
+        window.add(Some(0.0), 100, 0.01);
+        window.add(Some(0.005), 100, 0.01);
+        assert!(window.peak(0.01).is_none());
+    }
+}
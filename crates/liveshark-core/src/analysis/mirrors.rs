@@ -0,0 +1,201 @@
+//! Mirrored universe detection.
+//!
+//! Two universes carrying the same look for the whole capture usually mean a
+//! duplicated patch or a node routing one input to two outputs. Each
+//! universe's output (the last frame of any source) is sampled on a shared
+//! grid and reduced to a fingerprint of its non-zero span, so a copy patched
+//! a few channels further along still matches, with a constant channel
+//! offset.
+
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use super::dmx::DmxFrame;
+use crate::{Protocol, UniverseMirror};
+
+/// Spacing of the sampling grid (seconds).
+const MIRROR_SAMPLE_INTERVAL_S: f64 = 0.25;
+/// Most samples taken per universe (one day), so a bogus timestamp cannot
+/// inflate the grid.
+const MAX_MIRROR_SAMPLES: usize = 345_600;
+/// Fewest non-blank samples before a pair is judged; two static looks that
+/// happen to match briefly are not a mirror.
+pub const MIN_MIRROR_SAMPLES: u64 = 8;
+/// Share of non-blank samples that must match at the same offset (percent).
+const MIN_MATCH_PERCENT: u64 = 95;
+
+/// Non-zero span of a frame: first non-zero slot and a hash of the span.
+/// `None` for an all-zero frame.
+type Fingerprint = Option<(u16, u64)>;
+
+/// Output of one universe on the grid, from its first frame on.
+struct Sampled {
+    universe: u16,
+    proto: Protocol,
+    first_sample: usize,
+    samples: Vec<Fingerprint>,
+}
+
+/// Pairs of universes whose output matches over the capture, sorted by the
+/// first universe of each pair. `outputs` holds each universe's frames (all
+/// sources) in any order; frames without timestamps are ignored.
+pub(crate) fn detect_mirrors(outputs: Vec<(u16, Protocol, Vec<&DmxFrame>)>) -> Vec<UniverseMirror> {
+    let origin = outputs
+        .iter()
+        .flat_map(|(_, _, frames)| frames.iter().filter_map(|frame| frame.timestamp))
+        .reduce(f64::min);
+    let Some(origin) = origin else {
+        return Vec::new();
+    };
+    let mut sampled: Vec<Sampled> = outputs
+        .into_iter()
+        .filter_map(|(universe, proto, frames)| sample(universe, proto, frames, origin))
+        .collect();
+    sampled.sort_by(|a, b| a.universe.cmp(&b.universe).then(a.proto.cmp(&b.proto)));
+
+    let mut mirrors = Vec::new();
+    for (index, left) in sampled.iter().enumerate() {
+        for right in &sampled[index + 1..] {
+            if let Some(mirror) = compare(left, right) {
+                mirrors.push(mirror);
+            }
+        }
+    }
+    mirrors
+}
+
+fn sample(
+    universe: u16,
+    proto: Protocol,
+    mut frames: Vec<&DmxFrame>,
+    origin: f64,
+) -> Option<Sampled> {
+    frames.retain(|frame| frame.timestamp.is_some());
+    frames.sort_by(|a, b| {
+        let ts = |frame: &DmxFrame| frame.timestamp.unwrap_or_default();
+        ts(a).total_cmp(&ts(b))
+    });
+    let first_ts = frames.first()?.timestamp?;
+    let last_ts = frames.last()?.timestamp?;
+    let index_of = |ts: f64| ((ts - origin) / MIRROR_SAMPLE_INTERVAL_S).floor() as usize;
+    let first_sample = index_of(first_ts);
+    let last_sample = index_of(last_ts).min(first_sample + MAX_MIRROR_SAMPLES - 1);
+
+    let mut samples = Vec::with_capacity(last_sample - first_sample + 1);
+    let mut frames = frames.into_iter().peekable();
+    let mut current: Fingerprint = None;
+    for index in first_sample..=last_sample {
+        // A sample shows the last frame received up to the end of its slot.
+        let end = origin + (index + 1) as f64 * MIRROR_SAMPLE_INTERVAL_S;
+        while let Some(frame) = frames.next_if(|frame| frame.timestamp.is_some_and(|ts| ts < end)) {
+            current = fingerprint(&frame.slots);
+        }
+        samples.push(current);
+    }
+    Some(Sampled {
+        universe,
+        proto,
+        first_sample,
+        samples,
+    })
+}
+
+fn fingerprint(slots: &[u8; 512]) -> Fingerprint {
+    let first = slots.iter().position(|value| *value != 0)?;
+    let last = slots.iter().rposition(|value| *value != 0)?;
+    let mut hasher = DefaultHasher::new();
+    slots[first..=last].hash(&mut hasher);
+    Some((first as u16, hasher.finish()))
+}
+
+fn compare(left: &Sampled, right: &Sampled) -> Option<UniverseMirror> {
+    let start = left.first_sample.max(right.first_sample);
+    let end =
+        (left.first_sample + left.samples.len()).min(right.first_sample + right.samples.len());
+    let mut considered = 0u64;
+    let mut offsets: HashMap<i16, u64> = HashMap::new();
+    for index in start..end {
+        let a = left.samples[index - left.first_sample];
+        let b = right.samples[index - right.first_sample];
+        if a.is_none() && b.is_none() {
+            continue;
+        }
+        considered += 1;
+        if let (Some((a_first, a_hash)), Some((b_first, b_hash))) = (a, b) {
+            if a_hash == b_hash {
+                *offsets.entry(b_first as i16 - a_first as i16).or_default() += 1;
+            }
+        }
+    }
+    if considered < MIN_MIRROR_SAMPLES {
+        return None;
+    }
+    // Lowest offset wins a tie so the result is deterministic.
+    let (offset, matching) = offsets
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))?;
+    (matching * 100 >= considered * MIN_MATCH_PERCENT).then_some(UniverseMirror {
+        universe: left.universe,
+        proto: left.proto,
+        mirror: right.universe,
+        mirror_proto: right.proto,
+        channel_offset: offset,
+        samples: considered,
+        matching_samples: matching,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::detect_mirrors;
+    use crate::Protocol;
+    use crate::analysis::dmx::DmxFrame;
+
+    fn frames(universe: u16, offset: usize, levels: impl Iterator<Item = u8>) -> Vec<DmxFrame> {
+        levels
+            .enumerate()
+            .map(|(step, level)| {
+                let mut slots = [0u8; 512];
+                slots[offset] = level;
+                slots[offset + 1] = level / 2;
+                DmxFrame {
+                    universe,
+                    timestamp: Some(step as f64 * 0.1),
+                    source_id: "artnet:10.0.0.1:6454".to_string(),
+                    protocol: Protocol::ArtNet,
+                    slots,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn shifted_copy_is_reported_with_its_offset() {
+        let ramp = || (0u8..60).map(|step| step.wrapping_mul(7) | 1);
+        let original = frames(1, 0, ramp());
+        let copy = frames(5, 10, ramp());
+        let other = frames(2, 0, (0u8..60).map(|step| 255 - step));
+        let mirrors = detect_mirrors(vec![
+            (5, Protocol::ArtNet, copy.iter().collect()),
+            (1, Protocol::ArtNet, original.iter().collect()),
+            (2, Protocol::ArtNet, other.iter().collect()),
+        ]);
+        assert_eq!(mirrors.len(), 1);
+        let mirror = &mirrors[0];
+        assert_eq!((mirror.universe, mirror.mirror), (1, 5));
+        assert_eq!(mirror.channel_offset, 10);
+        assert_eq!(mirror.matching_samples, mirror.samples);
+    }
+
+    #[test]
+    fn blank_universes_are_not_mirrors() {
+        let blank_a = frames(1, 0, std::iter::repeat_n(0, 60));
+        let blank_b = frames(2, 0, std::iter::repeat_n(0, 60));
+        let mirrors = detect_mirrors(vec![
+            (1, Protocol::ArtNet, blank_a.iter().collect()),
+            (2, Protocol::ArtNet, blank_b.iter().collect()),
+        ]);
+        assert!(mirrors.is_empty());
+    }
+}
@@ -9,37 +9,132 @@
 //! - Output lists are sorted deterministically (universes, flows, conflicts, compliance).
 //! - Sliding-window metrics use the same inclusion rule: [t - W, t].
 //! - DMX reconstruction is stateful per (universe, source, protocol).
+//! - Violations go through the rules engine, which honors the configured `RuleSet`.
 //!
+use std::borrow::Borrow;
 use std::collections::HashMap;
-use std::net::IpAddr;
+use std::net::SocketAddr;
 use std::path::Path;
 
 use thiserror::Error;
 use time::{OffsetDateTime, format_description::well_known::Rfc3339};
 
-use crate::source::{PacketEvent, PacketSource, PcapFileSource, SourceError};
+use crate::report::{
+    ReportBuilder, ReportError, cmp_events, cmp_fades, cmp_movement, cmp_priority_spans,
+    cmp_scene_changes, cmp_silences, cmp_takeovers,
+};
+use crate::rules::{PacketFields, RuleId};
+use crate::source::{PacketEvent, PacketSource, SourceError};
 use crate::{
-    CaptureSummary, ComplianceSummary, DEFAULT_GENERATED_AT, Report, Violation, make_stub_report,
+    CaptureSummary, DEFAULT_GENERATED_AT, FixtureMap, MetricSeries, PrioritySpan, Protocol, Report,
+    SkipReason, SkippedPackets, Takeover, TrafficBreakdown, TrafficCount, TriggerEvent,
 };
 
 const ARTNET_PORT: u16 = 6454;
 const SACN_PORT: u16 = 5568;
+/// Capture summary durations are rounded to microseconds, the resolution of
+/// pcap timestamps.
+const DURATION_SCALE: f64 = 1_000_000.0;
+/// Capture summary averages are rounded to three decimals so reports stay
+/// byte-identical across JSON round trips.
+const AVERAGE_SCALE: f64 = 1_000.0;
 
+mod address_priority;
+mod channels;
+mod citp;
+mod compliance;
+mod config;
+mod controllers;
+mod discovery;
 mod dmx;
+mod fades;
 mod flows;
-mod udp;
+mod health;
+mod igmp;
+mod microbursts;
+mod mirrors;
+mod movement;
+mod network;
+mod nodes;
+mod observer;
+mod osc;
+mod other;
+mod ownership;
+mod priorities;
+mod profile;
+mod psn;
+mod ptp;
+mod rdm;
+mod scenes;
+mod series;
+mod stability;
+mod sync;
+mod takeovers;
+mod timecode;
+mod timestamps;
+pub(crate) mod udp;
 mod universes;
 
-use dmx::{DmxFrame, DmxProtocol, DmxStateStore, DmxStore};
-use flows::{FlowKey, FlowStats, add_flow_stats, build_flow_summaries};
-use udp::parse_udp_packet;
+pub(crate) use compliance::sequence_gap;
+pub use config::{Analyzer, AnalyzerBuilder, AnalyzerConfig};
+pub use dmx::{DmxFrame, DmxStateStore};
+pub use observer::{AnalysisObserver, ViolationEvent};
+pub use profile::{Profile, UnknownProfileError};
+
+use address_priority::AddressPriorityTracker;
+use citp::CitpTracker;
+use compliance::{
+    RulesEngine, artaddress_detail, artnet_violation, detail_example, is_broadcast, packet_fields,
+    port_detail, sacn_violation, udp_violation,
+};
+use discovery::DiscoveryTracker;
+use dmx::{
+    DmxStore, FrameWrite, ReleasedArtDmx, ReleasedSacnDmx, artnet_source_id, sacn_source_id,
+};
+use fades::detect_fades;
+use flows::{FlowKey, FlowStats, add_flow_stats, build_flow_series, build_flow_summaries};
+use health::health_score;
+use igmp::IgmpTracker;
+use mirrors::detect_mirrors;
+use movement::movement_smoothness;
+use network::NetworkTracker;
+use nodes::NodeTracker;
+use osc::OscTracker;
+use other::OtherTraffic;
+use priorities::priority_timeline;
+use psn::PsnTrackers;
+use ptp::PtpTracker;
+use rdm::{RdmPacket, RdmTracker, decode_rdm};
+use scenes::detect_scene_changes;
+use series::Timeline;
+use sync::SyncTracker;
+use takeovers::detect_takeovers;
+use timecode::TimecodeTracker;
+use timestamps::TimestampMonitor;
+use udp::error::UdpError;
+use udp::{decapsulate, is_supported_linktype, parse_truncated_udp_packet, parse_udp_packet};
 use universes::{
-    UniverseStats, add_artnet_frame, add_sacn_frame, build_artnet_universe_summaries,
-    build_conflicts, build_sacn_universe_summaries,
+    UniverseStats, add_artnet_frame, add_artnet_nzs, add_burst_sample, add_destination,
+    add_sacn_frame, add_sacn_preview, add_sacn_unicast, build_artnet_universe_summaries,
+    build_conflicts, build_sacn_universe_summaries, build_silences, build_source_dropouts,
+    build_universe_series, lost_between, skip_sacn_sequence, terminate_sacn_source,
 };
 
-use crate::protocols::artnet::parse_artdmx;
-use crate::protocols::sacn::parse_sacn_dmx;
+use crate::decode::Endpoints;
+use crate::protocols::artnet::error::ArtNetError;
+use crate::protocols::dhcp::layout::{
+    CLIENT_PORT as DHCP_CLIENT_PORT, SERVER_PORT as DHCP_SERVER_PORT,
+};
+use crate::protocols::ptp::layout::{
+    EVENT_PORT as PTP_EVENT_PORT, GENERAL_PORT as PTP_GENERAL_PORT,
+};
+use crate::protocols::sacn::error::SacnError;
+use crate::protocols::{
+    decode_artaddress, decode_artdmx, decode_artnzs, decode_artpollreply, decode_artsync,
+    decode_arttimecode, decode_arttrigger, decode_citp, decode_dhcp, decode_osc, decode_psn_data,
+    decode_psn_info, decode_ptp, decode_sacn_address_priority, decode_sacn_discovery,
+    decode_sacn_dmx, decode_sacn_sync,
+};
 
 /// Errors returned by analysis entry points.
 ///
@@ -59,10 +154,16 @@ pub enum AnalysisError {
     Io(#[from] std::io::Error),
     #[error("Source error: {0}")]
     Source(#[from] SourceError),
+    #[error("Invalid analyzer configuration: {0}")]
+    InvalidConfig(String),
+    #[error("Invalid report: {0}")]
+    Report(#[from] ReportError),
 }
 
 /// Analyze a PCAP/PCAPNG file from disk.
 ///
+/// Equivalent to `Analyzer::default().analyze_file(path)`.
+///
 /// # Errors
 /// Returns `AnalysisError` when the file cannot be opened or parsed.
 ///
@@ -76,12 +177,13 @@ pub enum AnalysisError {
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
 pub fn analyze_pcap_file(path: &Path) -> Result<Report, AnalysisError> {
-    let source = PcapFileSource::open(path)?;
-    analyze_source(path, source)
+    Analyzer::default().analyze_file(path)
 }
 
 /// Analyze a packet source and produce a report.
 ///
+/// Equivalent to `Analyzer::default().analyze_source(path, source)`.
+///
 /// # Errors
 /// Returns `AnalysisError` for I/O or parsing failures originating from the
 /// packet source.
@@ -105,9 +207,97 @@ pub fn analyze_pcap_file(path: &Path) -> Result<Report, AnalysisError> {
 /// assert!(report.flows.is_empty());
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
-pub fn analyze_source<S: PacketSource>(
+pub fn analyze_source<S: PacketSource>(path: &Path, source: S) -> Result<Report, AnalysisError> {
+    Analyzer::default().analyze_source(path, source)
+}
+
+fn run_analysis<S: PacketSource>(
+    config: &AnalyzerConfig,
     path: &Path,
-    mut source: S,
+    source: S,
+    observer: &mut dyn AnalysisObserver,
+) -> Result<Report, AnalysisError> {
+    let bytes = path.metadata()?.len();
+    analyze_input(
+        config,
+        &path.display().to_string(),
+        Some(bytes),
+        OwnedPackets(source),
+        observer,
+    )
+}
+
+/// Packets the analysis loop reads: owned ones from a [`PacketSource`], or
+/// packets borrowed from the caller, which are analyzed in place.
+trait PacketFeed {
+    type Packet: Borrow<PacketEvent>;
+
+    fn next_packet(&mut self) -> Result<Option<Self::Packet>, SourceError>;
+
+    /// Hand a packet back once the loop is done with it.
+    fn release(&mut self, packet: Self::Packet);
+
+    fn snaplen(&self) -> Option<u32>;
+}
+
+/// Feed reading from a packet source, which gets each buffer back.
+struct OwnedPackets<S>(S);
+
+impl<S: PacketSource> PacketFeed for OwnedPackets<S> {
+    type Packet = PacketEvent;
+
+    fn next_packet(&mut self) -> Result<Option<PacketEvent>, SourceError> {
+        self.0.next_packet()
+    }
+
+    fn release(&mut self, packet: PacketEvent) {
+        self.0.recycle(packet.data);
+    }
+
+    fn snaplen(&self) -> Option<u32> {
+        self.0.snaplen()
+    }
+}
+
+/// Feed over packets the caller keeps, such as a window of live frames.
+struct BorrowedPackets<I>(I);
+
+impl<'a, I: Iterator<Item = &'a PacketEvent>> PacketFeed for BorrowedPackets<I> {
+    type Packet = &'a PacketEvent;
+
+    fn next_packet(&mut self) -> Result<Option<&'a PacketEvent>, SourceError> {
+        Ok(self.0.next())
+    }
+
+    fn release(&mut self, _packet: &'a PacketEvent) {}
+
+    fn snaplen(&self) -> Option<u32> {
+        None
+    }
+}
+
+/// Analyze packets the caller keeps in memory under the input name `input`,
+/// without copying them.
+pub(crate) fn analyze_borrowed<'a, I>(
+    config: &AnalyzerConfig,
+    input: &str,
+    packets: I,
+) -> Result<Report, AnalysisError>
+where
+    I: Iterator<Item = &'a PacketEvent>,
+{
+    analyze_input(config, input, None, BorrowedPackets(packets), &mut ())
+}
+
+/// Run the analysis over `source`, reporting it as `input`. Without
+/// `input_bytes` (no file behind the source), the report's input size is the
+/// bytes of the packets read.
+fn analyze_input<F: PacketFeed>(
+    config: &AnalyzerConfig,
+    input: &str,
+    input_bytes: Option<u64>,
+    mut source: F,
+    observer: &mut dyn AnalysisObserver,
 ) -> Result<Report, AnalysisError> {
     let mut packets_total = 0u64;
     let mut first_ts = None;
@@ -116,453 +306,1023 @@ pub fn analyze_source<S: PacketSource>(
     let mut artnet_stats: HashMap<u16, UniverseStats> = HashMap::new();
     let mut sacn_stats: HashMap<u16, UniverseStats> = HashMap::new();
     let mut dmx_store = DmxStore::new();
+    let mut sync_tracker = SyncTracker::default();
+    let mut discovery_tracker = DiscoveryTracker::default();
+    let mut address_priorities = AddressPriorityTracker::default();
+    let mut node_tracker = NodeTracker::default();
+    let mut timecode_tracker = TimecodeTracker::default();
+    let mut psn_trackers = PsnTrackers::default();
+    let mut osc_tracker = OscTracker::default();
+    let mut citp_tracker = CitpTracker::default();
+    let mut ptp_tracker = PtpTracker::default();
+    let mut igmp_tracker = IgmpTracker::default();
+    let mut network_tracker = NetworkTracker::default();
+    let mut triggers = Vec::new();
+    let mut rdm_tracker = RdmTracker::default();
     let mut dmx_state = DmxStateStore::new();
-    let mut compliance: HashMap<String, ComplianceSummary> = HashMap::new();
+    let mut compliance = RulesEngine::new(config);
+    let mut traffic = TrafficBreakdown::default();
+    let mut other_traffic = OtherTraffic::default();
+    let mut skipped = SkippedPackets::default();
+    let mut packet_sizes: Option<(u64, u64)> = None;
+    let mut timestamps = TimestampMonitor::new(config.max_timestamp_jump_s);
 
-    while let Some(PacketEvent { ts, linktype, data }) = source.next_packet()? {
+    while config.max_packets.is_none_or(|limit| packets_total < limit) {
+        let Some(packet) = source.next_packet()? else {
+            break;
+        };
+        let event: &PacketEvent = packet.borrow();
+        observer.on_packet(event);
+        let PacketEvent {
+            ts,
+            linktype,
+            ref data,
+            orig_len,
+        } = *event;
         packets_total += 1;
         update_ts_bounds(&mut first_ts, &mut last_ts, ts);
-        match parse_udp_packet(linktype, &data) {
+        timestamps.observe(packets_total, ts);
+        update_size_bounds(&mut packet_sizes, data.len() as u64);
+        let mut skip = None;
+        // Traffic counters keep the size on the wire; decoding sees the inner frame.
+        let (linktype, frame) = if config.decapsulate {
+            decapsulate(linktype, data)
+        } else {
+            (linktype, &data[..])
+        };
+        // A truncated packet still counts in its flow, but decoding its
+        // payload only yields misleading "too short" errors.
+        let truncated = orig_len.filter(|len| *len > data.len());
+        let parsed = match truncated {
+            Some(_) => parse_truncated_udp_packet(linktype, frame),
+            None => parse_udp_packet(linktype, frame),
+        };
+        match parsed {
             Ok(Some(udp)) => {
-                match parse_artdmx(udp.payload) {
-                    Ok(Some(art)) => {
+                let mut traffic_proto = Protocol::Udp;
+                compliance.examine(Protocol::Udp);
+                compliance.set_vlan(udp.vlan);
+                compliance.check_broadcast(observer, &udp, ts);
+                compliance.check_datagram_size(observer, &udp, ts);
+                compliance.check_checksums(observer, &udp, ts);
+                if let Some(original) = truncated {
+                    skip = Some(SkipReason::Truncated);
+                    let source = Some((&udp.src_ip, udp.src_port));
+                    compliance.record_truncated(observer, data.len(), original, source, ts);
+                }
+                let mut fields = packet_fields(&udp);
+                let artnet = if truncated.is_some() {
+                    Ok(None)
+                } else if let Ok(Some(reply)) = decode_artpollreply(udp.payload) {
+                    if config.artnet_enabled {
+                        traffic_proto = Protocol::ArtNet;
+                        compliance.examine(Protocol::ArtNet);
+                        node_tracker.add_reply(ts, udp.src_ip, reply);
+                    } else {
+                        skip = Some(SkipReason::ProtocolDisabled);
+                    }
+                    Ok(None)
+                } else if let Ok(Some(sync)) = decode_artsync(udp.payload) {
+                    if config.artnet_enabled {
+                        traffic_proto = Protocol::ArtNet;
+                        compliance.examine(Protocol::ArtNet);
+                        compliance.check_protocol_version(
+                            observer,
+                            "ArtSync",
+                            sync.protocol_version,
+                            &udp,
+                            ts,
+                        );
+                        let released = dmx_state.artsync_released(&Endpoints::from_udp(&udp), ts);
+                        record_artnet_frames(
+                            released,
+                            &mut artnet_stats,
+                            config,
+                            observer,
+                            &mut dmx_store,
+                        );
+                    } else {
+                        skip = Some(SkipReason::ProtocolDisabled);
+                    }
+                    Ok(None)
+                } else if let Some(timecode) = decode_arttimecode(udp.payload).transpose() {
+                    match timecode {
+                        Ok(timecode) if config.artnet_enabled => {
+                            traffic_proto = Protocol::ArtNet;
+                            compliance.examine(Protocol::ArtNet);
+                            compliance.check_protocol_version(
+                                observer,
+                                "ArtTimeCode",
+                                timecode.protocol_version,
+                                &udp,
+                                ts,
+                            );
+                            timecode_tracker.add(ts, udp.src_ip, timecode);
+                            Ok(None)
+                        }
+                        Ok(_) => {
+                            skip = Some(SkipReason::ProtocolDisabled);
+                            Ok(None)
+                        }
+                        Err(_) if !config.artnet_enabled => Ok(None),
+                        Err(err) => Err(err),
+                    }
+                } else if let Some(trigger) = decode_arttrigger(udp.payload).transpose() {
+                    match trigger {
+                        Ok(trigger) if config.artnet_enabled => {
+                            traffic_proto = Protocol::ArtNet;
+                            compliance.examine(Protocol::ArtNet);
+                            compliance.check_protocol_version(
+                                observer,
+                                "ArtTrigger",
+                                trigger.protocol_version,
+                                &udp,
+                                ts,
+                            );
+                            triggers.push(TriggerEvent {
+                                timestamp: ts,
+                                source_ip: udp.src_ip.to_string(),
+                                destination_ip: udp.dst_ip.to_string(),
+                                oem: trigger.oem,
+                                key: trigger.key,
+                                key_name: trigger.key_name().map(str::to_string),
+                                sub_key: trigger.sub_key,
+                            });
+                            Ok(None)
+                        }
+                        Ok(_) => {
+                            skip = Some(SkipReason::ProtocolDisabled);
+                            Ok(None)
+                        }
+                        Err(_) if !config.artnet_enabled => Ok(None),
+                        Err(err) => Err(err),
+                    }
+                } else if let Some(address) = decode_artaddress(udp.payload).transpose() {
+                    match address {
+                        Ok(address) if config.artnet_enabled => {
+                            traffic_proto = Protocol::ArtNet;
+                            compliance.examine(Protocol::ArtNet);
+                            compliance.check_protocol_version(
+                                observer,
+                                "ArtAddress",
+                                address.protocol_version,
+                                &udp,
+                                ts,
+                            );
+                            compliance.record_packet(
+                                observer,
+                                RuleId::ArtNetAddress,
+                                artaddress_detail(&udp.dst_ip, &address),
+                                &udp,
+                                ts,
+                            );
+                            Ok(None)
+                        }
+                        Ok(_) => {
+                            skip = Some(SkipReason::ProtocolDisabled);
+                            Ok(None)
+                        }
+                        Err(_) if !config.artnet_enabled => Ok(None),
+                        Err(err) => Err(err),
+                    }
+                } else if let Some(packet) = decode_rdm(udp.payload) {
+                    match packet {
+                        Ok(_) if !config.artnet_enabled => {
+                            skip = Some(SkipReason::ProtocolDisabled);
+                            Ok(None)
+                        }
+                        Ok(mut packet)
+                            if packet
+                                .universe()
+                                .is_none_or(|universe| config.universe_selected(universe)) =>
+                        {
+                            traffic_proto = Protocol::ArtNet;
+                            compliance.examine(Protocol::ArtNet);
+                            compliance.check_protocol_version(
+                                observer,
+                                packet.name(),
+                                packet.protocol_version(),
+                                &udp,
+                                ts,
+                            );
+                            fields = PacketFields {
+                                proto: Protocol::ArtNet,
+                                universe: packet.universe(),
+                                ..fields
+                            };
+                            if let RdmPacket::TodRequest(request) = &mut packet {
+                                request
+                                    .universes
+                                    .retain(|universe| config.universe_selected(*universe));
+                            }
+                            rdm_tracker.add(ts, udp.src_ip, packet);
+                            Ok(None)
+                        }
+                        Ok(_) => {
+                            traffic_proto = Protocol::ArtNet;
+                            skip = Some(SkipReason::UniverseFiltered);
+                            Ok(None)
+                        }
+                        Err(_) if !config.artnet_enabled => Ok(None),
+                        Err(err) => Err(err),
+                    }
+                } else if let Some(nzs) = decode_artnzs(udp.payload).transpose() {
+                    match nzs {
+                        Ok(_) if !config.artnet_enabled => {
+                            skip = Some(SkipReason::ProtocolDisabled);
+                            Ok(None)
+                        }
+                        Ok(nzs) if config.universe_selected(nzs.universe) => {
+                            traffic_proto = Protocol::ArtNet;
+                            compliance.examine(Protocol::ArtNet);
+                            compliance.check_protocol_version(
+                                observer,
+                                "ArtNzs",
+                                nzs.protocol_version,
+                                &udp,
+                                ts,
+                            );
+                            fields = PacketFields {
+                                proto: Protocol::ArtNet,
+                                universe: Some(nzs.universe),
+                                sequence: nzs.sequence,
+                                ..fields
+                            };
+                            add_artnet_nzs(&mut artnet_stats, nzs.universe, nzs.start_code);
+                            Ok(None)
+                        }
+                        Ok(_) => {
+                            traffic_proto = Protocol::ArtNet;
+                            skip = Some(SkipReason::UniverseFiltered);
+                            Ok(None)
+                        }
+                        Err(_) if !config.artnet_enabled => Ok(None),
+                        Err(err) => Err(err),
+                    }
+                } else if config.artnet_enabled {
+                    decode_artdmx(udp.payload)
+                } else {
+                    if matches!(decode_artdmx(udp.payload), Ok(Some(_))) {
+                        skip = Some(SkipReason::ProtocolDisabled);
+                    }
+                    Ok(None)
+                };
+                match artnet {
+                    Ok(Some(art)) if config.universe_selected(art.universe) => {
+                        traffic_proto = Protocol::ArtNet;
+                        compliance.examine(Protocol::ArtNet);
+                        compliance.check_protocol_version(
+                            observer,
+                            "ArtDmx",
+                            art.protocol_version,
+                            &udp,
+                            ts,
+                        );
+                        fields = PacketFields {
+                            proto: Protocol::ArtNet,
+                            universe: Some(art.universe),
+                            sequence: art.sequence,
+                            ..fields
+                        };
                         if udp.src_port != ARTNET_PORT && udp.dst_port != ARTNET_PORT {
-                            record_violation(
-                                &mut compliance,
-                                "artnet",
-                                "LS-ARTNET-PORT",
-                                "warning",
-                                "Non-standard Art-Net port (expected 6454); packet accepted",
-                                format_violation_example(
-                                    format!(
-                                        "ports={}:{}->{}:{}",
-                                        udp.src_ip, udp.src_port, udp.dst_ip, udp.dst_port
-                                    ),
-                                    Some((&udp.src_ip, udp.src_port)),
-                                    ts,
+                            compliance.record_packet(
+                                observer,
+                                RuleId::ArtNetPort,
+                                port_detail(&udp),
+                                &udp,
+                                ts,
+                            );
+                        }
+                        if udp.dst_ip.is_multicast() {
+                            compliance.record_packet(
+                                observer,
+                                RuleId::ArtNetMulticast,
+                                format!(
+                                    "{}, group={}",
+                                    compliance.universe_key(art.universe),
+                                    udp.dst_ip
                                 ),
+                                &udp,
+                                ts,
                             );
                         }
-                        let source_id = add_artnet_frame(
+                        let source_id = artnet_source_id(&udp.src_ip, udp.src_port);
+                        add_destination(&mut artnet_stats, art.universe, udp.dst_ip);
+                        add_burst_sample(
                             &mut artnet_stats,
+                            config,
                             art.universe,
-                            &udp.src_ip,
-                            udp.src_port,
-                            art.sequence,
                             ts,
+                            udp.payload.len() as u64,
                         );
-                        let slots = dmx_state.apply_partial(
+                        compliance.check_universe_rate(
+                            observer,
+                            Protocol::ArtNet,
                             art.universe,
-                            source_id.clone(),
-                            DmxProtocol::ArtNet,
-                            &art.slots,
+                            &udp,
+                            ts,
+                        );
+                        compliance.check_artnet_interval(
+                            observer,
+                            art.universe,
+                            &source_id,
+                            &udp,
+                            ts,
+                        );
+                        compliance.check_sequence(
+                            observer,
+                            Protocol::ArtNet,
+                            art.universe,
+                            &source_id,
+                            art.sequence,
+                            &udp,
+                            ts,
+                        );
+                        let released =
+                            dmx_state.artdmx_released(Endpoints::from_udp(&udp), ts, art);
+                        record_artnet_frames(
+                            released,
+                            &mut artnet_stats,
+                            config,
+                            observer,
+                            &mut dmx_store,
                         );
-                        dmx_store.push(DmxFrame {
-                            universe: art.universe,
-                            timestamp: ts,
-                            source_id,
-                            protocol: DmxProtocol::ArtNet,
-                            slots,
-                        });
                     }
+                    // Universe filtered out.
+                    Ok(Some(_)) => {
+                        traffic_proto = Protocol::ArtNet;
+                        skip = Some(SkipReason::UniverseFiltered);
+                    }
+                    // Not a DMX packet.
                     Ok(None) => {}
-                    Err(err) => match err {
-                        crate::protocols::artnet::error::ArtNetError::InvalidUniverseId {
-                            value,
-                        } => {
-                            record_violation(
-                                &mut compliance,
-                                "artnet",
-                                "LS-ARTNET-UNIVERSE-ID",
-                                "error",
-                                "Invalid Art-Net universe id; packet ignored",
-                                format_violation_example(
-                                    format!("value={}", value),
-                                    Some((&udp.src_ip, udp.src_port)),
-                                    ts,
-                                ),
-                            );
+                    Err(err) => {
+                        if !matches!(err, ArtNetError::TooShort { .. }) {
+                            traffic_proto = Protocol::ArtNet;
+                            skip = Some(SkipReason::MalformedArtnet);
+                            compliance.examine(Protocol::ArtNet);
                         }
-                        crate::protocols::artnet::error::ArtNetError::InvalidDmxLength { len } => {
-                            record_violation(
-                                &mut compliance,
-                                "artnet",
-                                "LS-ARTNET-LENGTH",
-                                "error",
-                                "Invalid ArtDMX length; packet ignored",
-                                format_violation_example(
-                                    format!("length={}", len),
-                                    Some((&udp.src_ip, udp.src_port)),
-                                    ts,
-                                ),
+                        let (rule, detail) = artnet_violation(&err);
+                        compliance.record_payload_error(
+                            observer,
+                            rule,
+                            detail,
+                            &udp,
+                            err.offset(),
+                            ts,
+                        );
+                    }
+                }
+                // Synchronization packets first: the data parser would reject them as too short.
+                let sacn = if truncated.is_some() {
+                    Ok(None)
+                } else if let Ok(Some(sync)) = decode_sacn_sync(udp.payload) {
+                    if config.sacn_enabled {
+                        traffic_proto = Protocol::Sacn;
+                        compliance.examine(Protocol::Sacn);
+                        sync_tracker.add_sync(ts, &sync);
+                        let released = dmx_state.sacn_sync_released(ts, &sync);
+                        record_sacn_frames(released, observer, &mut dmx_store);
+                    } else {
+                        skip = Some(SkipReason::ProtocolDisabled);
+                    }
+                    Ok(None)
+                } else if let Ok(Some(discovery)) = decode_sacn_discovery(udp.payload) {
+                    if config.sacn_enabled {
+                        traffic_proto = Protocol::Sacn;
+                        compliance.examine(Protocol::Sacn);
+                        discovery_tracker.add_discovery(udp.src_ip, discovery, |universe| {
+                            config.universe_selected(universe)
+                        });
+                    } else {
+                        skip = Some(SkipReason::ProtocolDisabled);
+                    }
+                    Ok(None)
+                } else if let Ok(Some(priorities)) = decode_sacn_address_priority(udp.payload) {
+                    // Per-address priorities share the sequence counter of the DMX data
+                    // but are not frames.
+                    if !config.sacn_enabled {
+                        skip = Some(SkipReason::ProtocolDisabled);
+                    } else if !config.universe_selected(priorities.universe) {
+                        traffic_proto = Protocol::Sacn;
+                        skip = Some(SkipReason::UniverseFiltered);
+                    } else {
+                        traffic_proto = Protocol::Sacn;
+                        compliance.examine(Protocol::Sacn);
+                        let source_id = sacn_source_id(&priorities.cid, &udp.src_ip, udp.src_port);
+                        compliance.check_sequence(
+                            observer,
+                            Protocol::Sacn,
+                            priorities.universe,
+                            &source_id,
+                            priorities.sequence,
+                            &udp,
+                            ts,
+                        );
+                        skip_sacn_sequence(
+                            &mut sacn_stats,
+                            priorities.universe,
+                            &source_id,
+                            priorities.sequence,
+                        );
+                        address_priorities.add(priorities.universe, &source_id, priorities.slots);
+                    }
+                    Ok(None)
+                } else if config.sacn_enabled {
+                    decode_sacn_dmx(udp.payload)
+                } else {
+                    if matches!(decode_sacn_dmx(udp.payload), Ok(Some(_))) {
+                        skip = Some(SkipReason::ProtocolDisabled);
+                    }
+                    Ok(None)
+                };
+                match sacn {
+                    // Receivers ignore the data of a terminated stream and drop the source.
+                    Ok(Some(sacn))
+                        if sacn.stream_terminated() && config.universe_selected(sacn.universe) =>
+                    {
+                        traffic_proto = Protocol::Sacn;
+                        compliance.examine(Protocol::Sacn);
+                        fields = PacketFields {
+                            proto: Protocol::Sacn,
+                            universe: Some(sacn.universe),
+                            sequence: sacn.sequence,
+                            priority: Some(sacn.priority),
+                            ..fields
+                        };
+                        let source_id = sacn_source_id(&sacn.cid, &udp.src_ip, udp.src_port);
+                        compliance.check_sequence(
+                            observer,
+                            Protocol::Sacn,
+                            sacn.universe,
+                            &source_id,
+                            sacn.sequence,
+                            &udp,
+                            ts,
+                        );
+                        skip_sacn_sequence(
+                            &mut sacn_stats,
+                            sacn.universe,
+                            &source_id,
+                            sacn.sequence,
+                        );
+                        terminate_sacn_source(&mut sacn_stats, sacn.universe, &source_id, ts);
+                    }
+                    // Preview data is not live output: counted, but kept out of the frames.
+                    Ok(Some(sacn))
+                        if sacn.preview_data() && config.universe_selected(sacn.universe) =>
+                    {
+                        traffic_proto = Protocol::Sacn;
+                        compliance.examine(Protocol::Sacn);
+                        fields = PacketFields {
+                            proto: Protocol::Sacn,
+                            universe: Some(sacn.universe),
+                            sequence: sacn.sequence,
+                            priority: Some(sacn.priority),
+                            ..fields
+                        };
+                        let source_id = sacn_source_id(&sacn.cid, &udp.src_ip, udp.src_port);
+                        compliance.check_sequence(
+                            observer,
+                            Protocol::Sacn,
+                            sacn.universe,
+                            &source_id,
+                            sacn.sequence,
+                            &udp,
+                            ts,
+                        );
+                        skip_sacn_sequence(
+                            &mut sacn_stats,
+                            sacn.universe,
+                            &source_id,
+                            sacn.sequence,
+                        );
+                        add_sacn_preview(&mut sacn_stats, sacn.universe);
+                    }
+                    Ok(Some(sacn)) if config.universe_selected(sacn.universe) => {
+                        traffic_proto = Protocol::Sacn;
+                        compliance.examine(Protocol::Sacn);
+                        fields = PacketFields {
+                            proto: Protocol::Sacn,
+                            universe: Some(sacn.universe),
+                            sequence: sacn.sequence,
+                            priority: Some(sacn.priority),
+                            ..fields
+                        };
+                        if udp.src_port != SACN_PORT && udp.dst_port != SACN_PORT {
+                            compliance.record_packet(
+                                observer,
+                                RuleId::SacnPort,
+                                port_detail(&udp),
+                                &udp,
+                                ts,
                             );
                         }
-                        crate::protocols::artnet::error::ArtNetError::TooShort {
-                            needed,
-                            actual,
-                        } => {
-                            record_violation(
-                                &mut compliance,
-                                "artnet",
-                                "LS-ARTNET-TOO-SHORT",
-                                "error",
-                                "Invalid Art-Net payload length; packet ignored",
-                                format_violation_example(
-                                    format!("needed={}, actual={}", needed, actual),
-                                    Some((&udp.src_ip, udp.src_port)),
-                                    ts,
+                        if is_broadcast(&udp.dst_ip) {
+                            compliance.record_packet(
+                                observer,
+                                RuleId::SacnBroadcast,
+                                format!(
+                                    "{}, dst={}",
+                                    compliance.universe_key(sacn.universe),
+                                    udp.dst_ip
                                 ),
+                                &udp,
+                                ts,
                             );
                         }
-                        crate::protocols::artnet::error::ArtNetError::UnsupportedOpCode {
-                            opcode,
-                        } => {
-                            record_violation(
-                                &mut compliance,
-                                "artnet",
-                                "LS-ARTNET-OPCODE",
-                                "error",
-                                "Unsupported Art-Net opcode; packet ignored",
-                                format_violation_example(
-                                    format!("opcode=0x{:04x}", opcode),
-                                    Some((&udp.src_ip, udp.src_port)),
-                                    ts,
-                                ),
+                        compliance.check_multicast_group(observer, sacn.universe, &udp, ts);
+                        if let Some(issue) = sacn.source_name_issue {
+                            compliance.record_packet(
+                                observer,
+                                RuleId::SacnSourceName,
+                                format!("cid={}, issue={}", sacn.cid, issue.as_str()),
+                                &udp,
+                                ts,
                             );
                         }
-                    },
-                }
-                match parse_sacn_dmx(udp.payload) {
-                    Ok(Some(sacn)) => {
-                        if udp.src_port != SACN_PORT && udp.dst_port != SACN_PORT {
-                            record_violation(
-                                &mut compliance,
-                                "sacn",
-                                "LS-SACN-PORT",
-                                "warning",
-                                "Non-standard sACN port (expected 5568); packet accepted",
-                                format_violation_example(
-                                    format!(
-                                        "ports={}:{}->{}:{}",
-                                        udp.src_ip, udp.src_port, udp.dst_ip, udp.dst_port
-                                    ),
-                                    Some((&udp.src_ip, udp.src_port)),
-                                    ts,
-                                ),
-                            );
+                        compliance.track_cid_address(&sacn.cid, &udp, ts);
+                        discovery_tracker.add_data(&sacn.cid, sacn.universe);
+                        if let Some(sync_address) = sacn.sync_address {
+                            sync_tracker.add_data(ts, &sacn.cid, sacn.universe, sync_address);
                         }
                         let source_id = add_sacn_frame(
                             &mut sacn_stats,
+                            config,
                             sacn.universe,
                             &udp.src_ip,
                             udp.src_port,
-                            sacn.cid,
-                            sacn.source_name,
+                            sacn.cid.clone(),
+                            sacn.source_name.clone(),
+                            udp.dst_ip.is_multicast().then_some(udp.ttl),
                             sacn.sequence,
                             ts,
                         );
-                        let slots = dmx_state.apply_partial(
+                        add_destination(&mut sacn_stats, sacn.universe, udp.dst_ip);
+                        if !udp.dst_ip.is_multicast() && !is_broadcast(&udp.dst_ip) {
+                            let receiver = SocketAddr::new(udp.dst_ip, udp.dst_port);
+                            add_sacn_unicast(&mut sacn_stats, sacn.universe, receiver);
+                            compliance.check_unicast(observer, sacn.universe, receiver, &udp, ts);
+                        }
+                        add_burst_sample(
+                            &mut sacn_stats,
+                            config,
                             sacn.universe,
-                            source_id.clone(),
-                            DmxProtocol::Sacn,
-                            &sacn.slots,
+                            ts,
+                            udp.payload.len() as u64,
                         );
-                        dmx_store.push(DmxFrame {
-                            universe: sacn.universe,
-                            timestamp: ts,
-                            source_id,
-                            protocol: DmxProtocol::Sacn,
-                            slots,
-                        });
+                        compliance.check_multicast_ttl(observer, &source_id, &udp, ts);
+                        compliance.check_universe_rate(
+                            observer,
+                            Protocol::Sacn,
+                            sacn.universe,
+                            &udp,
+                            ts,
+                        );
+                        compliance.check_source_rate(observer, sacn.universe, &source_id, &udp, ts);
+                        compliance.check_priority(
+                            observer,
+                            sacn.universe,
+                            &source_id,
+                            sacn.priority,
+                            &udp,
+                            ts,
+                        );
+                        compliance.check_sequence(
+                            observer,
+                            Protocol::Sacn,
+                            sacn.universe,
+                            &source_id,
+                            sacn.sequence,
+                            &udp,
+                            ts,
+                        );
+                        let released =
+                            dmx_state.sacn_dmx_released(Endpoints::from_udp(&udp), ts, sacn);
+                        record_sacn_frames(released, observer, &mut dmx_store);
                     }
+                    // Universe filtered out.
+                    Ok(Some(_)) => {
+                        traffic_proto = Protocol::Sacn;
+                        skip = Some(SkipReason::UniverseFiltered);
+                    }
+                    // Not a DMX packet.
                     Ok(None) => {}
-                    Err(err) => match err {
-                        crate::protocols::sacn::error::SacnError::InvalidStartCode { value } => {
-                            record_violation(
-                                &mut compliance,
-                                "sacn",
-                                "LS-SACN-START-CODE",
-                                "error",
-                                "Invalid sACN start code; packet ignored",
-                                format_violation_example(
-                                    format!("value={}", value),
-                                    Some((&udp.src_ip, udp.src_port)),
-                                    ts,
-                                ),
-                            );
-                        }
-                        crate::protocols::sacn::error::SacnError::InvalidPropertyValueCount {
-                            count,
-                        } => {
-                            record_violation(
-                                &mut compliance,
-                                "sacn",
-                                "LS-SACN-PROPERTY-COUNT",
-                                "error",
-                                "Invalid sACN property value count; packet ignored",
-                                format_violation_example(
-                                    format!("count={}", count),
-                                    Some((&udp.src_ip, udp.src_port)),
-                                    ts,
-                                ),
-                            );
-                        }
-                        crate::protocols::sacn::error::SacnError::InvalidDmxLength { length } => {
-                            record_violation(
-                                &mut compliance,
-                                "sacn",
-                                "LS-SACN-DMX-LENGTH",
-                                "error",
-                                "Invalid sACN DMX data length; packet ignored",
-                                format_violation_example(
-                                    format!("length={}", length),
-                                    Some((&udp.src_ip, udp.src_port)),
-                                    ts,
-                                ),
-                            );
-                        }
-                        crate::protocols::sacn::error::SacnError::TooShort { needed, actual } => {
-                            record_violation(
-                                &mut compliance,
-                                "sacn",
-                                "LS-SACN-TOO-SHORT",
-                                "error",
-                                "Invalid sACN payload length; packet ignored",
-                                format_violation_example(
-                                    format!("needed={}, actual={}", needed, actual),
-                                    Some((&udp.src_ip, udp.src_port)),
-                                    ts,
-                                ),
-                            );
-                        }
-                        crate::protocols::sacn::error::SacnError::InvalidAcnPid => {
-                            record_violation(
-                                &mut compliance,
-                                "sacn",
-                                "LS-SACN-ACN-PID",
-                                "error",
-                                "Invalid sACN ACN PID; packet ignored",
-                                format_violation_example(
-                                    "acn_pid=invalid".to_string(),
-                                    Some((&udp.src_ip, udp.src_port)),
-                                    ts,
-                                ),
-                            );
+                    Err(err) => {
+                        if !matches!(err, SacnError::TooShort { .. }) {
+                            traffic_proto = Protocol::Sacn;
+                            skip = Some(SkipReason::MalformedSacn);
+                            compliance.examine(Protocol::Sacn);
                         }
-                        crate::protocols::sacn::error::SacnError::InvalidRootVector { value } => {
-                            record_violation(
-                                &mut compliance,
-                                "sacn",
-                                "LS-SACN-ROOT-VECTOR",
-                                "error",
-                                "Invalid sACN root vector; packet ignored",
-                                format_violation_example(
-                                    format!("value=0x{:08x}", value),
-                                    Some((&udp.src_ip, udp.src_port)),
-                                    ts,
-                                ),
-                            );
+                        let (rule, detail) = sacn_violation(&err);
+                        compliance.record_payload_error(
+                            observer,
+                            rule,
+                            detail,
+                            &udp,
+                            err.offset(),
+                            ts,
+                        );
+                    }
+                }
+                // PosiStageNet is summarized but stays other UDP traffic.
+                if traffic_proto == Protocol::Udp && skip.is_none() {
+                    match decode_psn_data(udp.payload) {
+                        Ok(Some(data)) => psn_trackers.add_data(ts, udp.src_ip, &data),
+                        Ok(None) => match decode_psn_info(udp.payload) {
+                            Ok(Some(info)) => psn_trackers.add_info(udp.src_ip, &info),
+                            Ok(None) => {}
+                            Err(_) => skip = Some(SkipReason::MalformedPsn),
+                        },
+                        Err(_) => skip = Some(SkipReason::MalformedPsn),
+                    }
+                }
+                let mut app_proto = None;
+                if traffic_proto == Protocol::Udp && skip.is_none() {
+                    match decode_citp(udp.payload) {
+                        Ok(Some(citp)) => {
+                            citp_tracker.add(ts, udp.src_ip, &citp);
+                            app_proto = Some(Protocol::Citp);
                         }
-                        crate::protocols::sacn::error::SacnError::InvalidFramingVector {
-                            value,
-                        } => {
-                            record_violation(
-                                &mut compliance,
-                                "sacn",
-                                "LS-SACN-FRAMING-VECTOR",
-                                "error",
-                                "Invalid sACN framing vector; packet ignored",
-                                format_violation_example(
-                                    format!("value=0x{:08x}", value),
-                                    Some((&udp.src_ip, udp.src_port)),
-                                    ts,
-                                ),
-                            );
+                        Ok(None) => {}
+                        Err(_) => skip = Some(SkipReason::MalformedCitp),
+                    }
+                }
+                // PTP has no magic number: it is recognized by its ports.
+                if traffic_proto == Protocol::Udp
+                    && skip.is_none()
+                    && [PTP_EVENT_PORT, PTP_GENERAL_PORT].contains(&udp.dst_port)
+                {
+                    match decode_ptp(udp.payload) {
+                        Ok(Some(message)) => {
+                            ptp_tracker.add(ts, udp.src_ip, &message);
+                            app_proto = Some(Protocol::Ptp);
                         }
-                        crate::protocols::sacn::error::SacnError::InvalidDmpVector { value } => {
-                            record_violation(
-                                &mut compliance,
-                                "sacn",
-                                "LS-SACN-DMP-VECTOR",
-                                "error",
-                                "Invalid sACN DMP vector; packet ignored",
-                                format_violation_example(
-                                    format!("value=0x{:02x}", value),
-                                    Some((&udp.src_ip, udp.src_port)),
-                                    ts,
-                                ),
-                            );
+                        Ok(None) => {}
+                        Err(_) => skip = Some(SkipReason::MalformedPtp),
+                    }
+                }
+                if traffic_proto == Protocol::Udp
+                    && skip.is_none()
+                    && [DHCP_SERVER_PORT, DHCP_CLIENT_PORT].contains(&udp.dst_port)
+                {
+                    match decode_dhcp(udp.payload) {
+                        Ok(Some(message)) => {
+                            network_tracker.add_dhcp(ts, &message);
+                            app_proto = Some(Protocol::Dhcp);
                         }
-                    },
+                        Ok(None) => {}
+                        Err(_) => skip = Some(SkipReason::MalformedDhcp),
+                    }
                 }
-                add_flow_stats(&mut flow_stats, &udp, ts);
+                // OSC detection is a heuristic (a leading `/` or `#bundle`):
+                // a payload it rejects is simply not OSC.
+                if traffic_proto == Protocol::Udp && skip.is_none() {
+                    if let Ok(Some(osc)) = decode_osc(udp.payload) {
+                        osc_tracker.add(ts, udp.src_ip, &osc);
+                        app_proto = Some(Protocol::Osc);
+                    }
+                }
+                compliance.check_custom_rules(&fields, ts);
+                add_flow_stats(&mut flow_stats, config, &udp, app_proto, ts);
+                let class = match traffic_proto {
+                    Protocol::ArtNet => &mut traffic.artnet,
+                    Protocol::Sacn => &mut traffic.sacn,
+                    Protocol::Udp
+                    | Protocol::Tcp
+                    | Protocol::Osc
+                    | Protocol::Citp
+                    | Protocol::Ptp
+                    | Protocol::Dhcp => {
+                        other_traffic.add_udp(&udp, data.len());
+                        &mut traffic.other_udp
+                    }
+                };
+                count_traffic(class, data);
             }
-            Ok(None) => {}
-            Err(err) => match err {
-                crate::analysis::udp::error::UdpError::Slice(message) => record_violation(
-                    &mut compliance,
-                    "udp",
-                    "LS-UDP-SLICE",
-                    "error",
-                    "Invalid UDP slice; packet ignored",
-                    message,
-                ),
-                crate::analysis::udp::error::UdpError::MissingNetworkLayer => record_violation(
-                    &mut compliance,
-                    "udp",
-                    "LS-UDP-MISSING-NETWORK",
-                    "warning",
-                    "Invalid UDP packet: missing network layer; packet ignored",
-                    "missing network layer".to_string(),
-                ),
-                crate::analysis::udp::error::UdpError::MissingIpPayload => record_violation(
-                    &mut compliance,
-                    "udp",
-                    "LS-UDP-MISSING-PAYLOAD",
-                    "warning",
-                    "Invalid UDP packet: missing IP payload; packet ignored",
-                    "missing IP payload".to_string(),
-                ),
-                crate::analysis::udp::error::UdpError::TooShort { needed, actual } => {
-                    record_violation(
-                        &mut compliance,
-                        "udp",
-                        "LS-UDP-TOO-SHORT",
-                        "error",
-                        "Invalid UDP payload length; packet ignored",
-                        format!("needed={}, actual={}", needed, actual),
-                    )
+            Ok(None) => {
+                skip = Some(if is_supported_linktype(linktype) {
+                    SkipReason::NonUdp
+                } else {
+                    SkipReason::UnsupportedLinktype
+                });
+                count_traffic(&mut traffic.non_udp, data);
+                other_traffic.add_frame(linktype, frame);
+                igmp_tracker.add_frame(ts, linktype, frame);
+            }
+            Err(err) => {
+                count_traffic(&mut traffic.unparsed, data);
+                compliance.examine(Protocol::Udp);
+                match (err, truncated) {
+                    // The capture cut into the headers themselves.
+                    (UdpError::Slice(_), Some(original)) => {
+                        skip = Some(SkipReason::Truncated);
+                        compliance.record_truncated(observer, data.len(), original, None, ts);
+                    }
+                    (err, _) => {
+                        skip = Some(udp_skip_reason(&err));
+                        // ARP has no IP layer.
+                        if matches!(err, UdpError::MissingNetworkLayer) {
+                            network_tracker.add_frame(ts, linktype, frame);
+                        }
+                        let (rule, detail) = udp_violation(err);
+                        compliance.record(observer, rule, detail_example(detail));
+                    }
                 }
-            },
+            }
+        }
+        if let Some(reason) = skip {
+            skipped.add(reason);
         }
+        compliance.end_packet();
+        source.release(packet);
     }
+    // Frames still waiting for an ArtSync or sACN synchronization packet
+    // when the capture ends.
+    record_artnet_frames(
+        dmx_state.flush_released(),
+        &mut artnet_stats,
+        config,
+        observer,
+        &mut dmx_store,
+    );
+    record_sacn_frames(dmx_state.flush_sacn_released(), observer, &mut dmx_store);
+    compliance.check_cid_addresses(observer);
+    compliance.check_source_rates(observer);
+    compliance.check_subscribers(observer, igmp_tracker.joined_universes().as_ref());
+    let sacn_discovery = discovery_tracker.finish();
+    let address_priorities = address_priorities.finish();
+    compliance.check_universe_discovery(observer, &sacn_discovery);
 
-    let mut report = make_stub_report(&path.display().to_string(), path.metadata()?.len());
-    report.capture_summary = Some(CaptureSummary {
+    let capture_duration_s = match (first_ts, last_ts) {
+        (Some(start), Some(end)) if end >= start => Some(end - start),
+        _ => None,
+    };
+    let duration_s = capture_duration_s.filter(|duration| *duration > 0.0);
+    let bytes_total = traffic.bytes_total();
+    let capture_summary = CaptureSummary {
         packets_total,
         time_start: ts_to_rfc3339(first_ts),
         time_end: ts_to_rfc3339(last_ts),
-    });
-    report.generated_at = report
-        .capture_summary
-        .as_ref()
-        .and_then(|summary| summary.time_end.clone().or(summary.time_start.clone()))
+        traffic: Some(traffic),
+        duration_s: capture_duration_s.map(|duration| round_to(duration, DURATION_SCALE)),
+        avg_pps: duration_s
+            .map(|duration| round_to(packets_total as f64 / duration, AVERAGE_SCALE)),
+        avg_bps: duration_s.map(|duration| round_to(bytes_total as f64 / duration, AVERAGE_SCALE)),
+        packet_size_min: packet_sizes.map(|(min, _)| min),
+        packet_size_avg: (packets_total > 0)
+            .then(|| round_to(bytes_total as f64 / packets_total as f64, AVERAGE_SCALE)),
+        packet_size_max: packet_sizes.map(|(_, max)| max),
+        snaplen: source.snaplen(),
+        skipped: Some(skipped),
+    };
+    let generated_at = capture_summary
+        .time_end
+        .clone()
+        .or(capture_summary.time_start.clone())
         .unwrap_or_else(|| DEFAULT_GENERATED_AT.to_string());
 
-    let duration_s = match (first_ts, last_ts) {
-        (Some(start), Some(end)) if end > start => Some(end - start),
+    let mut conflicts = build_conflicts(&artnet_stats, &dmx_store, config, Protocol::ArtNet);
+    conflicts.extend(build_conflicts(
+        &sacn_stats,
+        &dmx_store,
+        config,
+        Protocol::Sacn,
+    ));
+    conflicts.sort_by(|a, b| {
+        a.universe
+            .cmp(&b.universe)
+            .then_with(|| a.sources.cmp(&b.sources))
+    });
+    if let Some(fixtures) = &config.fixtures {
+        fixtures.annotate_conflicts(&mut conflicts);
+    }
+    if let Some(patch) = &config.patch {
+        patch.annotate_conflicts(&mut conflicts);
+    }
+    for conflict in &conflicts {
+        observer.on_conflict_detected(conflict);
+    }
+    let series = match (config.series, first_ts, last_ts) {
+        (true, Some(first), Some(last)) => {
+            let timeline = Timeline::new(first, last);
+            let mut universes = build_universe_series(&artnet_stats, Protocol::ArtNet, timeline);
+            universes.extend(build_universe_series(&sacn_stats, Protocol::Sacn, timeline));
+            universes.sort_by(|a, b| {
+                a.universe
+                    .cmp(&b.universe)
+                    .then_with(|| a.proto.cmp(&b.proto))
+            });
+            Some(MetricSeries {
+                start: timeline.origin as f64,
+                universes,
+                flows: build_flow_series(&flow_stats, timeline),
+            })
+        }
         _ => None,
     };
-
-    let mut conflicts = build_conflicts(&artnet_stats, &dmx_store, "artnet");
-    conflicts.extend(build_conflicts(&sacn_stats, &dmx_store, "sacn"));
-    report.conflicts = conflicts;
-    report.flows = build_flow_summaries(flow_stats, duration_s);
-    report.universes = {
-        let mut universes = build_artnet_universe_summaries(artnet_stats, &dmx_store);
-        universes.extend(build_sacn_universe_summaries(sacn_stats, &dmx_store));
+    let movement_axes = config
+        .fixtures
+        .as_ref()
+        .map(FixtureMap::movement_axes)
+        .unwrap_or_default();
+    let mut scene_changes = Vec::new();
+    let mut fades = Vec::new();
+    let mut movement = Vec::new();
+    for frames in dmx_store.sources() {
+        scene_changes.extend(detect_scene_changes(
+            frames,
+            config.scene_change_min_fraction,
+        ));
+        fades.extend(detect_fades(frames));
+        if let Some(first) = frames.first() {
+            let axes: Vec<_> = movement_axes
+                .iter()
+                .filter(|axis| axis.universe == first.universe)
+                .collect();
+            movement.extend(movement_smoothness(frames, &axes));
+        }
+    }
+    scene_changes.sort_by(cmp_scene_changes);
+    fades.sort_by(cmp_fades);
+    movement.sort_by(cmp_movement);
+    let universe_keys: Vec<(u16, Protocol)> = artnet_stats
+        .keys()
+        .map(|universe| (*universe, Protocol::ArtNet))
+        .chain(
+            sacn_stats
+                .keys()
+                .map(|universe| (*universe, Protocol::Sacn)),
+        )
+        .collect();
+    let mut takeovers: Vec<Takeover> = universe_keys
+        .iter()
+        .flat_map(|(universe, protocol)| {
+            detect_takeovers(*protocol, dmx_store.written_frames(*universe, *protocol))
+        })
+        .collect();
+    takeovers.sort_by(cmp_takeovers);
+    let mut priority_spans: Vec<PrioritySpan> = sacn_stats
+        .keys()
+        .flat_map(|universe| priority_timeline(dmx_store.written_frames(*universe, Protocol::Sacn)))
+        .collect();
+    priority_spans.sort_by(cmp_priority_spans);
+    let mut silences = build_silences(&mut artnet_stats, config, Protocol::ArtNet);
+    silences.extend(build_silences(&mut sacn_stats, config, Protocol::Sacn));
+    silences.sort_by(cmp_silences);
+    let mut events = build_source_dropouts(&sacn_stats, last_ts);
+    events.sort_by(cmp_events);
+    let mirrored_universes = detect_mirrors(
+        universe_keys
+            .iter()
+            .copied()
+            .map(|(universe, protocol)| {
+                (
+                    universe,
+                    protocol,
+                    dmx_store.frames_for_universe(universe, protocol),
+                )
+            })
+            .collect(),
+    );
+    let nodes = node_tracker.finish(last_ts, |universe, start, end| {
+        lost_between(&artnet_stats, universe, start, end)
+    });
+    let universes = {
+        let mut universes = build_artnet_universe_summaries(artnet_stats, &dmx_store, config);
+        universes.extend(build_sacn_universe_summaries(
+            sacn_stats, &dmx_store, config,
+        ));
         universes.sort_by(|a, b| {
             a.universe
                 .cmp(&b.universe)
                 .then_with(|| a.proto.cmp(&b.proto))
         });
+        if let Some(fixtures) = &config.fixtures {
+            for universe in &mut universes {
+                fixtures.annotate_pairs(universe.universe, &mut universe.channel_pairs);
+            }
+        }
         universes
     };
-    report.compliance = finalize_compliance(compliance);
+    let (rdm_responders, rdm_transactions) = rdm_tracker.finish();
+    let input_bytes = input_bytes.unwrap_or(bytes_total);
+    let mut builder = ReportBuilder::new(input, input_bytes)
+        .generated_at(generated_at)
+        .capture_summary(capture_summary)
+        .universes(universes)
+        .flows(build_flow_summaries(flow_stats, config, duration_s))
+        .conflicts(conflicts)
+        .compliance(compliance.finalize())
+        .rules(config.active_rules())
+        .other_traffic(other_traffic.finalize(config.max_other_flows))
+        .scene_changes(scene_changes)
+        .fades(fades)
+        .movement(movement)
+        .mirrored_universes(mirrored_universes)
+        .takeovers(takeovers)
+        .priority_timeline(priority_spans)
+        .silences(silences)
+        .events(events)
+        .sync_latency(sync_tracker.finish())
+        .sacn_discovery(sacn_discovery)
+        .address_priorities(address_priorities)
+        .nodes(nodes)
+        .timecode(timecode_tracker.finish())
+        .triggers(triggers)
+        .rdm_responders(rdm_responders)
+        .rdm_transactions(rdm_transactions)
+        .psn_trackers(psn_trackers.finish())
+        .osc_addresses(osc_tracker.finish())
+        .citp_peers(citp_tracker.finish())
+        .ptp_domains(ptp_tracker.finish())
+        .igmp_groups(igmp_tracker.finish())
+        .network_events(network_tracker.finish())
+        .warnings(timestamps.finish());
+    if let Some(profile) = config.profile {
+        builder = builder.profile(profile.as_str());
+    }
+    if let Some(series) = series {
+        builder = builder.series(series);
+    }
+    if let Some(patch) = &config.patch {
+        builder = builder.patch(patch.entries().to_vec());
+    }
+    let mut report = builder.build()?;
+    report.health = Some(health_score(&report));
     Ok(report)
 }
 
-fn finalize_compliance(compliance: HashMap<String, ComplianceSummary>) -> Vec<ComplianceSummary> {
-    if compliance.is_empty() {
-        return Vec::new();
-    }
-    let mut entries: Vec<ComplianceSummary> = compliance.into_values().collect();
-    for entry in &mut entries {
-        entry.violations.sort_by(|a, b| {
-            severity_rank(&a.severity)
-                .cmp(&severity_rank(&b.severity))
-                .then_with(|| a.id.cmp(&b.id))
-        });
-        for violation in &mut entry.violations {
-            violation.examples.sort();
+fn udp_skip_reason(err: &UdpError) -> SkipReason {
+    match err {
+        UdpError::MissingNetworkLayer => SkipReason::NonIp,
+        UdpError::Fragmented { .. } => SkipReason::Fragmented,
+        UdpError::Slice(_) | UdpError::MissingIpPayload | UdpError::TooShort { .. } => {
+            SkipReason::MalformedUdp
         }
     }
-    entries.sort_by(|a, b| a.protocol.cmp(&b.protocol));
-    entries
 }
 
-fn severity_rank(severity: &str) -> u8 {
-    match severity {
-        "error" => 0,
-        "warning" => 1,
-        _ => 2,
+/// Record ArtDMX frames output by the ArtSync gate: universe metrics follow
+/// the frames' (possibly synchronized) timestamps.
+fn record_artnet_frames(
+    released: Vec<ReleasedArtDmx>,
+    stats: &mut HashMap<u16, UniverseStats>,
+    config: &AnalyzerConfig,
+    observer: &mut dyn AnalysisObserver,
+    dmx_store: &mut DmxStore,
+) {
+    for ReleasedArtDmx {
+        frame,
+        endpoints,
+        sequence,
+        slots,
+    } in released
+    {
+        add_artnet_frame(
+            stats,
+            config,
+            frame.universe,
+            &endpoints.src_ip,
+            endpoints.src_port,
+            sequence,
+            frame.timestamp,
+        );
+        observer.on_dmx_frame(&frame);
+        let write = FrameWrite {
+            slots,
+            priority: None,
+        };
+        dmx_store.push_written(frame, write);
     }
 }
 
-fn record_violation(
-    compliance: &mut HashMap<String, ComplianceSummary>,
-    protocol: &str,
-    id: &str,
-    severity: &str,
-    message: &str,
-    example: String,
+/// Record sACN frames output by the synchronization gate; universe metrics
+/// already counted the packets on arrival.
+fn record_sacn_frames(
+    released: Vec<ReleasedSacnDmx>,
+    observer: &mut dyn AnalysisObserver,
+    dmx_store: &mut DmxStore,
 ) {
-    let protocol = protocol.trim().to_ascii_lowercase();
-    let id = id.trim();
-    let severity = severity.trim();
-    let message = message.trim();
-    let example = normalize_example(example.trim());
-    let protocol_key = protocol.clone();
-    let entry = compliance
-        .entry(protocol_key)
-        .or_insert_with(|| ComplianceSummary {
-            protocol: protocol.clone(),
-            compliance_percentage: 100.0,
-            violations: Vec::new(),
-        });
-
-    if let Some(existing) = entry.violations.iter_mut().find(|v| v.id == id) {
-        existing.count += 1;
-        if existing.examples.len() < 3 && !existing.examples.contains(&example) {
-            existing.examples.push(example);
-        }
-        return;
+    for ReleasedSacnDmx { frame, write } in released {
+        observer.on_dmx_frame(&frame);
+        dmx_store.push_written(frame, write);
     }
+}
 
-    entry.violations.push(Violation {
-        id: id.to_string(),
-        severity: severity.to_string(),
-        message: message.to_string(),
-        count: 1,
-        examples: vec![example],
-    });
+fn count_traffic(count: &mut TrafficCount, data: &[u8]) {
+    count.packets += 1;
+    count.bytes += data.len() as u64;
 }
 
-fn format_violation_example(
-    base: String,
-    source: Option<(&IpAddr, u16)>,
-    ts: Option<f64>,
-) -> String {
-    let base = base.trim().to_string();
-    let Some((ip, port)) = source else {
-        return base;
-    };
-    let ts = ts_to_rfc3339(ts).unwrap_or_else(|| "unknown".to_string());
-    if base.is_empty() {
-        format!("source {}:{} @ {}", ip, port, ts)
-    } else {
-        format!("source {}:{} @ {}; {}", ip, port, ts, base)
-    }
+pub(crate) fn round_to(value: f64, scale: f64) -> f64 {
+    (value * scale).round() / scale
 }
 
-fn normalize_example(example: &str) -> String {
-    if example.is_empty() {
-        return "source unknown @ unknown".to_string();
-    }
-    if example.starts_with("source ") {
-        return example.to_string();
-    }
-    format!("source unknown @ unknown; {}", example)
+fn update_size_bounds(bounds: &mut Option<(u64, u64)>, size: u64) {
+    *bounds = Some(match *bounds {
+        Some((min, max)) => (min.min(size), max.max(size)),
+        None => (size, size),
+    });
 }
 
 fn update_ts_bounds(first: &mut Option<f64>, last: &mut Option<f64>, ts: Option<f64>) {
@@ -598,144 +1358,1492 @@ fn ts_to_rfc3339(ts: Option<f64>) -> Option<String> {
 
 #[cfg(test)]
 mod tests {
-    use super::{ComplianceSummary, finalize_compliance, record_violation};
-    use std::collections::HashMap;
+    use std::path::Path;
+
+    use etherparse::{IpNumber, Ipv4Header, PacketBuilder};
+    use pcap_parser::Linktype;
+
+    use super::udp::IEEE802_11_RADIOTAP;
+    use super::{AnalyzerConfig, run_analysis};
+    use crate::rules::RuleId;
+    use crate::source::PacketEvent;
+    use crate::test_util::{
+        ArtAddressBuilder, ArtDmxBuilder, ArtNzsBuilder, ArtRdmBuilder, ArtSyncBuilder,
+        ArtTimeCodeBuilder, ArtTodDataBuilder, ArtTodRequestBuilder, ArtTriggerBuilder,
+        MemorySource, PsnDataBuilder, PsnInfoBuilder, SacnDataBuilder, SacnDiscoveryBuilder,
+        SacnSyncBuilder, udp_event,
+    };
+    use crate::{
+        AnalysisObserver, DmxFrame, EventKind, FixtureMap, NetworkEventKind, NonZeroStartCodes,
+        PortAddress, Protocol, RdmPortInventory, RdmUid, Report, RigPatch, Severity, SkipReason,
+        TakeoverCause, TriggerEvent, Violation, WarningKind,
+    };
+
+    fn artdmx_payload(universe: u16) -> Vec<u8> {
+        ArtDmxBuilder::new(universe).slots(vec![0; 2]).build()
+    }
+
+    fn sacn_payload(universe: u16) -> Vec<u8> {
+        SacnDataBuilder::new(universe)
+            .source_name("A")
+            .slots(vec![0; 2])
+            .build()
+    }
+
+    fn analyze(config: &AnalyzerConfig, events: Vec<PacketEvent>) -> Report {
+        let source = events.into_iter().collect::<MemorySource>();
+        // The report input only needs an existing file to size.
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+        run_analysis(config, &path, source, &mut ()).unwrap()
+    }
+
+    fn find_violation<'a>(report: &'a Report, id: &str) -> Option<&'a Violation> {
+        report
+            .compliance
+            .iter()
+            .flat_map(|entry| &entry.violations)
+            .find(|violation| violation.id == id)
+    }
 
     #[test]
-    fn compliance_aggregates_by_protocol_and_id() {
-        let mut compliance: HashMap<String, ComplianceSummary> = HashMap::new();
+    fn artdmx_to_multicast_group_is_reported() {
+        let payload = artdmx_payload(1);
+        let events = vec![
+            udp_event(0.0, [10, 0, 0, 1], [239, 255, 0, 1], 6454, &payload),
+            udp_event(1.0, [10, 0, 0, 1], [10, 255, 255, 255], 6454, &payload),
+        ];
+        let report = analyze(&AnalyzerConfig::default(), events);
 
-        record_violation(
-            &mut compliance,
-            "artnet",
-            "LS-ARTNET-UNIVERSE-ID",
-            "error",
-            "Invalid Art-Net universe id; packet ignored",
-            "value=32768".to_string(),
+        let violation =
+            find_violation(&report, "LS-ARTNET-MULTICAST").expect("multicast violation");
+        assert_eq!(violation.count, 1);
+        assert_eq!(violation.examples[0].source_port, Some(6454));
+        assert_eq!(
+            violation.examples[0].detail,
+            "universe=1, group=239.255.0.1"
         );
-        record_violation(
-            &mut compliance,
-            "artnet",
-            "LS-ARTNET-UNIVERSE-ID",
-            "error",
-            "Invalid Art-Net universe id; packet ignored",
-            "value=40000".to_string(),
+    }
+
+    #[test]
+    fn vlan_tagged_frames_are_analyzed_with_their_vlan() {
+        fn tagged(mut event: PacketEvent, tags: &[(u16, u16)]) -> PacketEvent {
+            let mut data = event.data[..12].to_vec();
+            for (tpid, id) in tags {
+                data.extend_from_slice(&tpid.to_be_bytes());
+                data.extend_from_slice(&id.to_be_bytes());
+            }
+            data.extend_from_slice(&event.data[12..]);
+            event.data = data;
+            event
+        }
+        let payload = sacn_payload(7);
+        let console = [10, 0, 0, 1];
+        let events = vec![
+            tagged(
+                udp_event(0.0, console, [239, 255, 0, 7], 5568, &payload),
+                &[(0x8100, 10)],
+            ),
+            tagged(
+                udp_event(0.5, console, [239, 255, 0, 7], 5568, &payload),
+                &[(0x88a8, 200), (0x8100, 10)],
+            ),
+            tagged(
+                udp_event(1.0, console, [255, 255, 255, 255], 5568, &payload),
+                &[(0x8100, 10)],
+            ),
+        ];
+        let report = analyze(&AnalyzerConfig::default(), events);
+
+        assert_eq!(report.universes[0].frames_count, 3);
+        let vlans: Vec<_> = report
+            .flows
+            .iter()
+            .map(|flow| (flow.dst.as_str(), flow.vlan, flow.outer_vlan))
+            .collect();
+        assert_eq!(
+            vlans,
+            vec![
+                ("239.255.0.7:5568", Some(10), None),
+                ("239.255.0.7:5568", Some(10), Some(200)),
+                ("255.255.255.255:5568", Some(10), None),
+            ]
         );
-        record_violation(
-            &mut compliance,
-            "sacn",
-            "LS-SACN-START-CODE",
-            "error",
-            "Invalid sACN start code; packet ignored",
-            "value=1".to_string(),
+        let violation = find_violation(&report, "LS-SACN-BROADCAST").expect("broadcast violation");
+        assert_eq!(violation.examples[0].vlan, Some(10));
+    }
+
+    #[test]
+    fn tunneled_frames_are_analyzed_when_decapsulation_is_enabled() {
+        let inner = udp_event(0.0, [10, 0, 0, 1], [239, 255, 0, 7], 5568, &sacn_payload(7));
+        let vxlan = [&[0x08, 0, 0, 0, 0, 0, 0x64, 0][..], &inner.data].concat();
+        let events = || vec![udp_event(0.0, [10, 9, 0, 1], [10, 9, 0, 2], 4789, &vxlan)];
+
+        let report = analyze(&AnalyzerConfig::default(), events());
+        assert!(report.universes.is_empty());
+        assert_eq!(report.flows[0].dst, "10.9.0.2:4789");
+
+        let config = AnalyzerConfig {
+            decapsulate: true,
+            ..AnalyzerConfig::default()
+        };
+        let report = analyze(&config, events());
+        assert_eq!(report.universes[0].frames_count, 1);
+        assert_eq!(report.flows[0].dst, "239.255.0.7:5568");
+        assert_eq!(
+            report.capture_summary.unwrap().packet_size_max,
+            Some(vxlan.len() as u64 + 42)
         );
+    }
+
+    #[test]
+    fn truncated_packets_are_reported_and_not_decoded() {
+        let mut event = udp_event(0.0, [10, 0, 0, 1], [239, 255, 0, 7], 5568, &sacn_payload(7));
+        let original = event.data.len();
+        event.data.truncate(96);
+        event.orig_len = Some(original);
+        let mut cut_headers = udp_event(1.0, [10, 0, 0, 1], [239, 255, 0, 7], 5568, &[0; 8]);
+        cut_headers.data.truncate(30);
+        cut_headers.orig_len = Some(50);
+        let report = analyze(&AnalyzerConfig::default(), vec![event, cut_headers]);
 
-        let artnet = compliance.get("artnet").expect("artnet compliance");
-        assert_eq!(artnet.violations.len(), 1);
-        let violation = &artnet.violations[0];
+        assert!(report.universes.is_empty());
+        assert!(find_violation(&report, "LS-SACN-TOO-SHORT").is_none());
+        let violation = find_violation(&report, "LS-CAPTURE-TRUNCATED").expect("truncated");
         assert_eq!(violation.count, 2);
-        assert_eq!(violation.examples.len(), 2);
+        let example = |detail: &str| {
+            violation
+                .examples
+                .iter()
+                .find(|example| example.detail == detail)
+                .expect("example")
+        };
+        let cut_payload = example(&format!("captured=96, original={original}"));
+        assert_eq!(cut_payload.source_ip.as_deref(), Some("10.0.0.1"));
+        assert_eq!(example("captured=30, original=50").source_ip, None);
+        // Headers that survived still place the packet in its flow.
+        assert_eq!(report.flows.len(), 1);
+        assert_eq!(report.flows[0].dst, "239.255.0.7:5568");
+        let summary = report.capture_summary.unwrap();
+        assert_eq!(summary.skipped.unwrap().reasons[&SkipReason::Truncated], 2);
+        assert_eq!(summary.snaplen, None);
+        let traffic = summary.traffic.unwrap();
+        assert_eq!(traffic.other_udp.packets, 1);
+        assert_eq!(traffic.unparsed.packets, 1);
+    }
+
+    #[test]
+    fn wireless_frames_are_analyzed_with_their_retry_rate() {
+        fn over_the_air(mut event: PacketEvent, retry: bool) -> PacketEvent {
+            let flags = if retry { 0x0a } else { 0x02 };
+            let mut data = vec![0, 0, 8, 0, 0, 0, 0, 0, 0x08, flags, 0, 0];
+            data.extend_from_slice(&[0xff; 18]);
+            data.extend_from_slice(&[0, 0, 0xaa, 0xaa, 0x03, 0, 0, 0]);
+            data.extend_from_slice(&event.data[12..]);
+            event.linktype = IEEE802_11_RADIOTAP;
+            event.data = data;
+            event
+        }
+        let payload = sacn_payload(7);
+        let events = [(0.0, false), (0.5, true), (1.0, false), (1.5, false)]
+            .into_iter()
+            .map(|(ts, retry)| {
+                let event = udp_event(ts, [10, 0, 0, 1], [239, 255, 0, 7], 5568, &payload);
+                over_the_air(event, retry)
+            })
+            .collect();
+        let report = analyze(&AnalyzerConfig::default(), events);
+
+        assert_eq!(report.universes[0].frames_count, 4);
+        let wifi = report.flows[0].wifi.expect("wifi retries");
+        assert_eq!((wifi.frames, wifi.retries), (4, 1));
+        assert_eq!(wifi.retry_rate, 0.25);
+    }
+
+    #[test]
+    fn sacn_to_broadcast_is_reported() {
+        let payload = sacn_payload(7);
+        let events = vec![
+            udp_event(0.0, [10, 0, 0, 1], [239, 255, 0, 7], 5568, &payload),
+            udp_event(1.0, [10, 0, 0, 1], [255, 255, 255, 255], 5568, &payload),
+        ];
+        let report = analyze(&AnalyzerConfig::default(), events);
+
+        assert_eq!(report.universes[0].frames_count, 2);
+        let violation = find_violation(&report, "LS-SACN-BROADCAST").expect("broadcast violation");
+        assert_eq!(violation.count, 1);
+        assert_eq!(
+            violation.examples[0].detail,
+            "universe=7, dst=255.255.255.255"
+        );
+        assert!(find_violation(&report, "LS-SACN-SOURCE-NAME").is_none());
+
+        let sacn = report
+            .compliance
+            .iter()
+            .find(|entry| entry.protocol == Protocol::Sacn)
+            .expect("sacn compliance");
+        assert_eq!(sacn.compliance_percentage, 75.0);
+    }
+
+    #[test]
+    fn sacn_to_another_universe_group_is_reported() {
+        let payload = sacn_payload(258);
+        let events = vec![
+            udp_event(0.0, [10, 0, 0, 1], [239, 255, 1, 2], 5568, &payload),
+            udp_event(0.1, [10, 0, 0, 1], [239, 255, 0, 1], 5568, &payload),
+            udp_event(0.2, [10, 0, 0, 1], [10, 0, 0, 2], 5568, &payload),
+        ];
+        let report = analyze(&AnalyzerConfig::default(), events);
+
+        assert_eq!(report.universes[0].frames_count, 3);
+        let violation =
+            find_violation(&report, "LS-SACN-MULTICAST-MISMATCH").expect("mismatch violation");
+        assert_eq!(violation.count, 1);
+        assert_eq!(
+            violation.examples[0].detail,
+            "universe=258, dst=239.255.0.1, expected=239.255.1.2"
+        );
+    }
+
+    #[test]
+    fn sacn_unicast_receivers_are_listed_and_optionally_reported() {
+        let payload = sacn_payload(1);
+        let events = || {
+            vec![
+                udp_event(0.0, [10, 0, 0, 1], [239, 255, 0, 1], 5568, &payload),
+                udp_event(0.1, [10, 0, 0, 1], [10, 0, 0, 20], 5568, &payload),
+                udp_event(0.2, [10, 0, 0, 1], [10, 0, 0, 20], 5568, &payload),
+                udp_event(0.3, [10, 0, 0, 1], [10, 0, 0, 3], 5568, &payload),
+            ]
+        };
+        let report = analyze(&AnalyzerConfig::default(), events());
+        assert_eq!(
+            report.universes[0].unicast_receivers,
+            vec!["10.0.0.3:5568", "10.0.0.20:5568"]
+        );
+        assert!(find_violation(&report, "LS-SACN-UNICAST").is_none());
+
+        let mut config = AnalyzerConfig::default();
+        config.rules.set_enabled(RuleId::SacnUnicast, true);
+        let report = analyze(&config, events());
+        let violation = find_violation(&report, "LS-SACN-UNICAST").expect("unicast violation");
+        assert_eq!(violation.count, 2);
+        assert_eq!(
+            violation.examples[0].detail,
+            "universe=1, receiver=10.0.0.20:5568"
+        );
+    }
+
+    #[test]
+    fn custom_rules_are_reported_under_the_packet_protocol() {
+        let config = AnalyzerConfig::from_json(
+            r#"{"custom_rules": [
+                "proto == \"artnet\" && dst_port != 6454 -> warning CUSTOM-PORT",
+                "universe == 7 || ttl < 2 -> info CUSTOM-U7"
+            ]}"#,
+        )
+        .unwrap();
+        let events = vec![
+            udp_event(0.0, [10, 0, 0, 1], [10, 0, 0, 2], 6455, &artdmx_payload(1)),
+            udp_event(0.1, [10, 0, 0, 1], [10, 0, 0, 2], 6454, &artdmx_payload(7)),
+            udp_event(0.2, [10, 0, 0, 3], [239, 255, 0, 7], 5568, &sacn_payload(7)),
+        ];
+        let report = analyze(&config, events);
+
+        let port = find_violation(&report, "CUSTOM-PORT").expect("custom port violation");
+        assert_eq!(port.count, 1);
+        assert_eq!(
+            port.message,
+            r#"Custom rule: proto == "artnet" && dst_port != 6454"#
+        );
+        assert!(
+            port.examples[0]
+                .to_string()
+                .starts_with("source 10.0.0.1:6455 @ ")
+        );
+        assert_eq!(port.examples[0].detail, "proto=artnet, dst=10.0.0.2:6455");
+        assert_eq!(port.spec_reference, None);
+
+        let universe = find_violation(&report, "CUSTOM-U7").expect("custom universe violation");
+        assert_eq!(universe.count, 1);
+        let sacn = report
+            .compliance
+            .iter()
+            .find(|entry| entry.protocol == Protocol::Sacn)
+            .expect("sacn compliance");
+        assert!(sacn.violations.iter().any(|v| v.id == "CUSTOM-U7"));
+        assert_eq!(sacn.compliance_percentage, 100.0);
+
+        let ids: Vec<&str> = report.rules.iter().map(|rule| rule.id.as_str()).collect();
+        assert!(ids.contains(&"CUSTOM-PORT"));
+        assert!(ids.is_sorted());
+    }
+
+    #[test]
+    fn capture_summary_breaks_traffic_down_by_class() {
+        let artnet = udp_event(0.0, [10, 0, 0, 1], [10, 0, 0, 2], 6454, &artdmx_payload(1));
+        let artnet_bytes = artnet.data.len() as u64;
+        let tcp = {
+            let builder = PacketBuilder::ethernet2([1, 2, 3, 4, 5, 6], [7, 8, 9, 10, 11, 12])
+                .ipv4([10, 0, 0, 1], [10, 0, 0, 2], 64)
+                .tcp(80, 80, 0, 1024);
+            let mut data = Vec::<u8>::with_capacity(builder.size(0));
+            builder.write(&mut data, &[]).unwrap();
+            PacketEvent {
+                ts: Some(0.3),
+                linktype: Linktype::ETHERNET,
+                data,
+                orig_len: None,
+            }
+        };
+        let events = vec![
+            artnet,
+            udp_event(0.1, [10, 0, 0, 1], [239, 255, 0, 1], 5568, &sacn_payload(1)),
+            udp_event(0.2, [10, 0, 0, 1], [10, 0, 0, 2], 9999, b"hello"),
+            tcp,
+            PacketEvent {
+                ts: Some(0.4),
+                linktype: Linktype::ETHERNET,
+                data: vec![0u8; 4],
+                orig_len: None,
+            },
+        ];
+        let max_size = events.iter().map(|event| event.data.len() as u64).max();
+        let report = analyze(&AnalyzerConfig::default(), events);
+
+        let summary = report.capture_summary.expect("capture summary");
+        let traffic = summary.traffic.expect("traffic breakdown");
+        assert_eq!(traffic.artnet.packets, 1);
+        assert_eq!(traffic.artnet.bytes, artnet_bytes);
+        assert_eq!(traffic.sacn.packets, 1);
+        assert_eq!(traffic.other_udp.packets, 1);
+        assert_eq!(traffic.non_udp.packets, 1);
+        assert_eq!(traffic.unparsed.packets, 1);
+        assert_eq!(traffic.unparsed.bytes, 4);
+        assert_eq!(traffic.packets_total(), summary.packets_total);
+        let skipped = summary.skipped.as_ref().expect("skipped packets");
+        assert_eq!(skipped.total, 2);
+        assert_eq!(skipped.reasons[&SkipReason::NonUdp], 1);
+        assert_eq!(skipped.reasons[&SkipReason::MalformedUdp], 1);
+
+        let other: Vec<(Protocol, &str)> = report
+            .other_traffic
+            .iter()
+            .map(|flow| (flow.transport, flow.dst.as_str()))
+            .collect();
+        assert_eq!(other.len(), 2);
+        assert!(other.contains(&(Protocol::Udp, "10.0.0.2:9999")));
+        assert!(other.contains(&(Protocol::Tcp, "10.0.0.2:80")));
+
+        assert_eq!(summary.duration_s, Some(0.4));
+        assert_eq!(summary.avg_pps, Some(12.5));
+        assert!(
+            (summary.avg_bps.expect("avg bps") - traffic.bytes_total() as f64 / 0.4).abs() < 1e-3
+        );
+        assert_eq!(summary.packet_size_min, Some(4));
+        assert_eq!(summary.packet_size_max, max_size);
+        assert_eq!(
+            summary.packet_size_avg,
+            Some(traffic.bytes_total() as f64 / 5.0)
+        );
+    }
+
+    #[test]
+    fn in_memory_packets_are_reported_under_their_input_name() {
+        let events = vec![
+            udp_event(0.0, [10, 0, 0, 1], [10, 0, 0, 2], 6454, &artdmx_payload(1)),
+            udp_event(0.1, [10, 0, 0, 1], [10, 0, 0, 2], 6454, &artdmx_payload(1)),
+        ];
+        let bytes: u64 = events.iter().map(|event| event.data.len() as u64).sum();
+        let report = crate::Analyzer::default()
+            .analyze_packets("live:eth0", &events)
+            .unwrap();
+
+        assert_eq!(report.input.path, "live:eth0");
+        assert_eq!(report.input.bytes, bytes);
+        assert_eq!(report.capture_summary.unwrap().packets_total, 2);
+        assert_eq!(report.universes.len(), 1);
+    }
+    #[test]
+    fn artsync_releases_held_artdmx_at_the_sync_time() {
+        let desk = [10, 0, 0, 1];
+        let nodes = [10, 0, 0, 255];
+        let sync = ArtSyncBuilder::new().build();
+        let events = vec![
+            udp_event(0.0, desk, nodes, 6454, &sync),
+            udp_event(0.010, desk, nodes, 6454, &artdmx_payload(1)),
+            udp_event(0.025, desk, nodes, 6454, &sync),
+            udp_event(0.040, desk, nodes, 6454, &artdmx_payload(1)),
+            udp_event(0.050, desk, nodes, 6454, &sync),
+            // Never synchronized: output at its own time at the end.
+            udp_event(0.060, desk, nodes, 6454, &artdmx_payload(1)),
+        ];
+        let report = analyze(&AnalyzerConfig::default(), events);
+
+        let universe = &report.universes[0];
+        assert_eq!(universe.frames_count, 3);
+        assert_eq!(universe.first_seen, Some(0.025));
+        assert_eq!(universe.last_seen, Some(0.06));
+        assert!(find_violation(&report, "LS-ARTNET-OPCODE").is_none());
+        let traffic = report.capture_summary.unwrap().traffic.unwrap();
+        assert_eq!(traffic.artnet.packets, 6);
+    }
+
+    #[test]
+    fn sacn_sync_releases_held_frames_and_reports_latency_per_universe() {
+        struct FrameTimes(Vec<(u16, Option<f64>)>);
+
+        impl AnalysisObserver for FrameTimes {
+            fn on_dmx_frame(&mut self, frame: &DmxFrame) {
+                self.0.push((frame.universe, frame.timestamp));
+            }
+        }
+
+        let console = [10, 0, 0, 1];
+        let group = [239, 255, 0, 1];
+        let sync = SacnSyncBuilder::new(7000).build();
+        let data = |universe| SacnDataBuilder::new(universe).sync_address(7000).build();
+        let events = vec![
+            udp_event(0.0, console, group, 5568, &sync),
+            udp_event(0.010, console, group, 5568, &data(1)),
+            udp_event(0.012, console, group, 5568, &data(2)),
+            udp_event(0.025, console, group, 5568, &sync),
+            // Never synchronized: output at its own time at the end.
+            udp_event(0.040, console, group, 5568, &data(1)),
+        ];
+        let source = events.into_iter().collect::<MemorySource>();
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+        let mut frames = FrameTimes(Vec::new());
+        let report = run_analysis(&AnalyzerConfig::default(), &path, source, &mut frames).unwrap();
+
+        assert_eq!(
+            frames.0,
+            vec![(1, Some(0.025)), (2, Some(0.025)), (1, Some(0.04))]
+        );
+        let sync = &report.sync_latency[0];
+        assert_eq!(sync.universes, vec![1, 2]);
+        assert_eq!(sync.synced_frames, 2);
+        let per_universe: Vec<(u16, u64, f64)> = sync
+            .per_universe
+            .iter()
+            .map(|universe| {
+                let latency = universe.latency.as_ref().expect("latency");
+                (universe.universe, universe.synced_frames, latency.max_ms)
+            })
+            .collect();
+        assert_eq!(per_universe, vec![(1, 1, 15.0), (2, 1, 13.0)]);
+    }
+
+    #[test]
+    fn universe_discovery_is_compared_with_the_universes_sent() {
+        let console = [10, 0, 0, 1];
+        let discovery_group = [239, 255, 250, 214];
+        let events = vec![
+            udp_event(
+                0.0,
+                console,
+                discovery_group,
+                5568,
+                &SacnDiscoveryBuilder::new(vec![1, 3]).build(),
+            ),
+            udp_event(0.1, console, [239, 255, 0, 1], 5568, &sacn_payload(1)),
+            udp_event(0.2, console, [239, 255, 0, 2], 5568, &sacn_payload(2)),
+        ];
+        let report = analyze(&AnalyzerConfig::default(), events);
+
+        assert!(find_violation(&report, "LS-SACN-ROOT-VECTOR").is_none());
+        let source = &report.sacn_discovery[0];
+        assert_eq!(source.cid, "11".repeat(16));
+        assert_eq!(source.declared, vec![1, 3]);
+        assert_eq!(source.sent, vec![1, 2]);
+        assert_eq!(source.undeclared, vec![2]);
+        assert_eq!(source.not_sent, vec![3]);
+        let violation = find_violation(&report, "LS-SACN-DISCOVERY").expect("discovery");
+        assert_eq!(violation.severity, Severity::Warning);
+        assert_eq!(
+            violation.examples[0].detail,
+            format!("cid={}, undeclared=2, not_sent=3", "11".repeat(16))
+        );
+        let traffic = report.capture_summary.unwrap().traffic.unwrap();
+        assert_eq!(traffic.sacn.packets, 3);
+    }
+
+    #[test]
+    fn address_priority_packets_are_mapped_not_flagged() {
+        let console = [10, 0, 0, 1];
+        let group = [239, 255, 0, 1];
+        let mut priorities = vec![150; 2];
+        priorities.extend([0; 2]);
+        let events = (0u8..6)
+            .map(|sequence| {
+                let builder = SacnDataBuilder::new(1).sequence(sequence);
+                let payload = if sequence % 2 == 1 {
+                    builder.start_code(0xdd).slots(priorities.clone()).build()
+                } else {
+                    builder.slots(vec![255; 4]).build()
+                };
+                udp_event(f64::from(sequence) * 0.02, console, group, 5568, &payload)
+            })
+            .collect();
+        let report = analyze(&AnalyzerConfig::default(), events);
+
+        assert!(find_violation(&report, "LS-SACN-START-CODE").is_none());
+        assert!(find_violation(&report, "LS-SACN-SEQ-GAP").is_none());
+        let universe = &report.universes[0];
+        assert_eq!(universe.frames_count, 3);
+        assert_eq!(universe.loss_packets, Some(0));
+        let map = &report.address_priorities[0];
+        assert_eq!(map.source_id, format!("sacn:cid:{}", "11".repeat(16)));
+        assert_eq!((map.packets, map.changes), (3, 0));
+        assert_eq!(map.ranges.len(), 2);
+        assert_eq!((map.ranges[0].last_slot, map.ranges[0].priority), (2, 150));
+        assert_eq!((map.ranges[1].first_slot, map.ranges[1].priority), (3, 0));
+    }
+
+    #[test]
+    fn arttimecode_streams_are_tracked_as_artnet_traffic() {
+        let desk = [10, 0, 0, 1];
+        let nodes = [10, 0, 0, 255];
+        let events = vec![
+            udp_event(
+                0.0,
+                desk,
+                nodes,
+                6454,
+                &ArtTimeCodeBuilder::new(1, 0, 0, 0).build(),
+            ),
+            udp_event(
+                0.04,
+                desk,
+                nodes,
+                6454,
+                &ArtTimeCodeBuilder::new(1, 0, 0, 1).build(),
+            ),
+            udp_event(
+                0.08,
+                desk,
+                nodes,
+                6454,
+                &ArtTimeCodeBuilder::new(1, 5, 0, 0).build(),
+            ),
+        ];
+        let report = analyze(&AnalyzerConfig::default(), events);
+
+        assert!(find_violation(&report, "LS-ARTNET-OPCODE").is_none());
+        assert!(report.universes.is_empty());
+        assert_eq!(report.timecode.len(), 1);
+        let stream = &report.timecode[0];
+        assert_eq!(stream.source_ip, "10.0.0.1");
+        assert_eq!(stream.frame_rate, Some(25.0));
+        assert_eq!(stream.packets, 3);
+        assert_eq!(stream.first_value, "01:00:00:00");
+        assert_eq!(stream.last_value, "01:05:00:00");
+        assert_eq!(stream.discontinuities.len(), 1);
+        assert_eq!(stream.discontinuities[0].from, "01:00:00:01");
+        let traffic = report.capture_summary.unwrap().traffic.unwrap();
+        assert_eq!(traffic.artnet.packets, 3);
+    }
+
+    #[test]
+    fn psn_trackers_are_summarized_as_other_udp_traffic() {
+        let server = [10, 0, 0, 5];
+        let group = [236, 10, 10, 10];
+        let mut events = vec![udp_event(
+            0.0,
+            server,
+            group,
+            56565,
+            &PsnInfoBuilder::new("Stage tracking")
+                .tracker(1, "Lead")
+                .build(),
+        )];
+        for frame in 0..4u8 {
+            let x = f32::from(frame) * 0.5;
+            events.push(udp_event(
+                f64::from(frame) * 0.25,
+                server,
+                group,
+                56565,
+                &PsnDataBuilder::new(frame)
+                    .tracker(1, [x, 1.0, 0.0], Some([2.0, 0.0, 0.0]))
+                    .build(),
+            ));
+        }
+        let report = analyze(&AnalyzerConfig::default(), events);
+
+        assert_eq!(report.psn_trackers.len(), 1);
+        let tracker = &report.psn_trackers[0];
+        assert_eq!(tracker.source_ip, "10.0.0.5");
+        assert_eq!(tracker.name.as_deref(), Some("Lead"));
+        assert_eq!(tracker.updates, 4);
+        assert_eq!(tracker.update_rate_hz, Some(4.0));
+        assert_eq!(tracker.last_position, Some([1.5, 1.0, 0.0]));
+        assert_eq!(tracker.max_speed, Some(2.0));
+        assert!(tracker.dropouts.is_empty());
+        let capture = report.capture_summary.unwrap();
+        assert_eq!(capture.traffic.unwrap().other_udp.packets, 5);
+        assert_eq!(capture.skipped.unwrap().total, 0);
+    }
+
+    #[test]
+    fn osc_flows_and_address_patterns_are_reported() {
+        let desk = [10, 0, 0, 20];
+        let server = [10, 0, 0, 30];
+        let mut bundle = b"#bundle\0\0\0\0\0\0\0\0\x01".to_vec();
+        for message in [&b"/layer/1/opacity\0\0\0\0,f\0\0\0\0\0\0"[..], b"/cue/go\0"] {
+            bundle.extend_from_slice(&(message.len() as u32).to_be_bytes());
+            bundle.extend_from_slice(message);
+        }
+        let events = vec![
+            udp_event(0.0, desk, server, 8000, b"/cue/go\0,\0\0\0"),
+            udp_event(0.5, desk, server, 8000, &bundle),
+            // Text that merely starts with a slash is not OSC.
+            udp_event(1.0, desk, server, 9000, b"/not osc"),
+        ];
+        let report = analyze(&AnalyzerConfig::default(), events);
+
+        let addresses: Vec<(&str, u64)> = report
+            .osc_addresses
+            .iter()
+            .map(|address| (address.address.as_str(), address.messages))
+            .collect();
+        assert_eq!(addresses, [("/cue/go", 2), ("/layer/1/opacity", 1)]);
+        assert_eq!(report.osc_addresses[0].senders, ["10.0.0.20"]);
+        let flows: Vec<(&str, Protocol)> = report
+            .flows
+            .iter()
+            .map(|flow| (flow.dst.as_str(), flow.app_proto))
+            .collect();
+        assert_eq!(
+            flows,
+            [
+                ("10.0.0.30:8000", Protocol::Osc),
+                ("10.0.0.30:9000", Protocol::Udp)
+            ]
+        );
+    }
 
-        let sacn = compliance.get("sacn").expect("sacn compliance");
-        assert_eq!(sacn.violations.len(), 1);
-        assert_eq!(sacn.violations[0].count, 1);
+    #[test]
+    fn citp_peers_are_summarized_and_their_flows_marked() {
+        fn citp(layer: &[u8; 4], body: &[u8]) -> Vec<u8> {
+            let mut payload = b"CITP\x01\x00\x00\x00".to_vec();
+            payload.extend_from_slice(&(20 + body.len() as u32).to_le_bytes());
+            payload.extend_from_slice(&[1, 0, 0, 0]);
+            payload.extend_from_slice(layer);
+            payload.extend_from_slice(body);
+            payload
+        }
+        let server = [10, 0, 0, 40];
+        let group = [224, 0, 0, 180];
+        let location = citp(b"PINF", b"PLoc\xcb\x12MediaServer\0Server A\0Running\0");
+        // MSEX 1.0 stream frame of source 1: 160x90 JPEG, empty buffer.
+        let frame = citp(b"MSEX", b"\x01\x00StFr\x01\x00JPEG\xa0\x00\x5a\x00\x00\x00");
+        // Cut short of the size its header declares.
+        let mut truncated = citp(b"PINF", b"PLoc\xcb\x12MediaServer\0");
+        truncated.truncate(truncated.len() - 4);
+        let events = vec![
+            udp_event(0.0, server, group, 4809, &location),
+            udp_event(0.5, server, group, 4809, &frame),
+            udp_event(1.0, server, group, 4809, &frame),
+            udp_event(1.5, server, group, 4809, &truncated),
+        ];
+        let report = analyze(&AnalyzerConfig::default(), events);
+
+        assert_eq!(report.citp_peers.len(), 1);
+        let peer = &report.citp_peers[0];
+        assert_eq!(peer.source_ip, "10.0.0.40");
+        assert_eq!(peer.name.as_deref(), Some("Server A"));
+        assert_eq!(peer.listening_port, Some(4811));
+        assert_eq!(peer.packets, 3);
+        assert_eq!(peer.streams[0].format, "JPEG");
+        assert_eq!((peer.streams[0].width, peer.streams[0].height), (160, 90));
+        assert_eq!(peer.streams[0].fps, Some(2.0));
+        assert_eq!(report.flows[0].app_proto, Protocol::Citp);
+        let skipped = report.capture_summary.unwrap().skipped.unwrap();
+        assert_eq!(skipped.reasons[&SkipReason::MalformedCitp], 1);
     }
 
     #[test]
-    fn compliance_examples_are_deduplicated_and_capped() {
-        let mut compliance: HashMap<String, ComplianceSummary> = HashMap::new();
+    fn igmp_members_are_listed_and_universes_without_subscribers_flagged() {
+        fn igmp_event(ts: f64, src: [u8; 4], dst: [u8; 4], payload: &[u8]) -> PacketEvent {
+            let mut data = vec![7, 8, 9, 10, 11, 12, 1, 2, 3, 4, 5, 6, 0x08, 0x00];
+            Ipv4Header::new(payload.len() as u16, 1, IpNumber(2), src, dst)
+                .unwrap()
+                .write(&mut data)
+                .unwrap();
+            data.extend_from_slice(payload);
+            PacketEvent {
+                ts: Some(ts),
+                linktype: Linktype::ETHERNET,
+                data,
+                orig_len: None,
+            }
+        }
+        let console = [10, 0, 0, 1];
+        let node = [10, 0, 0, 20];
+        let universe_1 = [239, 255, 0, 1];
+        let universe_2 = [239, 255, 0, 2];
+        let sacn_only = vec![
+            udp_event(0.0, console, universe_1, 5568, &sacn_payload(1)),
+            udp_event(0.1, console, universe_2, 5568, &sacn_payload(2)),
+        ];
+        // Without any membership report, nothing can be concluded.
+        let report = analyze(&AnalyzerConfig::default(), sacn_only.clone());
+        assert!(report.igmp_groups.is_empty());
+        assert!(find_violation(&report, "LS-SACN-NO-SUBSCRIBER").is_none());
+
+        let mut events = vec![igmp_event(
+            0.0,
+            node,
+            universe_1,
+            &[0x16, 0, 0, 0, 239, 255, 0, 1],
+        )];
+        events.extend(sacn_only);
+        events.push(igmp_event(
+            0.5,
+            node,
+            [224, 0, 0, 2],
+            &[0x17, 0, 0, 0, 239, 255, 0, 1],
+        ));
+        let report = analyze(&AnalyzerConfig::default(), events);
+
+        assert_eq!(report.igmp_groups.len(), 1);
+        let group = &report.igmp_groups[0];
+        assert_eq!(group.universe, Some(1));
+        assert_eq!(group.members[0].receiver, "10.0.0.20");
+        assert_eq!(group.members[0].last_left, Some(0.5));
+        assert!(!group.members[0].member_at_end);
+        let violation = find_violation(&report, "LS-SACN-NO-SUBSCRIBER").expect("no subscriber");
+        assert_eq!(violation.count, 1);
+        assert!(violation.examples[0].detail.contains("universe=2"));
+    }
+
+    #[test]
+    fn ptp_grandmasters_and_master_changes_are_reported() {
+        fn announce(grandmaster: u8, priority1: u8) -> Vec<u8> {
+            let mut payload = vec![0u8; 64];
+            payload[0] = 0x0b;
+            payload[1] = 0x02;
+            payload[2..4].copy_from_slice(&64u16.to_be_bytes());
+            payload[20..28].copy_from_slice(&[grandmaster; 8]);
+            payload[28..30].copy_from_slice(&1u16.to_be_bytes());
+            payload[47] = priority1;
+            payload[48] = 248;
+            payload[52] = 128;
+            payload[53..61].copy_from_slice(&[grandmaster; 8]);
+            payload
+        }
+        let first = [10, 0, 0, 1];
+        let second = [10, 0, 0, 2];
+        let group = [224, 0, 1, 129];
+        let mut sync = announce(1, 128);
+        sync[0] = 0x00;
+        sync.truncate(44);
+        sync[2..4].copy_from_slice(&44u16.to_be_bytes());
+        let mut v1 = sync.clone();
+        v1[1] = 0x01;
+        let events = vec![
+            udp_event(0.0, first, group, 320, &announce(1, 128)),
+            udp_event(0.1, first, group, 319, &sync),
+            udp_event(1.0, first, group, 320, &announce(1, 128)),
+            udp_event(1.5, second, group, 320, &announce(2, 100)),
+            udp_event(2.0, first, group, 319, &v1),
+        ];
+        let report = analyze(&AnalyzerConfig::default(), events);
 
-        record_violation(
-            &mut compliance,
-            "udp",
-            "LS-UDP-SLICE",
-            "error",
-            "Invalid UDP slice; packet ignored",
-            "slice-c".to_string(),
+        assert_eq!(report.ptp_domains.len(), 1);
+        let domain = &report.ptp_domains[0];
+        assert_eq!(domain.messages, 4);
+        assert_eq!(domain.grandmasters.len(), 2);
+        assert_eq!(domain.grandmasters[0].announce_interval_s, Some(1.0));
+        assert_eq!(domain.master_changes.len(), 1);
+        assert_eq!(domain.master_changes[0].timestamp, Some(1.5));
+        assert_eq!(
+            domain.master_changes[0].grandmaster,
+            "02:02:02:02:02:02:02:02"
         );
-        record_violation(
-            &mut compliance,
-            "udp",
-            "LS-UDP-SLICE",
-            "error",
-            "Invalid UDP slice; packet ignored",
-            "slice-a".to_string(),
+        assert!(
+            report
+                .flows
+                .iter()
+                .all(|flow| flow.app_proto == Protocol::Ptp)
         );
-        record_violation(
-            &mut compliance,
-            "udp",
-            "LS-UDP-SLICE",
-            "error",
-            "Invalid UDP slice; packet ignored",
-            "slice-b".to_string(),
+        let skipped = report.capture_summary.unwrap().skipped.unwrap();
+        assert_eq!(skipped.reasons[&SkipReason::MalformedPtp], 1);
+    }
+
+    #[test]
+    fn arp_conflicts_and_dhcp_address_changes_are_reported() {
+        fn arp_event(ts: f64, mac: u8, ip: [u8; 4]) -> PacketEvent {
+            let mut data = vec![0xff; 6];
+            data.extend_from_slice(&[2, 0, 0, 0, 0, mac, 0x08, 0x06]);
+            data.extend_from_slice(&[0, 1, 8, 0, 6, 4, 0, 1, 2, 0, 0, 0, 0, mac]);
+            data.extend_from_slice(&ip);
+            data.extend_from_slice(&[0; 6]);
+            data.extend_from_slice(&ip);
+            PacketEvent {
+                ts: Some(ts),
+                linktype: Linktype::ETHERNET,
+                data,
+                orig_len: None,
+            }
+        }
+        fn dhcp_ack(client: u8, ip: [u8; 4]) -> Vec<u8> {
+            let mut payload = vec![0u8; 240];
+            payload[0] = 2;
+            payload[16..20].copy_from_slice(&ip);
+            payload[28..34].copy_from_slice(&[2, 0, 0, 0, 0, client]);
+            payload[236..240].copy_from_slice(&[0x63, 0x82, 0x53, 0x63]);
+            payload.extend_from_slice(&[53, 1, 5, 255]);
+            payload
+        }
+        let server = [10, 0, 0, 254];
+        let broadcast = [255, 255, 255, 255];
+        let events = vec![
+            udp_event(0.0, server, broadcast, 68, &dhcp_ack(1, [10, 0, 0, 10])),
+            arp_event(0.5, 1, [10, 0, 0, 10]),
+            arp_event(1.0, 2, [10, 0, 0, 10]),
+            udp_event(2.0, server, broadcast, 68, &dhcp_ack(1, [10, 0, 0, 11])),
+            udp_event(3.0, server, broadcast, 68, &[2, 1, 6]),
+        ];
+        let report = analyze(&AnalyzerConfig::default(), events);
+
+        let kinds: Vec<_> = report
+            .network_events
+            .iter()
+            .map(|event| (event.kind, event.timestamp, event.ip.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (NetworkEventKind::IpConflict, Some(1.0), "10.0.0.10"),
+                (NetworkEventKind::DhcpAddressChange, Some(2.0), "10.0.0.11"),
+            ]
         );
-        record_violation(
-            &mut compliance,
-            "udp",
-            "LS-UDP-SLICE",
-            "error",
-            "Invalid UDP slice; packet ignored",
-            "slice-a".to_string(),
+        assert_eq!(
+            report.network_events[1].previous_ip.as_deref(),
+            Some("10.0.0.10")
         );
-        record_violation(
-            &mut compliance,
-            "udp",
-            "LS-UDP-SLICE",
-            "error",
-            "Invalid UDP slice; packet ignored",
-            "slice-d".to_string(),
+        assert!(
+            report
+                .flows
+                .iter()
+                .all(|flow| flow.app_proto == Protocol::Dhcp)
         );
+        let skipped = report.capture_summary.unwrap().skipped.unwrap();
+        assert_eq!(skipped.reasons[&SkipReason::MalformedDhcp], 1);
+    }
 
-        let entries = finalize_compliance(compliance);
-        let udp = &entries[0];
-        let violation = &udp.violations[0];
-        assert_eq!(violation.count, 5);
-        assert_eq!(violation.examples.len(), 3);
+    #[test]
+    fn arttrigger_packets_are_listed_in_capture_order() {
+        let desk = [10, 0, 0, 1];
+        let nodes = [10, 0, 0, 255];
+        let events = vec![
+            udp_event(
+                1.0,
+                desk,
+                nodes,
+                6454,
+                &ArtTriggerBuilder::new(1, 5).build(),
+            ),
+            udp_event(
+                2.0,
+                desk,
+                [10, 0, 0, 7],
+                6454,
+                &ArtTriggerBuilder::new(7, 1).oem(0x007a).build(),
+            ),
+        ];
+        let report = analyze(&AnalyzerConfig::default(), events);
+
+        assert!(find_violation(&report, "LS-ARTNET-OPCODE").is_none());
+        assert_eq!(
+            report.triggers,
+            vec![
+                TriggerEvent {
+                    timestamp: Some(1.0),
+                    source_ip: "10.0.0.1".to_string(),
+                    destination_ip: "10.0.0.255".to_string(),
+                    oem: 0xffff,
+                    key: 1,
+                    key_name: Some("macro".to_string()),
+                    sub_key: 5,
+                },
+                TriggerEvent {
+                    timestamp: Some(2.0),
+                    source_ip: "10.0.0.1".to_string(),
+                    destination_ip: "10.0.0.7".to_string(),
+                    oem: 0x007a,
+                    key: 7,
+                    key_name: None,
+                    sub_key: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn artaddress_is_a_warning_with_source_and_target() {
+        // Programs Net 1.
+        let address = ArtAddressBuilder::new().net_switch(0x81).build();
+        let events = vec![
+            udp_event(0.0, [10, 0, 0, 1], [10, 0, 0, 2], 6454, &artdmx_payload(1)),
+            udp_event(1.5, [10, 0, 0, 99], [10, 0, 0, 2], 6454, &address),
+        ];
+        let report = analyze(&AnalyzerConfig::default(), events);
+
+        assert!(find_violation(&report, "LS-ARTNET-OPCODE").is_none());
+        let violation = find_violation(&report, "LS-ARTNET-ADDRESS").expect("artaddress violation");
+        assert_eq!(violation.severity, Severity::Warning);
+        assert_eq!(violation.count, 1);
+        let example = &violation.examples[0];
+        assert_eq!(example.source_ip.as_deref(), Some("10.0.0.99"));
+        assert_eq!(example.detail, "target=10.0.0.2, bind_index=0, net=1");
+        let traffic = report.capture_summary.unwrap().traffic.unwrap();
+        assert_eq!(traffic.artnet.packets, 2);
+    }
+
+    #[test]
+    fn old_protocol_versions_are_reported_once_per_source_and_version() {
+        let old = ArtDmxBuilder::new(1).protocol_version(10).build();
+        let events = vec![
+            udp_event(0.0, [10, 0, 0, 1], [10, 0, 0, 255], 6454, &old),
+            udp_event(0.1, [10, 0, 0, 1], [10, 0, 0, 255], 6454, &old),
+            udp_event(
+                0.2,
+                [10, 0, 0, 2],
+                [10, 0, 0, 255],
+                6454,
+                &artdmx_payload(1),
+            ),
+            udp_event(
+                0.3,
+                [10, 0, 0, 3],
+                [10, 0, 0, 255],
+                6454,
+                &ArtSyncBuilder::new().protocol_version(13).build(),
+            ),
+        ];
+        let report = analyze(&AnalyzerConfig::default(), events);
+
+        let violation = find_violation(&report, "LS-ARTNET-PROTVER").expect("protver violation");
+        assert_eq!(violation.severity, Severity::Warning);
+        assert_eq!(violation.count, 2);
         assert_eq!(
-            violation.examples,
+            violation.sources.keys().collect::<Vec<_>>(),
+            vec!["10.0.0.1", "10.0.0.3"]
+        );
+        let details: Vec<&str> = violation
+            .examples
+            .iter()
+            .map(|example| example.detail.as_str())
+            .collect();
+        assert_eq!(
+            details,
             vec![
-                "source unknown @ unknown; slice-a".to_string(),
-                "source unknown @ unknown; slice-b".to_string(),
-                "source unknown @ unknown; slice-c".to_string()
+                "packet=ArtDmx, protocol_version=10, min=14",
+                "packet=ArtSync, protocol_version=13, min=14",
             ]
         );
     }
 
     #[test]
-    fn compliance_entries_are_sorted_by_protocol_and_id() {
-        let mut compliance: HashMap<String, ComplianceSummary> = HashMap::new();
+    fn rdm_responders_and_transactions_are_reported_per_port() {
+        let console = [10, 0, 0, 1];
+        let node = [10, 0, 0, 20];
+        let controller = RdmUid {
+            manufacturer: 0x4c53,
+            device: 1,
+        };
+        let fixture = RdmUid {
+            manufacturer: 0x7a70,
+            device: 2,
+        };
+        let events = vec![
+            udp_event(
+                0.0,
+                console,
+                [10, 255, 255, 255],
+                6454,
+                &ArtTodRequestBuilder::new(0, vec![3, 4]).build(),
+            ),
+            udp_event(
+                0.1,
+                node,
+                console,
+                6454,
+                &ArtTodDataBuilder::new(3, vec![fixture]).port(2).build(),
+            ),
+            udp_event(
+                1.0,
+                console,
+                node,
+                6454,
+                &ArtRdmBuilder::new(3, controller, fixture, 0x20)
+                    .transaction(9)
+                    .build(),
+            ),
+            udp_event(
+                1.012,
+                node,
+                console,
+                6454,
+                &ArtRdmBuilder::new(3, fixture, controller, 0x21)
+                    .transaction(9)
+                    .response_type(2)
+                    .build(),
+            ),
+            // Filtered out with the universe selection.
+            udp_event(
+                2.0,
+                console,
+                node,
+                6454,
+                &ArtRdmBuilder::new(4, controller, fixture, 0x20).build(),
+            ),
+        ];
+        let config = AnalyzerConfig {
+            universes: Some([3].into()),
+            ..AnalyzerConfig::default()
+        };
+        let report = analyze(&config, events);
 
-        record_violation(
-            &mut compliance,
-            "sacn",
-            "LS-SACN-START-CODE",
-            "error",
-            "Invalid sACN start code; packet ignored",
-            "value=1".to_string(),
+        assert!(find_violation(&report, "LS-ARTNET-OPCODE").is_none());
+        assert_eq!(
+            report.rdm_responders,
+            vec![RdmPortInventory {
+                node_ip: "10.0.0.20".to_string(),
+                universe: 3,
+                port_address: PortAddress::from_masked(3),
+                port: Some(2),
+                bind_index: Some(1),
+                tod_uid_total: Some(1),
+                tod_packets: 1,
+                responders: vec!["7a70:00000002".to_string()],
+                unlisted: Vec::new(),
+            }]
         );
-        record_violation(
-            &mut compliance,
-            "artnet",
-            "LS-ARTNET-UNIVERSE-ID",
-            "error",
-            "Invalid Art-Net universe id; packet ignored",
-            "value=32768".to_string(),
+        assert_eq!(report.rdm_transactions.len(), 1);
+        let transactions = &report.rdm_transactions[0];
+        assert_eq!(transactions.universe, 3);
+        assert_eq!(transactions.tod_requests, 1);
+        assert_eq!((transactions.requests, transactions.responses), (1, 1));
+        assert_eq!((transactions.get, transactions.nacks), (1, 1));
+        assert_eq!(transactions.unanswered, 0);
+        assert_eq!(transactions.response_time.as_ref().unwrap().max_ms, 12.0);
+        let summary = report.capture_summary.unwrap();
+        assert_eq!(
+            summary.skipped.unwrap().reasons[&SkipReason::UniverseFiltered],
+            1
         );
-        record_violation(
-            &mut compliance,
-            "artnet",
-            "LS-ARTNET-LENGTH",
-            "error",
-            "Invalid ArtDMX length; packet ignored",
-            "length=0".to_string(),
+        assert_eq!(summary.traffic.unwrap().artnet.packets, 5);
+    }
+
+    #[test]
+    fn artnzs_packets_are_counted_per_universe_apart_from_frames() {
+        let desk = [10, 0, 0, 1];
+        let nodes = [10, 0, 0, 255];
+        let rdm = ArtNzsBuilder::new(1).start_code(0xcc).build();
+        let text = ArtNzsBuilder::new(2).data(*b"Cue 5").build();
+        let events = vec![
+            udp_event(0.0, desk, nodes, 6454, &artdmx_payload(1)),
+            udp_event(0.1, desk, nodes, 6454, &rdm),
+            udp_event(0.2, desk, nodes, 6454, &rdm),
+            udp_event(0.3, desk, nodes, 6454, &text),
+        ];
+        let report = analyze(&AnalyzerConfig::default(), events);
+
+        assert!(find_violation(&report, "LS-ARTNET-OPCODE").is_none());
+        let universes: Vec<(u16, u64, Option<&NonZeroStartCodes>)> = report
+            .universes
+            .iter()
+            .map(|universe| {
+                (
+                    universe.universe,
+                    universe.frames_count,
+                    universe.non_zero_start_codes.as_ref(),
+                )
+            })
+            .collect();
+        let rdm_codes = NonZeroStartCodes {
+            packets: 2,
+            start_codes: vec![0xcc],
+        };
+        let text_codes = NonZeroStartCodes {
+            packets: 1,
+            start_codes: vec![0x17],
+        };
+        assert_eq!(
+            universes,
+            vec![(1, 1, Some(&rdm_codes)), (2, 0, Some(&text_codes))]
         );
+        let traffic = report.capture_summary.unwrap().traffic.unwrap();
+        assert_eq!(traffic.artnet.packets, 4);
+    }
 
-        let entries = finalize_compliance(compliance);
-        assert_eq!(entries.len(), 2);
-        assert_eq!(entries[0].protocol, "artnet");
-        assert_eq!(entries[1].protocol, "sacn");
-        assert_eq!(entries[0].violations.len(), 2);
-        assert_eq!(entries[0].violations[0].id, "LS-ARTNET-LENGTH");
-        assert_eq!(entries[0].violations[1].id, "LS-ARTNET-UNIVERSE-ID");
+    #[test]
+    fn filtered_and_disabled_packets_are_counted_as_skipped() {
+        let config = AnalyzerConfig {
+            sacn_enabled: false,
+            universes: Some([1].into()),
+            ..AnalyzerConfig::default()
+        };
+        let events = vec![
+            udp_event(0.0, [10, 0, 0, 1], [10, 0, 0, 2], 6454, &artdmx_payload(1)),
+            udp_event(0.1, [10, 0, 0, 1], [10, 0, 0, 2], 6454, &artdmx_payload(2)),
+            udp_event(0.2, [10, 0, 0, 1], [239, 255, 0, 1], 5568, &sacn_payload(1)),
+            PacketEvent {
+                ts: Some(0.3),
+                linktype: Linktype::FDDI,
+                data: vec![0u8; 4],
+                orig_len: None,
+            },
+        ];
+        let report = analyze(&config, events);
+
+        let skipped = report
+            .capture_summary
+            .and_then(|summary| summary.skipped)
+            .expect("skipped packets");
+        assert_eq!(skipped.total, 3);
+        assert_eq!(skipped.reasons[&SkipReason::UniverseFiltered], 1);
+        assert_eq!(skipped.reasons[&SkipReason::ProtocolDisabled], 1);
+        assert_eq!(skipped.reasons[&SkipReason::UnsupportedLinktype], 1);
+    }
+
+    #[test]
+    fn timestamps_going_backwards_are_reported_as_warnings() {
+        let events = [10.0, 11.0, 2.0, 3.0, 12.0]
+            .into_iter()
+            .map(|ts| udp_event(ts, [10, 0, 0, 1], [10, 0, 0, 2], 9000, &[0; 8]))
+            .collect();
+        let report = analyze(&AnalyzerConfig::default(), events);
+
+        assert_eq!(report.warnings.len(), 1);
+        let warning = &report.warnings[0];
+        assert_eq!(warning.kind, WarningKind::TimestampBackwards);
+        assert_eq!((warning.first_packet, warning.last_packet), (3, 4));
+        assert_eq!(warning.packets, 2);
+        assert_eq!(warning.time_start.as_deref(), Some("1970-01-01T00:00:02Z"));
+    }
+
+    #[test]
+    fn series_are_only_embedded_on_request() {
+        let events = || {
+            [100.2, 100.7, 102.1]
+                .into_iter()
+                .map(|ts| udp_event(ts, [10, 0, 0, 1], [10, 0, 0, 2], 6454, &artdmx_payload(1)))
+                .collect()
+        };
+        assert!(
+            analyze(&AnalyzerConfig::default(), events())
+                .series
+                .is_none()
+        );
+
+        let config = AnalyzerConfig {
+            series: true,
+            ..AnalyzerConfig::default()
+        };
+        let series = analyze(&config, events()).series.unwrap();
+        assert_eq!(series.start, 100.0);
+        assert_eq!(series.universes.len(), 1);
+        assert_eq!(series.universes[0].fps, vec![2, 0, 1]);
+        assert!(series.universes[0].loss.is_none());
+        assert_eq!(series.flows[0].pps, vec![2, 0, 1]);
+    }
+
+    #[test]
+    fn sacn_sources_conflict_only_while_tied_at_the_same_priority() {
+        let group = [239, 255, 0, 1];
+        let mut events = Vec::new();
+        for step in 0u8..40 {
+            let ts = f64::from(step) * 0.1;
+            let main = SacnDataBuilder::new(1).sequence(step).slots(vec![255; 4]);
+            events.push(udp_event(ts, [10, 0, 0, 1], group, 5568, &main.build()));
+            if step >= 10 {
+                // The backup outranks the main console, then drops to its priority.
+                let priority = if step < 25 { 150 } else { 100 };
+                let backup = SacnDataBuilder::new(1)
+                    .cid([0x22; 16])
+                    .priority(priority)
+                    .sequence(step)
+                    .slots(vec![0; 4]);
+                events.push(udp_event(ts, [10, 0, 0, 2], group, 5568, &backup.build()));
+            }
+        }
+        let report = analyze(&AnalyzerConfig::default(), events);
+
+        let takeover = &report.takeovers[0];
+        assert_eq!(takeover.cause, TakeoverCause::OutPrioritized);
+        assert_eq!(takeover.timestamp, 1.0);
+        assert_eq!(report.conflicts.len(), 1);
+        let conflict = &report.conflicts[0];
+        assert_eq!(conflict.first_seen, Some(2.5));
+        assert!((conflict.overlap_duration_s - 1.4).abs() < 1e-6);
+
+        // Without the tie, the overlap is a takeover only.
+        let outranked: Vec<_> = (0u8..40)
+            .flat_map(|step| {
+                let ts = f64::from(step) * 0.1;
+                let main = SacnDataBuilder::new(1).sequence(step).build();
+                let backup = SacnDataBuilder::new(1)
+                    .cid([0x22; 16])
+                    .priority(150)
+                    .sequence(step)
+                    .build();
+                [
+                    udp_event(ts, [10, 0, 0, 1], group, 5568, &main),
+                    udp_event(ts, [10, 0, 0, 2], group, 5568, &backup),
+                ]
+            })
+            .collect();
+        let report = analyze(&AnalyzerConfig::default(), outranked);
+        assert!(report.conflicts.is_empty());
+        assert_eq!(report.takeovers.len(), 1);
+    }
+
+    #[test]
+    fn stream_termination_closes_the_source_interval() {
+        let group = [239, 255, 0, 1];
+        let mut events = Vec::new();
+        let mut sequence = 0u8;
+        let mut main = |ts: f64, options: u8| {
+            sequence = sequence.wrapping_add(1);
+            let payload = SacnDataBuilder::new(1)
+                .sequence(sequence)
+                .options(options)
+                .build();
+            udp_event(ts, [10, 0, 0, 1], group, 5568, &payload)
+        };
+        for step in 0..20 {
+            events.push(main(f64::from(step) * 0.1, 0));
+        }
+        // E1.31 sends three terminated packets.
+        for step in 0..3 {
+            events.push(main(2.0 + f64::from(step) * 0.01, 0x40));
+        }
+        for step in 80..100 {
+            events.push(main(f64::from(step) * 0.1, 0));
+        }
+        for step in 30..70 {
+            let backup = SacnDataBuilder::new(1)
+                .cid([0x22; 16])
+                .sequence(step as u8)
+                .build();
+            events.push(udp_event(
+                f64::from(step) * 0.1,
+                [10, 0, 0, 2],
+                group,
+                5568,
+                &backup,
+            ));
+        }
+        let report = analyze(&AnalyzerConfig::default(), events);
+
+        assert!(report.conflicts.is_empty());
+        let universe = &report.universes[0];
+        assert_eq!(universe.frames_count, 80);
+        assert_eq!(universe.loss_packets, Some(0));
+        let main = universe
+            .sources
+            .iter()
+            .find(|source| source.source_ip == "10.0.0.1")
+            .unwrap();
+        assert_eq!(main.terminated_at, vec![2.0]);
+    }
+
+    #[test]
+    fn preview_data_is_counted_apart_from_live_frames() {
+        let group = [239, 255, 0, 1];
+        let mut events = Vec::new();
+        for step in 0u8..40 {
+            let ts = f64::from(step) * 0.05;
+            // The console interleaves its preview output with the live one.
+            let options = if step % 2 == 1 { 0x80 } else { 0 };
+            let console = SacnDataBuilder::new(1)
+                .sequence(step)
+                .options(options)
+                .build();
+            events.push(udp_event(ts, [10, 0, 0, 1], group, 5568, &console));
+            let visualizer = SacnDataBuilder::new(1)
+                .cid([0x22; 16])
+                .sequence(step)
+                .options(0x80)
+                .build();
+            events.push(udp_event(ts, [10, 0, 0, 2], group, 5568, &visualizer));
+        }
+        let report = analyze(&AnalyzerConfig::default(), events);
+
+        assert!(report.conflicts.is_empty());
+        let universe = &report.universes[0];
+        assert_eq!(universe.frames_count, 20);
+        assert_eq!(universe.preview_frames, Some(60));
+        assert_eq!(universe.loss_packets, Some(0));
+        assert_eq!(universe.sources.len(), 1);
+        assert_eq!(
+            report
+                .capture_summary
+                .unwrap()
+                .traffic
+                .unwrap()
+                .sacn
+                .packets,
+            80
+        );
+    }
+
+    #[test]
+    fn sources_stopping_without_termination_are_dropouts() {
+        let group = [239, 255, 0, 1];
+        let frame = |ts: f64, cid: u8, options: u8| {
+            let payload = SacnDataBuilder::new(1)
+                .cid([cid; 16])
+                .sequence((ts * 10.0) as u8)
+                .options(options)
+                .build();
+            udp_event(ts, [10, 0, 0, cid], group, 5568, &payload)
+        };
+        let mut events = Vec::new();
+        for step in (0..20).chain(60..70) {
+            events.push(frame(f64::from(step) * 0.1, 0x11, 0));
+        }
+        for step in 0..20 {
+            events.push(frame(f64::from(step) * 0.1, 0x22, 0));
+        }
+        events.push(frame(2.0, 0x22, 0x40));
+        for step in 0..10 {
+            events.push(frame(f64::from(step) * 0.1, 0x33, 0));
+        }
+        events.sort_by(|a, b| a.ts.unwrap().total_cmp(&b.ts.unwrap()));
+        let report = analyze(&AnalyzerConfig::default(), events);
+
+        let dropouts: Vec<_> = report
+            .events
+            .iter()
+            .map(|event| (event.source_id.as_str(), event.duration_s, event.resumed))
+            .collect();
+        let resumed = format!("sacn:cid:{}", "11".repeat(16));
+        let silent = format!("sacn:cid:{}", "33".repeat(16));
+        assert_eq!(
+            dropouts,
+            vec![(silent.as_str(), 6.0, false), (resumed.as_str(), 4.1, true)]
+        );
+        assert_eq!(report.events[0].kind, EventKind::SourceDropout);
+        assert_eq!(report.events[0].timestamp, 0.9);
+    }
+
+    #[test]
+    fn fixture_map_labels_conflicting_channels() {
+        let payload = |value: u8| ArtDmxBuilder::new(1).slots([value, 0]).build();
+        let mut events = Vec::new();
+        for step in 0..=20 {
+            let ts = f64::from(step) * 0.1;
+            events.push(udp_event(
+                ts,
+                [10, 0, 0, 1],
+                [10, 0, 0, 9],
+                6454,
+                &payload(255),
+            ));
+            events.push(udp_event(
+                ts,
+                [10, 0, 0, 2],
+                [10, 0, 0, 9],
+                6454,
+                &payload(0),
+            ));
+        }
+        let fixtures = "personality,Par,1,Dimmer\npatch,Par 7,Par,1,1"
+            .parse::<FixtureMap>()
+            .unwrap();
+        let config = AnalyzerConfig {
+            fixtures: Some(fixtures),
+            ..AnalyzerConfig::default()
+        };
+        let report = analyze(&config, events);
+
+        let conflict = &report.conflicts[0];
+        assert_eq!(conflict.affected_channels, vec![1]);
+        assert_eq!(conflict.channel_labels.len(), 1);
+        assert_eq!(conflict.channel_labels[0].to_string(), "Par 7 – Dimmer");
+    }
+
+    #[test]
+    fn fixture_map_pan_axes_get_movement_smoothness() {
+        let events = (0u16..40)
+            .map(|step| {
+                let payload = ArtDmxBuilder::new(1)
+                    .slots((step * 300).to_be_bytes())
+                    .build();
+                udp_event(
+                    f64::from(step) * 0.025,
+                    [10, 0, 0, 1],
+                    [10, 0, 0, 9],
+                    6454,
+                    &payload,
+                )
+            })
+            .collect();
+        let fixtures = "personality,Spot,1,Pan\npersonality,Spot,2,Pan Fine\npatch,Spot 1,Spot,1,1"
+            .parse::<FixtureMap>()
+            .unwrap();
+        let config = AnalyzerConfig {
+            fixtures: Some(fixtures),
+            ..AnalyzerConfig::default()
+        };
+        let report = analyze(&config, events);
+
+        assert_eq!(report.movement.len(), 1);
+        let pan = &report.movement[0];
+        assert_eq!(pan.fixture, "Spot 1");
+        assert_eq!((pan.coarse, pan.fine), (1, Some(2)));
+        assert_eq!(pan.moving_frames, 39);
+        assert_eq!(pan.stalls, 0);
+        assert!(
+            analyze(&AnalyzerConfig::default(), Vec::new())
+                .movement
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn rig_patch_labels_conflicts_and_violations() {
+        let payload = |value: u8| ArtDmxBuilder::new(1).slots([0, value]).build();
+        let mut events = Vec::new();
+        for step in 0..=20 {
+            let ts = f64::from(step) * 0.1;
+            events.push(udp_event(
+                ts,
+                [10, 0, 0, 1],
+                [239, 255, 0, 1],
+                6454,
+                &payload(255),
+            ));
+            events.push(udp_event(
+                ts,
+                [10, 0, 0, 2],
+                [10, 0, 0, 9],
+                6454,
+                &payload(0),
+            ));
+        }
+        let patch = "1,1,1,House lights\n1,2,24,FOH truss,FOH,Wash"
+            .parse::<RigPatch>()
+            .unwrap();
+        let config = AnalyzerConfig {
+            patch: Some(patch),
+            ..AnalyzerConfig::default()
+        };
+        let report = analyze(&config, events);
+
+        assert_eq!(report.patch.len(), 2);
+        assert_eq!(report.conflicts[0].rig_labels, vec!["FOH truss"]);
+        let violation =
+            find_violation(&report, "LS-ARTNET-MULTICAST").expect("multicast violation");
+        assert_eq!(
+            violation.examples[0].detail,
+            "universe=1, rig=House lights + FOH truss, group=239.255.0.1"
+        );
     }
 }
@@ -0,0 +1,167 @@
+//! Pan/tilt movement smoothness.
+//!
+//! Moving heads interpolate between the positions they receive, so a
+//! missing frame mid-movement shows as a stall followed by a jump. For each
+//! pan/tilt axis named by the fixture map, the position steps between
+//! consecutive frames are summarized as percentiles of the full travel, and
+//! frame gaps after which the position jumped on are counted as stalls.
+//! This puts a visible cost on the loss and jitter figures of the universe.
+
+use super::dmx::DmxFrame;
+use super::{AVERAGE_SCALE, round_to};
+use crate::MovementSmoothness;
+use crate::fixtures::AxisSlots;
+
+/// Fewest moving frames before an axis is reported; a fixture nudged once
+/// says nothing about smoothness.
+const MIN_MOVING_FRAMES: usize = 3;
+/// Frame interval, as a multiple of the source's median interval, from
+/// which a gap during a movement counts as a stall.
+const STALL_GAP_FACTOR: f64 = 3.0;
+/// Full travel of a 16-bit position.
+const FULL_TRAVEL: f64 = 65_535.0;
+
+/// Smoothness of each axis of `axes` in the frames of one source; axes that
+/// did not move are left out. Frames without timestamps are ignored.
+pub(crate) fn movement_smoothness(
+    frames: &[DmxFrame],
+    axes: &[&AxisSlots],
+) -> Vec<MovementSmoothness> {
+    let timed: Vec<(f64, &[u8; 512])> = frames
+        .iter()
+        .filter_map(|frame| Some((frame.timestamp?, &frame.slots)))
+        .collect();
+    let (Some(first), Some(median_interval)) = (frames.first(), median_interval(&timed)) else {
+        return Vec::new();
+    };
+
+    axes.iter()
+        .filter_map(|axis| {
+            let position = |slots: &[u8; 512]| {
+                let coarse = u32::from(slots[usize::from(axis.coarse) - 1]) << 8;
+                let fine = axis
+                    .fine
+                    .map_or(0, |fine| u32::from(slots[usize::from(fine) - 1]));
+                coarse | fine
+            };
+            let mut steps = Vec::new();
+            let mut stalls = 0u64;
+            let mut max_stall: Option<f64> = None;
+            let mut was_moving = false;
+            for pair in timed.windows(2) {
+                let ((before_ts, before), (after_ts, after)) = (pair[0], pair[1]);
+                let step = position(before).abs_diff(position(after));
+                let gap = after_ts - before_ts;
+                if step > 0 && was_moving && gap > STALL_GAP_FACTOR * median_interval {
+                    stalls += 1;
+                    max_stall = Some(max_stall.map_or(gap, |longest| longest.max(gap)));
+                }
+                if step > 0 {
+                    steps.push(step);
+                }
+                was_moving = step > 0;
+            }
+            if steps.len() < MIN_MOVING_FRAMES {
+                return None;
+            }
+            steps.sort_unstable();
+            let percent =
+                |step: u32| round_to(f64::from(step) * 100.0 / FULL_TRAVEL, AVERAGE_SCALE);
+            Some(MovementSmoothness {
+                universe: first.universe,
+                proto: first.protocol,
+                source_id: first.source_id.clone(),
+                fixture: axis.fixture.clone(),
+                axis: axis.axis,
+                coarse: axis.coarse,
+                fine: axis.fine,
+                moving_frames: steps.len() as u64,
+                step_p50_pct: percent(nearest_rank(&steps, 50)),
+                step_p95_pct: percent(nearest_rank(&steps, 95)),
+                step_max_pct: percent(steps[steps.len() - 1]),
+                stalls,
+                max_stall_ms: max_stall.map(|gap| round_to(gap * 1000.0, AVERAGE_SCALE)),
+            })
+        })
+        .collect()
+}
+
+fn median_interval(timed: &[(f64, &[u8; 512])]) -> Option<f64> {
+    let mut intervals: Vec<f64> = timed.windows(2).map(|pair| pair[1].0 - pair[0].0).collect();
+    if intervals.is_empty() {
+        return None;
+    }
+    intervals.sort_by(f64::total_cmp);
+    Some(intervals[intervals.len() / 2])
+}
+
+/// Nearest-rank percentile of sorted, non-empty steps.
+fn nearest_rank(sorted: &[u32], percentile: usize) -> u32 {
+    let rank = (sorted.len() * percentile).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::movement_smoothness;
+    use crate::analysis::dmx::DmxFrame;
+    use crate::fixtures::AxisSlots;
+    use crate::{MovementAxis, Protocol};
+
+    fn pan() -> AxisSlots {
+        AxisSlots {
+            universe: 1,
+            fixture: "Spot 1".to_string(),
+            axis: MovementAxis::Pan,
+            coarse: 1,
+            fine: Some(2),
+        }
+    }
+
+    /// 40 fps frames with a 16-bit pan sweeping by `step` per frame.
+    fn sweep(frames: u16, step: u16) -> Vec<DmxFrame> {
+        (0..frames)
+            .map(|index| {
+                let mut slots = [0u8; 512];
+                slots[..2].copy_from_slice(&(index * step).to_be_bytes());
+                DmxFrame {
+                    universe: 1,
+                    timestamp: Some(f64::from(index) * 0.025),
+                    source_id: "artnet:10.0.0.1:6454".to_string(),
+                    protocol: Protocol::ArtNet,
+                    slots,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn steady_sweep_has_even_steps_and_no_stalls() {
+        let axis = pan();
+        let smoothness = movement_smoothness(&sweep(200, 100), &[&axis]);
+        assert_eq!(smoothness.len(), 1);
+        let pan = &smoothness[0];
+        assert_eq!(pan.moving_frames, 199);
+        assert_eq!(pan.step_p50_pct, 0.153);
+        assert_eq!(pan.step_max_pct, 0.153);
+        assert_eq!(pan.stalls, 0);
+        assert_eq!(pan.max_stall_ms, None);
+    }
+
+    #[test]
+    fn lost_frames_mid_sweep_are_stalls() {
+        let axis = pan();
+        let mut frames = sweep(200, 100);
+        frames.drain(50..58);
+        let pan = &movement_smoothness(&frames, &[&axis])[0];
+        assert_eq!(pan.stalls, 1);
+        assert_eq!(pan.max_stall_ms, Some(225.0));
+        assert_eq!(pan.step_max_pct, 1.373);
+    }
+
+    #[test]
+    fn static_axis_is_not_reported() {
+        let axis = pan();
+        assert!(movement_smoothness(&sweep(200, 0), &[&axis]).is_empty());
+    }
+}
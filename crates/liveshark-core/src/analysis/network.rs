@@ -0,0 +1,203 @@
+//! Network-health events from ARP and DHCP.
+//!
+//! Consoles and nodes that "drop out" are often fighting over an IPv4
+//! address: two hosts answering ARP for the same address steal each other's
+//! traffic, and a DHCP server handing a device a new address breaks every
+//! unicast stream patched to the old one. The tracker remembers which
+//! hardware address last claimed each IPv4 address (by ARP, or by a DHCPACK
+//! assigning it) and which address each client was last given, and records
+//! an event when either changes. ARP frames are not UDP, so they arrive here
+//! from the frames the UDP parser rejects.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::Ipv4Addr;
+
+use etherparse::{EtherType, SlicedPacket};
+use pcap_parser::Linktype;
+
+use crate::protocols::arp::layout::ETHER_TYPE;
+use crate::protocols::decode_arp;
+use crate::{ArpPacket, DhcpMessage, MacAddress, NetworkEvent, NetworkEventKind};
+
+/// IP conflicts and DHCP address changes, in capture order.
+#[derive(Debug, Default)]
+pub(crate) struct NetworkTracker {
+    owners: BTreeMap<Ipv4Addr, MacAddress>,
+    leases: BTreeMap<MacAddress, Ipv4Addr>,
+    conflicts: BTreeSet<(Ipv4Addr, MacAddress, MacAddress)>,
+    events: Vec<NetworkEvent>,
+}
+
+impl NetworkTracker {
+    /// Record the ARP packet an Ethernet frame carries, if any.
+    pub(crate) fn add_frame(&mut self, ts: Option<f64>, linktype: Linktype, data: &[u8]) {
+        if linktype != Linktype::ETHERNET {
+            return;
+        }
+        let Some(payload) = SlicedPacket::from_ethernet(data)
+            .ok()
+            .and_then(|sliced| sliced.ether_payload())
+        else {
+            return;
+        };
+        if payload.ether_type != EtherType(ETHER_TYPE) {
+            return;
+        }
+        if let Ok(Some(packet)) = decode_arp(payload.payload) {
+            self.add_arp(ts, &packet);
+        }
+    }
+
+    /// Record an ARP request or reply; probes (sender `0.0.0.0`) claim no
+    /// address.
+    pub(crate) fn add_arp(&mut self, ts: Option<f64>, packet: &ArpPacket) {
+        if packet.sender_ip.is_unspecified() {
+            return;
+        }
+        let Some(previous) = self.owners.insert(packet.sender_ip, packet.sender_mac) else {
+            return;
+        };
+        if previous == packet.sender_mac {
+            return;
+        }
+        // Two hosts fighting over an address alternate; report the pair once.
+        let pair = (
+            packet.sender_ip,
+            previous.min(packet.sender_mac),
+            previous.max(packet.sender_mac),
+        );
+        if self.conflicts.insert(pair) {
+            self.events.push(NetworkEvent {
+                kind: NetworkEventKind::IpConflict,
+                timestamp: ts,
+                ip: packet.sender_ip.to_string(),
+                mac: packet.sender_mac.to_string(),
+                previous_mac: Some(previous.to_string()),
+                previous_ip: None,
+            });
+        }
+    }
+
+    /// Record a DHCP message; only a DHCPACK assigning an address changes a
+    /// lease.
+    pub(crate) fn add_dhcp(&mut self, ts: Option<f64>, message: &DhcpMessage) {
+        if !message.is_ack() || message.your_ip.is_unspecified() {
+            return;
+        }
+        self.owners.insert(message.your_ip, message.client_mac);
+        let previous = self.leases.insert(message.client_mac, message.your_ip);
+        if let Some(previous) = previous {
+            if previous != message.your_ip {
+                self.events.push(NetworkEvent {
+                    kind: NetworkEventKind::DhcpAddressChange,
+                    timestamp: ts,
+                    ip: message.your_ip.to_string(),
+                    mac: message.client_mac.to_string(),
+                    previous_mac: None,
+                    previous_ip: Some(previous.to_string()),
+                });
+            }
+        }
+    }
+
+    pub(crate) fn finish(self) -> Vec<NetworkEvent> {
+        self.events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::NetworkTracker;
+    use crate::{ArpPacket, DhcpMessage, MacAddress, NetworkEventKind};
+
+    fn arp(sender: u8, ip: [u8; 4]) -> ArpPacket {
+        ArpPacket {
+            operation: 2,
+            sender_mac: MacAddress([2, 0, 0, 0, 0, sender]),
+            sender_ip: Ipv4Addr::from(ip),
+            target_mac: MacAddress([0; 6]),
+            target_ip: Ipv4Addr::new(10, 0, 0, 254),
+        }
+    }
+
+    fn ack(client: u8, ip: [u8; 4]) -> DhcpMessage {
+        DhcpMessage {
+            op: 2,
+            message_type: Some(5),
+            client_ip: Ipv4Addr::UNSPECIFIED,
+            your_ip: Ipv4Addr::from(ip),
+            client_mac: MacAddress([2, 0, 0, 0, 0, client]),
+            requested_ip: None,
+            server_id: Some(Ipv4Addr::new(10, 0, 0, 254)),
+        }
+    }
+
+    #[test]
+    fn conflicts_are_reported_once_per_pair_of_hosts() {
+        let mut tracker = NetworkTracker::default();
+        tracker.add_arp(Some(1.0), &arp(1, [10, 0, 0, 10]));
+        tracker.add_arp(Some(1.5), &arp(1, [10, 0, 0, 10]));
+        tracker.add_arp(Some(2.0), &arp(2, [10, 0, 0, 10]));
+        tracker.add_arp(Some(3.0), &arp(1, [10, 0, 0, 10]));
+        tracker.add_arp(Some(4.0), &arp(3, [0, 0, 0, 0]));
+        tracker.add_arp(Some(5.0), &arp(3, [10, 0, 0, 10]));
+
+        let events = tracker.finish();
+        let summary: Vec<_> = events
+            .iter()
+            .map(|event| {
+                (
+                    event.kind,
+                    event.timestamp,
+                    event.mac.as_str(),
+                    event.previous_mac.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    NetworkEventKind::IpConflict,
+                    Some(2.0),
+                    "02:00:00:00:00:02",
+                    Some("02:00:00:00:00:01")
+                ),
+                (
+                    NetworkEventKind::IpConflict,
+                    Some(5.0),
+                    "02:00:00:00:00:03",
+                    Some("02:00:00:00:00:01")
+                ),
+            ]
+        );
+        assert_eq!(events[0].ip, "10.0.0.10");
+    }
+
+    #[test]
+    fn dhcp_address_changes_and_assignments_are_tracked() {
+        let mut tracker = NetworkTracker::default();
+        tracker.add_dhcp(Some(1.0), &ack(1, [10, 0, 0, 10]));
+        tracker.add_dhcp(Some(2.0), &ack(1, [10, 0, 0, 10]));
+        let mut offer = ack(1, [10, 0, 0, 30]);
+        offer.message_type = Some(2);
+        tracker.add_dhcp(Some(2.5), &offer);
+        // The server reuses 10.0.0.10 for another client: not an ARP
+        // conflict once the new owner announces it.
+        tracker.add_dhcp(Some(3.0), &ack(1, [10, 0, 0, 11]));
+        tracker.add_dhcp(Some(3.5), &ack(2, [10, 0, 0, 10]));
+        tracker.add_arp(Some(4.0), &arp(2, [10, 0, 0, 10]));
+
+        let events = tracker.finish();
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.kind, NetworkEventKind::DhcpAddressChange);
+        assert_eq!(event.timestamp, Some(3.0));
+        assert_eq!(event.ip, "10.0.0.11");
+        assert_eq!(event.mac, "02:00:00:00:00:01");
+        assert_eq!(event.previous_ip.as_deref(), Some("10.0.0.10"));
+        assert_eq!(event.previous_mac, None);
+    }
+}
@@ -0,0 +1,168 @@
+//! Node availability from ArtPollReply announcements.
+//!
+//! Art-Net controllers poll every few seconds and every node answers with
+//! an ArtPollReply, so a node's replies arrive at a steady cadence. A gap of
+//! several cadences means the node was unreachable or rebooting; the loss
+//! counted on the universes it outputs during that gap tells whether the
+//! network failed with it.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::IpAddr;
+
+use super::{AVERAGE_SCALE, DURATION_SCALE, round_to};
+use crate::{ArtPollReply, NodeAvailability, NodeOffline};
+
+/// Missed reply cadences after which a node counts as offline.
+const OFFLINE_INTERVALS: f64 = 3.0;
+/// Fewest replies from which a cadence is estimated.
+const MIN_REPLIES: usize = 3;
+
+#[derive(Debug, Default)]
+struct Node {
+    short_name: Option<String>,
+    long_name: Option<String>,
+    outputs: BTreeSet<u16>,
+    replies: u64,
+    times: Vec<f64>,
+}
+
+/// ArtPollReply announcements per node, keyed by source IP and bind index.
+#[derive(Debug, Default)]
+pub(crate) struct NodeTracker {
+    nodes: BTreeMap<(String, u8), Node>,
+}
+
+impl NodeTracker {
+    /// Record an ArtPollReply sent from `src_ip`.
+    pub(crate) fn add_reply(&mut self, ts: Option<f64>, src_ip: IpAddr, reply: ArtPollReply) {
+        let node = self
+            .nodes
+            .entry((src_ip.to_string(), reply.bind_index))
+            .or_default();
+        node.replies += 1;
+        node.times.extend(ts);
+        node.short_name = reply.short_name.or(node.short_name.take());
+        node.long_name = reply.long_name.or(node.long_name.take());
+        node.outputs.extend(reply.outputs);
+    }
+
+    /// Availability of every node, sorted by IP then bind index. `lost`
+    /// counts the packets lost on a universe between two timestamps;
+    /// `capture_end` closes the window of a node that never replied again.
+    pub(crate) fn finish(
+        self,
+        capture_end: Option<f64>,
+        lost: impl Fn(u16, f64, f64) -> u64,
+    ) -> Vec<NodeAvailability> {
+        self.nodes
+            .into_iter()
+            .map(|((ip, bind_index), mut node)| {
+                node.times.sort_by(f64::total_cmp);
+                let interval = reply_interval(&node.times);
+                let mut offline = Vec::new();
+                if let Some(interval) = interval {
+                    let limit = interval * OFFLINE_INTERVALS;
+                    let gaps = node.times.windows(2).map(|pair| (pair[0], pair[1], true));
+                    let tail = node
+                        .times
+                        .last()
+                        .zip(capture_end)
+                        .map(|(last, end)| (*last, end, false));
+                    for (start, end, resumed) in gaps.chain(tail) {
+                        if end - start > limit {
+                            offline.push(NodeOffline {
+                                start,
+                                end,
+                                duration_s: round_to(end - start, DURATION_SCALE),
+                                resumed,
+                                lost_packets: node
+                                    .outputs
+                                    .iter()
+                                    .map(|universe| lost(*universe, start, end))
+                                    .sum(),
+                            });
+                        }
+                    }
+                }
+                NodeAvailability {
+                    ip,
+                    bind_index,
+                    short_name: node.short_name,
+                    long_name: node.long_name,
+                    universes: node.outputs.into_iter().collect(),
+                    replies: node.replies,
+                    first_seen: node.times.first().copied(),
+                    last_seen: node.times.last().copied(),
+                    reply_interval_s: interval.map(|interval| round_to(interval, AVERAGE_SCALE)),
+                    offline,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Median interval between sorted reply timestamps; `None` with fewer than
+/// `MIN_REPLIES` replies or a zero median.
+fn reply_interval(times: &[f64]) -> Option<f64> {
+    if times.len() < MIN_REPLIES {
+        return None;
+    }
+    let mut intervals: Vec<f64> = times.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    intervals.sort_by(f64::total_cmp);
+    Some(intervals[intervals.len() / 2]).filter(|interval| *interval > 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use super::NodeTracker;
+    use crate::ArtPollReply;
+
+    fn reply() -> ArtPollReply {
+        ArtPollReply {
+            ip: Ipv4Addr::new(2, 0, 0, 10),
+            short_name: Some("Node".to_string()),
+            long_name: None,
+            net_switch: 0,
+            sub_switch: 0,
+            outputs: vec![1, 2],
+            bind_index: 1,
+        }
+    }
+
+    #[test]
+    fn missed_replies_open_an_offline_window_with_the_loss_meanwhile() {
+        let mut tracker = NodeTracker::default();
+        let ip = IpAddr::V4(Ipv4Addr::new(2, 0, 0, 10));
+        for ts in [0.0, 3.0, 6.0, 9.0, 21.0, 24.0] {
+            tracker.add_reply(Some(ts), ip, reply());
+        }
+        let nodes = tracker.finish(Some(24.5), |universe, start, end| {
+            assert_eq!((start, end), (9.0, 21.0));
+            u64::from(universe) * 10
+        });
+        assert_eq!(nodes.len(), 1);
+        let node = &nodes[0];
+        assert_eq!(node.ip, "2.0.0.10");
+        assert_eq!(node.universes, vec![1, 2]);
+        assert_eq!(node.reply_interval_s, Some(3.0));
+        assert_eq!(node.offline.len(), 1);
+        assert_eq!(node.offline[0].duration_s, 12.0);
+        assert!(node.offline[0].resumed);
+        assert_eq!(node.offline[0].lost_packets, 30);
+    }
+
+    #[test]
+    fn node_silent_until_the_end_is_offline_without_resuming() {
+        let mut tracker = NodeTracker::default();
+        let ip = IpAddr::V4(Ipv4Addr::new(2, 0, 0, 10));
+        for ts in [0.0, 3.0, 6.0] {
+            tracker.add_reply(Some(ts), ip, reply());
+        }
+        let nodes = tracker.finish(Some(30.0), |_, _, _| 0);
+        assert_eq!(nodes[0].offline.len(), 1);
+        assert!(!nodes[0].offline[0].resumed);
+        assert_eq!(nodes[0].offline[0].end, 30.0);
+    }
+}
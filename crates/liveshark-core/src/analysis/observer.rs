@@ -0,0 +1,90 @@
+//! Streaming callbacks for analysis consumers.
+//!
+//! An `AnalysisObserver` is notified while the pipeline runs, so embedders can
+//! drive live views or custom exports without waiting for the final report.
+//! Every callback has a no-op default; implement only the ones you need.
+
+use super::dmx::DmxFrame;
+use crate::rules::RuleId;
+use crate::source::PacketEvent;
+use crate::{ConflictSummary, Severity, ViolationExample};
+
+/// Single compliance violation occurrence, as seen by an observer.
+///
+/// The rule carries the protocol, code, and message; `severity` is the
+/// effective severity after configuration overrides.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{Protocol, RuleId, Severity, ViolationEvent, ViolationExample};
+///
+/// let example = ViolationExample {
+///     source_ip: Some("10.0.0.1".to_string()),
+///     source_port: Some(5568),
+///     timestamp: None,
+///     detail: "value=1".to_string(),
+///     bytes: None,
+///     vlan: None,
+/// };
+/// let event = ViolationEvent {
+///     rule: RuleId::SacnStartCode,
+///     severity: Severity::Error,
+///     example: &example,
+/// };
+/// assert_eq!(event.rule.code(), "LS-SACN-START-CODE");
+/// assert_eq!(event.rule.protocol(), Protocol::Sacn);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ViolationEvent<'a> {
+    /// Rule that was violated.
+    pub rule: RuleId,
+    /// Severity reported for this rule.
+    pub severity: Severity,
+    /// Example for this occurrence.
+    pub example: &'a ViolationExample,
+}
+
+/// Callbacks invoked while a capture is analyzed.
+///
+/// Packets and frames are reported in capture order. Conflicts are only known
+/// once the whole capture has been read, so `on_conflict_detected` fires after
+/// the last packet, before the report is returned.
+///
+/// # Examples
+/// ```no_run
+/// use liveshark_core::{AnalysisObserver, Analyzer, DmxFrame};
+/// use std::path::Path;
+///
+/// #[derive(Default)]
+/// struct FrameCounter {
+///     frames: u64,
+/// }
+///
+/// impl AnalysisObserver for FrameCounter {
+///     fn on_dmx_frame(&mut self, _frame: &DmxFrame) {
+///         self.frames += 1;
+///     }
+/// }
+///
+/// let mut counter = FrameCounter::default();
+/// let report = Analyzer::default()
+///     .analyze_file_with_observer(Path::new("capture.pcapng"), &mut counter)?;
+/// println!("{} frames, {} universes", counter.frames, report.universes.len());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub trait AnalysisObserver {
+    /// Called for every packet read from the source, before decoding.
+    fn on_packet(&mut self, _packet: &PacketEvent) {}
+
+    /// Called for every reconstructed DMX frame.
+    fn on_dmx_frame(&mut self, _frame: &DmxFrame) {}
+
+    /// Called for every compliance violation occurrence.
+    fn on_violation(&mut self, _violation: &ViolationEvent<'_>) {}
+
+    /// Called for every conflict in the final report.
+    fn on_conflict_detected(&mut self, _conflict: &ConflictSummary) {}
+}
+
+/// No-op observer used by the plain analysis entry points.
+impl AnalysisObserver for () {}
@@ -0,0 +1,94 @@
+//! Open Sound Control message counts per address pattern.
+//!
+//! OSC drives show control between consoles, media servers, and playback
+//! (cue GO, fader levels, transport), so the addresses on the wire tell which
+//! commands were actually sent, how often, and by whom.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::IpAddr;
+
+use crate::{OscAddressSummary, OscPacket};
+
+#[derive(Debug, Default)]
+struct Address {
+    messages: u64,
+    senders: BTreeSet<IpAddr>,
+    first_seen: Option<f64>,
+    last_seen: Option<f64>,
+}
+
+/// OSC messages, keyed by address pattern.
+#[derive(Debug, Default)]
+pub(crate) struct OscTracker {
+    addresses: BTreeMap<String, Address>,
+}
+
+impl OscTracker {
+    /// Record the messages of an OSC packet sent from `src_ip`.
+    pub(crate) fn add(&mut self, ts: Option<f64>, src_ip: IpAddr, packet: &OscPacket) {
+        for message in &packet.messages {
+            let address = self.addresses.entry(message.address.clone()).or_default();
+            address.messages += 1;
+            address.senders.insert(src_ip);
+            if let Some(ts) = ts {
+                address.first_seen.get_or_insert(ts);
+                address.last_seen = Some(ts);
+            }
+        }
+    }
+
+    /// Address patterns sorted by address.
+    pub(crate) fn finish(self) -> Vec<OscAddressSummary> {
+        self.addresses
+            .into_iter()
+            .map(|(address, stats)| OscAddressSummary {
+                address,
+                messages: stats.messages,
+                senders: stats.senders.iter().map(IpAddr::to_string).collect(),
+                first_seen: stats.first_seen,
+                last_seen: stats.last_seen,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use super::OscTracker;
+    use crate::{OscMessage, OscPacket};
+
+    fn packet(addresses: &[&str]) -> OscPacket {
+        OscPacket {
+            messages: addresses
+                .iter()
+                .map(|address| OscMessage {
+                    address: address.to_string(),
+                    type_tags: None,
+                })
+                .collect(),
+            bundles: 0,
+        }
+    }
+
+    #[test]
+    fn messages_are_counted_per_address_with_their_senders() {
+        let mut tracker = OscTracker::default();
+        let desk = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 20));
+        let backup = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 3));
+        tracker.add(Some(1.0), desk, &packet(&["/cue/1/go", "/fader/1"]));
+        tracker.add(Some(2.0), backup, &packet(&["/cue/1/go"]));
+        tracker.add(None, desk, &packet(&["/cue/1/go"]));
+
+        let addresses = tracker.finish();
+        assert_eq!(addresses.len(), 2);
+        let go = &addresses[0];
+        assert_eq!(go.address, "/cue/1/go");
+        assert_eq!(go.messages, 3);
+        assert_eq!(go.senders, ["10.0.0.3", "10.0.0.20"]);
+        assert_eq!((go.first_seen, go.last_seen), (Some(1.0), Some(2.0)));
+        assert_eq!(addresses[1].address, "/fader/1");
+        assert_eq!(addresses[1].messages, 1);
+    }
+}
@@ -0,0 +1,194 @@
+//! "Other traffic": UDP and TCP flows that carry neither Art-Net nor sACN.
+//!
+//! Lighting VLANs are expected to be quiet apart from DMX; a backup job or a
+//! video stream sharing the network shows up here ranked by bytes, with a
+//! service name guessed from well-known ports.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+use etherparse::{NetSlice, SlicedPacket, TransportSlice};
+use pcap_parser::Linktype;
+
+use super::flows::format_endpoint;
+use super::udp::UdpPacket;
+use crate::report::cmp_other_traffic;
+use crate::{OtherTrafficSummary, Protocol};
+
+/// Well-known ports, checked on both ends of a flow (lowest port first).
+const WELL_KNOWN_PORTS: &[(u16, &str)] = &[
+    (22, "ssh"),
+    (23, "telnet"),
+    (53, "dns"),
+    (67, "dhcp"),
+    (68, "dhcp"),
+    (69, "tftp"),
+    (80, "http"),
+    (123, "ntp"),
+    (137, "netbios"),
+    (138, "netbios"),
+    (161, "snmp"),
+    (162, "snmp"),
+    (319, "ptp"),
+    (320, "ptp"),
+    (443, "https"),
+    (445, "smb"),
+    (514, "syslog"),
+    (1883, "mqtt"),
+    (1900, "ssdp"),
+    (4809, "citp"),
+    (5353, "mdns"),
+    (5355, "llmnr"),
+    (8080, "http"),
+    (56565, "psn"),
+];
+
+#[derive(Debug, Hash, PartialEq, Eq)]
+struct OtherFlowKey {
+    transport: Protocol,
+    src_ip: IpAddr,
+    src_port: u16,
+    dst_ip: IpAddr,
+    dst_port: u16,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct OtherTraffic {
+    flows: HashMap<OtherFlowKey, (u64, u64)>,
+}
+
+impl OtherTraffic {
+    /// Count a UDP datagram that was not identified as Art-Net or sACN.
+    pub(crate) fn add_udp(&mut self, udp: &UdpPacket<'_>, bytes: usize) {
+        self.add(
+            OtherFlowKey {
+                transport: Protocol::Udp,
+                src_ip: udp.src_ip,
+                src_port: udp.src_port,
+                dst_ip: udp.dst_ip,
+                dst_port: udp.dst_port,
+            },
+            bytes,
+        );
+    }
+
+    /// Count a non-UDP frame when it carries TCP; other frames are ignored.
+    pub(crate) fn add_frame(&mut self, linktype: Linktype, data: &[u8]) {
+        let sliced = match linktype {
+            Linktype::ETHERNET => SlicedPacket::from_ethernet(data),
+            Linktype::RAW => SlicedPacket::from_ip(data),
+            _ => return,
+        };
+        let Ok(sliced) = sliced else {
+            return;
+        };
+        let (Some(net), Some(TransportSlice::Tcp(tcp))) = (sliced.net, sliced.transport) else {
+            return;
+        };
+        let (src_ip, dst_ip) = net_addresses(&net);
+        self.add(
+            OtherFlowKey {
+                transport: Protocol::Tcp,
+                src_ip,
+                src_port: tcp.source_port(),
+                dst_ip,
+                dst_port: tcp.destination_port(),
+            },
+            data.len(),
+        );
+    }
+
+    fn add(&mut self, key: OtherFlowKey, bytes: usize) {
+        let (packets, total) = self.flows.entry(key).or_default();
+        *packets += 1;
+        *total += bytes as u64;
+    }
+
+    /// The `limit` largest flows by bytes, largest first (ties by transport,
+    /// source, then destination).
+    pub(crate) fn finalize(self, limit: usize) -> Vec<OtherTrafficSummary> {
+        let mut flows: Vec<OtherTrafficSummary> = self
+            .flows
+            .into_iter()
+            .map(|(key, (packets, bytes))| OtherTrafficSummary {
+                transport: key.transport,
+                src: format_endpoint(key.src_ip, key.src_port),
+                dst: format_endpoint(key.dst_ip, key.dst_port),
+                packets,
+                bytes,
+                service: guess_service(key.src_port, key.dst_port).map(str::to_string),
+            })
+            .collect();
+        flows.sort_by(cmp_other_traffic);
+        flows.truncate(limit);
+        flows
+    }
+}
+
+fn net_addresses(net: &NetSlice<'_>) -> (IpAddr, IpAddr) {
+    match net {
+        NetSlice::Ipv4(ipv4) => (
+            IpAddr::V4(ipv4.header().source_addr()),
+            IpAddr::V4(ipv4.header().destination_addr()),
+        ),
+        NetSlice::Ipv6(ipv6) => (
+            IpAddr::V6(ipv6.header().source_addr()),
+            IpAddr::V6(ipv6.header().destination_addr()),
+        ),
+    }
+}
+
+fn guess_service(src_port: u16, dst_port: u16) -> Option<&'static str> {
+    let lookup = |port| {
+        WELL_KNOWN_PORTS
+            .iter()
+            .find(|(known, _)| *known == port)
+            .map(|(_, name)| *name)
+    };
+    let (low, high) = (src_port.min(dst_port), src_port.max(dst_port));
+    lookup(low).or_else(|| lookup(high))
+}
+
+#[cfg(test)]
+mod tests {
+    use etherparse::PacketBuilder;
+    use pcap_parser::Linktype;
+
+    use super::{OtherTraffic, guess_service};
+    use crate::Protocol;
+
+    fn tcp_frame(dst_port: u16, payload: &[u8]) -> Vec<u8> {
+        let builder = PacketBuilder::ethernet2([1, 2, 3, 4, 5, 6], [7, 8, 9, 10, 11, 12])
+            .ipv4([10, 0, 0, 1], [10, 0, 0, 2], 64)
+            .tcp(50000, dst_port, 0, 1024);
+        let mut data = Vec::<u8>::with_capacity(builder.size(payload.len()));
+        builder.write(&mut data, payload).unwrap();
+        data
+    }
+
+    #[test]
+    fn ranks_flows_by_bytes_and_keeps_the_top() {
+        let mut other = OtherTraffic::default();
+        let small = tcp_frame(22, &[0; 10]);
+        let large = tcp_frame(443, &[0; 500]);
+        other.add_frame(Linktype::ETHERNET, &small);
+        other.add_frame(Linktype::ETHERNET, &large);
+        other.add_frame(Linktype::ETHERNET, &large);
+        other.add_frame(Linktype::ETHERNET, &[0u8; 4]);
+
+        let flows = other.finalize(1);
+        assert_eq!(flows.len(), 1);
+        assert_eq!(flows[0].transport, Protocol::Tcp);
+        assert_eq!(flows[0].dst, "10.0.0.2:443");
+        assert_eq!(flows[0].packets, 2);
+        assert_eq!(flows[0].bytes, 2 * large.len() as u64);
+        assert_eq!(flows[0].service.as_deref(), Some("https"));
+    }
+
+    #[test]
+    fn services_prefer_the_lowest_known_port() {
+        assert_eq!(guess_service(5353, 5353), Some("mdns"));
+        assert_eq!(guess_service(51000, 53), Some("dns"));
+        assert_eq!(guess_service(40000, 40001), None);
+    }
+}
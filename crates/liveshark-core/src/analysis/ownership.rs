@@ -0,0 +1,231 @@
+//! Channel ownership of contested universes.
+//!
+//! When several sources send the same universe, a receiver shows whichever
+//! wrote a slot last (Art-Net) or, for sACN, the highest-priority source
+//! still alive. Replaying the frames of all sources in time order and
+//! crediting each slot's holding time to its current owner answers "who
+//! actually controlled the rig" per channel range.
+
+use std::collections::HashMap;
+
+use super::dmx::{DmxFrame, FrameWrite};
+use super::round_to;
+use crate::{ChannelOwnership, OwnershipShare, Protocol};
+
+/// sACN source loss timeout (seconds, ANSI E1.31 network data loss): a
+/// silent source stops holding its priority after this long.
+const SACN_SOURCE_TIMEOUT_S: f64 = 2.5;
+/// Ownership percentages are rounded to one decimal.
+const PERCENT_SCALE: f64 = 10.0;
+
+/// Ownership per channel range of a universe with two or more sources;
+/// `sources` holds each source's frames and writes in arrival order. Empty
+/// for an uncontested universe.
+pub(crate) fn channel_ownership<'a>(
+    protocol: Protocol,
+    sources: impl IntoIterator<Item = (&'a [DmxFrame], &'a [FrameWrite])>,
+) -> Vec<ChannelOwnership> {
+    let mut events: Vec<(f64, usize, FrameWrite)> = Vec::new();
+    let mut source_ids: Vec<&str> = Vec::new();
+    for (frames, writes) in sources {
+        let Some(first) = frames.first() else {
+            continue;
+        };
+        let index = source_ids.len();
+        source_ids.push(&first.source_id);
+        events.extend(
+            frames
+                .iter()
+                .zip(writes)
+                .filter_map(|(frame, write)| Some((frame.timestamp?, index, *write))),
+        );
+    }
+    if source_ids.len() < 2 {
+        return Vec::new();
+    }
+    events.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+
+    let mut owners: [Option<usize>; 512] = [None; 512];
+    let mut held = vec![[0.0f64; 512]; source_ids.len()];
+    // Last frame time and priority of each sACN source.
+    let mut alive: HashMap<usize, (f64, u8)> = HashMap::new();
+    let mut previous_ts: Option<f64> = None;
+    for (ts, source, write) in events {
+        if let Some(previous) = previous_ts {
+            let elapsed = ts - previous;
+            for (channel, owner) in owners.iter().enumerate() {
+                if let Some(owner) = owner {
+                    held[*owner][channel] += elapsed;
+                }
+            }
+        }
+        previous_ts = Some(ts);
+
+        if protocol == Protocol::Sacn {
+            let priority = write.priority.unwrap_or_default();
+            alive.insert(source, (ts, priority));
+            alive.retain(|_, (last, _)| ts - *last <= SACN_SOURCE_TIMEOUT_S);
+            let highest = alive.values().map(|(_, priority)| *priority).max();
+            if highest.is_some_and(|highest| priority < highest) {
+                continue;
+            }
+        }
+        for owner in owners.iter_mut().take(usize::from(write.slots)) {
+            *owner = Some(source);
+        }
+    }
+
+    let shares_of = |channel: usize| -> Vec<OwnershipShare> {
+        let total: f64 = held.iter().map(|per_source| per_source[channel]).sum();
+        if total <= 0.0 {
+            return Vec::new();
+        }
+        let mut shares: Vec<OwnershipShare> = held
+            .iter()
+            .enumerate()
+            .filter(|(_, per_source)| per_source[channel] > 0.0)
+            .map(|(source, per_source)| OwnershipShare {
+                source_id: source_ids[source].to_string(),
+                percent: round_to(per_source[channel] / total * 100.0, PERCENT_SCALE),
+            })
+            .collect();
+        shares.sort_by(|a, b| {
+            b.percent
+                .total_cmp(&a.percent)
+                .then_with(|| a.source_id.cmp(&b.source_id))
+        });
+        shares
+    };
+
+    let mut ranges: Vec<ChannelOwnership> = Vec::new();
+    for channel in 0..512 {
+        let shares = shares_of(channel);
+        if shares.is_empty() {
+            continue;
+        }
+        let number = channel as u16 + 1;
+        match ranges.last_mut() {
+            Some(range) if range.end + 1 == number && range.owners == shares => {
+                range.end = number;
+            }
+            _ => ranges.push(ChannelOwnership {
+                start: number,
+                end: number,
+                owners: shares,
+            }),
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::channel_ownership;
+    use crate::Protocol;
+    use crate::analysis::dmx::{DmxFrame, FrameWrite};
+
+    fn source(
+        id: &str,
+        protocol: Protocol,
+        times: &[f64],
+        write: FrameWrite,
+    ) -> (Vec<DmxFrame>, Vec<FrameWrite>) {
+        let frames = times
+            .iter()
+            .map(|ts| DmxFrame {
+                universe: 1,
+                timestamp: Some(*ts),
+                source_id: id.to_string(),
+                protocol,
+                slots: [0u8; 512],
+            })
+            .collect();
+        (frames, vec![write; times.len()])
+    }
+
+    #[test]
+    fn last_writer_owns_the_slots_it_carried() {
+        // A full-universe console, and a node writing only slots 1-10 for
+        // the second half of the capture.
+        let console = source(
+            "artnet:10.0.0.1:6454",
+            Protocol::ArtNet,
+            &[0.0, 1.0, 2.0, 3.0, 4.0],
+            FrameWrite::default(),
+        );
+        let node = source(
+            "artnet:10.0.0.2:6454",
+            Protocol::ArtNet,
+            &[2.5, 3.5],
+            FrameWrite {
+                slots: 10,
+                priority: None,
+            },
+        );
+        let ranges = channel_ownership(
+            Protocol::ArtNet,
+            [
+                (console.0.as_slice(), console.1.as_slice()),
+                (node.0.as_slice(), node.1.as_slice()),
+            ],
+        );
+        assert_eq!(ranges.len(), 2);
+        assert_eq!((ranges[0].start, ranges[0].end), (1, 10));
+        assert_eq!(ranges[0].owners[0].source_id, "artnet:10.0.0.1:6454");
+        assert_eq!(ranges[0].owners[0].percent, 75.0);
+        assert_eq!(ranges[0].owners[1].percent, 25.0);
+        assert_eq!((ranges[1].start, ranges[1].end), (11, 512));
+        assert_eq!(ranges[1].owners.len(), 1);
+        assert_eq!(ranges[1].owners[0].percent, 100.0);
+    }
+
+    #[test]
+    fn higher_sacn_priority_keeps_control_until_timeout() {
+        let priority = |priority| FrameWrite {
+            slots: 512,
+            priority: Some(priority),
+        };
+        let main = source(
+            "sacn:cid:aa",
+            Protocol::Sacn,
+            &[0.0, 1.0, 2.0],
+            priority(150),
+        );
+        let backup = source(
+            "sacn:cid:bb",
+            Protocol::Sacn,
+            &[0.5, 1.5, 2.5, 5.0, 6.0],
+            priority(100),
+        );
+        let ranges = channel_ownership(
+            Protocol::Sacn,
+            [
+                (main.0.as_slice(), main.1.as_slice()),
+                (backup.0.as_slice(), backup.1.as_slice()),
+            ],
+        );
+        assert_eq!(ranges.len(), 1);
+        // The main source holds until the backup takes over at 5.0 (main
+        // silent for 3 s); the backup holds 5.0-6.0.
+        assert_eq!(ranges[0].owners[0].source_id, "sacn:cid:aa");
+        assert_eq!(ranges[0].owners[0].percent, 83.3);
+        assert_eq!(ranges[0].owners[1].percent, 16.7);
+    }
+
+    #[test]
+    fn single_source_has_no_ownership() {
+        let console = source(
+            "artnet:10.0.0.1:6454",
+            Protocol::ArtNet,
+            &[0.0, 1.0],
+            FrameWrite::default(),
+        );
+        assert!(
+            channel_ownership(
+                Protocol::ArtNet,
+                [(console.0.as_slice(), console.1.as_slice())]
+            )
+            .is_empty()
+        );
+    }
+}
@@ -0,0 +1,214 @@
+//! sACN priority timeline.
+//!
+//! Receivers follow the live sources with the highest priority and merge
+//! those tied at it. Replaying the frames of every source of a universe in
+//! time order gives the spans during which the same sources held that
+//! priority: a deliberate failover shows as a few long spans, a priority
+//! fight as many short ones or a lasting tie.
+
+use std::collections::BTreeMap;
+
+use super::dmx::{DmxFrame, FrameWrite};
+use super::takeovers::SOURCE_TIMEOUT_S;
+use crate::PrioritySpan;
+
+struct Source {
+    last: f64,
+    priority: u8,
+    live: bool,
+}
+
+/// Highest live priority and the sources holding it.
+type Holders<'a> = (u8, Vec<&'a str>);
+
+struct Timeline<'a> {
+    universe: u16,
+    current: Option<Holders<'a>>,
+    start: f64,
+    spans: Vec<PrioritySpan>,
+}
+
+impl<'a> Timeline<'a> {
+    /// Close the current span at `at` when the holders differ from it.
+    fn update(&mut self, at: f64, sources: &BTreeMap<&'a str, Source>) {
+        let highest = sources
+            .values()
+            .filter(|source| source.live)
+            .map(|source| source.priority)
+            .max();
+        let holders = highest.map(|priority| {
+            let ids = sources
+                .iter()
+                .filter(|(_, source)| source.live && source.priority == priority)
+                .map(|(id, _)| *id)
+                .collect();
+            (priority, ids)
+        });
+        if holders == self.current {
+            return;
+        }
+        self.close(at);
+        self.current = holders;
+        self.start = at;
+    }
+
+    fn close(&mut self, at: f64) {
+        if let Some((priority, holders)) = &self.current {
+            if at > self.start {
+                self.spans.push(PrioritySpan {
+                    universe: self.universe,
+                    start: self.start,
+                    end: at,
+                    priority: *priority,
+                    holders: holders.iter().map(|id| id.to_string()).collect(),
+                });
+            }
+        }
+    }
+}
+
+/// Priority spans of one sACN universe; `sources` holds each source's frames
+/// and writes in arrival order. Empty for a universe with a single source.
+pub(crate) fn priority_timeline<'a>(
+    sources: impl IntoIterator<Item = (&'a [DmxFrame], &'a [FrameWrite])>,
+) -> Vec<PrioritySpan> {
+    let mut universe = None;
+    let mut states: BTreeMap<&'a str, Source> = BTreeMap::new();
+    let mut events: Vec<(f64, &'a str, u8)> = Vec::new();
+    for (frames, writes) in sources {
+        let Some(first) = frames.first() else {
+            continue;
+        };
+        universe = Some(first.universe);
+        states.insert(
+            &first.source_id,
+            Source {
+                last: 0.0,
+                priority: 0,
+                live: false,
+            },
+        );
+        for (frame, write) in frames.iter().zip(writes) {
+            if let Some(ts) = frame.timestamp {
+                events.push((ts, &first.source_id, write.priority.unwrap_or_default()));
+            }
+        }
+    }
+    let Some(universe) = universe.filter(|_| states.len() >= 2) else {
+        return Vec::new();
+    };
+    events.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(b.1)));
+
+    let mut timeline = Timeline {
+        universe,
+        current: None,
+        start: 0.0,
+        spans: Vec::new(),
+    };
+    let mut end = 0.0;
+    for (ts, id, priority) in events {
+        // Sources that timed out since the previous frame drop out in order.
+        while let Some((expired, expiry)) = states
+            .iter()
+            .filter(|(_, source)| source.live && ts - source.last > SOURCE_TIMEOUT_S)
+            .map(|(id, source)| (*id, source.last + SOURCE_TIMEOUT_S))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+        {
+            if let Some(source) = states.get_mut(expired) {
+                source.live = false;
+            }
+            timeline.update(expiry, &states);
+        }
+        if let Some(source) = states.get_mut(id) {
+            source.last = ts;
+            source.priority = priority;
+            source.live = true;
+        }
+        timeline.update(ts, &states);
+        end = ts;
+    }
+    timeline.close(end);
+    timeline.spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::priority_timeline;
+    use crate::Protocol;
+    use crate::analysis::dmx::{DmxFrame, FrameWrite};
+
+    /// 40 fps frames of `source` between `start` and `end` (seconds).
+    fn frames(
+        source: &str,
+        start: f64,
+        end: f64,
+        priority: u8,
+    ) -> (Vec<DmxFrame>, Vec<FrameWrite>) {
+        let count = ((end - start) / 0.025).round() as usize;
+        let frames = (0..count)
+            .map(|index| DmxFrame {
+                universe: 1,
+                timestamp: Some(start + index as f64 * 0.025),
+                source_id: source.to_string(),
+                protocol: Protocol::Sacn,
+                slots: [0u8; 512],
+            })
+            .collect();
+        let writes = vec![
+            FrameWrite {
+                slots: 512,
+                priority: Some(priority),
+            };
+            count
+        ];
+        (frames, writes)
+    }
+
+    #[test]
+    fn higher_priority_source_takes_a_span_of_its_own() {
+        let main = frames("sacn:cid:main", 0.0, 10.0, 100);
+        let override_desk = frames("sacn:cid:override", 4.0, 6.0, 150);
+        let spans = priority_timeline([
+            (main.0.as_slice(), main.1.as_slice()),
+            (override_desk.0.as_slice(), override_desk.1.as_slice()),
+        ]);
+        let summary: Vec<_> = spans
+            .iter()
+            .map(|span| (span.priority, span.holders.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (100, vec!["sacn:cid:main".to_string()]),
+                (150, vec!["sacn:cid:override".to_string()]),
+                (100, vec!["sacn:cid:main".to_string()]),
+            ]
+        );
+        assert_eq!(spans[1].start, 4.0);
+        // The override stops after 5.975 s and times out 2.5 s later.
+        assert!((spans[2].start - 8.475).abs() < 1e-9);
+        assert!((spans[2].end - 9.975).abs() < 1e-9);
+    }
+
+    #[test]
+    fn equal_priorities_are_reported_as_a_tie() {
+        let main = frames("sacn:cid:main", 0.0, 5.0, 100);
+        let backup = frames("sacn:cid:backup", 1.0, 5.0, 100);
+        let spans = priority_timeline([
+            (main.0.as_slice(), main.1.as_slice()),
+            (backup.0.as_slice(), backup.1.as_slice()),
+        ]);
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[1].start, 1.0);
+        assert_eq!(
+            spans[1].holders,
+            vec!["sacn:cid:backup".to_string(), "sacn:cid:main".to_string()]
+        );
+    }
+
+    #[test]
+    fn single_source_has_no_timeline() {
+        let main = frames("sacn:cid:main", 0.0, 5.0, 100);
+        assert!(priority_timeline([(main.0.as_slice(), main.1.as_slice())]).is_empty());
+    }
+}
@@ -0,0 +1,189 @@
+//! Named compliance profiles.
+//!
+//! A profile is a preset over the default configuration: it toggles rules and
+//! moves thresholds for a kind of network in one step. Settings from a
+//! configuration file are applied on top of the profile, and the profile name
+//! is recorded in the report so runs made with different presets are not
+//! compared by mistake.
+//!
+//! - `strict`: every registry rule enabled, tighter rate limits.
+//! - `touring`: tolerant of temporary networks and capture laptops (checksum
+//!   offload, unnamed sources, fast software senders).
+//! - `install`: long-running permanent installs, where sequence gaps and
+//!   duplicated CIDs matter more.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::config::AnalyzerConfig;
+use crate::Severity;
+use crate::rules::RuleId;
+
+/// Universe frame rate above which `strict` reports flooding (frames per
+/// second): the 44 Hz DMX512 maximum plus margin for window edges.
+pub const STRICT_MAX_UNIVERSE_FPS: f64 = 50.0;
+/// Broadcast rate per source above which `strict` reports a storm.
+pub const STRICT_MAX_BROADCAST_PPS: f64 = 100.0;
+/// Broadcast rate per source above which `touring` reports a storm; Art-Net
+/// rigs on a flat network broadcast far more than installed systems.
+pub const TOURING_MAX_BROADCAST_PPS: f64 = 400.0;
+
+/// Rules `touring` disables: mostly artifacts of capture laptops and
+/// mixed-vendor rigs rather than problems on the wire.
+const TOURING_DISABLED: [RuleId; 4] = [
+    RuleId::ArtNetMinInterval,
+    RuleId::SacnSourceName,
+    RuleId::UdpChecksum,
+    RuleId::UdpIpChecksum,
+];
+
+/// Rules `install` enables on top of the defaults.
+const INSTALL_ENABLED: [RuleId; 2] = [RuleId::ArtNetSeqGap, RuleId::SacnSeqGap];
+
+/// Error returned when a string is not a known profile name.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::Profile;
+///
+/// let err = "relaxed".parse::<Profile>().unwrap_err();
+/// assert!(err.to_string().contains("relaxed"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("unknown profile: {name} (expected strict, touring, or install)")]
+pub struct UnknownProfileError {
+    pub name: String,
+}
+
+/// Named preset of rules and thresholds.
+///
+/// Serializes as its lowercase name.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{AnalyzerConfig, Profile, RuleId};
+///
+/// let profile: Profile = "strict".parse()?;
+/// let config = AnalyzerConfig::for_profile(profile);
+/// assert_eq!(config.profile, Some(Profile::Strict));
+/// assert!(config.rules.is_enabled(RuleId::SacnSeqGap));
+/// # Ok::<(), liveshark_core::UnknownProfileError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Profile {
+    /// Every rule enabled, tighter rate limits.
+    Strict,
+    /// Permissive preset for temporary touring networks.
+    Touring,
+    /// Preset for permanent installations.
+    Install,
+}
+
+impl Profile {
+    /// All profiles, in documentation order.
+    pub const ALL: &'static [Profile] = &[Profile::Strict, Profile::Touring, Profile::Install];
+
+    /// Canonical lowercase name, as recorded in reports.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Profile::Strict => "strict",
+            Profile::Touring => "touring",
+            Profile::Install => "install",
+        }
+    }
+
+    /// Apply the preset to `config` and record it as the active profile.
+    pub(crate) fn apply(self, config: &mut AnalyzerConfig) {
+        config.profile = Some(self);
+        match self {
+            Profile::Strict => {
+                for rule in RuleId::ALL {
+                    config.rules.set_enabled(*rule, true);
+                }
+                config.max_universe_fps = STRICT_MAX_UNIVERSE_FPS;
+                config.max_broadcast_pps = STRICT_MAX_BROADCAST_PPS;
+            }
+            Profile::Touring => {
+                for rule in TOURING_DISABLED {
+                    config.rules.set_enabled(rule, false);
+                }
+                config.max_broadcast_pps = TOURING_MAX_BROADCAST_PPS;
+            }
+            Profile::Install => {
+                for rule in INSTALL_ENABLED {
+                    config.rules.set_enabled(rule, true);
+                }
+                config
+                    .rules
+                    .set_severity(RuleId::SacnCidMultiIp, Severity::Error);
+            }
+        }
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Profile {
+    type Err = UnknownProfileError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Profile::ALL
+            .iter()
+            .copied()
+            .find(|profile| profile.as_str() == name)
+            .ok_or_else(|| UnknownProfileError {
+                name: name.to_string(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Profile, STRICT_MAX_UNIVERSE_FPS, TOURING_MAX_BROADCAST_PPS};
+    use crate::Severity;
+    use crate::analysis::config::AnalyzerConfig;
+    use crate::rules::RuleId;
+
+    #[test]
+    fn names_round_trip() {
+        for profile in Profile::ALL {
+            assert_eq!(profile.as_str().parse::<Profile>().unwrap(), *profile);
+            assert_eq!(
+                serde_json::to_string(profile).unwrap(),
+                format!("\"{profile}\"")
+            );
+        }
+    }
+
+    #[test]
+    fn presets_toggle_rules_and_thresholds() {
+        let strict = AnalyzerConfig::for_profile(Profile::Strict);
+        assert!(
+            RuleId::ALL
+                .iter()
+                .all(|rule| strict.rules.is_enabled(*rule))
+        );
+        assert_eq!(strict.max_universe_fps, STRICT_MAX_UNIVERSE_FPS);
+
+        let touring = AnalyzerConfig::for_profile(Profile::Touring);
+        assert!(!touring.rules.is_enabled(RuleId::UdpChecksum));
+        assert!(touring.rules.is_enabled(RuleId::SacnStartCode));
+        assert_eq!(touring.max_broadcast_pps, TOURING_MAX_BROADCAST_PPS);
+
+        let install = AnalyzerConfig::for_profile(Profile::Install);
+        assert!(install.rules.is_enabled(RuleId::ArtNetSeqGap));
+        assert_eq!(
+            install.rules.severity(RuleId::SacnCidMultiIp),
+            Severity::Error
+        );
+        assert_eq!(install.profile, Some(Profile::Install));
+    }
+}
@@ -0,0 +1,205 @@
+//! PosiStageNet tracker summaries from PSN data and info packets.
+//!
+//! Media servers and automation read tracker positions at the rate the
+//! tracking system sends them; a gap of more than half a second between two
+//! updates of a tracker is a dropout, during which followed fixtures and
+//! projections freeze. Info packets name the trackers, and the name is kept
+//! whether it arrives before or after the data.
+
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+
+use super::{AVERAGE_SCALE, DURATION_SCALE, round_to};
+use crate::{PsnData, PsnDropout, PsnInfo, PsnTrackerSummary};
+
+/// Gap between updates, in seconds, above which the tracker dropped out.
+const DROPOUT_GAP_S: f64 = 0.5;
+
+#[derive(Debug, Default)]
+struct Tracker {
+    updates: u64,
+    first_seen: Option<f64>,
+    last_seen: Option<f64>,
+    last_position: Option<[f32; 3]>,
+    max_speed: Option<f64>,
+    dropouts: Vec<PsnDropout>,
+}
+
+/// PSN trackers, keyed by source IP and tracker id.
+#[derive(Debug, Default)]
+pub(crate) struct PsnTrackers {
+    trackers: BTreeMap<(String, u16), Tracker>,
+    names: BTreeMap<(String, u16), String>,
+    system_names: BTreeMap<String, String>,
+}
+
+impl PsnTrackers {
+    /// Record the trackers of a PSN data packet sent from `src_ip`.
+    pub(crate) fn add_data(&mut self, ts: Option<f64>, src_ip: IpAddr, data: &PsnData) {
+        let source_ip = src_ip.to_string();
+        for update in &data.trackers {
+            let tracker = self
+                .trackers
+                .entry((source_ip.clone(), update.id))
+                .or_default();
+            tracker.updates += 1;
+            if update.position.is_some() {
+                tracker.last_position = update.position;
+            }
+            if let Some(speed) = update.speed {
+                let magnitude = speed
+                    .iter()
+                    .map(|axis| f64::from(*axis).powi(2))
+                    .sum::<f64>()
+                    .sqrt();
+                tracker.max_speed = Some(
+                    tracker
+                        .max_speed
+                        .map_or(magnitude, |max| max.max(magnitude)),
+                );
+            }
+            let Some(ts) = ts else {
+                continue;
+            };
+            if let Some(previous) = tracker.last_seen {
+                let gap = ts - previous;
+                if gap > DROPOUT_GAP_S {
+                    tracker.dropouts.push(PsnDropout {
+                        start: previous,
+                        duration_s: round_to(gap, DURATION_SCALE),
+                    });
+                }
+            }
+            tracker.first_seen.get_or_insert(ts);
+            tracker.last_seen = Some(ts);
+        }
+    }
+
+    /// Record the system and tracker names of a PSN info packet sent from
+    /// `src_ip`.
+    pub(crate) fn add_info(&mut self, src_ip: IpAddr, info: &PsnInfo) {
+        let source_ip = src_ip.to_string();
+        if let Some(system_name) = &info.system_name {
+            self.system_names
+                .insert(source_ip.clone(), system_name.clone());
+        }
+        for tracker in &info.trackers {
+            self.names
+                .insert((source_ip.clone(), tracker.id), tracker.name.clone());
+        }
+    }
+
+    /// Trackers with at least one update, sorted by source IP then tracker
+    /// id.
+    pub(crate) fn finish(mut self) -> Vec<PsnTrackerSummary> {
+        self.trackers
+            .into_iter()
+            .map(|((source_ip, tracker_id), tracker)| {
+                let update_rate_hz = match (tracker.first_seen, tracker.last_seen) {
+                    (Some(first), Some(last)) if last > first => Some(round_to(
+                        (tracker.updates - 1) as f64 / (last - first),
+                        AVERAGE_SCALE,
+                    )),
+                    _ => None,
+                };
+                PsnTrackerSummary {
+                    system_name: self.system_names.get(&source_ip).cloned(),
+                    name: self.names.remove(&(source_ip.clone(), tracker_id)),
+                    source_ip,
+                    tracker_id,
+                    updates: tracker.updates,
+                    update_rate_hz,
+                    first_seen: tracker.first_seen,
+                    last_seen: tracker.last_seen,
+                    last_position: tracker.last_position.map(|position| {
+                        position.map(|axis| round_to(f64::from(axis), AVERAGE_SCALE))
+                    }),
+                    max_speed: tracker
+                        .max_speed
+                        .map(|speed| round_to(speed, AVERAGE_SCALE)),
+                    dropouts: tracker.dropouts,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use super::PsnTrackers;
+    use crate::{PsnData, PsnInfo, PsnPacketHeader, PsnTracker, PsnTrackerName};
+
+    fn header() -> PsnPacketHeader {
+        PsnPacketHeader {
+            timestamp_us: 0,
+            version_high: 2,
+            version_low: 3,
+            frame_id: 0,
+            frame_packet_count: 1,
+        }
+    }
+
+    fn data(trackers: Vec<PsnTracker>) -> PsnData {
+        PsnData {
+            header: header(),
+            trackers,
+        }
+    }
+
+    #[test]
+    fn trackers_get_rate_speed_names_and_dropouts() {
+        let mut trackers = PsnTrackers::default();
+        let ip = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 5));
+        trackers.add_info(
+            ip,
+            &PsnInfo {
+                header: header(),
+                system_name: Some("Stage tracking".to_string()),
+                trackers: vec![PsnTrackerName {
+                    id: 1,
+                    name: "Lead".to_string(),
+                }],
+            },
+        );
+        for (ts, x) in [(0.0, 0.0), (0.02, 0.1), (0.04, 0.2), (1.04, 0.3)] {
+            trackers.add_data(
+                Some(ts),
+                ip,
+                &data(vec![PsnTracker {
+                    id: 1,
+                    position: Some([x, 2.0, 0.0]),
+                    speed: Some([3.0, 4.0, 0.0]),
+                    ..PsnTracker::default()
+                }]),
+            );
+        }
+        trackers.add_data(
+            Some(0.5),
+            ip,
+            &data(vec![PsnTracker {
+                id: 2,
+                ..PsnTracker::default()
+            }]),
+        );
+
+        let summaries = trackers.finish();
+        assert_eq!(summaries.len(), 2);
+        let lead = &summaries[0];
+        assert_eq!(lead.tracker_id, 1);
+        assert_eq!(lead.name.as_deref(), Some("Lead"));
+        assert_eq!(lead.system_name.as_deref(), Some("Stage tracking"));
+        assert_eq!(lead.updates, 4);
+        assert_eq!(lead.update_rate_hz, Some(2.885));
+        assert_eq!(lead.last_position, Some([0.3, 2.0, 0.0]));
+        assert_eq!(lead.max_speed, Some(5.0));
+        assert_eq!(lead.dropouts.len(), 1);
+        assert_eq!(lead.dropouts[0].start, 0.04);
+        assert_eq!(lead.dropouts[0].duration_s, 1.0);
+        let other = &summaries[1];
+        assert_eq!(other.name, None);
+        assert_eq!((other.updates, other.update_rate_hz), (1, None));
+        assert_eq!(other.max_speed, None);
+    }
+}
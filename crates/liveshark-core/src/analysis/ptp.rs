@@ -0,0 +1,262 @@
+//! PTP domain summaries from Announce messages.
+//!
+//! Every master port announces the grandmaster it follows; slaves pick the
+//! best announced grandmaster by comparing priority 1, clock class, clock
+//! accuracy, variance, priority 2, then identity. The tracker replays that
+//! comparison at each Announce over the grandmasters still heard within the
+//! announce receipt timeout (three announce intervals), so a change of best
+//! grandmaster is reported when a better clock appears, the current one
+//! degrades, or it falls silent and another takes over.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::IpAddr;
+
+use super::{AVERAGE_SCALE, DURATION_SCALE, round_to};
+use crate::{
+    PtpAnnounce, PtpClockIdentity, PtpDomainSummary, PtpGrandmasterSummary, PtpMasterChange,
+    PtpMessage,
+};
+
+/// Announce intervals without a message before a grandmaster is dropped.
+const ANNOUNCE_RECEIPT_TIMEOUT: f64 = 3.0;
+
+#[derive(Debug)]
+struct Grandmaster {
+    announce: PtpAnnounce,
+    log_announce_interval: i8,
+    announced_by: BTreeSet<String>,
+    announces: u64,
+    first_seen: Option<f64>,
+    last_seen: Option<f64>,
+    last_by_port: BTreeMap<(PtpClockIdentity, u16), f64>,
+    gap_total: f64,
+    gaps: u64,
+    max_gap: Option<f64>,
+}
+
+impl Grandmaster {
+    /// Whether an Announce was heard within the receipt timeout before `ts`.
+    fn is_alive(&self, ts: Option<f64>) -> bool {
+        match (ts, self.last_seen) {
+            (Some(now), Some(last)) => {
+                now - last
+                    <= ANNOUNCE_RECEIPT_TIMEOUT * 2f64.powi(i32::from(self.log_announce_interval))
+            }
+            _ => true,
+        }
+    }
+
+    /// Best master clock comparison key; lower wins.
+    fn rank(&self, identity: PtpClockIdentity) -> (u8, u8, u8, u16, u8, PtpClockIdentity) {
+        (
+            self.announce.priority1,
+            self.announce.clock_class,
+            self.announce.clock_accuracy,
+            self.announce.offset_scaled_log_variance,
+            self.announce.priority2,
+            identity,
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+struct Domain {
+    messages: u64,
+    grandmasters: BTreeMap<PtpClockIdentity, Grandmaster>,
+    best: Option<PtpClockIdentity>,
+    master_changes: Vec<PtpMasterChange>,
+}
+
+/// PTP domains, keyed by domain number.
+#[derive(Debug, Default)]
+pub(crate) struct PtpTracker {
+    domains: BTreeMap<u8, Domain>,
+}
+
+impl PtpTracker {
+    /// Record a PTP message sent from `src_ip`.
+    pub(crate) fn add(&mut self, ts: Option<f64>, src_ip: IpAddr, message: &PtpMessage) {
+        let domain = self.domains.entry(message.header.domain).or_default();
+        domain.messages += 1;
+        let Some(announce) = message.announce else {
+            return;
+        };
+        let grandmaster = domain
+            .grandmasters
+            .entry(announce.grandmaster)
+            .or_insert_with(|| Grandmaster {
+                announce,
+                log_announce_interval: message.header.log_message_interval,
+                announced_by: BTreeSet::new(),
+                announces: 0,
+                first_seen: None,
+                last_seen: None,
+                last_by_port: BTreeMap::new(),
+                gap_total: 0.0,
+                gaps: 0,
+                max_gap: None,
+            });
+        grandmaster.announce = announce;
+        grandmaster.log_announce_interval = message.header.log_message_interval;
+        grandmaster.announced_by.insert(src_ip.to_string());
+        grandmaster.announces += 1;
+        if let Some(ts) = ts {
+            let port = (message.header.source_clock, message.header.source_port);
+            if let Some(previous) = grandmaster.last_by_port.insert(port, ts) {
+                let gap = ts - previous;
+                grandmaster.gap_total += gap;
+                grandmaster.gaps += 1;
+                grandmaster.max_gap = Some(grandmaster.max_gap.map_or(gap, |max| max.max(gap)));
+            }
+            grandmaster.first_seen.get_or_insert(ts);
+            grandmaster.last_seen = Some(ts);
+        }
+
+        let best = domain
+            .grandmasters
+            .iter()
+            .filter(|(_, grandmaster)| grandmaster.is_alive(ts))
+            .min_by_key(|(identity, grandmaster)| grandmaster.rank(**identity))
+            .map(|(identity, _)| *identity);
+        if best != domain.best {
+            if let (Some(previous), Some(grandmaster)) = (domain.best, best) {
+                domain.master_changes.push(PtpMasterChange {
+                    timestamp: ts,
+                    previous: previous.to_string(),
+                    grandmaster: grandmaster.to_string(),
+                });
+            }
+            domain.best = best;
+        }
+    }
+
+    /// Domains with at least one message, sorted by domain number.
+    pub(crate) fn finish(self) -> Vec<PtpDomainSummary> {
+        self.domains
+            .into_iter()
+            .map(|(domain, state)| PtpDomainSummary {
+                domain,
+                messages: state.messages,
+                grandmasters: state
+                    .grandmasters
+                    .into_iter()
+                    .map(|(identity, grandmaster)| PtpGrandmasterSummary {
+                        identity: identity.to_string(),
+                        announced_by: grandmaster.announced_by.into_iter().collect(),
+                        priority1: grandmaster.announce.priority1,
+                        clock_class: grandmaster.announce.clock_class,
+                        clock_accuracy: grandmaster.announce.clock_accuracy,
+                        priority2: grandmaster.announce.priority2,
+                        steps_removed: grandmaster.announce.steps_removed,
+                        announces: grandmaster.announces,
+                        log_announce_interval: grandmaster.log_announce_interval,
+                        announce_interval_s: (grandmaster.gaps > 0).then(|| {
+                            round_to(
+                                grandmaster.gap_total / grandmaster.gaps as f64,
+                                AVERAGE_SCALE,
+                            )
+                        }),
+                        max_announce_gap_s: grandmaster
+                            .max_gap
+                            .map(|gap| round_to(gap, DURATION_SCALE)),
+                        first_seen: grandmaster.first_seen,
+                        last_seen: grandmaster.last_seen,
+                    })
+                    .collect(),
+                master_changes: state.master_changes,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use super::PtpTracker;
+    use crate::{PtpAnnounce, PtpClockIdentity, PtpHeader, PtpMessage};
+
+    fn announce(sender: u8, priority1: u8, clock_class: u8) -> PtpMessage {
+        PtpMessage {
+            header: PtpHeader {
+                message_type: 0xb,
+                message_length: 64,
+                domain: 0,
+                flags: 0,
+                source_clock: PtpClockIdentity([sender; 8]),
+                source_port: 1,
+                sequence_id: 0,
+                log_message_interval: 0,
+            },
+            announce: Some(PtpAnnounce {
+                grandmaster: PtpClockIdentity([sender; 8]),
+                priority1,
+                clock_class,
+                clock_accuracy: 0x21,
+                offset_scaled_log_variance: 0x4e5d,
+                priority2: 128,
+                steps_removed: 0,
+                time_source: 0x20,
+                current_utc_offset: 37,
+            }),
+        }
+    }
+
+    #[test]
+    fn best_master_changes_on_better_clock_and_on_timeout() {
+        let mut tracker = PtpTracker::default();
+        let a = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let b = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2));
+        // A announces every second; B (better priority 1) joins at 2.5 s.
+        for ts in [0.0, 1.0, 2.0] {
+            tracker.add(Some(ts), a, &announce(1, 128, 248));
+        }
+        tracker.add(Some(2.5), b, &announce(2, 100, 248));
+        tracker.add(Some(3.0), a, &announce(1, 128, 248));
+        // B falls silent after 3.5 s; A takes over once 3 s have passed.
+        tracker.add(Some(3.5), b, &announce(2, 100, 248));
+        tracker.add(Some(4.0), a, &announce(1, 128, 248));
+        tracker.add(Some(7.0), a, &announce(1, 128, 248));
+
+        let domains = tracker.finish();
+        assert_eq!(domains.len(), 1);
+        let domain = &domains[0];
+        assert_eq!(domain.messages, 8);
+        assert_eq!(domain.grandmasters.len(), 2);
+        let first = &domain.grandmasters[0];
+        assert_eq!(first.identity, "01:01:01:01:01:01:01:01");
+        assert_eq!(first.announced_by, vec!["10.0.0.1".to_string()]);
+        assert_eq!(first.announces, 6);
+        assert_eq!(first.log_announce_interval, 0);
+        assert_eq!(first.announce_interval_s, Some(1.4));
+        assert_eq!(first.max_announce_gap_s, Some(3.0));
+        assert_eq!(domain.grandmasters[1].announce_interval_s, Some(1.0));
+
+        let changes: Vec<_> = domain
+            .master_changes
+            .iter()
+            .map(|change| (change.timestamp, change.grandmaster.as_str()))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                (Some(2.5), "02:02:02:02:02:02:02:02"),
+                (Some(7.0), "01:01:01:01:01:01:01:01"),
+            ]
+        );
+        assert_eq!(domain.master_changes[0].previous, first.identity);
+    }
+
+    #[test]
+    fn other_messages_only_count_towards_the_domain() {
+        let mut tracker = PtpTracker::default();
+        let mut sync = announce(1, 128, 6);
+        sync.header.message_type = 0;
+        sync.header.domain = 4;
+        sync.announce = None;
+        tracker.add(None, IpAddr::V4(Ipv4Addr::LOCALHOST), &sync);
+        let domains = tracker.finish();
+        assert_eq!((domains[0].domain, domains[0].messages), (4, 1));
+        assert!(domains[0].grandmasters.is_empty());
+    }
+}
@@ -0,0 +1,362 @@
+//! RDM over Art-Net: responder inventory and transaction statistics.
+//!
+//! Nodes publish the responders found on each port in ArtTodData; the
+//! responses they relay in ArtRdm name responders too, including ones a
+//! stale table misses. Requests are matched with their responses by
+//! universe, the two UIDs, and the transaction number, so a responder that
+//! never answers shows up as unanswered requests.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::net::IpAddr;
+
+use crate::latency::distribution;
+use crate::protocols::artnet::error::ArtNetError;
+use crate::protocols::{decode_artrdm, decode_arttoddata, decode_arttodrequest};
+use crate::{
+    ArtRdm, ArtTodData, ArtTodRequest, PortAddress, RdmPortInventory, RdmTransactions, RdmUid,
+};
+
+const COMMAND_CLASS_DISCOVERY: u8 = 0x10;
+const COMMAND_CLASS_GET: u8 = 0x20;
+const COMMAND_CLASS_SET: u8 = 0x30;
+const RESPONSE_ACK: u8 = 0;
+const RESPONSE_ACK_TIMER: u8 = 1;
+const RESPONSE_NACK_REASON: u8 = 2;
+const RESPONSE_ACK_OVERFLOW: u8 = 3;
+
+/// RDM packet carried by Art-Net.
+#[derive(Debug)]
+pub(crate) enum RdmPacket {
+    TodRequest(ArtTodRequest),
+    TodData(ArtTodData),
+    Rdm(ArtRdm),
+}
+
+impl RdmPacket {
+    /// Art-Net packet name.
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            RdmPacket::TodRequest(_) => "ArtTodRequest",
+            RdmPacket::TodData(_) => "ArtTodData",
+            RdmPacket::Rdm(_) => "ArtRdm",
+        }
+    }
+
+    /// Art-Net protocol revision of the packet.
+    pub(crate) fn protocol_version(&self) -> u16 {
+        match self {
+            RdmPacket::TodRequest(request) => request.protocol_version,
+            RdmPacket::TodData(tod) => tod.protocol_version,
+            RdmPacket::Rdm(rdm) => rdm.protocol_version,
+        }
+    }
+
+    /// Universe of the port the packet belongs to; a table request may name
+    /// several.
+    pub(crate) fn universe(&self) -> Option<u16> {
+        match self {
+            RdmPacket::TodRequest(_) => None,
+            RdmPacket::TodData(tod) => Some(tod.universe),
+            RdmPacket::Rdm(rdm) => Some(rdm.universe),
+        }
+    }
+}
+
+/// Decode an ArtTodRequest, ArtTodData, or ArtRdm payload; `None` for any
+/// other payload.
+pub(crate) fn decode_rdm(payload: &[u8]) -> Option<Result<RdmPacket, ArtNetError>> {
+    if let Some(request) = decode_arttodrequest(payload).transpose() {
+        return Some(request.map(RdmPacket::TodRequest));
+    }
+    if let Some(tod) = decode_arttoddata(payload).transpose() {
+        return Some(tod.map(RdmPacket::TodData));
+    }
+    decode_artrdm(payload)
+        .transpose()
+        .map(|rdm| rdm.map(RdmPacket::Rdm))
+}
+
+#[derive(Debug, Default)]
+struct Port {
+    port: Option<u8>,
+    bind_index: Option<u8>,
+    tod_uid_total: Option<u16>,
+    tod_packets: u64,
+    tod: BTreeSet<RdmUid>,
+    responders: BTreeSet<RdmUid>,
+}
+
+#[derive(Debug, Default)]
+struct Universe {
+    tod_requests: u64,
+    requests: u64,
+    responses: u64,
+    discovery: u64,
+    get: u64,
+    set: u64,
+    acks: u64,
+    ack_timers: u64,
+    nacks: u64,
+    ack_overflows: u64,
+    unanswered: u64,
+    response_times: Vec<f64>,
+}
+
+/// Request awaiting its response: universe, controller, responder, and
+/// transaction number.
+type TransactionKey = (u16, RdmUid, RdmUid, u8);
+
+/// RDM inventory per node port and transactions per universe.
+#[derive(Debug, Default)]
+pub(crate) struct RdmTracker {
+    ports: BTreeMap<(String, u16), Port>,
+    universes: BTreeMap<u16, Universe>,
+    /// Command class and timestamp of each pending request.
+    pending: HashMap<TransactionKey, (u8, Option<f64>)>,
+}
+
+impl RdmTracker {
+    /// Record an RDM packet sent from `src_ip`.
+    pub(crate) fn add(&mut self, ts: Option<f64>, src_ip: IpAddr, packet: RdmPacket) {
+        match packet {
+            RdmPacket::TodRequest(request) => {
+                for universe in request.universes {
+                    self.universes.entry(universe).or_default().tod_requests += 1;
+                }
+            }
+            RdmPacket::TodData(tod) => {
+                let port = self
+                    .ports
+                    .entry((src_ip.to_string(), tod.universe))
+                    .or_default();
+                // Block 0 starts a new table.
+                if tod.block_count == 0 {
+                    port.tod.clear();
+                }
+                port.port = Some(tod.port);
+                port.bind_index = Some(tod.bind_index);
+                port.tod_uid_total = Some(tod.uid_total);
+                port.tod_packets += 1;
+                port.tod.extend(tod.uids);
+            }
+            RdmPacket::Rdm(rdm) => self.add_message(ts, src_ip, &rdm),
+        }
+    }
+
+    fn add_message(&mut self, ts: Option<f64>, src_ip: IpAddr, rdm: &ArtRdm) {
+        let message = &rdm.message;
+        let universe = self.universes.entry(rdm.universe).or_default();
+        if !message.is_response() {
+            universe.requests += 1;
+            match message.command_class {
+                COMMAND_CLASS_DISCOVERY => universe.discovery += 1,
+                COMMAND_CLASS_GET => universe.get += 1,
+                COMMAND_CLASS_SET => universe.set += 1,
+                _ => {}
+            }
+            if !message.destination.is_broadcast() {
+                let key = (
+                    rdm.universe,
+                    message.source,
+                    message.destination,
+                    message.transaction,
+                );
+                if self
+                    .pending
+                    .insert(key, (message.command_class, ts))
+                    .is_some()
+                {
+                    universe.unanswered += 1;
+                }
+            }
+            return;
+        }
+
+        universe.responses += 1;
+        match message.port_or_response {
+            RESPONSE_ACK => universe.acks += 1,
+            RESPONSE_ACK_TIMER => universe.ack_timers += 1,
+            RESPONSE_NACK_REASON => universe.nacks += 1,
+            RESPONSE_ACK_OVERFLOW => universe.ack_overflows += 1,
+            _ => {}
+        }
+        let key = (
+            rdm.universe,
+            message.destination,
+            message.source,
+            message.transaction,
+        );
+        if let Some(&(command_class, request_ts)) = self.pending.get(&key) {
+            if command_class + 1 == message.command_class {
+                self.pending.remove(&key);
+                if let (Some(request_ts), Some(ts)) = (request_ts, ts) {
+                    universe.response_times.push((ts - request_ts).max(0.0));
+                }
+            }
+        }
+        self.ports
+            .entry((src_ip.to_string(), rdm.universe))
+            .or_default()
+            .responders
+            .insert(message.source);
+    }
+
+    /// Inventory sorted by node IP then universe, and transactions sorted
+    /// by universe.
+    pub(crate) fn finish(mut self) -> (Vec<RdmPortInventory>, Vec<RdmTransactions>) {
+        for (universe, ..) in self.pending.keys() {
+            if let Some(stats) = self.universes.get_mut(universe) {
+                stats.unanswered += 1;
+            }
+        }
+        let ports = self
+            .ports
+            .into_iter()
+            .map(|((node_ip, universe), port)| {
+                let unlisted = if port.tod_packets > 0 {
+                    port.responders
+                        .difference(&port.tod)
+                        .map(RdmUid::to_string)
+                        .collect()
+                } else {
+                    Vec::new()
+                };
+                RdmPortInventory {
+                    node_ip,
+                    universe,
+                    port_address: PortAddress::from_masked(universe),
+                    port: port.port,
+                    bind_index: port.bind_index,
+                    tod_uid_total: port.tod_uid_total,
+                    tod_packets: port.tod_packets,
+                    responders: port
+                        .tod
+                        .union(&port.responders)
+                        .map(RdmUid::to_string)
+                        .collect(),
+                    unlisted,
+                }
+            })
+            .collect();
+        let transactions = self
+            .universes
+            .into_iter()
+            .map(|(universe, stats)| RdmTransactions {
+                universe,
+                port_address: PortAddress::from_masked(universe),
+                tod_requests: stats.tod_requests,
+                requests: stats.requests,
+                responses: stats.responses,
+                discovery: stats.discovery,
+                get: stats.get,
+                set: stats.set,
+                acks: stats.acks,
+                ack_timers: stats.ack_timers,
+                nacks: stats.nacks,
+                ack_overflows: stats.ack_overflows,
+                unanswered: stats.unanswered,
+                response_time: distribution(stats.response_times),
+            })
+            .collect();
+        (ports, transactions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use super::{RdmPacket, RdmTracker};
+    use crate::{ArtRdm, ArtTodData, RdmMessage, RdmUid};
+
+    const CONTROLLER: RdmUid = RdmUid {
+        manufacturer: 0x4c53,
+        device: 1,
+    };
+
+    fn uid(device: u32) -> RdmUid {
+        RdmUid {
+            manufacturer: 0x7a70,
+            device,
+        }
+    }
+
+    fn rdm(source: RdmUid, destination: RdmUid, command_class: u8, transaction: u8) -> RdmPacket {
+        RdmPacket::Rdm(ArtRdm {
+            protocol_version: 14,
+            rdm_version: 1,
+            universe: 3,
+            command: 0,
+            message: RdmMessage {
+                destination,
+                source,
+                transaction,
+                port_or_response: 0,
+                message_count: 0,
+                sub_device: 0,
+                command_class,
+                parameter_id: 0x0060,
+                data_len: 0,
+            },
+        })
+    }
+
+    #[test]
+    fn requests_are_matched_with_their_responses() {
+        let mut tracker = RdmTracker::default();
+        let console = IpAddr::V4(Ipv4Addr::new(2, 0, 0, 1));
+        let node = IpAddr::V4(Ipv4Addr::new(2, 0, 0, 10));
+        tracker.add(Some(1.0), console, rdm(CONTROLLER, uid(1), 0x20, 0));
+        tracker.add(Some(1.004), node, rdm(uid(1), CONTROLLER, 0x21, 0));
+        // No answer from the second fixture.
+        tracker.add(Some(1.1), console, rdm(CONTROLLER, uid(2), 0x30, 1));
+        // A response to another transaction does not match.
+        tracker.add(Some(1.2), node, rdm(uid(2), CONTROLLER, 0x31, 7));
+        // Broadcasts expect no response.
+        tracker.add(Some(1.3), console, rdm(CONTROLLER, uid(u32::MAX), 0x30, 2));
+
+        let (ports, transactions) = tracker.finish();
+        let stats = &transactions[0];
+        assert_eq!((stats.requests, stats.responses), (3, 2));
+        assert_eq!((stats.get, stats.set, stats.discovery), (1, 2, 0));
+        assert_eq!(stats.acks, 2);
+        assert_eq!(stats.unanswered, 1);
+        let response_time = stats.response_time.as_ref().unwrap();
+        assert_eq!(response_time.max_ms, 4.0);
+        assert_eq!(ports.len(), 1);
+        assert_eq!(ports[0].node_ip, "2.0.0.10");
+        assert_eq!(
+            ports[0].responders,
+            vec!["7a70:00000001".to_string(), "7a70:00000002".to_string()]
+        );
+        assert!(ports[0].unlisted.is_empty());
+    }
+
+    #[test]
+    fn responders_missing_from_the_table_are_unlisted() {
+        let mut tracker = RdmTracker::default();
+        let node = IpAddr::V4(Ipv4Addr::new(2, 0, 0, 10));
+        tracker.add(
+            Some(0.0),
+            node,
+            RdmPacket::TodData(ArtTodData {
+                protocol_version: 14,
+                rdm_version: 1,
+                port: 2,
+                bind_index: 1,
+                universe: 3,
+                command_response: 0,
+                uid_total: 1,
+                block_count: 0,
+                uids: vec![uid(1)],
+            }),
+        );
+        tracker.add(Some(0.5), node, rdm(uid(5), CONTROLLER, 0x21, 0));
+
+        let (ports, _) = tracker.finish();
+        assert_eq!(ports[0].port, Some(2));
+        assert_eq!(ports[0].tod_uid_total, Some(1));
+        assert_eq!(ports[0].responders.len(), 2);
+        assert_eq!(ports[0].unlisted, vec!["7a70:00000005".to_string()]);
+    }
+}
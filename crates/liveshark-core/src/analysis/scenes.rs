@@ -0,0 +1,136 @@
+//! Cue/scene change detection.
+//!
+//! A console going to a new cue rewrites most of the live channels of a
+//! universe in one frame. Comparing consecutive frames of each source and
+//! flagging those where a large share of the active channels jumped gives a
+//! list of cue moments that operators can line up with the show's cue stack.
+//! Slow fades move every channel a little per frame and are not reported.
+
+use super::dmx::DmxFrame;
+use super::{AVERAGE_SCALE, round_to};
+use crate::SceneChange;
+
+/// Smallest change of one slot between consecutive frames counted as part
+/// of a scene change (about 10% of full scale); smaller steps are fades.
+const MIN_SLOT_STEP: u8 = 26;
+/// Fewest changed slots for a scene change, so a universe with a handful of
+/// live channels does not report every bump.
+pub const MIN_SCENE_CHANNELS: u16 = 4;
+/// Quiet period after a scene change during which the same source cannot
+/// report another one (seconds); a cue split over a few frames counts once.
+const SCENE_HOLDOFF_S: f64 = 1.0;
+
+/// Scene changes of one source, in frame order. `min_fraction` is the share
+/// of active slots (slots non-zero in any frame of the source) that must
+/// change together.
+pub(crate) fn detect_scene_changes(frames: &[DmxFrame], min_fraction: f64) -> Vec<SceneChange> {
+    let mut active = [false; 512];
+    for frame in frames {
+        for (slot, value) in active.iter_mut().zip(frame.slots.iter()) {
+            *slot |= *value != 0;
+        }
+    }
+    let active_channels = active.iter().filter(|slot| **slot).count() as u16;
+    if active_channels < MIN_SCENE_CHANNELS {
+        return Vec::new();
+    }
+
+    let mut changes: Vec<SceneChange> = Vec::new();
+    for pair in frames.windows(2) {
+        let (prev, cur) = (&pair[0], &pair[1]);
+        let Some(timestamp) = cur.timestamp else {
+            continue;
+        };
+        let mut changed = 0u16;
+        let mut total_step = 0u64;
+        for (before, after) in prev.slots.iter().zip(cur.slots.iter()) {
+            let step = before.abs_diff(*after);
+            if step >= MIN_SLOT_STEP {
+                changed += 1;
+                total_step += u64::from(step);
+            }
+        }
+        if changed < MIN_SCENE_CHANNELS
+            || f64::from(changed) < min_fraction * f64::from(active_channels)
+        {
+            continue;
+        }
+        let held_off = changes.last().is_some_and(|last| {
+            last.source_id == cur.source_id && timestamp - last.timestamp < SCENE_HOLDOFF_S
+        });
+        if held_off {
+            continue;
+        }
+        changes.push(SceneChange {
+            timestamp,
+            universe: cur.universe,
+            proto: cur.protocol,
+            source_id: cur.source_id.clone(),
+            changed_channels: changed,
+            active_channels,
+            magnitude: round_to(
+                total_step as f64 / (f64::from(changed) * f64::from(u8::MAX)),
+                AVERAGE_SCALE,
+            ),
+        });
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::detect_scene_changes;
+    use crate::Protocol;
+    use crate::analysis::dmx::DmxFrame;
+
+    fn frames(levels: &[(f64, [u8; 8])]) -> Vec<DmxFrame> {
+        levels
+            .iter()
+            .map(|(ts, head)| {
+                let mut slots = [0u8; 512];
+                slots[..8].copy_from_slice(head);
+                DmxFrame {
+                    universe: 1,
+                    timestamp: Some(*ts),
+                    source_id: "artnet:10.0.0.1:6454".to_string(),
+                    protocol: Protocol::ArtNet,
+                    slots,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn snap_to_new_look_is_reported_once() {
+        let frames = frames(&[
+            (0.0, [255, 255, 255, 255, 10, 10, 10, 10]),
+            (0.025, [255, 255, 255, 255, 10, 10, 10, 10]),
+            // Cue 2 snaps in over two frames; the second half is held off.
+            (0.05, [0, 0, 0, 0, 10, 10, 10, 10]),
+            (0.075, [0, 0, 0, 0, 200, 200, 200, 200]),
+            (2.0, [128, 128, 128, 128, 200, 200, 200, 200]),
+        ]);
+        let changes = detect_scene_changes(&frames, 0.5);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].timestamp, 0.05);
+        assert_eq!(changes[0].changed_channels, 4);
+        assert_eq!(changes[0].active_channels, 8);
+        assert_eq!(changes[0].magnitude, 1.0);
+        assert_eq!(changes[1].timestamp, 2.0);
+        assert_eq!(changes[1].magnitude, 0.502);
+    }
+
+    #[test]
+    fn fades_and_small_looks_are_ignored() {
+        let fade: Vec<(f64, [u8; 8])> = (0u8..50)
+            .map(|step| (f64::from(step) * 0.025, [step * 5; 8]))
+            .collect();
+        assert!(detect_scene_changes(&frames(&fade), 0.5).is_empty());
+
+        let two_channels = frames(&[
+            (0.0, [255, 255, 0, 0, 0, 0, 0, 0]),
+            (1.0, [0, 0, 0, 0, 0, 0, 0, 0]),
+        ]);
+        assert!(detect_scene_changes(&two_channels, 0.5).is_empty());
+    }
+}
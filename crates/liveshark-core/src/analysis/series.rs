@@ -0,0 +1,152 @@
+//! Per-second metric series (opt-in).
+//!
+//! Summaries reduce a capture to one value per metric; dashboards also want
+//! the time dimension. When `AnalyzerConfig::series` is set, universe and flow
+//! statistics are additionally bucketed by whole second and spread over one
+//! shared timeline starting at the capture's first second.
+
+use std::collections::BTreeMap;
+
+use super::{AVERAGE_SCALE, round_to};
+
+/// Longest timeline emitted (seconds): one day. Buckets past it are dropped so
+/// a bogus timestamp jump cannot inflate every series.
+pub const MAX_SERIES_SECONDS: usize = 86_400;
+
+/// What one DMX frame contributed to its universe.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct FrameSample {
+    /// Packets missing before this one, from the sequence number.
+    pub lost: u64,
+    /// Absolute change of inter-arrival time versus the previous frame (seconds).
+    pub jitter: Option<f64>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub(crate) struct UniverseSecond {
+    frames: u64,
+    lost: u64,
+    jitter_sum: f64,
+    jitter_samples: u64,
+}
+
+impl UniverseSecond {
+    pub(crate) fn add(&mut self, sample: FrameSample) {
+        self.frames += 1;
+        self.lost += sample.lost;
+        if let Some(jitter) = sample.jitter {
+            self.jitter_sum += jitter;
+            self.jitter_samples += 1;
+        }
+    }
+
+    pub(crate) fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    pub(crate) fn lost(&self) -> u64 {
+        self.lost
+    }
+
+    /// Mean jitter over the second in milliseconds, if any frame had one.
+    pub(crate) fn jitter_ms(&self) -> Option<f64> {
+        (self.jitter_samples > 0).then(|| {
+            round_to(
+                self.jitter_sum / self.jitter_samples as f64 * 1000.0,
+                AVERAGE_SCALE,
+            )
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub(crate) struct FlowSecond {
+    pub packets: u64,
+    pub bytes: u64,
+}
+
+/// Whole second a timestamp falls in.
+pub(crate) fn second_of(ts: f64) -> i64 {
+    ts.floor() as i64
+}
+
+/// Shared timeline of a capture: first second and number of buckets.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Timeline {
+    pub origin: i64,
+    pub len: usize,
+}
+
+impl Timeline {
+    /// Timeline covering `[first_ts, last_ts]`, capped at [`MAX_SERIES_SECONDS`].
+    pub(crate) fn new(first_ts: f64, last_ts: f64) -> Self {
+        let origin = second_of(first_ts);
+        let span = second_of(last_ts).saturating_sub(origin).max(0) as u64 + 1;
+        Self {
+            origin,
+            len: usize::try_from(span)
+                .map_or(MAX_SERIES_SECONDS, |span| span.min(MAX_SERIES_SECONDS)),
+        }
+    }
+
+    /// One value per second of the timeline; seconds without data use
+    /// `T::default()`.
+    pub(crate) fn spread<T: Default, U>(
+        &self,
+        seconds: &BTreeMap<i64, T>,
+        value: impl Fn(&T) -> U,
+    ) -> Vec<U> {
+        let empty = T::default();
+        (0..self.len)
+            .map(|offset| {
+                let second = self.origin + offset as i64;
+                value(seconds.get(&second).unwrap_or(&empty))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{FrameSample, MAX_SERIES_SECONDS, Timeline, UniverseSecond};
+
+    #[test]
+    fn timeline_spreads_buckets_and_fills_gaps() {
+        let timeline = Timeline::new(10.4, 13.2);
+        assert_eq!((timeline.origin, timeline.len), (10, 4));
+
+        let mut seconds = BTreeMap::new();
+        seconds.insert(10, 5u64);
+        seconds.insert(13, 7u64);
+        assert_eq!(timeline.spread(&seconds, |count| *count), vec![5, 0, 0, 7]);
+    }
+
+    #[test]
+    fn timeline_is_capped() {
+        let timeline = Timeline::new(0.0, 1.0e9);
+        assert_eq!(timeline.len, MAX_SERIES_SECONDS);
+    }
+
+    #[test]
+    fn universe_second_averages_jitter() {
+        let mut second = UniverseSecond::default();
+        second.add(FrameSample {
+            lost: 2,
+            jitter: None,
+        });
+        second.add(FrameSample {
+            lost: 0,
+            jitter: Some(0.002),
+        });
+        second.add(FrameSample {
+            lost: 1,
+            jitter: Some(0.004),
+        });
+        assert_eq!(second.frames(), 3);
+        assert_eq!(second.lost(), 3);
+        assert_eq!(second.jitter_ms(), Some(3.0));
+        assert_eq!(UniverseSecond::default().jitter_ms(), None);
+    }
+}
@@ -0,0 +1,136 @@
+//! Frame-rate stability of a source.
+//!
+//! A steady sender spaces its frames evenly; the coefficient of variation
+//! (standard deviation over mean) of its inter-frame intervals measures how
+//! far it strays, independently of its rate. The capture-wide value hides a
+//! single bad second, so the worst one-second window is kept as well, and
+//! both fold into one score comparable across senders.
+
+use super::series::second_of;
+use super::{AVERAGE_SCALE, round_to};
+use crate::FrameRateStability;
+
+/// Fewest intervals for which a coefficient of variation is computed.
+const MIN_INTERVALS: u64 = 3;
+
+/// Running sums of inter-frame intervals.
+#[derive(Debug, Default, Clone, Copy)]
+struct Moments {
+    count: u64,
+    sum: f64,
+    sum_sq: f64,
+}
+
+impl Moments {
+    fn add(&mut self, iat: f64) {
+        self.count += 1;
+        self.sum += iat;
+        self.sum_sq += iat * iat;
+    }
+
+    fn cv(&self) -> Option<f64> {
+        if self.count < MIN_INTERVALS || self.sum <= 0.0 {
+            return None;
+        }
+        let count = self.count as f64;
+        let mean = self.sum / count;
+        let variance = (self.sum_sq / count - mean * mean).max(0.0);
+        Some(variance.sqrt() / mean)
+    }
+}
+
+/// Inter-frame interval statistics of one source of a universe.
+#[derive(Debug, Default)]
+pub(crate) struct IatStability {
+    overall: Moments,
+    window: Moments,
+    window_second: Option<i64>,
+    /// `(window start, cv)` of the least regular second so far.
+    worst: Option<(f64, f64)>,
+}
+
+impl IatStability {
+    /// Count the interval `iat` ending with the frame at `ts`; negative
+    /// intervals (timestamps going backwards) are ignored.
+    pub(crate) fn add(&mut self, ts: f64, iat: f64) {
+        if iat < 0.0 {
+            return;
+        }
+        let second = second_of(ts);
+        if self.window_second != Some(second) {
+            self.close_window();
+            self.window_second = Some(second);
+        }
+        self.overall.add(iat);
+        self.window.add(iat);
+    }
+
+    fn close_window(&mut self) {
+        if let (Some(second), Some(cv)) = (self.window_second, self.window.cv()) {
+            if self.worst.is_none_or(|(_, worst)| cv > worst) {
+                self.worst = Some((second as f64, cv));
+            }
+        }
+        self.window = Moments::default();
+    }
+
+    /// Stability of the source; `None` with fewer than `MIN_INTERVALS`
+    /// intervals overall.
+    pub(crate) fn finish(mut self) -> Option<FrameRateStability> {
+        self.close_window();
+        let cv = self.overall.cv()?;
+        let worst_cv = self.worst.map_or(cv, |(_, worst)| worst);
+        Some(FrameRateStability {
+            score: round_to(
+                100.0 * (1.0 - ((cv + worst_cv) / 2.0).min(1.0)),
+                AVERAGE_SCALE,
+            ),
+            iat_cv: round_to(cv, AVERAGE_SCALE),
+            worst_window_start: self.worst.map(|(start, _)| start),
+            worst_window_cv: self.worst.map(|(_, worst)| round_to(worst, AVERAGE_SCALE)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IatStability;
+
+    fn stability(times: &[f64]) -> Option<crate::FrameRateStability> {
+        let mut stats = IatStability::default();
+        for pair in times.windows(2) {
+            stats.add(pair[1], pair[1] - pair[0]);
+        }
+        stats.finish()
+    }
+
+    #[test]
+    fn evenly_spaced_frames_score_full_marks() {
+        let times: Vec<f64> = (0..200).map(|step| f64::from(step) * 0.025).collect();
+        let stability = stability(&times).expect("stability");
+        assert_eq!(stability.iat_cv, 0.0);
+        assert_eq!(stability.score, 100.0);
+    }
+
+    #[test]
+    fn one_ragged_second_is_the_worst_window() {
+        let mut times: Vec<f64> = (0..120).map(|step| f64::from(step) * 0.025).collect();
+        // Alternate 10 ms and 40 ms intervals from 3 s on.
+        let mut ts = 3.0;
+        for step in 0..40 {
+            ts += if step % 2 == 0 { 0.01 } else { 0.04 };
+            times.push(ts);
+        }
+        let stability = stability(&times).expect("stability");
+        assert_eq!(stability.worst_window_start, Some(3.0));
+        let worst_cv = stability.worst_window_cv.expect("worst window");
+        assert!(worst_cv > 0.5);
+        assert!(stability.iat_cv < worst_cv);
+        assert!(stability.score < 100.0);
+    }
+
+    #[test]
+    fn too_few_frames_have_no_score() {
+        assert!(stability(&[0.0, 0.025, 0.05]).is_none());
+    }
+}
@@ -0,0 +1,206 @@
+//! Sync-to-data latency of synchronized sACN.
+//!
+//! A synchronized source sends the data packets of its universes with a
+//! sync address, and receivers hold them until the source's next
+//! synchronization packet on that address. The time each data packet waits
+//! is the latency the synchronization adds to the output; packets no
+//! synchronization packet released within the timeout were never output in
+//! sync. Results are kept per sync address and, within it, per universe.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::latency::distribution;
+use crate::{SacnSync, SyncLatency, UniverseSyncLatency};
+
+/// Longest wait (seconds) of a data packet for its synchronization packet,
+/// the E1.31 network data loss timeout after which receivers stop
+/// waiting for synchronization.
+const SYNC_TIMEOUT_S: f64 = 2.5;
+
+#[derive(Debug, Default)]
+struct Universe {
+    latencies: Vec<f64>,
+    unsynced: u64,
+}
+
+#[derive(Debug, Default)]
+struct SyncAddress {
+    universes: BTreeMap<u16, Universe>,
+    sync_packets: u64,
+    /// Timestamp and universe of the data packets waiting for a
+    /// synchronization packet, per source CID.
+    pending: HashMap<String, Vec<(f64, u16)>>,
+}
+
+impl SyncAddress {
+    /// Count the packets of `cid` waiting longer than the timeout at `ts`
+    /// as unsynced.
+    fn expire(&mut self, cid: &str, ts: f64) {
+        if let Some(pending) = self.pending.get_mut(cid) {
+            let universes = &mut self.universes;
+            pending.retain(|&(data_ts, universe)| {
+                let expired = ts - data_ts > SYNC_TIMEOUT_S;
+                if expired {
+                    universes.entry(universe).or_default().unsynced += 1;
+                }
+                !expired
+            });
+        }
+    }
+}
+
+/// Sync-to-data latency per sync address.
+#[derive(Debug, Default)]
+pub(crate) struct SyncTracker {
+    addresses: BTreeMap<u16, SyncAddress>,
+    last_ts: Option<f64>,
+}
+
+impl SyncTracker {
+    /// Record a data packet of `cid` for `universe` naming `sync_address`.
+    pub(crate) fn add_data(
+        &mut self,
+        ts: Option<f64>,
+        cid: &str,
+        universe: u16,
+        sync_address: u16,
+    ) {
+        let address = self.addresses.entry(sync_address).or_default();
+        address.universes.entry(universe).or_default();
+        let Some(ts) = ts else {
+            return;
+        };
+        self.last_ts = Some(self.last_ts.map_or(ts, |last| last.max(ts)));
+        address.expire(cid, ts);
+        address
+            .pending
+            .entry(cid.to_string())
+            .or_default()
+            .push((ts, universe));
+    }
+
+    /// Record a synchronization packet, releasing the data packets its
+    /// source sent to the same sync address.
+    pub(crate) fn add_sync(&mut self, ts: Option<f64>, sync: &SacnSync) {
+        let address = self.addresses.entry(sync.sync_address).or_default();
+        address.sync_packets += 1;
+        let Some(ts) = ts else {
+            return;
+        };
+        self.last_ts = Some(self.last_ts.map_or(ts, |last| last.max(ts)));
+        address.expire(&sync.cid, ts);
+        if let Some(pending) = address.pending.get_mut(&sync.cid) {
+            let universes = &mut address.universes;
+            pending.retain(|&(data_ts, universe)| {
+                let released = data_ts <= ts;
+                if released {
+                    universes
+                        .entry(universe)
+                        .or_default()
+                        .latencies
+                        .push(ts - data_ts);
+                }
+                !released
+            });
+        }
+    }
+
+    /// Per-address results, sorted by sync address; packets still waiting
+    /// at the end of the capture count as unsynced once past the timeout.
+    pub(crate) fn finish(self) -> Vec<SyncLatency> {
+        let last_ts = self.last_ts;
+        self.addresses
+            .into_iter()
+            .map(|(sync_address, mut address)| {
+                if let Some(last_ts) = last_ts {
+                    let cids: Vec<String> = address.pending.keys().cloned().collect();
+                    for cid in cids {
+                        address.expire(&cid, last_ts);
+                    }
+                }
+                let mut latencies = Vec::new();
+                let mut unsynced = 0;
+                let per_universe: Vec<UniverseSyncLatency> = address
+                    .universes
+                    .into_iter()
+                    .map(|(universe, stats)| {
+                        latencies.extend_from_slice(&stats.latencies);
+                        unsynced += stats.unsynced;
+                        UniverseSyncLatency {
+                            universe,
+                            synced_frames: stats.latencies.len() as u64,
+                            unsynced_frames: stats.unsynced,
+                            latency: distribution(stats.latencies),
+                        }
+                    })
+                    .collect();
+                SyncLatency {
+                    sync_address,
+                    universes: per_universe.iter().map(|stats| stats.universe).collect(),
+                    sync_packets: address.sync_packets,
+                    synced_frames: latencies.len() as u64,
+                    unsynced_frames: unsynced,
+                    latency: distribution(latencies),
+                    per_universe,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SyncTracker;
+    use crate::SacnSync;
+
+    fn sync(cid: &str) -> SacnSync {
+        SacnSync {
+            cid: cid.to_string(),
+            sequence: 0,
+            sync_address: 7000,
+        }
+    }
+
+    #[test]
+    fn sync_packet_releases_pending_data_of_its_source() {
+        let mut tracker = SyncTracker::default();
+        for step in 0..10 {
+            let ts = f64::from(step) * 0.025;
+            tracker.add_data(Some(ts), "console", 1, 7000);
+            tracker.add_data(Some(ts + 0.001), "console", 2, 7000);
+            // Another source's sync on the same address releases nothing.
+            tracker.add_sync(Some(ts + 0.0015), &sync("other"));
+            tracker.add_sync(Some(ts + 0.002), &sync("console"));
+        }
+        let results = tracker.finish();
+        assert_eq!(results.len(), 1);
+        let result = &results[0];
+        assert_eq!(result.universes, vec![1, 2]);
+        assert_eq!(result.sync_packets, 20);
+        assert_eq!(result.synced_frames, 20);
+        assert_eq!(result.unsynced_frames, 0);
+        let latency = result.latency.as_ref().expect("latency");
+        assert_eq!(latency.min_ms, 1.0);
+        assert_eq!(latency.max_ms, 2.0);
+        let per_universe = &result.per_universe;
+        assert_eq!(per_universe.len(), 2);
+        assert_eq!(per_universe[0].universe, 1);
+        assert_eq!(per_universe[0].synced_frames, 10);
+        assert_eq!(per_universe[0].latency.as_ref().unwrap().max_ms, 2.0);
+        assert_eq!(per_universe[1].latency.as_ref().unwrap().max_ms, 1.0);
+    }
+
+    #[test]
+    fn data_never_synced_within_the_timeout_is_counted() {
+        let mut tracker = SyncTracker::default();
+        tracker.add_data(Some(0.0), "console", 1, 7000);
+        tracker.add_data(Some(1.0), "console", 1, 7000);
+        tracker.add_sync(Some(3.0), &sync("console"));
+        tracker.add_data(Some(4.0), "console", 1, 7000);
+        tracker.add_data(Some(7.0), "console", 1, 7000);
+        let results = tracker.finish();
+        assert_eq!(results[0].synced_frames, 1);
+        assert_eq!(results[0].unsynced_frames, 2);
+        assert_eq!(results[0].latency.as_ref().unwrap().p50_ms, 2000.0);
+    }
+}
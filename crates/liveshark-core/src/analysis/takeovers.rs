@@ -0,0 +1,224 @@
+//! Controller failover and takeover events.
+//!
+//! A universe is controlled by one source at a time: the incumbent keeps
+//! control until it falls silent for longer than the source loss timeout
+//! or, for sACN, until a live source outranks it. The frames of all sources
+//! are replayed in time order and every change of controller is reported,
+//! with the hole left in the output when the old controller stopped, so a
+//! backup console's failover can be audited from the capture.
+
+use super::dmx::{DmxFrame, FrameWrite};
+use super::{AVERAGE_SCALE, round_to};
+use crate::{Protocol, Takeover, TakeoverCause};
+
+/// Source loss timeout (seconds, ANSI E1.31 network data loss), applied to
+/// both protocols: a source silent for longer no longer holds control.
+pub(super) const SOURCE_TIMEOUT_S: f64 = 2.5;
+/// Longest gap (seconds) between the old controller's last frame and the
+/// new one's first frame still counted as a takeover; longer silences are
+/// a universe going dark and coming back, not a failover.
+const MAX_TAKEOVER_GAP_S: f64 = 10.0;
+
+struct Source<'a> {
+    id: &'a str,
+    /// Timestamps of the source's frames, sorted.
+    times: Vec<f64>,
+    last: Option<f64>,
+    priority: u8,
+}
+
+/// Takeovers of one universe; `sources` holds each source's frames and
+/// writes in arrival order. Empty for a universe with a single source.
+pub(crate) fn detect_takeovers<'a>(
+    protocol: Protocol,
+    sources: impl IntoIterator<Item = (&'a [DmxFrame], &'a [FrameWrite])>,
+) -> Vec<Takeover> {
+    let mut universe = None;
+    let mut states: Vec<Source<'a>> = Vec::new();
+    let mut events: Vec<(f64, &'a str, u8)> = Vec::new();
+    for (frames, writes) in sources {
+        let Some(first) = frames.first() else {
+            continue;
+        };
+        universe = Some(first.universe);
+        let mut times = Vec::with_capacity(frames.len());
+        for (frame, write) in frames.iter().zip(writes) {
+            if let Some(ts) = frame.timestamp {
+                times.push(ts);
+                events.push((ts, &first.source_id, write.priority.unwrap_or_default()));
+            }
+        }
+        times.sort_by(f64::total_cmp);
+        states.push(Source {
+            id: &first.source_id,
+            times,
+            last: None,
+            priority: 0,
+        });
+    }
+    let Some(universe) = universe.filter(|_| states.len() >= 2) else {
+        return Vec::new();
+    };
+    states.sort_by(|a, b| a.id.cmp(b.id));
+    events.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(b.1)));
+
+    let mut takeovers = Vec::new();
+    let mut controller: Option<usize> = None;
+    for (ts, id, priority) in events {
+        let Ok(sender) = states.binary_search_by(|state| state.id.cmp(id)) else {
+            continue;
+        };
+        states[sender].last = Some(ts);
+        states[sender].priority = priority;
+        let live =
+            |state: &Source<'_>| state.last.is_some_and(|last| ts - last <= SOURCE_TIMEOUT_S);
+        let Some(highest) = states
+            .iter()
+            .filter(|state| live(state))
+            .map(|state| state.priority)
+            .max()
+        else {
+            continue;
+        };
+        let Some(old) = controller else {
+            controller = Some(sender);
+            continue;
+        };
+        let old_live = live(&states[old]);
+        if old_live && states[old].priority == highest {
+            continue;
+        }
+        let new = if states[sender].priority == highest {
+            sender
+        } else {
+            match states
+                .iter()
+                .position(|state| live(state) && state.priority == highest)
+            {
+                Some(index) => index,
+                None => continue,
+            }
+        };
+        controller = Some(new);
+        let Some(old_last) = states[old].last else {
+            continue;
+        };
+        let (cause, timestamp) = if old_live {
+            (TakeoverCause::OutPrioritized, ts)
+        } else {
+            let times = &states[new].times;
+            let first_after = times[times.partition_point(|time| *time < old_last)..]
+                .first()
+                .copied()
+                .unwrap_or(ts);
+            (TakeoverCause::SourceStopped, first_after)
+        };
+        let gap = match cause {
+            TakeoverCause::OutPrioritized => 0.0,
+            TakeoverCause::SourceStopped => timestamp - old_last,
+        };
+        if gap > MAX_TAKEOVER_GAP_S {
+            continue;
+        }
+        let priority_of =
+            |index: usize| (protocol == Protocol::Sacn).then_some(states[index].priority);
+        takeovers.push(Takeover {
+            timestamp,
+            universe,
+            proto: protocol,
+            from_source: states[old].id.to_string(),
+            to_source: states[new].id.to_string(),
+            cause,
+            gap_ms: round_to(gap * 1000.0, AVERAGE_SCALE),
+            from_priority: priority_of(old),
+            to_priority: priority_of(new),
+        });
+    }
+    takeovers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::detect_takeovers;
+    use crate::analysis::dmx::{DmxFrame, FrameWrite};
+    use crate::{Protocol, TakeoverCause};
+
+    /// 40 fps frames of `source` between `start` and `end` (seconds).
+    fn frames(
+        source: &str,
+        start: f64,
+        end: f64,
+        priority: u8,
+    ) -> (Vec<DmxFrame>, Vec<FrameWrite>) {
+        let count = ((end - start) / 0.025).round() as usize;
+        let frames = (0..count)
+            .map(|index| DmxFrame {
+                universe: 1,
+                timestamp: Some(start + index as f64 * 0.025),
+                source_id: source.to_string(),
+                protocol: Protocol::Sacn,
+                slots: [0u8; 512],
+            })
+            .collect();
+        let writes = vec![
+            FrameWrite {
+                slots: 512,
+                priority: Some(priority),
+            };
+            count
+        ];
+        (frames, writes)
+    }
+
+    #[test]
+    fn backup_starting_after_main_stops_is_a_takeover_with_gap() {
+        let main = frames("sacn:cid:main", 0.0, 10.0, 100);
+        let backup = frames("sacn:cid:backup", 10.975, 20.0, 100);
+        let takeovers = detect_takeovers(
+            Protocol::Sacn,
+            [
+                (main.0.as_slice(), main.1.as_slice()),
+                (backup.0.as_slice(), backup.1.as_slice()),
+            ],
+        );
+        assert_eq!(takeovers.len(), 1);
+        let takeover = &takeovers[0];
+        assert_eq!(takeover.from_source, "sacn:cid:main");
+        assert_eq!(takeover.to_source, "sacn:cid:backup");
+        assert_eq!(takeover.cause, TakeoverCause::SourceStopped);
+        assert_eq!(takeover.timestamp, 10.975);
+        assert_eq!(takeover.gap_ms, 1000.0);
+    }
+
+    #[test]
+    fn higher_priority_source_out_prioritizes_the_incumbent() {
+        let main = frames("sacn:cid:main", 0.0, 10.0, 100);
+        let override_desk = frames("sacn:cid:override", 5.0, 10.0, 150);
+        let takeovers = detect_takeovers(
+            Protocol::Sacn,
+            [
+                (main.0.as_slice(), main.1.as_slice()),
+                (override_desk.0.as_slice(), override_desk.1.as_slice()),
+            ],
+        );
+        assert_eq!(takeovers.len(), 1);
+        assert_eq!(takeovers[0].cause, TakeoverCause::OutPrioritized);
+        assert_eq!(takeovers[0].timestamp, 5.0);
+        assert_eq!(takeovers[0].gap_ms, 0.0);
+        assert_eq!(takeovers[0].to_priority, Some(150));
+    }
+
+    #[test]
+    fn equal_priority_hot_standby_does_not_take_over_while_main_runs() {
+        let main = frames("sacn:cid:main", 0.0, 10.0, 100);
+        let backup = frames("sacn:cid:backup", 0.0, 10.0, 100);
+        let takeovers = detect_takeovers(
+            Protocol::Sacn,
+            [
+                (main.0.as_slice(), main.1.as_slice()),
+                (backup.0.as_slice(), backup.1.as_slice()),
+            ],
+        );
+        assert!(takeovers.is_empty());
+    }
+}
@@ -0,0 +1,213 @@
+//! Timecode tracks from ArtTimeCode packets.
+//!
+//! A running timecode advances with the capture clock, so its offset from
+//! the packet timestamps stays constant. A change of that offset by more
+//! than a couple of frames between two packets is a discontinuity (a jump,
+//! a restart, a locate); a gap of more than half a second between packets is
+//! a dropout, during which timecode-chased cues stall.
+
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+
+use super::{DURATION_SCALE, round_to};
+use crate::{ArtTimeCode, TimecodeDiscontinuity, TimecodeDropout, TimecodeStream};
+
+/// Offset change, in frames, above which two packets are discontinuous.
+const DISCONTINUITY_FRAMES: f64 = 2.0;
+/// Gap between packets, in seconds, above which the stream dropped out.
+const DROPOUT_GAP_S: f64 = 0.5;
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+#[derive(Debug)]
+struct Stream {
+    packets: u64,
+    first: ArtTimeCode,
+    last: ArtTimeCode,
+    first_seen: Option<f64>,
+    last_seen: Option<f64>,
+    /// Timestamp and value of the last timestamped packet.
+    last_timed: Option<(f64, ArtTimeCode)>,
+    discontinuities: Vec<TimecodeDiscontinuity>,
+    dropouts: Vec<TimecodeDropout>,
+}
+
+/// ArtTimeCode streams, keyed by source IP and stream identifier.
+#[derive(Debug, Default)]
+pub(crate) struct TimecodeTracker {
+    streams: BTreeMap<(String, u8), Stream>,
+}
+
+impl TimecodeTracker {
+    /// Record an ArtTimeCode sent from `src_ip`.
+    pub(crate) fn add(&mut self, ts: Option<f64>, src_ip: IpAddr, timecode: ArtTimeCode) {
+        let stream = self
+            .streams
+            .entry((src_ip.to_string(), timecode.stream_id))
+            .or_insert_with(|| Stream {
+                packets: 0,
+                first: timecode,
+                last: timecode,
+                first_seen: ts,
+                last_seen: ts,
+                last_timed: None,
+                discontinuities: Vec::new(),
+                dropouts: Vec::new(),
+            });
+        stream.packets += 1;
+        stream.last = timecode;
+        let Some(ts) = ts else {
+            return;
+        };
+        stream.first_seen.get_or_insert(ts);
+        stream.last_seen = Some(ts);
+        if let Some((previous_ts, previous)) = stream.last_timed {
+            let gap = ts - previous_ts;
+            if gap > DROPOUT_GAP_S {
+                stream.dropouts.push(TimecodeDropout {
+                    start: previous_ts,
+                    duration_s: round_to(gap, DURATION_SCALE),
+                });
+            }
+            if let Some(jump) = jump_s(previous_ts, &previous, ts, &timecode) {
+                stream.discontinuities.push(TimecodeDiscontinuity {
+                    timestamp: ts,
+                    from: previous.to_string(),
+                    to: timecode.to_string(),
+                    jump_s: round_to(jump, DURATION_SCALE),
+                });
+            }
+        }
+        stream.last_timed = Some((ts, timecode));
+    }
+
+    /// Timecode streams sorted by source IP then stream identifier.
+    pub(crate) fn finish(self) -> Vec<TimecodeStream> {
+        self.streams
+            .into_iter()
+            .map(|((source_ip, stream_id), stream)| TimecodeStream {
+                source_ip,
+                stream_id,
+                frame_rate: stream.last.frame_rate(),
+                packets: stream.packets,
+                first_value: stream.first.to_string(),
+                last_value: stream.last.to_string(),
+                first_seen: stream.first_seen,
+                last_seen: stream.last_seen,
+                discontinuities: stream.discontinuities,
+                dropouts: stream.dropouts,
+            })
+            .collect()
+    }
+}
+
+/// How far the timecode jumped between two packets beyond the capture time
+/// elapsed (seconds, negative backwards), when that exceeds
+/// `DISCONTINUITY_FRAMES`. A change of frame rate always counts; unknown
+/// types are never compared.
+fn jump_s(
+    previous_ts: f64,
+    previous: &ArtTimeCode,
+    ts: f64,
+    timecode: &ArtTimeCode,
+) -> Option<f64> {
+    let rate = timecode.frame_rate()?;
+    let previous_rate = previous.frame_rate()?;
+    let elapsed = position_s(timecode)? - position_s(previous)?;
+    // Midnight rollover: the shortest way around the day.
+    let elapsed =
+        (elapsed + SECONDS_PER_DAY / 2.0).rem_euclid(SECONDS_PER_DAY) - SECONDS_PER_DAY / 2.0;
+    let jump = elapsed - (ts - previous_ts);
+    (rate != previous_rate || jump.abs() > DISCONTINUITY_FRAMES / rate).then_some(jump)
+}
+
+/// Real time since midnight of a timecode value, counting dropped frame
+/// numbers out of drop-frame timecode.
+fn position_s(timecode: &ArtTimeCode) -> Option<f64> {
+    let rate = timecode.frame_rate()?;
+    let minutes = u32::from(timecode.hours) * 60 + u32::from(timecode.minutes);
+    let seconds = minutes * 60 + u32::from(timecode.seconds);
+    if timecode.is_drop_frame() {
+        // Frame numbers 0 and 1 are skipped every minute but each tenth.
+        let dropped = 2 * (minutes - minutes / 10);
+        let frames = seconds * 30 + u32::from(timecode.frames);
+        Some(f64::from(frames.saturating_sub(dropped)) / rate)
+    } else {
+        Some(f64::from(seconds) + f64::from(timecode.frames) / rate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use super::{TimecodeTracker, position_s};
+    use crate::ArtTimeCode;
+
+    fn timecode(minutes: u8, seconds: u8, frames: u8) -> ArtTimeCode {
+        ArtTimeCode {
+            protocol_version: 14,
+            stream_id: 0,
+            hours: 1,
+            minutes,
+            seconds,
+            frames,
+            timecode_type: 1,
+        }
+    }
+
+    #[test]
+    fn running_timecode_has_no_discontinuity_or_dropout() {
+        let mut tracker = TimecodeTracker::default();
+        let ip = IpAddr::V4(Ipv4Addr::new(2, 0, 0, 1));
+        for frame in 0..50u8 {
+            let ts = 10.0 + f64::from(frame) * 0.04;
+            tracker.add(Some(ts), ip, timecode(0, frame / 25, frame % 25));
+        }
+        let streams = tracker.finish();
+        assert_eq!(streams.len(), 1);
+        let stream = &streams[0];
+        assert_eq!(stream.packets, 50);
+        assert_eq!(stream.frame_rate, Some(25.0));
+        assert_eq!(stream.first_value, "01:00:00:00");
+        assert_eq!(stream.last_value, "01:00:01:24");
+        assert!(stream.discontinuities.is_empty());
+        assert!(stream.dropouts.is_empty());
+    }
+
+    #[test]
+    fn jumps_and_gaps_are_reported() {
+        let mut tracker = TimecodeTracker::default();
+        let ip = IpAddr::V4(Ipv4Addr::new(2, 0, 0, 1));
+        tracker.add(Some(0.0), ip, timecode(0, 0, 0));
+        tracker.add(Some(0.04), ip, timecode(0, 0, 1));
+        // Located 10 s ahead.
+        tracker.add(Some(0.08), ip, timecode(0, 10, 2));
+        // Two seconds without packets while the timecode kept running.
+        tracker.add(Some(2.08), ip, timecode(0, 12, 2));
+        let stream = &tracker.finish()[0];
+        assert_eq!(stream.discontinuities.len(), 1);
+        let discontinuity = &stream.discontinuities[0];
+        assert_eq!(discontinuity.timestamp, 0.08);
+        assert_eq!(discontinuity.from, "01:00:00:01");
+        assert_eq!(discontinuity.to, "01:00:10:02");
+        assert_eq!(discontinuity.jump_s, 10.0);
+        assert_eq!(stream.dropouts.len(), 1);
+        assert_eq!(stream.dropouts[0].start, 0.08);
+        assert_eq!(stream.dropouts[0].duration_s, 2.0);
+    }
+
+    #[test]
+    fn drop_frame_skips_the_first_two_frames_of_most_minutes() {
+        let mut before = timecode(0, 59, 29);
+        before.hours = 0;
+        before.timecode_type = 2;
+        let after = ArtTimeCode {
+            minutes: 1,
+            seconds: 0,
+            frames: 2,
+            ..before
+        };
+        let frame = position_s(&after).unwrap() - position_s(&before).unwrap();
+        assert!((frame - 1.0 / 29.97).abs() < 1e-9);
+    }
+}
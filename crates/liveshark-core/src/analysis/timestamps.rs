@@ -0,0 +1,164 @@
+//! Capture clock anomalies.
+//!
+//! Windowed metrics assume packet timestamps only move forward. A capture
+//! clock that steps backwards, or files merged out of order, silently skew
+//! FPS, jitter, and rate peaks; so do large forward jumps. This module finds
+//! those ranges so the report can warn about them.
+
+use super::ts_to_rfc3339;
+use crate::{AnalysisWarning, WarningKind};
+
+/// Packets whose timestamp is below the latest one seen so far.
+struct BackwardsRun {
+    first_packet: u64,
+    last_packet: u64,
+    first_ts: f64,
+    last_ts: f64,
+    packets: u64,
+    max_step_s: f64,
+}
+
+pub(crate) struct TimestampMonitor {
+    max_jump_s: f64,
+    latest: Option<f64>,
+    previous: Option<(u64, f64)>,
+    run: Option<BackwardsRun>,
+    warnings: Vec<AnalysisWarning>,
+}
+
+impl TimestampMonitor {
+    pub(crate) fn new(max_jump_s: f64) -> Self {
+        Self {
+            max_jump_s,
+            latest: None,
+            previous: None,
+            run: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Check the timestamp of packet `packet` (1-based, as in capture tools).
+    pub(crate) fn observe(&mut self, packet: u64, ts: Option<f64>) {
+        let Some(ts) = ts else {
+            return;
+        };
+        let Some(latest) = self.latest else {
+            self.latest = Some(ts);
+            self.previous = Some((packet, ts));
+            return;
+        };
+        if ts < latest {
+            let step = latest - ts;
+            match &mut self.run {
+                Some(run) if run.last_packet + 1 == packet => {
+                    run.last_packet = packet;
+                    run.last_ts = ts;
+                    run.packets += 1;
+                    run.max_step_s = run.max_step_s.max(step);
+                }
+                _ => {
+                    self.close_run();
+                    self.run = Some(BackwardsRun {
+                        first_packet: packet,
+                        last_packet: packet,
+                        first_ts: ts,
+                        last_ts: ts,
+                        packets: 1,
+                        max_step_s: step,
+                    });
+                }
+            }
+        } else {
+            self.close_run();
+            if let Some((previous_packet, previous_ts)) = self.previous {
+                let gap = ts - previous_ts;
+                if previous_ts >= latest && gap > self.max_jump_s {
+                    self.warnings.push(AnalysisWarning {
+                        kind: WarningKind::TimestampJump,
+                        first_packet: previous_packet,
+                        last_packet: packet,
+                        packets: 1,
+                        time_start: ts_to_rfc3339(Some(previous_ts)),
+                        time_end: ts_to_rfc3339(Some(ts)),
+                        message: format!("timestamps jump forward by {gap:.3} s"),
+                    });
+                }
+            }
+            self.latest = Some(ts);
+        }
+        self.previous = Some((packet, ts));
+    }
+
+    /// Warnings in packet order.
+    pub(crate) fn finish(mut self) -> Vec<AnalysisWarning> {
+        self.close_run();
+        self.warnings
+    }
+
+    fn close_run(&mut self) {
+        let Some(run) = self.run.take() else {
+            return;
+        };
+        self.warnings.push(AnalysisWarning {
+            kind: WarningKind::TimestampBackwards,
+            first_packet: run.first_packet,
+            last_packet: run.last_packet,
+            packets: run.packets,
+            time_start: ts_to_rfc3339(Some(run.first_ts)),
+            time_end: ts_to_rfc3339(Some(run.last_ts)),
+            message: format!(
+                "timestamps go back up to {:.3} s before an earlier packet",
+                run.max_step_s
+            ),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TimestampMonitor;
+    use crate::WarningKind;
+
+    fn run(timestamps: &[f64], max_jump_s: f64) -> Vec<(WarningKind, u64, u64, u64)> {
+        let mut monitor = TimestampMonitor::new(max_jump_s);
+        for (index, ts) in timestamps.iter().enumerate() {
+            monitor.observe(index as u64 + 1, Some(*ts));
+        }
+        monitor
+            .finish()
+            .into_iter()
+            .map(|warning| {
+                (
+                    warning.kind,
+                    warning.first_packet,
+                    warning.last_packet,
+                    warning.packets,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn monotonic_timestamps_raise_no_warning() {
+        assert!(run(&[0.0, 0.5, 0.5, 1.0], 10.0).is_empty());
+    }
+
+    #[test]
+    fn backwards_packets_are_merged_into_one_range() {
+        // Second file merged after the first: packets 4..=5 predate packet 3.
+        let warnings = run(&[10.0, 11.0, 12.0, 1.0, 2.0, 13.0], 60.0);
+        assert_eq!(warnings, vec![(WarningKind::TimestampBackwards, 4, 5, 2)]);
+    }
+
+    #[test]
+    fn large_forward_gaps_are_reported() {
+        let warnings = run(&[0.0, 1.0, 500.0, 501.0], 300.0);
+        assert_eq!(warnings, vec![(WarningKind::TimestampJump, 2, 3, 1)]);
+    }
+
+    #[test]
+    fn recovery_after_a_backwards_run_is_not_a_jump() {
+        let warnings = run(&[1000.0, 1.0, 1001.0], 300.0);
+        assert_eq!(warnings, vec![(WarningKind::TimestampBackwards, 2, 2, 1)]);
+    }
+}
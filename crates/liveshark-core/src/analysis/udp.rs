@@ -0,0 +1,75 @@
+//! UDP extraction for capture link types.
+//!
+//! Decoding lives in `liveshark-proto`; this module maps pcap link types
+//! onto the link layers it handles.
+
+use pcap_parser::Linktype;
+
+use liveshark_proto::udp::{LinkLayer, error::UdpError};
+pub use liveshark_proto::udp::{UdpPacket, error};
+
+/// Link types `pcap_parser` has no constant for.
+pub(crate) const IEEE802_11: Linktype = Linktype(105);
+pub(crate) const IEEE802_11_RADIOTAP: Linktype = Linktype(127);
+
+fn link_layer(linktype: Linktype) -> Option<LinkLayer> {
+    match linktype {
+        Linktype::ETHERNET => Some(LinkLayer::Ethernet),
+        Linktype::RAW => Some(LinkLayer::RawIp),
+        Linktype::NULL => Some(LinkLayer::Null),
+        Linktype::LOOP => Some(LinkLayer::Loop),
+        IEEE802_11 => Some(LinkLayer::Ieee80211),
+        IEEE802_11_RADIOTAP => Some(LinkLayer::Radiotap),
+        _ => None,
+    }
+}
+
+fn linktype_of(link: LinkLayer) -> Linktype {
+    match link {
+        LinkLayer::Ethernet => Linktype::ETHERNET,
+        LinkLayer::RawIp => Linktype::RAW,
+        LinkLayer::Null => Linktype::NULL,
+        LinkLayer::Loop => Linktype::LOOP,
+        LinkLayer::Ieee80211 => IEEE802_11,
+        LinkLayer::Radiotap => IEEE802_11_RADIOTAP,
+    }
+}
+
+/// Returns true for the link types `parse_udp_packet` can decode (Ethernet,
+/// raw IP, BSD loopback, and 802.11 with or without radiotap).
+pub fn is_supported_linktype(linktype: Linktype) -> bool {
+    link_layer(linktype).is_some()
+}
+
+/// Parse a UDP packet from a captured frame; `Ok(None)` when the link type
+/// is unsupported or the packet is not UDP.
+pub fn parse_udp_packet(
+    linktype: Linktype,
+    data: &[u8],
+) -> Result<Option<UdpPacket<'_>>, UdpError> {
+    match link_layer(linktype) {
+        Some(link) => liveshark_proto::parse_udp_packet(link, data),
+        None => Ok(None),
+    }
+}
+
+/// Parse the headers of a UDP packet the capture cut short; the payload is
+/// what was captured of it and checksums are not verified.
+pub fn parse_truncated_udp_packet(
+    linktype: Linktype,
+    data: &[u8],
+) -> Result<Option<UdpPacket<'_>>, UdpError> {
+    match link_layer(linktype) {
+        Some(link) => liveshark_proto::parse_truncated_udp_packet(link, data),
+        None => Ok(None),
+    }
+}
+
+/// Unwrap the frame carried by a VXLAN, GRE, or ERSPAN tunnel, with its link
+/// type; other frames are returned unchanged.
+pub fn decapsulate(linktype: Linktype, data: &[u8]) -> (Linktype, &[u8]) {
+    match link_layer(linktype).and_then(|link| liveshark_proto::decapsulate(link, data)) {
+        Some(tunnel) => (linktype_of(tunnel.link), tunnel.frame),
+        None => (linktype, data),
+    }
+}
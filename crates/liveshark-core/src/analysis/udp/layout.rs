@@ -1 +0,0 @@
-pub const UDP_HEADER_LEN: usize = 8;
@@ -1,8 +1,23 @@
-use std::collections::{HashMap, VecDeque};
-use std::net::IpAddr;
-
-use super::dmx::{DmxProtocol, DmxStore};
-use crate::{SourceSummary, UniverseSummary};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::net::{IpAddr, SocketAddr};
+
+use super::channels::detect_channel_pairs;
+use super::compliance::is_broadcast;
+use super::config::AnalyzerConfig;
+use super::controllers::{ActivitySpans, max_concurrent};
+use super::dmx::{DmxStore, artnet_source_id, sacn_source_id};
+use super::microbursts::BurstWindow;
+use super::ownership::channel_ownership;
+use super::sequence_gap;
+use super::series::{FrameSample, Timeline, UniverseSecond, second_of};
+use super::stability::IatStability;
+use super::takeovers::SOURCE_TIMEOUT_S;
+use super::{DURATION_SCALE, round_to};
+use crate::protocols::artnet::PortAddress;
+use crate::{
+    DestinationKind, EventKind, NonZeroStartCodes, Protocol, ReportEvent, Severity, SourceSummary,
+    UniverseDestination, UniverseSeries, UniverseSilence, UniverseSummary,
+};
 
 #[derive(Debug, Default)]
 pub(crate) struct UniverseStats {
@@ -11,6 +26,24 @@ pub(crate) struct UniverseStats {
     pub first_ts: Option<f64>,
     pub last_ts: Option<f64>,
     pub per_source: HashMap<String, UniverseSourceStats>,
+    pub destinations: HashMap<IpAddr, u64>,
+    /// Short-window peaks of the universe's packets (all sources).
+    pub microburst: BurstWindow,
+    /// Per-second buckets, filled only when series are requested.
+    pub seconds: BTreeMap<i64, UniverseSecond>,
+    /// Timestamps of the universe's frames, in arrival order; sorted when
+    /// silences are searched so out-of-order captures do not invent gaps.
+    pub frame_times: Vec<f64>,
+    /// `(timestamp, packets)` of each sequence gap, in arrival order.
+    pub loss_events: Vec<(f64, u64)>,
+    /// ArtNzs packets of the universe.
+    pub nzs_packets: u64,
+    /// Distinct start codes of those packets.
+    pub nzs_start_codes: BTreeSet<u8>,
+    /// sACN packets flagged as preview data.
+    pub preview_frames: u64,
+    /// Endpoints that received the universe by sACN unicast.
+    pub unicast_receivers: BTreeSet<SocketAddr>,
 }
 
 #[derive(Debug, Default)]
@@ -23,6 +56,9 @@ pub(crate) struct UniverseSourceStats {
     pub dup_packets: u64,
     pub reordered_packets: u64,
     pub last_seq: Option<u8>,
+    /// Latest sequence number, for the duplicate and reorder counts (both
+    /// protocols, unlike the loss metrics).
+    pub order_seq: Option<u8>,
     pub first_ts: Option<f64>,
     pub last_ts: Option<f64>,
     pub prev_iat: Option<f64>,
@@ -34,26 +70,37 @@ pub(crate) struct UniverseSourceStats {
     pub loss_samples: VecDeque<(f64, u64)>,
     pub burst_start_samples: VecDeque<f64>,
     pub burst_length_samples: VecDeque<(f64, u64)>,
+    pub stability: IatStability,
+    /// Latest sequence number, for `loss_events`.
+    pub gap_seq: Option<u8>,
+    pub activity: ActivitySpans,
+    /// First frame of the current active interval, `None` after a stream
+    /// termination until the next frame.
+    pub interval_start: Option<f64>,
+    /// Active intervals closed by a stream termination.
+    pub closed_intervals: Vec<(f64, f64)>,
+    /// Stream termination timestamps.
+    pub terminated_at: Vec<f64>,
+    /// `(last frame, silence)` of each stop longer than the source loss
+    /// timeout without a stream termination, in arrival order.
+    pub dropouts: Vec<(f64, f64)>,
 }
 
-const METRICS_WINDOW_S: f64 = 10.0;
-const FPS_WINDOW_S: f64 = 5.0;
-const CONFLICT_MIN_OVERLAP_S: f64 = 1.0;
-
-fn artnet_source_id(source_ip: &IpAddr, source_port: u16) -> String {
-    format!("artnet:{}:{}", source_ip, source_port)
-}
-
-fn sacn_source_id(cid: &str, source_ip: &IpAddr, source_port: u16) -> String {
-    if cid.is_empty() {
-        format!("sacn:{}:{}", source_ip, source_port)
-    } else {
-        format!("sacn:cid:{}", cid)
+impl UniverseSourceStats {
+    /// Intervals during which the source was sending: from a frame to the
+    /// last frame before a stream termination, or to its last frame.
+    fn active_intervals(&self) -> Vec<(f64, f64)> {
+        let mut intervals = self.closed_intervals.clone();
+        if let (Some(start), Some(end)) = (self.interval_start, self.last_ts) {
+            intervals.push((start, end));
+        }
+        intervals
     }
 }
 
 pub(crate) fn add_artnet_frame(
     stats: &mut HashMap<u16, UniverseStats>,
+    config: &AnalyzerConfig,
     universe: u16,
     source_ip: &IpAddr,
     source_port: u16,
@@ -71,9 +118,18 @@ pub(crate) fn add_artnet_frame(
             cid: None,
             source_name: None,
             source_id: None,
+            ttl: None,
+            stability: None,
+            terminated_at: Vec::new(),
         });
     let source_stats = entry.per_source.entry(source_id.clone()).or_default();
-    update_source_stats(source_stats, false, sequence, ts);
+    let gap = record_sequence_gap(source_stats, Protocol::ArtNet, sequence);
+    let sample = update_source_stats(source_stats, false, sequence, ts, config.metrics_window_s);
+    if let (Some(ts), Some(gap)) = (ts, gap) {
+        entry.loss_events.push((ts, gap));
+    }
+    record_second(entry, config, ts, sample);
+    entry.frame_times.extend(ts);
     update_ts_bounds(&mut entry.first_ts, &mut entry.last_ts, ts);
     source_id
 }
@@ -81,11 +137,13 @@ pub(crate) fn add_artnet_frame(
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn add_sacn_frame(
     stats: &mut HashMap<u16, UniverseStats>,
+    config: &AnalyzerConfig,
     universe: u16,
     source_ip: &IpAddr,
     source_port: u16,
     cid: String,
     source_name: Option<String>,
+    ttl: Option<u8>,
     sequence: Option<u8>,
     ts: Option<f64>,
 ) -> String {
@@ -100,52 +158,265 @@ pub(crate) fn add_sacn_frame(
             cid: Some(cid),
             source_name,
             source_id: None,
+            ttl,
+            stability: None,
+            terminated_at: Vec::new(),
         });
     let source_stats = entry.per_source.entry(source_id.clone()).or_default();
-    update_source_stats(source_stats, true, sequence, ts);
+    if let (Some(ts), Some(last)) = (ts, source_stats.last_ts) {
+        if source_stats.interval_start.is_some() && ts - last > SOURCE_TIMEOUT_S {
+            source_stats.dropouts.push((last, ts - last));
+        }
+    }
+    let gap = record_sequence_gap(source_stats, Protocol::Sacn, sequence);
+    let sample = update_source_stats(source_stats, true, sequence, ts, config.metrics_window_s);
+    if let (Some(ts), Some(gap)) = (ts, gap) {
+        entry.loss_events.push((ts, gap));
+    }
+    record_second(entry, config, ts, sample);
+    entry.frame_times.extend(ts);
     update_ts_bounds(&mut entry.first_ts, &mut entry.last_ts, ts);
     source_id
 }
 
+/// Advance the sequence of a known sACN source past a per-address priority
+/// packet. Those packets share the DMX sequence counter, so skipping them
+/// would count each one as a lost frame.
+pub(crate) fn skip_sacn_sequence(
+    stats: &mut HashMap<u16, UniverseStats>,
+    universe: u16,
+    source_id: &str,
+    sequence: Option<u8>,
+) {
+    let Some(sequence) = sequence else {
+        return;
+    };
+    if let Some(source_stats) = stats
+        .get_mut(&universe)
+        .and_then(|entry| entry.per_source.get_mut(source_id))
+    {
+        source_stats.last_seq = Some(sequence);
+        source_stats.order_seq = Some(sequence);
+        source_stats.gap_seq = Some(sequence);
+    }
+}
+
+/// Close the active interval of a known sACN source that terminated its
+/// stream at `ts`. Terminated packets repeat, so only the first one after a
+/// frame counts.
+pub(crate) fn terminate_sacn_source(
+    stats: &mut HashMap<u16, UniverseStats>,
+    universe: u16,
+    source_id: &str,
+    ts: Option<f64>,
+) {
+    let Some(entry) = stats.get_mut(&universe) else {
+        return;
+    };
+    let Some(source_stats) = entry.per_source.get_mut(source_id) else {
+        return;
+    };
+    let (Some(start), Some(end)) = (source_stats.interval_start.take(), source_stats.last_ts)
+    else {
+        return;
+    };
+    source_stats.closed_intervals.push((start, end));
+    source_stats.terminated_at.extend(ts);
+}
+
+/// Packets skipped before `sequence`, as the sequence gap rules count them
+/// (Art-Net included, unlike the loss metrics).
+fn record_sequence_gap(
+    stats: &mut UniverseSourceStats,
+    protocol: Protocol,
+    sequence: Option<u8>,
+) -> Option<u64> {
+    let sequence = sequence?;
+    let gap = stats
+        .gap_seq
+        .and_then(|last| sequence_gap(protocol, last, sequence))
+        .map(|(_, gap)| u64::from(gap));
+    stats.gap_seq = Some(sequence);
+    gap
+}
+
+/// Packets lost on `universe` between `start` and `end` (inclusive).
+pub(crate) fn lost_between(
+    stats: &HashMap<u16, UniverseStats>,
+    universe: u16,
+    start: f64,
+    end: f64,
+) -> u64 {
+    stats.get(&universe).map_or(0, |stats| {
+        stats
+            .loss_events
+            .iter()
+            .filter(|(ts, _)| (start..=end).contains(ts))
+            .map(|(_, lost)| lost)
+            .sum()
+    })
+}
+
+fn record_second(
+    entry: &mut UniverseStats,
+    config: &AnalyzerConfig,
+    ts: Option<f64>,
+    sample: FrameSample,
+) {
+    if let (true, Some(ts)) = (config.series, ts) {
+        entry.seconds.entry(second_of(ts)).or_default().add(sample);
+    }
+}
+
+/// Per-second series of every universe in `stats`, sorted by universe.
+pub(crate) fn build_universe_series(
+    stats: &HashMap<u16, UniverseStats>,
+    protocol: Protocol,
+    timeline: Timeline,
+) -> Vec<UniverseSeries> {
+    let mut series: Vec<UniverseSeries> = stats
+        .iter()
+        .map(|(universe, stats)| UniverseSeries {
+            universe: *universe,
+            proto: protocol,
+            fps: timeline.spread(&stats.seconds, UniverseSecond::frames),
+            loss: stats
+                .per_source
+                .values()
+                .any(|source| source.last_seq.is_some())
+                .then(|| timeline.spread(&stats.seconds, UniverseSecond::lost)),
+            jitter_ms: timeline.spread(&stats.seconds, UniverseSecond::jitter_ms),
+        })
+        .collect();
+    series.sort_by_key(|series| series.universe);
+    series
+}
+
+/// Count an ArtNzs packet of `universe` sent with `start_code`.
+pub(crate) fn add_artnet_nzs(
+    stats: &mut HashMap<u16, UniverseStats>,
+    universe: u16,
+    start_code: u8,
+) {
+    let entry = stats.entry(universe).or_default();
+    entry.nzs_packets += 1;
+    entry.nzs_start_codes.insert(start_code);
+}
+
+/// Count an sACN packet of `universe` flagged as preview data.
+pub(crate) fn add_sacn_preview(stats: &mut HashMap<u16, UniverseStats>, universe: u16) {
+    stats.entry(universe).or_default().preview_frames += 1;
+}
+
+/// Remember the endpoint that received `universe` by sACN unicast.
+pub(crate) fn add_sacn_unicast(
+    stats: &mut HashMap<u16, UniverseStats>,
+    universe: u16,
+    receiver: SocketAddr,
+) {
+    stats
+        .entry(universe)
+        .or_default()
+        .unicast_receivers
+        .insert(receiver);
+}
+
+/// Count a DMX packet of `universe` sent to `dst_ip`.
+pub(crate) fn add_destination(
+    stats: &mut HashMap<u16, UniverseStats>,
+    universe: u16,
+    dst_ip: IpAddr,
+) {
+    let entry = stats.entry(universe).or_default();
+    *entry.destinations.entry(dst_ip).or_default() += 1;
+}
+
+/// Count a DMX packet of `universe` carrying `bytes` of UDP payload in the
+/// microburst window.
+pub(crate) fn add_burst_sample(
+    stats: &mut HashMap<u16, UniverseStats>,
+    config: &AnalyzerConfig,
+    universe: u16,
+    ts: Option<f64>,
+    bytes: u64,
+) {
+    let entry = stats.entry(universe).or_default();
+    entry.microburst.add(ts, bytes, config.microburst_window_s);
+}
+
 pub(crate) fn build_artnet_universe_summaries(
     stats: HashMap<u16, UniverseStats>,
     dmx_store: &DmxStore,
+    config: &AnalyzerConfig,
 ) -> Vec<UniverseSummary> {
-    build_universe_summaries(stats, dmx_store, DmxProtocol::ArtNet, "artnet")
+    build_universe_summaries(stats, dmx_store, config, Protocol::ArtNet)
 }
 
 pub(crate) fn build_sacn_universe_summaries(
     stats: HashMap<u16, UniverseStats>,
     dmx_store: &DmxStore,
+    config: &AnalyzerConfig,
 ) -> Vec<UniverseSummary> {
-    build_universe_summaries(stats, dmx_store, DmxProtocol::Sacn, "sacn")
+    build_universe_summaries(stats, dmx_store, config, Protocol::Sacn)
 }
 
 fn build_universe_summaries(
     stats: HashMap<u16, UniverseStats>,
     dmx_store: &DmxStore,
-    protocol: DmxProtocol,
-    proto: &str,
+    config: &AnalyzerConfig,
+    protocol: Protocol,
 ) -> Vec<UniverseSummary> {
     let mut universes: Vec<UniverseSummary> = stats
         .into_iter()
-        .map(|(universe, stats)| {
-            let fps = fps_from_dmx(dmx_store, universe, protocol, stats.frames);
+        .map(|(universe, mut stats)| {
+            let fps = fps_from_dmx(
+                dmx_store,
+                universe,
+                protocol,
+                stats.frames,
+                config.fps_window_s,
+            );
+            let metrics = compute_metrics(&stats.per_source);
+            let max_concurrent_controllers = max_concurrent(
+                stats
+                    .per_source
+                    .values_mut()
+                    .map(|source| std::mem::take(&mut source.activity)),
+            );
             let mut sources_with_ids: Vec<(String, SourceSummary)> =
                 stats.sources.into_iter().collect();
             sources_with_ids.sort_by(|a, b| a.0.cmp(&b.0));
+            let controllers_count = sources_with_ids.len() as u64;
             let sources = sources_with_ids
                 .into_iter()
                 .map(|(id, mut summary)| {
+                    if let Some(source) = stats.per_source.remove(&id) {
+                        summary.stability = source.stability.finish();
+                        summary.terminated_at = source.terminated_at;
+                    }
                     summary.source_id = Some(id);
                     summary
                 })
                 .collect();
-            let metrics = compute_metrics(&stats.per_source);
+            let mut destinations: Vec<UniverseDestination> = stats
+                .destinations
+                .into_iter()
+                .map(|(dst_ip, packets)| UniverseDestination {
+                    dst_ip: dst_ip.to_string(),
+                    kind: destination_kind(&dst_ip),
+                    packets,
+                })
+                .collect();
+            destinations.sort_by(|a, b| a.dst_ip.cmp(&b.dst_ip));
+            let (first_snapshot, last_snapshot) = dmx_store
+                .snapshots(universe, protocol)
+                .map_or((None, None), |(first, last)| (Some(first), Some(last)));
 
             UniverseSummary {
                 universe,
-                proto: proto.to_string(),
+                proto: protocol,
+                port_address: (protocol == Protocol::ArtNet)
+                    .then(|| PortAddress::from_masked(universe)),
                 sources,
                 fps,
                 frames_count: stats.frames,
@@ -158,19 +429,53 @@ fn build_universe_summaries(
                 reordered_packets: metrics.reordered_packets,
                 first_seen: stats.first_ts,
                 last_seen: stats.last_ts,
+                destinations,
+                channel_pairs: detect_channel_pairs(
+                    dmx_store.sources_for_universe(universe, protocol),
+                ),
+                first_snapshot,
+                last_snapshot,
+                ownership: channel_ownership(
+                    protocol,
+                    dmx_store.written_frames(universe, protocol),
+                ),
+                microburst: stats.microburst.peak(config.microburst_window_s),
+                controllers_count,
+                max_concurrent_controllers,
+                non_zero_start_codes: (stats.nzs_packets > 0).then(|| NonZeroStartCodes {
+                    packets: stats.nzs_packets,
+                    start_codes: stats.nzs_start_codes.into_iter().collect(),
+                }),
+                preview_frames: (stats.preview_frames > 0).then_some(stats.preview_frames),
+                unicast_receivers: stats
+                    .unicast_receivers
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect(),
             }
         })
         .collect();
 
-    universes.sort_by(|a, b| a.universe.cmp(&b.universe));
+    universes.sort_by_key(|summary| summary.universe);
     universes
 }
 
+fn destination_kind(ip: &IpAddr) -> DestinationKind {
+    if ip.is_multicast() {
+        DestinationKind::Multicast
+    } else if is_broadcast(ip) {
+        DestinationKind::Broadcast
+    } else {
+        DestinationKind::Unicast
+    }
+}
+
 fn fps_from_dmx(
     dmx_store: &DmxStore,
     universe: u16,
-    protocol: DmxProtocol,
+    protocol: Protocol,
     fallback_frames: u64,
+    window_s: f64,
 ) -> Option<f64> {
     let frames = dmx_store.frames_for_universe(universe, protocol);
     let mut last_ts = None;
@@ -195,7 +500,7 @@ fn fps_from_dmx(
     if last_ts <= earliest_ts || frame_count == 0 {
         return None;
     }
-    let window_start = last_ts - FPS_WINDOW_S;
+    let window_start = last_ts - window_s;
     let mut window_count = 0u64;
     for frame in dmx_store.frames_for_universe(universe, protocol) {
         if let Some(ts) = frame.timestamp {
@@ -204,10 +509,10 @@ fn fps_from_dmx(
             }
         }
     }
-    let window_duration = if last_ts - earliest_ts < FPS_WINDOW_S {
+    let window_duration = if last_ts - earliest_ts < window_s {
         last_ts - earliest_ts
     } else {
-        FPS_WINDOW_S
+        window_s
     };
     match window_duration {
         duration if duration > 0.0 && window_count > 0 => Some(window_count as f64 / duration),
@@ -230,25 +535,33 @@ fn update_source_stats(
     seq_reliable: bool,
     sequence: Option<u8>,
     ts: Option<f64>,
-) {
+    window_s: f64,
+) -> FrameSample {
+    let mut sample = FrameSample::default();
     stats.frames += 1;
 
     if stats.first_ts.is_none() {
         stats.first_ts = ts;
     }
+    if stats.interval_start.is_none() {
+        stats.interval_start = ts;
+    }
     if let Some(ts) = ts {
+        stats.activity.add(ts);
         stats.frame_samples.push_back(ts);
-        prune_frame_samples(&mut stats.frame_samples, ts);
+        prune_frame_samples(&mut stats.frame_samples, ts, window_s);
     }
 
     if let (Some(ts), Some(last_ts)) = (ts, stats.last_ts) {
         let iat = ts - last_ts;
+        stats.stability.add(ts, iat);
         if let Some(prev_iat) = stats.prev_iat {
             let diff = (iat - prev_iat).abs();
+            sample.jitter = Some(diff);
             stats.jitter_sum += diff;
             stats.jitter_samples.push_back((ts, diff));
             while let Some((sample_ts, sample)) = stats.jitter_samples.front().copied() {
-                if ts - sample_ts <= METRICS_WINDOW_S {
+                if ts - sample_ts <= window_s {
                     break;
                 }
                 stats.jitter_sum -= sample;
@@ -265,32 +578,39 @@ fn update_source_stats(
     }
     stats.last_ts = ts;
 
-    if !seq_reliable {
-        return;
-    }
-
     if let Some(seq) = sequence {
-        if let Some(last) = stats.last_seq {
+        if let Some(last) = stats.order_seq {
             let delta = seq.wrapping_sub(last);
             if delta == 0 {
                 stats.dup_packets += 1;
             } else if delta >= 128 {
                 stats.reordered_packets += 1;
             }
+        }
+        stats.order_seq = Some(seq);
+    }
+
+    if !seq_reliable {
+        return sample;
+    }
+
+    if let Some(seq) = sequence {
+        if let Some(last) = stats.last_seq {
             let expected = last.wrapping_add(1);
             let gap = seq.wrapping_sub(expected) as u16;
             if gap > 0 && gap < 128 {
                 stats.loss += gap as u64;
+                sample.lost = gap as u64;
                 if let Some(ts) = ts {
                     stats.loss_sum += gap as u64;
                     stats.loss_samples.push_back((ts, gap as u64));
-                    prune_loss_samples(&mut stats.loss_samples, &mut stats.loss_sum, ts);
+                    prune_loss_samples(&mut stats.loss_samples, &mut stats.loss_sum, ts, window_s);
                 }
                 if stats.current_burst == 0 {
                     stats.burst_count += 1;
                     if let Some(ts) = ts {
                         stats.burst_start_samples.push_back(ts);
-                        prune_burst_starts(&mut stats.burst_start_samples, ts);
+                        prune_burst_starts(&mut stats.burst_start_samples, ts, window_s);
                     }
                 }
                 stats.current_burst += gap as u64;
@@ -303,7 +623,7 @@ fn update_source_stats(
                         stats
                             .burst_length_samples
                             .push_back((ts, stats.current_burst));
-                        prune_burst_lengths(&mut stats.burst_length_samples, ts);
+                        prune_burst_lengths(&mut stats.burst_length_samples, ts, window_s);
                     }
                 }
                 stats.current_burst = 0;
@@ -311,11 +631,13 @@ fn update_source_stats(
         }
         stats.last_seq = Some(seq);
     }
+    sample
 }
 
 fn compute_metrics(per_source: &HashMap<String, UniverseSourceStats>) -> UniverseMetrics {
     let mut jitter_peak = None;
     let mut any_seq = false;
+    let mut any_order_seq = false;
     let mut total_seq_frames = 0u64;
     let mut total_seq_loss = 0u64;
     let mut total_seq_bursts = 0u64;
@@ -333,6 +655,9 @@ fn compute_metrics(per_source: &HashMap<String, UniverseSourceStats>) -> Univers
             if max_burst > total_seq_max_burst {
                 total_seq_max_burst = max_burst;
             }
+        }
+        if stats.order_seq.is_some() {
+            any_order_seq = true;
             total_dup_packets += stats.dup_packets;
             total_reordered_packets += stats.reordered_packets;
         }
@@ -367,12 +692,12 @@ fn compute_metrics(per_source: &HashMap<String, UniverseSourceStats>) -> Univers
         None
     };
     let jitter_ms = jitter_peak.map(|value| value * 1000.0);
-    let dup_packets = if any_seq {
+    let dup_packets = if any_order_seq {
         Some(total_dup_packets)
     } else {
         None
     };
-    let reordered_packets = if any_seq {
+    let reordered_packets = if any_order_seq {
         Some(total_reordered_packets)
     } else {
         None
@@ -449,18 +774,18 @@ fn max_burst_len_in_window(stats: &UniverseSourceStats) -> u64 {
     max_len
 }
 
-fn prune_frame_samples(samples: &mut VecDeque<f64>, now: f64) {
+fn prune_frame_samples(samples: &mut VecDeque<f64>, now: f64, window_s: f64) {
     while let Some(ts) = samples.front().copied() {
-        if now - ts <= METRICS_WINDOW_S {
+        if now - ts <= window_s {
             break;
         }
         samples.pop_front();
     }
 }
 
-fn prune_loss_samples(samples: &mut VecDeque<(f64, u64)>, sum: &mut u64, now: f64) {
+fn prune_loss_samples(samples: &mut VecDeque<(f64, u64)>, sum: &mut u64, now: f64, window_s: f64) {
     while let Some((ts, loss)) = samples.front().copied() {
-        if now - ts <= METRICS_WINDOW_S {
+        if now - ts <= window_s {
             break;
         }
         *sum = sum.saturating_sub(loss);
@@ -468,28 +793,94 @@ fn prune_loss_samples(samples: &mut VecDeque<(f64, u64)>, sum: &mut u64, now: f6
     }
 }
 
-fn prune_burst_starts(samples: &mut VecDeque<f64>, now: f64) {
+fn prune_burst_starts(samples: &mut VecDeque<f64>, now: f64, window_s: f64) {
     while let Some(ts) = samples.front().copied() {
-        if now - ts <= METRICS_WINDOW_S {
+        if now - ts <= window_s {
             break;
         }
         samples.pop_front();
     }
 }
 
-fn prune_burst_lengths(samples: &mut VecDeque<(f64, u64)>, now: f64) {
+fn prune_burst_lengths(samples: &mut VecDeque<(f64, u64)>, now: f64, window_s: f64) {
     while let Some((ts, _)) = samples.front().copied() {
-        if now - ts <= METRICS_WINDOW_S {
+        if now - ts <= window_s {
             break;
         }
         samples.pop_front();
     }
 }
 
+/// Intervals longer than the silence threshold between consecutive frames
+/// of the universes of `protocol`, in no particular order.
+pub(crate) fn build_silences(
+    stats: &mut HashMap<u16, UniverseStats>,
+    config: &AnalyzerConfig,
+    protocol: Protocol,
+) -> Vec<UniverseSilence> {
+    let mut silences = Vec::new();
+    for (universe, stats) in stats {
+        let times = &mut stats.frame_times;
+        times.sort_by(f64::total_cmp);
+        for pair in times.windows(2) {
+            let duration = pair[1] - pair[0];
+            if duration > config.silence_threshold_s {
+                silences.push(UniverseSilence {
+                    universe: *universe,
+                    proto: protocol,
+                    start: pair[0],
+                    duration_s: round_to(duration, DURATION_SCALE),
+                });
+            }
+        }
+    }
+    silences
+}
+
+/// sACN sources silent for longer than the source loss timeout without
+/// terminating their stream, including those still silent at `capture_end`.
+pub(crate) fn build_source_dropouts(
+    stats: &HashMap<u16, UniverseStats>,
+    capture_end: Option<f64>,
+) -> Vec<ReportEvent> {
+    let mut events = Vec::new();
+    for (universe, stats) in stats {
+        for (source_id, source) in &stats.per_source {
+            let mut dropouts: Vec<(f64, f64, bool)> = source
+                .dropouts
+                .iter()
+                .map(|&(start, duration)| (start, duration, true))
+                .collect();
+            if let (Some(end), Some(last), Some(_)) =
+                (capture_end, source.last_ts, source.interval_start)
+            {
+                if end - last > SOURCE_TIMEOUT_S {
+                    dropouts.push((last, end - last, false));
+                }
+            }
+            events.extend(
+                dropouts
+                    .into_iter()
+                    .map(|(timestamp, duration, resumed)| ReportEvent {
+                        kind: EventKind::SourceDropout,
+                        timestamp,
+                        universe: *universe,
+                        proto: Protocol::Sacn,
+                        source_id: source_id.clone(),
+                        duration_s: round_to(duration, DURATION_SCALE),
+                        resumed,
+                    }),
+            );
+        }
+    }
+    events
+}
+
 pub(crate) fn build_conflicts(
     stats: &HashMap<u16, UniverseStats>,
     dmx_store: &DmxStore,
-    proto: &str,
+    config: &AnalyzerConfig,
+    proto: Protocol,
 ) -> Vec<crate::ConflictSummary> {
     let mut conflicts = Vec::new();
 
@@ -503,19 +894,34 @@ pub(crate) fn build_conflicts(
                 let src_a_stats = &uni.per_source[src_a_key];
                 let src_b_stats = &uni.per_source[src_b_key];
 
-                let (start_a, end_a) = match (src_a_stats.first_ts, src_a_stats.last_ts) {
-                    (Some(start), Some(end)) => (start, end),
-                    _ => continue,
-                };
-                let (start_b, end_b) = match (src_b_stats.first_ts, src_b_stats.last_ts) {
-                    (Some(start), Some(end)) => (start, end),
-                    _ => continue,
+                // Sum the overlaps of the sources' active intervals, which
+                // stream terminations split.
+                let mut first_overlap = None;
+                let mut overlap_end = f64::NEG_INFINITY;
+                let mut overlap = 0.0;
+                for (start_a, end_a) in src_a_stats.active_intervals() {
+                    for (start_b, end_b) in src_b_stats.active_intervals() {
+                        let start = start_a.max(start_b);
+                        let end = end_a.min(end_b);
+                        let (start, duration) = if proto == Protocol::Sacn {
+                            equal_priority_overlap(
+                                dmx_store, *universe, src_a_key, src_b_key, start, end,
+                            )
+                        } else {
+                            (start, (end - start).max(0.0))
+                        };
+                        if duration > 0.0 {
+                            first_overlap =
+                                Some(first_overlap.map_or(start, |first: f64| first.min(start)));
+                            overlap_end = overlap_end.max(end);
+                            overlap += duration;
+                        }
+                    }
+                }
+                let Some(overlap_start) = first_overlap else {
+                    continue;
                 };
-
-                let overlap_start = start_a.max(start_b);
-                let overlap_end = end_a.min(end_b);
-                let overlap = (overlap_end - overlap_start).max(0.0);
-                if overlap > CONFLICT_MIN_OVERLAP_S {
+                if overlap > config.conflict_min_overlap_s {
                     let src_a_label = source_label(src_a_key);
                     let src_b_label = source_label(src_b_key);
                     let affected_channels = compute_affected_channels(
@@ -529,12 +935,14 @@ pub(crate) fn build_conflicts(
                     conflicts.push(crate::ConflictSummary {
                         universe: *universe,
                         sources: vec![src_a_label, src_b_label],
-                        proto: Some(proto.to_string()),
+                        proto: Some(proto),
                         overlap_duration_s: overlap,
                         affected_channels,
-                        severity: "medium".to_string(),
+                        severity: Severity::Warning,
                         conflict_score: overlap,
                         first_seen: Some(overlap_start),
+                        channel_labels: Vec::new(),
+                        rig_labels: Vec::new(),
                     });
                 }
             }
@@ -544,11 +952,62 @@ pub(crate) fn build_conflicts(
     conflicts.sort_by(|a, b| {
         a.universe
             .cmp(&b.universe)
-            .then_with(|| a.sources.join(",").cmp(&b.sources.join(",")))
+            .then_with(|| a.sources.cmp(&b.sources))
     });
     conflicts
 }
 
+/// Start and duration of the time within `[start, end]` during which two
+/// sACN sources sent at the same priority. Receivers follow the higher
+/// priority, so sources only compete while tied; the rest of the overlap is
+/// a takeover. The whole window when the frames were not stored.
+fn equal_priority_overlap(
+    dmx_store: &DmxStore,
+    universe: u16,
+    src_a_key: &str,
+    src_b_key: &str,
+    start: f64,
+    end: f64,
+) -> (f64, f64) {
+    let sources = [src_a_key, src_b_key].map(|source_id| {
+        dmx_store
+            .frames_for(universe, source_id)
+            .zip(dmx_store.writes_for(universe, source_id))
+    });
+    let [Some(a), Some(b)] = sources else {
+        return (start, (end - start).max(0.0));
+    };
+    let mut events: Vec<(f64, usize, u8)> = [a, b]
+        .into_iter()
+        .enumerate()
+        .flat_map(|(index, (frames, writes))| {
+            frames.iter().zip(writes).filter_map(move |(frame, write)| {
+                Some((frame.timestamp?, index, write.priority.unwrap_or_default()))
+            })
+        })
+        .collect();
+    events.sort_by(|x, y| x.0.total_cmp(&y.0).then(x.1.cmp(&y.1)));
+
+    let mut priorities = [None; 2];
+    let mut previous = None;
+    let mut first = None;
+    let mut tied = 0.0;
+    for (ts, index, priority) in events {
+        if let Some(previous) = previous {
+            if priorities[0].is_some() && priorities[0] == priorities[1] {
+                let (from, to) = (f64::max(previous, start), f64::min(ts, end));
+                if to > from {
+                    first.get_or_insert(from);
+                    tied += to - from;
+                }
+            }
+        }
+        priorities[index] = Some(priority);
+        previous = Some(ts);
+    }
+    (first.unwrap_or(start), tied)
+}
+
 fn compute_affected_channels(
     dmx_store: &DmxStore,
     universe: u16,
@@ -601,24 +1060,53 @@ fn source_label(key: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::{
-        UniverseSourceStats, UniverseStats, add_artnet_frame, build_artnet_universe_summaries,
-        build_conflicts, compute_metrics, update_source_stats,
+        UniverseSourceStats, UniverseStats, add_artnet_frame, add_destination,
+        build_artnet_universe_summaries, build_conflicts, build_silences, compute_metrics,
+        update_source_stats,
     };
+    use crate::analysis::config::{AnalyzerConfig, DEFAULT_METRICS_WINDOW_S};
     use crate::{
-        SourceSummary,
-        analysis::dmx::{DmxFrame, DmxProtocol, DmxStore},
+        DestinationKind, Protocol, SourceSummary,
+        analysis::dmx::{DmxFrame, DmxStore},
     };
     use std::collections::{HashMap, VecDeque};
     use std::net::IpAddr;
 
+    #[test]
+    fn silences_span_gaps_between_frames_of_any_source() {
+        let mut stats = HashMap::new();
+        let config = AnalyzerConfig::default();
+        let main: IpAddr = "10.0.0.1".parse().unwrap();
+        let backup: IpAddr = "10.0.0.2".parse().unwrap();
+        // Out of arrival order: the backup's frame at 2.5 s fills the gap.
+        for (ip, ts) in [(main, 0.0), (main, 4.0), (backup, 2.5), (main, 4.5)] {
+            add_artnet_frame(&mut stats, &config, 1, &ip, 6454, None, Some(ts));
+        }
+        let silences = build_silences(&mut stats, &config, Protocol::ArtNet);
+        let spans: Vec<_> = silences
+            .iter()
+            .map(|silence| (silence.start, silence.duration_s))
+            .collect();
+        assert_eq!(spans, vec![(0.0, 2.5), (2.5, 1.5)]);
+    }
+
     #[test]
     fn universe_summary_without_timestamps_has_no_metrics() {
         let mut stats = HashMap::new();
         let ip: IpAddr = "10.0.0.1".parse().unwrap();
-        add_artnet_frame(&mut stats, 1, &ip, 6454, None, None);
+        add_artnet_frame(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            1,
+            &ip,
+            6454,
+            None,
+            None,
+        );
 
         let dmx_store = DmxStore::default();
-        let summaries = build_artnet_universe_summaries(stats, &dmx_store);
+        let summaries =
+            build_artnet_universe_summaries(stats, &dmx_store, &AnalyzerConfig::default());
         assert_eq!(summaries.len(), 1);
         let summary = &summaries[0];
         assert_eq!(summary.universe, 1);
@@ -630,19 +1118,75 @@ mod tests {
         assert!(summary.jitter_ms.is_none());
     }
 
+    #[test]
+    fn destinations_are_counted_and_classified() {
+        let mut stats = HashMap::new();
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let config = AnalyzerConfig::default();
+        for dst in ["10.0.0.50", "2.255.255.255", "10.0.0.50"] {
+            add_artnet_frame(&mut stats, &config, 1, &ip, 6454, None, Some(1.0));
+            add_destination(&mut stats, 1, dst.parse().unwrap());
+        }
+
+        let summaries = build_artnet_universe_summaries(stats, &DmxStore::default(), &config);
+        let destinations = &summaries[0].destinations;
+        assert_eq!(destinations.len(), 2);
+        assert_eq!(destinations[0].dst_ip, "10.0.0.50");
+        assert_eq!(destinations[0].kind, DestinationKind::Unicast);
+        assert_eq!(destinations[0].packets, 2);
+        assert_eq!(destinations[1].kind, DestinationKind::Broadcast);
+    }
+
     #[test]
     fn conflict_requires_overlap_over_one_second() {
         let mut stats = HashMap::new();
         let ip_a: IpAddr = "10.0.0.1".parse().unwrap();
         let ip_b: IpAddr = "10.0.0.2".parse().unwrap();
 
-        add_artnet_frame(&mut stats, 1, &ip_a, 6454, None, Some(0.0));
-        add_artnet_frame(&mut stats, 1, &ip_a, 6454, None, Some(2.5));
-        add_artnet_frame(&mut stats, 1, &ip_b, 6454, None, Some(1.0));
-        add_artnet_frame(&mut stats, 1, &ip_b, 6454, None, Some(3.0));
+        add_artnet_frame(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            1,
+            &ip_a,
+            6454,
+            None,
+            Some(0.0),
+        );
+        add_artnet_frame(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            1,
+            &ip_a,
+            6454,
+            None,
+            Some(2.5),
+        );
+        add_artnet_frame(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            1,
+            &ip_b,
+            6454,
+            None,
+            Some(1.0),
+        );
+        add_artnet_frame(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            1,
+            &ip_b,
+            6454,
+            None,
+            Some(3.0),
+        );
 
         let dmx_store = DmxStore::default();
-        let conflicts = build_conflicts(&stats, &dmx_store, "artnet");
+        let conflicts = build_conflicts(
+            &stats,
+            &dmx_store,
+            &AnalyzerConfig::default(),
+            Protocol::ArtNet,
+        );
         assert_eq!(conflicts.len(), 1);
         let conflict = &conflicts[0];
         assert_eq!(conflict.universe, 1);
@@ -663,11 +1207,28 @@ mod tests {
     fn universe_summaries_are_sorted_by_universe() {
         let mut stats = HashMap::new();
         let ip: IpAddr = "10.0.0.1".parse().unwrap();
-        add_artnet_frame(&mut stats, 2, &ip, 6454, None, Some(1.0));
-        add_artnet_frame(&mut stats, 1, &ip, 6454, None, Some(2.0));
+        add_artnet_frame(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            2,
+            &ip,
+            6454,
+            None,
+            Some(1.0),
+        );
+        add_artnet_frame(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            1,
+            &ip,
+            6454,
+            None,
+            Some(2.0),
+        );
 
         let dmx_store = DmxStore::new();
-        let summaries = build_artnet_universe_summaries(stats, &dmx_store);
+        let summaries =
+            build_artnet_universe_summaries(stats, &dmx_store, &AnalyzerConfig::default());
         let universes: Vec<u16> = summaries.into_iter().map(|s| s.universe).collect();
         assert_eq!(universes, vec![1, 2]);
     }
@@ -683,6 +1244,9 @@ mod tests {
                 cid: Some("cid-2".to_string()),
                 source_name: None,
                 source_id: None,
+                ttl: None,
+                stability: None,
+                terminated_at: Vec::new(),
             },
         );
         universe.sources.insert(
@@ -692,12 +1256,16 @@ mod tests {
                 cid: Some("cid-1".to_string()),
                 source_name: None,
                 source_id: None,
+                ttl: None,
+                stability: None,
+                terminated_at: Vec::new(),
             },
         );
         stats.insert(1, universe);
 
         let dmx_store = DmxStore::new();
-        let summaries = build_artnet_universe_summaries(stats, &dmx_store);
+        let summaries =
+            build_artnet_universe_summaries(stats, &dmx_store, &AnalyzerConfig::default());
         let sources = &summaries[0].sources;
         assert_eq!(sources.len(), 2);
         assert_eq!(sources[0].cid.as_deref(), Some("cid-1"));
@@ -714,17 +1282,86 @@ mod tests {
         let ip_b: IpAddr = "10.0.0.2".parse().unwrap();
         let ip_c: IpAddr = "10.0.0.3".parse().unwrap();
 
-        add_artnet_frame(&mut stats, 2, &ip_b, 6454, None, Some(1.0));
-        add_artnet_frame(&mut stats, 2, &ip_b, 6454, None, Some(4.0));
-        add_artnet_frame(&mut stats, 2, &ip_c, 6454, None, Some(1.5));
-        add_artnet_frame(&mut stats, 2, &ip_c, 6454, None, Some(4.5));
-        add_artnet_frame(&mut stats, 1, &ip_a, 6454, None, Some(1.0));
-        add_artnet_frame(&mut stats, 1, &ip_a, 6454, None, Some(3.5));
-        add_artnet_frame(&mut stats, 1, &ip_b, 6454, None, Some(1.5));
-        add_artnet_frame(&mut stats, 1, &ip_b, 6454, None, Some(4.0));
+        add_artnet_frame(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            2,
+            &ip_b,
+            6454,
+            None,
+            Some(1.0),
+        );
+        add_artnet_frame(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            2,
+            &ip_b,
+            6454,
+            None,
+            Some(4.0),
+        );
+        add_artnet_frame(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            2,
+            &ip_c,
+            6454,
+            None,
+            Some(1.5),
+        );
+        add_artnet_frame(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            2,
+            &ip_c,
+            6454,
+            None,
+            Some(4.5),
+        );
+        add_artnet_frame(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            1,
+            &ip_a,
+            6454,
+            None,
+            Some(1.0),
+        );
+        add_artnet_frame(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            1,
+            &ip_a,
+            6454,
+            None,
+            Some(3.5),
+        );
+        add_artnet_frame(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            1,
+            &ip_b,
+            6454,
+            None,
+            Some(1.5),
+        );
+        add_artnet_frame(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            1,
+            &ip_b,
+            6454,
+            None,
+            Some(4.0),
+        );
 
         let dmx_store = DmxStore::new();
-        let conflicts = build_conflicts(&stats, &dmx_store, "artnet");
+        let conflicts = build_conflicts(
+            &stats,
+            &dmx_store,
+            &AnalyzerConfig::default(),
+            Protocol::ArtNet,
+        );
 
         assert_eq!(conflicts.len(), 2);
         assert_eq!(conflicts[0].universe, 1);
@@ -736,10 +1373,34 @@ mod tests {
     #[test]
     fn jitter_uses_sliding_window() {
         let mut source_stats = UniverseSourceStats::default();
-        update_source_stats(&mut source_stats, true, None, Some(0.0));
-        update_source_stats(&mut source_stats, true, None, Some(1.0));
-        update_source_stats(&mut source_stats, true, None, Some(2.0));
-        update_source_stats(&mut source_stats, true, None, Some(13.0));
+        update_source_stats(
+            &mut source_stats,
+            true,
+            None,
+            Some(0.0),
+            DEFAULT_METRICS_WINDOW_S,
+        );
+        update_source_stats(
+            &mut source_stats,
+            true,
+            None,
+            Some(1.0),
+            DEFAULT_METRICS_WINDOW_S,
+        );
+        update_source_stats(
+            &mut source_stats,
+            true,
+            None,
+            Some(2.0),
+            DEFAULT_METRICS_WINDOW_S,
+        );
+        update_source_stats(
+            &mut source_stats,
+            true,
+            None,
+            Some(13.0),
+            DEFAULT_METRICS_WINDOW_S,
+        );
 
         let mut per_source = HashMap::new();
         per_source.insert("artnet:10.0.0.1:6454".to_string(), source_stats);
@@ -817,9 +1478,27 @@ mod tests {
     #[test]
     fn sacn_dup_packets_are_counted() {
         let mut stats = UniverseSourceStats::default();
-        update_source_stats(&mut stats, true, Some(10), Some(0.0));
-        update_source_stats(&mut stats, true, Some(10), Some(1.0));
-        update_source_stats(&mut stats, true, Some(11), Some(2.0));
+        update_source_stats(
+            &mut stats,
+            true,
+            Some(10),
+            Some(0.0),
+            DEFAULT_METRICS_WINDOW_S,
+        );
+        update_source_stats(
+            &mut stats,
+            true,
+            Some(10),
+            Some(1.0),
+            DEFAULT_METRICS_WINDOW_S,
+        );
+        update_source_stats(
+            &mut stats,
+            true,
+            Some(11),
+            Some(2.0),
+            DEFAULT_METRICS_WINDOW_S,
+        );
 
         assert_eq!(stats.dup_packets, 1);
         assert_eq!(stats.reordered_packets, 0);
@@ -828,21 +1507,84 @@ mod tests {
     #[test]
     fn sacn_reordered_packets_are_counted() {
         let mut stats = UniverseSourceStats::default();
-        update_source_stats(&mut stats, true, Some(10), Some(0.0));
-        update_source_stats(&mut stats, true, Some(9), Some(1.0));
-        update_source_stats(&mut stats, true, Some(11), Some(2.0));
+        update_source_stats(
+            &mut stats,
+            true,
+            Some(10),
+            Some(0.0),
+            DEFAULT_METRICS_WINDOW_S,
+        );
+        update_source_stats(
+            &mut stats,
+            true,
+            Some(9),
+            Some(1.0),
+            DEFAULT_METRICS_WINDOW_S,
+        );
+        update_source_stats(
+            &mut stats,
+            true,
+            Some(11),
+            Some(2.0),
+            DEFAULT_METRICS_WINDOW_S,
+        );
 
         assert_eq!(stats.dup_packets, 0);
         assert_eq!(stats.reordered_packets, 1);
     }
 
+    #[test]
+    fn artnet_dup_and_reordered_packets_are_counted() {
+        let mut stats = UniverseSourceStats::default();
+        for (seq, ts) in [(254, 0.0), (254, 1.0), (255, 2.0), (1, 3.0), (255, 4.0)] {
+            update_source_stats(
+                &mut stats,
+                false,
+                Some(seq),
+                Some(ts),
+                DEFAULT_METRICS_WINDOW_S,
+            );
+        }
+
+        assert_eq!(stats.dup_packets, 1);
+        assert_eq!(stats.reordered_packets, 1);
+        assert_eq!(stats.last_seq, None);
+        let metrics = compute_metrics(&HashMap::from([("artnet".to_string(), stats)]));
+        assert_eq!(metrics.dup_packets, Some(1));
+        assert!(metrics.loss_packets.is_none());
+    }
+
     #[test]
     fn sacn_wraparound_is_not_reordered() {
         let mut stats = UniverseSourceStats::default();
-        update_source_stats(&mut stats, true, Some(254), Some(0.0));
-        update_source_stats(&mut stats, true, Some(255), Some(1.0));
-        update_source_stats(&mut stats, true, Some(0), Some(2.0));
-        update_source_stats(&mut stats, true, Some(1), Some(3.0));
+        update_source_stats(
+            &mut stats,
+            true,
+            Some(254),
+            Some(0.0),
+            DEFAULT_METRICS_WINDOW_S,
+        );
+        update_source_stats(
+            &mut stats,
+            true,
+            Some(255),
+            Some(1.0),
+            DEFAULT_METRICS_WINDOW_S,
+        );
+        update_source_stats(
+            &mut stats,
+            true,
+            Some(0),
+            Some(2.0),
+            DEFAULT_METRICS_WINDOW_S,
+        );
+        update_source_stats(
+            &mut stats,
+            true,
+            Some(1),
+            Some(3.0),
+            DEFAULT_METRICS_WINDOW_S,
+        );
 
         assert_eq!(stats.reordered_packets, 0);
     }
@@ -851,10 +1593,42 @@ mod tests {
     fn fps_uses_last_five_seconds() {
         let mut stats = HashMap::new();
         let ip: IpAddr = "10.0.0.1".parse().unwrap();
-        add_artnet_frame(&mut stats, 1, &ip, 6454, None, Some(0.0));
-        add_artnet_frame(&mut stats, 1, &ip, 6454, None, Some(1.0));
-        add_artnet_frame(&mut stats, 1, &ip, 6454, None, Some(2.0));
-        add_artnet_frame(&mut stats, 1, &ip, 6454, None, Some(7.0));
+        add_artnet_frame(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            1,
+            &ip,
+            6454,
+            None,
+            Some(0.0),
+        );
+        add_artnet_frame(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            1,
+            &ip,
+            6454,
+            None,
+            Some(1.0),
+        );
+        add_artnet_frame(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            1,
+            &ip,
+            6454,
+            None,
+            Some(2.0),
+        );
+        add_artnet_frame(
+            &mut stats,
+            &AnalyzerConfig::default(),
+            1,
+            &ip,
+            6454,
+            None,
+            Some(7.0),
+        );
 
         let mut dmx_store = DmxStore::default();
         let mut slots = [0u8; 512];
@@ -864,12 +1638,13 @@ mod tests {
                 universe: 1,
                 timestamp: Some(ts),
                 source_id: "artnet:10.0.0.1:6454".to_string(),
-                protocol: DmxProtocol::ArtNet,
+                protocol: Protocol::ArtNet,
                 slots,
             });
         }
 
-        let summaries = build_artnet_universe_summaries(stats, &dmx_store);
+        let summaries =
+            build_artnet_universe_summaries(stats, &dmx_store, &AnalyzerConfig::default());
         let fps = summaries[0].fps.unwrap_or(0.0);
         assert!((fps - 0.4).abs() < 0.0001);
     }
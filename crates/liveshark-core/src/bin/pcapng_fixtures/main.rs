@@ -1,6 +1,11 @@
+use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use scenario::Scenario;
+
+mod scenario;
+
 const ETHERTYPE_IPV4: u16 = 0x0800;
 const UDP_PROTO: u8 = 17;
 const ARTNET_PORT: u16 = 6454;
@@ -21,6 +26,7 @@ const SACN_ACN_PID_RANGE: std::ops::Range<usize> = 4..16;
 const SACN_ROOT_VECTOR_RANGE: std::ops::Range<usize> = 18..22;
 const SACN_CID_RANGE: std::ops::Range<usize> = 22..38;
 const SACN_FRAMING_VECTOR_RANGE: std::ops::Range<usize> = 40..44;
+const SACN_PRIORITY_OFFSET: usize = 108;
 const SACN_SEQUENCE_OFFSET: usize = 111;
 const SACN_UNIVERSE_RANGE: std::ops::Range<usize> = 113..115;
 const SACN_DMP_VECTOR_OFFSET: usize = 117;
@@ -35,14 +41,42 @@ const SACN_ROOT_VECTOR_DATA: u32 = 0x0000_0004;
 const SACN_FRAMING_VECTOR_DMX: u32 = 0x0000_0002;
 const SACN_DMP_VECTOR_SET_PROPERTY: u8 = 0x02;
 
+/// Without arguments, writes the built-in fixtures and every
+/// `tests/golden/*/scenario.json`; otherwise only the given scenario files.
+/// A scenario's capture is written next to it as `input.pcapng`.
 fn main() -> Result<(), String> {
+    let args: Vec<PathBuf> = env::args_os().skip(1).map(PathBuf::from).collect();
+    if !args.is_empty() {
+        return args.iter().try_for_each(|path| write_scenario(path));
+    }
     let root = PathBuf::from("tests/golden");
     write_sacn_fixtures(&root)?;
     write_artnet_fixtures(&root)?;
     write_flow_fixtures(&root)?;
+    write_scenario_fixtures(&root)?;
     Ok(())
 }
 
+fn write_scenario_fixtures(root: &Path) -> Result<(), String> {
+    let entries =
+        fs::read_dir(root).map_err(|err| format!("failed to read {}: {}", root.display(), err))?;
+    let mut scenarios = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|err| format!("failed to read entry: {}", err))?;
+        let path = entry.path().join("scenario.json");
+        if path.exists() {
+            scenarios.push(path);
+        }
+    }
+    scenarios.sort();
+    scenarios.iter().try_for_each(|path| write_scenario(path))
+}
+
+fn write_scenario(path: &Path) -> Result<(), String> {
+    let output = path.with_file_name("input.pcapng");
+    Scenario::load(path)?.write(&output)
+}
+
 fn write_sacn_fixtures(root: &Path) -> Result<(), String> {
     write_capture(
         root.join("sacn_burst").join("input.pcapng"),
@@ -115,7 +149,7 @@ fn write_capture(path: PathBuf, spec: CaptureSpec) -> Result<(), String> {
     let mut packets = Vec::new();
     for (idx, seq) in spec.sequences.iter().copied().enumerate() {
         let payload = match spec.protocol {
-            Protocol::Sacn => build_sacn_payload(seq, &[seq, 0x00], 1),
+            Protocol::Sacn => build_sacn_payload(seq, &[seq, 0x00], 1, &cid_bytes(0), 0),
             Protocol::ArtNet => build_artnet_payload(seq, &[seq, 0x00], 1),
         };
         let frame = match spec.protocol {
@@ -169,16 +203,23 @@ fn build_artnet_payload(sequence: u8, slots: &[u8], universe: u16) -> Vec<u8> {
     payload
 }
 
-fn build_sacn_payload(sequence: u8, slots: &[u8], universe: u16) -> Vec<u8> {
+fn build_sacn_payload(
+    sequence: u8,
+    slots: &[u8],
+    universe: u16,
+    cid: &[u8; 16],
+    priority: u8,
+) -> Vec<u8> {
     let length = slots.len().min(SACN_DMX_MAX_SLOTS);
     let mut payload = vec![0u8; SACN_DMX_DATA_OFFSET + length];
     payload[SACN_PREAMBLE_SIZE_RANGE.clone()].copy_from_slice(&SACN_PREAMBLE_SIZE.to_be_bytes());
     payload[SACN_POSTAMBLE_SIZE_RANGE.clone()].copy_from_slice(&SACN_POSTAMBLE_SIZE.to_be_bytes());
     payload[SACN_ACN_PID_RANGE.clone()].copy_from_slice(SACN_ACN_PID);
     payload[SACN_ROOT_VECTOR_RANGE.clone()].copy_from_slice(&SACN_ROOT_VECTOR_DATA.to_be_bytes());
-    payload[SACN_CID_RANGE.clone()].copy_from_slice(&cid_bytes());
+    payload[SACN_CID_RANGE.clone()].copy_from_slice(cid);
     payload[SACN_FRAMING_VECTOR_RANGE.clone()]
         .copy_from_slice(&SACN_FRAMING_VECTOR_DMX.to_be_bytes());
+    payload[SACN_PRIORITY_OFFSET] = priority;
     payload[SACN_SEQUENCE_OFFSET] = sequence;
     payload[SACN_UNIVERSE_RANGE.clone()].copy_from_slice(&universe.to_be_bytes());
     payload[SACN_DMP_VECTOR_OFFSET] = SACN_DMP_VECTOR_SET_PROPERTY;
@@ -189,10 +230,11 @@ fn build_sacn_payload(sequence: u8, slots: &[u8], universe: u16) -> Vec<u8> {
     payload
 }
 
-fn cid_bytes() -> [u8; 16] {
+/// CID of the `source`-th sender; distinct senders get distinct CIDs.
+fn cid_bytes(source: usize) -> [u8; 16] {
     let mut cid = [0u8; 16];
     for (idx, value) in cid.iter_mut().enumerate() {
-        *value = idx as u8;
+        *value = (idx + 16 * source) as u8;
     }
     cid
 }
@@ -0,0 +1,215 @@
+//! Scenario descriptions for synthetic captures.
+//!
+//! A scenario is a JSON file listing DMX sources; each source sends frames
+//! of one universe at a steady rate, optionally with gaps (frames that are
+//! not sent but still consume a sequence number) and malformed packets.
+//! Conflicts and takeovers follow from several sources sharing a universe.
+//!
+//! ```json
+//! {
+//!   "sources": [
+//!     { "protocol": "sacn", "universe": 1, "src_ip": "10.0.0.1",
+//!       "priority": 100, "fps": 4.0, "frames": 24,
+//!       "gaps": [{ "start_s": 2.0, "duration_s": 1.0 }] },
+//!     { "protocol": "sacn", "universe": 1, "src_ip": "10.0.0.3",
+//!       "priority": 150, "start_s": 4.0, "fps": 4.0, "frames": 8,
+//!       "malformed": [{ "frame": 2, "kind": "bad_start_code" }] }
+//!   ]
+//! }
+//! ```
+
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use super::{
+    ARTNET_LENGTH_RANGE, ARTNET_PORT, SACN_PORT, SACN_START_CODE_OFFSET, build_artnet_payload,
+    build_ipv4_udp_packet, build_sacn_payload, cid_bytes, write_pcapng,
+};
+
+/// Sources of a capture; packets of all sources are merged by timestamp.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Scenario {
+    sources: Vec<Source>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum Protocol {
+    ArtNet,
+    Sacn,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Source {
+    protocol: Protocol,
+    universe: u16,
+    src_ip: String,
+    #[serde(default = "default_dst_ip")]
+    dst_ip: String,
+    /// UDP source port; the protocol's port when absent.
+    #[serde(default)]
+    src_port: Option<u16>,
+    /// sACN priority.
+    #[serde(default = "default_priority")]
+    priority: u8,
+    /// Timestamp (seconds) of the first frame.
+    #[serde(default)]
+    start_s: f64,
+    fps: f64,
+    frames: usize,
+    /// Sequence numbers, one per frame; counting up from 1 when absent.
+    #[serde(default)]
+    sequences: Option<Vec<u8>>,
+    /// Slot values of every frame.
+    #[serde(default = "default_slots")]
+    slots: Vec<u8>,
+    #[serde(default)]
+    gaps: Vec<Gap>,
+    #[serde(default)]
+    malformed: Vec<Malformed>,
+}
+
+/// Frames not sent; their sequence numbers are skipped.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Gap {
+    start_s: f64,
+    duration_s: f64,
+}
+
+/// A frame replaced by a malformed packet.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Malformed {
+    /// Index of the frame, from 0.
+    frame: usize,
+    kind: MalformedKind,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum MalformedKind {
+    /// Payload cut in the middle of the header.
+    Truncated,
+    /// Length field (Art-Net) or property count (sACN) larger than the data.
+    BadLength,
+    /// Non-zero DMX start code (sACN only).
+    BadStartCode,
+}
+
+fn default_dst_ip() -> String {
+    "10.0.0.2".to_string()
+}
+
+fn default_priority() -> u8 {
+    100
+}
+
+fn default_slots() -> Vec<u8> {
+    vec![0; 2]
+}
+
+impl Scenario {
+    /// Read a scenario from a JSON file.
+    pub(crate) fn load(path: &Path) -> Result<Self, String> {
+        let json = fs::read_to_string(path)
+            .map_err(|err| format!("failed to read {}: {}", path.display(), err))?;
+        serde_json::from_str(&json).map_err(|err| format!("invalid {}: {}", path.display(), err))
+    }
+
+    /// Write the capture described by the scenario to `path`.
+    pub(crate) fn write(&self, path: &Path) -> Result<(), String> {
+        let mut packets = Vec::new();
+        for (index, source) in self.sources.iter().enumerate() {
+            source.packets(index, &mut packets)?;
+        }
+        // Stable: frames of the same instant keep the order of their sources.
+        packets.sort_by_key(|(ts_us, _)| *ts_us);
+        write_pcapng(path, &packets)
+    }
+}
+
+impl Source {
+    fn packets(&self, index: usize, packets: &mut Vec<(u64, Vec<u8>)>) -> Result<(), String> {
+        if self.fps <= 0.0 {
+            return Err(format!("source {index}: fps must be positive"));
+        }
+        if let Some(sequences) = &self.sequences {
+            if sequences.len() != self.frames {
+                return Err(format!("source {index}: one sequence number per frame"));
+            }
+        }
+        let port = match self.protocol {
+            Protocol::ArtNet => ARTNET_PORT,
+            Protocol::Sacn => SACN_PORT,
+        };
+        let cid = cid_bytes(index);
+        let mut sequence = 0u8;
+        for frame in 0..self.frames {
+            sequence = match &self.sequences {
+                Some(sequences) => sequences[frame],
+                None => next_sequence(self.protocol, sequence),
+            };
+            let ts = self.start_s + frame as f64 / self.fps;
+            if self
+                .gaps
+                .iter()
+                .any(|gap| ts >= gap.start_s && ts < gap.start_s + gap.duration_s)
+            {
+                continue;
+            }
+            let mut payload = match self.protocol {
+                Protocol::ArtNet => build_artnet_payload(sequence, &self.slots, self.universe),
+                Protocol::Sacn => {
+                    build_sacn_payload(sequence, &self.slots, self.universe, &cid, self.priority)
+                }
+            };
+            if let Some(malformed) = self.malformed.iter().find(|bad| bad.frame == frame) {
+                self.corrupt(&mut payload, malformed.kind)
+                    .map_err(|err| format!("source {index}, frame {frame}: {err}"))?;
+            }
+            let packet = build_ipv4_udp_packet(
+                &self.src_ip,
+                &self.dst_ip,
+                self.src_port.unwrap_or(port),
+                port,
+                &payload,
+            );
+            packets.push(((ts * 1_000_000.0).round() as u64, packet));
+        }
+        Ok(())
+    }
+
+    fn corrupt(&self, payload: &mut Vec<u8>, kind: MalformedKind) -> Result<(), String> {
+        match (kind, self.protocol) {
+            (MalformedKind::Truncated, _) => payload.truncate(12),
+            (MalformedKind::BadLength, Protocol::ArtNet) => {
+                let length = (self.slots.len() + 2) as u16;
+                payload[ARTNET_LENGTH_RANGE.clone()].copy_from_slice(&length.to_be_bytes());
+            }
+            (MalformedKind::BadLength, Protocol::Sacn) => {
+                payload.truncate(payload.len() - 1);
+            }
+            (MalformedKind::BadStartCode, Protocol::Sacn) => {
+                payload[SACN_START_CODE_OFFSET] = 0x17;
+            }
+            (MalformedKind::BadStartCode, Protocol::ArtNet) => {
+                return Err("Art-Net has no start code".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Sequence number after `last`: Art-Net skips 0, which disables
+/// sequencing.
+fn next_sequence(protocol: Protocol, last: u8) -> u8 {
+    match (protocol, last.wrapping_add(1)) {
+        (Protocol::ArtNet, 0) => 1,
+        (_, next) => next,
+    }
+}
@@ -0,0 +1,252 @@
+//! Clock alignment of captures taken on different machines.
+//!
+//! Two capture hosts never share a clock: their offset can reach seconds and
+//! their rates differ by tens of ppm, so merged timelines drift apart. The
+//! UDP datagrams seen by both captures are matched by content (endpoints and
+//! payload). A coarse offset is voted from the datagrams that rarely repeat,
+//! the datagrams are then paired around it, and a least-squares line through
+//! the pairs gives the offset and skew that map the other capture's clock
+//! onto the reference's. The offset also absorbs the network latency
+//! between the two capture points.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+
+use crate::analysis::round_to;
+use crate::analysis::udp::parse_udp_packet;
+use crate::{ClockCorrection, PacketEvent, PacketSource, SourceError};
+
+/// Width of the bins the coarse offset is voted in (seconds).
+const COARSE_BIN_S: f64 = 0.01;
+/// Datagrams seen more often than this in either capture (e.g. a static
+/// DMX look) are left out of the coarse vote, which would otherwise be
+/// flooded with every pairing of their copies.
+const MAX_VOTE_REPEATS: usize = 8;
+/// Largest distance from the coarse offset at which two datagrams still
+/// pair (seconds); covers the drift of a long capture.
+const PAIR_WINDOW_S: f64 = 0.5;
+/// Shortest span of paired datagrams (seconds) over which a skew is
+/// estimated; shorter captures get an offset only.
+const MIN_SKEW_SPAN_S: f64 = 10.0;
+/// Offsets and skews are rounded to three decimals (microseconds, ppb).
+const CORRECTION_SCALE: f64 = 1_000.0;
+
+/// Timestamps of each datagram content in one capture, in capture order.
+type DatagramTimes = HashMap<u64, VecDeque<f64>>;
+
+impl ClockCorrection {
+    /// Timestamp `ts` of the corrected capture on the reference clock.
+    pub fn apply(&self, ts: f64) -> f64 {
+        ts + self.offset_ms / 1000.0 + self.skew_ppm * 1e-6 * (ts - self.anchor_s)
+    }
+}
+
+/// Estimate the correction that maps the clock of `other` onto the clock of
+/// `reference`; `input` names the corrected capture in the report. Without
+/// any shared datagram the correction is zero and `matched_packets` is 0.
+///
+/// # Errors
+/// Returns `SourceError` when either source fails.
+///
+/// # Examples
+/// ```no_run
+/// use liveshark_core::{PcapFileSource, estimate_clock_correction};
+/// use std::path::Path;
+///
+/// let correction = estimate_clock_correction(
+///     "node-port.pcapng",
+///     PcapFileSource::open(Path::new("console-port.pcapng"))?,
+///     PcapFileSource::open(Path::new("node-port.pcapng"))?,
+/// )?;
+/// println!("offset {} ms, skew {} ppm", correction.offset_ms, correction.skew_ppm);
+/// # Ok::<(), liveshark_core::SourceError>(())
+/// ```
+pub fn estimate_clock_correction<R: PacketSource, O: PacketSource>(
+    input: &str,
+    reference: R,
+    other: O,
+) -> Result<ClockCorrection, SourceError> {
+    let reference = datagram_times(reference)?;
+    let other = datagram_times(other)?;
+    let anchor = other
+        .values()
+        .filter_map(|times| times.front().copied())
+        .reduce(f64::min)
+        .unwrap_or(0.0);
+    let mut correction = ClockCorrection {
+        input: input.to_string(),
+        anchor_s: anchor,
+        offset_ms: 0.0,
+        skew_ppm: 0.0,
+        matched_packets: 0,
+    };
+    let Some(coarse) = coarse_offset(&reference, &other) else {
+        return Ok(correction);
+    };
+    let pairs = pair_datagrams(reference, &other, coarse, anchor);
+    let Some((offset, skew)) = fit_line(&pairs) else {
+        return Ok(correction);
+    };
+    correction.offset_ms = round_to(offset * 1000.0, CORRECTION_SCALE);
+    correction.skew_ppm = round_to(skew * 1e6, CORRECTION_SCALE);
+    correction.matched_packets = pairs.len() as u64;
+    Ok(correction)
+}
+
+/// Timestamps of the UDP datagrams of a capture, keyed by content digest.
+fn datagram_times<S: PacketSource>(mut source: S) -> Result<DatagramTimes, SourceError> {
+    let mut times = DatagramTimes::new();
+    while let Some(PacketEvent {
+        ts, linktype, data, ..
+    }) = source.next_packet()?
+    {
+        if let (Some(ts), Ok(Some(udp))) = (ts, parse_udp_packet(linktype, &data)) {
+            let mut hasher = DefaultHasher::new();
+            (udp.src_ip, udp.src_port, udp.dst_ip, udp.dst_port).hash(&mut hasher);
+            udp.payload.hash(&mut hasher);
+            times.entry(hasher.finish()).or_default().push_back(ts);
+        }
+        source.recycle(data);
+    }
+    for queue in times.values_mut() {
+        queue.make_contiguous().sort_by(f64::total_cmp);
+    }
+    Ok(times)
+}
+
+/// Most voted reference-minus-other time difference of rarely repeated
+/// datagrams; ties go to the smallest difference.
+fn coarse_offset(reference: &DatagramTimes, other: &DatagramTimes) -> Option<f64> {
+    let mut votes: BTreeMap<i64, u64> = BTreeMap::new();
+    for (digest, other_times) in other {
+        let Some(reference_times) = reference.get(digest) else {
+            continue;
+        };
+        if other_times.len() > MAX_VOTE_REPEATS || reference_times.len() > MAX_VOTE_REPEATS {
+            continue;
+        }
+        for other_ts in other_times {
+            for reference_ts in reference_times {
+                let bin = ((reference_ts - other_ts) / COARSE_BIN_S).round() as i64;
+                *votes.entry(bin).or_default() += 1;
+            }
+        }
+    }
+    let mut best: Option<(i64, u64)> = None;
+    for (bin, count) in votes {
+        if best.is_none_or(|(_, most)| count > most) {
+            best = Some((bin, count));
+        }
+    }
+    best.map(|(bin, _)| bin as f64 * COARSE_BIN_S)
+}
+
+/// `(other_ts - anchor, reference_ts - other_ts)` for each datagram of
+/// `other` paired with the earliest unpaired copy in `reference` within
+/// `PAIR_WINDOW_S` of the coarse offset.
+fn pair_datagrams(
+    mut reference: DatagramTimes,
+    other: &DatagramTimes,
+    coarse: f64,
+    anchor: f64,
+) -> Vec<(f64, f64)> {
+    let mut pairs = Vec::new();
+    for (digest, other_times) in other {
+        let Some(queue) = reference.get_mut(digest) else {
+            continue;
+        };
+        for other_ts in other_times {
+            let expected = other_ts + coarse;
+            while queue
+                .front()
+                .is_some_and(|reference_ts| *reference_ts < expected - PAIR_WINDOW_S)
+            {
+                queue.pop_front();
+            }
+            if let Some(reference_ts) = queue
+                .front()
+                .copied()
+                .filter(|reference_ts| *reference_ts <= expected + PAIR_WINDOW_S)
+            {
+                queue.pop_front();
+                pairs.push((other_ts - anchor, reference_ts - other_ts));
+            }
+        }
+    }
+    pairs.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+    pairs
+}
+
+/// Least-squares `(offset, skew)` of the pairs; the skew stays 0 when the
+/// pairs span less than `MIN_SKEW_SPAN_S`.
+fn fit_line(pairs: &[(f64, f64)]) -> Option<(f64, f64)> {
+    let (first, last) = (pairs.first()?, pairs.last()?);
+    let count = pairs.len() as f64;
+    let mean_x = pairs.iter().map(|(x, _)| x).sum::<f64>() / count;
+    let mean_y = pairs.iter().map(|(_, y)| y).sum::<f64>() / count;
+    if last.0 - first.0 < MIN_SKEW_SPAN_S {
+        return Some((mean_y, 0.0));
+    }
+    let covariance: f64 = pairs.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let variance: f64 = pairs.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    let skew = covariance / variance;
+    Some((mean_y - skew * mean_x, skew))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::estimate_clock_correction;
+    use crate::PacketEvent;
+    use crate::test_util::{MemorySource, udp_event};
+
+    fn datagram(ts: f64, counter: u32) -> PacketEvent {
+        udp_event(
+            ts,
+            [10, 0, 0, 1],
+            [10, 0, 0, 2],
+            5000,
+            &counter.to_be_bytes(),
+        )
+    }
+
+    /// 40 datagrams per second for a minute, seen by a second host whose
+    /// clock is 2.5 s behind and runs 50 ppm fast.
+    fn captures() -> (MemorySource, MemorySource) {
+        let times: Vec<f64> = (0..2400)
+            .map(|index| 100.0 + f64::from(index) * 0.025)
+            .collect();
+        let reference = times
+            .iter()
+            .zip(0..)
+            .map(|(ts, counter)| datagram(*ts, counter))
+            .collect();
+        let other = times
+            .iter()
+            .zip(0..)
+            .map(|(ts, counter)| datagram((ts - 100.0) * (1.0 + 50e-6) + 97.5, counter))
+            .collect();
+        (reference, other)
+    }
+
+    #[test]
+    fn offset_and_skew_map_the_other_clock_onto_the_reference() {
+        let (reference, other) = captures();
+        let correction = estimate_clock_correction("other.pcapng", reference, other).unwrap();
+        assert_eq!(correction.matched_packets, 2400);
+        assert_eq!(correction.anchor_s, 97.5);
+        assert_eq!(correction.offset_ms, 2500.0);
+        assert!((correction.skew_ppm + 50.0).abs() < 0.01);
+        assert!((correction.apply(97.5 + 60.003) - 160.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn captures_without_shared_datagrams_are_left_alone() {
+        let (reference, _) = captures();
+        let other = MemorySource::from_iter([datagram(5.0, 99_999)]);
+        let correction = estimate_clock_correction("other.pcapng", reference, other).unwrap();
+        assert_eq!(correction.matched_packets, 0);
+        assert_eq!(correction.offset_ms, 0.0);
+        assert_eq!(correction.apply(5.0), 5.0);
+    }
+}
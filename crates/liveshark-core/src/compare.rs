@@ -0,0 +1,464 @@
+//! Compare the DMX output of a capture against a reference capture.
+//!
+//! A playback system (show controller, media server, console in playback
+//! mode) is expected to reproduce a recorded show. Both captures are reduced
+//! to the output of each universe, the last frame received from any source,
+//! sampled on a fixed grid. Times are relative to the first DMX frame of each
+//! capture, so the recordings only need to start at the same point of the
+//! show. Samples where a channel differs by more than the tolerance are
+//! merged into divergence spans listing the channels and the largest
+//! difference.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::analysis::round_to;
+use crate::{
+    DecodedPacket, DecodedPacketIter, DmxStateStore, PacketSource, PcapFileSource, SourceError,
+};
+
+/// Default spacing of the sampling grid (seconds): a few DMX frames at
+/// typical refresh rates.
+pub const DEFAULT_COMPARE_INTERVAL_S: f64 = 0.1;
+/// Times are rounded to microseconds, the resolution of pcap timestamps, so
+/// frames on a grid point land on the same tick in both captures.
+const TIME_SCALE: f64 = 1_000_000.0;
+
+/// Error returned when a comparison cannot run.
+#[derive(Debug, Error)]
+pub enum CompareError {
+    /// The comparison options are out of range.
+    #[error("invalid comparison options: {0}")]
+    InvalidOptions(String),
+    /// A capture could not be read.
+    #[error("source error: {0}")]
+    Source(#[from] SourceError),
+}
+
+/// Sampling grid and tolerance of a comparison.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{CompareOptions, DEFAULT_COMPARE_INTERVAL_S};
+///
+/// let options = CompareOptions::default();
+/// assert_eq!(options.interval_s, DEFAULT_COMPARE_INTERVAL_S);
+/// assert_eq!(options.tolerance, 0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompareOptions {
+    /// Spacing of the sampling grid (seconds).
+    pub interval_s: f64,
+    /// Largest slot difference still counted as a match.
+    pub tolerance: u8,
+}
+
+impl Default for CompareOptions {
+    fn default() -> Self {
+        Self {
+            interval_s: DEFAULT_COMPARE_INTERVAL_S,
+            tolerance: 0,
+        }
+    }
+}
+
+/// Which captures carried a universe.
+///
+/// Serializes in `snake_case`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UniversePresence {
+    /// Both captures carried the universe.
+    Both,
+    /// Only the reference carried the universe.
+    ReferenceOnly,
+    /// Only the incident capture carried the universe.
+    IncidentOnly,
+}
+
+/// Consecutive samples where the output differed from the reference.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::DmxDivergence;
+///
+/// let divergence = DmxDivergence {
+///     start_s: 12.3,
+///     end_s: 14.0,
+///     channels: vec![1, 2, 3],
+///     max_delta: 255,
+/// };
+/// assert!(divergence.end_s >= divergence.start_s);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DmxDivergence {
+    /// First divergent sample (seconds from the first DMX frame).
+    pub start_s: f64,
+    /// Last divergent sample (seconds from the first DMX frame).
+    pub end_s: f64,
+    /// Channels that differed in any sample of the span (1-based, sorted).
+    pub channels: Vec<u16>,
+    /// Largest difference of one channel in the span.
+    pub max_delta: u8,
+}
+
+/// Comparison result of one universe.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UniverseComparison {
+    /// Universe number.
+    pub universe: u16,
+    /// Which captures carried the universe.
+    pub presence: UniversePresence,
+    /// Samples where both captures had output for the universe.
+    pub samples: u64,
+    /// Samples where at least one channel differed.
+    pub divergent_samples: u64,
+    /// Divergence spans, in time order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub divergences: Vec<DmxDivergence>,
+}
+
+impl UniverseComparison {
+    /// Returns true when both captures carried the universe and no sample
+    /// differed.
+    pub fn matches(&self) -> bool {
+        self.presence == UniversePresence::Both && self.divergent_samples == 0
+    }
+}
+
+/// Result of comparing an incident capture against a reference.
+///
+/// # Examples
+/// ```no_run
+/// use liveshark_core::{CompareOptions, compare_pcap_files};
+/// use std::path::Path;
+///
+/// let comparison = compare_pcap_files(
+///     Path::new("reference.pcapng"),
+///     Path::new("show.pcapng"),
+///     &CompareOptions::default(),
+/// )?;
+/// for universe in comparison.universes.iter().filter(|u| !u.matches()) {
+///     println!("universe {} diverges", universe.universe);
+/// }
+/// # Ok::<(), liveshark_core::CompareError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DmxComparison {
+    /// Spacing of the sampling grid (seconds).
+    pub interval_s: f64,
+    /// Largest slot difference counted as a match.
+    pub tolerance: u8,
+    /// Per-universe results, sorted by universe.
+    pub universes: Vec<UniverseComparison>,
+}
+
+impl DmxComparison {
+    /// Returns true when every universe matches the reference.
+    pub fn matches(&self) -> bool {
+        self.universes.iter().all(UniverseComparison::matches)
+    }
+}
+
+/// Compare the DMX output of two capture files.
+///
+/// # Errors
+/// Returns `CompareError::InvalidOptions` for a non-positive interval and
+/// `CompareError::Source` when a capture cannot be read.
+pub fn compare_pcap_files(
+    reference: &Path,
+    incident: &Path,
+    options: &CompareOptions,
+) -> Result<DmxComparison, CompareError> {
+    compare_sources(
+        PcapFileSource::open(reference)?,
+        PcapFileSource::open(incident)?,
+        options,
+    )
+}
+
+/// Compare the DMX output of two packet sources.
+///
+/// # Errors
+/// Returns `CompareError::InvalidOptions` for a non-positive interval and
+/// `CompareError::Source` when a source fails.
+pub fn compare_sources<R: PacketSource, I: PacketSource>(
+    reference: R,
+    incident: I,
+    options: &CompareOptions,
+) -> Result<DmxComparison, CompareError> {
+    let interval = options.interval_s;
+    if !interval.is_finite() || interval <= 0.0 {
+        return Err(CompareError::InvalidOptions(format!(
+            "interval_s must be a positive number of seconds (got {interval})"
+        )));
+    }
+    let reference = sample_output(reference, interval)?;
+    let incident = sample_output(incident, interval)?;
+
+    let universe_ids: BTreeSet<u16> = reference.keys().chain(incident.keys()).copied().collect();
+    let universes = universe_ids
+        .into_iter()
+        .map(
+            |universe| match (reference.get(&universe), incident.get(&universe)) {
+                (Some(expected), Some(actual)) => {
+                    compare_universe(universe, expected, actual, options)
+                }
+                (expected, _) => UniverseComparison {
+                    universe,
+                    presence: if expected.is_some() {
+                        UniversePresence::ReferenceOnly
+                    } else {
+                        UniversePresence::IncidentOnly
+                    },
+                    samples: 0,
+                    divergent_samples: 0,
+                    divergences: Vec::new(),
+                },
+            },
+        )
+        .collect();
+    Ok(DmxComparison {
+        interval_s: interval,
+        tolerance: options.tolerance,
+        universes,
+    })
+}
+
+/// Output of one universe on the sampling grid: each change holds the slots
+/// shown from its tick on, so a static look costs one entry whatever the
+/// capture length.
+#[derive(Debug, Default)]
+struct SampledUniverse {
+    changes: Vec<(u64, [u8; 512])>,
+    end_tick: u64,
+}
+
+impl SampledUniverse {
+    fn first_tick(&self) -> u64 {
+        self.changes.first().map_or(0, |(tick, _)| *tick)
+    }
+
+    /// Show `slots` from `tick` on; frames of the same tick replace each
+    /// other and late frames apply to the latest tick.
+    fn show(&mut self, tick: u64, slots: [u8; 512]) {
+        match self.changes.last_mut() {
+            Some((_, current)) if *current == slots => {}
+            Some((last, current)) if *last >= tick => *current = slots,
+            _ => self.changes.push((tick, slots)),
+        }
+    }
+}
+
+fn sample_output<S: PacketSource>(
+    source: S,
+    interval: f64,
+) -> Result<BTreeMap<u16, SampledUniverse>, SourceError> {
+    let mut state = DmxStateStore::new();
+    let mut universes: BTreeMap<u16, SampledUniverse> = BTreeMap::new();
+    let mut origin: Option<f64> = None;
+    let mut last_tick = 0u64;
+    for packet in DecodedPacketIter::new(source) {
+        let frame = match packet? {
+            DecodedPacket::ArtDmx {
+                ts: Some(ts),
+                endpoints,
+                frame,
+            } => state.apply_artdmx(&endpoints, Some(ts), &frame),
+            DecodedPacket::SacnDmx {
+                ts: Some(ts),
+                endpoints,
+                frame,
+            } => state.apply_sacn_dmx(&endpoints, Some(ts), &frame),
+            _ => continue,
+        };
+        let Some(ts) = frame.timestamp else {
+            continue;
+        };
+        let origin = *origin.get_or_insert(ts);
+        // Tick `k` samples the output just before `k * interval`, so a frame
+        // shows from the tick after its arrival.
+        let elapsed = round_to((ts - origin).max(0.0), TIME_SCALE);
+        let tick = round_to(elapsed / interval, TIME_SCALE).floor() as u64 + 1;
+        last_tick = last_tick.max(tick);
+        universes
+            .entry(frame.universe)
+            .or_default()
+            .show(tick, frame.slots);
+    }
+    for universe in universes.values_mut() {
+        universe.end_tick = last_tick + 1;
+    }
+    Ok(universes)
+}
+
+/// Slots shown at `tick`; `cursor` only moves forward.
+fn slots_at(universe: &SampledUniverse, cursor: &mut usize, tick: u64) -> [u8; 512] {
+    while universe
+        .changes
+        .get(*cursor + 1)
+        .is_some_and(|(next, _)| *next <= tick)
+    {
+        *cursor += 1;
+    }
+    universe.changes[*cursor].1
+}
+
+fn compare_universe(
+    universe: u16,
+    expected: &SampledUniverse,
+    actual: &SampledUniverse,
+    options: &CompareOptions,
+) -> UniverseComparison {
+    let first = expected.first_tick().max(actual.first_tick());
+    let end = expected.end_tick.min(actual.end_tick);
+    let time = |tick: u64| round_to(tick as f64 * options.interval_s, TIME_SCALE);
+
+    let mut comparison = UniverseComparison {
+        universe,
+        presence: UniversePresence::Both,
+        samples: end.saturating_sub(first),
+        divergent_samples: 0,
+        divergences: Vec::new(),
+    };
+    // Both outputs are constant between change ticks, so each segment is
+    // compared once.
+    let boundaries: BTreeSet<u64> = expected
+        .changes
+        .iter()
+        .chain(&actual.changes)
+        .map(|(tick, _)| *tick)
+        .filter(|tick| (first..end).contains(tick))
+        .chain((first < end).then_some(first))
+        .collect();
+    let boundaries: Vec<u64> = boundaries.into_iter().chain([end]).collect();
+    let (mut left_cursor, mut right_cursor) = (0, 0);
+    let mut open: Option<(u64, u64, BTreeSet<u16>, u8)> = None;
+    for segment in boundaries.windows(2) {
+        let (from, to) = (segment[0], segment[1]);
+        let left = slots_at(expected, &mut left_cursor, from);
+        let right = slots_at(actual, &mut right_cursor, from);
+        let mut channels = Vec::new();
+        let mut max_delta = 0u8;
+        for (index, (a, b)) in left.iter().zip(right.iter()).enumerate() {
+            let delta = a.abs_diff(*b);
+            if delta > options.tolerance {
+                channels.push(index as u16 + 1);
+                max_delta = max_delta.max(delta);
+            }
+        }
+        if channels.is_empty() {
+            continue;
+        }
+        comparison.divergent_samples += to - from;
+        match &mut open {
+            Some((_, last, span_channels, span_max)) if *last + 1 == from => {
+                *last = to - 1;
+                span_channels.extend(channels);
+                *span_max = (*span_max).max(max_delta);
+            }
+            _ => {
+                if let Some(span) = open.take() {
+                    comparison.divergences.push(close_span(span, time));
+                }
+                open = Some((from, to - 1, channels.into_iter().collect(), max_delta));
+            }
+        }
+    }
+    if let Some(span) = open {
+        comparison.divergences.push(close_span(span, time));
+    }
+    comparison
+}
+
+fn close_span(
+    (start, end, channels, max_delta): (u64, u64, BTreeSet<u16>, u8),
+    time: impl Fn(u64) -> f64,
+) -> DmxDivergence {
+    DmxDivergence {
+        start_s: time(start),
+        end_s: time(end),
+        channels: channels.into_iter().collect(),
+        max_delta,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CompareError, CompareOptions, UniversePresence, compare_sources};
+    use crate::PacketEvent;
+    use crate::test_util::{ArtDmxBuilder, MemorySource, udp_event};
+
+    fn artdmx(ts: f64, universe: u16, slots: [u8; 2]) -> PacketEvent {
+        let payload = ArtDmxBuilder::new(universe).slots(slots).build();
+        udp_event(ts, [10, 0, 0, 1], [10, 0, 0, 255], 6454, &payload)
+    }
+
+    /// One second of universe 1 at 10 fps; slot 1 is `level` from `from_s` on.
+    fn show(start: f64, from_s: f64, level: u8) -> MemorySource {
+        (0..10)
+            .map(|step| {
+                let t = f64::from(step) * 0.1;
+                let slot = if t >= from_s { level } else { 255 };
+                artdmx(start + t, 1, [slot, 50])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn identical_output_matches_regardless_of_start_time() {
+        let comparison = compare_sources(
+            show(0.0, 2.0, 0),
+            show(500.0, 2.0, 0),
+            &CompareOptions::default(),
+        )
+        .unwrap();
+        assert!(comparison.matches());
+        assert_eq!(comparison.universes[0].samples, 10);
+    }
+
+    #[test]
+    fn divergence_is_located_in_time_and_channels() {
+        let mut incident = show(0.0, 0.45, 100);
+        incident.push(artdmx(0.2, 2, [1, 1]));
+        let comparison =
+            compare_sources(show(0.0, 2.0, 0), incident, &CompareOptions::default()).unwrap();
+        assert!(!comparison.matches());
+
+        let universe = &comparison.universes[0];
+        assert_eq!(universe.divergent_samples, 5);
+        assert_eq!(universe.divergences.len(), 1);
+        let divergence = &universe.divergences[0];
+        assert_eq!((divergence.start_s, divergence.end_s), (0.6, 1.0));
+        assert_eq!(divergence.channels, vec![1]);
+        assert_eq!(divergence.max_delta, 155);
+        assert_eq!(
+            comparison.universes[1].presence,
+            UniversePresence::IncidentOnly
+        );
+
+        let tolerant = CompareOptions {
+            tolerance: 200,
+            ..CompareOptions::default()
+        };
+        let incident = show(0.0, 0.45, 100);
+        assert!(
+            compare_sources(show(0.0, 2.0, 0), incident, &tolerant)
+                .unwrap()
+                .matches()
+        );
+    }
+
+    #[test]
+    fn interval_must_be_positive() {
+        let options = CompareOptions {
+            interval_s: 0.0,
+            ..CompareOptions::default()
+        };
+        let err = compare_sources(show(0.0, 0.0, 0), show(0.0, 0.0, 0), &options).unwrap_err();
+        assert!(matches!(err, CompareError::InvalidOptions(_)));
+    }
+}
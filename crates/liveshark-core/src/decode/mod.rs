@@ -0,0 +1,963 @@
+//! Per-packet decoding without aggregation.
+//!
+//! `DecodedPacketIter` wraps any `PacketSource` and classifies each packet as
+//! ArtDMX, ArtNzs, ArtPollReply, ArtSync, ArtTimeCode, ArtTrigger, ArtAddress,
+//! ArtTodRequest, ArtTodData, ArtRdm, sACN DMX, sACN synchronization, other UDP, or non-UDP traffic, using the same parsers as the analysis pipeline. It keeps
+//! no state between packets, so tools can consume LiveShark's decoding
+//! without building a report. `DecodedPacket` displays as a one-line summary
+//! for packet dumps.
+//!
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+
+use pcap_parser::Linktype;
+
+use crate::Protocol;
+use crate::analysis::udp::{UdpPacket, parse_udp_packet};
+use crate::protocols::artnet::error::ArtNetError;
+use crate::protocols::artnet::{
+    ArtAddress, ArtDmx, ArtNzs, ArtPollReply, ArtRdm, ArtSync, ArtTimeCode, ArtTodData,
+    ArtTodRequest, ArtTrigger,
+};
+use crate::protocols::sacn::error::SacnError;
+use crate::protocols::sacn::{SacnDiscovery, SacnDmx, SacnSync};
+use crate::protocols::{
+    decode_artaddress, decode_artdmx, decode_artnzs, decode_artpollreply, decode_artrdm,
+    decode_artsync, decode_arttimecode, decode_arttoddata, decode_arttodrequest, decode_arttrigger,
+    decode_sacn_address_priority, decode_sacn_discovery, decode_sacn_dmx, decode_sacn_sync,
+};
+use crate::source::{PacketEvent, PacketSource, SourceError};
+
+/// UDP source and destination endpoints of a packet.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::Endpoints;
+///
+/// let endpoints = Endpoints {
+///     src_ip: "10.0.0.1".parse().unwrap(),
+///     src_port: 6454,
+///     dst_ip: "10.0.0.255".parse().unwrap(),
+///     dst_port: 6454,
+/// };
+/// assert_eq!(endpoints.src_port, 6454);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Endpoints {
+    pub src_ip: IpAddr,
+    pub src_port: u16,
+    pub dst_ip: IpAddr,
+    pub dst_port: u16,
+}
+
+impl Endpoints {
+    pub(crate) fn from_udp(udp: &UdpPacket<'_>) -> Self {
+        Self {
+            src_ip: udp.src_ip,
+            src_port: udp.src_port,
+            dst_ip: udp.dst_ip,
+            dst_port: udp.dst_port,
+        }
+    }
+}
+
+/// Classification of a single captured packet.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{DecodedPacket, PacketEvent, decode_packet};
+/// use pcap_parser::Linktype;
+///
+/// let event = PacketEvent {
+///     ts: Some(1.0),
+///     linktype: Linktype::NULL,
+///     data: vec![0u8; 4],
+///     orig_len: None,
+/// };
+/// let decoded = decode_packet(&event);
+/// assert!(matches!(decoded, DecodedPacket::NonUdp { .. }));
+/// assert_eq!(decoded.ts(), Some(1.0));
+/// assert_eq!(decoded.to_string(), "1.000000 - non-udp linktype=0");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodedPacket {
+    /// Valid ArtDMX packet.
+    ArtDmx {
+        ts: Option<f64>,
+        endpoints: Endpoints,
+        frame: ArtDmx,
+    },
+    /// Valid ArtNzs packet (non-zero start code data).
+    ArtNzs {
+        ts: Option<f64>,
+        endpoints: Endpoints,
+        frame: ArtNzs,
+    },
+    /// Valid ArtPollReply packet.
+    ArtPollReply {
+        ts: Option<f64>,
+        endpoints: Endpoints,
+        reply: ArtPollReply,
+    },
+    /// Valid ArtSync packet.
+    ArtSync {
+        ts: Option<f64>,
+        endpoints: Endpoints,
+        sync: ArtSync,
+    },
+    /// Valid ArtTimeCode packet.
+    ArtTimeCode {
+        ts: Option<f64>,
+        endpoints: Endpoints,
+        timecode: ArtTimeCode,
+    },
+    /// Valid ArtTrigger packet.
+    ArtTrigger {
+        ts: Option<f64>,
+        endpoints: Endpoints,
+        trigger: ArtTrigger,
+    },
+    /// Valid ArtAddress packet.
+    ArtAddress {
+        ts: Option<f64>,
+        endpoints: Endpoints,
+        address: ArtAddress,
+    },
+    /// Valid ArtTodRequest packet.
+    ArtTodRequest {
+        ts: Option<f64>,
+        endpoints: Endpoints,
+        request: ArtTodRequest,
+    },
+    /// Valid ArtTodData packet.
+    ArtTodData {
+        ts: Option<f64>,
+        endpoints: Endpoints,
+        tod: ArtTodData,
+    },
+    /// Valid ArtRdm packet.
+    ArtRdm {
+        ts: Option<f64>,
+        endpoints: Endpoints,
+        rdm: ArtRdm,
+    },
+    /// Valid sACN DMX packet.
+    SacnDmx {
+        ts: Option<f64>,
+        endpoints: Endpoints,
+        frame: SacnDmx,
+    },
+    /// Valid sACN per-address priority packet (start code 0xdd).
+    SacnAddressPriority {
+        ts: Option<f64>,
+        endpoints: Endpoints,
+        priorities: SacnDmx,
+    },
+    /// Valid sACN synchronization packet.
+    SacnSync {
+        ts: Option<f64>,
+        endpoints: Endpoints,
+        sync: SacnSync,
+    },
+    /// Valid sACN universe discovery packet.
+    SacnDiscovery {
+        ts: Option<f64>,
+        endpoints: Endpoints,
+        discovery: SacnDiscovery,
+    },
+    /// UDP packet that is neither Art-Net nor sACN.
+    OtherUdp {
+        ts: Option<f64>,
+        endpoints: Endpoints,
+        payload_len: usize,
+    },
+    /// Packet without a UDP layer (other transports or link types).
+    NonUdp { ts: Option<f64>, linktype: Linktype },
+    /// Packet rejected by the UDP, Art-Net, or sACN parser.
+    Malformed {
+        ts: Option<f64>,
+        endpoints: Option<Endpoints>,
+        protocol: Protocol,
+        reason: String,
+    },
+}
+
+impl DecodedPacket {
+    /// Packet timestamp in seconds (if available).
+    pub fn ts(&self) -> Option<f64> {
+        match self {
+            DecodedPacket::ArtDmx { ts, .. }
+            | DecodedPacket::ArtNzs { ts, .. }
+            | DecodedPacket::ArtPollReply { ts, .. }
+            | DecodedPacket::ArtSync { ts, .. }
+            | DecodedPacket::ArtTimeCode { ts, .. }
+            | DecodedPacket::ArtTrigger { ts, .. }
+            | DecodedPacket::ArtAddress { ts, .. }
+            | DecodedPacket::ArtTodRequest { ts, .. }
+            | DecodedPacket::ArtTodData { ts, .. }
+            | DecodedPacket::ArtRdm { ts, .. }
+            | DecodedPacket::SacnDmx { ts, .. }
+            | DecodedPacket::SacnAddressPriority { ts, .. }
+            | DecodedPacket::SacnSync { ts, .. }
+            | DecodedPacket::SacnDiscovery { ts, .. }
+            | DecodedPacket::OtherUdp { ts, .. }
+            | DecodedPacket::NonUdp { ts, .. }
+            | DecodedPacket::Malformed { ts, .. } => *ts,
+        }
+    }
+
+    /// UDP endpoints, when the packet carried a UDP layer.
+    pub fn endpoints(&self) -> Option<&Endpoints> {
+        match self {
+            DecodedPacket::ArtDmx { endpoints, .. }
+            | DecodedPacket::ArtNzs { endpoints, .. }
+            | DecodedPacket::ArtPollReply { endpoints, .. }
+            | DecodedPacket::ArtSync { endpoints, .. }
+            | DecodedPacket::ArtTimeCode { endpoints, .. }
+            | DecodedPacket::ArtTrigger { endpoints, .. }
+            | DecodedPacket::ArtAddress { endpoints, .. }
+            | DecodedPacket::ArtTodRequest { endpoints, .. }
+            | DecodedPacket::ArtTodData { endpoints, .. }
+            | DecodedPacket::ArtRdm { endpoints, .. }
+            | DecodedPacket::SacnDmx { endpoints, .. }
+            | DecodedPacket::SacnAddressPriority { endpoints, .. }
+            | DecodedPacket::SacnSync { endpoints, .. }
+            | DecodedPacket::SacnDiscovery { endpoints, .. }
+            | DecodedPacket::OtherUdp { endpoints, .. } => Some(endpoints),
+            DecodedPacket::Malformed { endpoints, .. } => endpoints.as_ref(),
+            DecodedPacket::NonUdp { .. } => None,
+        }
+    }
+
+    /// Protocol the packet was decoded (or rejected) as; `None` without a
+    /// UDP layer.
+    pub fn protocol(&self) -> Option<Protocol> {
+        match self {
+            DecodedPacket::ArtDmx { .. }
+            | DecodedPacket::ArtNzs { .. }
+            | DecodedPacket::ArtPollReply { .. }
+            | DecodedPacket::ArtSync { .. }
+            | DecodedPacket::ArtTimeCode { .. }
+            | DecodedPacket::ArtTrigger { .. }
+            | DecodedPacket::ArtAddress { .. }
+            | DecodedPacket::ArtTodRequest { .. }
+            | DecodedPacket::ArtTodData { .. }
+            | DecodedPacket::ArtRdm { .. } => Some(Protocol::ArtNet),
+            DecodedPacket::SacnDmx { .. }
+            | DecodedPacket::SacnAddressPriority { .. }
+            | DecodedPacket::SacnSync { .. }
+            | DecodedPacket::SacnDiscovery { .. } => Some(Protocol::Sacn),
+            DecodedPacket::OtherUdp { .. } => Some(Protocol::Udp),
+            DecodedPacket::Malformed { protocol, .. } => Some(*protocol),
+            DecodedPacket::NonUdp { .. } => None,
+        }
+    }
+
+    /// Universe of ArtDMX, ArtNzs, ArtTodData, ArtRdm, sACN DMX, and sACN
+    /// per-address priority packets.
+    pub fn universe(&self) -> Option<u16> {
+        match self {
+            DecodedPacket::ArtDmx { frame, .. } => Some(frame.universe),
+            DecodedPacket::ArtNzs { frame, .. } => Some(frame.universe),
+            DecodedPacket::ArtTodData { tod, .. } => Some(tod.universe),
+            DecodedPacket::ArtRdm { rdm, .. } => Some(rdm.universe),
+            DecodedPacket::SacnDmx { frame, .. } => Some(frame.universe),
+            DecodedPacket::SacnAddressPriority { priorities, .. } => Some(priorities.universe),
+            _ => None,
+        }
+    }
+}
+
+/// One line: timestamp (seconds, `-` when unknown), endpoints, protocol,
+/// then the packet's key fields or the decode error.
+impl fmt::Display for DecodedPacket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.ts() {
+            Some(ts) => write!(f, "{ts:.6} ")?,
+            None => f.write_str("- ")?,
+        }
+        if let Some(endpoints) = self.endpoints() {
+            write!(
+                f,
+                "{} -> {} ",
+                SocketAddr::new(endpoints.src_ip, endpoints.src_port),
+                SocketAddr::new(endpoints.dst_ip, endpoints.dst_port)
+            )?;
+        } else {
+            f.write_str("- ")?;
+        }
+        match self {
+            DecodedPacket::ArtDmx { frame, .. } => {
+                write!(f, "artnet dmx universe={}", frame.universe)?;
+                write_sequence(f, frame.sequence)?;
+                write!(f, " slots={}", frame.slots.len())
+            }
+            DecodedPacket::ArtNzs { frame, .. } => {
+                write!(f, "artnet nzs universe={}", frame.universe)?;
+                write_sequence(f, frame.sequence)?;
+                write!(
+                    f,
+                    " start_code=0x{:02x} len={}",
+                    frame.start_code,
+                    frame.data.len()
+                )
+            }
+            DecodedPacket::ArtPollReply { reply, .. } => {
+                write!(f, "artnet poll-reply node={}", reply.ip)?;
+                if let Some(name) = &reply.short_name {
+                    write!(f, " name={name:?}")?;
+                }
+                write!(f, " outputs={}", reply.outputs.len())
+            }
+            DecodedPacket::ArtSync { .. } => f.write_str("artnet sync"),
+            DecodedPacket::ArtTimeCode { timecode, .. } => {
+                write!(f, "artnet timecode {timecode}")?;
+                if timecode.stream_id != 0 {
+                    write!(f, " stream={}", timecode.stream_id)?;
+                }
+                match timecode.frame_rate() {
+                    Some(rate) => write!(f, " fps={rate}"),
+                    None => write!(f, " type={}", timecode.timecode_type),
+                }
+            }
+            DecodedPacket::ArtTrigger { trigger, .. } => {
+                write!(
+                    f,
+                    "artnet trigger oem=0x{:04x} key={}",
+                    trigger.oem, trigger.key
+                )?;
+                if let Some(name) = trigger.key_name() {
+                    write!(f, " ({name})")?;
+                }
+                write!(f, " sub_key={}", trigger.sub_key)
+            }
+            DecodedPacket::ArtAddress { address, .. } => {
+                write!(f, "artnet address bind_index={}", address.bind_index)?;
+                if let Some(name) = &address.short_name {
+                    write!(f, " name={name:?}")?;
+                }
+                if address.command != 0 {
+                    write!(f, " command=0x{:02x}", address.command)?;
+                }
+                Ok(())
+            }
+            DecodedPacket::ArtTodRequest { request, .. } => {
+                write!(f, "artnet tod-request universes=")?;
+                for (index, universe) in request.universes.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{universe}")?;
+                }
+                Ok(())
+            }
+            DecodedPacket::ArtTodData { tod, .. } => write!(
+                f,
+                "artnet tod-data universe={} port={} uids={}/{} block={}",
+                tod.universe,
+                tod.port,
+                tod.uids.len(),
+                tod.uid_total,
+                tod.block_count
+            ),
+            DecodedPacket::ArtRdm { rdm, .. } => {
+                let message = &rdm.message;
+                write!(
+                    f,
+                    "artnet rdm universe={} {} -> {} cc=0x{:02x} pid=0x{:04x} tn={}",
+                    rdm.universe,
+                    message.source,
+                    message.destination,
+                    message.command_class,
+                    message.parameter_id,
+                    message.transaction
+                )
+            }
+            DecodedPacket::SacnDmx { frame, .. } => {
+                write!(f, "sacn dmx universe={}", frame.universe)?;
+                write_sequence(f, frame.sequence)?;
+                write!(
+                    f,
+                    " priority={} slots={}",
+                    frame.priority,
+                    frame.slots.len()
+                )?;
+                if frame.stream_terminated() {
+                    f.write_str(" terminated")?;
+                }
+                Ok(())
+            }
+            DecodedPacket::SacnAddressPriority { priorities, .. } => {
+                write!(f, "sacn priority universe={}", priorities.universe)?;
+                write_sequence(f, priorities.sequence)?;
+                write!(f, " slots={}", priorities.slots.len())
+            }
+            DecodedPacket::SacnSync { sync, .. } => write!(
+                f,
+                "sacn sync address={} seq={}",
+                sync.sync_address, sync.sequence
+            ),
+            DecodedPacket::SacnDiscovery { discovery, .. } => write!(
+                f,
+                "sacn discovery page={}/{} universes={}",
+                discovery.page,
+                discovery.last_page,
+                discovery.universes.len()
+            ),
+            DecodedPacket::OtherUdp { payload_len, .. } => write!(f, "udp len={payload_len}"),
+            DecodedPacket::NonUdp { linktype, .. } => write!(f, "non-udp linktype={}", linktype.0),
+            DecodedPacket::Malformed {
+                protocol, reason, ..
+            } => write!(f, "{protocol} malformed: {reason}"),
+        }
+    }
+}
+
+fn write_sequence(f: &mut fmt::Formatter<'_>, sequence: Option<u8>) -> fmt::Result {
+    match sequence {
+        Some(sequence) => write!(f, " seq={sequence}"),
+        None => Ok(()),
+    }
+}
+
+/// Decode a single packet event.
+///
+/// Art-Net is tried before sACN, and node replies and synchronization
+/// packets before DMX data, matching the analysis pipeline. A UDP payload
+/// too short to hold an Art-Net or sACN header is reported as `OtherUdp`, not
+/// as a malformed packet, since there are not enough bytes to identify it.
+pub fn decode_packet(event: &PacketEvent) -> DecodedPacket {
+    let ts = event.ts;
+    let udp = match parse_udp_packet(event.linktype, &event.data) {
+        Ok(Some(udp)) => udp,
+        Ok(None) => {
+            return DecodedPacket::NonUdp {
+                ts,
+                linktype: event.linktype,
+            };
+        }
+        Err(err) => {
+            return DecodedPacket::Malformed {
+                ts,
+                endpoints: None,
+                protocol: Protocol::Udp,
+                reason: err.to_string(),
+            };
+        }
+    };
+    let endpoints = Endpoints::from_udp(&udp);
+
+    if let Ok(Some(reply)) = decode_artpollreply(udp.payload) {
+        return DecodedPacket::ArtPollReply {
+            ts,
+            endpoints,
+            reply,
+        };
+    }
+    if let Ok(Some(sync)) = decode_artsync(udp.payload) {
+        return DecodedPacket::ArtSync {
+            ts,
+            endpoints,
+            sync,
+        };
+    }
+    // The opcode identifies ArtTimeCode, ArtTrigger, ArtAddress, the RDM
+    // packets, and ArtNzs, so even a truncated one is malformed.
+    match decode_arttimecode(udp.payload) {
+        Ok(Some(timecode)) => {
+            return DecodedPacket::ArtTimeCode {
+                ts,
+                endpoints,
+                timecode,
+            };
+        }
+        Ok(None) => {}
+        Err(err) => {
+            return DecodedPacket::Malformed {
+                ts,
+                endpoints: Some(endpoints),
+                protocol: Protocol::ArtNet,
+                reason: err.to_string(),
+            };
+        }
+    }
+    match decode_arttrigger(udp.payload) {
+        Ok(Some(trigger)) => {
+            return DecodedPacket::ArtTrigger {
+                ts,
+                endpoints,
+                trigger,
+            };
+        }
+        Ok(None) => {}
+        Err(err) => {
+            return DecodedPacket::Malformed {
+                ts,
+                endpoints: Some(endpoints),
+                protocol: Protocol::ArtNet,
+                reason: err.to_string(),
+            };
+        }
+    }
+    match decode_artaddress(udp.payload) {
+        Ok(Some(address)) => {
+            return DecodedPacket::ArtAddress {
+                ts,
+                endpoints,
+                address,
+            };
+        }
+        Ok(None) => {}
+        Err(err) => {
+            return DecodedPacket::Malformed {
+                ts,
+                endpoints: Some(endpoints),
+                protocol: Protocol::ArtNet,
+                reason: err.to_string(),
+            };
+        }
+    }
+    match decode_arttodrequest(udp.payload) {
+        Ok(Some(request)) => {
+            return DecodedPacket::ArtTodRequest {
+                ts,
+                endpoints,
+                request,
+            };
+        }
+        Ok(None) => {}
+        Err(err) => {
+            return DecodedPacket::Malformed {
+                ts,
+                endpoints: Some(endpoints),
+                protocol: Protocol::ArtNet,
+                reason: err.to_string(),
+            };
+        }
+    }
+    match decode_arttoddata(udp.payload) {
+        Ok(Some(tod)) => {
+            return DecodedPacket::ArtTodData { ts, endpoints, tod };
+        }
+        Ok(None) => {}
+        Err(err) => {
+            return DecodedPacket::Malformed {
+                ts,
+                endpoints: Some(endpoints),
+                protocol: Protocol::ArtNet,
+                reason: err.to_string(),
+            };
+        }
+    }
+    match decode_artrdm(udp.payload) {
+        Ok(Some(rdm)) => {
+            return DecodedPacket::ArtRdm { ts, endpoints, rdm };
+        }
+        Ok(None) => {}
+        Err(err) => {
+            return DecodedPacket::Malformed {
+                ts,
+                endpoints: Some(endpoints),
+                protocol: Protocol::ArtNet,
+                reason: err.to_string(),
+            };
+        }
+    }
+    match decode_artnzs(udp.payload) {
+        Ok(Some(frame)) => {
+            return DecodedPacket::ArtNzs {
+                ts,
+                endpoints,
+                frame,
+            };
+        }
+        Ok(None) => {}
+        Err(err) => {
+            return DecodedPacket::Malformed {
+                ts,
+                endpoints: Some(endpoints),
+                protocol: Protocol::ArtNet,
+                reason: err.to_string(),
+            };
+        }
+    }
+    match decode_artdmx(udp.payload) {
+        Ok(Some(frame)) => {
+            return DecodedPacket::ArtDmx {
+                ts,
+                endpoints,
+                frame,
+            };
+        }
+        Ok(None) | Err(ArtNetError::TooShort { .. }) => {}
+        Err(err) => {
+            return DecodedPacket::Malformed {
+                ts,
+                endpoints: Some(endpoints),
+                protocol: Protocol::ArtNet,
+                reason: err.to_string(),
+            };
+        }
+    }
+
+    if let Ok(Some(sync)) = decode_sacn_sync(udp.payload) {
+        return DecodedPacket::SacnSync {
+            ts,
+            endpoints,
+            sync,
+        };
+    }
+    if let Ok(Some(priorities)) = decode_sacn_address_priority(udp.payload) {
+        return DecodedPacket::SacnAddressPriority {
+            ts,
+            endpoints,
+            priorities,
+        };
+    }
+    if let Ok(Some(discovery)) = decode_sacn_discovery(udp.payload) {
+        return DecodedPacket::SacnDiscovery {
+            ts,
+            endpoints,
+            discovery,
+        };
+    }
+    match decode_sacn_dmx(udp.payload) {
+        Ok(Some(frame)) => DecodedPacket::SacnDmx {
+            ts,
+            endpoints,
+            frame,
+        },
+        Ok(None) | Err(SacnError::TooShort { .. }) => DecodedPacket::OtherUdp {
+            ts,
+            endpoints,
+            payload_len: udp.payload.len(),
+        },
+        Err(err) => DecodedPacket::Malformed {
+            ts,
+            endpoints: Some(endpoints),
+            protocol: Protocol::Sacn,
+            reason: err.to_string(),
+        },
+    }
+}
+
+/// Iterator over decoded packets from a `PacketSource`.
+///
+/// # Examples
+/// ```no_run
+/// use liveshark_core::{DecodedPacket, DecodedPacketIter, PcapFileSource};
+/// use std::path::Path;
+///
+/// let source = PcapFileSource::open(Path::new("capture.pcapng"))?;
+/// for packet in DecodedPacketIter::new(source) {
+///     if let DecodedPacket::ArtDmx { frame, .. } = packet? {
+///         println!("universe {}", frame.universe);
+///     }
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct DecodedPacketIter<S> {
+    source: S,
+    done: bool,
+}
+
+impl<S: PacketSource> DecodedPacketIter<S> {
+    /// Wrap a packet source.
+    pub fn new(source: S) -> Self {
+        Self {
+            source,
+            done: false,
+        }
+    }
+
+    /// Return the wrapped source.
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+}
+
+impl<S: PacketSource> Iterator for DecodedPacketIter<S> {
+    type Item = Result<DecodedPacket, SourceError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.source.next_packet() {
+            Ok(Some(event)) => {
+                let decoded = decode_packet(&event);
+                self.source.recycle(event.data);
+                Some(Ok(decoded))
+            }
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DecodedPacket, decode_packet};
+    use crate::protocols::artnet::layout;
+    use crate::source::PacketEvent;
+    use crate::test_util::{
+        ArtAddressBuilder, ArtNzsBuilder, ArtRdmBuilder, ArtSyncBuilder, ArtTimeCodeBuilder,
+        ArtTodDataBuilder, ArtTodRequestBuilder, ArtTriggerBuilder, SacnDataBuilder,
+        SacnDiscoveryBuilder, SacnSyncBuilder,
+    };
+    use crate::{Protocol, RdmUid};
+    use etherparse::PacketBuilder;
+    use pcap_parser::Linktype;
+
+    fn udp_event(payload: &[u8]) -> PacketEvent {
+        let builder = PacketBuilder::ethernet2([1, 2, 3, 4, 5, 6], [7, 8, 9, 10, 11, 12])
+            .ipv4([10, 0, 0, 1], [10, 0, 0, 2], 64)
+            .udp(6454, 6454);
+        let mut data = Vec::<u8>::with_capacity(builder.size(payload.len()));
+        builder.write(&mut data, payload).unwrap();
+        PacketEvent {
+            ts: Some(2.0),
+            linktype: Linktype::ETHERNET,
+            data,
+            orig_len: None,
+        }
+    }
+
+    fn artdmx_payload(universe: u16, length: u16) -> Vec<u8> {
+        let mut payload = vec![0u8; layout::DMX_DATA_OFFSET + length as usize];
+        payload[..layout::ARTNET_ID.len()].copy_from_slice(layout::ARTNET_ID);
+        payload[layout::OP_CODE_RANGE.clone()]
+            .copy_from_slice(&layout::ARTDMX_OPCODE.to_le_bytes());
+        payload[layout::UNIVERSE_RANGE.clone()].copy_from_slice(&universe.to_le_bytes());
+        payload[layout::LENGTH_RANGE.clone()].copy_from_slice(&length.to_be_bytes());
+        payload
+    }
+
+    #[test]
+    fn decodes_artdmx_with_endpoints() {
+        let decoded = decode_packet(&udp_event(&artdmx_payload(3, 4)));
+        let DecodedPacket::ArtDmx {
+            ts,
+            endpoints,
+            frame,
+        } = decoded
+        else {
+            panic!("expected ArtDmx, got {decoded:?}");
+        };
+        assert_eq!(ts, Some(2.0));
+        assert_eq!(endpoints.src_port, 6454);
+        assert_eq!(endpoints.dst_ip.to_string(), "10.0.0.2");
+        assert_eq!(frame.universe, 3);
+        assert_eq!(frame.slots.len(), 4);
+    }
+
+    #[test]
+    fn short_unknown_udp_is_other_udp() {
+        let decoded = decode_packet(&udp_event(&[1, 2, 3]));
+        assert!(matches!(
+            decoded,
+            DecodedPacket::OtherUdp { payload_len: 3, .. }
+        ));
+        assert!(decoded.endpoints().is_some());
+    }
+
+    #[test]
+    fn display_summarizes_the_packet() {
+        let decoded = decode_packet(&udp_event(&artdmx_payload(3, 4)));
+        assert_eq!(
+            decoded.to_string(),
+            "2.000000 10.0.0.1:6454 -> 10.0.0.2:6454 artnet dmx universe=3 slots=4"
+        );
+        assert_eq!(decoded.protocol(), Some(Protocol::ArtNet));
+        assert_eq!(decoded.universe(), Some(3));
+
+        let malformed = decode_packet(&udp_event(&artdmx_payload(1, 3)));
+        assert!(
+            malformed
+                .to_string()
+                .ends_with("artnet malformed: invalid DMX length: 3 (expected even, 2..=512)")
+        );
+    }
+
+    #[test]
+    fn sync_packets_are_not_other_udp() {
+        let decoded = decode_packet(&udp_event(&SacnSyncBuilder::new(7000).sequence(4).build()));
+        assert!(matches!(decoded, DecodedPacket::SacnSync { .. }));
+        assert!(
+            decoded
+                .to_string()
+                .ends_with("sacn sync address=7000 seq=4")
+        );
+        assert_eq!(decoded.universe(), None);
+    }
+
+    #[test]
+    fn address_priority_packets_are_not_dmx() {
+        let payload = SacnDataBuilder::new(2)
+            .start_code(0xdd)
+            .slots(vec![100; 4])
+            .build();
+        let decoded = decode_packet(&udp_event(&payload));
+        assert!(matches!(decoded, DecodedPacket::SacnAddressPriority { .. }));
+        assert!(decoded.to_string().contains("sacn priority universe=2"));
+        assert!(decoded.to_string().ends_with("slots=4"));
+        assert_eq!(decoded.universe(), Some(2));
+    }
+
+    #[test]
+    fn discovery_packets_are_not_malformed_sacn() {
+        let payload = SacnDiscoveryBuilder::new(vec![1, 2, 3]).build();
+        let decoded = decode_packet(&udp_event(&payload));
+        assert!(matches!(decoded, DecodedPacket::SacnDiscovery { .. }));
+        assert!(
+            decoded
+                .to_string()
+                .ends_with("sacn discovery page=0/0 universes=3")
+        );
+        assert_eq!(decoded.protocol(), Some(Protocol::Sacn));
+    }
+
+    #[test]
+    fn artsync_is_not_malformed_artdmx() {
+        let decoded = decode_packet(&udp_event(&ArtSyncBuilder::new().build()));
+        assert!(matches!(decoded, DecodedPacket::ArtSync { .. }));
+        assert!(decoded.to_string().ends_with("artnet sync"));
+        assert_eq!(decoded.protocol(), Some(Protocol::ArtNet));
+        assert_eq!(decoded.universe(), None);
+    }
+
+    #[test]
+    fn arttimecode_is_decoded_with_its_frame_rate() {
+        let payload = ArtTimeCodeBuilder::new(1, 2, 3, 4).build();
+        let decoded = decode_packet(&udp_event(&payload));
+        assert!(matches!(decoded, DecodedPacket::ArtTimeCode { .. }));
+        assert!(
+            decoded
+                .to_string()
+                .ends_with("artnet timecode 01:02:03:04 fps=25")
+        );
+        assert_eq!(decoded.universe(), None);
+
+        let truncated = &payload[..payload.len() - 1];
+        assert!(matches!(
+            decode_packet(&udp_event(truncated)),
+            DecodedPacket::Malformed {
+                protocol: Protocol::ArtNet,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn arttrigger_is_decoded_with_its_key() {
+        let decoded = decode_packet(&udp_event(&ArtTriggerBuilder::new(1, 12).build()));
+        assert!(matches!(decoded, DecodedPacket::ArtTrigger { .. }));
+        assert!(
+            decoded
+                .to_string()
+                .ends_with("artnet trigger oem=0xffff key=1 (macro) sub_key=12")
+        );
+        assert_eq!(decoded.universe(), None);
+    }
+
+    #[test]
+    fn artaddress_is_decoded() {
+        let payload = ArtAddressBuilder::new()
+            .bind_index(2)
+            .short_name("Truss")
+            .command(0x90)
+            .build();
+        let decoded = decode_packet(&udp_event(&payload));
+        assert!(matches!(decoded, DecodedPacket::ArtAddress { .. }));
+        assert!(
+            decoded
+                .to_string()
+                .ends_with("artnet address bind_index=2 name=\"Truss\" command=0x90")
+        );
+    }
+
+    #[test]
+    fn rdm_packets_are_decoded() {
+        let controller = RdmUid {
+            manufacturer: 0x4c53,
+            device: 1,
+        };
+        let fixture = RdmUid {
+            manufacturer: 0x7a70,
+            device: 2,
+        };
+
+        let decoded = decode_packet(&udp_event(
+            &ArtTodRequestBuilder::new(0, vec![3, 4]).build(),
+        ));
+        assert!(matches!(decoded, DecodedPacket::ArtTodRequest { .. }));
+        assert!(
+            decoded
+                .to_string()
+                .ends_with("artnet tod-request universes=3,4")
+        );
+
+        let payload = ArtTodDataBuilder::new(3, vec![fixture])
+            .uid_total(2)
+            .build();
+        let decoded = decode_packet(&udp_event(&payload));
+        assert!(
+            decoded
+                .to_string()
+                .ends_with("artnet tod-data universe=3 port=1 uids=1/2 block=0")
+        );
+        assert_eq!(decoded.universe(), Some(3));
+
+        let payload = ArtRdmBuilder::new(3, controller, fixture, 0x20)
+            .transaction(5)
+            .build();
+        let decoded = decode_packet(&udp_event(&payload));
+        assert!(matches!(decoded, DecodedPacket::ArtRdm { .. }));
+        assert!(decoded.to_string().ends_with(
+            "artnet rdm universe=3 4c53:00000001 -> 7a70:00000002 cc=0x20 pid=0x0060 tn=5"
+        ));
+
+        let decoded = decode_packet(&udp_event(&payload[..payload.len() - 1]));
+        assert!(matches!(
+            decoded,
+            DecodedPacket::Malformed {
+                protocol: Protocol::ArtNet,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn artnzs_is_decoded_with_its_start_code() {
+        let payload = ArtNzsBuilder::new(3)
+            .start_code(0xcc)
+            .data([1, 2, 3])
+            .build();
+        let decoded = decode_packet(&udp_event(&payload));
+        assert!(matches!(decoded, DecodedPacket::ArtNzs { .. }));
+        assert!(
+            decoded
+                .to_string()
+                .ends_with("artnet nzs universe=3 start_code=0xcc len=3")
+        );
+        assert_eq!(decoded.universe(), Some(3));
+    }
+
+    #[test]
+    fn invalid_artdmx_is_malformed() {
+        let decoded = decode_packet(&udp_event(&artdmx_payload(1, 3)));
+        assert!(matches!(
+            decoded,
+            DecodedPacket::Malformed {
+                protocol: Protocol::ArtNet,
+                ..
+            }
+        ));
+    }
+}
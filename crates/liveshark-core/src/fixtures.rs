@@ -0,0 +1,349 @@
+//! Fixture labels for DMX slots.
+//!
+//! Reports name slots by universe and number; a lighting programmer thinks in
+//! fixtures and parameters. A fixture map combines personalities (parameter
+//! names per slot offset) with the patch (fixture name, personality, universe,
+//! start address) so channel-level findings can read "Spot 12 – Dimmer"
+//! instead of "universe 1, slot 101".
+//!
+//! The map is a plain CSV file, one record per line; blank lines and lines
+//! starting with `#` are ignored:
+//!
+//! ```text
+//! personality,<personality>,<offset>,<parameter>
+//! patch,<fixture>,<personality>,<universe>,<address>
+//! ```
+//!
+//! Offsets and addresses are 1-based. Fields cannot contain commas.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{ChannelPair, ConflictSummary, MovementAxis};
+
+/// Slots in a DMX universe.
+const UNIVERSE_SLOTS: u16 = 512;
+
+/// Error returned when a fixture map cannot be read or parsed.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::FixtureMap;
+///
+/// let err = "patch,Spot 1,Unknown,1,1".parse::<FixtureMap>().unwrap_err();
+/// assert!(err.to_string().contains("line 1"));
+/// ```
+#[derive(Debug, Error)]
+pub enum FixtureMapError {
+    /// The file could not be read.
+    #[error("failed to read fixture map: {0}")]
+    Io(#[from] std::io::Error),
+    /// A record is malformed or inconsistent with earlier records.
+    #[error("line {line}: {reason}")]
+    Line { line: usize, reason: String },
+}
+
+/// Fixture and parameter name of one patched slot.
+///
+/// Displays as `"<fixture> – <parameter>"`.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::ChannelLabel;
+///
+/// let label = ChannelLabel {
+///     channel: 101,
+///     fixture: "Spot 12".to_string(),
+///     parameter: "Dimmer".to_string(),
+/// };
+/// assert_eq!(label.to_string(), "Spot 12 – Dimmer");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelLabel {
+    /// Slot number (1-based).
+    pub channel: u16,
+    /// Fixture name from the patch.
+    pub fixture: String,
+    /// Parameter name from the fixture personality.
+    pub parameter: String,
+}
+
+impl fmt::Display for ChannelLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} – {}", self.fixture, self.parameter)
+    }
+}
+
+/// Slots carrying the pan or tilt of one patched fixture.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AxisSlots {
+    pub universe: u16,
+    pub fixture: String,
+    pub axis: MovementAxis,
+    /// Coarse (8-bit) slot.
+    pub coarse: u16,
+    /// Fine slot of a 16-bit axis.
+    pub fine: Option<u16>,
+}
+
+/// Slot labels per universe, built from personalities and a patch.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::FixtureMap;
+///
+/// let map: FixtureMap = "\
+/// personality,Spot,1,Dimmer
+/// personality,Spot,2,Pan
+/// patch,Spot 12,Spot,1,101
+/// ".parse()?;
+/// let label = map.label(1, 102).unwrap();
+/// assert_eq!(label.to_string(), "Spot 12 – Pan");
+/// assert!(map.label(1, 103).is_none());
+/// # Ok::<(), liveshark_core::FixtureMapError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FixtureMap {
+    labels: BTreeMap<(u16, u16), ChannelLabel>,
+}
+
+impl FixtureMap {
+    /// Read and parse a fixture map file.
+    ///
+    /// # Errors
+    /// Returns `FixtureMapError::Io` when the file cannot be read and
+    /// `FixtureMapError::Line` for the first invalid record.
+    pub fn from_file(path: &Path) -> Result<Self, FixtureMapError> {
+        std::fs::read_to_string(path)?.parse()
+    }
+
+    /// Label of a slot, if patched.
+    pub fn label(&self, universe: u16, channel: u16) -> Option<&ChannelLabel> {
+        self.labels.get(&(universe, channel))
+    }
+
+    /// Number of labelled slots.
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// Returns true when no slot is labelled.
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Label the affected channels of each conflict.
+    pub(crate) fn annotate_conflicts(&self, conflicts: &mut [ConflictSummary]) {
+        for conflict in conflicts {
+            conflict.channel_labels = conflict
+                .affected_channels
+                .iter()
+                .filter_map(|channel| self.label(conflict.universe, *channel))
+                .cloned()
+                .collect();
+        }
+    }
+
+    /// Pan and tilt axes of the patched fixtures, sorted by universe,
+    /// fixture, and axis. Parameters named `Pan`/`Tilt` are the coarse slots
+    /// and `Pan Fine`/`Tilt Fine` the fine slots (case, spaces, `_` and `-`
+    /// ignored); an axis without a coarse slot is left out.
+    pub(crate) fn movement_axes(&self) -> Vec<AxisSlots> {
+        // Coarse and fine slot of each (universe, fixture, axis).
+        type Slots = (Option<u16>, Option<u16>);
+        let mut axes: BTreeMap<(u16, &str, MovementAxis), Slots> = BTreeMap::new();
+        for ((universe, channel), label) in &self.labels {
+            let name: String = label
+                .parameter
+                .chars()
+                .filter(|c| !matches!(c, ' ' | '_' | '-'))
+                .collect::<String>()
+                .to_lowercase();
+            let (axis, fine) = match name.as_str() {
+                "pan" => (MovementAxis::Pan, false),
+                "panfine" => (MovementAxis::Pan, true),
+                "tilt" => (MovementAxis::Tilt, false),
+                "tiltfine" => (MovementAxis::Tilt, true),
+                _ => continue,
+            };
+            let slots = axes
+                .entry((*universe, label.fixture.as_str(), axis))
+                .or_default();
+            if fine {
+                slots.1 = Some(*channel);
+            } else {
+                slots.0 = Some(*channel);
+            }
+        }
+        axes.into_iter()
+            .filter_map(|((universe, fixture, axis), (coarse, fine))| {
+                Some(AxisSlots {
+                    universe,
+                    fixture: fixture.to_string(),
+                    axis,
+                    coarse: coarse?,
+                    fine,
+                })
+            })
+            .collect()
+    }
+
+    /// Label 16-bit pairs by their coarse slot.
+    pub(crate) fn annotate_pairs(&self, universe: u16, pairs: &mut [ChannelPair]) {
+        for pair in pairs {
+            pair.label = self
+                .label(universe, pair.coarse)
+                .map(ChannelLabel::to_string);
+        }
+    }
+}
+
+impl FromStr for FixtureMap {
+    type Err = FixtureMapError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut personalities: HashMap<String, BTreeMap<u16, String>> = HashMap::new();
+        let mut patch = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line_no = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |reason: String| FixtureMapError::Line {
+                line: line_no,
+                reason,
+            };
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            match fields.as_slice() {
+                ["personality", name, offset, parameter] => {
+                    let offset = parse_slot(offset, "offset").map_err(error)?;
+                    let previous = personalities
+                        .entry(name.to_string())
+                        .or_default()
+                        .insert(offset, parameter.to_string());
+                    if previous.is_some() {
+                        return Err(error(format!(
+                            "offset {offset} of personality {name} defined twice"
+                        )));
+                    }
+                }
+                ["patch", fixture, personality, universe, address] => {
+                    let universe = universe
+                        .parse::<u16>()
+                        .map_err(|_| error(format!("invalid universe: {universe}")))?;
+                    let address = parse_slot(address, "address").map_err(error)?;
+                    patch.push((line_no, *fixture, *personality, universe, address));
+                }
+                _ => {
+                    return Err(error(format!(
+                        "expected `personality,<name>,<offset>,<parameter>` or \
+                         `patch,<fixture>,<personality>,<universe>,<address>`, got `{line}`"
+                    )));
+                }
+            }
+        }
+
+        let mut labels = BTreeMap::new();
+        for (line, fixture, personality, universe, address) in patch {
+            let error = |reason: String| FixtureMapError::Line { line, reason };
+            let parameters = personalities
+                .get(personality)
+                .ok_or_else(|| error(format!("unknown personality: {personality}")))?;
+            for (offset, parameter) in parameters {
+                let channel = address + offset - 1;
+                if channel > UNIVERSE_SLOTS {
+                    return Err(error(format!(
+                        "{fixture} at address {address} does not fit in the universe"
+                    )));
+                }
+                let label = ChannelLabel {
+                    channel,
+                    fixture: fixture.to_string(),
+                    parameter: parameter.clone(),
+                };
+                if let Some(existing) = labels.insert((universe, channel), label) {
+                    return Err(error(format!(
+                        "slot {channel} of universe {universe} is already patched to {}",
+                        existing.fixture
+                    )));
+                }
+            }
+        }
+        Ok(Self { labels })
+    }
+}
+
+fn parse_slot(value: &str, field: &str) -> Result<u16, String> {
+    value
+        .parse::<u16>()
+        .ok()
+        .filter(|slot| (1..=UNIVERSE_SLOTS).contains(slot))
+        .ok_or_else(|| format!("{field} must be between 1 and {UNIVERSE_SLOTS}, got {value}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FixtureMap, FixtureMapError};
+    use crate::MovementAxis;
+
+    const RIG: &str = "\
+# personalities
+personality,Wash,1,Dimmer
+personality,Wash,2,Red
+personality,Wash,3,Green
+
+patch,Wash 1,Wash,1,1
+patch,Wash 2,Wash,1,4
+patch,Wash 3,Wash,2,510
+";
+
+    #[test]
+    fn patch_expands_personalities_into_slot_labels() {
+        let map: FixtureMap = RIG.parse().unwrap();
+        assert_eq!(map.len(), 9);
+        assert_eq!(map.label(1, 5).unwrap().to_string(), "Wash 2 – Red");
+        assert_eq!(map.label(2, 512).unwrap().parameter, "Green");
+        assert!(map.label(3, 1).is_none());
+    }
+
+    #[test]
+    fn pan_and_tilt_parameters_form_movement_axes() {
+        let map: FixtureMap = "\
+personality,Spot,1,Pan
+personality,Spot,2,Pan Fine
+personality,Spot,3,TILT
+personality,Spot,4,Dimmer
+patch,Spot 1,Spot,1,1
+patch,Spot 2,Spot,1,11
+"
+        .parse()
+        .unwrap();
+        let axes = map.movement_axes();
+        assert_eq!(axes.len(), 4);
+        assert_eq!(axes[0].fixture, "Spot 1");
+        assert_eq!(axes[0].axis, MovementAxis::Pan);
+        assert_eq!((axes[0].coarse, axes[0].fine), (1, Some(2)));
+        assert_eq!(axes[1].axis, MovementAxis::Tilt);
+        assert_eq!((axes[1].coarse, axes[1].fine), (3, None));
+        assert_eq!(axes[3].coarse, 13);
+    }
+
+    #[test]
+    fn inconsistent_records_report_their_line() {
+        let overlap = format!("{RIG}patch,Wash 4,Wash,1,3\n");
+        let err = overlap.parse::<FixtureMap>().unwrap_err();
+        assert!(matches!(err, FixtureMapError::Line { line: 9, .. }));
+        assert!(err.to_string().contains("already patched to Wash 1"));
+
+        let overflow = "personality,Wash,1,Dimmer\npersonality,Wash,2,Red\npatch,W,Wash,1,512";
+        assert!(overflow.parse::<FixtureMap>().is_err());
+        assert!("patch,W,Wash,1,0".parse::<FixtureMap>().is_err());
+        assert!("fixture,W".parse::<FixtureMap>().is_err());
+    }
+}
@@ -0,0 +1,398 @@
+//! One-way latency and loss between two capture points.
+//!
+//! The same DMX traffic captured at two points of the network (e.g. the
+//! console's switch port and a node's switch port) is matched packet by
+//! packet: two packets match when they carry the same protocol, universe,
+//! sender, sequence number and slots, and arrive within the match window of
+//! each other. Matched packets give the one-way latency between the points;
+//! upstream packets without a match were lost between the points, while
+//! sequence gaps already present upstream were lost before the first point.
+//! Both captures must share a clock (one machine with two interfaces, or
+//! hosts synchronized with PTP).
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{round_to, sequence_gap};
+use crate::{
+    CompareError, DecodedPacket, DecodedPacketIter, PacketSource, PcapFileSource, Protocol,
+    SourceError,
+};
+
+/// Default match window (seconds): far above switch and node latencies,
+/// well below the time a static look takes to repeat a sequence number.
+pub const DEFAULT_MATCH_WINDOW_S: f64 = 1.0;
+/// Latencies are rounded to microseconds, the resolution of pcap timestamps.
+const LATENCY_SCALE: f64 = 1_000.0;
+
+/// Timestamp and content digest of each DMX packet, per universe.
+type CapturedUniverses = BTreeMap<(u16, Protocol), Vec<(f64, u64)>>;
+/// Last sequence number per (universe, protocol, sender).
+type SequenceState = HashMap<(u16, Protocol, String), u8>;
+/// Sequence numbers skipped per universe.
+type SkippedSequences = BTreeMap<(u16, Protocol), u64>;
+
+/// Match window of a latency comparison.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{DEFAULT_MATCH_WINDOW_S, LatencyOptions};
+///
+/// assert_eq!(LatencyOptions::default().match_window_s, DEFAULT_MATCH_WINDOW_S);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatencyOptions {
+    /// Largest time difference between two matching packets (seconds).
+    pub match_window_s: f64,
+}
+
+impl Default for LatencyOptions {
+    fn default() -> Self {
+        Self {
+            match_window_s: DEFAULT_MATCH_WINDOW_S,
+        }
+    }
+}
+
+/// Distribution of a latency (milliseconds, three decimals). Between two
+/// captures, negative values mean their clocks are not aligned.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::LatencyDistribution;
+///
+/// let latency = LatencyDistribution {
+///     min_ms: 0.12,
+///     p50_ms: 0.2,
+///     p95_ms: 0.9,
+///     p99_ms: 1.4,
+///     max_ms: 3.1,
+///     mean_ms: 0.27,
+/// };
+/// assert!(latency.min_ms <= latency.p50_ms && latency.p99_ms <= latency.max_ms);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LatencyDistribution {
+    /// Lowest latency.
+    pub min_ms: f64,
+    /// Median latency (nearest rank).
+    pub p50_ms: f64,
+    /// 95th percentile latency (nearest rank).
+    pub p95_ms: f64,
+    /// 99th percentile latency (nearest rank).
+    pub p99_ms: f64,
+    /// Highest latency.
+    pub max_ms: f64,
+    /// Mean latency.
+    pub mean_ms: f64,
+}
+
+/// Latency and loss of one universe between the two capture points.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UniverseLatency {
+    /// Universe number.
+    pub universe: u16,
+    /// Protocol of the universe.
+    pub proto: Protocol,
+    /// Timestamped DMX packets of the universe in the upstream capture.
+    pub upstream_packets: u64,
+    /// Timestamped DMX packets of the universe in the downstream capture.
+    pub downstream_packets: u64,
+    /// Packets seen at both points.
+    pub matched: u64,
+    /// Upstream packets never seen downstream: lost between the points.
+    pub lost_between: u64,
+    /// Downstream packets without an upstream match (duplicates, or packets
+    /// the upstream capture missed).
+    pub downstream_only: u64,
+    /// Sequence numbers skipped in the upstream capture: lost before the
+    /// first point. Absent when the senders do not number their packets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lost_before_upstream: Option<u64>,
+    /// Latency of the matched packets; absent when none matched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency: Option<LatencyDistribution>,
+}
+
+/// Result of matching a downstream capture against an upstream one.
+///
+/// # Examples
+/// ```no_run
+/// use liveshark_core::{LatencyOptions, latency_pcap_files};
+/// use std::path::Path;
+///
+/// let latency = latency_pcap_files(
+///     Path::new("console-port.pcapng"),
+///     Path::new("node-port.pcapng"),
+///     &LatencyOptions::default(),
+/// )?;
+/// for universe in latency.universes.iter().filter(|u| u.lost_between > 0) {
+///     println!("universe {} loses packets on the way", universe.universe);
+/// }
+/// # Ok::<(), liveshark_core::CompareError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaptureLatency {
+    /// Largest time difference between two matching packets (seconds).
+    pub match_window_s: f64,
+    /// Per-universe results, sorted by universe and protocol.
+    pub universes: Vec<UniverseLatency>,
+}
+
+/// Match the DMX packets of two capture files.
+///
+/// # Errors
+/// Returns `CompareError::InvalidOptions` for a non-positive match window
+/// and `CompareError::Source` when a capture cannot be read.
+pub fn latency_pcap_files(
+    upstream: &Path,
+    downstream: &Path,
+    options: &LatencyOptions,
+) -> Result<CaptureLatency, CompareError> {
+    latency_sources(
+        PcapFileSource::open(upstream)?,
+        PcapFileSource::open(downstream)?,
+        options,
+    )
+}
+
+/// Match the DMX packets of two packet sources. Packets without timestamps
+/// are ignored.
+///
+/// # Errors
+/// Returns `CompareError::InvalidOptions` for a non-positive match window
+/// and `CompareError::Source` when a source fails.
+pub fn latency_sources<U: PacketSource, D: PacketSource>(
+    upstream: U,
+    downstream: D,
+    options: &LatencyOptions,
+) -> Result<CaptureLatency, CompareError> {
+    let window = options.match_window_s;
+    if !window.is_finite() || window <= 0.0 {
+        return Err(CompareError::InvalidOptions(format!(
+            "match_window_s must be a positive number of seconds (got {window})"
+        )));
+    }
+    let (upstream, lost_before) = capture_packets(upstream)?;
+    let (downstream, _) = capture_packets(downstream)?;
+
+    let keys: BTreeSet<(u16, Protocol)> =
+        upstream.keys().chain(downstream.keys()).copied().collect();
+    let universes = keys
+        .into_iter()
+        .map(|key| {
+            let sent = upstream.get(&key).map_or(&[][..], Vec::as_slice);
+            let received = downstream.get(&key).map_or(&[][..], Vec::as_slice);
+            let latencies = match_packets(sent, received, window);
+            let matched = latencies.len() as u64;
+            UniverseLatency {
+                universe: key.0,
+                proto: key.1,
+                upstream_packets: sent.len() as u64,
+                downstream_packets: received.len() as u64,
+                matched,
+                lost_between: sent.len() as u64 - matched,
+                downstream_only: received.len() as u64 - matched,
+                lost_before_upstream: lost_before.get(&key).copied(),
+                latency: distribution(latencies),
+            }
+        })
+        .collect();
+    Ok(CaptureLatency {
+        match_window_s: window,
+        universes,
+    })
+}
+
+/// Timestamped DMX packets of a capture, and the sequence numbers skipped
+/// per universe (only for universes whose senders number their packets).
+fn capture_packets<S: PacketSource>(
+    source: S,
+) -> Result<(CapturedUniverses, SkippedSequences), SourceError> {
+    let mut universes = CapturedUniverses::new();
+    let mut last_sequence = SequenceState::new();
+    let mut skipped = SkippedSequences::new();
+    for packet in DecodedPacketIter::new(source) {
+        let (ts, protocol, universe, sender, sequence, digest) = match packet? {
+            DecodedPacket::ArtDmx {
+                ts: Some(ts),
+                endpoints,
+                frame,
+            } => {
+                let sender = format!("{}:{}", endpoints.src_ip, endpoints.src_port);
+                let digest = digest(&sender, frame.sequence, &frame.slots);
+                let sequence = frame.sequence;
+                (
+                    ts,
+                    Protocol::ArtNet,
+                    frame.universe,
+                    sender,
+                    sequence,
+                    digest,
+                )
+            }
+            DecodedPacket::SacnDmx {
+                ts: Some(ts),
+                endpoints: _,
+                frame,
+            } => {
+                let digest = digest(&frame.cid, frame.sequence, &frame.slots);
+                let sequence = frame.sequence;
+                (
+                    ts,
+                    Protocol::Sacn,
+                    frame.universe,
+                    frame.cid,
+                    sequence,
+                    digest,
+                )
+            }
+            _ => continue,
+        };
+        universes
+            .entry((universe, protocol))
+            .or_default()
+            .push((ts, digest));
+        let Some(sequence) = sequence else {
+            continue;
+        };
+        let count = skipped.entry((universe, protocol)).or_default();
+        if let Some(last) = last_sequence.insert((universe, protocol, sender), sequence) {
+            if let Some((_, gap)) = sequence_gap(protocol, last, sequence) {
+                *count += u64::from(gap);
+            }
+        }
+    }
+    for packets in universes.values_mut() {
+        packets.sort_by(|a, b| a.0.total_cmp(&b.0));
+    }
+    Ok((universes, skipped))
+}
+
+/// Content digest of a DMX packet: sender, sequence number and slots.
+fn digest(sender: &str, sequence: Option<u8>, slots: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    sender.hash(&mut hasher);
+    sequence.hash(&mut hasher);
+    slots.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Latency of each matched packet (seconds). Each downstream packet takes
+/// the earliest unmatched upstream packet with the same digest within the
+/// window; upstream packets left behind by the window stay unmatched.
+fn match_packets(sent: &[(f64, u64)], received: &[(f64, u64)], window: f64) -> Vec<f64> {
+    let mut pending: HashMap<u64, VecDeque<f64>> = HashMap::new();
+    for (ts, digest) in sent {
+        pending.entry(*digest).or_default().push_back(*ts);
+    }
+    let mut latencies = Vec::new();
+    for (ts, digest) in received {
+        let Some(queue) = pending.get_mut(digest) else {
+            continue;
+        };
+        while queue.front().is_some_and(|sent_ts| *sent_ts < ts - window) {
+            queue.pop_front();
+        }
+        if let Some(sent_ts) = queue
+            .front()
+            .copied()
+            .filter(|sent_ts| *sent_ts <= ts + window)
+        {
+            queue.pop_front();
+            latencies.push(ts - sent_ts);
+        }
+    }
+    latencies
+}
+
+/// Distribution of `latencies` (seconds); `None` when there are none.
+pub(crate) fn distribution(mut latencies: Vec<f64>) -> Option<LatencyDistribution> {
+    if latencies.is_empty() {
+        return None;
+    }
+    latencies.sort_by(f64::total_cmp);
+    let ms = |seconds: f64| round_to(seconds * 1000.0, LATENCY_SCALE);
+    let rank = |percentile: usize| {
+        let rank = (latencies.len() * percentile).div_ceil(100).max(1);
+        ms(latencies[rank - 1])
+    };
+    let mean = latencies.iter().sum::<f64>() / latencies.len() as f64;
+    Some(LatencyDistribution {
+        min_ms: ms(latencies[0]),
+        p50_ms: rank(50),
+        p95_ms: rank(95),
+        p99_ms: rank(99),
+        max_ms: ms(latencies[latencies.len() - 1]),
+        mean_ms: ms(mean),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LatencyOptions, latency_sources};
+    use crate::PacketEvent;
+    use crate::test_util::{ArtDmxBuilder, MemorySource, udp_event};
+
+    fn artdmx(ts: f64, sequence: u8, level: u8) -> PacketEvent {
+        let payload = ArtDmxBuilder::new(1)
+            .sequence(sequence)
+            .slots([level, 0])
+            .build();
+        udp_event(ts, [10, 0, 0, 1], [10, 0, 0, 255], 6454, &payload)
+    }
+
+    #[test]
+    fn matched_packets_give_latency_and_loss_location() {
+        // Sequence 3 never reached the upstream point; sequence 6 was lost
+        // between the points. Downstream sees each packet 0.5 ms later.
+        let sent = [1u8, 2, 4, 5, 6, 7, 8];
+        let upstream: MemorySource = sent
+            .iter()
+            .map(|seq| artdmx(f64::from(*seq) * 0.025, *seq, 100))
+            .collect();
+        let downstream: MemorySource = sent
+            .iter()
+            .filter(|seq| **seq != 6)
+            .map(|seq| artdmx(f64::from(*seq) * 0.025 + 0.0005, *seq, 100))
+            .collect();
+        let latency =
+            latency_sources(upstream, downstream, &LatencyOptions::default()).expect("latency");
+        let universe = &latency.universes[0];
+        assert_eq!(universe.upstream_packets, 7);
+        assert_eq!(universe.matched, 6);
+        assert_eq!(universe.lost_between, 1);
+        assert_eq!(universe.downstream_only, 0);
+        assert_eq!(universe.lost_before_upstream, Some(1));
+        let distribution = universe.latency.as_ref().expect("distribution");
+        assert_eq!(distribution.p50_ms, 0.5);
+        assert_eq!(distribution.max_ms, 0.5);
+    }
+
+    #[test]
+    fn packets_outside_the_window_do_not_match() {
+        let upstream = MemorySource::from_iter([artdmx(0.0, 1, 100)]);
+        let downstream = MemorySource::from_iter([artdmx(2.0, 1, 100), artdmx(2.0, 2, 50)]);
+        let latency =
+            latency_sources(upstream, downstream, &LatencyOptions::default()).expect("latency");
+        let universe = &latency.universes[0];
+        assert_eq!(universe.matched, 0);
+        assert_eq!(universe.lost_between, 1);
+        assert_eq!(universe.downstream_only, 2);
+        assert_eq!(universe.latency, None);
+        assert!(
+            latency_sources(
+                MemorySource::default(),
+                MemorySource::default(),
+                &LatencyOptions {
+                    match_window_s: 0.0
+                },
+            )
+            .is_err()
+        );
+    }
+}
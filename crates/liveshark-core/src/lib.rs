@@ -3,8 +3,11 @@
 //!
 //! Architecture overview:
 //! - `source` provides packet inputs with timestamps and linktype metadata.
+//! - `decode` classifies individual packets without aggregating them.
 //! - `analysis` drives decoding, reconstructs DMX frames, and aggregates metrics.
 //! - `protocols` implements layout/reader/parser/error for each wire format.
+//! - `report` builds reports and validates their schema invariants.
+//! - `rules` registers every compliance rule (`LS-*` codes).
 //! - reports are serialized from deterministic, stable-order summaries.
 //! - parsers are pure (no I/O); protocol conventions live in readers.
 //!
@@ -27,28 +30,258 @@
 //! println!("report version: {}", report.report_version);
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
 
+mod activity;
 mod analysis;
+mod clock;
+mod compare;
+mod decode;
+mod fixtures;
+mod latency;
+mod patch;
 mod protocols;
+mod report;
+mod rules;
 mod source;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 
-pub use analysis::{AnalysisError, analyze_pcap_file, analyze_source};
-pub use source::{PacketEvent, PacketSource, PcapFileSource, SourceError};
+pub use activity::{
+    ActivityMetric, ChannelActivity, UniverseActivity, UnknownActivityMetricError,
+    channel_activity_pcap_file, channel_activity_source,
+};
+pub use analysis::{
+    AnalysisError, AnalysisObserver, Analyzer, AnalyzerBuilder, AnalyzerConfig, DmxFrame,
+    DmxStateStore, Profile, UnknownProfileError, ViolationEvent, analyze_pcap_file, analyze_source,
+};
+pub use clock::estimate_clock_correction;
+pub use compare::{
+    CompareError, CompareOptions, DEFAULT_COMPARE_INTERVAL_S, DmxComparison, DmxDivergence,
+    UniverseComparison, UniversePresence, compare_pcap_files, compare_sources,
+};
+pub use decode::{DecodedPacket, DecodedPacketIter, Endpoints, decode_packet};
+pub use fixtures::{ChannelLabel, FixtureMap, FixtureMapError};
+pub use latency::{
+    CaptureLatency, DEFAULT_MATCH_WINDOW_S, LatencyDistribution, LatencyOptions, UniverseLatency,
+    latency_pcap_files, latency_sources,
+};
+pub use patch::{PatchEntry, PatchError, RigPatch};
+#[cfg(feature = "arp")]
+pub use protocols::arp::parse_arp;
+pub use protocols::arp::{ArpPacket, MacAddress, error::ArpError};
+pub use protocols::artnet::{
+    ArtAddress, ArtDmx, ArtNzs, ArtPollReply, ArtRdm, ArtSync, ArtTimeCode, ArtTodData,
+    ArtTodRequest, ArtTrigger, PortAddress, RdmMessage, RdmUid,
+    error::{ArtNetError, PortAddressError},
+};
+#[cfg(feature = "artnet")]
+pub use protocols::artnet::{
+    parse_artaddress, parse_artdmx, parse_artnzs, parse_artpollreply, parse_artrdm, parse_artsync,
+    parse_arttimecode, parse_arttoddata, parse_arttodrequest, parse_arttrigger,
+};
+#[cfg(feature = "citp")]
+pub use protocols::citp::parse_citp;
+pub use protocols::citp::{
+    CitpHeader, CitpLayerStatus, CitpMessage, CitpPacket, CitpPeer, CitpStreamFrame,
+    error::CitpError,
+};
+#[cfg(feature = "dhcp")]
+pub use protocols::dhcp::parse_dhcp;
+pub use protocols::dhcp::{DhcpMessage, error::DhcpError};
+#[cfg(feature = "igmp")]
+pub use protocols::igmp::parse_igmp;
+pub use protocols::igmp::{IgmpGroupChange, IgmpMessage, error::IgmpError};
+#[cfg(feature = "osc")]
+pub use protocols::osc::parse_osc;
+pub use protocols::osc::{OscMessage, OscPacket, error::OscError};
+pub use protocols::psn::{
+    PsnData, PsnInfo, PsnPacketHeader, PsnTracker, PsnTrackerName, error::PsnError,
+};
+#[cfg(feature = "psn")]
+pub use protocols::psn::{parse_psn_data, parse_psn_info};
+#[cfg(feature = "ptp")]
+pub use protocols::ptp::parse_ptp;
+pub use protocols::ptp::{PtpAnnounce, PtpClockIdentity, PtpHeader, PtpMessage, error::PtpError};
+pub use protocols::sacn::{SacnDiscovery, SacnDmx, SacnSync, SourceNameIssue, error::SacnError};
+#[cfg(feature = "sacn")]
+pub use protocols::sacn::{
+    parse_sacn_address_priority, parse_sacn_discovery, parse_sacn_dmx, parse_sacn_sync,
+};
+pub use report::{
+    AppliedMigration, DEFAULT_FLOAT_DECIMALS, DEFAULT_TOOL_NAME, JsonOptions,
+    MIN_SUPPORTED_REPORT_VERSION, MigratedReport, ReportBuilder, ReportError, to_json_string,
+};
+pub use rules::{CustomRule, CustomRuleError, RuleId, RuleSet, RuleSetting, UnknownRuleError};
+#[cfg(target_os = "linux")]
+pub use source::LiveCaptureSource;
+pub use source::{MergedSource, PacketEvent, PacketSource, PcapFileSource, SourceError};
 
 /// Current report schema version.
-pub const REPORT_VERSION: u32 = 1;
+pub const REPORT_VERSION: u32 = 2;
 /// Default timestamp used when no capture time is available.
 pub const DEFAULT_GENERATED_AT: &str = "1970-01-01T00:00:00Z";
 
+/// Protocol identifier shared by the analysis pipeline and report sections.
+///
+/// Serializes to the lowercase wire names used in reports (`artnet`, `sacn`,
+/// `tcp`, `udp`). Ordering follows the serialized name so sorted report sections stay
+/// stable as variants are added.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::Protocol;
+///
+/// assert_eq!(Protocol::ArtNet.as_str(), "artnet");
+/// assert_eq!(serde_json::to_string(&Protocol::Sacn).unwrap(), "\"sacn\"");
+/// assert!(Protocol::ArtNet < Protocol::Udp);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Protocol {
+    /// Art-Net (ArtDMX and related opcodes).
+    #[serde(rename = "artnet")]
+    ArtNet,
+    /// Streaming ACN (ANSI E1.31).
+    #[serde(rename = "sacn")]
+    Sacn,
+    /// Generic UDP transport.
+    #[serde(rename = "udp")]
+    Udp,
+    /// Generic TCP transport (only reported in `other_traffic`).
+    #[serde(rename = "tcp")]
+    Tcp,
+    /// Open Sound Control over UDP (only reported as a flow `app_proto`).
+    #[serde(rename = "osc")]
+    Osc,
+    /// CITP media-server and visualizer traffic (only reported as a flow
+    /// `app_proto`).
+    #[serde(rename = "citp")]
+    Citp,
+    /// PTP (IEEE 1588) clock traffic (only reported as a flow `app_proto`).
+    #[serde(rename = "ptp")]
+    Ptp,
+    /// DHCP address assignment (only reported as a flow `app_proto`).
+    #[serde(rename = "dhcp")]
+    Dhcp,
+}
+
+impl Protocol {
+    /// Canonical lowercase name, as serialized in reports.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Protocol::ArtNet => "artnet",
+            Protocol::Sacn => "sacn",
+            Protocol::Udp => "udp",
+            Protocol::Tcp => "tcp",
+            Protocol::Osc => "osc",
+            Protocol::Citp => "citp",
+            Protocol::Ptp => "ptp",
+            Protocol::Dhcp => "dhcp",
+        }
+    }
+}
+
+impl fmt::Display for Protocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl PartialOrd for Protocol {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Protocol {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+/// Severity of a compliance violation or conflict.
+///
+/// Variants are declared from most to least severe, so the derived ordering
+/// ranks `Error` first. Violations serialize as `error`, `warning`, `info`.
+/// Conflicts keep their v0.1 labels (`high`, `medium`, `low`) on the wire; see
+/// [`ConflictSummary::severity`].
+///
+/// # Examples
+/// ```
+/// use liveshark_core::Severity;
+///
+/// assert_eq!(Severity::Warning.as_str(), "warning");
+/// assert_eq!(serde_json::to_string(&Severity::Error).unwrap(), "\"error\"");
+/// assert!(Severity::Error < Severity::Info);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Invalid traffic; the packet was ignored.
+    #[serde(alias = "high")]
+    Error,
+    /// Suspicious but accepted traffic.
+    #[serde(alias = "medium")]
+    Warning,
+    /// Informational finding.
+    #[serde(alias = "low")]
+    Info,
+}
+
+impl Severity {
+    /// Canonical lowercase name, as serialized for violations.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        }
+    }
+
+    /// Conflict label used in the v0.1 report schema.
+    pub fn conflict_label(&self) -> &'static str {
+        match self {
+            Severity::Error => "high",
+            Severity::Warning => "medium",
+            Severity::Info => "low",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Serde adapter keeping the `high`/`medium`/`low` conflict labels on the wire.
+mod conflict_severity {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::Severity;
+
+    pub fn serialize<S: Serializer>(severity: &Severity, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(severity.conflict_label())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Severity, D::Error> {
+        Severity::deserialize(deserializer)
+    }
+}
+
 /// Aggregated analysis report with deterministic ordering.
 ///
 /// # Examples
 /// ```
-/// use liveshark_core::make_stub_report;
+/// use liveshark_core::ReportBuilder;
 ///
-/// let report = make_stub_report("capture.pcapng", 123);
+/// let report = ReportBuilder::new("capture.pcapng", 123).build()?;
 /// assert_eq!(report.report_version, liveshark_core::REPORT_VERSION);
+/// # Ok::<(), liveshark_core::ReportError>(())
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Report {
@@ -65,6 +298,9 @@ pub struct Report {
     /// Optional capture summary (may be empty when unavailable).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub capture_summary: Option<CaptureSummary>,
+    /// Network health score of the capture, with per-universe sub-scores.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health: Option<HealthScore>,
     /// Per-universe summaries in stable order.
     pub universes: Vec<UniverseSummary>,
     /// Flow summaries in stable order.
@@ -73,6 +309,115 @@ pub struct Report {
     pub conflicts: Vec<ConflictSummary>,
     /// Protocol compliance summaries in stable order.
     pub compliance: Vec<ComplianceSummary>,
+    /// Compliance rules checked during analysis, sorted by ID.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<ActiveRule>,
+    /// Compliance profile the analysis ran with (e.g. `strict`); omitted when
+    /// the defaults were used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// Largest UDP/TCP flows that are neither Art-Net nor sACN, by bytes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub other_traffic: Vec<OtherTrafficSummary>,
+    /// Analysis warnings (e.g. capture clock anomalies), in packet order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<AnalysisWarning>,
+    /// Per-second metric series, present only when requested in the
+    /// analyzer configuration (`series`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub series: Option<MetricSeries>,
+    /// Rig patch the analysis ran with (labelled universe/channel ranges),
+    /// sorted by universe and start channel.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub patch: Vec<PatchEntry>,
+    /// Scene changes (cue moments) of all universes, in time order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scene_changes: Vec<SceneChange>,
+    /// Fades (channel ramps) of all universes, in time order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fades: Vec<Fade>,
+    /// Movement smoothness of the pan/tilt axes named by the fixture map,
+    /// sorted by universe, protocol, fixture, axis, and source.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub movement: Vec<MovementSmoothness>,
+    /// Pairs of universes carrying the same output, sorted by universe pair.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mirrored_universes: Vec<UniverseMirror>,
+    /// Changes of the source controlling a universe, in time order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub takeovers: Vec<Takeover>,
+    /// Which sACN sources held the highest active priority of a universe
+    /// over time, sorted by universe then start.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub priority_timeline: Vec<PrioritySpan>,
+    /// Intervals during which a universe received no frame at all, sorted
+    /// by start, universe, and protocol.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub silences: Vec<UniverseSilence>,
+    /// Timed events of individual sources, such as sACN source dropouts,
+    /// sorted by timestamp, universe, protocol, then source ID.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<ReportEvent>,
+    /// Delay from synchronized sACN data to its synchronization packet, per
+    /// sync address, sorted by sync address.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sync_latency: Vec<SyncLatency>,
+    /// Universes each sACN source declares in universe discovery against
+    /// the universes it sends, sorted by CID.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sacn_discovery: Vec<SacnUniverseDiscovery>,
+    /// sACN per-address priority maps (start code 0xdd), sorted by universe
+    /// then source ID.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub address_priorities: Vec<AddressPriorityMap>,
+    /// Art-Net nodes seen through their ArtPollReply announcements, sorted
+    /// by IP then bind index.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub nodes: Vec<NodeAvailability>,
+    /// Art-Net timecode streams (ArtTimeCode), sorted by source IP then
+    /// stream identifier.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub timecode: Vec<TimecodeStream>,
+    /// Art-Net triggers (ArtTrigger), in capture order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub triggers: Vec<TriggerEvent>,
+    /// RDM responders per node port (ArtTodData and ArtRdm), sorted by node
+    /// IP then universe.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rdm_responders: Vec<RdmPortInventory>,
+    /// RDM transactions (ArtRdm) per universe, sorted by universe.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rdm_transactions: Vec<RdmTransactions>,
+    /// PosiStageNet trackers, sorted by source IP then tracker id.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub psn_trackers: Vec<PsnTrackerSummary>,
+    /// Open Sound Control messages per address pattern, sorted by address.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub osc_addresses: Vec<OscAddressSummary>,
+    /// CITP peers (media servers, consoles, visualizers), sorted by source
+    /// IP.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub citp_peers: Vec<CitpPeerSummary>,
+    /// PTP domains with their grandmasters and master changes, sorted by
+    /// domain number.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ptp_domains: Vec<PtpDomainSummary>,
+    /// IGMP multicast groups with the receivers that joined or left them,
+    /// sorted by group address.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub igmp_groups: Vec<IgmpGroupSummary>,
+    /// IP conflicts and DHCP address changes seen in ARP and DHCP traffic,
+    /// in capture order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub network_events: Vec<NetworkEvent>,
+    /// Clock corrections applied to the captures merged into the first one,
+    /// in input order; empty for a single capture.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub clock_corrections: Vec<ClockCorrection>,
+    /// Free-form operator notes (what happened on site, when), in the order
+    /// given.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
 }
 
 /// Tool metadata embedded in reports.
@@ -125,10 +470,11 @@ pub struct InputInfo {
 ///     packets_total: 10,
 ///     time_start: None,
 ///     time_end: None,
+///     ..CaptureSummary::default()
 /// };
 /// assert_eq!(summary.packets_total, 10);
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CaptureSummary {
     /// Total packet count observed in the capture.
     pub packets_total: u64,
@@ -138,17 +484,184 @@ pub struct CaptureSummary {
     /// RFC3339 timestamp of the last packet (if known).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub time_end: Option<String>,
+    /// Packets and bytes per traffic class (v0.2 additive).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub traffic: Option<TrafficBreakdown>,
+    /// Seconds between the first and last packet (v0.2 additive).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_s: Option<f64>,
+    /// Average packets per second over `duration_s` (absent when zero).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avg_pps: Option<f64>,
+    /// Average captured bytes per second over `duration_s` (absent when zero).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avg_bps: Option<f64>,
+    /// Smallest captured packet, in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub packet_size_min: Option<u64>,
+    /// Mean captured packet size, in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub packet_size_avg: Option<f64>,
+    /// Largest captured packet, in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub packet_size_max: Option<u64>,
+    /// Capture snapshot length: the most bytes recorded per packet, when the
+    /// capture file sets one (v0.2 additive).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snaplen: Option<u32>,
+    /// Packets the analysis did not use, by reason (v0.2 additive).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<SkippedPackets>,
+}
+
+/// Packets left out of the analysis, so users can check what it covered.
+///
+/// Each packet is counted under at most one reason; `total` is the sum of
+/// `reasons`. UDP datagrams that are simply not DMX (counted in `flows` and
+/// `other_traffic`) are not skipped.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{SkipReason, SkippedPackets};
+///
+/// let mut skipped = SkippedPackets::default();
+/// skipped.add(SkipReason::NonUdp);
+/// skipped.add(SkipReason::NonUdp);
+/// skipped.add(SkipReason::UniverseFiltered);
+/// assert_eq!(skipped.total, 3);
+/// assert_eq!(skipped.reasons[&SkipReason::NonUdp], 2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkippedPackets {
+    /// Number of skipped packets.
+    pub total: u64,
+    /// Skipped packets per reason; reasons with no packet are omitted.
+    pub reasons: BTreeMap<SkipReason, u64>,
+}
+
+impl SkippedPackets {
+    /// Count one skipped packet.
+    pub fn add(&mut self, reason: SkipReason) {
+        self.total += 1;
+        *self.reasons.entry(reason).or_default() += 1;
+    }
+}
+
+/// Why a packet was left out of the analysis.
+///
+/// Serializes in snake case (for example `"universe_filtered"`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// Link type other than Ethernet or raw IP.
+    UnsupportedLinktype,
+    /// Frame without an IP layer (ARP, LLDP, ...).
+    NonIp,
+    /// IP packet carrying another transport than UDP.
+    NonUdp,
+    /// IP or UDP headers could not be decoded.
+    MalformedUdp,
+    /// UDP datagram split into IP fragments (not reassembled).
+    Fragmented,
+    /// Packet cut short by the capture snaplen (not decoded).
+    Truncated,
+    /// Art-Net packet rejected by the parser.
+    MalformedArtnet,
+    /// sACN packet rejected by the parser.
+    MalformedSacn,
+    /// PosiStageNet packet rejected by the parser.
+    MalformedPsn,
+    /// CITP packet rejected by the parser.
+    MalformedCitp,
+    /// PTP message on port 319 or 320 rejected by the parser.
+    MalformedPtp,
+    /// DHCP message on port 67 or 68 rejected by the parser.
+    MalformedDhcp,
+    /// DMX packet for a universe outside the configured universe filter.
+    UniverseFiltered,
+    /// DMX packet of a protocol disabled in the configuration.
+    ProtocolDisabled,
+}
+
+/// What a capture is made of: packet and byte totals per traffic class.
+///
+/// Every packet falls in exactly one class, so the packet counts sum to
+/// `packets_total`. Bytes are captured bytes (link layer included).
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{TrafficBreakdown, TrafficCount};
+///
+/// let traffic = TrafficBreakdown {
+///     sacn: TrafficCount { packets: 2, bytes: 1276 },
+///     non_udp: TrafficCount { packets: 1, bytes: 60 },
+///     ..TrafficBreakdown::default()
+/// };
+/// assert_eq!(traffic.packets_total(), 3);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrafficBreakdown {
+    /// UDP datagrams identified as Art-Net (valid or not).
+    pub artnet: TrafficCount,
+    /// UDP datagrams identified as sACN (valid or not).
+    pub sacn: TrafficCount,
+    /// Other UDP datagrams.
+    pub other_udp: TrafficCount,
+    /// Frames without UDP (ARP, TCP, ...) or on unsupported link types.
+    pub non_udp: TrafficCount,
+    /// Frames whose link, IP, or UDP headers could not be decoded.
+    pub unparsed: TrafficCount,
+}
+
+impl TrafficBreakdown {
+    /// Sum of the packet counts of all classes.
+    pub fn packets_total(&self) -> u64 {
+        self.classes().iter().map(|count| count.packets).sum()
+    }
+
+    /// Sum of the byte counts of all classes.
+    pub fn bytes_total(&self) -> u64 {
+        self.classes().iter().map(|count| count.bytes).sum()
+    }
+
+    fn classes(&self) -> [TrafficCount; 5] {
+        [
+            self.artnet,
+            self.sacn,
+            self.other_udp,
+            self.non_udp,
+            self.unparsed,
+        ]
+    }
+}
+
+/// Packet and byte totals for one traffic class.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::TrafficCount;
+///
+/// let count = TrafficCount { packets: 1, bytes: 638 };
+/// assert_eq!(count.bytes, 638);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrafficCount {
+    /// Number of packets.
+    pub packets: u64,
+    /// Captured bytes of those packets.
+    pub bytes: u64,
 }
 
 /// Per-universe metrics summary.
 ///
 /// # Examples
 /// ```
-/// use liveshark_core::UniverseSummary;
+/// use liveshark_core::{Protocol, UniverseSummary};
 ///
 /// let summary = UniverseSummary {
 ///     universe: 1,
-///     proto: "artnet".to_string(),
+///     proto: Protocol::ArtNet,
+///     port_address: None,
 ///     sources: Vec::new(),
 ///     fps: None,
 ///     frames_count: 0,
@@ -161,6 +674,17 @@ pub struct CaptureSummary {
 ///     reordered_packets: None,
 ///     first_seen: None,
 ///     last_seen: None,
+///     destinations: Vec::new(),
+///     channel_pairs: Vec::new(),
+///     first_snapshot: None,
+///     last_snapshot: None,
+///     ownership: Vec::new(),
+///     microburst: None,
+///     controllers_count: 0,
+///     max_concurrent_controllers: None,
+///     non_zero_start_codes: None,
+///     preview_frames: None,
+///     unicast_receivers: Vec::new(),
 /// };
 /// assert_eq!(summary.universe, 1);
 /// ```
@@ -168,8 +692,11 @@ pub struct CaptureSummary {
 pub struct UniverseSummary {
     /// Canonical universe identifier (u16).
     pub universe: u16,
-    /// Protocol name (e.g., "artnet", "sacn").
-    pub proto: String,
+    /// Protocol of the frames (`artnet` or `sacn`).
+    pub proto: Protocol,
+    /// Art-Net Port-Address breakdown of `universe` (Art-Net only), v0.2 additive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port_address: Option<PortAddress>,
     /// Observed sources for this universe (stable order).
     pub sources: Vec<SourceSummary>,
     /// Frames-per-second metric (windowed).
@@ -192,10 +719,12 @@ pub struct UniverseSummary {
     /// Inter-arrival jitter in milliseconds, when available.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub jitter_ms: Option<f64>,
-    /// Duplicate sACN packets observed (sequence tracked only).
+    /// Duplicate packets observed (sequence tracked only; Art-Net since
+    /// v0.2).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dup_packets: Option<u64>,
-    /// Reordered sACN packets observed (sequence tracked only).
+    /// Reordered packets observed (sequence tracked only; Art-Net since
+    /// v0.2).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reordered_packets: Option<u64>,
     /// Timestamp of first packet for this universe (seconds since capture start), v0.2 additive.
@@ -204,6 +733,243 @@ pub struct UniverseSummary {
     /// Timestamp of last packet for this universe (seconds since capture start), v0.2 additive.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_seen: Option<f64>,
+    /// Destination addresses that carried the universe, sorted by `dst_ip`,
+    /// v0.2 additive.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub destinations: Vec<UniverseDestination>,
+    /// Adjacent slots detected as coarse/fine 16-bit pairs, sorted by
+    /// `coarse`, v0.2 additive.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub channel_pairs: Vec<ChannelPair>,
+    /// Reconstructed slot values after the first frame of the capture, v0.2 additive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_snapshot: Option<DmxSnapshot>,
+    /// Reconstructed slot values after the last frame of the capture, v0.2
+    /// additive; the look on stage when the capture stopped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_snapshot: Option<DmxSnapshot>,
+    /// Which source controlled each channel range over the capture, sorted
+    /// by `start`; only for universes sent by two or more sources, v0.2
+    /// additive.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ownership: Vec<ChannelOwnership>,
+    /// Peak packet and byte counts over the short `microburst_window_s`
+    /// window, alongside the windowed rates above, v0.2 additive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub microburst: Option<MicroburstPeak>,
+    /// Distinct sources that sent the universe, v0.2 additive.
+    #[serde(default)]
+    pub controllers_count: u64,
+    /// Most sources sending at the same time, v0.2 additive; a source is
+    /// sending while its frames are less than 2.5 s apart.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_controllers: Option<u64>,
+    /// Art-Net ArtNzs packets (non-zero start code data such as RDM or
+    /// text) on the universe, v0.2 additive; they are not DMX frames.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub non_zero_start_codes: Option<NonZeroStartCodes>,
+    /// sACN packets flagged as preview data (not intended for live output),
+    /// v0.2 additive; they are not counted as frames or in the metrics.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preview_frames: Option<u64>,
+    /// Receiver endpoints (`ip:port`) the universe was sent to by unicast
+    /// (sACN only), sorted by address, v0.2 additive; unicast receivers do
+    /// not see a backup console that multicasts.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unicast_receivers: Vec<String>,
+}
+
+/// Packets of a universe sent with a non-zero start code.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::NonZeroStartCodes;
+///
+/// let nzs = NonZeroStartCodes {
+///     packets: 12,
+///     start_codes: vec![0x17, 0xcc],
+/// };
+/// assert_eq!(nzs.start_codes.len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NonZeroStartCodes {
+    /// Packets received.
+    pub packets: u64,
+    /// Distinct start codes seen, ascending.
+    pub start_codes: Vec<u8>,
+}
+
+/// Control of a channel range of a contested universe over the capture.
+///
+/// A slot belongs to the source that last wrote it (Art-Net) or, for sACN,
+/// to the highest-priority source still sending; the range's holding time
+/// is split between sources as percentages. Channels are 1-based.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{ChannelOwnership, OwnershipShare};
+///
+/// let range = ChannelOwnership {
+///     start: 1,
+///     end: 24,
+///     owners: vec![
+///         OwnershipShare {
+///             source_id: "artnet:10.0.0.1:6454".to_string(),
+///             percent: 92.5,
+///         },
+///         OwnershipShare {
+///             source_id: "artnet:10.0.0.2:6454".to_string(),
+///             percent: 7.5,
+///         },
+///     ],
+/// };
+/// assert_eq!(range.owners[0].percent + range.owners[1].percent, 100.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChannelOwnership {
+    /// First channel of the range.
+    pub start: u16,
+    /// Last channel of the range (inclusive).
+    pub end: u16,
+    /// Sources that held the range, by descending share.
+    pub owners: Vec<OwnershipShare>,
+}
+
+/// Share of a channel range's holding time won by one source.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OwnershipShare {
+    /// Source identifier (same format as `SourceSummary.source_id`).
+    pub source_id: String,
+    /// Share of the time the range had an owner (percent, one decimal).
+    pub percent: f64,
+}
+
+/// Coarse/fine slot pair carrying one 16-bit parameter (e.g. pan or tilt).
+///
+/// Detected when the coarse slot steps by one exactly as the fine slot wraps
+/// around; slots are 1-based.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::ChannelPair;
+///
+/// let pair = ChannelPair {
+///     coarse: 21,
+///     fine: 22,
+///     carries: 14,
+///     label: Some("Spot 3 – Pan".to_string()),
+/// };
+/// assert_eq!(pair.fine, pair.coarse + 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelPair {
+    /// Coarse (most significant) slot.
+    pub coarse: u16,
+    /// Fine (least significant) slot, always `coarse + 1`.
+    pub fine: u16,
+    /// Times the fine slot wrapped while the coarse slot stepped.
+    pub carries: u64,
+    /// Fixture label of the coarse slot (requires a fixture map).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// How a destination address delivers packets.
+///
+/// Serializes in lowercase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DestinationKind {
+    /// A single node.
+    Unicast,
+    /// A multicast group (e.g. the sACN group of the universe).
+    Multicast,
+    /// Limited or directed broadcast.
+    Broadcast,
+}
+
+/// Packets of a universe sent to one destination address.
+///
+/// A universe listed with both a unicast and a multicast destination is sent
+/// twice, usually by a console configured for both.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{DestinationKind, UniverseDestination};
+///
+/// let destination = UniverseDestination {
+///     dst_ip: "239.255.0.1".to_string(),
+///     kind: DestinationKind::Multicast,
+///     packets: 440,
+/// };
+/// assert_eq!(serde_json::to_value(&destination)?["kind"], "multicast");
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UniverseDestination {
+    /// Destination IP address.
+    pub dst_ip: String,
+    /// Unicast, multicast, or broadcast.
+    pub kind: DestinationKind,
+    /// DMX packets of the universe sent to this address.
+    pub packets: u64,
+}
+
+/// Run-length encoded 512-slot DMX state of one source at one point in time.
+///
+/// `runs` lists `[value, count]` pairs covering all 512 slots in order, so a
+/// blacked-out universe is `[[0, 512]]`.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::DmxSnapshot;
+///
+/// let mut slots = [0u8; 512];
+/// slots[..4].copy_from_slice(&[255, 255, 128, 0]);
+/// let snapshot = DmxSnapshot::new(Some(12.5), "artnet:10.0.0.1:6454", &slots);
+/// assert_eq!(snapshot.runs, vec![(255, 2), (128, 1), (0, 509)]);
+/// assert_eq!(snapshot.slots(), Some(slots));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DmxSnapshot {
+    /// Timestamp of the frame (seconds, same clock as `first_seen`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<f64>,
+    /// Source that sent the frame (same format as `SourceSummary.source_id`).
+    pub source_id: String,
+    /// `[value, count]` runs covering the 512 slots.
+    pub runs: Vec<(u8, u16)>,
+}
+
+impl DmxSnapshot {
+    /// Encode a reconstructed frame.
+    pub fn new(timestamp: Option<f64>, source_id: impl Into<String>, slots: &[u8; 512]) -> Self {
+        let mut runs: Vec<(u8, u16)> = Vec::new();
+        for &value in slots {
+            match runs.last_mut() {
+                Some((last, count)) if *last == value => *count += 1,
+                _ => runs.push((value, 1)),
+            }
+        }
+        Self {
+            timestamp,
+            source_id: source_id.into(),
+            runs,
+        }
+    }
+
+    /// Decode the runs back into 512 slots; `None` when they do not cover
+    /// exactly 512 slots.
+    pub fn slots(&self) -> Option<[u8; 512]> {
+        let mut slots = [0u8; 512];
+        let mut offset = 0usize;
+        for &(value, count) in &self.runs {
+            let end = offset + usize::from(count);
+            slots.get_mut(offset..end)?.fill(value);
+            offset = end;
+        }
+        (offset == slots.len()).then_some(slots)
+    }
 }
 
 /// Source metadata for a universe.
@@ -217,6 +983,9 @@ pub struct UniverseSummary {
 ///     cid: None,
 ///     source_name: None,
 ///     source_id: None,
+///     ttl: None,
+///     stability: None,
+///     terminated_at: Vec::new(),
 /// };
 /// assert_eq!(source.source_ip, "192.168.0.2");
 /// ```
@@ -233,35 +1002,93 @@ pub struct SourceSummary {
     /// Canonical source identifier (v0.2 additive), matching identifiers in conflicts[].sources[].
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source_id: Option<String>,
+    /// IP TTL (hop limit) of the first sACN multicast packet from this source (additive).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<u8>,
+    /// Regularity of the source's frame spacing on this universe (additive).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stability: Option<FrameRateStability>,
+    /// Timestamps at which the sACN source terminated its stream (Stream_Terminated
+    /// option), one per termination (additive).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub terminated_at: Vec<f64>,
+}
+
+/// Regularity of the inter-frame intervals of one source of a universe.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::FrameRateStability;
+///
+/// let stability = FrameRateStability {
+///     score: 97.5,
+///     iat_cv: 0.02,
+///     worst_window_start: Some(12.0),
+///     worst_window_cv: Some(0.03),
+/// };
+/// assert!(stability.score <= 100.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrameRateStability {
+    /// `100 * (1 - min(1, (iat_cv + worst_window_cv) / 2))`, three decimals;
+    /// 100 is a perfectly steady sender. Without a scored window,
+    /// `iat_cv` stands in for `worst_window_cv`.
+    pub score: f64,
+    /// Coefficient of variation (standard deviation over mean) of the
+    /// inter-frame intervals over the capture, three decimals.
+    pub iat_cv: f64,
+    /// Start of the one-second window (whole seconds, same clock as
+    /// `first_seen`) with the highest coefficient of variation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worst_window_start: Option<f64>,
+    /// Coefficient of variation within that window, three decimals.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worst_window_cv: Option<f64>,
 }
 
 /// Flow-level summary for a UDP endpoint pair.
 ///
 /// # Examples
 /// ```
-/// use liveshark_core::FlowSummary;
+/// use liveshark_core::{FlowSummary, Protocol};
 ///
 /// let flow = FlowSummary {
-///     app_proto: "udp".to_string(),
+///     app_proto: Protocol::Udp,
 ///     src: "192.168.0.1:6454".to_string(),
 ///     dst: "192.168.0.2:6454".to_string(),
+///     vlan: Some(10),
+///     outer_vlan: None,
 ///     pps: None,
 ///     bps: None,
 ///     iat_jitter_ms: None,
 ///     max_iat_ms: None,
 ///     pps_peak_1s: None,
 ///     bps_peak_1s: None,
+///     payload_size_min: None,
+///     payload_size_avg: None,
+///     payload_size_p50: None,
+///     payload_size_p95: None,
+///     payload_size_max: None,
+///     microburst: None,
+///     wifi: None,
 /// };
-/// assert_eq!(flow.app_proto, "udp");
+/// assert_eq!(flow.app_proto, Protocol::Udp);
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FlowSummary {
-    /// Application protocol name (e.g., "udp").
-    pub app_proto: String,
+    /// Application protocol (e.g., `udp`).
+    pub app_proto: Protocol,
     /// Source endpoint in `ip:port` form.
     pub src: String,
     /// Destination endpoint in `ip:port` form.
     pub dst: String,
+    /// 802.1Q VLAN ID of the flow's frames (the inner one when double
+    /// tagged), v0.2 additive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vlan: Option<u16>,
+    /// Service VLAN ID of double-tagged (QinQ) frames, v0.2 additive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outer_vlan: Option<u16>,
     /// Packets per second (flow active interval average).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pps: Option<f64>,
@@ -280,140 +1107,1879 @@ pub struct FlowSummary {
     /// Peak bytes per second over a 1s window.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bps_peak_1s: Option<u64>,
+    /// Smallest UDP payload in bytes, v0.2 additive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_size_min: Option<u64>,
+    /// Mean UDP payload in bytes (three decimals), v0.2 additive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_size_avg: Option<f64>,
+    /// Median UDP payload in bytes (nearest rank), v0.2 additive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_size_p50: Option<u64>,
+    /// 95th percentile UDP payload in bytes (nearest rank), v0.2 additive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_size_p95: Option<u64>,
+    /// Largest UDP payload in bytes, v0.2 additive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_size_max: Option<u64>,
+    /// Peak packet and byte counts over the short `microburst_window_s`
+    /// window, next to the 1s peaks, v0.2 additive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub microburst: Option<MicroburstPeak>,
+    /// 802.11 retransmissions, for flows captured over the air (radiotap or
+    /// 802.11 link types), v0.2 additive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wifi: Option<WifiRetries>,
 }
 
-/// Conflict summary between multiple sources on the same universe.
+/// Retransmissions of a flow's 802.11 data frames: frames with the retry
+/// flag were sent again after a missing acknowledgement, the first sign of
+/// a congested or out-of-range wireless link.
 ///
 /// # Examples
 /// ```
-/// use liveshark_core::ConflictSummary;
+/// use liveshark_core::WifiRetries;
 ///
-/// let conflict = ConflictSummary {
-///     universe: 1,
-///     sources: vec!["sacn:cid:deadbeef".to_string()],
-///     proto: None,
-///     overlap_duration_s: 1.2,
-///     affected_channels: Vec::new(),
-///     severity: "low".to_string(),
-///     conflict_score: 1.2,
-///     first_seen: None,
+/// let wifi = WifiRetries {
+///     frames: 200,
+///     retries: 30,
+///     retry_rate: 0.15,
 /// };
-/// assert_eq!(conflict.universe, 1);
+/// assert_eq!(wifi.retry_rate, wifi.retries as f64 / wifi.frames as f64);
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ConflictSummary {
-    /// Universe identifier for the conflict.
-    pub universe: u16,
-    /// Canonical source identifiers.
-    pub sources: Vec<String>,
-    /// Protocol name (v0.2 additive; "artnet" or "sacn"), when available.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub proto: Option<String>,
-    /// Duration of the overlap in seconds.
-    pub overlap_duration_s: f64,
-    /// Channel indices affected (empty in v0.1).
-    pub affected_channels: Vec<u16>,
-    /// Severity label (e.g., "low", "medium", "high").
-    pub severity: String,
-    /// Numeric conflict score (v0.1 mirrors overlap duration).
-    pub conflict_score: f64,
-    /// Timestamp of first detected conflict (seconds since capture start), v0.2 additive.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub first_seen: Option<f64>,
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WifiRetries {
+    /// 802.11 data frames of the flow.
+    pub frames: u64,
+    /// Frames with the retry flag set.
+    pub retries: u64,
+    /// `retries` over `frames` (0 to 1, three decimals).
+    pub retry_rate: f64,
 }
 
-/// Compliance summary for a protocol.
+/// Busiest short window of a flow or universe: bursts of a few milliseconds
+/// that a 1s peak averages away but that overflow small switch buffers.
 ///
 /// # Examples
 /// ```
-/// use liveshark_core::{ComplianceSummary, Violation};
+/// use liveshark_core::MicroburstPeak;
 ///
-/// let summary = ComplianceSummary {
-///     protocol: "artnet".to_string(),
-///     compliance_percentage: 100.0,
-///     violations: vec![Violation {
-///         id: "LS-ARTNET-PORT".to_string(),
-///         severity: "warning".to_string(),
-///         message: "Non-standard port".to_string(),
-///         count: 1,
-///         examples: Vec::new(),
-///     }],
+/// let peak = MicroburstPeak {
+///     window_ms: 10.0,
+///     packets: 5,
+///     bytes: 2650,
+///     pps: 500.0,
+///     bps: 265000.0,
 /// };
-/// assert_eq!(summary.violations.len(), 1);
+/// assert_eq!(peak.pps, peak.packets as f64 * 1000.0 / peak.window_ms);
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ComplianceSummary {
-    /// Protocol name (e.g., "artnet", "sacn", "udp").
-    pub protocol: String,
-    /// Compliance percentage (0.0-100.0).
-    pub compliance_percentage: f64,
-    /// Violations sorted by severity and ID.
-    pub violations: Vec<Violation>,
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MicroburstPeak {
+    /// Window length (milliseconds).
+    pub window_ms: f64,
+    /// Most packets seen within one window.
+    pub packets: u64,
+    /// Most UDP payload bytes seen within one window.
+    pub bytes: u64,
+    /// `packets` as a rate (packets per second, three decimals).
+    pub pps: f64,
+    /// `bytes` as a rate (bytes per second, three decimals).
+    pub bps: f64,
 }
 
-/// Single compliance violation record.
+/// Non-DMX flow listed in `other_traffic`, so unexpected bandwidth on a
+/// lighting network is visible.
+///
+/// Flows are keyed by transport and endpoints; `bytes` counts captured bytes
+/// (link layer included), like `capture_summary.traffic`.
 ///
 /// # Examples
 /// ```
-/// use liveshark_core::Violation;
+/// use liveshark_core::{OtherTrafficSummary, Protocol};
 ///
-/// let violation = Violation {
-///     id: "LS-UDP-TOO-SHORT".to_string(),
-///     severity: "error".to_string(),
-///     message: "Payload too short".to_string(),
-///     count: 1,
-///     examples: vec!["source 10.0.0.1:1234 @ 1970-01-01T00:00:00Z".to_string()],
+/// let flow = OtherTrafficSummary {
+///     transport: Protocol::Tcp,
+///     src: "10.0.0.5:51234".to_string(),
+///     dst: "10.0.0.9:443".to_string(),
+///     packets: 1200,
+///     bytes: 1_500_000,
+///     service: Some("https".to_string()),
 /// };
-/// assert_eq!(violation.count, 1);
+/// assert_eq!(flow.transport.as_str(), "tcp");
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Violation {
-    /// Stable violation identifier (e.g., `LS-SACN-START-CODE`).
-    pub id: String,
-    /// Severity label (`error` or `warning`).
-    pub severity: String,
-    /// Human-readable message explaining the violation.
-    pub message: String,
-    /// Number of occurrences aggregated into this violation.
-    pub count: u64,
-    /// At most three example contexts, formatted as `source ip:port @ ts; ...`.
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub examples: Vec<String>,
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OtherTrafficSummary {
+    /// Transport protocol (`udp` or `tcp`).
+    pub transport: Protocol,
+    /// Source endpoint in `ip:port` form.
+    pub src: String,
+    /// Destination endpoint in `ip:port` form.
+    pub dst: String,
+    /// Packets in the flow.
+    pub packets: u64,
+    /// Captured bytes in the flow.
+    pub bytes: u64,
+    /// Service guessed from a well-known port (e.g. `dns`, `mdns`, `http`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service: Option<String>,
 }
 
-/// Build a stub report with base fields filled and empty aggregates.
+/// Per-second metric series over one shared timeline.
+///
+/// Every array has one entry per second starting at `start`, so entry `i` of
+/// any series covers `[start + i, start + i + 1)`.
 ///
 /// # Examples
 /// ```
-/// use liveshark_core::make_stub_report;
+/// use liveshark_core::{FlowSeries, MetricSeries};
 ///
-/// let report = make_stub_report("capture.pcapng", 123);
-/// assert_eq!(report.report_version, liveshark_core::REPORT_VERSION);
-/// assert!(report.universes.is_empty());
+/// let series = MetricSeries {
+///     start: 1_700_000_000.0,
+///     universes: Vec::new(),
+///     flows: vec![FlowSeries {
+///         src: "10.0.0.1:6454".to_string(),
+///         dst: "10.0.0.255:6454".to_string(),
+///         vlan: None,
+///         outer_vlan: None,
+///         pps: vec![44, 44, 43],
+///         bps: vec![23_760, 23_760, 23_220],
+///     }],
+/// };
+/// assert_eq!(series.flows[0].pps.len(), 3);
 /// ```
-pub fn make_stub_report(input_path: &str, input_bytes: u64) -> Report {
-    Report {
-        report_version: REPORT_VERSION,
-        tool: ToolInfo {
-            name: "liveshark".to_string(),
-            version: env!("CARGO_PKG_VERSION").to_string(),
-        },
-        generated_at: DEFAULT_GENERATED_AT.to_string(),
-        input: InputInfo {
-            path: input_path.to_string(),
-            bytes: input_bytes,
-        },
-        capture_summary: None,
-        universes: vec![],
-        flows: vec![],
-        conflicts: vec![],
-        compliance: vec![],
-    }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricSeries {
+    /// First second of the timeline (seconds, same clock as `first_seen`).
+    pub start: f64,
+    /// Universe series, in the order of `universes[]`.
+    pub universes: Vec<UniverseSeries>,
+    /// Flow series, in the order of `flows[]`.
+    pub flows: Vec<FlowSeries>,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Per-second metrics of one universe.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UniverseSeries {
+    /// Universe identifier.
+    pub universe: u16,
+    /// Protocol of the frames (`artnet` or `sacn`).
+    pub proto: Protocol,
+    /// Frames received in each second, across all sources.
+    pub fps: Vec<u64>,
+    /// Packets missing in each second (sequence-tracked protocols only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loss: Option<Vec<u64>>,
+    /// Mean inter-arrival jitter in each second (ms), `null` without samples.
+    pub jitter_ms: Vec<Option<f64>>,
+}
+
+/// Per-second rates of one flow.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlowSeries {
+    /// Source endpoint in `ip:port` form.
+    pub src: String,
+    /// Destination endpoint in `ip:port` form.
+    pub dst: String,
+    /// VLAN ID of the flow, as in `flows[]`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vlan: Option<u16>,
+    /// Service VLAN ID of the flow, as in `flows[]`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outer_vlan: Option<u16>,
+    /// Packets in each second.
+    pub pps: Vec<u64>,
+    /// UDP payload bytes in each second.
+    pub bps: Vec<u64>,
+}
+
+/// Kind of an [`AnalysisWarning`].
+///
+/// Serializes in `snake_case`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningKind {
+    /// Packets are timestamped before an earlier packet of the capture (clock
+    /// stepped back, or files merged out of order).
+    TimestampBackwards,
+    /// Consecutive packets are further apart than `max_timestamp_jump_s`.
+    TimestampJump,
+}
+
+/// Condition that makes part of the analysis less trustworthy without being
+/// a protocol violation, such as timestamps that go backwards.
+///
+/// `first_packet` and `last_packet` are 1-based packet numbers, as shown by
+/// capture tools; windowed metrics covering that range may be skewed.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{AnalysisWarning, WarningKind};
+///
+/// let warning = AnalysisWarning {
+///     kind: WarningKind::TimestampBackwards,
+///     first_packet: 1200,
+///     last_packet: 1450,
+///     packets: 251,
+///     time_start: Some("2024-05-01T20:00:00Z".to_string()),
+///     time_end: Some("2024-05-01T20:00:05Z".to_string()),
+///     message: "timestamps go back up to 3600.000 s before an earlier packet".to_string(),
+/// };
+/// let json = serde_json::to_value(&warning)?;
+/// assert_eq!(json["kind"], "timestamp_backwards");
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnalysisWarning {
+    /// What was detected.
+    pub kind: WarningKind,
+    /// First packet of the affected range.
+    pub first_packet: u64,
+    /// Last packet of the affected range.
+    pub last_packet: u64,
+    /// Packets flagged in the range.
+    pub packets: u64,
+    /// RFC3339 timestamp of `first_packet`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_start: Option<String>,
+    /// RFC3339 timestamp of `last_packet`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_end: Option<String>,
+    /// Human-readable description.
+    pub message: String,
+}
+
+/// Moment where a large share of a universe's active channels changed in one
+/// frame, typically a console going to a new cue.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{Protocol, SceneChange};
+///
+/// let change = SceneChange {
+///     timestamp: 12.5,
+///     universe: 1,
+///     proto: Protocol::ArtNet,
+///     source_id: "artnet:10.0.0.1:6454".to_string(),
+///     changed_channels: 96,
+///     active_channels: 120,
+///     magnitude: 0.74,
+/// };
+/// assert!(change.changed_channels <= change.active_channels);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SceneChange {
+    /// Time of the frame that changed (seconds, same clock as `first_seen`).
+    pub timestamp: f64,
+    /// Universe of the frame.
+    pub universe: u16,
+    /// Protocol of the universe.
+    pub proto: Protocol,
+    /// Source that sent the frame.
+    pub source_id: String,
+    /// Channels that moved by at least 10% of full scale since the previous
+    /// frame of the source.
+    pub changed_channels: u16,
+    /// Channels non-zero at any point of the source's frames.
+    pub active_channels: u16,
+    /// Mean change of the changed channels, as a fraction of full scale
+    /// (0 to 1, three decimals).
+    pub magnitude: f64,
+}
+
+/// Ramp of a range of adjacent channels moving in one direction over several
+/// frames of one source, with how smoothly it was sent.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{Fade, FadeDirection, Protocol, SteppyCause};
+///
+/// let fade = Fade {
+///     timestamp: 42.0,
+///     duration_s: 3.0,
+///     universe: 1,
+///     proto: Protocol::ArtNet,
+///     source_id: "artnet:10.0.0.1:6454".to_string(),
+///     start_channel: 1,
+///     end_channel: 24,
+///     direction: FadeDirection::Down,
+///     delta: 255,
+///     steps: 30,
+///     max_step: 9,
+///     fps: 10.0,
+///     max_gap_ms: 100.0,
+///     steppy_cause: Some(SteppyCause::LowFrameRate),
+/// };
+/// assert!(fade.start_channel <= fade.end_channel);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fade {
+    /// Time of the last frame before the ramp (seconds, same clock as
+    /// `first_seen`).
+    pub timestamp: f64,
+    /// Time from `timestamp` to the frame that reached the final level
+    /// (seconds).
+    pub duration_s: f64,
+    /// Universe of the frames.
+    pub universe: u16,
+    /// Protocol of the universe.
+    pub proto: Protocol,
+    /// Source that sent the frames.
+    pub source_id: String,
+    /// First channel of the range (1-based).
+    pub start_channel: u16,
+    /// Last channel of the range (inclusive).
+    pub end_channel: u16,
+    /// Whether the levels rose or fell.
+    pub direction: FadeDirection,
+    /// Largest overall level change of a channel in the range.
+    pub delta: u8,
+    /// Most level changes of a channel in the range.
+    pub steps: u32,
+    /// Largest level change between two frames.
+    pub max_step: u8,
+    /// Frame rate of the source during the fade.
+    pub fps: f64,
+    /// Longest interval between two frames during the fade (milliseconds).
+    pub max_gap_ms: f64,
+    /// Why the fade looks steppy, when its steps are large; absent for a
+    /// smooth fade.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub steppy_cause: Option<SteppyCause>,
+}
+
+/// Direction of a fade.
+///
+/// Serializes in lowercase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FadeDirection {
+    /// Levels rose.
+    Up,
+    /// Levels fell.
+    Down,
+}
+
+/// Reason a fade moved in large steps.
+///
+/// Serializes in snake case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SteppyCause {
+    /// The source sent too few frames per second for the fade's speed.
+    LowFrameRate,
+    /// Frames were missing (lost or late) during the fade.
+    FrameGap,
+}
+
+/// How smoothly one pan or tilt axis of a patched fixture moved, from the
+/// frames of one source.
+///
+/// Positions combine the coarse and fine slots into a 16-bit value; steps
+/// are the position changes between consecutive frames, as a percentage of
+/// the full travel.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{MovementAxis, MovementSmoothness, Protocol};
+///
+/// let pan = MovementSmoothness {
+///     universe: 1,
+///     proto: Protocol::ArtNet,
+///     source_id: "artnet:10.0.0.1:6454".to_string(),
+///     fixture: "Spot 3".to_string(),
+///     axis: MovementAxis::Pan,
+///     coarse: 21,
+///     fine: Some(22),
+///     moving_frames: 400,
+///     step_p50_pct: 0.05,
+///     step_p95_pct: 0.12,
+///     step_max_pct: 1.4,
+///     stalls: 2,
+///     max_stall_ms: Some(180.0),
+/// };
+/// assert!(pan.step_p50_pct <= pan.step_p95_pct);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MovementSmoothness {
+    /// Universe of the fixture.
+    pub universe: u16,
+    /// Protocol of the frames.
+    pub proto: Protocol,
+    /// Source that sent the frames.
+    pub source_id: String,
+    /// Fixture name from the fixture map.
+    pub fixture: String,
+    /// Axis measured.
+    pub axis: MovementAxis,
+    /// Coarse slot of the axis.
+    pub coarse: u16,
+    /// Fine slot of the axis, when it is 16-bit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fine: Option<u16>,
+    /// Frames in which the position changed.
+    pub moving_frames: u64,
+    /// Median step (percent of full travel, three decimals).
+    pub step_p50_pct: f64,
+    /// 95th percentile step (percent of full travel, three decimals).
+    pub step_p95_pct: f64,
+    /// Largest step (percent of full travel, three decimals).
+    pub step_max_pct: f64,
+    /// Frame gaps during a movement after which the fixture jumped to catch
+    /// up (lost or late frames).
+    pub stalls: u64,
+    /// Longest of those gaps (milliseconds).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_stall_ms: Option<f64>,
+}
+
+/// Movement axis of a moving-head fixture.
+///
+/// Serializes in lowercase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MovementAxis {
+    /// Horizontal rotation.
+    Pan,
+    /// Vertical rotation.
+    Tilt,
+}
+
+/// Two universes whose output matched over the capture, possibly shifted by
+/// a constant number of channels; a sign of a duplicated patch or of a node
+/// routing one input to two outputs.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{Protocol, UniverseMirror};
+///
+/// let mirror = UniverseMirror {
+///     universe: 1,
+///     proto: Protocol::ArtNet,
+///     mirror: 5,
+///     mirror_proto: Protocol::ArtNet,
+///     channel_offset: 0,
+///     samples: 240,
+///     matching_samples: 240,
+/// };
+/// assert!(mirror.matching_samples <= mirror.samples);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UniverseMirror {
+    /// Lower universe of the pair.
+    pub universe: u16,
+    /// Protocol of `universe`.
+    pub proto: Protocol,
+    /// Universe carrying the copy.
+    pub mirror: u16,
+    /// Protocol of `mirror`.
+    pub mirror_proto: Protocol,
+    /// Channel of the copy minus channel of the original for the same value
+    /// (0 for an exact copy).
+    pub channel_offset: i16,
+    /// Samples where either universe had a non-blank output.
+    pub samples: u64,
+    /// Samples where the outputs matched at `channel_offset`.
+    pub matching_samples: u64,
+}
+
+/// Control of a universe passing from one source to another, e.g. a backup
+/// console taking over after the main one stopped.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{Protocol, Takeover, TakeoverCause};
+///
+/// let takeover = Takeover {
+///     timestamp: 612.4,
+///     universe: 1,
+///     proto: Protocol::Sacn,
+///     from_source: "sacn:cid:main".to_string(),
+///     to_source: "sacn:cid:backup".to_string(),
+///     cause: TakeoverCause::SourceStopped,
+///     gap_ms: 1240.0,
+///     from_priority: Some(100),
+///     to_priority: Some(100),
+/// };
+/// assert_ne!(takeover.from_source, takeover.to_source);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Takeover {
+    /// Time the new source took control (seconds, same clock as
+    /// `first_seen`).
+    pub timestamp: f64,
+    /// Universe identifier.
+    pub universe: u16,
+    /// Protocol of the universe.
+    pub proto: Protocol,
+    /// Source that lost control.
+    pub from_source: String,
+    /// Source that took control.
+    pub to_source: String,
+    /// Why control changed hands.
+    pub cause: TakeoverCause,
+    /// Time between the last frame of `from_source` and the first frame of
+    /// `to_source` after it (milliseconds, three decimals); 0 when
+    /// `from_source` was out-prioritized while still sending.
+    pub gap_ms: f64,
+    /// sACN priority of `from_source` when it lost control; absent for
+    /// Art-Net.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_priority: Option<u8>,
+    /// sACN priority of `to_source`; absent for Art-Net.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_priority: Option<u8>,
+}
+
+/// Why control of a universe changed hands.
+///
+/// Serializes in snake case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TakeoverCause {
+    /// The controlling source fell silent for longer than the source loss
+    /// timeout.
+    SourceStopped,
+    /// A live source with a higher sACN priority outranked the controller.
+    OutPrioritized,
+}
+
+/// Interval during which the same sACN sources held the highest active
+/// priority of a universe; several holders are a tie, whose frames the
+/// receivers merge (HTP) or fight over.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::PrioritySpan;
+///
+/// let span = PrioritySpan {
+///     universe: 1,
+///     start: 0.0,
+///     end: 12.5,
+///     priority: 100,
+///     holders: vec!["sacn:cid:main".to_string(), "sacn:cid:backup".to_string()],
+/// };
+/// assert!(span.holders.len() > 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrioritySpan {
+    /// Universe identifier.
+    pub universe: u16,
+    /// Start of the interval (seconds, same clock as `first_seen`).
+    pub start: f64,
+    /// End of the interval: the next change, or the last frame of the
+    /// universe for the final span.
+    pub end: f64,
+    /// Highest priority among the live sources.
+    pub priority: u8,
+    /// Live sources sending at `priority`, sorted.
+    pub holders: Vec<String>,
+}
+
+/// Interval longer than `silence_threshold_s` during which a universe
+/// received no frame from any source, e.g. an output freeze.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{Protocol, UniverseSilence};
+///
+/// let silence = UniverseSilence {
+///     universe: 1,
+///     proto: Protocol::Sacn,
+///     start: 42.1,
+///     duration_s: 3.2,
+/// };
+/// assert!(silence.duration_s > 1.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UniverseSilence {
+    /// Universe identifier.
+    pub universe: u16,
+    /// Protocol of the universe.
+    pub proto: Protocol,
+    /// Time of the last frame before the silence (seconds, same clock as
+    /// `first_seen`).
+    pub start: f64,
+    /// Time until the next frame (seconds, microsecond resolution).
+    pub duration_s: f64,
+}
+
+/// Timed event of one source of a universe.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{EventKind, Protocol, ReportEvent};
+///
+/// let event = ReportEvent {
+///     kind: EventKind::SourceDropout,
+///     timestamp: 42.1,
+///     universe: 1,
+///     proto: Protocol::Sacn,
+///     source_id: "sacn:cid:00112233445566778899aabbccddeeff".to_string(),
+///     duration_s: 4.2,
+///     resumed: true,
+/// };
+/// assert!(event.duration_s > 2.5);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportEvent {
+    pub kind: EventKind,
+    /// Time of the source's last frame before the event (seconds, same
+    /// clock as `first_seen`).
+    pub timestamp: f64,
+    /// Universe identifier.
+    pub universe: u16,
+    /// Protocol of the universe.
+    pub proto: Protocol,
+    /// Source identifier, as in the universe's `sources`.
+    pub source_id: String,
+    /// Time until the source's next frame, or until the end of the capture
+    /// when it never resumed (seconds, microsecond resolution).
+    pub duration_s: f64,
+    /// Whether the source sent again before the end of the capture.
+    pub resumed: bool,
+}
+
+/// Kind of a report event.
+///
+/// Serializes in snake case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    /// An sACN source stopped sending for longer than the 2.5 s network
+    /// data loss timeout without terminating its stream.
+    SourceDropout,
+}
+
+/// Overall network health of a capture, from 100 (clean) down to 0.
+///
+/// Each universe starts at 100 and loses points for loss, jitter,
+/// conflicts, and data-loss events (silences); the capture score is the
+/// mean of the universe scores minus a penalty for the worst protocol
+/// compliance. `penalties` holds the points behind `score`.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{HealthPenalties, HealthScore};
+///
+/// let health = HealthScore {
+///     score: 88.5,
+///     penalties: HealthPenalties {
+///         loss: 4.0,
+///         jitter: 2.5,
+///         violations: 5.0,
+///         ..HealthPenalties::default()
+///     },
+///     universes: Vec::new(),
+/// };
+/// assert_eq!(100.0 - health.penalties.total(), health.score);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthScore {
+    /// Capture score, 0 to 100, one decimal.
+    pub score: f64,
+    /// Points deducted from 100, per factor.
+    pub penalties: HealthPenalties,
+    /// Sub-scores of every universe, in `universes` order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub universes: Vec<UniverseHealth>,
+}
+
+/// Points a health score lost per factor (one decimal).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HealthPenalties {
+    /// Sequence loss (up to 40).
+    pub loss: f64,
+    /// Inter-arrival jitter (up to 20).
+    pub jitter: f64,
+    /// Conflicting sources (up to 20).
+    pub conflicts: f64,
+    /// Silences of the universe (up to 20).
+    pub data_loss: f64,
+    /// Compliance violations, weighted by severity (up to 20; capture only).
+    pub violations: f64,
+}
+
+impl HealthPenalties {
+    /// Sum of all penalties.
+    pub fn total(&self) -> f64 {
+        self.loss + self.jitter + self.conflicts + self.data_loss + self.violations
+    }
+}
+
+/// Health sub-score of one universe.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{HealthPenalties, Protocol, UniverseHealth};
+///
+/// let health = UniverseHealth {
+///     universe: 1,
+///     proto: Protocol::Sacn,
+///     score: 100.0,
+///     penalties: HealthPenalties::default(),
+/// };
+/// assert_eq!(health.penalties.total(), 0.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UniverseHealth {
+    /// Universe identifier.
+    pub universe: u16,
+    /// Protocol of the universe.
+    pub proto: Protocol,
+    /// Universe score, 0 to 100, one decimal.
+    pub score: f64,
+    /// Points deducted from 100, per factor (`violations` is always 0).
+    pub penalties: HealthPenalties,
+}
+
+/// Delay between synchronized sACN data packets and the synchronization
+/// packet that releases them, for one sync address.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::SyncLatency;
+///
+/// let sync = SyncLatency {
+///     sync_address: 7000,
+///     universes: vec![1, 2, 3, 4],
+///     sync_packets: 1200,
+///     synced_frames: 4800,
+///     unsynced_frames: 0,
+///     latency: None,
+///     per_universe: Vec::new(),
+/// };
+/// assert_eq!(sync.synced_frames, 4 * sync.sync_packets);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncLatency {
+    /// Synchronization universe.
+    pub sync_address: u16,
+    /// Universes whose data packets name this sync address, sorted.
+    pub universes: Vec<u16>,
+    /// Synchronization packets sent to the address.
+    pub sync_packets: u64,
+    /// Data packets released by a synchronization packet of their source
+    /// within the timeout.
+    pub synced_frames: u64,
+    /// Data packets with no synchronization packet of their source within
+    /// the timeout.
+    pub unsynced_frames: u64,
+    /// Delay from each synced data packet to its synchronization packet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency: Option<LatencyDistribution>,
+    /// The same counts and delays for each universe, sorted by universe.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub per_universe: Vec<UniverseSyncLatency>,
+}
+
+/// Sync-to-data latency of one universe under a sync address.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::UniverseSyncLatency;
+///
+/// let universe = UniverseSyncLatency {
+///     universe: 1,
+///     synced_frames: 1200,
+///     unsynced_frames: 3,
+///     latency: None,
+/// };
+/// assert_eq!(universe.synced_frames + universe.unsynced_frames, 1203);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UniverseSyncLatency {
+    /// Universe of the data packets.
+    pub universe: u16,
+    /// Data packets of the universe released within the timeout.
+    pub synced_frames: u64,
+    /// Data packets of the universe never released within the timeout.
+    pub unsynced_frames: u64,
+    /// Delay from each synced data packet to its synchronization packet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency: Option<LatencyDistribution>,
+}
+
+/// Universes an sACN source declares in universe discovery, compared with
+/// the universes it sends data for.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::SacnUniverseDiscovery;
+///
+/// let source = SacnUniverseDiscovery {
+///     cid: "00112233445566778899aabbccddeeff".to_string(),
+///     source_name: Some("console".to_string()),
+///     source_ip: "10.0.0.1".to_string(),
+///     discovery_packets: 6,
+///     pages: 1,
+///     declared: vec![1, 2, 3],
+///     sent: vec![1, 2, 4],
+///     undeclared: vec![4],
+///     not_sent: vec![3],
+/// };
+/// assert!(!source.undeclared.is_empty());
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SacnUniverseDiscovery {
+    /// Component identifier of the source.
+    pub cid: String,
+    /// Source name from the last discovery packet naming one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_name: Option<String>,
+    /// IP address of the last discovery packet.
+    pub source_ip: String,
+    /// Universe discovery packets received.
+    pub discovery_packets: u64,
+    /// Pages the source splits its universe list into.
+    pub pages: u16,
+    /// Universes listed in any discovery page, sorted.
+    pub declared: Vec<u16>,
+    /// Universes the source sent data for, sorted.
+    pub sent: Vec<u16>,
+    /// Universes sent but never declared, sorted.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub undeclared: Vec<u16>,
+    /// Universes declared but never sent, sorted.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub not_sent: Vec<u16>,
+}
+
+/// Per-address priorities an sACN source sends for a universe (start code
+/// 0xdd), as of its last priority packet.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{AddressPriorityMap, AddressPriorityRange};
+///
+/// let map = AddressPriorityMap {
+///     universe: 1,
+///     source_id: "sacn:cid:00112233445566778899aabbccddeeff".to_string(),
+///     packets: 40,
+///     changes: 1,
+///     ranges: vec![
+///         AddressPriorityRange { first_slot: 1, last_slot: 24, priority: 150 },
+///         AddressPriorityRange { first_slot: 25, last_slot: 512, priority: 0 },
+///     ],
+/// };
+/// assert_eq!(map.ranges[1].priority, 0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddressPriorityMap {
+    pub universe: u16,
+    /// Source identifier, as in the universe's `sources`.
+    pub source_id: String,
+    /// Per-address priority packets received.
+    pub packets: u64,
+    /// Packets whose priorities differ from the previous packet's.
+    pub changes: u64,
+    /// Runs of slots sharing a priority, in slot order.
+    pub ranges: Vec<AddressPriorityRange>,
+}
+
+/// Consecutive slots sharing a per-address priority. A priority of 0 means
+/// the source does not drive those slots.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::AddressPriorityRange;
+///
+/// let range = AddressPriorityRange { first_slot: 1, last_slot: 24, priority: 150 };
+/// assert_eq!(range.last_slot - range.first_slot + 1, 24);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddressPriorityRange {
+    /// First slot of the run, 1-based.
+    pub first_slot: u16,
+    /// Last slot of the run, 1-based.
+    pub last_slot: u16,
+    pub priority: u8,
+}
+
+/// Availability of an Art-Net node from the cadence of its ArtPollReply
+/// announcements.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{NodeAvailability, NodeOffline};
+///
+/// let node = NodeAvailability {
+///     ip: "2.0.0.10".to_string(),
+///     bind_index: 1,
+///     short_name: Some("Truss node".to_string()),
+///     long_name: None,
+///     universes: vec![1, 2],
+///     replies: 40,
+///     first_seen: Some(0.0),
+///     last_seen: Some(120.0),
+///     reply_interval_s: Some(3.0),
+///     offline: vec![NodeOffline {
+///         start: 30.0,
+///         end: 42.0,
+///         duration_s: 12.0,
+///         resumed: true,
+///         lost_packets: 0,
+///     }],
+/// };
+/// assert_eq!(node.offline.len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeAvailability {
+    /// Source IP address of the replies.
+    pub ip: String,
+    /// Bind index of the replies (one per group of ports of a device).
+    pub bind_index: u8,
+    /// Latest non-empty short name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short_name: Option<String>,
+    /// Latest non-empty long name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub long_name: Option<String>,
+    /// Port-Addresses the node announced as outputs, sorted.
+    pub universes: Vec<u16>,
+    /// ArtPollReply packets received.
+    pub replies: u64,
+    /// First reply (seconds, same clock as `first_seen` of universes).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_seen: Option<f64>,
+    /// Last reply.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_seen: Option<f64>,
+    /// Median interval between replies (seconds, three decimals); absent
+    /// with fewer than three replies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_interval_s: Option<f64>,
+    /// Windows during which the node stopped replying, in time order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub offline: Vec<NodeOffline>,
+}
+
+/// Interval of more than three reply cadences without an ArtPollReply.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeOffline {
+    /// Last reply before the gap.
+    pub start: f64,
+    /// Next reply, or the end of the capture when the node never replied
+    /// again.
+    pub end: f64,
+    /// `end - start` (seconds, microsecond resolution).
+    pub duration_s: f64,
+    /// Whether the node replied again before the end of the capture.
+    pub resumed: bool,
+    /// Art-Net packets lost (sequence gaps) on the node's universes
+    /// between `start` and `end`.
+    pub lost_packets: u64,
+}
+
+/// Timecode stream sent with ArtTimeCode by one source.
+///
+/// Values read `hh:mm:ss:ff`, with `;` before the frames for drop-frame
+/// timecode.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{TimecodeDiscontinuity, TimecodeStream};
+///
+/// let stream = TimecodeStream {
+///     source_ip: "2.0.0.1".to_string(),
+///     stream_id: 0,
+///     frame_rate: Some(25.0),
+///     packets: 4500,
+///     first_value: "01:00:00:00".to_string(),
+///     last_value: "01:03:10:00".to_string(),
+///     first_seen: Some(0.0),
+///     last_seen: Some(180.0),
+///     discontinuities: vec![TimecodeDiscontinuity {
+///         timestamp: 60.0,
+///         from: "01:01:00:00".to_string(),
+///         to: "01:01:10:00".to_string(),
+///         jump_s: 10.0,
+///     }],
+///     dropouts: Vec::new(),
+/// };
+/// assert_eq!(stream.discontinuities.len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimecodeStream {
+    /// Source IP address of the packets.
+    pub source_ip: String,
+    /// ArtTimeCode stream identifier; 0 is the master stream.
+    pub stream_id: u8,
+    /// Frame rate of the latest packet (24, 25, 29.97, or 30); absent for
+    /// an unknown timecode type.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frame_rate: Option<f64>,
+    /// ArtTimeCode packets received.
+    pub packets: u64,
+    /// Timecode value of the first packet.
+    pub first_value: String,
+    /// Timecode value of the last packet.
+    pub last_value: String,
+    /// First packet (seconds, same clock as `first_seen` of universes).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_seen: Option<f64>,
+    /// Last packet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_seen: Option<f64>,
+    /// Jumps of the timecode against the capture clock, in time order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub discontinuities: Vec<TimecodeDiscontinuity>,
+    /// Gaps of more than 0.5 s between packets, in time order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dropouts: Vec<TimecodeDropout>,
+}
+
+/// Timecode value that moved by more than two frames beyond the capture
+/// time elapsed since the previous packet, or changed frame rate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimecodeDiscontinuity {
+    /// Timestamp of the packet after the jump.
+    pub timestamp: f64,
+    /// Timecode value before the jump.
+    pub from: String,
+    /// Timecode value after the jump.
+    pub to: String,
+    /// Jump beyond the elapsed capture time (seconds, negative when the
+    /// timecode went backwards).
+    pub jump_s: f64,
+}
+
+/// Interval of more than 0.5 s without an ArtTimeCode packet of a stream.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimecodeDropout {
+    /// Last packet before the gap.
+    pub start: f64,
+    /// Gap length (seconds, microsecond resolution).
+    pub duration_s: f64,
+}
+
+/// PosiStageNet tracker sent by one tracking system.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::PsnTrackerSummary;
+///
+/// let tracker = PsnTrackerSummary {
+///     source_ip: "10.0.0.5".to_string(),
+///     tracker_id: 1,
+///     name: Some("Lead vocal".to_string()),
+///     system_name: Some("Stage tracking".to_string()),
+///     updates: 1800,
+///     update_rate_hz: Some(60.0),
+///     first_seen: Some(0.0),
+///     last_seen: Some(30.0),
+///     last_position: Some([1.25, 3.0, 0.0]),
+///     max_speed: Some(1.4),
+///     dropouts: Vec::new(),
+/// };
+/// assert_eq!(tracker.update_rate_hz, Some(60.0));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PsnTrackerSummary {
+    /// Source IP address of the packets.
+    pub source_ip: String,
+    /// Tracker id.
+    pub tracker_id: u16,
+    /// Tracker name from PSN info packets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Name of the tracking system from PSN info packets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_name: Option<String>,
+    /// PSN data packets listing the tracker.
+    pub updates: u64,
+    /// Updates per second between the first and last update; absent with
+    /// fewer than two timestamped updates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_rate_hz: Option<f64>,
+    /// First update (seconds, same clock as `first_seen` of universes).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_seen: Option<f64>,
+    /// Last update.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_seen: Option<f64>,
+    /// Last position sent (x, y, z metres, millimetre resolution).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_position: Option<[f64; 3]>,
+    /// Highest speed sent (metres per second); absent when no update
+    /// carried a speed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_speed: Option<f64>,
+    /// Gaps of more than 0.5 s between updates, in time order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dropouts: Vec<PsnDropout>,
+}
+
+/// Interval of more than 0.5 s without an update of a PSN tracker.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PsnDropout {
+    /// Last update before the gap.
+    pub start: f64,
+    /// Gap length (seconds, microsecond resolution).
+    pub duration_s: f64,
+}
+
+/// Open Sound Control messages sent to one address pattern.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::OscAddressSummary;
+///
+/// let address = OscAddressSummary {
+///     address: "/cue/12/go".to_string(),
+///     messages: 3,
+///     senders: vec!["10.0.0.20".to_string()],
+///     first_seen: Some(12.5),
+///     last_seen: Some(98.0),
+/// };
+/// assert_eq!(address.messages, 3);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OscAddressSummary {
+    /// Address pattern of the messages.
+    pub address: String,
+    /// Messages sent to the address, bundled ones included.
+    pub messages: u64,
+    /// Source IPs of the messages, sorted by address.
+    pub senders: Vec<String>,
+    /// First message (seconds, same clock as `first_seen` of universes).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_seen: Option<f64>,
+    /// Last message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_seen: Option<f64>,
+}
+
+/// CITP peer seen over UDP, from its PINF announcements and MSEX layer
+/// status and streams.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{CitpLayerSummary, CitpPeerSummary, CitpStreamSummary};
+///
+/// let peer = CitpPeerSummary {
+///     source_ip: "10.0.0.40".to_string(),
+///     name: Some("Server A".to_string()),
+///     peer_type: Some("MediaServer".to_string()),
+///     state: Some("Running".to_string()),
+///     listening_port: Some(4811),
+///     packets: 120,
+///     layers: vec![CitpLayerSummary {
+///         layer: 1,
+///         physical_output: 0,
+///         media_name: "intro.mov".to_string(),
+///     }],
+///     streams: vec![CitpStreamSummary {
+///         source_id: 1,
+///         format: "JPEG".to_string(),
+///         width: 160,
+///         height: 90,
+///         frames: 100,
+///         fps: Some(10.0),
+///     }],
+/// };
+/// assert_eq!(peer.streams[0].fps, Some(10.0));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CitpPeerSummary {
+    /// Source IP address of the packets.
+    pub source_ip: String,
+    /// Name from the latest PINF/PLoc announcement.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Peer type from the latest announcement (`LightingConsole`,
+    /// `MediaServer`, `Visualizer`, ...).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peer_type: Option<String>,
+    /// State from the latest announcement.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// TCP port from the latest announcement.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub listening_port: Option<u16>,
+    /// CITP packets sent.
+    pub packets: u64,
+    /// Media-server layers from MSEX layer status, sorted by layer number.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub layers: Vec<CitpLayerSummary>,
+    /// MSEX streams sent, sorted by source id.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub streams: Vec<CitpStreamSummary>,
+}
+
+/// Latest MSEX status of a media-server layer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CitpLayerSummary {
+    /// Layer number.
+    pub layer: u8,
+    /// Physical output the layer is shown on.
+    pub physical_output: u8,
+    /// Media playing on the layer.
+    pub media_name: String,
+}
+
+/// MSEX stream (thumbnails or preview video) of one video source.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CitpStreamSummary {
+    /// Video source id.
+    pub source_id: u16,
+    /// Frame format of the latest frame (`RGB8`, `JPEG`, `PNG `, ...).
+    pub format: String,
+    /// Width of the latest frame, in pixels.
+    pub width: u16,
+    /// Height of the latest frame, in pixels.
+    pub height: u16,
+    /// Frames received.
+    pub frames: u64,
+    /// Frames per second between the first and last frame; absent with
+    /// fewer than two timestamped frames.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fps: Option<f64>,
+}
+
+/// PTP domain seen over UDP, from the messages on ports 319 and 320.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{PtpDomainSummary, PtpGrandmasterSummary, PtpMasterChange};
+///
+/// let domain = PtpDomainSummary {
+///     domain: 0,
+///     messages: 250,
+///     grandmasters: vec![PtpGrandmasterSummary {
+///         identity: "00:1b:21:ff:fe:12:34:56".to_string(),
+///         announced_by: vec!["10.0.0.1".to_string()],
+///         priority1: 128,
+///         clock_class: 6,
+///         clock_accuracy: 0x21,
+///         priority2: 128,
+///         steps_removed: 0,
+///         announces: 30,
+///         log_announce_interval: 1,
+///         announce_interval_s: Some(2.0),
+///         max_announce_gap_s: Some(2.004),
+///         first_seen: Some(0.0),
+///         last_seen: Some(58.0),
+///     }],
+///     master_changes: vec![PtpMasterChange {
+///         timestamp: Some(31.5),
+///         previous: "00:1b:21:ff:fe:00:00:01".to_string(),
+///         grandmaster: "00:1b:21:ff:fe:12:34:56".to_string(),
+///     }],
+/// };
+/// assert_eq!(domain.grandmasters[0].clock_class, 6);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PtpDomainSummary {
+    /// PTP domain number.
+    pub domain: u8,
+    /// PTP messages of any type in the domain.
+    pub messages: u64,
+    /// Grandmasters named by Announce messages, sorted by identity.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub grandmasters: Vec<PtpGrandmasterSummary>,
+    /// Changes of the best grandmaster, in capture order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub master_changes: Vec<PtpMasterChange>,
+}
+
+/// Grandmaster clock advertised by Announce messages.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PtpGrandmasterSummary {
+    /// Clock identity, as eight colon-separated hex bytes.
+    pub identity: String,
+    /// Source IP addresses of the ports announcing it (the grandmaster
+    /// itself, or boundary clocks), sorted.
+    pub announced_by: Vec<String>,
+    /// Priority 1 from the latest Announce.
+    pub priority1: u8,
+    /// Clock class from the latest Announce.
+    pub clock_class: u8,
+    /// Clock accuracy from the latest Announce.
+    pub clock_accuracy: u8,
+    /// Priority 2 from the latest Announce.
+    pub priority2: u8,
+    /// Steps removed from the latest Announce.
+    pub steps_removed: u16,
+    /// Announce messages naming it.
+    pub announces: u64,
+    /// Announce interval advertised by the latest Announce, as log2
+    /// seconds.
+    pub log_announce_interval: i8,
+    /// Mean measured interval between Announce messages of a port, in
+    /// seconds; absent with fewer than two timestamped messages from one
+    /// port.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub announce_interval_s: Option<f64>,
+    /// Longest measured interval between Announce messages of a port, in
+    /// seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_announce_gap_s: Option<f64>,
+    /// First Announce.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_seen: Option<f64>,
+    /// Last Announce.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_seen: Option<f64>,
+}
+
+/// Change of the best grandmaster of a PTP domain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PtpMasterChange {
+    /// Timestamp of the Announce that made the change visible (seconds).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<f64>,
+    /// Identity of the grandmaster before the change.
+    pub previous: String,
+    /// Identity of the grandmaster after the change.
+    pub grandmaster: String,
+}
+
+/// IPv4 multicast group named by IGMP membership reports or leaves.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{IgmpGroupSummary, IgmpMemberSummary};
+///
+/// let group = IgmpGroupSummary {
+///     group: "239.255.0.1".to_string(),
+///     universe: Some(1),
+///     members: vec![IgmpMemberSummary {
+///         receiver: "10.0.0.20".to_string(),
+///         joins: 3,
+///         leaves: 0,
+///         first_joined: Some(0.5),
+///         last_left: None,
+///         member_at_end: true,
+///     }],
+/// };
+/// assert_eq!(group.universe, Some(1));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IgmpGroupSummary {
+    /// Multicast group address.
+    pub group: String,
+    /// sACN universe carried by the group (239.255.H.L), if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub universe: Option<u16>,
+    /// Receivers that joined or left the group, sorted by address.
+    pub members: Vec<IgmpMemberSummary>,
+}
+
+/// One receiver's membership of a multicast group.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IgmpMemberSummary {
+    /// Source IP address of the reports.
+    pub receiver: String,
+    /// Membership reports for the group (joins, and answers to queries).
+    pub joins: u64,
+    /// Leaves for the group.
+    pub leaves: u64,
+    /// First membership report.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_joined: Option<f64>,
+    /// Last leave.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_left: Option<f64>,
+    /// Whether the receiver's last message for the group was a report.
+    pub member_at_end: bool,
+}
+
+/// Network-health event from ARP or DHCP traffic.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{NetworkEvent, NetworkEventKind};
+///
+/// let event = NetworkEvent {
+///     kind: NetworkEventKind::IpConflict,
+///     timestamp: Some(31.2),
+///     ip: "10.0.0.10".to_string(),
+///     mac: "02:00:00:00:00:02".to_string(),
+///     previous_mac: Some("02:00:00:00:00:01".to_string()),
+///     previous_ip: None,
+/// };
+/// assert_eq!(event.kind, NetworkEventKind::IpConflict);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetworkEvent {
+    pub kind: NetworkEventKind,
+    /// Time of the ARP or DHCP packet that revealed the event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<f64>,
+    /// IPv4 address claimed or assigned.
+    pub ip: String,
+    /// Hardware address of the host that claimed or received `ip`.
+    pub mac: String,
+    /// Hardware address that claimed `ip` before (`ip_conflict`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_mac: Option<String>,
+    /// Address the host was assigned before (`dhcp_address_change`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_ip: Option<String>,
+}
+
+/// Kind of a network-health event.
+///
+/// Serializes in snake case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NetworkEventKind {
+    /// A second host announced an IPv4 address in ARP.
+    IpConflict,
+    /// A DHCP server acknowledged a different address for a client than
+    /// its previous lease.
+    DhcpAddressChange,
+}
+
+/// ArtTrigger packet seen in the capture.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::TriggerEvent;
+///
+/// let trigger = TriggerEvent {
+///     timestamp: Some(12.5),
+///     source_ip: "2.0.0.1".to_string(),
+///     destination_ip: "2.255.255.255".to_string(),
+///     oem: 0xffff,
+///     key: 1,
+///     key_name: Some("macro".to_string()),
+///     sub_key: 4,
+/// };
+/// assert_eq!(trigger.sub_key, 4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TriggerEvent {
+    /// Packet timestamp (seconds).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<f64>,
+    /// Source IP address of the trigger.
+    pub source_ip: String,
+    /// Destination IP address (a node, or a broadcast address).
+    pub destination_ip: String,
+    /// OEM code the trigger is meant for; 65535 addresses every device.
+    pub oem: u16,
+    /// Trigger key.
+    pub key: u8,
+    /// `ascii`, `macro`, `soft`, or `show` for the keys Art-Net defines
+    /// with OEM 65535; absent otherwise.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_name: Option<String>,
+    /// Trigger sub-key (character, macro, soft key, or show number).
+    pub sub_key: u8,
+}
+
+/// RDM responders behind one node port, from its ArtTodData table of
+/// devices and the ArtRdm responses it relayed.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{PortAddress, RdmPortInventory};
+///
+/// let port = RdmPortInventory {
+///     node_ip: "2.0.0.10".to_string(),
+///     universe: 3,
+///     port_address: PortAddress::from_masked(3),
+///     port: Some(1),
+///     bind_index: Some(1),
+///     tod_uid_total: Some(2),
+///     tod_packets: 1,
+///     responders: vec!["7a70:00000001".to_string(), "7a70:00000002".to_string()],
+///     unlisted: Vec::new(),
+/// };
+/// assert_eq!(port.responders.len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RdmPortInventory {
+    /// IP address of the node.
+    pub node_ip: String,
+    /// 15-bit Port-Address of the port.
+    pub universe: u16,
+    /// Net, Sub-Net, and Universe breakdown of `universe`.
+    pub port_address: PortAddress,
+    /// Physical port of the node, from its latest ArtTodData.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<u8>,
+    /// Bind index of the port, from its latest ArtTodData.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bind_index: Option<u8>,
+    /// UIDs the node declared in its latest table of devices.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tod_uid_total: Option<u16>,
+    /// ArtTodData packets received.
+    pub tod_packets: u64,
+    /// UIDs of the responders (`mmmm:dddddddd`), from the tables and the
+    /// responses, sorted.
+    pub responders: Vec<String>,
+    /// Responders that answered through the port but are missing from its
+    /// table of devices (only when a table was seen), sorted.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unlisted: Vec<String>,
+}
+
+/// RDM transactions carried by ArtRdm on one universe.
+///
+/// A request is answered by a response of the next command class with the
+/// same transaction number, between the same two UIDs. Broadcast requests
+/// expect no response.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{PortAddress, RdmTransactions};
+///
+/// let transactions = RdmTransactions {
+///     universe: 3,
+///     port_address: PortAddress::from_masked(3),
+///     tod_requests: 1,
+///     requests: 10,
+///     responses: 9,
+///     discovery: 0,
+///     get: 8,
+///     set: 2,
+///     acks: 8,
+///     ack_timers: 0,
+///     nacks: 1,
+///     ack_overflows: 0,
+///     unanswered: 1,
+///     response_time: None,
+/// };
+/// assert_eq!(transactions.requests - transactions.unanswered, transactions.responses);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RdmTransactions {
+    /// 15-bit Port-Address of the universe.
+    pub universe: u16,
+    /// Net, Sub-Net, and Universe breakdown of `universe`.
+    pub port_address: PortAddress,
+    /// ArtTodRequest packets asking for the universe's table of devices.
+    pub tod_requests: u64,
+    /// Requests (even command classes).
+    pub requests: u64,
+    /// Responses (odd command classes).
+    pub responses: u64,
+    /// Discovery requests.
+    pub discovery: u64,
+    /// GET requests.
+    pub get: u64,
+    /// SET requests.
+    pub set: u64,
+    /// Responses of type `ACK`.
+    pub acks: u64,
+    /// Responses of type `ACK_TIMER` (the responder asks to retry later).
+    pub ack_timers: u64,
+    /// Responses of type `NACK_REASON`.
+    pub nacks: u64,
+    /// Responses of type `ACK_OVERFLOW` (the answer spans several messages).
+    pub ack_overflows: u64,
+    /// Addressed requests with no response in the capture, or repeated
+    /// before their response.
+    pub unanswered: u64,
+    /// Time between requests and their responses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_time: Option<LatencyDistribution>,
+}
+
+/// Correction mapping the clock of a merged capture onto the clock of the
+/// first capture: `ts + offset_ms / 1000 + skew_ppm * 1e-6 * (ts - anchor_s)`.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::ClockCorrection;
+///
+/// let correction = ClockCorrection {
+///     input: "stage.pcapng".to_string(),
+///     anchor_s: 100.0,
+///     offset_ms: 2500.0,
+///     skew_ppm: -50.0,
+///     matched_packets: 2400,
+/// };
+/// assert_eq!(correction.apply(100.0), 102.5);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClockCorrection {
+    /// Path of the corrected capture.
+    pub input: String,
+    /// Earliest timestamp of the corrected capture, where `offset_ms`
+    /// applies (seconds, its own clock).
+    pub anchor_s: f64,
+    /// Offset added at `anchor_s` (milliseconds, three decimals); includes
+    /// the network latency between the two capture points.
+    pub offset_ms: f64,
+    /// Rate correction (ppm, three decimals); 0 when the matched packets
+    /// span less than 10 s.
+    pub skew_ppm: f64,
+    /// UDP datagrams matched between the two captures; 0 when none was,
+    /// and the capture was merged uncorrected.
+    pub matched_packets: u64,
+}
+
+/// Conflict summary between multiple sources on the same universe.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{ConflictSummary, Severity};
+///
+/// let conflict = ConflictSummary {
+///     universe: 1,
+///     sources: vec!["sacn:cid:deadbeef".to_string()],
+///     proto: None,
+///     overlap_duration_s: 1.2,
+///     affected_channels: Vec::new(),
+///     severity: Severity::Info,
+///     conflict_score: 1.2,
+///     first_seen: None,
+///     channel_labels: Vec::new(),
+///     rig_labels: vec!["FOH truss".to_string()],
+/// };
+/// assert_eq!(conflict.universe, 1);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictSummary {
+    /// Universe identifier for the conflict.
+    pub universe: u16,
+    /// Canonical source identifiers.
+    pub sources: Vec<String>,
+    /// Protocol of the conflicting sources (v0.2 additive), when available.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proto: Option<Protocol>,
+    /// Duration of the overlap in seconds; for sACN, only the time the
+    /// sources sent at the same priority.
+    pub overlap_duration_s: f64,
+    /// Channel indices affected (empty in v0.1).
+    pub affected_channels: Vec<u16>,
+    /// Conflict severity, serialized as `low`, `medium`, or `high`.
+    #[serde(with = "conflict_severity")]
+    pub severity: Severity,
+    /// Numeric conflict score (v0.1 mirrors overlap duration).
+    pub conflict_score: f64,
+    /// Timestamp of first detected conflict (seconds since capture start), v0.2 additive.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_seen: Option<f64>,
+    /// Fixture labels of the affected channels that are patched, v0.2
+    /// additive (requires a fixture map).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub channel_labels: Vec<ChannelLabel>,
+    /// Rig patch labels of the affected channels (or of the universe when no
+    /// channel differed), v0.2 additive (requires a rig patch).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rig_labels: Vec<String>,
+}
+
+/// Compliance summary for a protocol.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{ComplianceSummary, Protocol, Severity, Violation};
+///
+/// let summary = ComplianceSummary {
+///     protocol: Protocol::ArtNet,
+///     compliance_percentage: 100.0,
+///     violations: vec![Violation {
+///         id: "LS-ARTNET-PORT".to_string(),
+///         severity: Severity::Warning,
+///         message: "Non-standard port".to_string(),
+///         count: 1,
+///         examples: Vec::new(),
+///         sources: Default::default(),
+///         spec_reference: None,
+///     }],
+/// };
+/// assert_eq!(summary.violations.len(), 1);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplianceSummary {
+    /// Protocol the violations belong to (e.g., `artnet`, `sacn`, `udp`).
+    pub protocol: Protocol,
+    /// Compliance percentage (0.0-100.0).
+    pub compliance_percentage: f64,
+    /// Violations sorted by severity and ID.
+    pub violations: Vec<Violation>,
+}
+
+/// Compliance rule that was checked, with its effective severity.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{ActiveRule, Severity};
+///
+/// let rule = ActiveRule {
+///     id: "LS-ARTNET-PORT".to_string(),
+///     severity: Severity::Warning,
+/// };
+/// assert_eq!(rule.severity.as_str(), "warning");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActiveRule {
+    /// Rule identifier (e.g., `LS-SACN-START-CODE`).
+    pub id: String,
+    /// Severity reported for violations of this rule.
+    pub severity: Severity,
+}
+
+/// Single compliance violation record.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{Severity, Violation, ViolationExample};
+///
+/// let violation = Violation {
+///     id: "LS-UDP-TOO-SHORT".to_string(),
+///     severity: Severity::Error,
+///     message: "Payload too short".to_string(),
+///     count: 1,
+///     examples: vec![ViolationExample {
+///         source_ip: Some("10.0.0.1".to_string()),
+///         source_port: Some(1234),
+///         timestamp: Some("1970-01-01T00:00:00Z".to_string()),
+///         detail: String::new(),
+///         bytes: None,
+///         vlan: None,
+///     }],
+///     sources: Default::default(),
+///     spec_reference: None,
+/// };
+/// assert_eq!(violation.count, 1);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Violation {
+    /// Stable violation identifier (e.g., `LS-SACN-START-CODE`).
+    pub id: String,
+    /// Violation severity (`error`, `warning`, or `info`).
+    pub severity: Severity,
+    /// Human-readable message explaining the violation.
+    pub message: String,
+    /// Number of occurrences aggregated into this violation.
+    pub count: u64,
+    /// At most three example occurrences, sorted and deduplicated.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<ViolationExample>,
+    /// Occurrences per source IP, for rules counted per source (checksum rules).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sources: BTreeMap<String, u64>,
+    /// Clause of the protocol standard the rule enforces (e.g.,
+    /// `ANSI E1.31-2018, section 6.2.3 (Priority)`); absent for custom rules.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spec_reference: Option<String>,
+}
+
+/// One occurrence of a violation: the packet source, its time, and the
+/// rule-specific detail.
+///
+/// Fields are absent when unknown (e.g. no source for undecodable frames).
+/// `Display` renders the report v1 string form,
+/// `source ip:port @ timestamp; detail`, with `unknown` for missing parts;
+/// `bytes` and `vlan` are not part of it.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::ViolationExample;
+///
+/// let example = ViolationExample {
+///     source_ip: Some("10.0.0.1".to_string()),
+///     source_port: Some(5568),
+///     timestamp: None,
+///     detail: "value=1".to_string(),
+///     bytes: None,
+///     vlan: None,
+/// };
+/// assert_eq!(example.to_string(), "source 10.0.0.1:5568 @ unknown; value=1");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ViolationExample {
+    /// Source IP of the offending packet (if known).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_ip: Option<String>,
+    /// Source UDP port of the offending packet (if known).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_port: Option<u16>,
+    /// RFC3339 timestamp of the offending packet (if known).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    /// Rule-specific `key=value` context (may be empty).
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub detail: String,
+    /// Payload bytes around the offending field, for Art-Net and sACN
+    /// decoding errors when `violation_example_bytes` is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bytes: Option<ExampleBytes>,
+    /// 802.1Q VLAN ID of the offending packet (the inner one when double
+    /// tagged), when it was tagged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vlan: Option<u16>,
+}
+
+impl fmt::Display for ViolationExample {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNKNOWN: &str = "unknown";
+        match (&self.source_ip, self.source_port) {
+            (Some(ip), Some(port)) => write!(f, "source {ip}:{port}")?,
+            (Some(ip), None) => write!(f, "source {ip}")?,
+            (None, _) => write!(f, "source {UNKNOWN}")?,
+        }
+        write!(f, " @ {}", self.timestamp.as_deref().unwrap_or(UNKNOWN))?;
+        if !self.detail.is_empty() {
+            write!(f, "; {}", self.detail)?;
+        }
+        Ok(())
+    }
+}
+
+/// Hexdump of a payload region attached to a violation example.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::ExampleBytes;
+///
+/// let bytes = ExampleBytes::from_payload(&[0x41, 0x72, 0x74, 0x00, 0x17], 3, 4).unwrap();
+/// assert_eq!(bytes.offset, 3);
+/// assert_eq!(bytes.hex, "00 17");
+/// assert!(ExampleBytes::from_payload(&[0x41], 3, 4).is_none());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ExampleBytes {
+    /// Offset of the first byte in the UDP payload.
+    pub offset: usize,
+    /// Lowercase hex bytes separated by spaces.
+    pub hex: String,
+}
+
+impl ExampleBytes {
+    /// At most `len` bytes of `payload` from `offset`; `None` when the
+    /// region is empty.
+    pub fn from_payload(payload: &[u8], offset: usize, len: usize) -> Option<Self> {
+        let region = payload.get(offset..)?;
+        let region = &region[..region.len().min(len)];
+        if region.is_empty() {
+            return None;
+        }
+        let hex = region
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        Some(Self { offset, hex })
+    }
+}
+
+/// Build a stub report with base fields filled and empty aggregates.
+///
+/// # Examples
+/// ```
+/// #![allow(deprecated)]
+/// use liveshark_core::make_stub_report;
+///
+/// let report = make_stub_report("capture.pcapng", 123);
+/// assert_eq!(report.report_version, liveshark_core::REPORT_VERSION);
+/// assert!(report.universes.is_empty());
+/// ```
+#[deprecated(note = "use `ReportBuilder::new(path, bytes).build()` instead")]
+pub fn make_stub_report(input_path: &str, input_bytes: u64) -> Report {
+    ReportBuilder::new(input_path, input_bytes).into_report()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
     fn report_omits_optional_fields_when_none() {
@@ -432,15 +2998,21 @@ mod tests {
                 packets_total: 1,
                 time_start: None,
                 time_end: None,
+                ..CaptureSummary::default()
             }),
+            health: None,
             universes: vec![UniverseSummary {
                 universe: 1,
-                proto: "artnet".to_string(),
+                proto: Protocol::ArtNet,
+                port_address: None,
                 sources: vec![SourceSummary {
                     source_ip: "10.0.0.1".to_string(),
                     cid: None,
                     source_name: None,
                     source_id: None,
+                    ttl: None,
+                    stability: None,
+                    terminated_at: Vec::new(),
                 }],
                 fps: None,
                 frames_count: 1,
@@ -453,20 +3025,70 @@ mod tests {
                 reordered_packets: None,
                 first_seen: None,
                 last_seen: None,
+                destinations: Vec::new(),
+                channel_pairs: Vec::new(),
+                first_snapshot: None,
+                last_snapshot: None,
+                ownership: Vec::new(),
+                microburst: None,
+                controllers_count: 0,
+                max_concurrent_controllers: None,
+                non_zero_start_codes: None,
+                preview_frames: None,
+                unicast_receivers: Vec::new(),
             }],
             flows: vec![FlowSummary {
-                app_proto: "udp".to_string(),
+                app_proto: Protocol::Udp,
                 src: "10.0.0.1:1000".to_string(),
                 dst: "10.0.0.2:2000".to_string(),
+                vlan: None,
+                outer_vlan: None,
                 pps: None,
                 bps: None,
                 iat_jitter_ms: None,
                 max_iat_ms: None,
                 pps_peak_1s: None,
                 bps_peak_1s: None,
+                payload_size_min: None,
+                payload_size_avg: None,
+                payload_size_p50: None,
+                payload_size_p95: None,
+                payload_size_max: None,
+                microburst: None,
+                wifi: None,
             }],
             conflicts: vec![],
             compliance: vec![],
+            rules: vec![],
+            profile: None,
+            other_traffic: Vec::new(),
+            warnings: Vec::new(),
+            series: None,
+            patch: Vec::new(),
+            scene_changes: Vec::new(),
+            fades: Vec::new(),
+            movement: Vec::new(),
+            mirrored_universes: Vec::new(),
+            takeovers: Vec::new(),
+            priority_timeline: Vec::new(),
+            silences: Vec::new(),
+            events: Vec::new(),
+            sync_latency: Vec::new(),
+            sacn_discovery: Vec::new(),
+            address_priorities: Vec::new(),
+            nodes: Vec::new(),
+            timecode: Vec::new(),
+            triggers: Vec::new(),
+            rdm_responders: Vec::new(),
+            rdm_transactions: Vec::new(),
+            psn_trackers: Vec::new(),
+            osc_addresses: Vec::new(),
+            citp_peers: Vec::new(),
+            ptp_domains: Vec::new(),
+            igmp_groups: Vec::new(),
+            network_events: Vec::new(),
+            clock_corrections: Vec::new(),
+            notes: Vec::new(),
         };
 
         let value = serde_json::to_value(&report).expect("report json");
@@ -485,4 +3107,30 @@ mod tests {
         assert!(flow.get("bps").is_none());
         assert!(flow.get("iat_jitter_ms").is_none());
     }
+
+    #[test]
+    fn conflict_severity_keeps_v01_labels() {
+        let conflict = ConflictSummary {
+            universe: 1,
+            sources: vec!["a".to_string(), "b".to_string()],
+            proto: None,
+            overlap_duration_s: 2.0,
+            affected_channels: Vec::new(),
+            severity: Severity::Warning,
+            conflict_score: 2.0,
+            first_seen: None,
+            channel_labels: Vec::new(),
+            rig_labels: Vec::new(),
+        };
+
+        let value = serde_json::to_value(&conflict).expect("conflict json");
+        assert_eq!(value["severity"], "medium");
+        let parsed: ConflictSummary = serde_json::from_value(value).expect("parse conflict");
+        assert_eq!(parsed.severity, Severity::Warning);
+
+        let violation: Violation =
+            serde_json::from_str(r#"{"id":"LS-X","severity":"warning","message":"m","count":1}"#)
+                .expect("parse violation");
+        assert_eq!(violation.severity, Severity::Warning);
+    }
 }
@@ -0,0 +1,313 @@
+//! Rig patch: production labels for universes and channel ranges.
+//!
+//! Where a fixture map names single slots, the rig patch names blocks of the
+//! show as the production calls them ("FOH truss", "Stage left booms"), with
+//! an optional rig position and fixture type. Reports carry the patch as its
+//! own section and use the labels in conflicts and violation details.
+//!
+//! The patch is a plain CSV file, one range per line; blank lines and lines
+//! starting with `#` are ignored:
+//!
+//! ```text
+//! <universe>,<start>,<end>,<label>[,<position>[,<fixture type>]]
+//! ```
+//!
+//! Channels are 1-based and inclusive. Fields cannot contain commas. MVR
+//! files are not read directly; export the patch from the console or
+//! visualiser as CSV.
+
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::ConflictSummary;
+
+/// Slots in a DMX universe.
+const UNIVERSE_SLOTS: u16 = 512;
+/// Extension of My Virtual Rig exchange files, rejected with a hint.
+const MVR_EXTENSION: &str = "mvr";
+
+/// Error returned when a rig patch cannot be read or parsed.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::RigPatch;
+///
+/// let err = "1,20,10,FOH".parse::<RigPatch>().unwrap_err();
+/// assert!(err.to_string().contains("line 1"));
+/// ```
+#[derive(Debug, Error)]
+pub enum PatchError {
+    /// The file could not be read.
+    #[error("failed to read patch: {0}")]
+    Io(#[from] std::io::Error),
+    /// The file is an MVR archive, which is not supported.
+    #[error("MVR files are not supported; export the patch as CSV")]
+    Mvr,
+    /// A record is malformed or overlaps an earlier one.
+    #[error("line {line}: {reason}")]
+    Line { line: usize, reason: String },
+}
+
+/// One labelled channel range of the rig.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::PatchEntry;
+///
+/// let entry = PatchEntry {
+///     universe: 1,
+///     start: 1,
+///     end: 96,
+///     label: "FOH truss".to_string(),
+///     position: Some("FOH".to_string()),
+///     fixture_type: Some("Wash".to_string()),
+/// };
+/// assert!(entry.contains(1, 48));
+/// assert!(!entry.contains(2, 48));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PatchEntry {
+    /// Universe the range belongs to.
+    pub universe: u16,
+    /// First channel of the range (1-based).
+    pub start: u16,
+    /// Last channel of the range (inclusive).
+    pub end: u16,
+    /// Production name of the range.
+    pub label: String,
+    /// Rig position (truss, boom, ...), if given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<String>,
+    /// Fixture type patched on the range, if given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fixture_type: Option<String>,
+}
+
+impl PatchEntry {
+    /// Returns true when the channel of the universe lies in this range.
+    pub fn contains(&self, universe: u16, channel: u16) -> bool {
+        self.universe == universe && (self.start..=self.end).contains(&channel)
+    }
+}
+
+/// Labelled channel ranges, sorted by universe and start channel.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::RigPatch;
+///
+/// let patch: RigPatch = "\
+/// 1,1,96,FOH truss,FOH,Wash
+/// 1,97,200,Stage left booms
+/// ".parse()?;
+/// assert_eq!(patch.universe_label(1).as_deref(), Some("FOH truss + Stage left booms"));
+/// assert_eq!(patch.channel_labels(1, [100, 120]), vec!["Stage left booms"]);
+/// # Ok::<(), liveshark_core::PatchError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RigPatch {
+    entries: Vec<PatchEntry>,
+}
+
+impl RigPatch {
+    /// Read and parse a patch file.
+    ///
+    /// # Errors
+    /// Returns `PatchError::Mvr` for `.mvr` files, `PatchError::Io` when the
+    /// file cannot be read, and `PatchError::Line` for the first invalid
+    /// record.
+    pub fn from_file(path: &Path) -> Result<Self, PatchError> {
+        let is_mvr = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case(MVR_EXTENSION));
+        if is_mvr {
+            return Err(PatchError::Mvr);
+        }
+        std::fs::read_to_string(path)?.parse()
+    }
+
+    /// Ranges of the patch, sorted by universe and start channel.
+    pub fn entries(&self) -> &[PatchEntry] {
+        &self.entries
+    }
+
+    /// Labels of all ranges of a universe joined with ` + `, if any.
+    pub fn universe_label(&self, universe: u16) -> Option<String> {
+        let labels: Vec<&str> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.universe == universe)
+            .map(|entry| entry.label.as_str())
+            .collect();
+        (!labels.is_empty()).then(|| labels.join(" + "))
+    }
+
+    /// Labels of the ranges covering any of the channels, in patch order.
+    pub fn channel_labels(
+        &self,
+        universe: u16,
+        channels: impl IntoIterator<Item = u16>,
+    ) -> Vec<String> {
+        let channels: Vec<u16> = channels.into_iter().collect();
+        self.entries
+            .iter()
+            .filter(|entry| {
+                channels
+                    .iter()
+                    .any(|channel| entry.contains(universe, *channel))
+            })
+            .map(|entry| entry.label.clone())
+            .collect()
+    }
+
+    /// Label each conflict with the ranges of its affected channels, or with
+    /// the whole universe when no channel differed.
+    pub(crate) fn annotate_conflicts(&self, conflicts: &mut [ConflictSummary]) {
+        for conflict in conflicts {
+            conflict.rig_labels = if conflict.affected_channels.is_empty() {
+                self.universe_label(conflict.universe).into_iter().collect()
+            } else {
+                self.channel_labels(
+                    conflict.universe,
+                    conflict.affected_channels.iter().copied(),
+                )
+            };
+        }
+    }
+}
+
+impl FromStr for RigPatch {
+    type Err = PatchError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut entries: Vec<(usize, PatchEntry)> = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line_no = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |reason: String| PatchError::Line {
+                line: line_no,
+                reason,
+            };
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let (universe, start, end, label, rest) = match fields.as_slice() {
+                [universe, start, end, label, rest @ ..] if rest.len() <= 2 => {
+                    (*universe, *start, *end, *label, rest)
+                }
+                _ => {
+                    return Err(error(format!(
+                        "expected `<universe>,<start>,<end>,<label>[,<position>[,<fixture type>]]`, \
+                         got `{line}`"
+                    )));
+                }
+            };
+            let universe = universe
+                .parse::<u16>()
+                .map_err(|_| error(format!("invalid universe: {universe}")))?;
+            let start = parse_channel(start, "start").map_err(error)?;
+            let end = parse_channel(end, "end").map_err(error)?;
+            if end < start {
+                return Err(error(format!("range {start}-{end} ends before it starts")));
+            }
+            if label.is_empty() {
+                return Err(error("label must not be empty".to_string()));
+            }
+            let optional = |index: usize| {
+                rest.get(index)
+                    .filter(|value| !value.is_empty())
+                    .map(|value| value.to_string())
+            };
+            entries.push((
+                line_no,
+                PatchEntry {
+                    universe,
+                    start,
+                    end,
+                    label: label.to_string(),
+                    position: optional(0),
+                    fixture_type: optional(1),
+                },
+            ));
+        }
+
+        entries.sort_by_key(|(_, entry)| (entry.universe, entry.start));
+        for pair in entries.windows(2) {
+            let (previous, (line, current)) = (&pair[0].1, &pair[1]);
+            if previous.universe == current.universe && current.start <= previous.end {
+                return Err(PatchError::Line {
+                    line: *line,
+                    reason: format!(
+                        "range {}-{} of universe {} overlaps {}",
+                        current.start, current.end, current.universe, previous.label
+                    ),
+                });
+            }
+        }
+        Ok(Self {
+            entries: entries.into_iter().map(|(_, entry)| entry).collect(),
+        })
+    }
+}
+
+fn parse_channel(value: &str, field: &str) -> Result<u16, String> {
+    value
+        .parse::<u16>()
+        .ok()
+        .filter(|channel| (1..=UNIVERSE_SLOTS).contains(channel))
+        .ok_or_else(|| format!("{field} must be between 1 and {UNIVERSE_SLOTS}, got {value}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{PatchError, RigPatch};
+
+    const RIG: &str = "\
+# universe,start,end,label,position,fixture type
+2,1,48,Back truss,US truss,Spot
+1,101,200,Stage left booms,SL,
+1,1,100,FOH truss,FOH,Wash
+";
+
+    #[test]
+    fn ranges_are_sorted_and_looked_up() {
+        let patch: RigPatch = RIG.parse().unwrap();
+        let labels: Vec<&str> = patch
+            .entries()
+            .iter()
+            .map(|entry| entry.label.as_str())
+            .collect();
+        assert_eq!(labels, vec!["FOH truss", "Stage left booms", "Back truss"]);
+        assert_eq!(patch.entries()[1].position.as_deref(), Some("SL"));
+        assert!(patch.entries()[1].fixture_type.is_none());
+        assert_eq!(
+            patch.channel_labels(1, [50, 150, 300]),
+            vec!["FOH truss", "Stage left booms"]
+        );
+        assert_eq!(patch.universe_label(2).as_deref(), Some("Back truss"));
+        assert!(patch.universe_label(3).is_none());
+    }
+
+    #[test]
+    fn invalid_records_report_their_line() {
+        let overlap = format!("{RIG}1,200,210,Truss 2\n");
+        let err = overlap.parse::<RigPatch>().unwrap_err();
+        assert!(matches!(err, PatchError::Line { line: 5, .. }));
+        assert!(err.to_string().contains("overlaps Stage left booms"));
+
+        assert!("1,0,10,FOH".parse::<RigPatch>().is_err());
+        assert!("1,1,513,FOH".parse::<RigPatch>().is_err());
+        assert!("1,1,10,".parse::<RigPatch>().is_err());
+        assert!("1,1,10".parse::<RigPatch>().is_err());
+        assert!(matches!(
+            RigPatch::from_file(Path::new("show.MVR")),
+            Err(PatchError::Mvr)
+        ));
+    }
+}
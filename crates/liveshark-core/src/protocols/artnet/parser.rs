@@ -1,175 +0,0 @@
-use super::error::ArtNetError;
-use super::layout;
-use super::reader::ArtNetReader;
-
-/// Parsed ArtDMX payload with raw slot data.
-///
-/// Note: this struct lives in an internal module; the example is
-/// illustrative and not compiled as a public doctest.
-///
-/// # Examples
-/// ```text
-/// use liveshark_core::protocols::artnet::ArtDmx;
-///
-/// let frame = ArtDmx {
-///     universe: 1,
-///     sequence: Some(1),
-///     slots: vec![1, 2, 3, 4],
-/// };
-/// assert_eq!(frame.slots.len(), 4);
-/// ```
-#[derive(Debug)]
-pub struct ArtDmx {
-    pub universe: u16,
-    pub sequence: Option<u8>,
-    pub slots: Vec<u8>,
-}
-
-/// Parse an ArtDMX payload from a UDP payload.
-///
-/// Returns `Ok(None)` when the payload is not Art-Net. Returns `Err` for
-/// malformed Art-Net packets.
-///
-/// Note: this parser lives in an internal module; the example is illustrative
-/// and not compiled as a public doctest.
-///
-/// # Examples
-/// ```text
-/// use liveshark_core::protocols::artnet::{layout, parse_artdmx};
-///
-/// let length = 4u16;
-/// let mut payload = vec![0u8; layout::DMX_DATA_OFFSET + length as usize];
-/// payload[..layout::ARTNET_ID.len()].copy_from_slice(layout::ARTNET_ID);
-/// payload[layout::OP_CODE_RANGE.clone()]
-///     .copy_from_slice(&layout::ARTDMX_OPCODE.to_le_bytes());
-/// payload[layout::SEQUENCE_OFFSET] = 0x01;
-/// payload[layout::UNIVERSE_RANGE.clone()].copy_from_slice(&1u16.to_le_bytes());
-/// payload[layout::LENGTH_RANGE.clone()].copy_from_slice(&length.to_be_bytes());
-/// payload[layout::DMX_DATA_OFFSET..layout::DMX_DATA_OFFSET + 4]
-///     .copy_from_slice(&[1, 2, 3, 4]);
-///
-/// let parsed = parse_artdmx(&payload)?.expect("artdmx");
-/// assert_eq!(parsed.universe, 1);
-/// assert_eq!(parsed.slots.len(), 4);
-/// # Ok::<(), Box<dyn std::error::Error>>(())
-/// ```
-///
-/// # Errors
-/// Returns `ArtNetError` when the payload is too short, the opcode is not
-/// supported, the universe id is out of range, or the length field is invalid.
-pub fn parse_artdmx(payload: &[u8]) -> Result<Option<ArtDmx>, ArtNetError> {
-    let reader = ArtNetReader::new(payload);
-    reader.require_len(layout::DMX_DATA_OFFSET)?;
-
-    let signature = reader.read_signature()?;
-    if signature != layout::ARTNET_ID {
-        return Ok(None);
-    }
-
-    let opcode = reader.read_u16_le(layout::OP_CODE_RANGE.clone())?;
-    if opcode != layout::ARTDMX_OPCODE {
-        return Err(ArtNetError::UnsupportedOpCode { opcode });
-    }
-
-    let sequence = reader.read_optional_nonzero_u8(layout::SEQUENCE_OFFSET)?;
-    let universe = reader.read_universe_id(layout::UNIVERSE_RANGE.clone())?;
-    let data_len = reader.read_dmx_length(layout::LENGTH_RANGE.clone())?;
-    let needed = layout::DMX_DATA_OFFSET
-        .checked_add(data_len)
-        .ok_or(ArtNetError::InvalidDmxLength { len: data_len })?;
-    reader.require_len(needed)?;
-    let data = reader.read_slice(layout::DMX_DATA_OFFSET..needed)?;
-    let slots = data.to_vec();
-
-    Ok(Some(ArtDmx {
-        universe,
-        sequence,
-        slots,
-    }))
-}
-
-#[cfg(test)]
-mod tests {
-    use super::parse_artdmx;
-    use crate::protocols::artnet::error::ArtNetError;
-    use crate::protocols::artnet::layout;
-
-    #[test]
-    fn parse_valid_artdmx() {
-        let length = 4u16;
-        let mut payload = vec![0u8; layout::DMX_DATA_OFFSET + length as usize];
-        payload[..layout::ARTNET_ID.len()].copy_from_slice(layout::ARTNET_ID);
-        payload[layout::OP_CODE_RANGE.clone()]
-            .copy_from_slice(&layout::ARTDMX_OPCODE.to_le_bytes());
-        payload[layout::SEQUENCE_OFFSET] = 0x12;
-        payload[layout::UNIVERSE_RANGE.clone()].copy_from_slice(&1u16.to_le_bytes());
-        payload[layout::LENGTH_RANGE.clone()].copy_from_slice(&length.to_be_bytes());
-        payload[layout::DMX_DATA_OFFSET..layout::DMX_DATA_OFFSET + 4]
-            .copy_from_slice(&[1, 2, 3, 4]);
-
-        let parsed = parse_artdmx(&payload).unwrap();
-        assert!(parsed.is_some());
-        let parsed = parsed.unwrap();
-        assert_eq!(parsed.universe, 1);
-        assert_eq!(parsed.sequence, Some(0x12));
-        assert_eq!(&parsed.slots[..4], &[1, 2, 3, 4]);
-        assert_eq!(parsed.slots.len(), 4);
-    }
-
-    #[test]
-    fn parse_non_artnet() {
-        let payload = vec![0u8; layout::DMX_DATA_OFFSET];
-        let parsed = parse_artdmx(&payload).unwrap();
-        assert!(parsed.is_none());
-    }
-
-    #[test]
-    fn parse_short_payload() {
-        let payload = vec![0u8; layout::DMX_DATA_OFFSET - 1];
-        let err = parse_artdmx(&payload).unwrap_err();
-        let msg = err.to_string();
-        assert!(msg.contains("payload too short"));
-    }
-
-    #[test]
-    fn parse_invalid_length() {
-        let length = (layout::DMX_MAX_SLOTS as u16) + 1;
-        let mut payload = vec![0u8; layout::DMX_DATA_OFFSET];
-        payload[..layout::ARTNET_ID.len()].copy_from_slice(layout::ARTNET_ID);
-        payload[layout::OP_CODE_RANGE.clone()]
-            .copy_from_slice(&layout::ARTDMX_OPCODE.to_le_bytes());
-        payload[layout::LENGTH_RANGE.clone()].copy_from_slice(&length.to_be_bytes());
-
-        let err = parse_artdmx(&payload).unwrap_err();
-        assert!(matches!(err, ArtNetError::InvalidDmxLength { .. }));
-    }
-
-    #[test]
-    fn parse_invalid_universe_id() {
-        let mut payload = vec![0u8; layout::DMX_DATA_OFFSET];
-        payload[..layout::ARTNET_ID.len()].copy_from_slice(layout::ARTNET_ID);
-        payload[layout::OP_CODE_RANGE.clone()]
-            .copy_from_slice(&layout::ARTDMX_OPCODE.to_le_bytes());
-        payload[layout::UNIVERSE_RANGE.clone()].copy_from_slice(&0x8000u16.to_le_bytes());
-        payload[layout::LENGTH_RANGE.clone()].copy_from_slice(&2u16.to_be_bytes());
-
-        let err = parse_artdmx(&payload).unwrap_err();
-        assert!(matches!(
-            err,
-            ArtNetError::InvalidUniverseId { value: 0x8000 }
-        ));
-    }
-
-    #[test]
-    fn parse_unsupported_opcode() {
-        let mut payload = vec![0u8; layout::DMX_DATA_OFFSET];
-        payload[..layout::ARTNET_ID.len()].copy_from_slice(layout::ARTNET_ID);
-        payload[layout::OP_CODE_RANGE.clone()].copy_from_slice(&0x1234u16.to_le_bytes());
-
-        let err = parse_artdmx(&payload).unwrap_err();
-        assert!(matches!(
-            err,
-            ArtNetError::UnsupportedOpCode { opcode: 0x1234 }
-        ));
-    }
-}
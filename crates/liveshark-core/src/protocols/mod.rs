@@ -1,15 +1,177 @@
 //! Protocol decoding modules.
 //!
-//! Each protocol follows a layered structure:
-//! - `layout`: byte offsets and ranges (source of truth)
-//! - `reader`: safe byte access and protocol conventions
-//! - `parser`: domain-level decoding (no direct byte indexing)
-//! - `error`: explicit, actionable errors
+//! The parsers live in the `no_std` `liveshark-proto` crate so embedded
+//! gateways reuse the exact decoding of the analyzer; each protocol keeps its
+//! `layout`, `reader`, `parser`, and `error` layers there.
 //!
 //! Parsers are pure and contain no I/O; sources and analysis layers handle
-//! file access and aggregation. Protocol conventions live in readers so the
-//! parsing surface stays minimal and consistent with the spec.
+//! file access and aggregation. The `decode_*` functions are the analysis
+//! layer's entry points: without a protocol's feature they decode nothing,
+//! so its packets are counted as other UDP traffic.
 //!
-pub mod artnet;
-pub(crate) mod common;
-pub mod sacn;
+pub use liveshark_proto::{arp, artnet, citp, dhcp, igmp, osc, psn, ptp, sacn};
+
+#[cfg(not(feature = "arp"))]
+use arp::{ArpPacket, error::ArpError};
+#[cfg(not(feature = "artnet"))]
+use artnet::{
+    ArtAddress, ArtDmx, ArtNzs, ArtPollReply, ArtRdm, ArtSync, ArtTimeCode, ArtTodData,
+    ArtTodRequest, ArtTrigger, error::ArtNetError,
+};
+#[cfg(not(feature = "citp"))]
+use citp::{CitpPacket, error::CitpError};
+#[cfg(not(feature = "dhcp"))]
+use dhcp::{DhcpMessage, error::DhcpError};
+#[cfg(not(feature = "igmp"))]
+use igmp::{IgmpMessage, error::IgmpError};
+#[cfg(not(feature = "osc"))]
+use osc::{OscPacket, error::OscError};
+#[cfg(not(feature = "psn"))]
+use psn::{PsnData, PsnInfo, error::PsnError};
+use ptp::{PtpMessage, error::PtpError};
+#[cfg(not(feature = "sacn"))]
+use sacn::{SacnDiscovery, SacnDmx, SacnSync, error::SacnError};
+
+#[cfg(feature = "arp")]
+pub(crate) use arp::parse_arp as decode_arp;
+#[cfg(feature = "artnet")]
+pub(crate) use artnet::{
+    parse_artaddress as decode_artaddress, parse_artdmx as decode_artdmx,
+    parse_artnzs as decode_artnzs, parse_artpollreply as decode_artpollreply,
+    parse_artrdm as decode_artrdm, parse_artsync as decode_artsync,
+    parse_arttimecode as decode_arttimecode, parse_arttoddata as decode_arttoddata,
+    parse_arttodrequest as decode_arttodrequest, parse_arttrigger as decode_arttrigger,
+};
+#[cfg(feature = "citp")]
+pub(crate) use citp::parse_citp as decode_citp;
+#[cfg(feature = "dhcp")]
+pub(crate) use dhcp::parse_dhcp as decode_dhcp;
+#[cfg(feature = "igmp")]
+pub(crate) use igmp::parse_igmp as decode_igmp;
+#[cfg(feature = "osc")]
+pub(crate) use osc::parse_osc as decode_osc;
+#[cfg(feature = "psn")]
+pub(crate) use psn::{parse_psn_data as decode_psn_data, parse_psn_info as decode_psn_info};
+#[cfg(feature = "sacn")]
+pub(crate) use sacn::{
+    parse_sacn_address_priority as decode_sacn_address_priority,
+    parse_sacn_discovery as decode_sacn_discovery, parse_sacn_dmx as decode_sacn_dmx,
+    parse_sacn_sync as decode_sacn_sync,
+};
+
+#[cfg(not(feature = "artnet"))]
+pub(crate) fn decode_artdmx(_payload: &[u8]) -> Result<Option<ArtDmx>, ArtNetError> {
+    Ok(None)
+}
+
+#[cfg(not(feature = "artnet"))]
+pub(crate) fn decode_artpollreply(_payload: &[u8]) -> Result<Option<ArtPollReply>, ArtNetError> {
+    Ok(None)
+}
+
+#[cfg(not(feature = "artnet"))]
+pub(crate) fn decode_artnzs(_payload: &[u8]) -> Result<Option<ArtNzs>, ArtNetError> {
+    Ok(None)
+}
+
+#[cfg(not(feature = "artnet"))]
+pub(crate) fn decode_artsync(_payload: &[u8]) -> Result<Option<ArtSync>, ArtNetError> {
+    Ok(None)
+}
+
+#[cfg(not(feature = "artnet"))]
+pub(crate) fn decode_arttimecode(_payload: &[u8]) -> Result<Option<ArtTimeCode>, ArtNetError> {
+    Ok(None)
+}
+
+#[cfg(not(feature = "artnet"))]
+pub(crate) fn decode_artaddress(_payload: &[u8]) -> Result<Option<ArtAddress>, ArtNetError> {
+    Ok(None)
+}
+
+#[cfg(not(feature = "artnet"))]
+pub(crate) fn decode_arttrigger(_payload: &[u8]) -> Result<Option<ArtTrigger>, ArtNetError> {
+    Ok(None)
+}
+
+#[cfg(not(feature = "artnet"))]
+pub(crate) fn decode_arttodrequest(_payload: &[u8]) -> Result<Option<ArtTodRequest>, ArtNetError> {
+    Ok(None)
+}
+
+#[cfg(not(feature = "artnet"))]
+pub(crate) fn decode_arttoddata(_payload: &[u8]) -> Result<Option<ArtTodData>, ArtNetError> {
+    Ok(None)
+}
+
+#[cfg(not(feature = "artnet"))]
+pub(crate) fn decode_artrdm(_payload: &[u8]) -> Result<Option<ArtRdm>, ArtNetError> {
+    Ok(None)
+}
+
+#[cfg(not(feature = "sacn"))]
+pub(crate) fn decode_sacn_dmx(_payload: &[u8]) -> Result<Option<SacnDmx>, SacnError> {
+    Ok(None)
+}
+
+#[cfg(not(feature = "sacn"))]
+pub(crate) fn decode_sacn_address_priority(_payload: &[u8]) -> Result<Option<SacnDmx>, SacnError> {
+    Ok(None)
+}
+
+#[cfg(not(feature = "sacn"))]
+pub(crate) fn decode_sacn_sync(_payload: &[u8]) -> Result<Option<SacnSync>, SacnError> {
+    Ok(None)
+}
+
+#[cfg(not(feature = "sacn"))]
+pub(crate) fn decode_sacn_discovery(_payload: &[u8]) -> Result<Option<SacnDiscovery>, SacnError> {
+    Ok(None)
+}
+
+#[cfg(not(feature = "psn"))]
+pub(crate) fn decode_psn_data(_payload: &[u8]) -> Result<Option<PsnData>, PsnError> {
+    Ok(None)
+}
+
+#[cfg(not(feature = "psn"))]
+pub(crate) fn decode_psn_info(_payload: &[u8]) -> Result<Option<PsnInfo>, PsnError> {
+    Ok(None)
+}
+
+#[cfg(not(feature = "osc"))]
+pub(crate) fn decode_osc(_payload: &[u8]) -> Result<Option<OscPacket>, OscError> {
+    Ok(None)
+}
+
+#[cfg(not(feature = "citp"))]
+pub(crate) fn decode_citp(_payload: &[u8]) -> Result<Option<CitpPacket>, CitpError> {
+    Ok(None)
+}
+
+/// PTP has no magic number: the caller selects it by port, so every payload
+/// decodes or fails.
+#[cfg(feature = "ptp")]
+pub(crate) fn decode_ptp(payload: &[u8]) -> Result<Option<PtpMessage>, PtpError> {
+    ptp::parse_ptp(payload).map(Some)
+}
+
+#[cfg(not(feature = "ptp"))]
+pub(crate) fn decode_ptp(_payload: &[u8]) -> Result<Option<PtpMessage>, PtpError> {
+    Ok(None)
+}
+
+#[cfg(not(feature = "igmp"))]
+pub(crate) fn decode_igmp(_payload: &[u8]) -> Result<Option<IgmpMessage>, IgmpError> {
+    Ok(None)
+}
+
+#[cfg(not(feature = "arp"))]
+pub(crate) fn decode_arp(_payload: &[u8]) -> Result<Option<ArpPacket>, ArpError> {
+    Ok(None)
+}
+
+#[cfg(not(feature = "dhcp"))]
+pub(crate) fn decode_dhcp(_payload: &[u8]) -> Result<Option<DhcpMessage>, DhcpError> {
+    Ok(None)
+}
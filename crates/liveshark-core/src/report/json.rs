@@ -0,0 +1,183 @@
+//! Report JSON output with fixed float precision.
+//!
+//! Metrics such as `fps`, `loss_rate`, or `jitter_ms` come out of float
+//! arithmetic with binary noise in their last digits (`700.0000000000001`),
+//! which differs between platforms and makes report diffs noisy. Rounding
+//! every float to a fixed number of decimals while writing keeps the JSON
+//! identical wherever the noise lies below that precision.
+
+use std::io;
+
+use serde::Serialize;
+use serde_json::ser::{CompactFormatter, Formatter, PrettyFormatter, Serializer};
+
+/// Decimals kept by default: microseconds for timestamps and durations in
+/// seconds, well below any meaningful rate or percentage.
+pub const DEFAULT_FLOAT_DECIMALS: u32 = 6;
+
+/// Magnitude from which every f64 is an integer.
+const EXACT_INTEGER_LIMIT: f64 = 9_007_199_254_740_992.0;
+
+/// JSON layout and float precision of a serialized report.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{DEFAULT_FLOAT_DECIMALS, JsonOptions, to_json_string};
+///
+/// let options = JsonOptions::default();
+/// assert_eq!(options.float_decimals, Some(DEFAULT_FLOAT_DECIMALS));
+/// assert_eq!(to_json_string(&[700.0000000000001, 0.1 + 0.2], options)?, "[700.0,0.3]");
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonOptions {
+    /// Indent the output.
+    pub pretty: bool,
+    /// Decimals every float is rounded to; `None` writes full precision.
+    pub float_decimals: Option<u32>,
+}
+
+impl Default for JsonOptions {
+    fn default() -> Self {
+        Self {
+            pretty: false,
+            float_decimals: Some(DEFAULT_FLOAT_DECIMALS),
+        }
+    }
+}
+
+/// Serialize `value` (usually a `Report`) to JSON according to `options`.
+///
+/// # Errors
+/// Returns the serializer error when `value` cannot be represented as JSON.
+pub fn to_json_string<T: Serialize + ?Sized>(
+    value: &T,
+    options: JsonOptions,
+) -> serde_json::Result<String> {
+    let mut out = Vec::new();
+    if options.pretty {
+        let formatter = RoundingFormatter::new(PrettyFormatter::new(), options.float_decimals);
+        value.serialize(&mut Serializer::with_formatter(&mut out, formatter))?;
+    } else {
+        let formatter = RoundingFormatter::new(CompactFormatter, options.float_decimals);
+        value.serialize(&mut Serializer::with_formatter(&mut out, formatter))?;
+    }
+    // serde_json only writes valid UTF-8.
+    Ok(String::from_utf8(out).expect("JSON output is UTF-8"))
+}
+
+/// Formatter rounding floats before handing them to `inner`; the layout
+/// methods `inner` overrides are forwarded.
+struct RoundingFormatter<F> {
+    inner: F,
+    scale: Option<f64>,
+}
+
+impl<F> RoundingFormatter<F> {
+    fn new(inner: F, decimals: Option<u32>) -> Self {
+        Self {
+            inner,
+            scale: decimals.map(|decimals| 10f64.powi(decimals as i32)),
+        }
+    }
+
+    fn round(&self, value: f64) -> f64 {
+        match self.scale {
+            // Past 2^53 the scaled value has no fraction left to drop.
+            Some(scale) if (value * scale).abs() < EXACT_INTEGER_LIMIT => {
+                (value * scale).round() / scale
+            }
+            _ => value,
+        }
+    }
+}
+
+impl<F: Formatter> Formatter for RoundingFormatter<F> {
+    fn write_f32<W: ?Sized + io::Write>(&mut self, writer: &mut W, value: f32) -> io::Result<()> {
+        self.write_f64(writer, f64::from(value))
+    }
+
+    fn write_f64<W: ?Sized + io::Write>(&mut self, writer: &mut W, value: f64) -> io::Result<()> {
+        let value = self.round(value);
+        self.inner.write_f64(writer, value)
+    }
+
+    fn begin_array<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.begin_array(writer)
+    }
+
+    fn end_array<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.end_array(writer)
+    }
+
+    fn begin_array_value<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        self.inner.begin_array_value(writer, first)
+    }
+
+    fn end_array_value<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.end_array_value(writer)
+    }
+
+    fn begin_object<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.begin_object(writer)
+    }
+
+    fn end_object<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.end_object(writer)
+    }
+
+    fn begin_object_key<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        self.inner.begin_object_key(writer, first)
+    }
+
+    fn begin_object_value<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.begin_object_value(writer)
+    }
+
+    fn end_object_value<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.end_object_value(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{JsonOptions, to_json_string};
+
+    #[test]
+    fn pretty_output_keeps_its_layout_with_rounded_floats() {
+        let value = serde_json::json!({ "fps": 39.99999999999999, "frames": [1, 2] });
+        let options = JsonOptions {
+            pretty: true,
+            ..JsonOptions::default()
+        };
+        let expected = serde_json::to_string_pretty(&serde_json::json!({
+            "fps": 40.0,
+            "frames": [1, 2],
+        }))
+        .unwrap();
+        assert_eq!(to_json_string(&value, options).unwrap(), expected);
+    }
+
+    #[test]
+    fn full_precision_matches_serde_json() {
+        let value = [700.0000000000001, 1e300, 0.123_456_789];
+        let options = JsonOptions {
+            pretty: false,
+            float_decimals: None,
+        };
+        assert_eq!(
+            to_json_string(&value, options).unwrap(),
+            serde_json::to_string(&value).unwrap()
+        );
+        let rounded = to_json_string(&value, JsonOptions::default()).unwrap();
+        assert_eq!(rounded, "[700.0,1e+300,0.123457]");
+    }
+}
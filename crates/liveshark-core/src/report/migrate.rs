@@ -0,0 +1,350 @@
+//! Versioned report deserialization.
+//!
+//! Reports are read as untyped JSON first, then upgraded one schema version at
+//! a time until they match `REPORT_VERSION`, and only then decoded into
+//! `Report`. Each step is recorded so callers can tell which shape the input
+//! had. Additive fields never need a step (see the specification); migrations
+//! only cover breaking schema changes.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::ReportError;
+use crate::{REPORT_VERSION, Report, ViolationExample};
+
+/// Single schema upgrade applied while loading a report.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::AppliedMigration;
+///
+/// let step = AppliedMigration {
+///     from: 1,
+///     to: 2,
+///     description: "example step",
+/// };
+/// assert_eq!(step.to, step.from + 1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct AppliedMigration {
+    /// Schema version before the step.
+    pub from: u32,
+    /// Schema version after the step.
+    pub to: u32,
+    /// What the step changed.
+    pub description: &'static str,
+}
+
+/// Report loaded from any supported schema version.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{Report, ReportBuilder};
+///
+/// let json = serde_json::to_string(&ReportBuilder::new("capture.pcapng", 0).build()?)?;
+/// let loaded = Report::from_json_any_version(&json)?;
+/// assert_eq!(loaded.source_version, liveshark_core::REPORT_VERSION);
+/// assert!(loaded.migrations.is_empty());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct MigratedReport {
+    /// Report upgraded to the current schema.
+    pub report: Report,
+    /// `report_version` found in the input.
+    pub source_version: u32,
+    /// Steps applied, oldest first (empty when the input was current).
+    pub migrations: Vec<AppliedMigration>,
+}
+
+/// Upgrade step from `from` to `from + 1`.
+pub(crate) struct Migration {
+    pub(crate) from: u32,
+    pub(crate) description: &'static str,
+    pub(crate) apply: fn(&mut Value) -> Result<(), ReportError>,
+}
+
+/// Known upgrade steps, oldest first.
+const MIGRATIONS: &[Migration] = &[Migration {
+    from: 1,
+    description: "structured violation examples",
+    apply: structure_examples,
+}];
+
+/// Oldest `report_version` this crate can read.
+pub const MIN_SUPPORTED_REPORT_VERSION: u32 = 1;
+
+impl Report {
+    /// Parse a JSON report of any supported schema version and upgrade it to
+    /// the current one.
+    ///
+    /// # Errors
+    /// Returns `ReportError::InvalidJson` for malformed input or a missing
+    /// `report_version`, and `ReportError::UnsupportedVersion` when the version
+    /// is newer than this crate or older than any known migration.
+    pub fn from_json_any_version(json: &str) -> Result<MigratedReport, ReportError> {
+        let value: Value = serde_json::from_str(json).map_err(invalid_json)?;
+        migrate_value(
+            value,
+            MIGRATIONS,
+            MIN_SUPPORTED_REPORT_VERSION,
+            REPORT_VERSION,
+        )
+    }
+}
+
+fn migrate_value(
+    mut value: Value,
+    migrations: &[Migration],
+    min_version: u32,
+    current_version: u32,
+) -> Result<MigratedReport, ReportError> {
+    let source_version = read_version(&value)?;
+    if source_version < min_version || source_version > current_version {
+        return Err(ReportError::UnsupportedVersion {
+            found: source_version,
+            expected: current_version,
+        });
+    }
+
+    let mut version = source_version;
+    let mut applied = Vec::new();
+    while version < current_version {
+        let step = migrations
+            .iter()
+            .find(|migration| migration.from == version)
+            .ok_or(ReportError::UnsupportedVersion {
+                found: source_version,
+                expected: current_version,
+            })?;
+        (step.apply)(&mut value)?;
+        version += 1;
+        if let Some(object) = value.as_object_mut() {
+            object.insert("report_version".to_string(), Value::from(version));
+        }
+        applied.push(AppliedMigration {
+            from: step.from,
+            to: version,
+            description: step.description,
+        });
+    }
+
+    let report = serde_json::from_value(value).map_err(invalid_json)?;
+    Ok(MigratedReport {
+        report,
+        source_version,
+        migrations: applied,
+    })
+}
+
+fn read_version(value: &Value) -> Result<u32, ReportError> {
+    value
+        .get("report_version")
+        .and_then(Value::as_u64)
+        .and_then(|version| u32::try_from(version).ok())
+        .ok_or_else(|| ReportError::InvalidJson("missing or invalid report_version".to_string()))
+}
+
+/// v1 -> v2: `compliance[].violations[].examples[]` strings of the form
+/// `source <ip>:<port> @ <ts>; <detail>` become `ViolationExample` objects,
+/// re-sorted in the v2 canonical order.
+fn structure_examples(value: &mut Value) -> Result<(), ReportError> {
+    let Some(entries) = value.get_mut("compliance").and_then(Value::as_array_mut) else {
+        return Ok(());
+    };
+    for entry in entries {
+        let Some(violations) = entry.get_mut("violations").and_then(Value::as_array_mut) else {
+            continue;
+        };
+        for violation in violations {
+            let Some(examples) = violation.get_mut("examples").and_then(Value::as_array_mut) else {
+                continue;
+            };
+            let mut parsed: Vec<ViolationExample> = examples
+                .iter()
+                .filter_map(Value::as_str)
+                .map(parse_legacy_example)
+                .collect();
+            parsed.sort();
+            *examples = parsed
+                .iter()
+                .map(serde_json::to_value)
+                .collect::<Result<_, _>>()
+                .map_err(invalid_json)?;
+        }
+    }
+    Ok(())
+}
+
+/// Parse a v1 example string; anything not in the documented shape is kept
+/// whole as the detail.
+fn parse_legacy_example(example: &str) -> ViolationExample {
+    let fallback = || ViolationExample {
+        detail: example.trim().to_string(),
+        ..ViolationExample::default()
+    };
+    let Some(rest) = example.strip_prefix("source ") else {
+        return fallback();
+    };
+    let Some((source, rest)) = rest.split_once(" @ ") else {
+        return fallback();
+    };
+    let (timestamp, detail) = rest.split_once("; ").unwrap_or((rest, ""));
+    let (source_ip, source_port) = if source == "unknown" {
+        (None, None)
+    } else {
+        let Some((ip, port)) = source.rsplit_once(':') else {
+            return fallback();
+        };
+        let Ok(port) = port.parse() else {
+            return fallback();
+        };
+        (Some(ip.to_string()), Some(port))
+    };
+    ViolationExample {
+        source_ip,
+        source_port,
+        timestamp: (timestamp != "unknown").then(|| timestamp.to_string()),
+        detail: detail.trim().to_string(),
+        bytes: None,
+        vlan: None,
+    }
+}
+
+fn invalid_json(err: serde_json::Error) -> ReportError {
+    ReportError::InvalidJson(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::{Migration, migrate_value, parse_legacy_example};
+    use crate::report::{ReportBuilder, ReportError};
+    use crate::{Report, ViolationExample};
+
+    fn current_json(version: u32) -> Value {
+        let mut value =
+            serde_json::to_value(ReportBuilder::new("capture.pcapng", 1).build().unwrap()).unwrap();
+        value["report_version"] = json!(version);
+        value
+    }
+
+    fn rename_tool(value: &mut Value) -> Result<(), ReportError> {
+        value["tool"]["name"] = json!("renamed");
+        Ok(())
+    }
+
+    fn noop(_value: &mut Value) -> Result<(), ReportError> {
+        Ok(())
+    }
+
+    const STEPS: &[Migration] = &[
+        Migration {
+            from: 1,
+            description: "rename tool",
+            apply: rename_tool,
+        },
+        Migration {
+            from: 2,
+            description: "noop",
+            apply: noop,
+        },
+    ];
+
+    #[test]
+    fn applies_steps_in_order_up_to_current() {
+        let migrated = migrate_value(current_json(1), STEPS, 1, 3).unwrap();
+        assert_eq!(migrated.source_version, 1);
+        assert_eq!(migrated.report.report_version, 3);
+        assert_eq!(migrated.report.tool.name, "renamed");
+        let steps: Vec<(u32, u32)> = migrated
+            .migrations
+            .iter()
+            .map(|step| (step.from, step.to))
+            .collect();
+        assert_eq!(steps, vec![(1, 2), (2, 3)]);
+
+        let migrated = migrate_value(current_json(2), STEPS, 1, 3).unwrap();
+        assert_eq!(migrated.migrations.len(), 1);
+        assert_eq!(migrated.report.tool.name, "liveshark");
+    }
+
+    #[test]
+    fn rejects_unknown_versions() {
+        assert!(matches!(
+            migrate_value(current_json(4), STEPS, 1, 3),
+            Err(ReportError::UnsupportedVersion { found: 4, .. })
+        ));
+        assert!(matches!(
+            migrate_value(current_json(0), STEPS, 1, 3),
+            Err(ReportError::UnsupportedVersion { found: 0, .. })
+        ));
+        assert!(matches!(
+            migrate_value(current_json(1), &[], 1, 2),
+            Err(ReportError::UnsupportedVersion { found: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_missing_version() {
+        let mut value = current_json(1);
+        value.as_object_mut().unwrap().remove("report_version");
+        assert!(matches!(
+            migrate_value(value, STEPS, 1, 3),
+            Err(ReportError::InvalidJson(_))
+        ));
+    }
+
+    #[test]
+    fn parses_legacy_example_strings() {
+        assert_eq!(
+            parse_legacy_example("source 10.0.0.1:5568 @ 1970-01-01T00:00:01Z; value=1"),
+            ViolationExample {
+                source_ip: Some("10.0.0.1".to_string()),
+                source_port: Some(5568),
+                timestamp: Some("1970-01-01T00:00:01Z".to_string()),
+                detail: "value=1".to_string(),
+                bytes: None,
+                vlan: None,
+            }
+        );
+        assert_eq!(
+            parse_legacy_example("source unknown @ unknown; slice-a"),
+            ViolationExample {
+                detail: "slice-a".to_string(),
+                ..ViolationExample::default()
+            }
+        );
+        assert_eq!(
+            parse_legacy_example("free text").detail,
+            "free text".to_string()
+        );
+    }
+
+    #[test]
+    fn upgrades_v1_examples_to_objects() {
+        let mut value = current_json(1);
+        value["compliance"] = json!([{
+            "protocol": "sacn",
+            "compliance_percentage": 50.0,
+            "violations": [{
+                "id": "LS-SACN-START-CODE",
+                "severity": "error",
+                "message": "Non-zero start code",
+                "count": 2,
+                "examples": [
+                    "source unknown @ unknown; value=2",
+                    "source 10.0.0.1:5568 @ 1970-01-01T00:00:01Z; value=1"
+                ]
+            }]
+        }]);
+        let json = serde_json::to_string(&value).unwrap();
+        let migrated = Report::from_json_any_version(&json).unwrap();
+        assert_eq!(migrated.source_version, 1);
+        let examples = &migrated.report.compliance[0].violations[0].examples;
+        assert_eq!(examples[0].detail, "value=2");
+        assert_eq!(examples[1].source_port, Some(5568));
+        assert!(migrated.report.validate().is_ok());
+    }
+}
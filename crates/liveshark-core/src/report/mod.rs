@@ -0,0 +1,787 @@
+//! Programmatic report construction and validation.
+//!
+//! `ReportBuilder` assembles a `Report` from its sections and checks the
+//! invariants documented in the specification (Appendix A) before handing it
+//! out: current schema version, RFC3339 timestamps, and canonical ordering of
+//! every list. Third-party producers get the same guarantees as reports
+//! emitted by the analysis pipeline. `migrate` reads reports written with
+//! older schema versions.
+//!
+use std::cmp::Ordering;
+
+use thiserror::Error;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+use crate::{
+    ActiveRule, AddressPriorityMap, AnalysisWarning, CaptureSummary, CitpPeerSummary,
+    ComplianceSummary, ConflictSummary, DEFAULT_GENERATED_AT, Fade, FlowSummary, HealthScore,
+    IgmpGroupSummary, InputInfo, MetricSeries, MovementSmoothness, NetworkEvent, NodeAvailability,
+    OscAddressSummary, OtherTrafficSummary, PatchEntry, PrioritySpan, PsnTrackerSummary,
+    PtpDomainSummary, REPORT_VERSION, RdmPortInventory, RdmTransactions, Report, ReportEvent,
+    SacnUniverseDiscovery, SceneChange, SourceSummary, SyncLatency, Takeover, TimecodeStream,
+    ToolInfo, TriggerEvent, UniverseMirror, UniverseSilence, UniverseSummary,
+};
+
+mod json;
+mod migrate;
+
+pub use json::{DEFAULT_FLOAT_DECIMALS, JsonOptions, to_json_string};
+pub use migrate::{AppliedMigration, MIN_SUPPORTED_REPORT_VERSION, MigratedReport};
+
+/// Tool name written by default into `tool.name`.
+pub const DEFAULT_TOOL_NAME: &str = "liveshark";
+
+/// Errors returned when a report breaks a schema invariant.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{ReportBuilder, ReportError};
+///
+/// let err = ReportBuilder::new("capture.pcapng", 0)
+///     .generated_at("yesterday")
+///     .build()
+///     .unwrap_err();
+/// assert!(matches!(err, ReportError::InvalidTimestamp { field: "generated_at", .. }));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ReportError {
+    #[error("unsupported report_version {found} (expected {expected})")]
+    UnsupportedVersion { found: u32, expected: u32 },
+    #[error("invalid report JSON: {0}")]
+    InvalidJson(String),
+    #[error("{field} must not be empty")]
+    EmptyField { field: &'static str },
+    #[error("{field} is not an RFC3339 timestamp: {value:?}")]
+    InvalidTimestamp { field: &'static str, value: String },
+    #[error("capture_summary.time_start is after capture_summary.time_end")]
+    InvertedCaptureWindow,
+    #[error("{section} is not in canonical order at index {index}")]
+    Unsorted { section: &'static str, index: usize },
+}
+
+/// Fluent builder for [`Report`].
+///
+/// Starts from an empty report for the given input, stamped with the current
+/// schema version and this crate's tool info. `build` validates the result.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{CaptureSummary, ReportBuilder};
+///
+/// let report = ReportBuilder::new("capture.pcapng", 123)
+///     .tool("my-producer", "1.0.0")
+///     .generated_at("2024-05-01T12:00:00Z")
+///     .capture_summary(CaptureSummary {
+///         packets_total: 10,
+///         time_start: Some("2024-05-01T11:59:00Z".to_string()),
+///         time_end: Some("2024-05-01T12:00:00Z".to_string()),
+///         ..CaptureSummary::default()
+///     })
+///     .build()?;
+/// assert_eq!(report.tool.name, "my-producer");
+/// assert_eq!(report.report_version, liveshark_core::REPORT_VERSION);
+/// # Ok::<(), liveshark_core::ReportError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReportBuilder {
+    report: Report,
+}
+
+impl ReportBuilder {
+    /// Start a report for the given input path and size in bytes.
+    pub fn new(input_path: impl Into<String>, input_bytes: u64) -> Self {
+        Self {
+            report: Report {
+                report_version: REPORT_VERSION,
+                tool: ToolInfo {
+                    name: DEFAULT_TOOL_NAME.to_string(),
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                },
+                generated_at: DEFAULT_GENERATED_AT.to_string(),
+                input: InputInfo {
+                    path: input_path.into(),
+                    bytes: input_bytes,
+                },
+                capture_summary: None,
+                health: None,
+                universes: Vec::new(),
+                flows: Vec::new(),
+                conflicts: Vec::new(),
+                compliance: Vec::new(),
+                rules: Vec::new(),
+                profile: None,
+                other_traffic: Vec::new(),
+                warnings: Vec::new(),
+                series: None,
+                patch: Vec::new(),
+                scene_changes: Vec::new(),
+                fades: Vec::new(),
+                movement: Vec::new(),
+                mirrored_universes: Vec::new(),
+                takeovers: Vec::new(),
+                priority_timeline: Vec::new(),
+                silences: Vec::new(),
+                events: Vec::new(),
+                sync_latency: Vec::new(),
+                sacn_discovery: Vec::new(),
+                address_priorities: Vec::new(),
+                nodes: Vec::new(),
+                timecode: Vec::new(),
+                triggers: Vec::new(),
+                rdm_responders: Vec::new(),
+                rdm_transactions: Vec::new(),
+                psn_trackers: Vec::new(),
+                osc_addresses: Vec::new(),
+                citp_peers: Vec::new(),
+                ptp_domains: Vec::new(),
+                igmp_groups: Vec::new(),
+                network_events: Vec::new(),
+                clock_corrections: Vec::new(),
+                notes: Vec::new(),
+            },
+        }
+    }
+
+    /// Tool identification written into `tool`.
+    pub fn tool(mut self, name: impl Into<String>, version: impl Into<String>) -> Self {
+        self.report.tool = ToolInfo {
+            name: name.into(),
+            version: version.into(),
+        };
+        self
+    }
+
+    /// Replace the input metadata.
+    pub fn input(mut self, input: InputInfo) -> Self {
+        self.report.input = input;
+        self
+    }
+
+    /// RFC3339 generation timestamp.
+    pub fn generated_at(mut self, timestamp: impl Into<String>) -> Self {
+        self.report.generated_at = timestamp.into();
+        self
+    }
+
+    /// Capture summary (packet count and capture window).
+    pub fn capture_summary(mut self, summary: CaptureSummary) -> Self {
+        self.report.capture_summary = Some(summary);
+        self
+    }
+
+    /// Universe summaries, in canonical order.
+    pub fn universes(mut self, universes: Vec<UniverseSummary>) -> Self {
+        self.report.universes = universes;
+        self
+    }
+
+    /// Flow summaries, in canonical order.
+    pub fn flows(mut self, flows: Vec<FlowSummary>) -> Self {
+        self.report.flows = flows;
+        self
+    }
+
+    /// Conflict summaries, in canonical order.
+    pub fn conflicts(mut self, conflicts: Vec<ConflictSummary>) -> Self {
+        self.report.conflicts = conflicts;
+        self
+    }
+
+    /// Compliance summaries, in canonical order.
+    pub fn compliance(mut self, compliance: Vec<ComplianceSummary>) -> Self {
+        self.report.compliance = compliance;
+        self
+    }
+
+    /// Rules checked during analysis, sorted by ID.
+    pub fn rules(mut self, rules: Vec<ActiveRule>) -> Self {
+        self.report.rules = rules;
+        self
+    }
+
+    /// Non-DMX flows, in canonical order (largest first).
+    pub fn other_traffic(mut self, flows: Vec<OtherTrafficSummary>) -> Self {
+        self.report.other_traffic = flows;
+        self
+    }
+
+    /// Analysis warnings, in packet order.
+    pub fn warnings(mut self, warnings: Vec<AnalysisWarning>) -> Self {
+        self.report.warnings = warnings;
+        self
+    }
+
+    /// Per-second metric series.
+    pub fn series(mut self, series: MetricSeries) -> Self {
+        self.report.series = Some(series);
+        self
+    }
+
+    /// Rig patch ranges, sorted by universe and start channel.
+    pub fn patch(mut self, patch: Vec<PatchEntry>) -> Self {
+        self.report.patch = patch;
+        self
+    }
+
+    /// Scene changes, in time order.
+    pub fn scene_changes(mut self, changes: Vec<SceneChange>) -> Self {
+        self.report.scene_changes = changes;
+        self
+    }
+
+    /// Fades, in time order.
+    pub fn fades(mut self, fades: Vec<Fade>) -> Self {
+        self.report.fades = fades;
+        self
+    }
+
+    /// Movement smoothness per fixture axis, sorted by universe, protocol,
+    /// fixture, axis, and source.
+    pub fn movement(mut self, movement: Vec<MovementSmoothness>) -> Self {
+        self.report.movement = movement;
+        self
+    }
+
+    /// Mirrored universe pairs, sorted by universe pair.
+    pub fn mirrored_universes(mut self, mirrors: Vec<UniverseMirror>) -> Self {
+        self.report.mirrored_universes = mirrors;
+        self
+    }
+
+    /// Takeover events, in time order.
+    pub fn takeovers(mut self, takeovers: Vec<Takeover>) -> Self {
+        self.report.takeovers = takeovers;
+        self
+    }
+
+    /// sACN priority spans, sorted by universe then start.
+    pub fn priority_timeline(mut self, priority_timeline: Vec<PrioritySpan>) -> Self {
+        self.report.priority_timeline = priority_timeline;
+        self
+    }
+
+    /// Universe silences, sorted by start, universe, then protocol.
+    pub fn silences(mut self, silences: Vec<UniverseSilence>) -> Self {
+        self.report.silences = silences;
+        self
+    }
+
+    /// Source events, sorted by timestamp, universe, protocol, then source ID.
+    pub fn events(mut self, events: Vec<ReportEvent>) -> Self {
+        self.report.events = events;
+        self
+    }
+
+    /// Sync-to-data latency per sync address, sorted by sync address.
+    pub fn sync_latency(mut self, sync_latency: Vec<SyncLatency>) -> Self {
+        self.report.sync_latency = sync_latency;
+        self
+    }
+
+    /// sACN universe discovery per source, sorted by CID.
+    pub fn sacn_discovery(mut self, sacn_discovery: Vec<SacnUniverseDiscovery>) -> Self {
+        self.report.sacn_discovery = sacn_discovery;
+        self
+    }
+
+    /// sACN per-address priority maps, sorted by universe then source ID.
+    pub fn address_priorities(mut self, address_priorities: Vec<AddressPriorityMap>) -> Self {
+        self.report.address_priorities = address_priorities;
+        self
+    }
+
+    /// Art-Net node availability, sorted by IP then bind index.
+    pub fn nodes(mut self, nodes: Vec<NodeAvailability>) -> Self {
+        self.report.nodes = nodes;
+        self
+    }
+
+    /// Art-Net timecode streams, sorted by source IP then stream identifier.
+    pub fn timecode(mut self, timecode: Vec<TimecodeStream>) -> Self {
+        self.report.timecode = timecode;
+        self
+    }
+
+    /// Art-Net triggers, in capture order.
+    pub fn triggers(mut self, triggers: Vec<TriggerEvent>) -> Self {
+        self.report.triggers = triggers;
+        self
+    }
+
+    /// RDM responders per node port, sorted by node IP then universe.
+    pub fn rdm_responders(mut self, rdm_responders: Vec<RdmPortInventory>) -> Self {
+        self.report.rdm_responders = rdm_responders;
+        self
+    }
+
+    /// RDM transactions per universe, sorted by universe.
+    pub fn rdm_transactions(mut self, rdm_transactions: Vec<RdmTransactions>) -> Self {
+        self.report.rdm_transactions = rdm_transactions;
+        self
+    }
+
+    /// PosiStageNet trackers, sorted by source IP then tracker id.
+    pub fn psn_trackers(mut self, psn_trackers: Vec<PsnTrackerSummary>) -> Self {
+        self.report.psn_trackers = psn_trackers;
+        self
+    }
+
+    /// Open Sound Control messages per address pattern, sorted by address.
+    pub fn osc_addresses(mut self, osc_addresses: Vec<OscAddressSummary>) -> Self {
+        self.report.osc_addresses = osc_addresses;
+        self
+    }
+
+    /// CITP peers, sorted by source IP.
+    pub fn citp_peers(mut self, citp_peers: Vec<CitpPeerSummary>) -> Self {
+        self.report.citp_peers = citp_peers;
+        self
+    }
+
+    /// PTP domains, sorted by domain number.
+    pub fn ptp_domains(mut self, ptp_domains: Vec<PtpDomainSummary>) -> Self {
+        self.report.ptp_domains = ptp_domains;
+        self
+    }
+
+    /// IGMP multicast groups, sorted by group address.
+    pub fn igmp_groups(mut self, igmp_groups: Vec<IgmpGroupSummary>) -> Self {
+        self.report.igmp_groups = igmp_groups;
+        self
+    }
+
+    /// ARP and DHCP network-health events, in capture order.
+    pub fn network_events(mut self, network_events: Vec<NetworkEvent>) -> Self {
+        self.report.network_events = network_events;
+        self
+    }
+
+    /// Operator notes, in the order given.
+    pub fn notes(mut self, notes: Vec<String>) -> Self {
+        self.report.notes = notes;
+        self
+    }
+
+    /// Network health score of the capture.
+    pub fn health(mut self, health: HealthScore) -> Self {
+        self.report.health = Some(health);
+        self
+    }
+
+    /// Name of the compliance profile the analysis ran with.
+    pub fn profile(mut self, profile: impl Into<String>) -> Self {
+        self.report.profile = Some(profile.into());
+        self
+    }
+
+    /// Validate the invariants and return the report.
+    ///
+    /// # Errors
+    /// Returns `ReportError` when a timestamp is not RFC3339, the tool name is
+    /// empty, or a section is not in canonical order.
+    pub fn build(self) -> Result<Report, ReportError> {
+        self.report.validate()?;
+        Ok(self.report)
+    }
+
+    /// Return the report without validation.
+    pub(crate) fn into_report(self) -> Report {
+        self.report
+    }
+}
+
+impl Report {
+    /// Check the schema invariants of this report.
+    ///
+    /// # Examples
+    /// ```
+    /// use liveshark_core::ReportBuilder;
+    ///
+    /// let mut report = ReportBuilder::new("capture.pcapng", 0).build()?;
+    /// report.report_version = 99;
+    /// assert!(report.validate().is_err());
+    /// # Ok::<(), liveshark_core::ReportError>(())
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the first `ReportError` found.
+    pub fn validate(&self) -> Result<(), ReportError> {
+        if self.report_version != REPORT_VERSION {
+            return Err(ReportError::UnsupportedVersion {
+                found: self.report_version,
+                expected: REPORT_VERSION,
+            });
+        }
+        if self.tool.name.trim().is_empty() {
+            return Err(ReportError::EmptyField { field: "tool.name" });
+        }
+        parse_timestamp("generated_at", &self.generated_at)?;
+        if let Some(summary) = &self.capture_summary {
+            validate_capture_summary(summary)?;
+        }
+
+        check_sorted("universes", &self.universes, |a, b| {
+            a.universe
+                .cmp(&b.universe)
+                .then_with(|| a.proto.cmp(&b.proto))
+        })?;
+        for universe in &self.universes {
+            check_sorted("universes[].sources", &universe.sources, cmp_sources)?;
+            check_sorted(
+                "universes[].destinations",
+                &universe.destinations,
+                |a, b| a.dst_ip.cmp(&b.dst_ip),
+            )?;
+            check_sorted(
+                "universes[].channel_pairs",
+                &universe.channel_pairs,
+                |a, b| a.coarse.cmp(&b.coarse),
+            )?;
+        }
+        check_sorted("flows", &self.flows, |a, b| {
+            a.src
+                .cmp(&b.src)
+                .then_with(|| a.dst.cmp(&b.dst))
+                .then_with(|| (a.vlan, a.outer_vlan).cmp(&(b.vlan, b.outer_vlan)))
+                .then_with(|| a.app_proto.cmp(&b.app_proto))
+        })?;
+        check_sorted("conflicts", &self.conflicts, |a, b| {
+            a.universe
+                .cmp(&b.universe)
+                .then_with(|| a.sources.cmp(&b.sources))
+        })?;
+        for conflict in &self.conflicts {
+            check_sorted("conflicts[].sources", &conflict.sources, Ord::cmp)?;
+        }
+        check_sorted("compliance", &self.compliance, |a, b| {
+            a.protocol.cmp(&b.protocol)
+        })?;
+        for entry in &self.compliance {
+            check_sorted("compliance[].violations", &entry.violations, |a, b| {
+                a.severity.cmp(&b.severity).then_with(|| a.id.cmp(&b.id))
+            })?;
+            for violation in &entry.violations {
+                check_sorted(
+                    "compliance[].violations[].examples",
+                    &violation.examples,
+                    Ord::cmp,
+                )?;
+            }
+        }
+        check_sorted("rules", &self.rules, |a, b| a.id.cmp(&b.id))?;
+        check_sorted("other_traffic", &self.other_traffic, cmp_other_traffic)?;
+        if let Some(series) = &self.series {
+            check_sorted("series.universes", &series.universes, |a, b| {
+                a.universe
+                    .cmp(&b.universe)
+                    .then_with(|| a.proto.cmp(&b.proto))
+            })?;
+            check_sorted("series.flows", &series.flows, |a, b| {
+                a.src.cmp(&b.src).then_with(|| a.dst.cmp(&b.dst))
+            })?;
+        }
+        check_sorted("patch", &self.patch, |a, b| {
+            a.universe
+                .cmp(&b.universe)
+                .then_with(|| a.start.cmp(&b.start))
+        })?;
+        check_sorted("scene_changes", &self.scene_changes, cmp_scene_changes)?;
+        check_sorted("fades", &self.fades, cmp_fades)?;
+        check_sorted("movement", &self.movement, cmp_movement)?;
+        check_sorted("mirrored_universes", &self.mirrored_universes, |a, b| {
+            (a.universe, a.proto, a.mirror, a.mirror_proto).cmp(&(
+                b.universe,
+                b.proto,
+                b.mirror,
+                b.mirror_proto,
+            ))
+        })?;
+        check_sorted("takeovers", &self.takeovers, cmp_takeovers)?;
+        check_sorted(
+            "priority_timeline",
+            &self.priority_timeline,
+            cmp_priority_spans,
+        )?;
+        check_sorted("silences", &self.silences, cmp_silences)?;
+        check_sorted("events", &self.events, cmp_events)?;
+        check_sorted("sync_latency", &self.sync_latency, |a, b| {
+            a.sync_address.cmp(&b.sync_address)
+        })?;
+        check_sorted("sacn_discovery", &self.sacn_discovery, |a, b| {
+            a.cid.cmp(&b.cid)
+        })?;
+        check_sorted("address_priorities", &self.address_priorities, |a, b| {
+            a.universe
+                .cmp(&b.universe)
+                .then_with(|| a.source_id.cmp(&b.source_id))
+        })?;
+        check_sorted("nodes", &self.nodes, |a, b| {
+            a.ip.cmp(&b.ip)
+                .then_with(|| a.bind_index.cmp(&b.bind_index))
+        })?;
+        check_sorted("timecode", &self.timecode, |a, b| {
+            a.source_ip
+                .cmp(&b.source_ip)
+                .then_with(|| a.stream_id.cmp(&b.stream_id))
+        })?;
+        check_sorted("psn_trackers", &self.psn_trackers, |a, b| {
+            a.source_ip
+                .cmp(&b.source_ip)
+                .then_with(|| a.tracker_id.cmp(&b.tracker_id))
+        })?;
+        check_sorted("osc_addresses", &self.osc_addresses, |a, b| {
+            a.address.cmp(&b.address)
+        })?;
+        check_sorted("citp_peers", &self.citp_peers, |a, b| {
+            a.source_ip.cmp(&b.source_ip)
+        })?;
+        check_sorted("ptp_domains", &self.ptp_domains, |a, b| {
+            a.domain.cmp(&b.domain)
+        })?;
+        check_sorted("igmp_groups", &self.igmp_groups, |a, b| {
+            a.group.cmp(&b.group)
+        })?;
+        check_sorted("warnings", &self.warnings, |a, b| {
+            a.first_packet
+                .cmp(&b.first_packet)
+                .then_with(|| a.kind.cmp(&b.kind))
+        })?;
+        for warning in &self.warnings {
+            if let Some(value) = &warning.time_start {
+                parse_timestamp("warnings[].time_start", value)?;
+            }
+            if let Some(value) = &warning.time_end {
+                parse_timestamp("warnings[].time_end", value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn validate_capture_summary(summary: &CaptureSummary) -> Result<(), ReportError> {
+    let start = summary
+        .time_start
+        .as_deref()
+        .map(|value| parse_timestamp("capture_summary.time_start", value))
+        .transpose()?;
+    let end = summary
+        .time_end
+        .as_deref()
+        .map(|value| parse_timestamp("capture_summary.time_end", value))
+        .transpose()?;
+    if let (Some(start), Some(end)) = (start, end) {
+        if start > end {
+            return Err(ReportError::InvertedCaptureWindow);
+        }
+    }
+    Ok(())
+}
+
+fn parse_timestamp(field: &'static str, value: &str) -> Result<OffsetDateTime, ReportError> {
+    OffsetDateTime::parse(value, &Rfc3339).map_err(|_| ReportError::InvalidTimestamp {
+        field,
+        value: value.to_string(),
+    })
+}
+
+/// Sources sort by `source_id` (v0.2); without ids, by `source_ip` then `cid`
+/// with absent `cid` last (v0.1).
+fn cmp_sources(a: &SourceSummary, b: &SourceSummary) -> Ordering {
+    match (&a.source_id, &b.source_id) {
+        (Some(a_id), Some(b_id)) => a_id.cmp(b_id),
+        _ => a
+            .source_ip
+            .cmp(&b.source_ip)
+            .then_with(|| match (&a.cid, &b.cid) {
+                (Some(a_cid), Some(b_cid)) => a_cid.cmp(b_cid),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }),
+    }
+}
+
+/// `other_traffic` sorts by bytes, largest first, then transport, source, and
+/// destination.
+pub(crate) fn cmp_other_traffic(a: &OtherTrafficSummary, b: &OtherTrafficSummary) -> Ordering {
+    b.bytes
+        .cmp(&a.bytes)
+        .then_with(|| a.transport.cmp(&b.transport))
+        .then_with(|| a.src.cmp(&b.src))
+        .then_with(|| a.dst.cmp(&b.dst))
+}
+
+/// `scene_changes` sorts by time, then universe, protocol, and source.
+pub(crate) fn cmp_scene_changes(a: &SceneChange, b: &SceneChange) -> Ordering {
+    a.timestamp
+        .total_cmp(&b.timestamp)
+        .then_with(|| a.universe.cmp(&b.universe))
+        .then_with(|| a.proto.cmp(&b.proto))
+        .then_with(|| a.source_id.cmp(&b.source_id))
+}
+
+/// `fades` sorts by time, then universe, protocol, source, and first channel.
+pub(crate) fn cmp_fades(a: &Fade, b: &Fade) -> Ordering {
+    a.timestamp
+        .total_cmp(&b.timestamp)
+        .then_with(|| a.universe.cmp(&b.universe))
+        .then_with(|| a.proto.cmp(&b.proto))
+        .then_with(|| a.source_id.cmp(&b.source_id))
+        .then_with(|| a.start_channel.cmp(&b.start_channel))
+}
+
+/// `movement` sorts by universe, protocol, fixture, axis, then source.
+pub(crate) fn cmp_movement(a: &MovementSmoothness, b: &MovementSmoothness) -> Ordering {
+    (a.universe, a.proto, &a.fixture, a.axis, &a.source_id).cmp(&(
+        b.universe,
+        b.proto,
+        &b.fixture,
+        b.axis,
+        &b.source_id,
+    ))
+}
+
+/// `takeovers` sort by timestamp, universe, protocol, then the source that
+/// lost control.
+pub(crate) fn cmp_takeovers(a: &Takeover, b: &Takeover) -> Ordering {
+    a.timestamp
+        .total_cmp(&b.timestamp)
+        .then_with(|| a.universe.cmp(&b.universe))
+        .then_with(|| a.proto.cmp(&b.proto))
+        .then_with(|| a.from_source.cmp(&b.from_source))
+}
+
+/// `priority_timeline` sorts by universe, then start.
+pub(crate) fn cmp_priority_spans(a: &PrioritySpan, b: &PrioritySpan) -> Ordering {
+    a.universe
+        .cmp(&b.universe)
+        .then_with(|| a.start.total_cmp(&b.start))
+}
+
+/// `events` sort by timestamp, universe, protocol, then source ID.
+pub(crate) fn cmp_events(a: &ReportEvent, b: &ReportEvent) -> Ordering {
+    a.timestamp
+        .total_cmp(&b.timestamp)
+        .then_with(|| a.universe.cmp(&b.universe))
+        .then_with(|| a.proto.cmp(&b.proto))
+        .then_with(|| a.source_id.cmp(&b.source_id))
+}
+
+/// `silences` sort by start, universe, then protocol.
+pub(crate) fn cmp_silences(a: &UniverseSilence, b: &UniverseSilence) -> Ordering {
+    a.start
+        .total_cmp(&b.start)
+        .then_with(|| a.universe.cmp(&b.universe))
+        .then_with(|| a.proto.cmp(&b.proto))
+}
+
+fn check_sorted<T>(
+    section: &'static str,
+    items: &[T],
+    cmp: impl Fn(&T, &T) -> Ordering,
+) -> Result<(), ReportError> {
+    match items
+        .windows(2)
+        .position(|pair| cmp(&pair[0], &pair[1]) == Ordering::Greater)
+    {
+        Some(index) => Err(ReportError::Unsorted {
+            section,
+            index: index + 1,
+        }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ReportBuilder, ReportError};
+    use crate::{CaptureSummary, ConflictSummary, FlowSummary, Protocol, Severity};
+
+    fn flow(src: &str, dst: &str) -> FlowSummary {
+        FlowSummary {
+            app_proto: Protocol::Udp,
+            src: src.to_string(),
+            dst: dst.to_string(),
+            vlan: None,
+            outer_vlan: None,
+            pps: None,
+            bps: None,
+            iat_jitter_ms: None,
+            max_iat_ms: None,
+            pps_peak_1s: None,
+            bps_peak_1s: None,
+            payload_size_min: None,
+            payload_size_avg: None,
+            payload_size_p50: None,
+            payload_size_p95: None,
+            payload_size_max: None,
+            microburst: None,
+            wifi: None,
+        }
+    }
+
+    fn conflict(universe: u16, sources: &[&str]) -> ConflictSummary {
+        ConflictSummary {
+            universe,
+            sources: sources.iter().map(|source| source.to_string()).collect(),
+            proto: Some(Protocol::Sacn),
+            overlap_duration_s: 1.0,
+            affected_channels: Vec::new(),
+            severity: Severity::Warning,
+            conflict_score: 1.0,
+            first_seen: None,
+            channel_labels: Vec::new(),
+            rig_labels: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn default_builder_produces_valid_stub() {
+        let report = ReportBuilder::new("capture.pcapng", 7).build().unwrap();
+        assert_eq!(report.input.bytes, 7);
+        assert!(report.universes.is_empty());
+        assert!(report.capture_summary.is_none());
+    }
+
+    #[test]
+    fn rejects_unsorted_sections() {
+        let err = ReportBuilder::new("capture.pcapng", 0)
+            .flows(vec![flow("10.0.0.2:1", "x"), flow("10.0.0.1:1", "x")])
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ReportError::Unsorted {
+                section: "flows",
+                index: 1
+            }
+        );
+
+        let err = ReportBuilder::new("capture.pcapng", 0)
+            .conflicts(vec![conflict(1, &["b", "a"])])
+            .build()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ReportError::Unsorted {
+                section: "conflicts[].sources",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn rejects_inverted_capture_window() {
+        let err = ReportBuilder::new("capture.pcapng", 0)
+            .capture_summary(CaptureSummary {
+                packets_total: 1,
+                time_start: Some("1970-01-01T00:00:02Z".to_string()),
+                time_end: Some("1970-01-01T00:00:01Z".to_string()),
+                ..CaptureSummary::default()
+            })
+            .build()
+            .unwrap_err();
+        assert_eq!(err, ReportError::InvertedCaptureWindow);
+    }
+}
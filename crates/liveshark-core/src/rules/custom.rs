@@ -0,0 +1,567 @@
+//! User-defined compliance rules.
+//!
+//! A custom rule is one line of configuration: a condition over decoded
+//! packet fields, then `->`, the severity, and the reported rule ID.
+//!
+//! ```text
+//! proto == "artnet" && dst_port != 6454 -> warning CUSTOM-PORT
+//! ```
+//!
+//! Conditions combine comparisons (`==`, `!=`, `<`, `<=`, `>`, `>=`) with
+//! `&&`, `||`, `!`, and parentheses; `&&` binds tighter than `||`. Fields and
+//! literals are type-checked when the rule is parsed: `proto` takes `"artnet"`,
+//! `"sacn"` or `"udp"`, `src_ip` / `dst_ip` take quoted addresses, and both only
+//! support `==` and `!=`; every other field is numeric. A comparison on a field
+//! the packet does not carry (e.g. `universe` on plain UDP) is false.
+//!
+//! IDs starting with `LS-` are reserved for the built-in registry.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{Protocol, Severity};
+
+/// Separates the condition from the severity and ID.
+const ARROW: &str = "->";
+/// Prefix reserved for built-in rule codes.
+const RESERVED_PREFIX: &str = "LS-";
+const SEVERITIES: [Severity; 3] = [Severity::Error, Severity::Warning, Severity::Info];
+
+/// Error returned when a custom rule cannot be parsed.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::CustomRule;
+///
+/// let err = "ttl > 64 -> warning LS-TTL".parse::<CustomRule>().unwrap_err();
+/// assert!(err.to_string().contains("reserved"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid custom rule `{rule}`: {reason}")]
+pub struct CustomRuleError {
+    /// Rule text as written in the configuration.
+    pub rule: String,
+    /// What is wrong with it.
+    pub reason: String,
+}
+
+/// Site-specific rule evaluated on every decoded packet.
+///
+/// Serializes as its source text, so configuration files list rules as plain
+/// strings under `custom_rules`. Matches are reported in `compliance[]` under
+/// the packet protocol, with the rule ID and severity chosen by the user.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{CustomRule, Severity};
+///
+/// let rule: CustomRule = r#"proto == "artnet" && dst_port != 6454 -> warning CUSTOM-PORT"#
+///     .parse()?;
+/// assert_eq!(rule.id(), "CUSTOM-PORT");
+/// assert_eq!(rule.severity(), Severity::Warning);
+/// assert_eq!(rule.condition(), r#"proto == "artnet" && dst_port != 6454"#);
+///
+/// assert!(r#"dst_port == "6454" -> error CUSTOM-PORT"#.parse::<CustomRule>().is_err());
+/// # Ok::<(), liveshark_core::CustomRuleError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct CustomRule {
+    condition: String,
+    severity: Severity,
+    id: String,
+    expr: Expr,
+}
+
+impl CustomRule {
+    /// Rule ID reported in violations and `rules[]`.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Severity reported for matches.
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Condition text, left of `->`.
+    pub fn condition(&self) -> &str {
+        &self.condition
+    }
+
+    /// Returns true when the packet satisfies the condition.
+    pub(crate) fn matches(&self, packet: &PacketFields) -> bool {
+        self.expr.eval(packet)
+    }
+}
+
+impl fmt::Display for CustomRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.condition, ARROW, self.severity, self.id
+        )
+    }
+}
+
+impl FromStr for CustomRule {
+    type Err = CustomRuleError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        parse_rule(text).map_err(|reason| CustomRuleError {
+            rule: text.to_string(),
+            reason,
+        })
+    }
+}
+
+impl TryFrom<String> for CustomRule {
+    type Error = CustomRuleError;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        text.parse()
+    }
+}
+
+impl From<CustomRule> for String {
+    fn from(rule: CustomRule) -> Self {
+        rule.to_string()
+    }
+}
+
+/// Decoded packet fields visible to custom rule conditions.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PacketFields {
+    pub(crate) proto: Protocol,
+    pub(crate) src_ip: IpAddr,
+    pub(crate) dst_ip: IpAddr,
+    pub(crate) src_port: u16,
+    pub(crate) dst_port: u16,
+    pub(crate) ttl: u8,
+    pub(crate) ip_len: usize,
+    pub(crate) payload_len: usize,
+    pub(crate) universe: Option<u16>,
+    pub(crate) sequence: Option<u8>,
+    pub(crate) priority: Option<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Proto,
+    SrcIp,
+    DstIp,
+    SrcPort,
+    DstPort,
+    Ttl,
+    IpLen,
+    PayloadLen,
+    Universe,
+    Sequence,
+    Priority,
+}
+
+impl Field {
+    const ALL: &'static [(&'static str, Field)] = &[
+        ("proto", Field::Proto),
+        ("src_ip", Field::SrcIp),
+        ("dst_ip", Field::DstIp),
+        ("src_port", Field::SrcPort),
+        ("dst_port", Field::DstPort),
+        ("ttl", Field::Ttl),
+        ("ip_len", Field::IpLen),
+        ("payload_len", Field::PayloadLen),
+        ("universe", Field::Universe),
+        ("sequence", Field::Sequence),
+        ("priority", Field::Priority),
+    ];
+
+    fn value(self, packet: &PacketFields) -> Option<Literal> {
+        let number = |value: usize| Some(Literal::Number(value as u64));
+        match self {
+            Field::Proto => Some(Literal::Protocol(packet.proto)),
+            Field::SrcIp => Some(Literal::Ip(packet.src_ip)),
+            Field::DstIp => Some(Literal::Ip(packet.dst_ip)),
+            Field::SrcPort => number(usize::from(packet.src_port)),
+            Field::DstPort => number(usize::from(packet.dst_port)),
+            Field::Ttl => number(usize::from(packet.ttl)),
+            Field::IpLen => number(packet.ip_len),
+            Field::PayloadLen => number(packet.payload_len),
+            Field::Universe => packet.universe.and_then(|v| number(usize::from(v))),
+            Field::Sequence => packet.sequence.and_then(|v| number(usize::from(v))),
+            Field::Priority => packet.priority.and_then(|v| number(usize::from(v))),
+        }
+    }
+
+    /// Parse a literal of this field's type.
+    fn literal(self, token: &Token) -> Result<Literal, String> {
+        match (self, token) {
+            (Field::Proto, Token::Str(value)) => [Protocol::ArtNet, Protocol::Sacn, Protocol::Udp]
+                .into_iter()
+                .find(|protocol| protocol.as_str() == value)
+                .map(Literal::Protocol)
+                .ok_or_else(|| format!("unknown protocol \"{value}\"")),
+            (Field::SrcIp | Field::DstIp, Token::Str(value)) => value
+                .parse()
+                .map(Literal::Ip)
+                .map_err(|_| format!("invalid IP address \"{value}\"")),
+            (Field::Proto | Field::SrcIp | Field::DstIp, _) => {
+                Err("expected a quoted string".to_string())
+            }
+            (_, Token::Number(value)) => Ok(Literal::Number(*value)),
+            _ => Err("expected a number".to_string()),
+        }
+    }
+
+    fn is_text(self) -> bool {
+        matches!(self, Field::Proto | Field::SrcIp | Field::DstIp)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Literal {
+    Protocol(Protocol),
+    Ip(IpAddr),
+    Number(u64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl CmpOp {
+    fn holds(self, ordering: Ordering) -> bool {
+        match self {
+            CmpOp::Eq => ordering.is_eq(),
+            CmpOp::Ne => ordering.is_ne(),
+            CmpOp::Lt => ordering.is_lt(),
+            CmpOp::Le => ordering.is_le(),
+            CmpOp::Gt => ordering.is_gt(),
+            CmpOp::Ge => ordering.is_ge(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Compare {
+        field: Field,
+        op: CmpOp,
+        value: Literal,
+    },
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    fn eval(&self, packet: &PacketFields) -> bool {
+        match self {
+            Expr::Compare { field, op, value } => match (field.value(packet), value) {
+                (Some(Literal::Number(actual)), Literal::Number(expected)) => {
+                    op.holds(actual.cmp(expected))
+                }
+                (Some(actual), expected) => (actual == *expected) == (*op == CmpOp::Eq),
+                (None, _) => false,
+            },
+            Expr::Not(inner) => !inner.eval(packet),
+            Expr::And(left, right) => left.eval(packet) && right.eval(packet),
+            Expr::Or(left, right) => left.eval(packet) || right.eval(packet),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Ident(String),
+    Number(u64),
+    Str(String),
+    Cmp(CmpOp),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+fn parse_rule(text: &str) -> Result<CustomRule, String> {
+    let (condition, target) = text
+        .rsplit_once(ARROW)
+        .ok_or_else(|| format!("expected `<condition> {ARROW} <severity> <ID>`"))?;
+    let condition = condition.trim();
+    let mut target = target.split_whitespace();
+    let (Some(severity), Some(id), None) = (target.next(), target.next(), target.next()) else {
+        return Err(format!("expected `{ARROW} <severity> <ID>`"));
+    };
+    let severity = SEVERITIES
+        .into_iter()
+        .find(|candidate| candidate.as_str() == severity)
+        .ok_or_else(|| format!("unknown severity `{severity}` (error, warning, or info)"))?;
+    let valid_id = id
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid_id {
+        return Err(format!(
+            "rule ID `{id}` may only contain letters, digits, `-` and `_`"
+        ));
+    }
+    if id.to_ascii_uppercase().starts_with(RESERVED_PREFIX) {
+        return Err(format!(
+            "rule ID `{id}` uses the reserved `{RESERVED_PREFIX}` prefix"
+        ));
+    }
+
+    let mut parser = Parser {
+        tokens: tokenize(condition)?,
+        pos: 0,
+    };
+    let expr = parser.or()?;
+    if let Some(token) = parser.peek() {
+        return Err(format!("unexpected {token:?} after the condition"));
+    }
+    Ok(CustomRule {
+        condition: condition.to_string(),
+        severity,
+        id: id.to_string(),
+        expr,
+    })
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let next = chars.peek().map(|(_, next)| *next);
+        let token = match (c, next) {
+            _ if c.is_whitespace() => continue,
+            ('(', _) => Token::Open,
+            (')', _) => Token::Close,
+            ('&', Some('&')) => Token::And,
+            ('|', Some('|')) => Token::Or,
+            ('=', Some('=')) => Token::Cmp(CmpOp::Eq),
+            ('!', Some('=')) => Token::Cmp(CmpOp::Ne),
+            ('<', Some('=')) => Token::Cmp(CmpOp::Le),
+            ('>', Some('=')) => Token::Cmp(CmpOp::Ge),
+            ('!', _) => Token::Not,
+            ('<', _) => Token::Cmp(CmpOp::Lt),
+            ('>', _) => Token::Cmp(CmpOp::Gt),
+            ('"', _) => {
+                let rest = &text[start + c.len_utf8()..];
+                let (value, _) = rest
+                    .split_once('"')
+                    .ok_or_else(|| "unterminated string".to_string())?;
+                for _ in value.chars().chain(['"']) {
+                    chars.next();
+                }
+                tokens.push(Token::Str(value.to_string()));
+                continue;
+            }
+            _ if c.is_ascii_digit() || c.is_ascii_alphabetic() || c == '_' => {
+                let mut word = String::from(c);
+                while let Some((_, c)) =
+                    chars.next_if(|(_, c)| c.is_ascii_alphanumeric() || *c == '_')
+                {
+                    word.push(c);
+                }
+                let token = if c.is_ascii_digit() {
+                    Token::Number(
+                        word.parse()
+                            .map_err(|_| format!("invalid number `{word}`"))?,
+                    )
+                } else {
+                    Token::Ident(word)
+                };
+                tokens.push(token);
+                continue;
+            }
+            _ => return Err(format!("unexpected character `{c}`")),
+        };
+        if matches!(
+            token,
+            Token::And | Token::Or | Token::Cmp(CmpOp::Eq | CmpOp::Ne | CmpOp::Le | CmpOp::Ge)
+        ) {
+            chars.next();
+        }
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// Recursive-descent parser: `or := and ("||" and)*`,
+/// `and := unary ("&&" unary)*`, `unary := "!" unary | "(" or ")" | compare`.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Result<Token, String> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| "unexpected end of condition".to_string())?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut expr = self.and()?;
+        while self.eat(&Token::Or) {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut expr = self.unary()?;
+        while self.eat(&Token::And) {
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.eat(&Token::Not) {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        if self.eat(&Token::Open) {
+            let expr = self.or()?;
+            if !self.eat(&Token::Close) {
+                return Err("missing `)`".to_string());
+            }
+            return Ok(expr);
+        }
+        self.compare()
+    }
+
+    fn compare(&mut self) -> Result<Expr, String> {
+        let Token::Ident(name) = self.next()? else {
+            return Err("expected a field name".to_string());
+        };
+        let field = Field::ALL
+            .iter()
+            .find(|(candidate, _)| *candidate == name)
+            .map(|(_, field)| *field)
+            .ok_or_else(|| format!("unknown field `{name}`"))?;
+        let Token::Cmp(op) = self.next()? else {
+            return Err(format!("expected a comparison after `{name}`"));
+        };
+        if field.is_text() && !matches!(op, CmpOp::Eq | CmpOp::Ne) {
+            return Err(format!("`{name}` only supports == and !="));
+        }
+        let value = field
+            .literal(&self.next()?)
+            .map_err(|reason| format!("`{name}`: {reason}"))?;
+        Ok(Expr::Compare { field, op, value })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use super::{CustomRule, PacketFields};
+    use crate::{Protocol, Severity};
+
+    fn artdmx(dst_port: u16) -> PacketFields {
+        PacketFields {
+            proto: Protocol::ArtNet,
+            src_ip: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)),
+            dst_ip: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 255)),
+            src_port: 6454,
+            dst_port,
+            ttl: 64,
+            ip_len: 558,
+            payload_len: 530,
+            universe: Some(3),
+            sequence: Some(1),
+            priority: None,
+        }
+    }
+
+    fn rule(text: &str) -> CustomRule {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_severity_id_and_condition() {
+        let parsed = rule(r#"  proto == "artnet" && dst_port != 6454 -> warning CUSTOM-PORT "#);
+        assert_eq!(parsed.id(), "CUSTOM-PORT");
+        assert_eq!(parsed.severity(), Severity::Warning);
+        assert_eq!(
+            parsed.to_string(),
+            r#"proto == "artnet" && dst_port != 6454 -> warning CUSTOM-PORT"#
+        );
+        assert!(parsed.matches(&artdmx(6455)));
+        assert!(!parsed.matches(&artdmx(6454)));
+    }
+
+    #[test]
+    fn evaluates_precedence_negation_and_missing_fields() {
+        let packet = artdmx(6454);
+        assert!(rule("universe == 1 || universe == 3 && ttl >= 64 -> info U").matches(&packet));
+        assert!(!rule("(universe == 1 || universe == 3) && ttl < 64 -> info U").matches(&packet));
+        assert!(rule(r#"!(src_ip == "10.0.0.2") -> info U"#).matches(&packet));
+        assert!(!rule("priority <= 200 -> info U").matches(&packet));
+        assert!(rule("!(priority <= 200) -> info U").matches(&packet));
+        assert!(rule(r#"dst_ip == "10.0.0.255" && payload_len > 18 -> error U"#).matches(&packet));
+    }
+
+    #[test]
+    fn rejects_invalid_rules() {
+        for text in [
+            "ttl > 1",
+            "ttl > 1 -> fatal CUSTOM",
+            "ttl > 1 -> warning",
+            "ttl > 1 -> warning A B",
+            "ttl > 1 -> warning ls-ttl",
+            "ttl > 1 -> warning BAD.ID",
+            "hops > 1 -> warning CUSTOM",
+            r#"proto < "artnet" -> warning CUSTOM"#,
+            r#"proto == "dmx" -> warning CUSTOM"#,
+            r#"src_ip == "10.0.0" -> warning CUSTOM"#,
+            r#"ttl == "1" -> warning CUSTOM"#,
+            r#"proto == "artnet -> warning CUSTOM"#,
+            "(ttl > 1 -> warning CUSTOM",
+            "ttl > 1 ttl -> warning CUSTOM",
+            "ttl > 99999999999999999999 -> warning CUSTOM",
+            "ttl > 1 & ttl < 9 -> warning CUSTOM",
+        ] {
+            assert!(text.parse::<CustomRule>().is_err(), "{text}");
+        }
+    }
+
+    #[test]
+    fn serializes_as_source_text() {
+        let parsed: CustomRule = serde_json::from_str(r#""ttl>64->error CUSTOM-TTL""#).unwrap();
+        assert_eq!(
+            serde_json::to_string(&parsed).unwrap(),
+            r#""ttl>64 -> error CUSTOM-TTL""#
+        );
+        assert!(serde_json::from_str::<CustomRule>(r#""ttl >""#).is_err());
+    }
+}
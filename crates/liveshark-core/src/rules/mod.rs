@@ -0,0 +1,755 @@
+//! Registry of compliance rules.
+//!
+//! Every `LS-*` violation emitted by the analysis pipeline has a `RuleId`
+//! carrying its stable code, protocol, default severity, report message, and a
+//! reference into the protocol standard. Reports keep the code as a plain
+//! string (`Violation::id`) so unknown rules from newer producers still parse;
+//! `Violation::rule` maps it back to a `RuleId` when known.
+//!
+//! `RuleSet` holds per-rule overrides (enable/disable, severity) and is part of
+//! the analyzer configuration, next to site-specific `CustomRule`s.
+//!
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{ActiveRule, Protocol, Severity, Violation};
+
+mod custom;
+
+pub(crate) use custom::PacketFields;
+pub use custom::{CustomRule, CustomRuleError};
+
+/// Error returned when a string is not a known rule code.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::RuleId;
+///
+/// let err = "LS-NOPE".parse::<RuleId>().unwrap_err();
+/// assert!(err.to_string().contains("LS-NOPE"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("unknown rule code: {code}")]
+pub struct UnknownRuleError {
+    pub code: String,
+}
+
+/// Stable identity of a compliance rule.
+///
+/// Serializes as its code (for example `"LS-SACN-START-CODE"`).
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{Protocol, RuleId, Severity};
+///
+/// let rule: RuleId = "LS-SACN-START-CODE".parse()?;
+/// assert_eq!(rule, RuleId::SacnStartCode);
+/// assert_eq!(rule.protocol(), Protocol::Sacn);
+/// assert_eq!(rule.default_severity(), Severity::Error);
+/// assert!(RuleId::ALL.contains(&rule));
+/// # Ok::<(), liveshark_core::UnknownRuleError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(into = "&'static str", try_from = "String")]
+pub enum RuleId {
+    /// UDP packet could not be sliced from the link layer.
+    UdpSlice,
+    /// Packet has no network (IP) layer.
+    UdpMissingNetwork,
+    /// IP packet carries no payload.
+    UdpMissingPayload,
+    /// UDP datagram shorter than the 8-byte UDP header.
+    UdpTooShort,
+    /// Sustained broadcast traffic from one source above the configured rate.
+    UdpBroadcastStorm,
+    /// UDP datagram carried in IP fragments (not reassembled).
+    UdpFragmented,
+    /// Lighting-protocol IP datagram longer than the configured maximum.
+    UdpOversized,
+    /// IPv4 header checksum does not match the header.
+    UdpIpChecksum,
+    /// UDP checksum does not match the pseudo-header and datagram.
+    UdpChecksum,
+    /// Packet cut short by the capture snapshot length.
+    CaptureTruncated,
+    /// ArtDMX sent from and to ports other than 6454.
+    ArtNetPort,
+    /// ArtDMX sent to a multicast group.
+    ArtNetMulticast,
+    /// Art-Net universe frame rate above the configured maximum.
+    ArtNetUniverseRate,
+    /// Art-Net Port-Address above 0x7FFF.
+    ArtNetUniverseId,
+    /// ArtDMX length odd or outside 2..=512.
+    ArtNetLength,
+    /// Art-Net payload shorter than the ArtDMX header or declared length.
+    ArtNetTooShort,
+    /// Art-Net opcode LiveShark does not decode.
+    ArtNetOpcode,
+    /// ArtDMX sequence numbers skipped (optional).
+    ArtNetSeqGap,
+    /// ArtAddress remote reconfiguration of a node.
+    ArtNetAddress,
+    /// Art-Net protocol revision older than Art-Net 4 (below 14).
+    ArtNetProtVer,
+    /// ArtDMX packets for a universe sent faster than the minimum interval.
+    ArtNetMinInterval,
+    /// sACN sent from and to ports other than 5568.
+    SacnPort,
+    /// sACN DMX start code other than 0x00 and 0xdd (per-address priority).
+    SacnStartCode,
+    /// sACN property value count is 0 or exceeds 513.
+    SacnPropertyCount,
+    /// sACN DMX data length cannot be represented for the payload.
+    SacnDmxLength,
+    /// sACN payload shorter than the fixed headers.
+    SacnTooShort,
+    /// sACN ACN packet identifier mismatch.
+    SacnAcnPid,
+    /// sACN root layer vector is not VECTOR_ROOT_E131_DATA.
+    SacnRootVector,
+    /// sACN framing layer vector is not VECTOR_E131_DATA_PACKET.
+    SacnFramingVector,
+    /// sACN DMP layer vector is not VECTOR_DMP_SET_PROPERTY.
+    SacnDmpVector,
+    /// sACN sequence numbers skipped (optional).
+    SacnSeqGap,
+    /// sACN source name not null-terminated UTF-8, or empty.
+    SacnSourceName,
+    /// sACN multicast TTL outside the configured range.
+    SacnMulticastTtl,
+    /// sACN priority in the reserved range 201..=255.
+    SacnPriority,
+    /// sACN priority changed mid-stream for a source and universe.
+    SacnPriorityChange,
+    /// One sACN CID sent from more than one IP address.
+    SacnCidMultiIp,
+    /// One sACN CID sent from several IP addresses at overlapping times.
+    SacnCidCollision,
+    /// sACN data sent to a broadcast address.
+    SacnBroadcast,
+    /// sACN multicast group does not match the universe in the packet.
+    SacnMulticastMismatch,
+    /// sACN data sent by unicast (optional).
+    SacnUnicast,
+    /// sACN universe multicast while no receiver joined its group by IGMP.
+    SacnNoSubscriber,
+    /// sACN universe frame rate above the configured maximum.
+    SacnUniverseRate,
+    /// sACN source refresh rate above the configured maximum (44 Hz).
+    SacnRate,
+    /// sACN universe discovery differs from the universes a source sends.
+    SacnDiscovery,
+}
+
+impl RuleId {
+    /// Every known rule, in code order.
+    pub const ALL: &'static [RuleId] = &[
+        RuleId::ArtNetAddress,
+        RuleId::ArtNetLength,
+        RuleId::ArtNetMinInterval,
+        RuleId::ArtNetMulticast,
+        RuleId::ArtNetOpcode,
+        RuleId::ArtNetPort,
+        RuleId::ArtNetProtVer,
+        RuleId::ArtNetSeqGap,
+        RuleId::ArtNetTooShort,
+        RuleId::ArtNetUniverseId,
+        RuleId::ArtNetUniverseRate,
+        RuleId::CaptureTruncated,
+        RuleId::SacnAcnPid,
+        RuleId::SacnBroadcast,
+        RuleId::SacnCidCollision,
+        RuleId::SacnCidMultiIp,
+        RuleId::SacnDiscovery,
+        RuleId::SacnDmpVector,
+        RuleId::SacnDmxLength,
+        RuleId::SacnFramingVector,
+        RuleId::SacnMulticastMismatch,
+        RuleId::SacnMulticastTtl,
+        RuleId::SacnNoSubscriber,
+        RuleId::SacnPort,
+        RuleId::SacnPriority,
+        RuleId::SacnPriorityChange,
+        RuleId::SacnPropertyCount,
+        RuleId::SacnRate,
+        RuleId::SacnRootVector,
+        RuleId::SacnSeqGap,
+        RuleId::SacnSourceName,
+        RuleId::SacnStartCode,
+        RuleId::SacnTooShort,
+        RuleId::SacnUnicast,
+        RuleId::SacnUniverseRate,
+        RuleId::UdpBroadcastStorm,
+        RuleId::UdpChecksum,
+        RuleId::UdpFragmented,
+        RuleId::UdpIpChecksum,
+        RuleId::UdpMissingNetwork,
+        RuleId::UdpMissingPayload,
+        RuleId::UdpOversized,
+        RuleId::UdpSlice,
+        RuleId::UdpTooShort,
+    ];
+
+    /// Stable code written into `Violation::id`.
+    pub fn code(self) -> &'static str {
+        match self {
+            RuleId::UdpSlice => "LS-UDP-SLICE",
+            RuleId::UdpMissingNetwork => "LS-UDP-MISSING-NETWORK",
+            RuleId::UdpMissingPayload => "LS-UDP-MISSING-PAYLOAD",
+            RuleId::UdpTooShort => "LS-UDP-TOO-SHORT",
+            RuleId::UdpBroadcastStorm => "LS-UDP-BROADCAST-STORM",
+            RuleId::UdpFragmented => "LS-UDP-FRAGMENTED",
+            RuleId::UdpOversized => "LS-UDP-OVERSIZED",
+            RuleId::UdpIpChecksum => "LS-UDP-IP-CHECKSUM",
+            RuleId::UdpChecksum => "LS-UDP-CHECKSUM",
+            RuleId::CaptureTruncated => "LS-CAPTURE-TRUNCATED",
+            RuleId::ArtNetPort => "LS-ARTNET-PORT",
+            RuleId::ArtNetMulticast => "LS-ARTNET-MULTICAST",
+            RuleId::ArtNetUniverseRate => "LS-ARTNET-UNIVERSE-RATE",
+            RuleId::ArtNetUniverseId => "LS-ARTNET-UNIVERSE-ID",
+            RuleId::ArtNetLength => "LS-ARTNET-LENGTH",
+            RuleId::ArtNetTooShort => "LS-ARTNET-TOO-SHORT",
+            RuleId::ArtNetOpcode => "LS-ARTNET-OPCODE",
+            RuleId::ArtNetSeqGap => "LS-ARTNET-SEQ-GAP",
+            RuleId::ArtNetMinInterval => "LS-ARTNET-MIN-INTERVAL",
+            RuleId::ArtNetAddress => "LS-ARTNET-ADDRESS",
+            RuleId::ArtNetProtVer => "LS-ARTNET-PROTVER",
+            RuleId::SacnPort => "LS-SACN-PORT",
+            RuleId::SacnStartCode => "LS-SACN-START-CODE",
+            RuleId::SacnPropertyCount => "LS-SACN-PROPERTY-COUNT",
+            RuleId::SacnDmxLength => "LS-SACN-DMX-LENGTH",
+            RuleId::SacnTooShort => "LS-SACN-TOO-SHORT",
+            RuleId::SacnAcnPid => "LS-SACN-ACN-PID",
+            RuleId::SacnRootVector => "LS-SACN-ROOT-VECTOR",
+            RuleId::SacnFramingVector => "LS-SACN-FRAMING-VECTOR",
+            RuleId::SacnDmpVector => "LS-SACN-DMP-VECTOR",
+            RuleId::SacnSeqGap => "LS-SACN-SEQ-GAP",
+            RuleId::SacnSourceName => "LS-SACN-SOURCE-NAME",
+            RuleId::SacnMulticastTtl => "LS-SACN-MULTICAST-TTL",
+            RuleId::SacnPriority => "LS-SACN-PRIORITY",
+            RuleId::SacnPriorityChange => "LS-SACN-PRIORITY-CHANGE",
+            RuleId::SacnCidMultiIp => "LS-SACN-CID-MULTI-IP",
+            RuleId::SacnCidCollision => "LS-SACN-CID-COLLISION",
+            RuleId::SacnBroadcast => "LS-SACN-BROADCAST",
+            RuleId::SacnMulticastMismatch => "LS-SACN-MULTICAST-MISMATCH",
+            RuleId::SacnUnicast => "LS-SACN-UNICAST",
+            RuleId::SacnNoSubscriber => "LS-SACN-NO-SUBSCRIBER",
+            RuleId::SacnUniverseRate => "LS-SACN-UNIVERSE-RATE",
+            RuleId::SacnRate => "LS-SACN-RATE",
+            RuleId::SacnDiscovery => "LS-SACN-DISCOVERY",
+        }
+    }
+
+    /// Protocol whose compliance entry collects this rule.
+    pub fn protocol(self) -> Protocol {
+        match self {
+            RuleId::UdpSlice
+            | RuleId::UdpMissingNetwork
+            | RuleId::UdpMissingPayload
+            | RuleId::UdpTooShort
+            | RuleId::UdpBroadcastStorm
+            | RuleId::UdpFragmented
+            | RuleId::UdpOversized
+            | RuleId::UdpIpChecksum
+            | RuleId::UdpChecksum
+            | RuleId::CaptureTruncated => Protocol::Udp,
+            RuleId::ArtNetPort
+            | RuleId::ArtNetMulticast
+            | RuleId::ArtNetUniverseRate
+            | RuleId::ArtNetUniverseId
+            | RuleId::ArtNetLength
+            | RuleId::ArtNetTooShort
+            | RuleId::ArtNetOpcode
+            | RuleId::ArtNetSeqGap
+            | RuleId::ArtNetMinInterval
+            | RuleId::ArtNetAddress
+            | RuleId::ArtNetProtVer => Protocol::ArtNet,
+            RuleId::SacnPort
+            | RuleId::SacnStartCode
+            | RuleId::SacnPropertyCount
+            | RuleId::SacnDmxLength
+            | RuleId::SacnTooShort
+            | RuleId::SacnAcnPid
+            | RuleId::SacnRootVector
+            | RuleId::SacnFramingVector
+            | RuleId::SacnDmpVector
+            | RuleId::SacnSeqGap
+            | RuleId::SacnSourceName
+            | RuleId::SacnMulticastTtl
+            | RuleId::SacnPriority
+            | RuleId::SacnPriorityChange
+            | RuleId::SacnCidMultiIp
+            | RuleId::SacnCidCollision
+            | RuleId::SacnBroadcast
+            | RuleId::SacnMulticastMismatch
+            | RuleId::SacnUnicast
+            | RuleId::SacnNoSubscriber
+            | RuleId::SacnUniverseRate
+            | RuleId::SacnRate
+            | RuleId::SacnDiscovery => Protocol::Sacn,
+        }
+    }
+
+    /// Severity used when the rule is reported.
+    pub fn default_severity(self) -> Severity {
+        match self {
+            RuleId::UdpMissingNetwork
+            | RuleId::UdpMissingPayload
+            | RuleId::UdpBroadcastStorm
+            | RuleId::UdpFragmented
+            | RuleId::UdpOversized
+            | RuleId::UdpIpChecksum
+            | RuleId::UdpChecksum
+            | RuleId::CaptureTruncated
+            | RuleId::ArtNetPort
+            | RuleId::ArtNetMulticast
+            | RuleId::ArtNetUniverseRate
+            | RuleId::ArtNetSeqGap
+            | RuleId::ArtNetMinInterval
+            | RuleId::ArtNetAddress
+            | RuleId::ArtNetProtVer
+            | RuleId::SacnPort
+            | RuleId::SacnSeqGap
+            | RuleId::SacnSourceName
+            | RuleId::SacnMulticastTtl
+            | RuleId::SacnPriority
+            | RuleId::SacnPriorityChange
+            | RuleId::SacnCidMultiIp
+            | RuleId::SacnBroadcast
+            | RuleId::SacnUnicast
+            | RuleId::SacnNoSubscriber
+            | RuleId::SacnUniverseRate
+            | RuleId::SacnRate
+            | RuleId::SacnDiscovery => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+
+    /// Whether the rule is checked when the configuration does not mention it.
+    ///
+    /// Optional rules (sequence gaps) duplicate metrics already in the report
+    /// and must be enabled explicitly.
+    pub fn enabled_by_default(self) -> bool {
+        !matches!(
+            self,
+            RuleId::ArtNetSeqGap | RuleId::SacnSeqGap | RuleId::SacnUnicast
+        )
+    }
+
+    /// Message written into `Violation::message`.
+    pub fn message(self) -> &'static str {
+        match self {
+            RuleId::UdpSlice => "Invalid UDP slice; packet ignored",
+            RuleId::UdpMissingNetwork => {
+                "Invalid UDP packet: missing network layer; packet ignored"
+            }
+            RuleId::UdpMissingPayload => "Invalid UDP packet: missing IP payload; packet ignored",
+            RuleId::UdpTooShort => "Invalid UDP payload length; packet ignored",
+            RuleId::UdpBroadcastStorm => "Sustained broadcast traffic above the configured rate",
+            RuleId::UdpFragmented => "Fragmented UDP datagram; packet ignored",
+            RuleId::UdpOversized => "Lighting datagram exceeds the configured MTU",
+            RuleId::UdpIpChecksum => "Bad IPv4 header checksum; packet accepted",
+            RuleId::UdpChecksum => "Bad UDP checksum; packet accepted",
+            RuleId::CaptureTruncated => {
+                "Packet truncated by the capture snaplen; payload not decoded"
+            }
+            RuleId::ArtNetPort => "Non-standard Art-Net port (expected 6454); packet accepted",
+            RuleId::ArtNetMulticast => "ArtDMX sent to a multicast group; packet accepted",
+            RuleId::ArtNetUniverseRate => {
+                "Art-Net universe frame rate above the configured maximum"
+            }
+            RuleId::ArtNetUniverseId => "Invalid Art-Net universe id; packet ignored",
+            RuleId::ArtNetLength => "Invalid ArtDMX length; packet ignored",
+            RuleId::ArtNetTooShort => "Invalid Art-Net payload length; packet ignored",
+            RuleId::ArtNetOpcode => "Unsupported Art-Net opcode; packet ignored",
+            RuleId::ArtNetSeqGap => "Art-Net sequence gap; packets missing",
+            RuleId::ArtNetMinInterval => "ArtDMX sent faster than the minimum interval",
+            RuleId::ArtNetAddress => "ArtAddress sent: remote reconfiguration of a node",
+            RuleId::ArtNetProtVer => "Art-Net protocol revision older than Art-Net 4",
+            RuleId::SacnPort => "Non-standard sACN port (expected 5568); packet accepted",
+            RuleId::SacnStartCode => "Invalid sACN start code; packet ignored",
+            RuleId::SacnPropertyCount => "Invalid sACN property value count; packet ignored",
+            RuleId::SacnDmxLength => "Invalid sACN DMX data length; packet ignored",
+            RuleId::SacnTooShort => "Invalid sACN payload length; packet ignored",
+            RuleId::SacnAcnPid => "Invalid sACN ACN PID; packet ignored",
+            RuleId::SacnRootVector => "Invalid sACN root vector; packet ignored",
+            RuleId::SacnFramingVector => "Invalid sACN framing vector; packet ignored",
+            RuleId::SacnDmpVector => "Invalid sACN DMP vector; packet ignored",
+            RuleId::SacnSeqGap => "sACN sequence gap; packets missing",
+            RuleId::SacnSourceName => "Malformed sACN source name; packet accepted",
+            RuleId::SacnMulticastTtl => "sACN multicast TTL outside the accepted range",
+            RuleId::SacnPriority => "Reserved sACN priority (above 200); packet accepted",
+            RuleId::SacnPriorityChange => "sACN priority changed mid-stream",
+            RuleId::SacnCidMultiIp => "sACN CID sent from several IP addresses",
+            RuleId::SacnCidCollision => "sACN CID sent from several IP addresses at the same time",
+            RuleId::SacnBroadcast => "sACN sent to a broadcast address; packet accepted",
+            RuleId::SacnMulticastMismatch => "sACN multicast group does not match the universe",
+            RuleId::SacnUnicast => "sACN sent by unicast; packet accepted",
+            RuleId::SacnNoSubscriber => "sACN universe multicast with no IGMP subscriber",
+            RuleId::SacnUniverseRate => "sACN universe frame rate above the configured maximum",
+            RuleId::SacnRate => "sACN source refresh rate above the DMX512 maximum",
+            RuleId::SacnDiscovery => "sACN universe discovery does not match the universes sent",
+        }
+    }
+
+    /// One-line description of what the rule checks.
+    pub fn description(self) -> &'static str {
+        match self {
+            RuleId::UdpSlice => "Link, IP, or UDP headers could not be decoded.",
+            RuleId::UdpMissingNetwork => "Frame carries no IPv4/IPv6 network layer.",
+            RuleId::UdpMissingPayload => "IP packet carries no transport payload.",
+            RuleId::UdpTooShort => "UDP datagram is shorter than the 8-byte UDP header.",
+            RuleId::UdpBroadcastStorm => {
+                "A source broadcasts faster than `max_broadcast_pps` over `flow_rate_window_s`."
+            }
+            RuleId::UdpFragmented => "UDP datagram arrives as IPv4/IPv6 fragments.",
+            RuleId::UdpOversized => {
+                "IP datagram on port 6454 or 5568 is longer than `max_ip_datagram_len`."
+            }
+            RuleId::UdpIpChecksum => "IPv4 header checksum differs from the recomputed value.",
+            RuleId::UdpChecksum => {
+                "Non-zero UDP checksum differs from the recomputed value (IPv6: any checksum)."
+            }
+            RuleId::CaptureTruncated => {
+                "Captured length is shorter than the packet on the wire (capture snaplen); flows count it, its payload is not decoded."
+            }
+            RuleId::ArtNetPort => "ArtDMX neither sent from nor to UDP port 6454.",
+            RuleId::ArtNetMulticast => {
+                "ArtDMX destination is a multicast group (Art-Net is unicast or broadcast)."
+            }
+            RuleId::ArtNetUniverseRate => {
+                "Universe frame rate over `fps_window_s` exceeds `max_universe_fps`."
+            }
+            RuleId::ArtNetUniverseId => "Port-Address exceeds the 15-bit range (> 0x7FFF).",
+            RuleId::ArtNetLength => "ArtDMX length is odd or outside 2..=512.",
+            RuleId::ArtNetTooShort => {
+                "Payload is shorter than the ArtDMX header or its declared length."
+            }
+            RuleId::ArtNetOpcode => {
+                "Art-Net packet with an opcode other than OpDmx, OpNzs, OpSync, OpPollReply, OpTimeCode, OpTrigger, OpAddress, OpTodRequest, OpTodData, or OpRdm."
+            }
+            RuleId::ArtNetSeqGap => {
+                "ArtDMX sequence skips at least `min_sequence_gap` values for a source."
+            }
+            RuleId::ArtNetMinInterval => {
+                "Consecutive ArtDMX from a source for one universe closer than `artnet_min_interval_s`."
+            }
+            RuleId::ArtNetAddress => {
+                "ArtAddress (0x6000) reprograms a node's names, Port-Address, or behaviour remotely."
+            }
+            RuleId::ArtNetProtVer => {
+                "Art-Net packet whose ProtVer field is below 14, reported once per source and revision."
+            }
+            RuleId::SacnPort => "sACN neither sent from nor to UDP port 5568.",
+            RuleId::SacnStartCode => {
+                "DMX start code is neither 0x00 (null start code) nor 0xDD (per-address priority)."
+            }
+            RuleId::SacnPropertyCount => "DMP property value count is 0 or exceeds 513.",
+            RuleId::SacnDmxLength => "DMX data length cannot be represented for the payload.",
+            RuleId::SacnTooShort => "Payload is shorter than the root, framing, and DMP headers.",
+            RuleId::SacnAcnPid => "ACN packet identifier does not match \"ASC-E1.17\".",
+            RuleId::SacnRootVector => "Root layer vector is not VECTOR_ROOT_E131_DATA.",
+            RuleId::SacnFramingVector => "Framing layer vector is not VECTOR_E131_DATA_PACKET.",
+            RuleId::SacnDmpVector => "DMP layer vector is not VECTOR_DMP_SET_PROPERTY.",
+            RuleId::SacnSeqGap => {
+                "Sequence number skips at least `min_sequence_gap` values for a source."
+            }
+            RuleId::SacnSourceName => {
+                "Source name is empty, not null-terminated, or not valid UTF-8."
+            }
+            RuleId::SacnMulticastTtl => {
+                "Multicast TTL outside `min_multicast_ttl..=max_multicast_ttl` for a source."
+            }
+            RuleId::SacnPriority => "Data priority is above 200 (201..=255 are reserved).",
+            RuleId::SacnPriorityChange => {
+                "Priority differs from the previous packet of the same source and universe."
+            }
+            RuleId::SacnCidMultiIp => {
+                "The same CID appears with more than one source IP during the capture, never from two at once."
+            }
+            RuleId::SacnCidCollision => {
+                "The same CID is active from more than one source IP at once, usually a cloned console configuration."
+            }
+            RuleId::SacnBroadcast => {
+                "E1.31 data sent to a broadcast address instead of multicast or unicast."
+            }
+            RuleId::SacnUnicast => {
+                "E1.31 data sent to a single receiver instead of the universe's multicast group."
+            }
+            RuleId::SacnNoSubscriber => {
+                "Universe multicast to its IPv4 group while the capture's IGMP reports show no receiver joining it."
+            }
+            RuleId::SacnMulticastMismatch => {
+                "E1.31 data multicast to a group other than 239.255.H.L for its universe (ff18::8300:U over IPv6)."
+            }
+            RuleId::SacnUniverseRate => {
+                "Universe frame rate over `fps_window_s` exceeds `max_universe_fps`."
+            }
+            RuleId::SacnRate => {
+                "One source's refresh rate for a universe over `fps_window_s` exceeds `max_source_fps`."
+            }
+            RuleId::SacnDiscovery => {
+                "A source's universe discovery omits universes it sends or lists ones it never sends."
+            }
+        }
+    }
+
+    /// Reference into the protocol standard the rule enforces.
+    pub fn spec_reference(self) -> &'static str {
+        match self {
+            RuleId::UdpSlice | RuleId::UdpTooShort | RuleId::UdpChecksum => {
+                "RFC 768 (User Datagram Protocol)"
+            }
+            RuleId::UdpIpChecksum => "RFC 791, section 3.1 (Header Checksum)",
+            RuleId::CaptureTruncated => {
+                "PCAP / PCAPNG file format (SnapLen, Original Packet Length)"
+            }
+            RuleId::UdpBroadcastStorm => "RFC 919 (Broadcasting Internet Datagrams)",
+            RuleId::UdpMissingNetwork
+            | RuleId::UdpMissingPayload
+            | RuleId::UdpFragmented
+            | RuleId::UdpOversized => "RFC 791 / RFC 8200 (IPv4 / IPv6)",
+            RuleId::ArtNetPort => "Art-Net 4, UDP port 0x1936",
+            RuleId::ArtNetMulticast => "Art-Net 4, Protocol operation (unicast and broadcast)",
+            RuleId::ArtNetUniverseId => "Art-Net 4, Port-Address",
+            RuleId::ArtNetLength | RuleId::ArtNetTooShort => "Art-Net 4, ArtDmx packet definition",
+            RuleId::ArtNetOpcode => "Art-Net 4, OpCode table",
+            RuleId::ArtNetAddress => "Art-Net 4, ArtAddress packet definition",
+            RuleId::ArtNetProtVer => "Art-Net 4, ProtVerHi/ProtVerLo fields",
+            RuleId::ArtNetSeqGap => "Art-Net 4, ArtDmx Sequence field",
+            RuleId::ArtNetMinInterval | RuleId::ArtNetUniverseRate => {
+                "Art-Net 4, ArtDmx refresh rate (DMX512 maximum 44 Hz)"
+            }
+            RuleId::SacnPort => "ANSI E1.31-2018, Appendix A (ACN_SDT_MULTICAST_PORT)",
+            RuleId::SacnStartCode => "ANSI E1.31-2018, section 7.7 (Property Values)",
+            RuleId::SacnPropertyCount => "ANSI E1.31-2018, section 7.6 (Property Value Count)",
+            RuleId::SacnDmxLength | RuleId::SacnTooShort => {
+                "ANSI E1.31-2018, section 4.1 (E1.31 Data Packet)"
+            }
+            RuleId::SacnAcnPid => "ANSI E1.31-2018, section 5.3 (ACN Packet Identifier)",
+            RuleId::SacnRootVector => "ANSI E1.31-2018, section 5.5 (Vector)",
+            RuleId::SacnFramingVector => "ANSI E1.31-2018, section 6.2.1 (Vector)",
+            RuleId::SacnDmpVector => "ANSI E1.31-2018, section 7.2 (Vector)",
+            RuleId::SacnSeqGap => "ANSI E1.31-2018, section 6.7 (Sequence Numbering)",
+            RuleId::SacnSourceName => "ANSI E1.31-2018, section 6.2.2 (Source Name)",
+            RuleId::SacnMulticastTtl
+            | RuleId::SacnBroadcast
+            | RuleId::SacnMulticastMismatch
+            | RuleId::SacnUnicast => "ANSI E1.31-2018, section 9.3 (Multicast Addressing)",
+            RuleId::SacnNoSubscriber => "RFC 2236 / RFC 3376 (IGMPv2 / IGMPv3 group membership)",
+            RuleId::SacnUniverseRate | RuleId::SacnRate => {
+                "ANSI E1.11-2008 (DMX512-A), maximum refresh rate"
+            }
+            RuleId::SacnDiscovery => "ANSI E1.31-2018, section 8 (Universe Discovery Layer)",
+            RuleId::SacnCidMultiIp | RuleId::SacnCidCollision => {
+                "ANSI E1.31-2018, section 5.6 (CID: Component Identifier)"
+            }
+            RuleId::SacnPriority | RuleId::SacnPriorityChange => {
+                "ANSI E1.31-2018, section 6.2.3 (Priority)"
+            }
+        }
+    }
+}
+
+impl fmt::Display for RuleId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for RuleId {
+    type Err = UnknownRuleError;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        RuleId::ALL
+            .iter()
+            .copied()
+            .find(|rule| rule.code() == code)
+            .ok_or_else(|| UnknownRuleError {
+                code: code.to_string(),
+            })
+    }
+}
+
+impl From<RuleId> for &'static str {
+    fn from(rule: RuleId) -> Self {
+        rule.code()
+    }
+}
+
+impl TryFrom<String> for RuleId {
+    type Error = UnknownRuleError;
+
+    fn try_from(code: String) -> Result<Self, Self::Error> {
+        code.parse()
+    }
+}
+
+impl Violation {
+    /// Known rule for this violation, or `None` for codes this version does
+    /// not define.
+    ///
+    /// # Examples
+    /// ```
+    /// use liveshark_core::{RuleId, Severity, Violation};
+    ///
+    /// let violation = Violation {
+    ///     id: "LS-ARTNET-LENGTH".to_string(),
+    ///     severity: Severity::Error,
+    ///     message: "Invalid ArtDMX length; packet ignored".to_string(),
+    ///     count: 1,
+    ///     examples: Vec::new(),
+    ///     sources: Default::default(),
+    ///     spec_reference: None,
+    /// };
+    /// assert_eq!(violation.rule(), Some(RuleId::ArtNetLength));
+    /// ```
+    pub fn rule(&self) -> Option<RuleId> {
+        self.id.parse().ok()
+    }
+}
+
+/// Override for a single rule; unset fields keep the registry defaults.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{RuleSetting, Severity};
+///
+/// let setting: RuleSetting = serde_json::from_str(r#"{"severity": "warning"}"#).unwrap();
+/// assert_eq!(setting.severity, Some(Severity::Warning));
+/// assert_eq!(setting.enabled, None);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuleSetting {
+    /// Enable (`true`) or disable (`false`) the rule.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// Severity reported instead of the rule default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<Severity>,
+}
+
+/// Rule overrides applied during analysis.
+///
+/// Serializes as a map from rule code to [`RuleSetting`], for example
+/// `{"LS-ARTNET-PORT": {"enabled": false}}`. Rules without an entry use their
+/// registry defaults.
+///
+/// # Examples
+/// ```
+/// use liveshark_core::{RuleId, RuleSet, Severity};
+///
+/// let mut rules = RuleSet::default();
+/// rules.set_enabled(RuleId::ArtNetPort, false);
+/// rules.set_severity(RuleId::SacnPort, Severity::Error);
+///
+/// assert!(!rules.is_enabled(RuleId::ArtNetPort));
+/// assert_eq!(rules.severity(RuleId::SacnPort), Severity::Error);
+/// assert_eq!(rules.active().len(), RuleSet::default().active().len() - 1);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RuleSet {
+    overrides: BTreeMap<RuleId, RuleSetting>,
+}
+
+impl RuleSet {
+    /// Returns true when the rule is checked.
+    pub fn is_enabled(&self, rule: RuleId) -> bool {
+        self.overrides
+            .get(&rule)
+            .and_then(|setting| setting.enabled)
+            .unwrap_or_else(|| rule.enabled_by_default())
+    }
+
+    /// Severity reported for the rule.
+    pub fn severity(&self, rule: RuleId) -> Severity {
+        self.overrides
+            .get(&rule)
+            .and_then(|setting| setting.severity)
+            .unwrap_or_else(|| rule.default_severity())
+    }
+
+    /// Enable or disable a rule.
+    pub fn set_enabled(&mut self, rule: RuleId, enabled: bool) {
+        self.overrides.entry(rule).or_default().enabled = Some(enabled);
+    }
+
+    /// Override the severity of a rule.
+    pub fn set_severity(&mut self, rule: RuleId, severity: Severity) {
+        self.overrides.entry(rule).or_default().severity = Some(severity);
+    }
+
+    /// Override applied to a rule, if any.
+    pub fn setting(&self, rule: RuleId) -> Option<&RuleSetting> {
+        self.overrides.get(&rule)
+    }
+
+    /// Enabled rules with their effective severity, in code order.
+    pub fn active(&self) -> Vec<ActiveRule> {
+        RuleId::ALL
+            .iter()
+            .copied()
+            .filter(|rule| self.is_enabled(*rule))
+            .map(|rule| ActiveRule {
+                id: rule.code().to_string(),
+                severity: self.severity(rule),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RuleId, RuleSet};
+    use crate::Severity;
+
+    #[test]
+    fn codes_are_unique_sorted_and_round_trip() {
+        let codes: Vec<&str> = RuleId::ALL.iter().map(|rule| rule.code()).collect();
+        let mut sorted = codes.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(codes, sorted);
+        for rule in RuleId::ALL {
+            assert_eq!(rule.code().parse::<RuleId>().unwrap(), *rule);
+            // Capture rules are about the file, not a protocol; they are
+            // reported with UDP.
+            let prefix = match rule {
+                RuleId::CaptureTruncated => "CAPTURE".to_string(),
+                _ => rule.protocol().as_str().to_ascii_uppercase(),
+            };
+            assert!(rule.code().starts_with(&format!("LS-{prefix}-")));
+        }
+    }
+
+    #[test]
+    fn serializes_as_code() {
+        let json = serde_json::to_string(&RuleId::UdpSlice).unwrap();
+        assert_eq!(json, "\"LS-UDP-SLICE\"");
+        let parsed: RuleId = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, RuleId::UdpSlice);
+        assert!(serde_json::from_str::<RuleId>("\"LS-UNKNOWN\"").is_err());
+    }
+
+    #[test]
+    fn rule_set_parses_overrides_by_code() {
+        let rules: RuleSet = serde_json::from_str(
+            r#"{"LS-UDP-SLICE": {"enabled": false}, "LS-SACN-PORT": {"severity": "info"}}"#,
+        )
+        .unwrap();
+        assert!(!rules.is_enabled(RuleId::UdpSlice));
+        assert!(rules.is_enabled(RuleId::SacnPort));
+        assert_eq!(rules.severity(RuleId::SacnPort), Severity::Info);
+        assert_eq!(rules.severity(RuleId::UdpTooShort), Severity::Error);
+
+        assert!(serde_json::from_str::<RuleSet>(r#"{"LS-NOPE": {}}"#).is_err());
+        assert!(serde_json::from_str::<RuleSet>(r#"{"LS-UDP-SLICE": {"on": true}}"#).is_err());
+    }
+}
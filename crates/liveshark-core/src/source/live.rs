@@ -0,0 +1,279 @@
+//! Live capture from a network interface (Linux `AF_PACKET`).
+//!
+//! A raw packet socket bound to one interface receives every frame the
+//! interface sees, in both directions, with the interface in promiscuous mode
+//! so mirror-port traffic addressed to other hosts is kept. Timestamps are
+//! taken from the system clock when the frame is read.
+
+use std::ffi::CString;
+use std::io;
+use std::mem;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use pcap_parser::Linktype;
+
+use super::pool::BufferPool;
+use super::{PacketEvent, PacketSource, SourceError};
+use crate::analysis::udp::{IEEE802_11, IEEE802_11_RADIOTAP};
+
+/// Bytes kept per frame; longer frames are reported as truncated.
+const SNAPLEN: usize = 262_144;
+/// How long a read blocks before the deadline is checked again.
+const READ_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Packet source reading frames live from a network interface.
+///
+/// Without a deadline, `next_packet` blocks until a frame arrives and the
+/// stream never ends; [`LiveCaptureSource::stop_at`] ends it at a given
+/// instant, so a caller can capture in slices and analyze between them.
+/// Opening the socket needs the `CAP_NET_RAW` capability (or root).
+///
+/// # Examples
+/// ```no_run
+/// use liveshark_core::{LiveCaptureSource, PacketSource};
+/// use std::time::{Duration, Instant};
+///
+/// let mut source = LiveCaptureSource::open("eth0")?;
+/// source.stop_at(Some(Instant::now() + Duration::from_secs(1)));
+/// while let Some(packet) = source.next_packet()? {
+///     println!("{} bytes", packet.data.len());
+/// }
+/// # Ok::<(), liveshark_core::SourceError>(())
+/// ```
+pub struct LiveCaptureSource {
+    socket: OwnedFd,
+    interface: String,
+    linktype: Linktype,
+    loopback: bool,
+    deadline: Option<Instant>,
+    buffer: Vec<u8>,
+    pool: BufferPool,
+}
+
+impl LiveCaptureSource {
+    /// Open a capture on `interface` (for example `eth0`).
+    ///
+    /// # Errors
+    /// Returns `SourceError::Io` when the interface does not exist, its link
+    /// type is not supported, or the socket cannot be opened (usually a
+    /// missing `CAP_NET_RAW`).
+    pub fn open(interface: &str) -> Result<Self, SourceError> {
+        let name = CString::new(interface).map_err(|_| no_such_interface(interface))?;
+        // SAFETY: `name` is a valid NUL-terminated string.
+        let index = unsafe { libc::if_nametoindex(name.as_ptr()) };
+        if index == 0 {
+            return Err(no_such_interface(interface).into());
+        }
+        let protocol = (libc::ETH_P_ALL as u16).to_be();
+        // SAFETY: plain socket creation; the descriptor is owned below.
+        let fd = unsafe { libc::socket(libc::AF_PACKET, libc::SOCK_RAW, i32::from(protocol)) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error().into());
+        }
+        // SAFETY: `fd` is a freshly created descriptor nobody else owns.
+        let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+        let hardware = hardware_type(&socket, &name)?;
+        let linktype = linktype_of(hardware).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("unsupported link type {hardware} on interface {interface}"),
+            )
+        })?;
+
+        // SAFETY: all-zero is a valid `sockaddr_ll`.
+        let mut address: libc::sockaddr_ll = unsafe { mem::zeroed() };
+        address.sll_family = libc::AF_PACKET as u16;
+        address.sll_protocol = protocol;
+        address.sll_ifindex = index as i32;
+        // SAFETY: `address` is a valid `sockaddr_ll` of the given size.
+        let bound = unsafe {
+            libc::bind(
+                socket.as_raw_fd(),
+                (&address as *const libc::sockaddr_ll).cast(),
+                mem::size_of::<libc::sockaddr_ll>() as libc::socklen_t,
+            )
+        };
+        if bound < 0 {
+            return Err(io::Error::last_os_error().into());
+        }
+
+        // SAFETY: all-zero is a valid `packet_mreq`.
+        let mut membership: libc::packet_mreq = unsafe { mem::zeroed() };
+        membership.mr_ifindex = index as i32;
+        membership.mr_type = libc::PACKET_MR_PROMISC as u16;
+        set_option(
+            &socket,
+            libc::SOL_PACKET,
+            libc::PACKET_ADD_MEMBERSHIP,
+            &membership,
+        )?;
+        let timeout = libc::timeval {
+            tv_sec: 0,
+            tv_usec: READ_TIMEOUT.as_micros() as libc::suseconds_t,
+        };
+        set_option(&socket, libc::SOL_SOCKET, libc::SO_RCVTIMEO, &timeout)?;
+
+        Ok(Self {
+            socket,
+            interface: interface.to_string(),
+            linktype,
+            loopback: hardware == libc::ARPHRD_LOOPBACK,
+            deadline: None,
+            buffer: vec![0; SNAPLEN],
+            pool: BufferPool::new(),
+        })
+    }
+
+    /// Interface the source captures on.
+    pub fn interface(&self) -> &str {
+        &self.interface
+    }
+
+    /// End the stream at `deadline`: `next_packet` returns `None` once it has
+    /// passed. `None` captures until the process stops.
+    pub fn stop_at(&mut self, deadline: Option<Instant>) {
+        self.deadline = deadline;
+    }
+}
+
+impl PacketSource for LiveCaptureSource {
+    fn next_packet(&mut self) -> Result<Option<PacketEvent>, SourceError> {
+        loop {
+            if self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+            {
+                return Ok(None);
+            }
+            // SAFETY: all-zero is a valid `sockaddr_ll`.
+            let mut from: libc::sockaddr_ll = unsafe { mem::zeroed() };
+            let mut from_len = mem::size_of::<libc::sockaddr_ll>() as libc::socklen_t;
+            // SAFETY: `buffer` and `from` are valid for the lengths passed.
+            // MSG_TRUNC makes the call return the frame's full length.
+            let received = unsafe {
+                libc::recvfrom(
+                    self.socket.as_raw_fd(),
+                    self.buffer.as_mut_ptr().cast(),
+                    self.buffer.len(),
+                    libc::MSG_TRUNC,
+                    (&mut from as *mut libc::sockaddr_ll).cast(),
+                    &mut from_len,
+                )
+            };
+            if received < 0 {
+                let err = io::Error::last_os_error();
+                match err.kind() {
+                    io::ErrorKind::WouldBlock | io::ErrorKind::Interrupted => continue,
+                    _ => return Err(err.into()),
+                }
+            }
+            // Loopback frames are seen once outgoing and once incoming.
+            if self.loopback && from.sll_pkttype == libc::PACKET_OUTGOING {
+                continue;
+            }
+            let original = received as usize;
+            let captured = original.min(self.buffer.len());
+            let ts = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|elapsed| elapsed.as_secs_f64());
+            return Ok(Some(PacketEvent {
+                ts,
+                linktype: self.linktype,
+                data: self.pool.fill(&self.buffer[..captured]),
+                orig_len: Some(original),
+            }));
+        }
+    }
+
+    fn snaplen(&self) -> Option<u32> {
+        Some(SNAPLEN as u32)
+    }
+
+    fn recycle(&mut self, data: Vec<u8>) {
+        self.pool.release(data);
+    }
+}
+
+fn no_such_interface(interface: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("no network interface named {interface}"),
+    )
+}
+
+/// ARP hardware type (`ARPHRD_*`) of the interface called `name`.
+fn hardware_type(socket: &OwnedFd, name: &CString) -> io::Result<u16> {
+    // SAFETY: all-zero is a valid `ifreq`.
+    let mut request: libc::ifreq = unsafe { mem::zeroed() };
+    for (slot, byte) in request
+        .ifr_name
+        .iter_mut()
+        .zip(name.as_bytes().iter().take(libc::IFNAMSIZ - 1))
+    {
+        *slot = *byte as libc::c_char;
+    }
+    // SAFETY: SIOCGIFHWADDR reads the name and fills the hardware address of
+    // `request`, which outlives the call.
+    let result = unsafe { libc::ioctl(socket.as_raw_fd(), libc::SIOCGIFHWADDR, &mut request) };
+    if result < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: SIOCGIFHWADDR fills the `ifru_hwaddr` member.
+    Ok(unsafe { request.ifr_ifru.ifru_hwaddr.sa_family })
+}
+
+/// Link type of frames read from an interface of the given hardware type.
+fn linktype_of(hardware: u16) -> Option<Linktype> {
+    match hardware {
+        // Linux hands loopback frames over with a zeroed Ethernet header.
+        libc::ARPHRD_ETHER | libc::ARPHRD_LOOPBACK => Some(Linktype::ETHERNET),
+        libc::ARPHRD_NONE => Some(Linktype::RAW),
+        libc::ARPHRD_IEEE80211 => Some(IEEE802_11),
+        libc::ARPHRD_IEEE80211_RADIOTAP => Some(IEEE802_11_RADIOTAP),
+        _ => None,
+    }
+}
+
+fn set_option<T>(socket: &OwnedFd, level: i32, name: i32, value: &T) -> io::Result<()> {
+    // SAFETY: `value` is valid for `size_of::<T>()` bytes during the call.
+    let result = unsafe {
+        libc::setsockopt(
+            socket.as_raw_fd(),
+            level,
+            name,
+            (value as *const T).cast(),
+            mem::size_of::<T>() as libc::socklen_t,
+        )
+    };
+    if result < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_rejects_unknown_interfaces() {
+        let err = LiveCaptureSource::open("liveshark-none0")
+            .err()
+            .expect("unknown interface");
+        assert!(err.to_string().contains("liveshark-none0"));
+    }
+
+    #[test]
+    fn linktypes_follow_the_interface_hardware() {
+        assert_eq!(linktype_of(libc::ARPHRD_ETHER), Some(Linktype::ETHERNET));
+        assert_eq!(linktype_of(libc::ARPHRD_LOOPBACK), Some(Linktype::ETHERNET));
+        assert_eq!(linktype_of(libc::ARPHRD_NONE), Some(Linktype::RAW));
+        assert_eq!(
+            linktype_of(libc::ARPHRD_IEEE80211_RADIOTAP),
+            Some(IEEE802_11_RADIOTAP)
+        );
+        assert_eq!(linktype_of(libc::ARPHRD_INFINIBAND), None);
+    }
+}
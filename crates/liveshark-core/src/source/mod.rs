@@ -1,11 +1,19 @@
 //! Packet sources for analysis.
 //!
-//! Sources abstract capture inputs (pcap/pcapng today) and keep I/O separate
-//! from protocol parsing. A `PacketSource` yields raw packets in capture order
-//! with optional timestamps and linktype metadata.
+//! Sources abstract capture inputs (pcap/pcapng files, or a network interface
+//! on Linux) and keep I/O separate from protocol parsing. A `PacketSource`
+//! yields raw packets in capture order with optional timestamps and linktype
+//! metadata.
 //!
+#[cfg(target_os = "linux")]
+mod live;
+mod merge;
 mod pcap;
+mod pool;
 
+#[cfg(target_os = "linux")]
+pub use live::LiveCaptureSource;
+pub use merge::MergedSource;
 pub use pcap::PcapFileSource;
 
 use pcap_parser::Linktype;
@@ -22,6 +30,7 @@ use thiserror::Error;
 ///     ts: Some(1.0),
 ///     linktype: Linktype::ETHERNET,
 ///     data: vec![0xde, 0xad, 0xbe, 0xef],
+///     orig_len: None,
 /// };
 /// assert_eq!(event.data.len(), 4);
 /// ```
@@ -33,6 +42,9 @@ pub struct PacketEvent {
     pub linktype: Linktype,
     /// Packet bytes.
     pub data: Vec<u8>,
+    /// Length of the packet on the wire, when the source records it; longer
+    /// than `data` when the capture truncated the packet (snaplen).
+    pub orig_len: Option<usize>,
 }
 
 /// Abstract packet source for the analysis pipeline.
@@ -50,6 +62,7 @@ pub struct PacketEvent {
 ///             ts: Some(0.0),
 ///             linktype: Linktype::ETHERNET,
 ///             data: vec![0u8; 4],
+///             orig_len: None,
 ///         }))
 ///     }
 /// }
@@ -57,6 +70,20 @@ pub struct PacketEvent {
 pub trait PacketSource {
     /// Returns the next packet event, or `None` at end of stream.
     fn next_packet(&mut self) -> Result<Option<PacketEvent>, SourceError>;
+
+    /// Capture snapshot length: the most bytes recorded per packet, when the
+    /// source limits it. The default implementation reports none.
+    fn snaplen(&self) -> Option<u32> {
+        None
+    }
+
+    /// Hands a packet buffer back to the source once the caller is done with it.
+    ///
+    /// Sources that pool their buffers may reuse the allocation for a later
+    /// `next_packet` call. The default implementation drops the buffer.
+    fn recycle(&mut self, data: Vec<u8>) {
+        drop(data);
+    }
 }
 
 /// Errors produced by `PacketSource` implementations.
@@ -5,6 +5,7 @@ use pcap_parser::{
     Block, LegacyPcapReader, Linktype, PcapBlockOwned, PcapNGReader, traits::PcapReaderIterator,
 };
 
+use crate::source::pool::BufferPool;
 use crate::source::{PacketEvent, PacketSource, SourceError};
 
 use super::error::PcapSourceError;
@@ -15,6 +16,10 @@ use super::reader::{
 
 /// Packet source backed by a PCAP or PCAPNG file.
 ///
+/// By default every packet is copied into a freshly allocated buffer. Call
+/// [`PcapFileSource::with_buffer_pool`] to reuse buffers handed back through
+/// [`PacketSource::recycle`] instead.
+///
 /// # Examples
 /// ```no_run
 /// use liveshark_core::{PacketSource, PcapFileSource};
@@ -26,16 +31,19 @@ use super::reader::{
 /// ```
 pub struct PcapFileSource {
     inner: PcapReader,
+    pool: Option<BufferPool>,
 }
 
 enum PcapReader {
     Legacy {
         reader: LegacyPcapReader<File>,
         linktype: Option<Linktype>,
+        snaplen: Option<u32>,
     },
     Ng {
         reader: PcapNGReader<File>,
         linktypes: Vec<Linktype>,
+        snaplen: Option<u32>,
     },
 }
 
@@ -44,13 +52,47 @@ impl PcapFileSource {
     pub fn open(path: &Path) -> Result<Self, SourceError> {
         let file = File::open(path).map_err(SourceError::from)?;
         let inner = create_reader(file).map_err(SourceError::from)?;
-        Ok(Self { inner })
+        Ok(Self { inner, pool: None })
+    }
+
+    /// Enable recycled packet buffers.
+    ///
+    /// Buffers returned through [`PacketSource::recycle`] are reused for later
+    /// packets, avoiding a heap allocation per packet once the pool is warm.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use liveshark_core::{PacketSource, PcapFileSource};
+    /// use std::path::Path;
+    ///
+    /// let mut source = PcapFileSource::open(Path::new("capture.pcapng"))?.with_buffer_pool();
+    /// while let Some(event) = source.next_packet()? {
+    ///     // ... inspect event.data ...
+    ///     source.recycle(event.data);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn with_buffer_pool(mut self) -> Self {
+        self.pool.get_or_insert_with(BufferPool::new);
+        self
     }
 }
 
 impl PacketSource for PcapFileSource {
     fn next_packet(&mut self) -> Result<Option<PacketEvent>, SourceError> {
-        next_packet(&mut self.inner).map_err(SourceError::from)
+        next_packet(&mut self.inner, self.pool.as_mut()).map_err(SourceError::from)
+    }
+
+    fn recycle(&mut self, data: Vec<u8>) {
+        if let Some(pool) = self.pool.as_mut() {
+            pool.release(data);
+        }
+    }
+
+    fn snaplen(&self) -> Option<u32> {
+        match self.inner {
+            PcapReader::Legacy { snaplen, .. } | PcapReader::Ng { snaplen, .. } => snaplen,
+        }
     }
 }
 
@@ -68,6 +110,7 @@ fn create_reader(file: File) -> Result<PcapReader, PcapSourceError> {
         Ok(PcapReader::Ng {
             reader,
             linktypes: Vec::new(),
+            snaplen: None,
         })
     } else {
         let reader = LegacyPcapReader::new(layout::PCAP_READER_BUFFER_SIZE, file).map_err(|e| {
@@ -79,18 +122,44 @@ fn create_reader(file: File) -> Result<PcapReader, PcapSourceError> {
         Ok(PcapReader::Legacy {
             reader,
             linktype: None,
+            snaplen: None,
         })
     }
 }
 
-fn next_packet(reader: &mut PcapReader) -> Result<Option<PacketEvent>, PcapSourceError> {
+fn copy_packet_data(pool: Option<&mut BufferPool>, bytes: &[u8]) -> Vec<u8> {
+    match pool {
+        Some(pool) => pool.fill(bytes),
+        None => bytes.to_vec(),
+    }
+}
+
+/// Interfaces of one file may differ; the smallest limit is the one that
+/// truncates packets. Zero means no limit.
+fn smallest_snaplen(current: Option<u32>, snaplen: u32) -> Option<u32> {
+    match (current, snaplen) {
+        (current, 0) => current,
+        (Some(current), snaplen) => Some(current.min(snaplen)),
+        (None, snaplen) => Some(snaplen),
+    }
+}
+
+fn next_packet(
+    reader: &mut PcapReader,
+    mut pool: Option<&mut BufferPool>,
+) -> Result<Option<PacketEvent>, PcapSourceError> {
     loop {
         match reader {
-            PcapReader::Legacy { reader, linktype } => match reader.next() {
+            PcapReader::Legacy {
+                reader,
+                linktype,
+                snaplen,
+            } => match reader.next() {
                 Ok((offset, block)) => {
                     let event = match block {
                         PcapBlockOwned::LegacyHeader(header) => {
                             *linktype = Some(header.network);
+                            *snaplen = Some(header.snaplen).filter(|len| *len > 0);
                             None
                         }
                         PcapBlockOwned::Legacy(packet) => {
@@ -99,7 +168,8 @@ fn next_packet(reader: &mut PcapReader) -> Result<Option<PacketEvent>, PcapSourc
                             Some(PacketEvent {
                                 ts: Some(ts),
                                 linktype: lt,
-                                data: packet.data.to_vec(),
+                                data: copy_packet_data(pool.as_deref_mut(), packet.data),
+                                orig_len: Some(packet.origlen as usize),
                             })
                         }
                         _ => None,
@@ -123,11 +193,16 @@ fn next_packet(reader: &mut PcapReader) -> Result<Option<PacketEvent>, PcapSourc
                     });
                 }
             },
-            PcapReader::Ng { reader, linktypes } => match reader.next() {
+            PcapReader::Ng {
+                reader,
+                linktypes,
+                snaplen,
+            } => match reader.next() {
                 Ok((offset, block)) => {
                     let event = match block {
                         PcapBlockOwned::NG(Block::InterfaceDescription(intf)) => {
                             linktypes.push(intf.linktype);
+                            *snaplen = smallest_snaplen(*snaplen, intf.snaplen);
                             None
                         }
                         PcapBlockOwned::NG(Block::EnhancedPacket(packet)) => {
@@ -136,7 +211,8 @@ fn next_packet(reader: &mut PcapReader) -> Result<Option<PacketEvent>, PcapSourc
                             Some(PacketEvent {
                                 ts: Some(ts),
                                 linktype: lt,
-                                data: packet.data.to_vec(),
+                                data: copy_packet_data(pool.as_deref_mut(), packet.data),
+                                orig_len: Some(packet.origlen as usize),
                             })
                         }
                         _ => None,
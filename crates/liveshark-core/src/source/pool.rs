@@ -0,0 +1,80 @@
+//! Recycled packet buffers.
+//!
+//! A `BufferPool` keeps a bounded stack of cleared `Vec<u8>` buffers so packet
+//! sources can avoid a heap allocation per packet once the pool is warm.
+
+/// Maximum number of idle buffers retained by a pool.
+pub const BUFFER_POOL_CAPACITY: usize = 32;
+
+/// Bounded pool of reusable packet buffers.
+///
+/// # Examples
+/// Note: this type lives in an internal module; the example is illustrative
+/// and not compiled as a public doctest.
+/// ```text
+/// let mut pool = BufferPool::new();
+/// let buf = pool.fill(&[1, 2, 3]);
+/// assert_eq!(buf, vec![1, 2, 3]);
+/// pool.release(buf);
+/// ```
+#[derive(Debug, Default)]
+pub struct BufferPool {
+    buffers: Vec<Vec<u8>>,
+}
+
+impl BufferPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copy `bytes` into a pooled buffer, allocating only when the pool is empty.
+    pub fn fill(&mut self, bytes: &[u8]) -> Vec<u8> {
+        match self.buffers.pop() {
+            Some(mut buf) => {
+                buf.clear();
+                buf.extend_from_slice(bytes);
+                buf
+            }
+            None => bytes.to_vec(),
+        }
+    }
+
+    /// Return a buffer to the pool; extra buffers beyond the capacity are dropped.
+    pub fn release(&mut self, buf: Vec<u8>) {
+        if self.buffers.len() < BUFFER_POOL_CAPACITY {
+            self.buffers.push(buf);
+        }
+    }
+
+    /// Number of idle buffers currently held.
+    #[cfg(test)]
+    pub fn idle(&self) -> usize {
+        self.buffers.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BUFFER_POOL_CAPACITY, BufferPool};
+
+    #[test]
+    fn fill_reuses_released_allocation() {
+        let mut pool = BufferPool::new();
+        let first = pool.fill(&[1, 2, 3, 4]);
+        let ptr = first.as_ptr();
+        pool.release(first);
+        let second = pool.fill(&[9, 8]);
+        assert_eq!(second, vec![9, 8]);
+        assert_eq!(second.as_ptr(), ptr);
+        assert_eq!(pool.idle(), 0);
+    }
+
+    #[test]
+    fn release_is_bounded() {
+        let mut pool = BufferPool::new();
+        for _ in 0..BUFFER_POOL_CAPACITY + 5 {
+            pool.release(Vec::with_capacity(16));
+        }
+        assert_eq!(pool.idle(), BUFFER_POOL_CAPACITY);
+    }
+}
@@ -0,0 +1,1137 @@
+//! Protocol payload builders for tests (`test-util` feature).
+//!
+//! The builders write UDP payloads byte for byte from the protocol
+//! `layout` modules, so tests can produce Art-Net, sACN, and PSN traffic without
+//! repeating offsets. Defaults build valid packets; the setters marked as
+//! invalid ones produce packets the parsers reject, for compliance tests.
+//! [`udp_event`] wraps a payload in a captured frame, and [`MemorySource`]
+//! feeds such frames to the analysis.
+//!
+//! Valid builders can also be generated, with [`arbitrary::Arbitrary`] for
+//! fuzzers and [`proptest::arbitrary::Arbitrary`] for property tests.
+//!
+//! # Examples
+//! ```
+//! use liveshark_core::parse_artdmx;
+//! use liveshark_core::test_util::ArtDmxBuilder;
+//!
+//! let payload = ArtDmxBuilder::new(3).sequence(7).slots(vec![255, 0]).build();
+//! let frame = parse_artdmx(&payload)?.expect("artdmx");
+//! assert_eq!(frame.universe, 3);
+//! assert_eq!(frame.slots, vec![255, 0]);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::collections::VecDeque;
+
+use arbitrary::Unstructured;
+use etherparse::PacketBuilder;
+use pcap_parser::Linktype;
+use proptest::prelude::{BoxedStrategy, Strategy, any};
+
+use crate::protocols::artnet::layout as artnet_layout;
+use crate::protocols::psn::layout as psn_layout;
+use crate::protocols::sacn::layout as sacn_layout;
+use crate::{PacketEvent, PacketSource, RdmUid, SourceError};
+
+/// Protocol revision sent by Art-Net 4 devices.
+const ARTNET_PROTOCOL_VERSION: u16 = 14;
+/// Highest sACN data universe.
+const SACN_UNIVERSE_MAX: u16 = 63999;
+
+// sACN fields the parsers do not read, written so captures of built
+// packets decode cleanly in other tools.
+const SACN_ROOT_FLAGS_LENGTH_OFFSET: usize = 16;
+const SACN_FRAMING_FLAGS_LENGTH_OFFSET: usize = 38;
+const SACN_DMP_FLAGS_LENGTH_OFFSET: usize = 115;
+/// High nibble of every PDU's flags and length field.
+const SACN_PDU_FLAGS: u16 = 0x7000;
+/// DMP address and data type of DMX data (relative, 1-byte properties).
+const SACN_DMP_ADDRESS_TYPE_DMX: u8 = 0xa1;
+
+/// Builder of ArtDmx payloads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtDmxBuilder {
+    universe: u16,
+    sequence: u8,
+    physical: u8,
+    protocol_version: u16,
+    slots: Vec<u8>,
+    opcode: u16,
+    length: Option<u16>,
+}
+
+impl ArtDmxBuilder {
+    /// ArtDmx for the 15-bit Port-Address `universe`, with sequencing
+    /// disabled and 512 zero slots.
+    pub fn new(universe: u16) -> Self {
+        Self {
+            universe,
+            sequence: 0,
+            physical: 0,
+            protocol_version: ARTNET_PROTOCOL_VERSION,
+            slots: vec![0; artnet_layout::DMX_MAX_SLOTS],
+            opcode: artnet_layout::ARTDMX_OPCODE,
+            length: None,
+        }
+    }
+
+    /// Sequence number; 0 disables sequencing.
+    pub fn sequence(mut self, sequence: u8) -> Self {
+        self.sequence = sequence;
+        self
+    }
+
+    /// Physical input port.
+    pub fn physical(mut self, physical: u8) -> Self {
+        self.physical = physical;
+        self
+    }
+
+    /// Protocol revision (`ProtVerHi`/`ProtVerLo`).
+    pub fn protocol_version(mut self, version: u16) -> Self {
+        self.protocol_version = version;
+        self
+    }
+
+    /// Slot values; an odd count or more than 512 slots is invalid.
+    pub fn slots(mut self, slots: impl Into<Vec<u8>>) -> Self {
+        self.slots = slots.into();
+        self
+    }
+
+    /// Opcode (invalid unless OpDmx).
+    pub fn opcode(mut self, opcode: u16) -> Self {
+        self.opcode = opcode;
+        self
+    }
+
+    /// Length field, instead of the slot count (invalid when they differ).
+    pub fn length(mut self, length: u16) -> Self {
+        self.length = Some(length);
+        self
+    }
+
+    /// UDP payload.
+    pub fn build(&self) -> Vec<u8> {
+        let mut payload = vec![0u8; artnet_layout::DMX_DATA_OFFSET + self.slots.len()];
+        payload[..artnet_layout::ARTNET_ID.len()].copy_from_slice(artnet_layout::ARTNET_ID);
+        payload[artnet_layout::OP_CODE_RANGE.clone()].copy_from_slice(&self.opcode.to_le_bytes());
+        payload[artnet_layout::PROTOCOL_VERSION_RANGE.clone()]
+            .copy_from_slice(&self.protocol_version.to_be_bytes());
+        payload[artnet_layout::SEQUENCE_OFFSET] = self.sequence;
+        payload[artnet_layout::PHYSICAL_OFFSET] = self.physical;
+        payload[artnet_layout::UNIVERSE_RANGE.clone()]
+            .copy_from_slice(&self.universe.to_le_bytes());
+        let length = self.length.unwrap_or(self.slots.len() as u16);
+        payload[artnet_layout::LENGTH_RANGE.clone()].copy_from_slice(&length.to_be_bytes());
+        payload[artnet_layout::DMX_DATA_OFFSET..].copy_from_slice(&self.slots);
+        payload
+    }
+}
+
+/// Builder of ArtNzs payloads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtNzsBuilder {
+    universe: u16,
+    sequence: u8,
+    start_code: u8,
+    data: Vec<u8>,
+}
+
+impl ArtNzsBuilder {
+    /// ArtNzs for the 15-bit Port-Address `universe`, with sequencing
+    /// disabled and one byte of text data (start code `0x17`).
+    pub fn new(universe: u16) -> Self {
+        Self {
+            universe,
+            sequence: 0,
+            start_code: 0x17,
+            data: vec![0],
+        }
+    }
+
+    /// Sequence number; 0 disables sequencing.
+    pub fn sequence(mut self, sequence: u8) -> Self {
+        self.sequence = sequence;
+        self
+    }
+
+    /// Start code of the data.
+    pub fn start_code(mut self, start_code: u8) -> Self {
+        self.start_code = start_code;
+        self
+    }
+
+    /// Data after the start code; empty or more than 512 bytes is invalid.
+    pub fn data(mut self, data: impl Into<Vec<u8>>) -> Self {
+        self.data = data.into();
+        self
+    }
+
+    /// UDP payload.
+    pub fn build(&self) -> Vec<u8> {
+        let mut payload = vec![0u8; artnet_layout::DMX_DATA_OFFSET + self.data.len()];
+        payload[..artnet_layout::ARTNET_ID.len()].copy_from_slice(artnet_layout::ARTNET_ID);
+        payload[artnet_layout::OP_CODE_RANGE.clone()]
+            .copy_from_slice(&artnet_layout::ARTNZS_OPCODE.to_le_bytes());
+        payload[artnet_layout::PROTOCOL_VERSION_RANGE.clone()]
+            .copy_from_slice(&ARTNET_PROTOCOL_VERSION.to_be_bytes());
+        payload[artnet_layout::SEQUENCE_OFFSET] = self.sequence;
+        payload[artnet_layout::NZS_START_CODE_OFFSET] = self.start_code;
+        payload[artnet_layout::UNIVERSE_RANGE.clone()]
+            .copy_from_slice(&self.universe.to_le_bytes());
+        payload[artnet_layout::LENGTH_RANGE.clone()]
+            .copy_from_slice(&(self.data.len() as u16).to_be_bytes());
+        payload[artnet_layout::DMX_DATA_OFFSET..].copy_from_slice(&self.data);
+        payload
+    }
+}
+
+/// Builder of ArtSync payloads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtSyncBuilder {
+    protocol_version: u16,
+}
+
+impl ArtSyncBuilder {
+    /// ArtSync of an Art-Net 4 controller.
+    pub fn new() -> Self {
+        Self {
+            protocol_version: ARTNET_PROTOCOL_VERSION,
+        }
+    }
+
+    /// Protocol revision (`ProtVerHi`/`ProtVerLo`).
+    pub fn protocol_version(mut self, version: u16) -> Self {
+        self.protocol_version = version;
+        self
+    }
+
+    /// UDP payload.
+    pub fn build(&self) -> Vec<u8> {
+        let mut payload = vec![0u8; artnet_layout::ARTSYNC_LEN];
+        payload[..artnet_layout::ARTNET_ID.len()].copy_from_slice(artnet_layout::ARTNET_ID);
+        payload[artnet_layout::OP_CODE_RANGE.clone()]
+            .copy_from_slice(&artnet_layout::ARTSYNC_OPCODE.to_le_bytes());
+        payload[artnet_layout::PROTOCOL_VERSION_RANGE.clone()]
+            .copy_from_slice(&self.protocol_version.to_be_bytes());
+        payload
+    }
+}
+
+impl Default for ArtSyncBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builder of ArtTimeCode payloads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtTimeCodeBuilder {
+    stream_id: u8,
+    hours: u8,
+    minutes: u8,
+    seconds: u8,
+    frames: u8,
+    timecode_type: u8,
+}
+
+impl ArtTimeCodeBuilder {
+    /// EBU (25 fps) timecode `hours:minutes:seconds:frames` on the master
+    /// stream.
+    pub fn new(hours: u8, minutes: u8, seconds: u8, frames: u8) -> Self {
+        Self {
+            stream_id: 0,
+            hours,
+            minutes,
+            seconds,
+            frames,
+            timecode_type: 1,
+        }
+    }
+
+    /// Stream identifier; 0 is the master stream.
+    pub fn stream_id(mut self, stream_id: u8) -> Self {
+        self.stream_id = stream_id;
+        self
+    }
+
+    /// Timecode type: 0 Film, 1 EBU, 2 DF, 3 SMPTE.
+    pub fn timecode_type(mut self, timecode_type: u8) -> Self {
+        self.timecode_type = timecode_type;
+        self
+    }
+
+    /// UDP payload.
+    pub fn build(&self) -> Vec<u8> {
+        let mut payload = vec![0u8; artnet_layout::ARTTIMECODE_LEN];
+        payload[..artnet_layout::ARTNET_ID.len()].copy_from_slice(artnet_layout::ARTNET_ID);
+        payload[artnet_layout::OP_CODE_RANGE.clone()]
+            .copy_from_slice(&artnet_layout::ARTTIMECODE_OPCODE.to_le_bytes());
+        payload[artnet_layout::PROTOCOL_VERSION_RANGE.clone()]
+            .copy_from_slice(&ARTNET_PROTOCOL_VERSION.to_be_bytes());
+        payload[artnet_layout::TIMECODE_STREAM_ID_OFFSET] = self.stream_id;
+        payload[artnet_layout::TIMECODE_FRAMES_OFFSET] = self.frames;
+        payload[artnet_layout::TIMECODE_SECONDS_OFFSET] = self.seconds;
+        payload[artnet_layout::TIMECODE_MINUTES_OFFSET] = self.minutes;
+        payload[artnet_layout::TIMECODE_HOURS_OFFSET] = self.hours;
+        payload[artnet_layout::TIMECODE_TYPE_OFFSET] = self.timecode_type;
+        payload
+    }
+}
+
+/// Builder of ArtTrigger payloads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtTriggerBuilder {
+    oem: u16,
+    key: u8,
+    sub_key: u8,
+}
+
+impl ArtTriggerBuilder {
+    /// General (OEM `0xffff`) trigger `key` with `sub_key`.
+    pub fn new(key: u8, sub_key: u8) -> Self {
+        Self {
+            oem: 0xffff,
+            key,
+            sub_key,
+        }
+    }
+
+    /// OEM code the trigger is meant for.
+    pub fn oem(mut self, oem: u16) -> Self {
+        self.oem = oem;
+        self
+    }
+
+    /// UDP payload, with 512 zero data bytes.
+    pub fn build(&self) -> Vec<u8> {
+        let mut payload =
+            vec![0u8; artnet_layout::ARTTRIGGER_MIN_LEN + artnet_layout::DMX_MAX_SLOTS];
+        payload[..artnet_layout::ARTNET_ID.len()].copy_from_slice(artnet_layout::ARTNET_ID);
+        payload[artnet_layout::OP_CODE_RANGE.clone()]
+            .copy_from_slice(&artnet_layout::ARTTRIGGER_OPCODE.to_le_bytes());
+        payload[artnet_layout::PROTOCOL_VERSION_RANGE.clone()]
+            .copy_from_slice(&ARTNET_PROTOCOL_VERSION.to_be_bytes());
+        payload[artnet_layout::TRIGGER_OEM_RANGE.clone()].copy_from_slice(&self.oem.to_be_bytes());
+        payload[artnet_layout::TRIGGER_KEY_OFFSET] = self.key;
+        payload[artnet_layout::TRIGGER_SUB_KEY_OFFSET] = self.sub_key;
+        payload
+    }
+}
+
+/// Builder of ArtAddress payloads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtAddressBuilder {
+    bind_index: u8,
+    net_switch: u8,
+    sub_switch: u8,
+    short_name: String,
+    command: u8,
+}
+
+impl ArtAddressBuilder {
+    /// ArtAddress to the root device that changes nothing (every switch
+    /// `0x7f`, empty names, no command).
+    pub fn new() -> Self {
+        Self {
+            bind_index: 0,
+            net_switch: 0x7f,
+            sub_switch: 0x7f,
+            short_name: String::new(),
+            command: 0,
+        }
+    }
+
+    /// Bind index of the addressed ports.
+    pub fn bind_index(mut self, bind_index: u8) -> Self {
+        self.bind_index = bind_index;
+        self
+    }
+
+    /// Raw `Net` switch value (bit 7 programs it).
+    pub fn net_switch(mut self, net_switch: u8) -> Self {
+        self.net_switch = net_switch;
+        self
+    }
+
+    /// Raw `Sub-Net` switch value (bit 7 programs it).
+    pub fn sub_switch(mut self, sub_switch: u8) -> Self {
+        self.sub_switch = sub_switch;
+        self
+    }
+
+    /// New short name (truncated to 17 bytes).
+    pub fn short_name(mut self, name: &str) -> Self {
+        self.short_name = name.to_string();
+        self
+    }
+
+    /// Node command.
+    pub fn command(mut self, command: u8) -> Self {
+        self.command = command;
+        self
+    }
+
+    /// UDP payload.
+    pub fn build(&self) -> Vec<u8> {
+        let mut payload = vec![0u8; artnet_layout::ARTADDRESS_LEN];
+        payload[..artnet_layout::ARTNET_ID.len()].copy_from_slice(artnet_layout::ARTNET_ID);
+        payload[artnet_layout::OP_CODE_RANGE.clone()]
+            .copy_from_slice(&artnet_layout::ARTADDRESS_OPCODE.to_le_bytes());
+        payload[artnet_layout::PROTOCOL_VERSION_RANGE.clone()]
+            .copy_from_slice(&ARTNET_PROTOCOL_VERSION.to_be_bytes());
+        payload[artnet_layout::ADDRESS_NET_SWITCH_OFFSET] = self.net_switch;
+        payload[artnet_layout::ADDRESS_BIND_INDEX_OFFSET] = self.bind_index;
+        let name = self.short_name.as_bytes();
+        let len = name
+            .len()
+            .min(artnet_layout::ADDRESS_SHORT_NAME_RANGE.len() - 1);
+        let start = artnet_layout::ADDRESS_SHORT_NAME_RANGE.start;
+        payload[start..start + len].copy_from_slice(&name[..len]);
+        payload[artnet_layout::ADDRESS_SW_IN_RANGE.clone()].fill(0x7f);
+        payload[artnet_layout::ADDRESS_SW_OUT_RANGE.clone()].fill(0x7f);
+        payload[artnet_layout::ADDRESS_SUB_SWITCH_OFFSET] = self.sub_switch;
+        payload[artnet_layout::ADDRESS_COMMAND_OFFSET] = self.command;
+        payload
+    }
+}
+
+impl Default for ArtAddressBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn artnet_header(payload: &mut [u8], opcode: u16) {
+    payload[..artnet_layout::ARTNET_ID.len()].copy_from_slice(artnet_layout::ARTNET_ID);
+    payload[artnet_layout::OP_CODE_RANGE.clone()].copy_from_slice(&opcode.to_le_bytes());
+    payload[artnet_layout::PROTOCOL_VERSION_RANGE.clone()]
+        .copy_from_slice(&ARTNET_PROTOCOL_VERSION.to_be_bytes());
+}
+
+fn write_port_address(payload: &mut [u8], universe: u16) {
+    let [net, address] = universe.to_be_bytes();
+    payload[artnet_layout::RDM_NET_OFFSET] = net;
+    payload[artnet_layout::RDM_ADDRESS_OFFSET] = address;
+}
+
+fn uid_bytes(uid: RdmUid) -> [u8; artnet_layout::RDM_UID_LEN] {
+    let mut bytes = [0u8; artnet_layout::RDM_UID_LEN];
+    bytes[..2].copy_from_slice(&uid.manufacturer.to_be_bytes());
+    bytes[2..].copy_from_slice(&uid.device.to_be_bytes());
+    bytes
+}
+
+/// Builder of ArtTodRequest payloads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtTodRequestBuilder {
+    net: u8,
+    addresses: Vec<u8>,
+}
+
+impl ArtTodRequestBuilder {
+    /// Full table request for the given Sub-Net/Universe bytes of `net`.
+    pub fn new(net: u8, addresses: Vec<u8>) -> Self {
+        Self { net, addresses }
+    }
+
+    /// UDP payload.
+    pub fn build(&self) -> Vec<u8> {
+        let mut payload =
+            vec![0u8; artnet_layout::TOD_REQUEST_ADDRESS_OFFSET + self.addresses.len()];
+        artnet_header(&mut payload, artnet_layout::ARTTODREQUEST_OPCODE);
+        payload[artnet_layout::RDM_NET_OFFSET] = self.net;
+        payload[artnet_layout::TOD_REQUEST_AD_COUNT_OFFSET] = self.addresses.len() as u8;
+        payload[artnet_layout::TOD_REQUEST_ADDRESS_OFFSET..].copy_from_slice(&self.addresses);
+        payload
+    }
+}
+
+/// Builder of ArtTodData payloads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtTodDataBuilder {
+    universe: u16,
+    port: u8,
+    bind_index: u8,
+    uid_total: Option<u16>,
+    block_count: u8,
+    uids: Vec<RdmUid>,
+}
+
+impl ArtTodDataBuilder {
+    /// Single-block table of port 1 on `universe` listing `uids`.
+    pub fn new(universe: u16, uids: Vec<RdmUid>) -> Self {
+        Self {
+            universe,
+            port: 1,
+            bind_index: 1,
+            uid_total: None,
+            block_count: 0,
+            uids,
+        }
+    }
+
+    /// Physical port of the node.
+    pub fn port(mut self, port: u8) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Bind index of the port.
+    pub fn bind_index(mut self, bind_index: u8) -> Self {
+        self.bind_index = bind_index;
+        self
+    }
+
+    /// UIDs in the full table (defaults to the UIDs of this block).
+    pub fn uid_total(mut self, uid_total: u16) -> Self {
+        self.uid_total = Some(uid_total);
+        self
+    }
+
+    /// Index of this block.
+    pub fn block_count(mut self, block_count: u8) -> Self {
+        self.block_count = block_count;
+        self
+    }
+
+    /// UDP payload.
+    pub fn build(&self) -> Vec<u8> {
+        let mut payload = vec![
+            0u8;
+            artnet_layout::TOD_UIDS_OFFSET
+                + self.uids.len() * artnet_layout::RDM_UID_LEN
+        ];
+        artnet_header(&mut payload, artnet_layout::ARTTODDATA_OPCODE);
+        payload[artnet_layout::RDM_VERSION_OFFSET] = 1;
+        payload[artnet_layout::TOD_PORT_OFFSET] = self.port;
+        payload[artnet_layout::TOD_BIND_INDEX_OFFSET] = self.bind_index;
+        write_port_address(&mut payload, self.universe);
+        let total = self.uid_total.unwrap_or(self.uids.len() as u16);
+        payload[artnet_layout::TOD_UID_TOTAL_RANGE.clone()].copy_from_slice(&total.to_be_bytes());
+        payload[artnet_layout::TOD_BLOCK_COUNT_OFFSET] = self.block_count;
+        payload[artnet_layout::TOD_UID_COUNT_OFFSET] = self.uids.len() as u8;
+        for (index, uid) in self.uids.iter().enumerate() {
+            let start = artnet_layout::TOD_UIDS_OFFSET + index * artnet_layout::RDM_UID_LEN;
+            payload[start..start + artnet_layout::RDM_UID_LEN].copy_from_slice(&uid_bytes(*uid));
+        }
+        payload
+    }
+}
+
+/// Builder of ArtRdm payloads (Art-Net 4 form, without the RDM start code).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtRdmBuilder {
+    universe: u16,
+    source: RdmUid,
+    destination: RdmUid,
+    transaction: u8,
+    port_or_response: u8,
+    command_class: u8,
+    parameter_id: u16,
+}
+
+impl ArtRdmBuilder {
+    /// Message of `command_class` from `source` to `destination` on
+    /// `universe`: transaction 0, `DEVICE_INFO`, port 1 or `ACK`.
+    pub fn new(universe: u16, source: RdmUid, destination: RdmUid, command_class: u8) -> Self {
+        Self {
+            universe,
+            source,
+            destination,
+            transaction: 0,
+            port_or_response: if command_class & 0x01 != 0 { 0 } else { 1 },
+            command_class,
+            parameter_id: 0x0060,
+        }
+    }
+
+    /// Transaction number.
+    pub fn transaction(mut self, transaction: u8) -> Self {
+        self.transaction = transaction;
+        self
+    }
+
+    /// Response type of a response (`ACK` 0, `ACK_TIMER` 1, `NACK_REASON` 2).
+    pub fn response_type(mut self, response_type: u8) -> Self {
+        self.port_or_response = response_type;
+        self
+    }
+
+    /// Parameter ID.
+    pub fn parameter_id(mut self, parameter_id: u16) -> Self {
+        self.parameter_id = parameter_id;
+        self
+    }
+
+    /// UDP payload.
+    pub fn build(&self) -> Vec<u8> {
+        let mut payload =
+            vec![0u8; artnet_layout::RDM_PACKET_OFFSET + artnet_layout::RDM_HEADER_LEN];
+        artnet_header(&mut payload, artnet_layout::ARTRDM_OPCODE);
+        payload[artnet_layout::RDM_VERSION_OFFSET] = 1;
+        write_port_address(&mut payload, self.universe);
+        let message = &mut payload[artnet_layout::RDM_PACKET_OFFSET..];
+        // Sub start code and message length (with the start code and checksum).
+        message[0] = 0x01;
+        message[1] = (artnet_layout::RDM_HEADER_LEN + 1) as u8;
+        message[artnet_layout::RDM_DESTINATION_RANGE.clone()]
+            .copy_from_slice(&uid_bytes(self.destination));
+        message[artnet_layout::RDM_SOURCE_RANGE.clone()].copy_from_slice(&uid_bytes(self.source));
+        message[artnet_layout::RDM_TRANSACTION_OFFSET] = self.transaction;
+        message[artnet_layout::RDM_PORT_OR_RESPONSE_OFFSET] = self.port_or_response;
+        message[artnet_layout::RDM_COMMAND_CLASS_OFFSET] = self.command_class;
+        message[artnet_layout::RDM_PID_RANGE.clone()]
+            .copy_from_slice(&self.parameter_id.to_be_bytes());
+        payload
+    }
+}
+
+/// Builder of sACN data (DMX) payloads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SacnDataBuilder {
+    universe: u16,
+    cid: [u8; 16],
+    source_name: String,
+    priority: u8,
+    sync_address: u16,
+    sequence: u8,
+    options: u8,
+    start_code: u8,
+    slots: Vec<u8>,
+    root_vector: u32,
+    framing_vector: u32,
+    property_count: Option<u16>,
+}
+
+impl SacnDataBuilder {
+    /// Data packet for `universe` at priority 100 with 512 zero slots.
+    pub fn new(universe: u16) -> Self {
+        Self {
+            universe,
+            cid: [0x11; 16],
+            source_name: "LiveShark".to_string(),
+            priority: 100,
+            sync_address: 0,
+            sequence: 0,
+            options: 0,
+            start_code: 0,
+            slots: vec![0; sacn_layout::DMX_MAX_SLOTS],
+            root_vector: sacn_layout::ROOT_VECTOR_DATA,
+            framing_vector: sacn_layout::FRAMING_VECTOR_DMX,
+            property_count: None,
+        }
+    }
+
+    /// Component identifier of the source.
+    pub fn cid(mut self, cid: [u8; 16]) -> Self {
+        self.cid = cid;
+        self
+    }
+
+    /// Source name; truncated to 63 bytes.
+    pub fn source_name(mut self, name: impl Into<String>) -> Self {
+        self.source_name = name.into();
+        self
+    }
+
+    /// Data priority; above 200 is invalid.
+    pub fn priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Synchronization address; 0 for unsynchronized data.
+    pub fn sync_address(mut self, sync_address: u16) -> Self {
+        self.sync_address = sync_address;
+        self
+    }
+
+    /// Sequence number.
+    pub fn sequence(mut self, sequence: u8) -> Self {
+        self.sequence = sequence;
+        self
+    }
+
+    /// Options flags (preview, stream terminated, force synchronization).
+    pub fn options(mut self, options: u8) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// DMX start code; anything but 0 is not dimmer data.
+    pub fn start_code(mut self, start_code: u8) -> Self {
+        self.start_code = start_code;
+        self
+    }
+
+    /// Slot values, start code excluded; more than 512 slots is invalid.
+    pub fn slots(mut self, slots: impl Into<Vec<u8>>) -> Self {
+        self.slots = slots.into();
+        self
+    }
+
+    /// Root layer vector (invalid unless `VECTOR_ROOT_E131_DATA`).
+    pub fn root_vector(mut self, vector: u32) -> Self {
+        self.root_vector = vector;
+        self
+    }
+
+    /// Framing layer vector (invalid unless `VECTOR_E131_DATA_PACKET`).
+    pub fn framing_vector(mut self, vector: u32) -> Self {
+        self.framing_vector = vector;
+        self
+    }
+
+    /// DMP property value count, instead of the slot count plus the start
+    /// code (invalid when they differ).
+    pub fn property_count(mut self, count: u16) -> Self {
+        self.property_count = Some(count);
+        self
+    }
+
+    /// UDP payload.
+    pub fn build(&self) -> Vec<u8> {
+        let len = sacn_layout::DMX_DATA_OFFSET + self.slots.len();
+        let mut payload = vec![0u8; len];
+        write_root_layer(&mut payload, self.root_vector, &self.cid);
+        write_pdu_length(&mut payload, SACN_FRAMING_FLAGS_LENGTH_OFFSET);
+        payload[sacn_layout::FRAMING_VECTOR_RANGE.clone()]
+            .copy_from_slice(&self.framing_vector.to_be_bytes());
+        let name = self.source_name.as_bytes();
+        let name_len = name.len().min(sacn_layout::SOURCE_NAME_RANGE.len() - 1);
+        let name_start = sacn_layout::SOURCE_NAME_RANGE.start;
+        payload[name_start..name_start + name_len].copy_from_slice(&name[..name_len]);
+        payload[sacn_layout::PRIORITY_OFFSET] = self.priority;
+        payload[sacn_layout::SYNC_ADDRESS_RANGE.clone()]
+            .copy_from_slice(&self.sync_address.to_be_bytes());
+        payload[sacn_layout::SEQUENCE_OFFSET] = self.sequence;
+        payload[sacn_layout::OPTIONS_OFFSET] = self.options;
+        payload[sacn_layout::UNIVERSE_RANGE.clone()].copy_from_slice(&self.universe.to_be_bytes());
+        write_pdu_length(&mut payload, SACN_DMP_FLAGS_LENGTH_OFFSET);
+        payload[sacn_layout::DMP_VECTOR_OFFSET] = sacn_layout::DMP_VECTOR_SET_PROPERTY;
+        payload[sacn_layout::DMP_ADDRESS_TYPE_OFFSET] = SACN_DMP_ADDRESS_TYPE_DMX;
+        payload[sacn_layout::DMP_ADDRESS_INCREMENT_RANGE.clone()]
+            .copy_from_slice(&1u16.to_be_bytes());
+        let count = self.property_count.unwrap_or(self.slots.len() as u16 + 1);
+        payload[sacn_layout::DMP_PROPERTY_VALUE_COUNT_RANGE.clone()]
+            .copy_from_slice(&count.to_be_bytes());
+        payload[sacn_layout::START_CODE_OFFSET] = self.start_code;
+        payload[sacn_layout::DMX_DATA_OFFSET..].copy_from_slice(&self.slots);
+        payload
+    }
+}
+
+/// Builder of sACN synchronization payloads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SacnSyncBuilder {
+    sync_address: u16,
+    cid: [u8; 16],
+    sequence: u8,
+}
+
+impl SacnSyncBuilder {
+    /// Synchronization packet for `sync_address`.
+    pub fn new(sync_address: u16) -> Self {
+        Self {
+            sync_address,
+            cid: [0x11; 16],
+            sequence: 0,
+        }
+    }
+
+    /// Component identifier of the source.
+    pub fn cid(mut self, cid: [u8; 16]) -> Self {
+        self.cid = cid;
+        self
+    }
+
+    /// Sequence number.
+    pub fn sequence(mut self, sequence: u8) -> Self {
+        self.sequence = sequence;
+        self
+    }
+
+    /// UDP payload.
+    pub fn build(&self) -> Vec<u8> {
+        let len = sacn_layout::SYNC_LEN;
+        let mut payload = vec![0u8; len];
+        write_root_layer(&mut payload, sacn_layout::ROOT_VECTOR_EXTENDED, &self.cid);
+        write_pdu_length(&mut payload, SACN_FRAMING_FLAGS_LENGTH_OFFSET);
+        payload[sacn_layout::FRAMING_VECTOR_RANGE.clone()]
+            .copy_from_slice(&sacn_layout::FRAMING_VECTOR_SYNC.to_be_bytes());
+        payload[sacn_layout::SYNC_SEQUENCE_OFFSET] = self.sequence;
+        payload[sacn_layout::SYNC_UNIVERSE_RANGE.clone()]
+            .copy_from_slice(&self.sync_address.to_be_bytes());
+        payload
+    }
+}
+
+/// Builder of sACN universe discovery payloads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SacnDiscoveryBuilder {
+    universes: Vec<u16>,
+    cid: [u8; 16],
+    source_name: String,
+    page: u8,
+    last_page: u8,
+}
+
+impl SacnDiscoveryBuilder {
+    /// Single-page discovery packet listing `universes`.
+    pub fn new(universes: Vec<u16>) -> Self {
+        Self {
+            universes,
+            cid: [0x11; 16],
+            source_name: "LiveShark".to_string(),
+            page: 0,
+            last_page: 0,
+        }
+    }
+
+    /// Component identifier of the source.
+    pub fn cid(mut self, cid: [u8; 16]) -> Self {
+        self.cid = cid;
+        self
+    }
+
+    /// Source name; truncated to 63 bytes.
+    pub fn source_name(mut self, name: impl Into<String>) -> Self {
+        self.source_name = name.into();
+        self
+    }
+
+    /// Page number and last page number.
+    pub fn page(mut self, page: u8, last_page: u8) -> Self {
+        self.page = page;
+        self.last_page = last_page;
+        self
+    }
+
+    /// UDP payload.
+    pub fn build(&self) -> Vec<u8> {
+        let len = sacn_layout::DISCOVERY_UNIVERSES_OFFSET + 2 * self.universes.len();
+        let mut payload = vec![0u8; len];
+        write_root_layer(&mut payload, sacn_layout::ROOT_VECTOR_EXTENDED, &self.cid);
+        write_pdu_length(&mut payload, SACN_FRAMING_FLAGS_LENGTH_OFFSET);
+        payload[sacn_layout::FRAMING_VECTOR_RANGE.clone()]
+            .copy_from_slice(&sacn_layout::FRAMING_VECTOR_DISCOVERY.to_be_bytes());
+        let name = self.source_name.as_bytes();
+        let name_len = name
+            .len()
+            .min(sacn_layout::DISCOVERY_SOURCE_NAME_RANGE.len() - 1);
+        let name_start = sacn_layout::DISCOVERY_SOURCE_NAME_RANGE.start;
+        payload[name_start..name_start + name_len].copy_from_slice(&name[..name_len]);
+        write_pdu_length(
+            &mut payload,
+            sacn_layout::DISCOVERY_LAYER_FLAGS_LENGTH_RANGE.start,
+        );
+        payload[sacn_layout::DISCOVERY_LAYER_VECTOR_RANGE.clone()]
+            .copy_from_slice(&sacn_layout::DISCOVERY_VECTOR_UNIVERSE_LIST.to_be_bytes());
+        payload[sacn_layout::DISCOVERY_PAGE_OFFSET] = self.page;
+        payload[sacn_layout::DISCOVERY_LAST_PAGE_OFFSET] = self.last_page;
+        for (index, universe) in self.universes.iter().enumerate() {
+            let offset = sacn_layout::DISCOVERY_UNIVERSES_OFFSET + 2 * index;
+            payload[offset..offset + 2].copy_from_slice(&universe.to_be_bytes());
+        }
+        payload
+    }
+}
+
+/// Builder of PosiStageNet data payloads.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PsnDataBuilder {
+    frame_id: u8,
+    trackers: Vec<(u16, [f32; 3], Option<[f32; 3]>)>,
+}
+
+impl PsnDataBuilder {
+    /// Data packet of frame `frame_id`, without trackers.
+    pub fn new(frame_id: u8) -> Self {
+        Self {
+            frame_id,
+            trackers: Vec::new(),
+        }
+    }
+
+    /// Add tracker `id` at `position`, with an optional speed.
+    pub fn tracker(mut self, id: u16, position: [f32; 3], speed: Option<[f32; 3]>) -> Self {
+        self.trackers.push((id, position, speed));
+        self
+    }
+
+    /// UDP payload.
+    pub fn build(&self) -> Vec<u8> {
+        let trackers: Vec<u8> = self
+            .trackers
+            .iter()
+            .flat_map(|(id, position, speed)| {
+                let mut fields =
+                    psn_chunk(psn_layout::TRACKER_POSITION, false, &psn_vector(position));
+                if let Some(speed) = speed {
+                    fields.extend(psn_chunk(
+                        psn_layout::TRACKER_SPEED,
+                        false,
+                        &psn_vector(speed),
+                    ));
+                }
+                psn_chunk(*id, true, &fields)
+            })
+            .collect();
+        let mut chunks = psn_header(self.frame_id);
+        chunks.extend(psn_chunk(psn_layout::DATA_TRACKER_LIST, true, &trackers));
+        psn_chunk(psn_layout::DATA_PACKET, true, &chunks)
+    }
+}
+
+/// Builder of PosiStageNet info payloads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PsnInfoBuilder {
+    system_name: String,
+    trackers: Vec<(u16, String)>,
+}
+
+impl PsnInfoBuilder {
+    /// Info packet of the tracking system `system_name`, without trackers.
+    pub fn new(system_name: impl Into<String>) -> Self {
+        Self {
+            system_name: system_name.into(),
+            trackers: Vec::new(),
+        }
+    }
+
+    /// Add tracker `id` named `name`.
+    pub fn tracker(mut self, id: u16, name: impl Into<String>) -> Self {
+        self.trackers.push((id, name.into()));
+        self
+    }
+
+    /// UDP payload.
+    pub fn build(&self) -> Vec<u8> {
+        let trackers: Vec<u8> = self
+            .trackers
+            .iter()
+            .flat_map(|(id, name)| {
+                psn_chunk(
+                    *id,
+                    true,
+                    &psn_chunk(psn_layout::INFO_TRACKER_NAME, false, name.as_bytes()),
+                )
+            })
+            .collect();
+        let mut chunks = psn_header(0);
+        chunks.extend(psn_chunk(
+            psn_layout::INFO_SYSTEM_NAME,
+            false,
+            self.system_name.as_bytes(),
+        ));
+        chunks.extend(psn_chunk(psn_layout::INFO_TRACKER_LIST, true, &trackers));
+        psn_chunk(psn_layout::INFO_PACKET, true, &chunks)
+    }
+}
+
+/// Packet source replaying packets held in memory, in order.
+#[derive(Debug, Clone, Default)]
+pub struct MemorySource {
+    packets: VecDeque<PacketEvent>,
+}
+
+impl MemorySource {
+    /// Append `packet` after the packets already queued.
+    pub fn push(&mut self, packet: PacketEvent) {
+        self.packets.push_back(packet);
+    }
+}
+
+impl FromIterator<PacketEvent> for MemorySource {
+    fn from_iter<I: IntoIterator<Item = PacketEvent>>(packets: I) -> Self {
+        Self {
+            packets: packets.into_iter().collect(),
+        }
+    }
+}
+
+impl PacketSource for MemorySource {
+    fn next_packet(&mut self) -> Result<Option<PacketEvent>, SourceError> {
+        Ok(self.packets.pop_front())
+    }
+}
+
+/// Ethernet frame carrying `payload` in a UDP datagram from `src` to `dst`,
+/// both ports `port`, captured at `ts`.
+pub fn udp_event(ts: f64, src: [u8; 4], dst: [u8; 4], port: u16, payload: &[u8]) -> PacketEvent {
+    let builder = PacketBuilder::ethernet2([1, 2, 3, 4, 5, 6], [7, 8, 9, 10, 11, 12])
+        .ipv4(src, dst, 64)
+        .udp(port, port);
+    let mut data = Vec::with_capacity(builder.size(payload.len()));
+    builder
+        .write(&mut data, payload)
+        .expect("payload fits in a UDP datagram");
+    PacketEvent {
+        ts: Some(ts),
+        linktype: Linktype::ETHERNET,
+        data,
+        orig_len: None,
+    }
+}
+
+/// PSN chunk: header (id, then data length and sub-chunk flag) and data.
+fn psn_chunk(id: u16, has_subchunks: bool, data: &[u8]) -> Vec<u8> {
+    let flag = if has_subchunks {
+        psn_layout::CHUNK_HAS_SUBCHUNKS
+    } else {
+        0
+    };
+    let mut chunk = id.to_le_bytes().to_vec();
+    chunk.extend_from_slice(&(data.len() as u16 | flag).to_le_bytes());
+    chunk.extend_from_slice(data);
+    chunk
+}
+
+/// PSN packet header chunk of PSN 2.3, for a frame sent in one packet.
+fn psn_header(frame_id: u8) -> Vec<u8> {
+    let mut data = vec![0u8; psn_layout::PACKET_HEADER_LEN];
+    data[psn_layout::HEADER_VERSION_HIGH_OFFSET] = 2;
+    data[psn_layout::HEADER_VERSION_LOW_OFFSET] = 3;
+    data[psn_layout::HEADER_FRAME_ID_OFFSET] = frame_id;
+    data[psn_layout::HEADER_FRAME_PACKET_COUNT_OFFSET] = 1;
+    psn_chunk(psn_layout::PACKET_HEADER, false, &data)
+}
+
+fn psn_vector(values: &[f32; 3]) -> Vec<u8> {
+    values
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect()
+}
+
+fn write_root_layer(payload: &mut [u8], vector: u32, cid: &[u8; 16]) {
+    payload[sacn_layout::PREAMBLE_SIZE_RANGE.clone()]
+        .copy_from_slice(&sacn_layout::PREAMBLE_SIZE.to_be_bytes());
+    payload[sacn_layout::POSTAMBLE_SIZE_RANGE.clone()]
+        .copy_from_slice(&sacn_layout::POSTAMBLE_SIZE.to_be_bytes());
+    payload[sacn_layout::ACN_PID_RANGE.clone()].copy_from_slice(sacn_layout::ACN_PID);
+    write_pdu_length(payload, SACN_ROOT_FLAGS_LENGTH_OFFSET);
+    payload[sacn_layout::ROOT_VECTOR_RANGE.clone()].copy_from_slice(&vector.to_be_bytes());
+    payload[sacn_layout::CID_RANGE.clone()].copy_from_slice(cid);
+}
+
+/// Flags and length field of the PDU starting at `offset` and running to
+/// the end of the payload.
+fn write_pdu_length(payload: &mut [u8], offset: usize) {
+    let field = SACN_PDU_FLAGS | (payload.len() - offset) as u16;
+    payload[offset..offset + 2].copy_from_slice(&field.to_be_bytes());
+}
+
+/// Even slot count in 2..=512, as ArtDmx requires.
+fn artdmx_slots(u: &mut Unstructured<'_>) -> arbitrary::Result<Vec<u8>> {
+    let pairs = u.int_in_range(1..=artnet_layout::DMX_MAX_SLOTS / 2)?;
+    (0..pairs * 2).map(|_| u.arbitrary()).collect()
+}
+
+impl<'a> arbitrary::Arbitrary<'a> for ArtDmxBuilder {
+    /// A valid ArtDmx.
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(
+            Self::new(u.int_in_range(0..=artnet_layout::PORT_ADDRESS_MAX)?)
+                .sequence(u.arbitrary()?)
+                .physical(u.arbitrary()?)
+                .slots(artdmx_slots(u)?),
+        )
+    }
+}
+
+impl<'a> arbitrary::Arbitrary<'a> for SacnDataBuilder {
+    /// A valid sACN data packet of DMX dimmer data.
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let name_len = u.int_in_range(0..=sacn_layout::SOURCE_NAME_RANGE.len() - 1)?;
+        let name = (0..name_len)
+            .map(|_| Ok(char::from(u.int_in_range(b' '..=b'~')?)))
+            .collect::<arbitrary::Result<String>>()?;
+        let slot_count = u.int_in_range(0..=sacn_layout::DMX_MAX_SLOTS)?;
+        let slots = (0..slot_count)
+            .map(|_| u.arbitrary())
+            .collect::<arbitrary::Result<Vec<u8>>>()?;
+        Ok(Self::new(u.int_in_range(1..=SACN_UNIVERSE_MAX)?)
+            .cid(u.arbitrary()?)
+            .source_name(name)
+            .priority(u.int_in_range(0..=sacn_layout::MAX_PRIORITY)?)
+            .sequence(u.arbitrary()?)
+            .slots(slots))
+    }
+}
+
+impl<'a> arbitrary::Arbitrary<'a> for SacnSyncBuilder {
+    /// A valid synchronization packet.
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self::new(u.int_in_range(1..=SACN_UNIVERSE_MAX)?)
+            .cid(u.arbitrary()?)
+            .sequence(u.arbitrary()?))
+    }
+}
+
+impl proptest::arbitrary::Arbitrary for ArtDmxBuilder {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// Valid ArtDmx packets.
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        let slots = (1..=artnet_layout::DMX_MAX_SLOTS / 2)
+            .prop_flat_map(|pairs| proptest::collection::vec(any::<u8>(), pairs * 2));
+        (
+            0..=artnet_layout::PORT_ADDRESS_MAX,
+            any::<u8>(),
+            any::<u8>(),
+            slots,
+        )
+            .prop_map(|(universe, sequence, physical, slots)| {
+                Self::new(universe)
+                    .sequence(sequence)
+                    .physical(physical)
+                    .slots(slots)
+            })
+            .boxed()
+    }
+}
+
+impl proptest::arbitrary::Arbitrary for SacnDataBuilder {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// Valid sACN data packets of DMX dimmer data.
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (
+            1..=SACN_UNIVERSE_MAX,
+            any::<[u8; 16]>(),
+            "[ -~]{0,63}",
+            0..=sacn_layout::MAX_PRIORITY,
+            any::<u8>(),
+            proptest::collection::vec(any::<u8>(), 0..=sacn_layout::DMX_MAX_SLOTS),
+        )
+            .prop_map(|(universe, cid, name, priority, sequence, slots)| {
+                Self::new(universe)
+                    .cid(cid)
+                    .source_name(name)
+                    .priority(priority)
+                    .sequence(sequence)
+                    .slots(slots)
+            })
+            .boxed()
+    }
+}
+
+impl proptest::arbitrary::Arbitrary for SacnSyncBuilder {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// Valid synchronization packets.
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (1..=SACN_UNIVERSE_MAX, any::<[u8; 16]>(), any::<u8>())
+            .prop_map(|(sync_address, cid, sequence)| {
+                Self::new(sync_address).cid(cid).sequence(sequence)
+            })
+            .boxed()
+    }
+}
@@ -0,0 +1,342 @@
+use std::path::{Path, PathBuf};
+
+use liveshark_core::{
+    AnalysisObserver, Analyzer, ConflictSummary, DecodedPacket, DecodedPacketIter, DmxFrame,
+    DmxStateStore, PacketEvent, PcapFileSource, RuleId, RuleSet, ViolationEvent, analyze_pcap_file,
+};
+
+#[derive(Default)]
+struct RecordingObserver {
+    packets: u64,
+    frames: u64,
+    violations: Vec<String>,
+    conflicts: u64,
+}
+
+impl AnalysisObserver for RecordingObserver {
+    fn on_packet(&mut self, _packet: &PacketEvent) {
+        self.packets += 1;
+    }
+
+    fn on_dmx_frame(&mut self, frame: &DmxFrame) {
+        assert!(!frame.source_id.is_empty());
+        self.frames += 1;
+    }
+
+    fn on_violation(&mut self, violation: &ViolationEvent<'_>) {
+        self.violations.push(violation.rule.code().to_string());
+    }
+
+    fn on_conflict_detected(&mut self, _conflict: &ConflictSummary) {
+        self.conflicts += 1;
+    }
+}
+
+fn golden_input(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .join("tests")
+        .join("golden")
+        .join(name)
+        .join("input.pcapng")
+}
+
+#[test]
+fn default_analyzer_matches_free_function() {
+    let input = golden_input("sacn_conflict");
+    let expected = analyze_pcap_file(&input).expect("analyze pcap");
+    let actual = Analyzer::builder()
+        .build()
+        .expect("build analyzer")
+        .analyze_file(&input)
+        .expect("analyze pcap");
+
+    assert_eq!(
+        serde_json::to_value(actual).unwrap(),
+        serde_json::to_value(expected).unwrap()
+    );
+}
+
+#[test]
+fn buffer_pool_is_opt_in_and_does_not_change_reports() {
+    let input = golden_input("sacn");
+    let plain = Analyzer::builder().build().expect("build analyzer");
+    assert!(!plain.buffer_pool());
+    let pooled = Analyzer::builder()
+        .buffer_pool(true)
+        .build()
+        .expect("build analyzer");
+    assert!(pooled.buffer_pool());
+
+    let expected = plain.analyze_file(&input).expect("analyze pcap");
+    let actual = pooled.analyze_file(&input).expect("analyze pcap");
+    assert_eq!(
+        serde_json::to_value(actual).unwrap(),
+        serde_json::to_value(expected).unwrap()
+    );
+}
+
+#[test]
+fn disabled_protocol_yields_no_universes() {
+    let input = golden_input("sacn_burst");
+    assert!(!analyze_pcap_file(&input).unwrap().universes.is_empty());
+    let report = Analyzer::builder()
+        .sacn(false)
+        .build()
+        .expect("build analyzer")
+        .analyze_file(&input)
+        .expect("analyze pcap");
+
+    assert!(report.universes.is_empty());
+    assert!(!report.flows.is_empty());
+}
+
+#[test]
+fn universe_filter_and_packet_limit_are_applied() {
+    let input = golden_input("artnet");
+    let report = Analyzer::builder()
+        .universes([u16::MAX])
+        .max_packets(1)
+        .build()
+        .expect("build analyzer")
+        .analyze_file(&input)
+        .expect("analyze pcap");
+
+    assert!(report.universes.is_empty());
+    let summary = report.capture_summary.expect("capture summary");
+    assert_eq!(summary.packets_total, 1);
+}
+
+#[test]
+fn observer_sees_packets_frames_and_conflicts() {
+    let input = golden_input("artnet_conflict");
+    let mut observer = RecordingObserver::default();
+    let report = Analyzer::default()
+        .analyze_file_with_observer(&input, &mut observer)
+        .expect("analyze pcap");
+
+    let summary = report.capture_summary.expect("capture summary");
+    assert_eq!(observer.packets, summary.packets_total);
+    let frames: u64 = report.universes.iter().map(|u| u.frames_count).sum();
+    assert_eq!(observer.frames, frames);
+    assert_eq!(observer.conflicts, report.conflicts.len() as u64);
+    assert!(observer.conflicts > 0);
+}
+
+#[test]
+fn observer_sees_every_violation_occurrence() {
+    let input = golden_input("sacn_invalid_start_code");
+    let mut observer = RecordingObserver::default();
+    let report = Analyzer::default()
+        .analyze_file_with_observer(&input, &mut observer)
+        .expect("analyze pcap");
+
+    let expected: u64 = report
+        .compliance
+        .iter()
+        .flat_map(|entry| entry.violations.iter())
+        .map(|violation| violation.count)
+        .sum();
+    assert!(expected > 0);
+    assert_eq!(observer.violations.len() as u64, expected);
+}
+
+#[test]
+fn decoded_packets_match_report_frame_counts() {
+    let input = golden_input("sacn_burst");
+    let report = analyze_pcap_file(&input).expect("analyze pcap");
+    let source = PcapFileSource::open(&input).expect("open pcap");
+
+    let mut sacn_frames = 0u64;
+    for packet in DecodedPacketIter::new(source) {
+        if let DecodedPacket::SacnDmx { endpoints, .. } = packet.expect("decode packet") {
+            assert_eq!(endpoints.dst_port, 5568);
+            sacn_frames += 1;
+        }
+    }
+
+    let expected: u64 = report.universes.iter().map(|u| u.frames_count).sum();
+    assert!(sacn_frames > 0);
+    assert_eq!(sacn_frames, expected);
+}
+
+#[derive(Default)]
+struct FrameCollector {
+    frames: Vec<DmxFrame>,
+}
+
+impl AnalysisObserver for FrameCollector {
+    fn on_dmx_frame(&mut self, frame: &DmxFrame) {
+        self.frames.push(frame.clone());
+    }
+}
+
+#[test]
+fn public_reconstruction_matches_pipeline_frames() {
+    for fixture in ["artnet_conflict", "sacn_dup_reorder"] {
+        let input = golden_input(fixture);
+        let mut collector = FrameCollector::default();
+        Analyzer::default()
+            .analyze_file_with_observer(&input, &mut collector)
+            .expect("analyze pcap");
+
+        let mut state = DmxStateStore::new();
+        let mut frames = Vec::new();
+        let source = PcapFileSource::open(&input).expect("open pcap");
+        for packet in DecodedPacketIter::new(source) {
+            match packet.expect("decode packet") {
+                DecodedPacket::ArtDmx {
+                    ts,
+                    endpoints,
+                    frame,
+                } => frames.extend(state.apply_artdmx_synced(&endpoints, ts, &frame)),
+                DecodedPacket::ArtSync { ts, endpoints, .. } => {
+                    frames.extend(state.apply_artsync(&endpoints, ts))
+                }
+                DecodedPacket::SacnDmx {
+                    ts,
+                    endpoints,
+                    frame,
+                } => frames.push(state.apply_sacn_dmx(&endpoints, ts, &frame)),
+                _ => {}
+            }
+        }
+        frames.extend(state.flush_artsync());
+
+        assert!(!frames.is_empty(), "no frames in {fixture}");
+        assert_eq!(frames, collector.frames, "frame mismatch in {fixture}");
+    }
+}
+
+#[test]
+fn disabled_rules_are_not_reported_or_observed() {
+    let input = golden_input("artnet");
+    let analyzer = Analyzer::builder()
+        .rule_enabled(RuleId::SacnTooShort, false)
+        .build()
+        .expect("build analyzer");
+    let mut observer = RecordingObserver::default();
+    let report = analyzer
+        .analyze_file_with_observer(&input, &mut observer)
+        .expect("analyze pcap");
+
+    assert!(
+        !observer
+            .violations
+            .iter()
+            .any(|code| code == "LS-SACN-TOO-SHORT")
+    );
+    assert!(
+        report
+            .compliance
+            .iter()
+            .flat_map(|entry| &entry.violations)
+            .all(|violation| violation.id != "LS-SACN-TOO-SHORT")
+    );
+    assert!(
+        report
+            .rules
+            .iter()
+            .all(|rule| rule.id != "LS-SACN-TOO-SHORT")
+    );
+    assert_eq!(report.rules.len(), RuleSet::default().active().len() - 1);
+}
+
+#[test]
+fn sequence_gap_rules_are_opt_in_and_respect_threshold() {
+    let input = golden_input("sacn_gap");
+    let gap_violations = |report: &liveshark_core::Report| -> u64 {
+        report
+            .compliance
+            .iter()
+            .flat_map(|entry| &entry.violations)
+            .filter(|violation| violation.id == "LS-SACN-SEQ-GAP")
+            .map(|violation| violation.count)
+            .sum()
+    };
+
+    assert_eq!(gap_violations(&analyze_pcap_file(&input).unwrap()), 0);
+
+    let report = Analyzer::builder()
+        .rule_enabled(RuleId::SacnSeqGap, true)
+        .build()
+        .expect("build analyzer")
+        .analyze_file(&input)
+        .expect("analyze pcap");
+    assert_eq!(gap_violations(&report), 1);
+    assert!(report.rules.iter().any(|rule| rule.id == "LS-SACN-SEQ-GAP"));
+
+    let report = Analyzer::builder()
+        .rule_enabled(RuleId::SacnSeqGap, true)
+        .min_sequence_gap(8)
+        .build()
+        .expect("build analyzer")
+        .analyze_file(&input)
+        .expect("analyze pcap");
+    assert_eq!(gap_violations(&report), 0);
+}
+
+#[test]
+fn artnet_min_interval_flags_packets_closer_than_threshold() {
+    let input = golden_input("artnet_burst");
+    let interval_violations = |report: &liveshark_core::Report| {
+        report
+            .compliance
+            .iter()
+            .flat_map(|entry| &entry.violations)
+            .find(|violation| violation.id == "LS-ARTNET-MIN-INTERVAL")
+            .cloned()
+    };
+
+    assert!(interval_violations(&analyze_pcap_file(&input).unwrap()).is_none());
+
+    let report = Analyzer::builder()
+        .artnet_min_interval_s(2.0)
+        .build()
+        .expect("build analyzer")
+        .analyze_file(&input)
+        .expect("analyze pcap");
+    let violation = interval_violations(&report).expect("interval violation");
+    assert_eq!(violation.count, 4);
+    assert!(
+        violation.examples[0]
+            .detail
+            .contains("interval_ms=1000.000, min_ms=2000.000")
+    );
+}
+
+#[test]
+fn decoding_error_examples_carry_offending_bytes_when_configured() {
+    let input = golden_input("sacn_invalid_start_code");
+    let start_code_example = |report: &liveshark_core::Report| {
+        report
+            .compliance
+            .iter()
+            .flat_map(|entry| &entry.violations)
+            .find(|violation| violation.id == "LS-SACN-START-CODE")
+            .map(|violation| violation.examples[0].clone())
+            .expect("start code violation")
+    };
+
+    let example = start_code_example(&analyze_pcap_file(&input).unwrap());
+    assert!(example.bytes.is_none());
+
+    let report = Analyzer::builder()
+        .violation_example_bytes(4)
+        .build()
+        .expect("build analyzer")
+        .analyze_file(&input)
+        .expect("analyze pcap");
+    let bytes = start_code_example(&report).bytes.expect("offending bytes");
+    assert_eq!(bytes.offset, 125);
+    assert!(bytes.hex.starts_with("01"));
+    assert!(bytes.hex.split(' ').count() <= 4);
+
+    assert!(
+        Analyzer::builder()
+            .violation_example_bytes(65)
+            .build()
+            .is_err()
+    );
+}
@@ -134,3 +134,45 @@ fn golden_sacn_dup_reorder_has_metrics() {
     assert_eq!(summary.dup_packets, Some(1));
     assert_eq!(summary.reordered_packets, Some(1));
 }
+
+#[test]
+fn golden_sacn_priority_takeover() {
+    run_golden("tests/golden/sacn_priority_takeover");
+}
+
+#[test]
+fn golden_sacn_priority_takeover_has_takeover() {
+    let report = load_expected_report("tests/golden/sacn_priority_takeover");
+    assert_eq!(report.takeovers.len(), 1);
+    assert_eq!(report.takeovers[0].to_priority, Some(150));
+    assert_eq!(report.universes[0].loss_packets, Some(5));
+}
+
+#[test]
+fn golden_reports_load_through_versioned_reader() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .join("tests")
+        .join("golden");
+    let mut checked = 0;
+    for entry in fs::read_dir(&root).expect("read golden dir") {
+        let path = entry
+            .expect("golden entry")
+            .path()
+            .join("expected_report.json");
+        if !path.exists() {
+            continue;
+        }
+        let json = fs::read_to_string(&path).expect("read expected_report.json");
+        let loaded = Report::from_json_any_version(&json)
+            .unwrap_or_else(|err| panic!("load {}: {err}", path.display()));
+        assert!(loaded.migrations.is_empty());
+        loaded
+            .report
+            .validate()
+            .unwrap_or_else(|err| panic!("validate {}: {err}", path.display()));
+        checked += 1;
+    }
+    assert!(checked > 0);
+}
@@ -27,6 +27,33 @@ fn pcap_source_reads_packets_from_fixture() {
     assert!(packets > 0);
 }
 
+#[test]
+fn pcap_source_buffer_pool_yields_identical_packets() {
+    let path = repo_root()
+        .join("tests")
+        .join("golden")
+        .join("sacn")
+        .join("input.pcapng");
+    let mut plain = PcapFileSource::open(&path).unwrap();
+    let mut pooled = PcapFileSource::open(&path).unwrap().with_buffer_pool();
+
+    let mut packets = 0;
+    while let Some(expected) = plain.next_packet().unwrap() {
+        let actual = pooled
+            .next_packet()
+            .unwrap()
+            .expect("pooled source ended early");
+        assert_eq!(actual.ts, expected.ts);
+        assert_eq!(actual.linktype, expected.linktype);
+        assert_eq!(actual.data, expected.data);
+        pooled.recycle(actual.data);
+        packets += 1;
+    }
+
+    assert!(packets > 0);
+    assert!(pooled.next_packet().unwrap().is_none());
+}
+
 #[test]
 fn pcap_source_rejects_truncated_file() {
     let mut path = std::env::temp_dir();
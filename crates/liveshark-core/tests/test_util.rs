@@ -0,0 +1,95 @@
+use arbitrary::{Arbitrary, Unstructured};
+use proptest::prelude::*;
+
+use liveshark_core::test_util::{ArtDmxBuilder, SacnDataBuilder, SacnSyncBuilder};
+use liveshark_core::{ArtNetError, SacnError, parse_artdmx, parse_sacn_dmx, parse_sacn_sync};
+
+proptest! {
+    #[test]
+    fn generated_artdmx_parses(builder in any::<ArtDmxBuilder>()) {
+        let payload = builder.build();
+        let frame = parse_artdmx(&payload).expect("valid artdmx").expect("artdmx");
+        prop_assert_eq!(frame.slots.len(), payload.len() - 18);
+    }
+
+    #[test]
+    fn generated_sacn_data_parses(builder in any::<SacnDataBuilder>()) {
+        let payload = builder.build();
+        let frame = parse_sacn_dmx(&payload).expect("valid sacn").expect("sacn");
+        prop_assert_eq!(frame.slots.len(), payload.len() - 126);
+        prop_assert!(frame.priority <= 200);
+    }
+
+    #[test]
+    fn generated_sacn_sync_parses(builder in any::<SacnSyncBuilder>()) {
+        let sync = parse_sacn_sync(&builder.build()).expect("valid sync").expect("sync");
+        prop_assert!(sync.sync_address > 0);
+    }
+}
+
+#[test]
+fn builders_round_trip_their_fields() {
+    let frame = parse_artdmx(
+        &ArtDmxBuilder::new(0x0121)
+            .sequence(9)
+            .slots([1, 2, 3, 4])
+            .build(),
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(frame.universe, 0x0121);
+    assert_eq!(frame.sequence, Some(9));
+    assert_eq!(frame.slots, vec![1, 2, 3, 4]);
+
+    let payload = SacnDataBuilder::new(7)
+        .cid([0xab; 16])
+        .source_name("Desk")
+        .priority(150)
+        .sync_address(7000)
+        .sequence(3)
+        .slots([10, 20])
+        .build();
+    let frame = parse_sacn_dmx(&payload).unwrap().unwrap();
+    assert_eq!(frame.universe, 7);
+    assert_eq!(frame.cid, "ab".repeat(16));
+    assert_eq!(frame.source_name.as_deref(), Some("Desk"));
+    assert_eq!(frame.priority, 150);
+    assert_eq!(frame.sync_address, Some(7000));
+    assert_eq!(frame.slots, vec![10, 20]);
+
+    let sync = parse_sacn_sync(&SacnSyncBuilder::new(7000).sequence(4).build())
+        .unwrap()
+        .unwrap();
+    assert_eq!((sync.sync_address, sync.sequence), (7000, 4));
+}
+
+#[test]
+fn invalid_setters_build_rejected_payloads() {
+    let err = parse_artdmx(&ArtDmxBuilder::new(1).opcode(0x2000).build()).unwrap_err();
+    assert!(matches!(
+        err,
+        ArtNetError::UnsupportedOpCode { opcode: 0x2000 }
+    ));
+    assert!(parse_artdmx(&ArtDmxBuilder::new(1).slots([1, 2, 3]).build()).is_err());
+    assert!(parse_artdmx(&ArtDmxBuilder::new(1).slots([1, 2]).length(4).build()).is_err());
+
+    let err = parse_sacn_dmx(&SacnDataBuilder::new(1).start_code(0xdd).build()).unwrap_err();
+    assert!(matches!(err, SacnError::InvalidStartCode { value: 0xdd }));
+    let err = parse_sacn_dmx(&SacnDataBuilder::new(1).root_vector(9).build()).unwrap_err();
+    assert!(matches!(err, SacnError::InvalidRootVector { value: 9 }));
+    assert!(parse_sacn_dmx(&SacnDataBuilder::new(1).property_count(600).build()).is_err());
+}
+
+#[test]
+fn arbitrary_builders_are_valid() {
+    let bytes: Vec<u8> = (0..4096u32)
+        .map(|index| (index * 131 % 251) as u8)
+        .collect();
+    let mut input = Unstructured::new(&bytes);
+    let artdmx = ArtDmxBuilder::arbitrary(&mut input).unwrap();
+    assert!(matches!(parse_artdmx(&artdmx.build()), Ok(Some(_))));
+    let sacn = SacnDataBuilder::arbitrary(&mut input).unwrap();
+    assert!(matches!(parse_sacn_dmx(&sacn.build()), Ok(Some(_))));
+    let sync = SacnSyncBuilder::arbitrary(&mut input).unwrap();
+    assert!(matches!(parse_sacn_sync(&sync.build()), Ok(Some(_))));
+}
@@ -0,0 +1,38 @@
+use core::fmt;
+
+/// Errors returned by ARP parsing and reading.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::ArpError;
+///
+/// let err = ArpError::TooShort { needed: 28, actual: 20 };
+/// assert!(err.to_string().contains("too short"));
+/// assert_eq!(err.offset(), 0);
+/// ```
+#[derive(Debug)]
+pub enum ArpError {
+    TooShort { needed: usize, actual: usize },
+}
+
+impl ArpError {
+    /// Payload offset of the field the error is about; 0 for truncated
+    /// packets.
+    pub fn offset(&self) -> usize {
+        match self {
+            Self::TooShort { .. } => 0,
+        }
+    }
+}
+
+impl fmt::Display for ArpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooShort { needed, actual } => {
+                write!(f, "payload too short: need {needed} bytes, got {actual}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ArpError {}
@@ -0,0 +1,22 @@
+/// EtherType of ARP.
+pub const ETHER_TYPE: u16 = 0x0806;
+
+/// Ethernet/IPv4 ARP packet, big-endian.
+pub const PACKET_LEN: usize = 28;
+pub const HARDWARE_TYPE_RANGE: core::ops::Range<usize> = 0..2;
+pub const PROTOCOL_TYPE_RANGE: core::ops::Range<usize> = 2..4;
+pub const HARDWARE_LEN_OFFSET: usize = 4;
+pub const PROTOCOL_LEN_OFFSET: usize = 5;
+pub const OPERATION_RANGE: core::ops::Range<usize> = 6..8;
+pub const SENDER_MAC_RANGE: core::ops::Range<usize> = 8..14;
+pub const SENDER_IP_RANGE: core::ops::Range<usize> = 14..18;
+pub const TARGET_MAC_RANGE: core::ops::Range<usize> = 18..24;
+pub const TARGET_IP_RANGE: core::ops::Range<usize> = 24..28;
+
+pub const HARDWARE_ETHERNET: u16 = 1;
+pub const PROTOCOL_IPV4: u16 = 0x0800;
+pub const MAC_LEN: u8 = 6;
+pub const IPV4_LEN: u8 = 4;
+
+pub const OPERATION_REQUEST: u16 = 1;
+pub const OPERATION_REPLY: u16 = 2;
@@ -0,0 +1,22 @@
+//! ARP (RFC 826) decoding for Ethernet and IPv4.
+//!
+//! Hosts announce which hardware address owns an IPv4 address in ARP
+//! requests and replies (EtherType 0x0806); gratuitous ARP repeats the
+//! sender's own address as the target when a host comes up or changes
+//! address. Only the Ethernet/IPv4 form is decoded; other hardware or
+//! protocol types are not ARP this crate knows.
+//!
+//! Errors report packets shorter than the 28-byte Ethernet/IPv4 layout.
+//!
+//! Decoding (`reader` and `parse_arp`) is compiled with the `arp` feature;
+//! packet types and `layout` are always available.
+//!
+pub mod error;
+pub mod layout;
+pub mod parser;
+#[cfg(feature = "arp")]
+pub mod reader;
+
+#[cfg(feature = "arp")]
+pub use parser::parse_arp;
+pub use parser::{ArpPacket, MacAddress};
@@ -0,0 +1,129 @@
+use core::fmt;
+use core::net::Ipv4Addr;
+
+#[cfg(feature = "arp")]
+use super::error::ArpError;
+#[cfg(feature = "arp")]
+use super::layout;
+#[cfg(feature = "arp")]
+use super::reader::ArpReader;
+
+/// Ethernet hardware (MAC) address.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::MacAddress;
+///
+/// let mac = MacAddress([0x00, 0x0e, 0xc6, 0x01, 0x02, 0x03]);
+/// assert_eq!(mac.to_string(), "00:0e:c6:01:02:03");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MacAddress(pub [u8; 6]);
+
+impl fmt::Display for MacAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c, d, e, g] = self.0;
+        write!(f, "{a:02x}:{b:02x}:{c:02x}:{d:02x}:{e:02x}:{g:02x}")
+    }
+}
+
+/// Ethernet/IPv4 ARP request or reply.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::{ArpPacket, MacAddress};
+///
+/// let packet = ArpPacket {
+///     operation: 1,
+///     sender_mac: MacAddress([2, 0, 0, 0, 0, 1]),
+///     sender_ip: "10.0.0.1".parse().unwrap(),
+///     target_mac: MacAddress([0; 6]),
+///     target_ip: "10.0.0.1".parse().unwrap(),
+/// };
+/// assert!(packet.is_gratuitous());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArpPacket {
+    /// Operation (1 request, 2 reply).
+    pub operation: u16,
+    /// Hardware address of the sender.
+    pub sender_mac: MacAddress,
+    /// IPv4 address the sender claims; `0.0.0.0` for an address probe.
+    pub sender_ip: Ipv4Addr,
+    /// Hardware address of the target (zero in requests).
+    pub target_mac: MacAddress,
+    /// IPv4 address asked for or answered to.
+    pub target_ip: Ipv4Addr,
+}
+
+impl ArpPacket {
+    /// Whether the sender announces its own address (gratuitous ARP).
+    pub fn is_gratuitous(&self) -> bool {
+        !self.sender_ip.is_unspecified() && self.sender_ip == self.target_ip
+    }
+}
+
+/// Parse an ARP packet from the payload of an Ethernet frame with
+/// EtherType 0x0806.
+///
+/// Returns `Ok(None)` when the packet is not Ethernet/IPv4 ARP.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::parse_arp;
+///
+/// let mut payload = vec![0, 1, 8, 0, 6, 4, 0, 2];
+/// payload.extend_from_slice(&[2, 0, 0, 0, 0, 1, 10, 0, 0, 1]);
+/// payload.extend_from_slice(&[2, 0, 0, 0, 0, 2, 10, 0, 0, 2]);
+///
+/// let packet = parse_arp(&payload)?.expect("arp");
+/// assert_eq!(packet.operation, 2);
+/// assert_eq!(packet.sender_mac.to_string(), "02:00:00:00:00:01");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// Returns `ArpError` when the packet is shorter than the Ethernet/IPv4
+/// layout.
+#[cfg(feature = "arp")]
+pub fn parse_arp(payload: &[u8]) -> Result<Option<ArpPacket>, ArpError> {
+    let reader = ArpReader::new(payload);
+    let ethernet_ipv4 = reader.read_u16_be(layout::HARDWARE_TYPE_RANGE)?
+        == layout::HARDWARE_ETHERNET
+        && reader.read_u16_be(layout::PROTOCOL_TYPE_RANGE)? == layout::PROTOCOL_IPV4
+        && reader.read_u8(layout::HARDWARE_LEN_OFFSET)? == layout::MAC_LEN
+        && reader.read_u8(layout::PROTOCOL_LEN_OFFSET)? == layout::IPV4_LEN;
+    if !ethernet_ipv4 {
+        return Ok(None);
+    }
+    Ok(Some(ArpPacket {
+        operation: reader.read_u16_be(layout::OPERATION_RANGE)?,
+        sender_mac: reader.read_mac(layout::SENDER_MAC_RANGE)?,
+        sender_ip: reader.read_ipv4(layout::SENDER_IP_RANGE)?,
+        target_mac: reader.read_mac(layout::TARGET_MAC_RANGE)?,
+        target_ip: reader.read_ipv4(layout::TARGET_IP_RANGE)?,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_arp;
+    use crate::arp::error::ArpError;
+
+    #[test]
+    fn parse_skips_other_hardware_and_rejects_truncated_packets() {
+        let mut payload = vec![0, 6, 8, 0, 6, 4, 0, 1];
+        payload.extend_from_slice(&[0; 20]);
+        assert_eq!(parse_arp(&payload).unwrap(), None);
+        payload[1] = 1;
+        let packet = parse_arp(&payload).unwrap().expect("arp");
+        assert!(!packet.is_gratuitous());
+        assert!(matches!(
+            parse_arp(&payload[..20]),
+            Err(ArpError::TooShort {
+                needed: 24,
+                actual: 20
+            })
+        ));
+    }
+}
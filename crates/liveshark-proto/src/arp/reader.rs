@@ -0,0 +1,61 @@
+use core::net::Ipv4Addr;
+
+use super::error::ArpError;
+use super::parser::MacAddress;
+
+/// Safe byte reader for ARP packets.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::arp::reader::ArpReader;
+///
+/// let reader = ArpReader::new(&[0, 1, 8, 0, 6, 4]);
+/// assert_eq!(reader.read_u16_be(2..4).unwrap(), 0x0800);
+/// assert_eq!(reader.read_u8(4).unwrap(), 6);
+/// ```
+pub struct ArpReader<'a> {
+    payload: &'a [u8],
+}
+
+impl<'a> ArpReader<'a> {
+    pub fn new(payload: &'a [u8]) -> Self {
+        Self { payload }
+    }
+
+    /// Read a single byte at the given offset.
+    pub fn read_u8(&self, offset: usize) -> Result<u8, ArpError> {
+        self.payload.get(offset).copied().ok_or(ArpError::TooShort {
+            needed: offset + 1,
+            actual: self.payload.len(),
+        })
+    }
+
+    /// Read a big-endian `u16` from the given range.
+    pub fn read_u16_be(&self, range: core::ops::Range<usize>) -> Result<u16, ArpError> {
+        Ok(u16::from_be_bytes(self.read_array(range)?))
+    }
+
+    /// Read a hardware address from the given range.
+    pub fn read_mac(&self, range: core::ops::Range<usize>) -> Result<MacAddress, ArpError> {
+        Ok(MacAddress(self.read_array(range)?))
+    }
+
+    /// Read an IPv4 address from the given range.
+    pub fn read_ipv4(&self, range: core::ops::Range<usize>) -> Result<Ipv4Addr, ArpError> {
+        Ok(Ipv4Addr::from(self.read_array::<4>(range)?))
+    }
+
+    fn read_array<const N: usize>(
+        &self,
+        range: core::ops::Range<usize>,
+    ) -> Result<[u8; N], ArpError> {
+        let bytes = self.payload.get(range.clone()).ok_or(ArpError::TooShort {
+            needed: range.end,
+            actual: self.payload.len(),
+        })?;
+        bytes.try_into().map_err(|_| ArpError::TooShort {
+            needed: N,
+            actual: bytes.len(),
+        })
+    }
+}
@@ -0,0 +1,97 @@
+use alloc::string::String;
+use core::fmt;
+
+use super::layout;
+
+/// Errors returned by Art-Net parsing and reading.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::ArtNetError;
+///
+/// let err = ArtNetError::InvalidDmxLength { len: 0 };
+/// assert!(err.to_string().contains("invalid DMX length"));
+/// assert_eq!(err.offset(), 16);
+/// ```
+#[derive(Debug)]
+pub enum ArtNetError {
+    TooShort { needed: usize, actual: usize },
+    InvalidDmxLength { len: usize },
+    InvalidUniverseId { value: u16 },
+    UnsupportedOpCode { opcode: u16 },
+}
+
+impl ArtNetError {
+    /// Payload offset of the field the error is about; 0 for truncated
+    /// payloads.
+    pub fn offset(&self) -> usize {
+        match self {
+            Self::TooShort { .. } => 0,
+            Self::InvalidDmxLength { .. } => layout::LENGTH_RANGE.start,
+            Self::InvalidUniverseId { .. } => layout::UNIVERSE_RANGE.start,
+            Self::UnsupportedOpCode { .. } => layout::OP_CODE_RANGE.start,
+        }
+    }
+}
+
+impl fmt::Display for ArtNetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooShort { needed, actual } => {
+                write!(f, "payload too short: need {needed} bytes, got {actual}")
+            }
+            Self::InvalidDmxLength { len } => {
+                write!(f, "invalid DMX length: {len} (expected even, 2..=512)")
+            }
+            Self::InvalidUniverseId { value } => write!(f, "invalid Art-Net universe id: {value}"),
+            Self::UnsupportedOpCode { opcode } => {
+                write!(f, "unsupported Art-Net opcode: {opcode}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ArtNetError {}
+
+/// Errors returned when building or parsing an Art-Net Port-Address.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::{PortAddress, PortAddressError};
+///
+/// let err = PortAddress::new(0, 16, 0).unwrap_err();
+/// assert!(matches!(err, PortAddressError::InvalidParts { sub_net: 16, .. }));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PortAddressError {
+    OutOfRange { value: u16 },
+    InvalidParts { net: u8, sub_net: u8, universe: u8 },
+    InvalidText { value: String },
+}
+
+impl fmt::Display for PortAddressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfRange { value } => {
+                write!(
+                    f,
+                    "invalid Art-Net port address: {value} (expected 0..=32767)"
+                )
+            }
+            Self::InvalidParts {
+                net,
+                sub_net,
+                universe,
+            } => write!(
+                f,
+                "invalid Art-Net port address {net}:{sub_net}:{universe} (expected net 0..=127, sub-net and universe 0..=15)"
+            ),
+            Self::InvalidText { value } => write!(
+                f,
+                "invalid Art-Net port address text: {value:?} (expected net:sub-net:universe or 0..=32767)"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for PortAddressError {}
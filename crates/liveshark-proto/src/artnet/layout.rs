@@ -0,0 +1,111 @@
+pub const ARTNET_ID: &[u8; 8] = b"Art-Net\0";
+
+pub const OP_CODE_RANGE: core::ops::Range<usize> = 8..10;
+pub const PROTOCOL_VERSION_RANGE: core::ops::Range<usize> = 10..12;
+/// Lowest protocol revision of Art-Net 4 devices (`ProtVerLo` 14).
+pub const PROTOCOL_VERSION_MIN: u16 = 14;
+pub const SEQUENCE_OFFSET: usize = 12;
+pub const PHYSICAL_OFFSET: usize = 13;
+/// ArtNzs carries its start code where ArtDMX has the physical port.
+pub const NZS_START_CODE_OFFSET: usize = 13;
+pub const SUB_UNI_OFFSET: usize = 14;
+pub const NET_OFFSET: usize = 15;
+pub const UNIVERSE_RANGE: core::ops::Range<usize> = 14..16;
+pub const LENGTH_RANGE: core::ops::Range<usize> = 16..18;
+pub const DMX_DATA_OFFSET: usize = 18;
+pub const DMX_MAX_SLOTS: usize = 512;
+
+pub const ARTDMX_OPCODE: u16 = 0x5000;
+pub const ARTPOLLREPLY_OPCODE: u16 = 0x2100;
+pub const ARTNZS_OPCODE: u16 = 0x5100;
+pub const ARTSYNC_OPCODE: u16 = 0x5200;
+pub const ARTTIMECODE_OPCODE: u16 = 0x9700;
+pub const ARTTRIGGER_OPCODE: u16 = 0x9900;
+pub const ARTADDRESS_OPCODE: u16 = 0x6000;
+pub const ARTTODREQUEST_OPCODE: u16 = 0x8000;
+pub const ARTTODDATA_OPCODE: u16 = 0x8100;
+pub const ARTRDM_OPCODE: u16 = 0x8300;
+/// ArtSync length: ID, opcode, protocol version, and the two `Aux` bytes.
+pub const ARTSYNC_LEN: usize = 14;
+
+// ArtTimeCode fields.
+pub const TIMECODE_STREAM_ID_OFFSET: usize = 13;
+pub const TIMECODE_FRAMES_OFFSET: usize = 14;
+pub const TIMECODE_SECONDS_OFFSET: usize = 15;
+pub const TIMECODE_MINUTES_OFFSET: usize = 16;
+pub const TIMECODE_HOURS_OFFSET: usize = 17;
+pub const TIMECODE_TYPE_OFFSET: usize = 18;
+pub const ARTTIMECODE_LEN: usize = 19;
+
+// ArtTrigger fields.
+pub const TRIGGER_OEM_RANGE: core::ops::Range<usize> = 14..16;
+pub const TRIGGER_KEY_OFFSET: usize = 16;
+pub const TRIGGER_SUB_KEY_OFFSET: usize = 17;
+/// ArtTrigger length up to `SubKey`; the trailing `Data` is not read.
+pub const ARTTRIGGER_MIN_LEN: usize = 18;
+
+// ArtAddress fields.
+pub const ADDRESS_NET_SWITCH_OFFSET: usize = 12;
+pub const ADDRESS_BIND_INDEX_OFFSET: usize = 13;
+pub const ADDRESS_SHORT_NAME_RANGE: core::ops::Range<usize> = 14..32;
+pub const ADDRESS_LONG_NAME_RANGE: core::ops::Range<usize> = 32..96;
+pub const ADDRESS_SW_IN_RANGE: core::ops::Range<usize> = 96..100;
+pub const ADDRESS_SW_OUT_RANGE: core::ops::Range<usize> = 100..104;
+pub const ADDRESS_SUB_SWITCH_OFFSET: usize = 104;
+pub const ADDRESS_COMMAND_OFFSET: usize = 106;
+pub const ARTADDRESS_LEN: usize = 107;
+/// Bit set in a switch value of ArtAddress to program it.
+pub const ADDRESS_PROGRAM_BIT: u8 = 0x80;
+
+// RDM packets (ArtTodRequest, ArtTodData, ArtRdm).
+pub const RDM_VERSION_OFFSET: usize = 12;
+pub const RDM_NET_OFFSET: usize = 21;
+pub const RDM_COMMAND_OFFSET: usize = 22;
+/// Low byte of the Port-Address (Sub-Net and Universe) in ArtTodData and
+/// ArtRdm.
+pub const RDM_ADDRESS_OFFSET: usize = 23;
+pub const TOD_REQUEST_AD_COUNT_OFFSET: usize = 23;
+pub const TOD_REQUEST_ADDRESS_OFFSET: usize = 24;
+pub const TOD_REQUEST_MAX_ADDRESSES: usize = 32;
+pub const TOD_PORT_OFFSET: usize = 13;
+pub const TOD_BIND_INDEX_OFFSET: usize = 20;
+pub const TOD_UID_TOTAL_RANGE: core::ops::Range<usize> = 24..26;
+pub const TOD_BLOCK_COUNT_OFFSET: usize = 26;
+pub const TOD_UID_COUNT_OFFSET: usize = 27;
+pub const TOD_UIDS_OFFSET: usize = 28;
+pub const RDM_PACKET_OFFSET: usize = 24;
+pub const RDM_UID_LEN: usize = 6;
+
+// RDM message (ANSI E1.20) carried by ArtRdm, after the optional start code.
+pub const RDM_START_CODE: u8 = 0xcc;
+pub const RDM_DESTINATION_RANGE: core::ops::Range<usize> = 2..8;
+pub const RDM_SOURCE_RANGE: core::ops::Range<usize> = 8..14;
+pub const RDM_TRANSACTION_OFFSET: usize = 14;
+pub const RDM_PORT_OR_RESPONSE_OFFSET: usize = 15;
+pub const RDM_MESSAGE_COUNT_OFFSET: usize = 16;
+pub const RDM_SUB_DEVICE_RANGE: core::ops::Range<usize> = 17..19;
+pub const RDM_COMMAND_CLASS_OFFSET: usize = 19;
+pub const RDM_PID_RANGE: core::ops::Range<usize> = 20..22;
+pub const RDM_PDL_OFFSET: usize = 22;
+/// RDM message header length, up to the parameter data length.
+pub const RDM_HEADER_LEN: usize = 23;
+
+// ArtPollReply fields (the opcode directly follows the ID: no protocol version).
+pub const POLL_REPLY_IP_RANGE: core::ops::Range<usize> = 10..14;
+pub const POLL_REPLY_NET_SWITCH_OFFSET: usize = 18;
+pub const POLL_REPLY_SUB_SWITCH_OFFSET: usize = 19;
+pub const POLL_REPLY_SHORT_NAME_RANGE: core::ops::Range<usize> = 26..44;
+pub const POLL_REPLY_LONG_NAME_RANGE: core::ops::Range<usize> = 44..108;
+pub const POLL_REPLY_NUM_PORTS_RANGE: core::ops::Range<usize> = 172..174;
+pub const POLL_REPLY_PORT_TYPES_RANGE: core::ops::Range<usize> = 174..178;
+pub const POLL_REPLY_SW_OUT_RANGE: core::ops::Range<usize> = 190..194;
+pub const POLL_REPLY_BIND_INDEX_OFFSET: usize = 211;
+/// Port type bit set when the port outputs DMX from the network.
+pub const PORT_TYPE_OUTPUT: u8 = 0x80;
+/// Shortest accepted ArtPollReply (Art-Net 3, up to the MAC address); later
+/// fields are read when present.
+pub const POLL_REPLY_MIN_LEN: usize = 207;
+
+pub const PORT_ADDRESS_MAX: u16 = 0x7fff;
+pub const PORT_ADDRESS_NET_MAX: u8 = 0x7f;
+pub const PORT_ADDRESS_NIBBLE_MAX: u8 = 0x0f;
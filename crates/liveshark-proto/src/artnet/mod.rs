@@ -0,0 +1,32 @@
+//! Art-Net protocol decoding.
+//!
+//! The parser validates the Art-Net signature and opcode, then decodes ArtDMX
+//! payloads into domain-friendly structures. Length and universe constraints
+//! are enforced to avoid invalid frame reconstruction; ArtDMX length is
+//! required to be even and within 2..=512.
+//!
+//! Errors are explicit and actionable (e.g., invalid length, universe id, or
+//! unsupported opcode). Byte offsets and protocol conventions live in
+//! `layout` and `reader` respectively.
+//!
+//! Decoding (`reader` and the `parse_*` functions) is compiled with the
+//! `artnet` feature; frame types and `layout` are always available.
+//!
+pub mod error;
+pub mod layout;
+pub mod parser;
+pub mod port_address;
+pub mod rdm;
+#[cfg(feature = "artnet")]
+pub mod reader;
+
+pub use parser::{ArtAddress, ArtDmx, ArtNzs, ArtPollReply, ArtSync, ArtTimeCode, ArtTrigger};
+#[cfg(feature = "artnet")]
+pub use parser::{
+    parse_artaddress, parse_artdmx, parse_artnzs, parse_artpollreply, parse_artsync,
+    parse_arttimecode, parse_arttrigger,
+};
+pub use port_address::PortAddress;
+pub use rdm::{ArtRdm, ArtTodData, ArtTodRequest, RdmMessage, RdmUid};
+#[cfg(feature = "artnet")]
+pub use rdm::{parse_artrdm, parse_arttoddata, parse_arttodrequest};
@@ -0,0 +1,964 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "artnet")]
+use super::error::ArtNetError;
+use super::layout;
+use super::port_address::PortAddress;
+#[cfg(feature = "artnet")]
+use super::reader::ArtNetReader;
+
+/// Parsed ArtDMX packet: header fields and raw slot data.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::ArtDmx;
+///
+/// let frame = ArtDmx {
+///     protocol_version: 14,
+///     sequence: Some(1),
+///     physical: 0,
+///     sub_uni: 0x21,
+///     net: 0x01,
+///     universe: 0x0121,
+///     slots: vec![1, 2, 3, 4],
+/// };
+/// assert_eq!(frame.slots.len(), 4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtDmx {
+    /// Art-Net protocol revision (`ProtVerHi`/`ProtVerLo`, 14 for Art-Net 4).
+    pub protocol_version: u16,
+    /// Sequence number; `None` when the sender disables sequencing (zero).
+    pub sequence: Option<u8>,
+    /// Physical input port the data originated from (informational only).
+    pub physical: u8,
+    /// Low byte of the Port-Address (`SubUni`: sub-net and universe nibbles).
+    pub sub_uni: u8,
+    /// High 7 bits of the Port-Address (`Net`).
+    pub net: u8,
+    /// 15-bit Port-Address (`Net` << 8 | `SubUni`).
+    pub universe: u16,
+    /// DMX slot values (start code excluded), 2..=512 bytes.
+    pub slots: Vec<u8>,
+}
+
+impl ArtDmx {
+    /// Port-Address split into net, sub-net, and universe.
+    ///
+    /// # Examples
+    /// ```
+    /// use liveshark_proto::{ArtDmx, PortAddress};
+    ///
+    /// let frame = ArtDmx {
+    ///     protocol_version: 14,
+    ///     sequence: None,
+    ///     physical: 0,
+    ///     sub_uni: 0x21,
+    ///     net: 0x01,
+    ///     universe: 0x0121,
+    ///     slots: vec![0, 0],
+    /// };
+    /// assert_eq!(frame.port_address(), PortAddress::new(1, 2, 1).unwrap());
+    /// ```
+    pub fn port_address(&self) -> PortAddress {
+        PortAddress::from_masked(self.universe)
+    }
+}
+
+/// Parsed ArtNzs packet: data sent with a non-zero start code (RDM, text,
+/// or manufacturer packets) on a universe.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::ArtNzs;
+///
+/// let packet = ArtNzs {
+///     protocol_version: 14,
+///     sequence: None,
+///     start_code: 0x17,
+///     sub_uni: 0x01,
+///     net: 0,
+///     universe: 1,
+///     data: b"Cue 12".to_vec(),
+/// };
+/// assert_eq!(packet.start_code, 0x17);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtNzs {
+    /// Art-Net protocol revision (`ProtVerHi`/`ProtVerLo`, 14 for Art-Net 4).
+    pub protocol_version: u16,
+    /// Sequence number; `None` when the sender disables sequencing (zero).
+    pub sequence: Option<u8>,
+    /// Start code of the data (non-zero; zero is ArtDMX).
+    pub start_code: u8,
+    /// Low byte of the Port-Address (`SubUni`: sub-net and universe nibbles).
+    pub sub_uni: u8,
+    /// High 7 bits of the Port-Address (`Net`).
+    pub net: u8,
+    /// 15-bit Port-Address (`Net` << 8 | `SubUni`).
+    pub universe: u16,
+    /// Data following the start code, 1..=512 bytes.
+    pub data: Vec<u8>,
+}
+
+/// Parsed ArtTimeCode packet: one timecode value of a stream.
+///
+/// Displays as `hh:mm:ss:ff`, with `;` before the frames for drop-frame
+/// timecode.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::ArtTimeCode;
+///
+/// let timecode = ArtTimeCode {
+///     protocol_version: 14,
+///     stream_id: 0,
+///     hours: 1,
+///     minutes: 2,
+///     seconds: 3,
+///     frames: 4,
+///     timecode_type: 1,
+/// };
+/// assert_eq!(timecode.to_string(), "01:02:03:04");
+/// assert_eq!(timecode.frame_rate(), Some(25.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArtTimeCode {
+    /// Art-Net protocol revision (`ProtVerHi`/`ProtVerLo`, 14 for Art-Net 4).
+    pub protocol_version: u16,
+    /// Stream identifier; 0 is the master stream.
+    pub stream_id: u8,
+    /// Hours, 0..=23.
+    pub hours: u8,
+    /// Minutes, 0..=59.
+    pub minutes: u8,
+    /// Seconds, 0..=59.
+    pub seconds: u8,
+    /// Frames, 0..rate.
+    pub frames: u8,
+    /// Timecode type: 0 Film (24 fps), 1 EBU (25), 2 DF (29.97 drop
+    /// frame), 3 SMPTE (30).
+    pub timecode_type: u8,
+}
+
+impl ArtTimeCode {
+    /// Nominal frame rate of the timecode type; `None` for unknown types.
+    pub fn frame_rate(&self) -> Option<f64> {
+        match self.timecode_type {
+            0 => Some(24.0),
+            1 => Some(25.0),
+            2 => Some(29.97),
+            3 => Some(30.0),
+            _ => None,
+        }
+    }
+
+    /// Whether the timecode uses drop-frame numbering.
+    pub fn is_drop_frame(&self) -> bool {
+        self.timecode_type == 2
+    }
+}
+
+impl core::fmt::Display for ArtTimeCode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let separator = if self.is_drop_frame() { ';' } else { ':' };
+        write!(
+            f,
+            "{:02}:{:02}:{:02}{separator}{:02}",
+            self.hours, self.minutes, self.seconds, self.frames
+        )
+    }
+}
+
+/// Parsed ArtTrigger packet: a remote trigger (key press, macro, show) sent
+/// to nodes.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::ArtTrigger;
+///
+/// let trigger = ArtTrigger {
+///     protocol_version: 14,
+///     oem: 0xffff,
+///     key: 1,
+///     sub_key: 12,
+/// };
+/// assert_eq!(trigger.key_name(), Some("macro"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArtTrigger {
+    /// Art-Net protocol revision (`ProtVerHi`/`ProtVerLo`, 14 for Art-Net 4).
+    pub protocol_version: u16,
+    /// OEM code the trigger is meant for; `0xffff` addresses every device.
+    pub oem: u16,
+    /// Trigger key; 0..=3 are defined when `oem` is `0xffff`.
+    pub key: u8,
+    /// Trigger sub-key: the character, macro, soft key, or show number.
+    pub sub_key: u8,
+}
+
+impl ArtTrigger {
+    /// Name of the key defined by Art-Net 4 for the general OEM code
+    /// (`ascii`, `macro`, `soft`, `show`); `None` for other keys and
+    /// manufacturer-specific triggers.
+    pub fn key_name(&self) -> Option<&'static str> {
+        if self.oem != 0xffff {
+            return None;
+        }
+        match self.key {
+            0 => Some("ascii"),
+            1 => Some("macro"),
+            2 => Some("soft"),
+            3 => Some("show"),
+            _ => None,
+        }
+    }
+}
+
+/// Parsed ArtAddress packet: a remote reconfiguration of a node.
+///
+/// Switch values (`net_switch`, `sub_switch`, `sw_in`, `sw_out`) program
+/// the node when bit 7 is set (the value is in the low bits), reset it to
+/// its hardware switches when 0, and leave it unchanged otherwise.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::ArtAddress;
+///
+/// let address = ArtAddress {
+///     protocol_version: 14,
+///     net_switch: 0x82,
+///     bind_index: 1,
+///     short_name: Some("Truss".to_string()),
+///     long_name: None,
+///     sw_in: [0x7f; 4],
+///     sw_out: [0x7f; 4],
+///     sub_switch: 0x7f,
+///     command: 0,
+/// };
+/// assert_eq!(ArtAddress::programmed(address.net_switch), Some(2));
+/// assert_eq!(ArtAddress::programmed(address.sub_switch), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtAddress {
+    /// Art-Net protocol revision (`ProtVerHi`/`ProtVerLo`, 14 for Art-Net 4).
+    pub protocol_version: u16,
+    /// `Net` switch value.
+    pub net_switch: u8,
+    /// Bind index of the addressed ports (0 or 1 for the root device).
+    pub bind_index: u8,
+    /// New short name; `None` leaves it unchanged.
+    pub short_name: Option<String>,
+    /// New long name; `None` leaves it unchanged.
+    pub long_name: Option<String>,
+    /// `Universe` switch values of the input ports.
+    pub sw_in: [u8; 4],
+    /// `Universe` switch values of the output ports.
+    pub sw_out: [u8; 4],
+    /// `Sub-Net` switch value.
+    pub sub_switch: u8,
+    /// Node command (merge mode, cancel merge, LED, ...); 0 is no command.
+    pub command: u8,
+}
+
+impl ArtAddress {
+    /// Value a switch byte programs, or `None` when it does not program one.
+    pub fn programmed(switch: u8) -> Option<u8> {
+        (switch & layout::ADDRESS_PROGRAM_BIT != 0).then_some(switch & !layout::ADDRESS_PROGRAM_BIT)
+    }
+}
+
+/// Parsed ArtPollReply packet: the identity and output ports of a node.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::ArtPollReply;
+/// use core::net::Ipv4Addr;
+///
+/// let reply = ArtPollReply {
+///     ip: Ipv4Addr::new(2, 0, 0, 10),
+///     short_name: Some("Node 1".to_string()),
+///     long_name: None,
+///     net_switch: 0,
+///     sub_switch: 0,
+///     outputs: vec![0, 1],
+///     bind_index: 1,
+/// };
+/// assert_eq!(reply.outputs.len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtPollReply {
+    /// IP address the node announces.
+    pub ip: core::net::Ipv4Addr,
+    /// Short name; `None` when empty.
+    pub short_name: Option<String>,
+    /// Long name; `None` when empty.
+    pub long_name: Option<String>,
+    /// `Net` bits of the port addresses.
+    pub net_switch: u8,
+    /// `Sub-Net` bits of the port addresses.
+    pub sub_switch: u8,
+    /// Port-Addresses of the ports outputting DMX from the network.
+    pub outputs: Vec<u16>,
+    /// Index of the reply among the replies of one device (0 or 1 for the
+    /// root device; 0 when the packet predates the field).
+    pub bind_index: u8,
+}
+
+/// Parsed ArtSync packet: the trigger to output the ArtDMX data received
+/// since the previous ArtSync.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::ArtSync;
+///
+/// let sync = ArtSync { protocol_version: 14 };
+/// assert_eq!(sync.protocol_version, 14);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArtSync {
+    /// Art-Net protocol revision (`ProtVerHi`/`ProtVerLo`, 14 for Art-Net 4).
+    pub protocol_version: u16,
+}
+
+/// Parse an ArtSync payload from a UDP payload.
+///
+/// Returns `Ok(None)` when the payload is not an Art-Net ArtSync.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::parse_artsync;
+///
+/// let mut payload = b"Art-Net\0".to_vec();
+/// payload.extend_from_slice(&0x5200u16.to_le_bytes()); // OpSync
+/// payload.extend_from_slice(&[0, 14, 0, 0]); // protocol version, Aux1, Aux2
+///
+/// let sync = parse_artsync(&payload)?.expect("artsync");
+/// assert_eq!(sync.protocol_version, 14);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// Returns `ArtNetError::TooShort` when an ArtSync is truncated.
+#[cfg(feature = "artnet")]
+pub fn parse_artsync(payload: &[u8]) -> Result<Option<ArtSync>, ArtNetError> {
+    let reader = ArtNetReader::new(payload);
+    let is_sync = reader
+        .read_signature()
+        .is_ok_and(|signature| signature == layout::ARTNET_ID)
+        && reader
+            .read_u16_le(layout::OP_CODE_RANGE.clone())
+            .is_ok_and(|opcode| opcode == layout::ARTSYNC_OPCODE);
+    if !is_sync {
+        return Ok(None);
+    }
+    reader.require_len(layout::ARTSYNC_LEN)?;
+
+    Ok(Some(ArtSync {
+        protocol_version: reader.read_u16_be(layout::PROTOCOL_VERSION_RANGE.clone())?,
+    }))
+}
+
+/// Parse an ArtPollReply payload from a UDP payload.
+///
+/// Returns `Ok(None)` when the payload is not an Art-Net ArtPollReply.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::parse_artpollreply;
+///
+/// let mut payload = vec![0u8; 239];
+/// payload[..8].copy_from_slice(b"Art-Net\0");
+/// payload[8..10].copy_from_slice(&0x2100u16.to_le_bytes()); // OpPollReply
+/// payload[10..14].copy_from_slice(&[2, 0, 0, 10]); // IP address
+/// payload[26..30].copy_from_slice(b"Node"); // short name
+/// payload[173] = 1; // one port
+/// payload[174] = 0x80; // port 1 outputs DMX
+/// payload[190] = 3; // SwOut of port 1
+///
+/// let reply = parse_artpollreply(&payload)?.expect("artpollreply");
+/// assert_eq!(reply.short_name.as_deref(), Some("Node"));
+/// assert_eq!(reply.outputs, vec![3]);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// Returns `ArtNetError::TooShort` when an ArtPollReply is truncated.
+#[cfg(feature = "artnet")]
+pub fn parse_artpollreply(payload: &[u8]) -> Result<Option<ArtPollReply>, ArtNetError> {
+    let reader = ArtNetReader::new(payload);
+    let is_reply = reader
+        .read_signature()
+        .is_ok_and(|signature| signature == layout::ARTNET_ID)
+        && reader
+            .read_u16_le(layout::OP_CODE_RANGE.clone())
+            .is_ok_and(|opcode| opcode == layout::ARTPOLLREPLY_OPCODE);
+    if !is_reply {
+        return Ok(None);
+    }
+    reader.require_len(layout::POLL_REPLY_MIN_LEN)?;
+
+    let ip = reader.read_slice(layout::POLL_REPLY_IP_RANGE.clone())?;
+    let net_switch = reader.read_u8(layout::POLL_REPLY_NET_SWITCH_OFFSET)? & 0x7f;
+    let sub_switch = reader.read_u8(layout::POLL_REPLY_SUB_SWITCH_OFFSET)? & 0x0f;
+    let ports = reader.read_u16_be(layout::POLL_REPLY_NUM_PORTS_RANGE.clone())?;
+    let port_types = reader.read_slice(layout::POLL_REPLY_PORT_TYPES_RANGE.clone())?;
+    let sw_out = reader.read_slice(layout::POLL_REPLY_SW_OUT_RANGE.clone())?;
+    let outputs = port_types
+        .iter()
+        .zip(sw_out)
+        .take(usize::from(ports))
+        .filter(|(port_type, _)| *port_type & layout::PORT_TYPE_OUTPUT != 0)
+        .map(|(_, universe)| {
+            (u16::from(net_switch) << 8) | (u16::from(sub_switch) << 4) | u16::from(universe & 0x0f)
+        })
+        .collect();
+
+    Ok(Some(ArtPollReply {
+        ip: core::net::Ipv4Addr::new(ip[0], ip[1], ip[2], ip[3]),
+        short_name: reader.read_name(layout::POLL_REPLY_SHORT_NAME_RANGE.clone())?,
+        long_name: reader.read_name(layout::POLL_REPLY_LONG_NAME_RANGE.clone())?,
+        net_switch,
+        sub_switch,
+        outputs,
+        bind_index: reader
+            .read_u8(layout::POLL_REPLY_BIND_INDEX_OFFSET)
+            .unwrap_or_default(),
+    }))
+}
+
+/// Parse an ArtNzs payload from a UDP payload.
+///
+/// Returns `Ok(None)` when the payload is not an Art-Net ArtNzs. Returns
+/// `Err` for malformed ArtNzs packets.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::parse_artnzs;
+///
+/// let mut payload = b"Art-Net\0".to_vec();
+/// payload.extend_from_slice(&0x5100u16.to_le_bytes()); // OpNzs
+/// payload.extend_from_slice(&[0, 14]); // protocol version
+/// payload.extend_from_slice(&[0x01, 0xcc]); // sequence, start code (RDM)
+/// payload.extend_from_slice(&[0x01, 0x00]); // SubUni, Net
+/// payload.extend_from_slice(&3u16.to_be_bytes()); // length
+/// payload.extend_from_slice(&[1, 2, 3]);
+///
+/// let parsed = parse_artnzs(&payload)?.expect("artnzs");
+/// assert_eq!(parsed.universe, 1);
+/// assert_eq!(parsed.start_code, 0xcc);
+/// assert_eq!(parsed.data, vec![1, 2, 3]);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// Returns `ArtNetError` when the payload is too short, the universe id is
+/// out of range, or the length field is outside 1..=512.
+#[cfg(feature = "artnet")]
+pub fn parse_artnzs(payload: &[u8]) -> Result<Option<ArtNzs>, ArtNetError> {
+    let reader = ArtNetReader::new(payload);
+    let is_nzs = reader
+        .read_signature()
+        .is_ok_and(|signature| signature == layout::ARTNET_ID)
+        && reader
+            .read_u16_le(layout::OP_CODE_RANGE.clone())
+            .is_ok_and(|opcode| opcode == layout::ARTNZS_OPCODE);
+    if !is_nzs {
+        return Ok(None);
+    }
+    reader.require_len(layout::DMX_DATA_OFFSET)?;
+
+    let protocol_version = reader.read_u16_be(layout::PROTOCOL_VERSION_RANGE.clone())?;
+    let sequence = reader.read_optional_nonzero_u8(layout::SEQUENCE_OFFSET)?;
+    let start_code = reader.read_u8(layout::NZS_START_CODE_OFFSET)?;
+    let sub_uni = reader.read_u8(layout::SUB_UNI_OFFSET)?;
+    let net = reader.read_u8(layout::NET_OFFSET)?;
+    let port_address = reader.read_port_address(layout::UNIVERSE_RANGE.clone())?;
+    let data_len = usize::from(reader.read_u16_be(layout::LENGTH_RANGE.clone())?);
+    if !(1..=layout::DMX_MAX_SLOTS).contains(&data_len) {
+        return Err(ArtNetError::InvalidDmxLength { len: data_len });
+    }
+    let needed = layout::DMX_DATA_OFFSET + data_len;
+    reader.require_len(needed)?;
+    let data = reader.read_slice(layout::DMX_DATA_OFFSET..needed)?.to_vec();
+
+    Ok(Some(ArtNzs {
+        protocol_version,
+        sequence,
+        start_code,
+        sub_uni,
+        net,
+        universe: port_address.raw(),
+        data,
+    }))
+}
+
+/// Parse an ArtTimeCode payload from a UDP payload.
+///
+/// Returns `Ok(None)` when the payload is not an Art-Net ArtTimeCode.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::parse_arttimecode;
+///
+/// let mut payload = b"Art-Net\0".to_vec();
+/// payload.extend_from_slice(&0x9700u16.to_le_bytes()); // OpTimeCode
+/// payload.extend_from_slice(&[0, 14, 0, 0]); // protocol version, filler, stream
+/// payload.extend_from_slice(&[24, 59, 59, 9, 3]); // frames, s, min, h, SMPTE
+///
+/// let timecode = parse_arttimecode(&payload)?.expect("arttimecode");
+/// assert_eq!(timecode.to_string(), "09:59:59:24");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// Returns `ArtNetError::TooShort` when an ArtTimeCode is truncated.
+#[cfg(feature = "artnet")]
+pub fn parse_arttimecode(payload: &[u8]) -> Result<Option<ArtTimeCode>, ArtNetError> {
+    let reader = ArtNetReader::new(payload);
+    let is_timecode = reader
+        .read_signature()
+        .is_ok_and(|signature| signature == layout::ARTNET_ID)
+        && reader
+            .read_u16_le(layout::OP_CODE_RANGE.clone())
+            .is_ok_and(|opcode| opcode == layout::ARTTIMECODE_OPCODE);
+    if !is_timecode {
+        return Ok(None);
+    }
+    reader.require_len(layout::ARTTIMECODE_LEN)?;
+
+    Ok(Some(ArtTimeCode {
+        protocol_version: reader.read_u16_be(layout::PROTOCOL_VERSION_RANGE.clone())?,
+        stream_id: reader.read_u8(layout::TIMECODE_STREAM_ID_OFFSET)?,
+        hours: reader.read_u8(layout::TIMECODE_HOURS_OFFSET)?,
+        minutes: reader.read_u8(layout::TIMECODE_MINUTES_OFFSET)?,
+        seconds: reader.read_u8(layout::TIMECODE_SECONDS_OFFSET)?,
+        frames: reader.read_u8(layout::TIMECODE_FRAMES_OFFSET)?,
+        timecode_type: reader.read_u8(layout::TIMECODE_TYPE_OFFSET)?,
+    }))
+}
+
+/// Parse an ArtTrigger payload from a UDP payload.
+///
+/// Returns `Ok(None)` when the payload is not an Art-Net ArtTrigger. The
+/// trailing `Data` bytes are not read.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::parse_arttrigger;
+///
+/// let mut payload = b"Art-Net\0".to_vec();
+/// payload.extend_from_slice(&0x9900u16.to_le_bytes()); // OpTrigger
+/// payload.extend_from_slice(&[0, 14, 0, 0]); // protocol version, filler
+/// payload.extend_from_slice(&[0xff, 0xff, 3, 2]); // OEM, KeyShow, show 2
+/// payload.extend_from_slice(&[0; 512]); // data
+///
+/// let trigger = parse_arttrigger(&payload)?.expect("arttrigger");
+/// assert_eq!(trigger.key_name(), Some("show"));
+/// assert_eq!(trigger.sub_key, 2);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// Returns `ArtNetError::TooShort` when an ArtTrigger ends before `SubKey`.
+#[cfg(feature = "artnet")]
+pub fn parse_arttrigger(payload: &[u8]) -> Result<Option<ArtTrigger>, ArtNetError> {
+    let reader = ArtNetReader::new(payload);
+    let is_trigger = reader
+        .read_signature()
+        .is_ok_and(|signature| signature == layout::ARTNET_ID)
+        && reader
+            .read_u16_le(layout::OP_CODE_RANGE.clone())
+            .is_ok_and(|opcode| opcode == layout::ARTTRIGGER_OPCODE);
+    if !is_trigger {
+        return Ok(None);
+    }
+    reader.require_len(layout::ARTTRIGGER_MIN_LEN)?;
+
+    Ok(Some(ArtTrigger {
+        protocol_version: reader.read_u16_be(layout::PROTOCOL_VERSION_RANGE.clone())?,
+        oem: reader.read_u16_be(layout::TRIGGER_OEM_RANGE.clone())?,
+        key: reader.read_u8(layout::TRIGGER_KEY_OFFSET)?,
+        sub_key: reader.read_u8(layout::TRIGGER_SUB_KEY_OFFSET)?,
+    }))
+}
+
+/// Parse an ArtAddress payload from a UDP payload.
+///
+/// Returns `Ok(None)` when the payload is not an Art-Net ArtAddress.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::parse_artaddress;
+///
+/// let mut payload = vec![0u8; 107];
+/// payload[..8].copy_from_slice(b"Art-Net\0");
+/// payload[8..10].copy_from_slice(&0x6000u16.to_le_bytes()); // OpAddress
+/// payload[14..19].copy_from_slice(b"Truss"); // short name
+/// payload[104] = 0x83; // program Sub-Net 3
+///
+/// let address = parse_artaddress(&payload)?.expect("artaddress");
+/// assert_eq!(address.short_name.as_deref(), Some("Truss"));
+/// assert_eq!(address.sub_switch, 0x83);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// Returns `ArtNetError::TooShort` when an ArtAddress is truncated.
+#[cfg(feature = "artnet")]
+pub fn parse_artaddress(payload: &[u8]) -> Result<Option<ArtAddress>, ArtNetError> {
+    let reader = ArtNetReader::new(payload);
+    let is_address = reader
+        .read_signature()
+        .is_ok_and(|signature| signature == layout::ARTNET_ID)
+        && reader
+            .read_u16_le(layout::OP_CODE_RANGE.clone())
+            .is_ok_and(|opcode| opcode == layout::ARTADDRESS_OPCODE);
+    if !is_address {
+        return Ok(None);
+    }
+    reader.require_len(layout::ARTADDRESS_LEN)?;
+
+    let mut sw_in = [0u8; 4];
+    sw_in.copy_from_slice(reader.read_slice(layout::ADDRESS_SW_IN_RANGE.clone())?);
+    let mut sw_out = [0u8; 4];
+    sw_out.copy_from_slice(reader.read_slice(layout::ADDRESS_SW_OUT_RANGE.clone())?);
+    Ok(Some(ArtAddress {
+        protocol_version: reader.read_u16_be(layout::PROTOCOL_VERSION_RANGE.clone())?,
+        net_switch: reader.read_u8(layout::ADDRESS_NET_SWITCH_OFFSET)?,
+        bind_index: reader.read_u8(layout::ADDRESS_BIND_INDEX_OFFSET)?,
+        short_name: reader.read_name(layout::ADDRESS_SHORT_NAME_RANGE.clone())?,
+        long_name: reader.read_name(layout::ADDRESS_LONG_NAME_RANGE.clone())?,
+        sw_in,
+        sw_out,
+        sub_switch: reader.read_u8(layout::ADDRESS_SUB_SWITCH_OFFSET)?,
+        command: reader.read_u8(layout::ADDRESS_COMMAND_OFFSET)?,
+    }))
+}
+
+/// Parse an ArtDMX payload from a UDP payload.
+///
+/// Returns `Ok(None)` when the payload is not Art-Net. Returns `Err` for
+/// malformed Art-Net packets.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::parse_artdmx;
+///
+/// let mut payload = b"Art-Net\0".to_vec();
+/// payload.extend_from_slice(&0x5000u16.to_le_bytes()); // OpDmx
+/// payload.extend_from_slice(&[0, 14]); // protocol version
+/// payload.extend_from_slice(&[0x01, 0x00]); // sequence, physical
+/// payload.extend_from_slice(&[0x01, 0x00]); // SubUni, Net
+/// payload.extend_from_slice(&4u16.to_be_bytes()); // length
+/// payload.extend_from_slice(&[1, 2, 3, 4]);
+///
+/// let parsed = parse_artdmx(&payload)?.expect("artdmx");
+/// assert_eq!(parsed.universe, 1);
+/// assert_eq!(parsed.protocol_version, 14);
+/// assert_eq!(parsed.slots.len(), 4);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// Returns `ArtNetError` when the payload is too short, the opcode is not
+/// supported, the universe id is out of range, or the length field is invalid.
+#[cfg(feature = "artnet")]
+pub fn parse_artdmx(payload: &[u8]) -> Result<Option<ArtDmx>, ArtNetError> {
+    let reader = ArtNetReader::new(payload);
+    reader.require_len(layout::DMX_DATA_OFFSET)?;
+
+    let signature = reader.read_signature()?;
+    if signature != layout::ARTNET_ID {
+        return Ok(None);
+    }
+
+    let opcode = reader.read_u16_le(layout::OP_CODE_RANGE.clone())?;
+    if opcode != layout::ARTDMX_OPCODE {
+        return Err(ArtNetError::UnsupportedOpCode { opcode });
+    }
+
+    let protocol_version = reader.read_u16_be(layout::PROTOCOL_VERSION_RANGE.clone())?;
+    let sequence = reader.read_optional_nonzero_u8(layout::SEQUENCE_OFFSET)?;
+    let physical = reader.read_u8(layout::PHYSICAL_OFFSET)?;
+    let sub_uni = reader.read_u8(layout::SUB_UNI_OFFSET)?;
+    let net = reader.read_u8(layout::NET_OFFSET)?;
+    let port_address = reader.read_port_address(layout::UNIVERSE_RANGE.clone())?;
+    let data_len = reader.read_dmx_length(layout::LENGTH_RANGE.clone())?;
+    let needed = layout::DMX_DATA_OFFSET
+        .checked_add(data_len)
+        .ok_or(ArtNetError::InvalidDmxLength { len: data_len })?;
+    reader.require_len(needed)?;
+    let data = reader.read_slice(layout::DMX_DATA_OFFSET..needed)?;
+    let slots = data.to_vec();
+
+    Ok(Some(ArtDmx {
+        protocol_version,
+        sequence,
+        physical,
+        sub_uni,
+        net,
+        universe: port_address.raw(),
+        slots,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ArtAddress, parse_artaddress, parse_artdmx, parse_artnzs, parse_artpollreply,
+        parse_artsync, parse_arttimecode, parse_arttrigger,
+    };
+    use crate::artnet::error::ArtNetError;
+    use crate::artnet::layout;
+
+    #[test]
+    fn parse_valid_artdmx() {
+        let length = 4u16;
+        let mut payload = vec![0u8; layout::DMX_DATA_OFFSET + length as usize];
+        payload[..layout::ARTNET_ID.len()].copy_from_slice(layout::ARTNET_ID);
+        payload[layout::OP_CODE_RANGE.clone()]
+            .copy_from_slice(&layout::ARTDMX_OPCODE.to_le_bytes());
+        payload[layout::PROTOCOL_VERSION_RANGE.clone()].copy_from_slice(&14u16.to_be_bytes());
+        payload[layout::SEQUENCE_OFFSET] = 0x12;
+        payload[layout::PHYSICAL_OFFSET] = 0x03;
+        payload[layout::UNIVERSE_RANGE.clone()].copy_from_slice(&0x0121u16.to_le_bytes());
+        payload[layout::LENGTH_RANGE.clone()].copy_from_slice(&length.to_be_bytes());
+        payload[layout::DMX_DATA_OFFSET..layout::DMX_DATA_OFFSET + 4]
+            .copy_from_slice(&[1, 2, 3, 4]);
+
+        let parsed = parse_artdmx(&payload).unwrap();
+        assert!(parsed.is_some());
+        let parsed = parsed.unwrap();
+        assert_eq!(parsed.protocol_version, 14);
+        assert_eq!(parsed.physical, 0x03);
+        assert_eq!(parsed.sub_uni, 0x21);
+        assert_eq!(parsed.net, 0x01);
+        assert_eq!(parsed.universe, 0x0121);
+        assert_eq!(parsed.port_address().to_string(), "1:2:1");
+        assert_eq!(parsed.sequence, Some(0x12));
+        assert_eq!(&parsed.slots[..4], &[1, 2, 3, 4]);
+        assert_eq!(parsed.slots.len(), 4);
+    }
+
+    #[test]
+    fn parse_artpollreply_output_ports() {
+        let mut payload = vec![0u8; layout::POLL_REPLY_MIN_LEN];
+        payload[..layout::ARTNET_ID.len()].copy_from_slice(layout::ARTNET_ID);
+        payload[layout::OP_CODE_RANGE.clone()]
+            .copy_from_slice(&layout::ARTPOLLREPLY_OPCODE.to_le_bytes());
+        payload[layout::POLL_REPLY_IP_RANGE.clone()].copy_from_slice(&[10, 0, 0, 7]);
+        payload[layout::POLL_REPLY_NET_SWITCH_OFFSET] = 0x01;
+        payload[layout::POLL_REPLY_SUB_SWITCH_OFFSET] = 0x02;
+        payload[layout::POLL_REPLY_LONG_NAME_RANGE.start..][..8].copy_from_slice(b"Dimmers ");
+        payload[layout::POLL_REPLY_NUM_PORTS_RANGE.clone()].copy_from_slice(&3u16.to_be_bytes());
+        // Port 1 outputs, port 2 is an input, port 3 outputs, port 4 is unused.
+        payload[layout::POLL_REPLY_PORT_TYPES_RANGE.clone()]
+            .copy_from_slice(&[0x80, 0x40, 0xc0, 0x80]);
+        payload[layout::POLL_REPLY_SW_OUT_RANGE.clone()].copy_from_slice(&[0, 1, 2, 3]);
+
+        let reply = parse_artpollreply(&payload).unwrap().expect("artpollreply");
+        assert_eq!(reply.ip.to_string(), "10.0.0.7");
+        assert_eq!(reply.short_name, None);
+        assert_eq!(reply.long_name.as_deref(), Some("Dimmers"));
+        assert_eq!(reply.outputs, vec![0x0120, 0x0122]);
+        assert_eq!(reply.bind_index, 0);
+
+        assert!(matches!(
+            parse_artpollreply(&payload[..layout::POLL_REPLY_MIN_LEN - 1]),
+            Err(ArtNetError::TooShort { .. })
+        ));
+        assert!(parse_artdmx(&payload).is_err());
+    }
+
+    #[test]
+    fn parse_artnzs_packets() {
+        let length = 3u16;
+        let mut payload = vec![0u8; layout::DMX_DATA_OFFSET + length as usize];
+        payload[..layout::ARTNET_ID.len()].copy_from_slice(layout::ARTNET_ID);
+        payload[layout::OP_CODE_RANGE.clone()]
+            .copy_from_slice(&layout::ARTNZS_OPCODE.to_le_bytes());
+        payload[layout::SEQUENCE_OFFSET] = 0x05;
+        payload[layout::NZS_START_CODE_OFFSET] = 0x17;
+        payload[layout::UNIVERSE_RANGE.clone()].copy_from_slice(&0x0102u16.to_le_bytes());
+        payload[layout::LENGTH_RANGE.clone()].copy_from_slice(&length.to_be_bytes());
+        payload[layout::DMX_DATA_OFFSET..].copy_from_slice(b"Go!");
+
+        let nzs = parse_artnzs(&payload).unwrap().expect("artnzs");
+        assert_eq!(nzs.start_code, 0x17);
+        assert_eq!(nzs.sequence, Some(0x05));
+        assert_eq!(nzs.universe, 0x0102);
+        assert_eq!(nzs.data, b"Go!".to_vec());
+        assert!(matches!(
+            parse_artnzs(&payload[..payload.len() - 1]),
+            Err(ArtNetError::TooShort { .. })
+        ));
+        assert!(matches!(
+            parse_artdmx(&payload),
+            Err(ArtNetError::UnsupportedOpCode { .. })
+        ));
+
+        payload[layout::LENGTH_RANGE.clone()].copy_from_slice(&0u16.to_be_bytes());
+        assert!(matches!(
+            parse_artnzs(&payload),
+            Err(ArtNetError::InvalidDmxLength { len: 0 })
+        ));
+        payload[layout::OP_CODE_RANGE.clone()]
+            .copy_from_slice(&layout::ARTDMX_OPCODE.to_le_bytes());
+        assert_eq!(parse_artnzs(&payload).unwrap(), None);
+    }
+
+    #[test]
+    fn parse_arttimecode_packets() {
+        let mut payload = vec![0u8; layout::ARTTIMECODE_LEN];
+        payload[..layout::ARTNET_ID.len()].copy_from_slice(layout::ARTNET_ID);
+        payload[layout::OP_CODE_RANGE.clone()]
+            .copy_from_slice(&layout::ARTTIMECODE_OPCODE.to_le_bytes());
+        payload[layout::TIMECODE_STREAM_ID_OFFSET] = 2;
+        payload[layout::TIMECODE_FRAMES_OFFSET] = 2;
+        payload[layout::TIMECODE_SECONDS_OFFSET] = 0;
+        payload[layout::TIMECODE_MINUTES_OFFSET] = 1;
+        payload[layout::TIMECODE_HOURS_OFFSET] = 10;
+        payload[layout::TIMECODE_TYPE_OFFSET] = 2;
+
+        let timecode = parse_arttimecode(&payload).unwrap().expect("arttimecode");
+        assert_eq!(timecode.stream_id, 2);
+        assert_eq!(timecode.to_string(), "10:01:00;02");
+        assert_eq!(timecode.frame_rate(), Some(29.97));
+        assert!(matches!(
+            parse_arttimecode(&payload[..layout::ARTTIMECODE_LEN - 1]),
+            Err(ArtNetError::TooShort { .. })
+        ));
+
+        payload[layout::TIMECODE_TYPE_OFFSET] = 7;
+        let timecode = parse_arttimecode(&payload).unwrap().expect("arttimecode");
+        assert_eq!(timecode.frame_rate(), None);
+    }
+
+    #[test]
+    fn parse_arttrigger_packets() {
+        let mut payload = vec![0u8; layout::ARTTRIGGER_MIN_LEN];
+        payload[..layout::ARTNET_ID.len()].copy_from_slice(layout::ARTNET_ID);
+        payload[layout::OP_CODE_RANGE.clone()]
+            .copy_from_slice(&layout::ARTTRIGGER_OPCODE.to_le_bytes());
+        payload[layout::TRIGGER_OEM_RANGE.clone()].copy_from_slice(&[0x00, 0x7a]);
+        payload[layout::TRIGGER_KEY_OFFSET] = 1;
+        payload[layout::TRIGGER_SUB_KEY_OFFSET] = 9;
+
+        let trigger = parse_arttrigger(&payload).unwrap().expect("arttrigger");
+        assert_eq!(trigger.oem, 0x007a);
+        assert_eq!((trigger.key, trigger.sub_key), (1, 9));
+        // Key names only apply to the general OEM code.
+        assert_eq!(trigger.key_name(), None);
+        assert!(matches!(
+            parse_arttrigger(&payload[..layout::ARTTRIGGER_MIN_LEN - 1]),
+            Err(ArtNetError::TooShort { .. })
+        ));
+    }
+
+    #[test]
+    fn parse_artaddress_packets() {
+        let mut payload = vec![0u8; layout::ARTADDRESS_LEN];
+        payload[..layout::ARTNET_ID.len()].copy_from_slice(layout::ARTNET_ID);
+        payload[layout::OP_CODE_RANGE.clone()]
+            .copy_from_slice(&layout::ARTADDRESS_OPCODE.to_le_bytes());
+        payload[layout::ADDRESS_NET_SWITCH_OFFSET] = 0x7f;
+        payload[layout::ADDRESS_BIND_INDEX_OFFSET] = 2;
+        payload[layout::ADDRESS_SW_OUT_RANGE.clone()].copy_from_slice(&[0x85, 0x7f, 0x7f, 0x7f]);
+        payload[layout::ADDRESS_SUB_SWITCH_OFFSET] = 0x7f;
+        payload[layout::ADDRESS_COMMAND_OFFSET] = 0x02;
+
+        let address = parse_artaddress(&payload).unwrap().expect("artaddress");
+        assert_eq!(address.bind_index, 2);
+        assert_eq!(address.short_name, None);
+        assert_eq!(ArtAddress::programmed(address.net_switch), None);
+        assert_eq!(ArtAddress::programmed(address.sw_out[0]), Some(5));
+        assert_eq!(address.command, 0x02);
+        assert!(matches!(
+            parse_artaddress(&payload[..layout::ARTADDRESS_LEN - 1]),
+            Err(ArtNetError::TooShort { .. })
+        ));
+    }
+
+    #[test]
+    fn parse_artsync_packets() {
+        let mut payload = vec![0u8; layout::ARTSYNC_LEN];
+        payload[..layout::ARTNET_ID.len()].copy_from_slice(layout::ARTNET_ID);
+        payload[layout::OP_CODE_RANGE.clone()]
+            .copy_from_slice(&layout::ARTSYNC_OPCODE.to_le_bytes());
+        payload[layout::PROTOCOL_VERSION_RANGE.clone()].copy_from_slice(&14u16.to_be_bytes());
+
+        let sync = parse_artsync(&payload).unwrap().expect("artsync");
+        assert_eq!(sync.protocol_version, 14);
+        assert!(matches!(
+            parse_artsync(&payload[..layout::ARTSYNC_LEN - 1]),
+            Err(ArtNetError::TooShort { .. })
+        ));
+        assert!(matches!(
+            parse_artdmx(&payload),
+            Err(ArtNetError::TooShort { .. })
+        ));
+
+        payload[layout::OP_CODE_RANGE.clone()]
+            .copy_from_slice(&layout::ARTDMX_OPCODE.to_le_bytes());
+        assert_eq!(parse_artsync(&payload).unwrap(), None);
+    }
+
+    #[test]
+    fn parse_non_artnet() {
+        let payload = vec![0u8; layout::DMX_DATA_OFFSET];
+        let parsed = parse_artdmx(&payload).unwrap();
+        assert!(parsed.is_none());
+    }
+
+    #[test]
+    fn parse_short_payload() {
+        let payload = vec![0u8; layout::DMX_DATA_OFFSET - 1];
+        let err = parse_artdmx(&payload).unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("payload too short"));
+    }
+
+    #[test]
+    fn parse_invalid_length() {
+        let length = (layout::DMX_MAX_SLOTS as u16) + 1;
+        let mut payload = vec![0u8; layout::DMX_DATA_OFFSET];
+        payload[..layout::ARTNET_ID.len()].copy_from_slice(layout::ARTNET_ID);
+        payload[layout::OP_CODE_RANGE.clone()]
+            .copy_from_slice(&layout::ARTDMX_OPCODE.to_le_bytes());
+        payload[layout::LENGTH_RANGE.clone()].copy_from_slice(&length.to_be_bytes());
+
+        let err = parse_artdmx(&payload).unwrap_err();
+        assert!(matches!(err, ArtNetError::InvalidDmxLength { .. }));
+    }
+
+    #[test]
+    fn parse_invalid_universe_id() {
+        let mut payload = vec![0u8; layout::DMX_DATA_OFFSET];
+        payload[..layout::ARTNET_ID.len()].copy_from_slice(layout::ARTNET_ID);
+        payload[layout::OP_CODE_RANGE.clone()]
+            .copy_from_slice(&layout::ARTDMX_OPCODE.to_le_bytes());
+        payload[layout::UNIVERSE_RANGE.clone()].copy_from_slice(&0x8000u16.to_le_bytes());
+        payload[layout::LENGTH_RANGE.clone()].copy_from_slice(&2u16.to_be_bytes());
+
+        let err = parse_artdmx(&payload).unwrap_err();
+        assert!(matches!(
+            err,
+            ArtNetError::InvalidUniverseId { value: 0x8000 }
+        ));
+    }
+
+    #[test]
+    fn parse_unsupported_opcode() {
+        let mut payload = vec![0u8; layout::DMX_DATA_OFFSET];
+        payload[..layout::ARTNET_ID.len()].copy_from_slice(layout::ARTNET_ID);
+        payload[layout::OP_CODE_RANGE.clone()].copy_from_slice(&0x1234u16.to_le_bytes());
+
+        let err = parse_artdmx(&payload).unwrap_err();
+        assert!(matches!(
+            err,
+            ArtNetError::UnsupportedOpCode { opcode: 0x1234 }
+        ));
+    }
+}
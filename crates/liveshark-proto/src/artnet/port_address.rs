@@ -0,0 +1,214 @@
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::error::PortAddressError;
+use super::layout;
+
+/// Art-Net 15-bit Port-Address, split into net, sub-net, and universe.
+///
+/// Consoles usually patch Art-Net as `net:sub-net:universe` (for example
+/// `0:1:5`), while the wire carries a single 15-bit value. `PortAddress`
+/// converts between both forms so patch numbers are never confused with the
+/// raw value.
+///
+/// In reports it serializes as `{"net": .., "sub_net": .., "universe": ..}`.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::PortAddress;
+///
+/// let address = PortAddress::new(1, 2, 5)?;
+/// assert_eq!(address.raw(), 0x0125);
+/// assert_eq!(address.to_string(), "1:2:5");
+///
+/// let parsed: PortAddress = "1:2:5".parse()?;
+/// assert_eq!(parsed, address);
+/// assert_eq!(PortAddress::try_from(0x0125u16)?, address);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "PortAddressParts", into = "PortAddressParts")]
+pub struct PortAddress(u16);
+
+impl PortAddress {
+    /// Build a Port-Address from its net (0..=127), sub-net (0..=15), and
+    /// universe (0..=15) parts.
+    ///
+    /// # Errors
+    /// Returns `PortAddressError::InvalidParts` when a part is out of range.
+    pub fn new(net: u8, sub_net: u8, universe: u8) -> Result<Self, PortAddressError> {
+        if net > layout::PORT_ADDRESS_NET_MAX
+            || sub_net > layout::PORT_ADDRESS_NIBBLE_MAX
+            || universe > layout::PORT_ADDRESS_NIBBLE_MAX
+        {
+            return Err(PortAddressError::InvalidParts {
+                net,
+                sub_net,
+                universe,
+            });
+        }
+        Ok(Self(
+            (u16::from(net) << 8) | (u16::from(sub_net) << 4) | u16::from(universe),
+        ))
+    }
+
+    /// Keep the low 15 bits of `value`, dropping the reserved top bit.
+    pub fn from_masked(value: u16) -> Self {
+        Self(value & layout::PORT_ADDRESS_MAX)
+    }
+
+    /// Raw 15-bit value as carried on the wire.
+    pub fn raw(self) -> u16 {
+        self.0
+    }
+
+    /// Net (high 7 bits).
+    pub fn net(self) -> u8 {
+        (self.0 >> 8) as u8
+    }
+
+    /// Sub-net (bits 7..4).
+    pub fn sub_net(self) -> u8 {
+        ((self.0 >> 4) & u16::from(layout::PORT_ADDRESS_NIBBLE_MAX)) as u8
+    }
+
+    /// Universe within the sub-net (bits 3..0).
+    pub fn universe(self) -> u8 {
+        (self.0 & u16::from(layout::PORT_ADDRESS_NIBBLE_MAX)) as u8
+    }
+
+    /// Low byte of the Port-Address (`SubUni` on the wire).
+    pub fn sub_uni(self) -> u8 {
+        (self.0 & 0x00ff) as u8
+    }
+}
+
+impl TryFrom<u16> for PortAddress {
+    type Error = PortAddressError;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        if value > layout::PORT_ADDRESS_MAX {
+            return Err(PortAddressError::OutOfRange { value });
+        }
+        Ok(Self(value))
+    }
+}
+
+impl From<PortAddress> for u16 {
+    fn from(address: PortAddress) -> Self {
+        address.raw()
+    }
+}
+
+impl fmt::Display for PortAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.net(), self.sub_net(), self.universe())
+    }
+}
+
+/// Parses `net:sub-net:universe` (`.` is accepted as separator) or a raw
+/// decimal Port-Address.
+impl FromStr for PortAddress {
+    type Err = PortAddressError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || PortAddressError::InvalidText {
+            value: value.to_string(),
+        };
+        let parts: Vec<&str> = value.trim().split([':', '.']).collect();
+        match parts.as_slice() {
+            [raw] => {
+                let raw = raw.parse::<u16>().map_err(|_| invalid())?;
+                Self::try_from(raw)
+            }
+            [net, sub_net, universe] => {
+                let net = net.parse::<u8>().map_err(|_| invalid())?;
+                let sub_net = sub_net.parse::<u8>().map_err(|_| invalid())?;
+                let universe = universe.parse::<u8>().map_err(|_| invalid())?;
+                Self::new(net, sub_net, universe)
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PortAddressParts {
+    net: u8,
+    sub_net: u8,
+    universe: u8,
+}
+
+impl TryFrom<PortAddressParts> for PortAddress {
+    type Error = PortAddressError;
+
+    fn try_from(parts: PortAddressParts) -> Result<Self, Self::Error> {
+        Self::new(parts.net, parts.sub_net, parts.universe)
+    }
+}
+
+impl From<PortAddress> for PortAddressParts {
+    fn from(address: PortAddress) -> Self {
+        Self {
+            net: address.net(),
+            sub_net: address.sub_net(),
+            universe: address.universe(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PortAddress;
+    use crate::artnet::error::PortAddressError;
+
+    #[test]
+    fn splits_raw_value() {
+        let address = PortAddress::try_from(0x7fffu16).unwrap();
+        assert_eq!(address.net(), 127);
+        assert_eq!(address.sub_net(), 15);
+        assert_eq!(address.universe(), 15);
+        assert_eq!(address.sub_uni(), 0xff);
+    }
+
+    #[test]
+    fn rejects_out_of_range_parts() {
+        assert!(matches!(
+            PortAddress::new(128, 0, 0),
+            Err(PortAddressError::InvalidParts { net: 128, .. })
+        ));
+        assert!(PortAddress::new(0, 16, 0).is_err());
+        assert!(PortAddress::new(0, 0, 16).is_err());
+        assert!(PortAddress::try_from(0x8000u16).is_err());
+    }
+
+    #[test]
+    fn parses_patch_notation() {
+        assert_eq!("0.1.2".parse::<PortAddress>().unwrap().raw(), 0x0012);
+        assert_eq!(" 18 ".parse::<PortAddress>().unwrap().raw(), 18);
+        assert!("1:2".parse::<PortAddress>().is_err());
+        assert!("a:b:c".parse::<PortAddress>().is_err());
+    }
+
+    #[test]
+    fn serializes_as_parts() {
+        let address = PortAddress::new(1, 2, 3).unwrap();
+        let value = serde_json::to_value(address).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({"net": 1, "sub_net": 2, "universe": 3})
+        );
+        let parsed: PortAddress = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed, address);
+        assert!(
+            serde_json::from_value::<PortAddress>(
+                serde_json::json!({"net": 1, "sub_net": 16, "universe": 3})
+            )
+            .is_err()
+        );
+    }
+}
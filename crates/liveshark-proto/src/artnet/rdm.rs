@@ -0,0 +1,488 @@
+//! RDM over Art-Net: table of devices (ArtTodRequest, ArtTodData) and RDM
+//! messages (ArtRdm).
+//!
+//! Nodes run RDM discovery on their DMX ports and publish the UIDs found in
+//! ArtTodData; controllers then address responders with ArtRdm, which
+//! carries one ANSI E1.20 message. Only the RDM message header is decoded:
+//! parameter data stays opaque.
+
+use alloc::vec::Vec;
+
+#[cfg(feature = "artnet")]
+use super::error::ArtNetError;
+use super::layout;
+#[cfg(feature = "artnet")]
+use super::reader::ArtNetReader;
+
+/// RDM unique identifier: ESTA manufacturer ID and device ID.
+///
+/// Displays as `mmmm:dddddddd` (hexadecimal).
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::RdmUid;
+///
+/// let uid = RdmUid::from_bytes([0x7a, 0x70, 0, 0, 0, 1]);
+/// assert_eq!(uid.to_string(), "7a70:00000001");
+/// assert!(!uid.is_broadcast());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RdmUid {
+    /// ESTA manufacturer ID.
+    pub manufacturer: u16,
+    /// Device ID within the manufacturer.
+    pub device: u32,
+}
+
+impl RdmUid {
+    /// UID from its six bytes on the wire (big-endian).
+    pub fn from_bytes(bytes: [u8; layout::RDM_UID_LEN]) -> Self {
+        Self {
+            manufacturer: u16::from_be_bytes([bytes[0], bytes[1]]),
+            device: u32::from_be_bytes([bytes[2], bytes[3], bytes[4], bytes[5]]),
+        }
+    }
+
+    /// Whether the UID addresses every device (of every manufacturer, or of
+    /// one manufacturer).
+    pub fn is_broadcast(&self) -> bool {
+        self.device == u32::MAX
+    }
+}
+
+impl core::fmt::Display for RdmUid {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:04x}:{:08x}", self.manufacturer, self.device)
+    }
+}
+
+/// Parsed ArtTodRequest packet: a controller asking nodes for their table of
+/// devices.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::ArtTodRequest;
+///
+/// let request = ArtTodRequest {
+///     protocol_version: 14,
+///     command: 0,
+///     universes: vec![1, 2],
+/// };
+/// assert_eq!(request.universes.len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtTodRequest {
+    /// Art-Net protocol revision (`ProtVerHi`/`ProtVerLo`, 14 for Art-Net 4).
+    pub protocol_version: u16,
+    /// Request command; 0 (`TodFull`) asks for the full table.
+    pub command: u8,
+    /// 15-bit Port-Addresses whose table is requested.
+    pub universes: Vec<u16>,
+}
+
+/// Parsed ArtTodData packet: one block of a node port's table of devices.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::{ArtTodData, RdmUid};
+///
+/// let tod = ArtTodData {
+///     protocol_version: 14,
+///     rdm_version: 1,
+///     port: 1,
+///     bind_index: 1,
+///     universe: 3,
+///     command_response: 0,
+///     uid_total: 1,
+///     block_count: 0,
+///     uids: vec![RdmUid::from_bytes([0x7a, 0x70, 0, 0, 0, 1])],
+/// };
+/// assert_eq!(tod.uids.len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtTodData {
+    /// Art-Net protocol revision (`ProtVerHi`/`ProtVerLo`, 14 for Art-Net 4).
+    pub protocol_version: u16,
+    /// RDM standard version (1 for ANSI E1.20).
+    pub rdm_version: u8,
+    /// Physical port of the node (1..=4).
+    pub port: u8,
+    /// Bind index of the port (0 or 1 for the root device).
+    pub bind_index: u8,
+    /// 15-bit Port-Address of the port.
+    pub universe: u16,
+    /// 0 for a full table, 0xff when the table is not available.
+    pub command_response: u8,
+    /// UIDs in the full table, over every block.
+    pub uid_total: u16,
+    /// Index of this block in the table.
+    pub block_count: u8,
+    /// UIDs carried by this block.
+    pub uids: Vec<RdmUid>,
+}
+
+/// Header of an ANSI E1.20 RDM message.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::{RdmMessage, RdmUid};
+///
+/// let message = RdmMessage {
+///     destination: RdmUid::from_bytes([0x7a, 0x70, 0, 0, 0, 1]),
+///     source: RdmUid::from_bytes([0x4c, 0x53, 0, 0, 0, 9]),
+///     transaction: 4,
+///     port_or_response: 1,
+///     message_count: 0,
+///     sub_device: 0,
+///     command_class: 0x20,
+///     parameter_id: 0x0060,
+///     data_len: 0,
+/// };
+/// assert!(!message.is_response());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RdmMessage {
+    /// Addressed UID.
+    pub destination: RdmUid,
+    /// Sending UID.
+    pub source: RdmUid,
+    /// Transaction number, echoed by the response.
+    pub transaction: u8,
+    /// Port ID in requests, response type in responses (0 `ACK`, 1
+    /// `ACK_TIMER`, 2 `NACK_REASON`, 3 `ACK_OVERFLOW`).
+    pub port_or_response: u8,
+    /// Queued messages the responder holds.
+    pub message_count: u8,
+    /// Sub-device; 0 is the root device.
+    pub sub_device: u16,
+    /// Command class: 0x10/0x11 discovery, 0x20/0x21 GET, 0x30/0x31 SET
+    /// (odd values are responses).
+    pub command_class: u8,
+    /// Parameter ID.
+    pub parameter_id: u16,
+    /// Parameter data length.
+    pub data_len: u8,
+}
+
+impl RdmMessage {
+    /// Whether the message is a response (odd command class).
+    pub fn is_response(&self) -> bool {
+        self.command_class & 0x01 != 0
+    }
+}
+
+/// Parsed ArtRdm packet: one RDM message to or from a node port.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::{ArtRdm, RdmMessage, RdmUid};
+///
+/// let uid = RdmUid::from_bytes([0x7a, 0x70, 0, 0, 0, 1]);
+/// let rdm = ArtRdm {
+///     protocol_version: 14,
+///     rdm_version: 1,
+///     universe: 3,
+///     command: 0,
+///     message: RdmMessage {
+///         destination: uid,
+///         source: uid,
+///         transaction: 0,
+///         port_or_response: 0,
+///         message_count: 0,
+///         sub_device: 0,
+///         command_class: 0x21,
+///         parameter_id: 0x0060,
+///         data_len: 0,
+///     },
+/// };
+/// assert!(rdm.message.is_response());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArtRdm {
+    /// Art-Net protocol revision (`ProtVerHi`/`ProtVerLo`, 14 for Art-Net 4).
+    pub protocol_version: u16,
+    /// RDM standard version (1 for ANSI E1.20).
+    pub rdm_version: u8,
+    /// 15-bit Port-Address of the port.
+    pub universe: u16,
+    /// ArtRdm command; 0 (`ArProcess`) processes the message.
+    pub command: u8,
+    /// Carried RDM message.
+    pub message: RdmMessage,
+}
+
+#[cfg(feature = "artnet")]
+fn is_opcode(reader: &ArtNetReader<'_>, opcode: u16) -> bool {
+    reader
+        .read_signature()
+        .is_ok_and(|signature| signature == layout::ARTNET_ID)
+        && reader
+            .read_u16_le(layout::OP_CODE_RANGE.clone())
+            .is_ok_and(|actual| actual == opcode)
+}
+
+#[cfg(feature = "artnet")]
+fn read_port_address(reader: &ArtNetReader<'_>) -> Result<u16, ArtNetError> {
+    let net = reader.read_u8(layout::RDM_NET_OFFSET)? & layout::PORT_ADDRESS_NET_MAX;
+    let address = reader.read_u8(layout::RDM_ADDRESS_OFFSET)?;
+    Ok((u16::from(net) << 8) | u16::from(address))
+}
+
+#[cfg(feature = "artnet")]
+fn read_uid(reader: &ArtNetReader<'_>, start: usize) -> Result<RdmUid, ArtNetError> {
+    let mut bytes = [0u8; layout::RDM_UID_LEN];
+    bytes.copy_from_slice(reader.read_slice(start..start + layout::RDM_UID_LEN)?);
+    Ok(RdmUid::from_bytes(bytes))
+}
+
+/// Parse an ArtTodRequest payload from a UDP payload.
+///
+/// Returns `Ok(None)` when the payload is not an Art-Net ArtTodRequest.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::parse_arttodrequest;
+///
+/// let mut payload = vec![0u8; 26];
+/// payload[..8].copy_from_slice(b"Art-Net\0");
+/// payload[8..10].copy_from_slice(&0x8000u16.to_le_bytes()); // OpTodRequest
+/// payload[21] = 1; // Net
+/// payload[23] = 2; // two addresses
+/// payload[24..26].copy_from_slice(&[0x00, 0x01]);
+///
+/// let request = parse_arttodrequest(&payload)?.expect("arttodrequest");
+/// assert_eq!(request.universes, vec![0x100, 0x101]);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// Returns `ArtNetError::TooShort` when the addresses it counts are missing.
+#[cfg(feature = "artnet")]
+pub fn parse_arttodrequest(payload: &[u8]) -> Result<Option<ArtTodRequest>, ArtNetError> {
+    let reader = ArtNetReader::new(payload);
+    if !is_opcode(&reader, layout::ARTTODREQUEST_OPCODE) {
+        return Ok(None);
+    }
+    reader.require_len(layout::TOD_REQUEST_ADDRESS_OFFSET)?;
+    let count = usize::from(reader.read_u8(layout::TOD_REQUEST_AD_COUNT_OFFSET)?)
+        .min(layout::TOD_REQUEST_MAX_ADDRESSES);
+    let net = reader.read_u8(layout::RDM_NET_OFFSET)? & layout::PORT_ADDRESS_NET_MAX;
+    let start = layout::TOD_REQUEST_ADDRESS_OFFSET;
+    let universes = reader
+        .read_slice(start..start + count)?
+        .iter()
+        .map(|address| (u16::from(net) << 8) | u16::from(*address))
+        .collect();
+
+    Ok(Some(ArtTodRequest {
+        protocol_version: reader.read_u16_be(layout::PROTOCOL_VERSION_RANGE.clone())?,
+        command: reader.read_u8(layout::RDM_COMMAND_OFFSET)?,
+        universes,
+    }))
+}
+
+/// Parse an ArtTodData payload from a UDP payload.
+///
+/// Returns `Ok(None)` when the payload is not an Art-Net ArtTodData.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::parse_arttoddata;
+///
+/// let mut payload = vec![0u8; 34];
+/// payload[..8].copy_from_slice(b"Art-Net\0");
+/// payload[8..10].copy_from_slice(&0x8100u16.to_le_bytes()); // OpTodData
+/// payload[13] = 1; // port 1
+/// payload[23] = 3; // Sub-Net 0, Universe 3
+/// payload[24..26].copy_from_slice(&1u16.to_be_bytes()); // one UID in total
+/// payload[27] = 1; // one UID in this block
+/// payload[28..34].copy_from_slice(&[0x7a, 0x70, 0, 0, 0, 1]);
+///
+/// let tod = parse_arttoddata(&payload)?.expect("arttoddata");
+/// assert_eq!(tod.universe, 3);
+/// assert_eq!(tod.uids[0].to_string(), "7a70:00000001");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// Returns `ArtNetError::TooShort` when the UIDs it counts are missing.
+#[cfg(feature = "artnet")]
+pub fn parse_arttoddata(payload: &[u8]) -> Result<Option<ArtTodData>, ArtNetError> {
+    let reader = ArtNetReader::new(payload);
+    if !is_opcode(&reader, layout::ARTTODDATA_OPCODE) {
+        return Ok(None);
+    }
+    reader.require_len(layout::TOD_UIDS_OFFSET)?;
+    let count = usize::from(reader.read_u8(layout::TOD_UID_COUNT_OFFSET)?);
+    reader.require_len(layout::TOD_UIDS_OFFSET + count * layout::RDM_UID_LEN)?;
+    let uids = (0..count)
+        .map(|index| {
+            read_uid(
+                &reader,
+                layout::TOD_UIDS_OFFSET + index * layout::RDM_UID_LEN,
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Some(ArtTodData {
+        protocol_version: reader.read_u16_be(layout::PROTOCOL_VERSION_RANGE.clone())?,
+        rdm_version: reader.read_u8(layout::RDM_VERSION_OFFSET)?,
+        port: reader.read_u8(layout::TOD_PORT_OFFSET)?,
+        bind_index: reader.read_u8(layout::TOD_BIND_INDEX_OFFSET)?,
+        universe: read_port_address(&reader)?,
+        command_response: reader.read_u8(layout::RDM_COMMAND_OFFSET)?,
+        uid_total: reader.read_u16_be(layout::TOD_UID_TOTAL_RANGE.clone())?,
+        block_count: reader.read_u8(layout::TOD_BLOCK_COUNT_OFFSET)?,
+        uids,
+    }))
+}
+
+/// Parse an ArtRdm payload from a UDP payload.
+///
+/// Returns `Ok(None)` when the payload is not an Art-Net ArtRdm. The RDM
+/// message may start with its `0xCC` start code (Art-Net 3) or without it
+/// (Art-Net 4).
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::parse_artrdm;
+///
+/// let mut payload = vec![0u8; 24 + 24];
+/// payload[..8].copy_from_slice(b"Art-Net\0");
+/// payload[8..10].copy_from_slice(&0x8300u16.to_le_bytes()); // OpRdm
+/// payload[23] = 3; // Sub-Net 0, Universe 3
+/// let rdm = &mut payload[24..];
+/// rdm[0] = 0x01; // sub start code
+/// rdm[1] = 24; // message length
+/// rdm[2..8].copy_from_slice(&[0x7a, 0x70, 0, 0, 0, 1]); // destination
+/// rdm[19] = 0x20; // GET_COMMAND
+/// rdm[20..22].copy_from_slice(&0x0060u16.to_be_bytes()); // DEVICE_INFO
+///
+/// let rdm = parse_artrdm(&payload)?.expect("artrdm");
+/// assert_eq!(rdm.universe, 3);
+/// assert_eq!(rdm.message.destination.to_string(), "7a70:00000001");
+/// assert_eq!(rdm.message.parameter_id, 0x0060);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// Returns `ArtNetError::TooShort` when the RDM message header is truncated.
+#[cfg(feature = "artnet")]
+pub fn parse_artrdm(payload: &[u8]) -> Result<Option<ArtRdm>, ArtNetError> {
+    let reader = ArtNetReader::new(payload);
+    if !is_opcode(&reader, layout::ARTRDM_OPCODE) {
+        return Ok(None);
+    }
+    reader.require_len(layout::RDM_PACKET_OFFSET + 1)?;
+    let mut start = layout::RDM_PACKET_OFFSET;
+    if reader.read_u8(start)? == layout::RDM_START_CODE {
+        start += 1;
+    }
+    reader.require_len(start + layout::RDM_HEADER_LEN)?;
+    let message = RdmMessage {
+        destination: read_uid(&reader, start + layout::RDM_DESTINATION_RANGE.start)?,
+        source: read_uid(&reader, start + layout::RDM_SOURCE_RANGE.start)?,
+        transaction: reader.read_u8(start + layout::RDM_TRANSACTION_OFFSET)?,
+        port_or_response: reader.read_u8(start + layout::RDM_PORT_OR_RESPONSE_OFFSET)?,
+        message_count: reader.read_u8(start + layout::RDM_MESSAGE_COUNT_OFFSET)?,
+        sub_device: reader.read_u16_be(
+            start + layout::RDM_SUB_DEVICE_RANGE.start..start + layout::RDM_SUB_DEVICE_RANGE.end,
+        )?,
+        command_class: reader.read_u8(start + layout::RDM_COMMAND_CLASS_OFFSET)?,
+        parameter_id: reader
+            .read_u16_be(start + layout::RDM_PID_RANGE.start..start + layout::RDM_PID_RANGE.end)?,
+        data_len: reader.read_u8(start + layout::RDM_PDL_OFFSET)?,
+    };
+
+    Ok(Some(ArtRdm {
+        protocol_version: reader.read_u16_be(layout::PROTOCOL_VERSION_RANGE.clone())?,
+        rdm_version: reader.read_u8(layout::RDM_VERSION_OFFSET)?,
+        universe: read_port_address(&reader)?,
+        command: reader.read_u8(layout::RDM_COMMAND_OFFSET)?,
+        message,
+    }))
+}
+
+#[cfg(all(test, feature = "artnet"))]
+mod tests {
+    use super::{parse_artrdm, parse_arttoddata, parse_arttodrequest};
+    use crate::artnet::error::ArtNetError;
+    use crate::artnet::layout;
+
+    fn header(opcode: u16, len: usize) -> Vec<u8> {
+        let mut payload = vec![0u8; len];
+        payload[..layout::ARTNET_ID.len()].copy_from_slice(layout::ARTNET_ID);
+        payload[layout::OP_CODE_RANGE.clone()].copy_from_slice(&opcode.to_le_bytes());
+        payload
+    }
+
+    #[test]
+    fn parse_arttodrequest_addresses() {
+        let mut payload = header(
+            layout::ARTTODREQUEST_OPCODE,
+            layout::TOD_REQUEST_ADDRESS_OFFSET,
+        );
+        payload[layout::TOD_REQUEST_AD_COUNT_OFFSET] = 1;
+        assert!(matches!(
+            parse_arttodrequest(&payload),
+            Err(ArtNetError::TooShort { .. })
+        ));
+        payload.push(0x12);
+        let request = parse_arttodrequest(&payload)
+            .unwrap()
+            .expect("arttodrequest");
+        assert_eq!(request.universes, vec![0x12]);
+    }
+
+    #[test]
+    fn parse_arttoddata_blocks() {
+        let mut payload = header(layout::ARTTODDATA_OPCODE, layout::TOD_UIDS_OFFSET + 12);
+        payload[layout::TOD_PORT_OFFSET] = 2;
+        payload[layout::TOD_BIND_INDEX_OFFSET] = 1;
+        payload[layout::RDM_NET_OFFSET] = 1;
+        payload[layout::RDM_ADDRESS_OFFSET] = 0x23;
+        payload[layout::TOD_UID_TOTAL_RANGE.clone()].copy_from_slice(&3u16.to_be_bytes());
+        payload[layout::TOD_UID_COUNT_OFFSET] = 2;
+        payload[layout::TOD_UIDS_OFFSET..layout::TOD_UIDS_OFFSET + 6]
+            .copy_from_slice(&[0x7a, 0x70, 0, 0, 0, 1]);
+        payload[layout::TOD_UIDS_OFFSET + 6..].copy_from_slice(&[0x7a, 0x70, 0, 0, 0, 2]);
+
+        let tod = parse_arttoddata(&payload).unwrap().expect("arttoddata");
+        assert_eq!((tod.port, tod.bind_index, tod.universe), (2, 1, 0x123));
+        assert_eq!(tod.uid_total, 3);
+        assert_eq!(tod.uids.len(), 2);
+        assert_eq!(tod.uids[1].device, 2);
+        assert!(matches!(
+            parse_arttoddata(&payload[..payload.len() - 1]),
+            Err(ArtNetError::TooShort { .. })
+        ));
+    }
+
+    #[test]
+    fn parse_artrdm_with_and_without_start_code() {
+        let len = layout::RDM_PACKET_OFFSET + layout::RDM_HEADER_LEN;
+        let mut payload = header(layout::ARTRDM_OPCODE, len);
+        let start = layout::RDM_PACKET_OFFSET;
+        payload[start] = 0x01;
+        payload[start + layout::RDM_SOURCE_RANGE.start..start + layout::RDM_SOURCE_RANGE.end]
+            .copy_from_slice(&[0x7a, 0x70, 0, 0, 0, 1]);
+        payload[start + layout::RDM_TRANSACTION_OFFSET] = 7;
+        payload[start + layout::RDM_PORT_OR_RESPONSE_OFFSET] = 2;
+        payload[start + layout::RDM_COMMAND_CLASS_OFFSET] = 0x21;
+
+        let rdm = parse_artrdm(&payload).unwrap().expect("artrdm");
+        assert_eq!(rdm.message.source.to_string(), "7a70:00000001");
+        assert_eq!(rdm.message.transaction, 7);
+        assert!(rdm.message.is_response());
+
+        let mut with_start_code = payload.clone();
+        with_start_code.insert(start, layout::RDM_START_CODE);
+        let rdm = parse_artrdm(&with_start_code).unwrap().expect("artrdm");
+        assert_eq!(rdm.message.transaction, 7);
+
+        assert!(matches!(
+            parse_artrdm(&payload[..len - 1]),
+            Err(ArtNetError::TooShort { .. })
+        ));
+    }
+}
@@ -1,15 +1,15 @@
+use alloc::string::{String, ToString};
+
 use super::error::ArtNetError;
 use super::layout;
-use crate::protocols::common::reader::optional_nonzero_u8;
+use super::port_address::PortAddress;
+use crate::common::reader::optional_nonzero_u8;
 
 /// Safe byte reader for Art-Net payloads.
 ///
-/// Note: this reader lives in an internal module; the example is illustrative
-/// and not compiled as a public doctest.
-///
 /// # Examples
-/// ```text
-/// use liveshark_core::protocols::artnet::{layout, reader::ArtNetReader};
+/// ```
+/// use liveshark_proto::artnet::{layout, reader::ArtNetReader};
 ///
 /// let mut payload = vec![0u8; layout::LENGTH_RANGE.end];
 /// payload[layout::LENGTH_RANGE.clone()].copy_from_slice(&2u16.to_be_bytes());
@@ -38,7 +38,7 @@ impl<'a> ArtNetReader<'a> {
     }
 
     /// Read a little-endian `u16` from the given range.
-    pub fn read_u16_le(&self, range: std::ops::Range<usize>) -> Result<u16, ArtNetError> {
+    pub fn read_u16_le(&self, range: core::ops::Range<usize>) -> Result<u16, ArtNetError> {
         let bytes = self.read_slice(range)?;
         if bytes.len() != 2 {
             return Err(ArtNetError::TooShort {
@@ -50,7 +50,7 @@ impl<'a> ArtNetReader<'a> {
     }
 
     /// Read and validate the DMX data length (1..=512).
-    pub fn read_dmx_length(&self, range: std::ops::Range<usize>) -> Result<usize, ArtNetError> {
+    pub fn read_dmx_length(&self, range: core::ops::Range<usize>) -> Result<usize, ArtNetError> {
         let value = self.read_u16_be(range)?;
         let len = value as usize;
         if !(2..=layout::DMX_MAX_SLOTS).contains(&len) || len % 2 != 0 {
@@ -59,17 +59,17 @@ impl<'a> ArtNetReader<'a> {
         Ok(len)
     }
 
-    /// Read the canonical universe identifier and validate its range.
-    pub fn read_universe_id(&self, range: std::ops::Range<usize>) -> Result<u16, ArtNetError> {
+    /// Read the 15-bit Port-Address and validate its range.
+    pub fn read_port_address(
+        &self,
+        range: core::ops::Range<usize>,
+    ) -> Result<PortAddress, ArtNetError> {
         let value = self.read_u16_le(range)?;
-        if value > 0x7fff {
-            return Err(ArtNetError::InvalidUniverseId { value });
-        }
-        Ok(value)
+        PortAddress::try_from(value).map_err(|_| ArtNetError::InvalidUniverseId { value })
     }
 
     /// Read a big-endian `u16` from the given range.
-    pub fn read_u16_be(&self, range: std::ops::Range<usize>) -> Result<u16, ArtNetError> {
+    pub fn read_u16_be(&self, range: core::ops::Range<usize>) -> Result<u16, ArtNetError> {
         let bytes = self.read_slice(range)?;
         if bytes.len() != 2 {
             return Err(ArtNetError::TooShort {
@@ -98,7 +98,7 @@ impl<'a> ArtNetReader<'a> {
     }
 
     /// Read a byte slice from the given range.
-    pub fn read_slice(&self, range: std::ops::Range<usize>) -> Result<&'a [u8], ArtNetError> {
+    pub fn read_slice(&self, range: core::ops::Range<usize>) -> Result<&'a [u8], ArtNetError> {
         self.payload
             .get(range.clone())
             .ok_or(ArtNetError::TooShort {
@@ -107,6 +107,17 @@ impl<'a> ArtNetReader<'a> {
             })
     }
 
+    /// Read a null-terminated ASCII name, returning `None` when empty.
+    pub fn read_name(&self, range: core::ops::Range<usize>) -> Result<Option<String>, ArtNetError> {
+        let bytes = self.read_slice(range)?;
+        let content = &bytes[..bytes
+            .iter()
+            .position(|&byte| byte == 0)
+            .unwrap_or(bytes.len())];
+        let name = String::from_utf8_lossy(content).trim().to_string();
+        Ok(Some(name).filter(|name| !name.is_empty()))
+    }
+
     /// Read the Art-Net signature bytes.
     pub fn read_signature(&self) -> Result<&'a [u8], ArtNetError> {
         self.read_slice(0..layout::ARTNET_ID.len())
@@ -116,8 +127,8 @@ impl<'a> ArtNetReader<'a> {
 #[cfg(test)]
 mod tests {
     use super::ArtNetReader;
-    use crate::protocols::artnet::error::ArtNetError;
-    use crate::protocols::artnet::layout;
+    use crate::artnet::error::ArtNetError;
+    use crate::artnet::layout;
 
     #[test]
     fn read_optional_nonzero_u8() {
@@ -136,10 +147,10 @@ mod tests {
     }
 
     #[test]
-    fn read_universe_id_rejects_out_of_range() {
+    fn read_port_address_rejects_out_of_range() {
         let payload = [0x00u8, 0x80u8];
         let reader = ArtNetReader::new(&payload);
-        let err = reader.read_universe_id(0..2).unwrap_err();
+        let err = reader.read_port_address(0..2).unwrap_err();
         assert!(matches!(
             err,
             ArtNetError::InvalidUniverseId { value: 0x8000 }
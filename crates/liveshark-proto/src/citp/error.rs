@@ -0,0 +1,49 @@
+use core::fmt;
+
+/// Errors returned by CITP parsing and reading.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::CitpError;
+///
+/// let err = CitpError::MessageSize { declared: 64, actual: 40 };
+/// assert!(err.to_string().contains("64"));
+/// assert_eq!(err.offset(), 8);
+/// ```
+#[derive(Debug)]
+pub enum CitpError {
+    TooShort { needed: usize, actual: usize },
+    MessageSize { declared: usize, actual: usize },
+    UnterminatedString { offset: usize },
+}
+
+impl CitpError {
+    /// Payload offset of the field the error is about; 0 for truncated
+    /// payloads.
+    pub fn offset(&self) -> usize {
+        match self {
+            Self::TooShort { .. } => 0,
+            Self::MessageSize { .. } => super::layout::MESSAGE_SIZE_RANGE.start,
+            Self::UnterminatedString { offset } => *offset,
+        }
+    }
+}
+
+impl fmt::Display for CitpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooShort { needed, actual } => {
+                write!(f, "payload too short: need {needed} bytes, got {actual}")
+            }
+            Self::MessageSize { declared, actual } => write!(
+                f,
+                "message size {declared} does not match the {actual}-byte payload"
+            ),
+            Self::UnterminatedString { offset } => {
+                write!(f, "string at offset {offset} is not null-terminated")
+            }
+        }
+    }
+}
+
+impl core::error::Error for CitpError {}
@@ -0,0 +1,53 @@
+/// UDP port of CITP peer location and multicast streams.
+pub const DEFAULT_PORT: u16 = 4809;
+/// Multicast group of CITP peer location.
+pub const DEFAULT_MULTICAST_GROUP: [u8; 4] = [224, 0, 0, 180];
+
+/// CITP header, little-endian; cookies are four ASCII bytes.
+pub const COOKIE: &[u8; 4] = b"CITP";
+pub const HEADER_LEN: usize = 20;
+pub const VERSION_MAJOR_OFFSET: usize = 4;
+pub const VERSION_MINOR_OFFSET: usize = 5;
+pub const REQUEST_INDEX_RANGE: core::ops::Range<usize> = 6..8;
+pub const MESSAGE_SIZE_RANGE: core::ops::Range<usize> = 8..12;
+pub const MESSAGE_PART_COUNT_RANGE: core::ops::Range<usize> = 12..14;
+pub const MESSAGE_PART_RANGE: core::ops::Range<usize> = 14..16;
+pub const LAYER_RANGE: core::ops::Range<usize> = 16..20;
+
+/// Peer information layer.
+pub const LAYER_PINF: &[u8; 4] = b"PINF";
+/// Media server extensions layer.
+pub const LAYER_MSEX: &[u8; 4] = b"MSEX";
+
+/// PINF header: the message cookie.
+pub const PINF_CONTENT_TYPE_RANGE: core::ops::Range<usize> = 20..24;
+/// Peer location: listening TCP port, then type, name and state strings
+/// (null-terminated ASCII).
+pub const PINF_PLOC: &[u8; 4] = b"PLoc";
+pub const PLOC_LISTENING_PORT_RANGE: core::ops::Range<usize> = 24..26;
+pub const PLOC_STRINGS_OFFSET: usize = 26;
+
+/// MSEX header: MSEX version, then the message cookie.
+pub const MSEX_VERSION_MAJOR_OFFSET: usize = 20;
+pub const MSEX_VERSION_MINOR_OFFSET: usize = 21;
+pub const MSEX_CONTENT_TYPE_RANGE: core::ops::Range<usize> = 22..26;
+pub const MSEX_BODY_OFFSET: usize = 26;
+
+/// Layer status: layer count, then one entry per layer.
+pub const MSEX_LSTA: &[u8; 4] = b"LSta";
+/// Layer entry fields before the media name (UCS-2, null-terminated):
+/// layer, physical output, library number, media number in MSEX 1.0 and
+/// 1.1; MSEX 1.2 replaces the library number by a library type and a
+/// 4-byte library id.
+pub const LSTA_LAYER_PREFIX_LEN_V1_0: usize = 4;
+pub const LSTA_LAYER_PREFIX_LEN_V1_2: usize = 8;
+/// Layer entry fields after the media name: position, length (`u32`),
+/// frame rate (`u8`), status flags (`u32`).
+pub const LSTA_LAYER_SUFFIX_LEN: usize = 13;
+
+/// Stream frame: in MSEX 1.2, the media server UUID (36 ASCII characters
+/// and a null byte), then source id (`u16`), frame format cookie, width,
+/// height, and buffer size (`u16`), then the image data.
+pub const MSEX_STFR: &[u8; 4] = b"StFr";
+pub const STFR_UUID_LEN: usize = 37;
+pub const STFR_FIELDS_LEN: usize = 12;
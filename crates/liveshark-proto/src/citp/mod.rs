@@ -0,0 +1,25 @@
+//! CITP (Controller Interface Transport Protocol) decoding.
+//!
+//! Every CITP packet opens with a 20-byte little-endian header: the `CITP`
+//! cookie, the version, the message size, its part number, and a layer
+//! cookie. The parser reads peer announcements (`PINF`/`PLoc`) and the
+//! media-server layers (`MSEX`) carried over UDP: layer status (`LSta`) and
+//! stream frames (`StFr`, thumbnails and previews), whose image data is
+//! left undecoded. Other content types keep only their cookies.
+//!
+//! A payload without the `CITP` cookie is not CITP. Errors report truncated
+//! packets, a declared message size that does not match the datagram, and
+//! unterminated strings.
+//!
+//! Decoding (`reader` and `parse_citp`) is compiled with the `citp` feature;
+//! packet types and `layout` are always available.
+//!
+pub mod error;
+pub mod layout;
+pub mod parser;
+#[cfg(feature = "citp")]
+pub mod reader;
+
+#[cfg(feature = "citp")]
+pub use parser::parse_citp;
+pub use parser::{CitpHeader, CitpLayerStatus, CitpMessage, CitpPacket, CitpPeer, CitpStreamFrame};
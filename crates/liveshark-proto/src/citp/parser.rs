@@ -0,0 +1,412 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "citp")]
+use super::error::CitpError;
+#[cfg(feature = "citp")]
+use super::layout;
+#[cfg(feature = "citp")]
+use super::reader::CitpReader;
+
+/// CITP header shared by every packet.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::CitpHeader;
+///
+/// let header = CitpHeader {
+///     version_major: 1,
+///     version_minor: 0,
+///     message_size: 64,
+///     message_part_count: 1,
+///     message_part: 0,
+///     layer: *b"PINF",
+/// };
+/// assert_eq!(&header.layer, b"PINF");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CitpHeader {
+    /// Protocol version, major part.
+    pub version_major: u8,
+    /// Protocol version, minor part.
+    pub version_minor: u8,
+    /// Size of the packet, header included.
+    pub message_size: u32,
+    /// Packets the message is split into.
+    pub message_part_count: u16,
+    /// Index of this packet in the message.
+    pub message_part: u16,
+    /// Layer cookie (`PINF`, `MSEX`, `SDMX`, ...).
+    pub layer: [u8; 4],
+}
+
+/// Peer announcing itself with PINF/PLoc.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::CitpPeer;
+///
+/// let peer = CitpPeer {
+///     listening_port: 4811,
+///     peer_type: "MediaServer".to_string(),
+///     name: "Server A".to_string(),
+///     state: "Running".to_string(),
+/// };
+/// assert_eq!(peer.peer_type, "MediaServer");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CitpPeer {
+    /// TCP port the peer accepts connections on; 0 when it does not.
+    pub listening_port: u16,
+    /// Peer type (`LightingConsole`, `MediaServer`, `Visualizer`, ...).
+    pub peer_type: String,
+    /// Display name.
+    pub name: String,
+    /// Free-form state.
+    pub state: String,
+}
+
+/// Layer of a media server in MSEX/LSta.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::CitpLayerStatus;
+///
+/// let layer = CitpLayerStatus {
+///     layer: 1,
+///     physical_output: 0,
+///     media_name: "intro.mov".to_string(),
+///     media_position: 25,
+///     media_length: 750,
+/// };
+/// assert_eq!(layer.media_name, "intro.mov");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CitpLayerStatus {
+    /// Layer number.
+    pub layer: u8,
+    /// Physical output the layer is shown on.
+    pub physical_output: u8,
+    /// Media playing on the layer.
+    pub media_name: String,
+    /// Current frame of the media.
+    pub media_position: u32,
+    /// Length of the media, in frames.
+    pub media_length: u32,
+}
+
+/// Frame of an MSEX/StFr stream (thumbnail or preview video); the image data
+/// is not decoded.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::CitpStreamFrame;
+///
+/// let frame = CitpStreamFrame {
+///     source_id: 2,
+///     format: *b"JPEG",
+///     width: 160,
+///     height: 90,
+/// };
+/// assert_eq!(&frame.format, b"JPEG");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CitpStreamFrame {
+    /// Video source the stream shows.
+    pub source_id: u16,
+    /// Frame format cookie (`RGB8`, `JPEG`, `PNG `, ...).
+    pub format: [u8; 4],
+    /// Frame width, in pixels.
+    pub width: u16,
+    /// Frame height, in pixels.
+    pub height: u16,
+}
+
+/// Content of a CITP packet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CitpMessage {
+    /// PINF/PLoc peer location.
+    PeerLocation(CitpPeer),
+    /// MSEX/LSta layer status, in packet order.
+    LayerStatus(Vec<CitpLayerStatus>),
+    /// MSEX/StFr stream frame.
+    StreamFrame(CitpStreamFrame),
+    /// Another message, or a part of a message split over several packets;
+    /// only its content type cookie is read.
+    Other { content_type: Option<[u8; 4]> },
+}
+
+/// Parsed CITP packet.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::{CitpHeader, CitpMessage, CitpPacket};
+///
+/// let packet = CitpPacket {
+///     header: CitpHeader {
+///         version_major: 1,
+///         version_minor: 0,
+///         message_size: 24,
+///         message_part_count: 1,
+///         message_part: 0,
+///         layer: *b"SDMX",
+///     },
+///     message: CitpMessage::Other { content_type: Some(*b"ChBk") },
+/// };
+/// assert!(matches!(packet.message, CitpMessage::Other { .. }));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CitpPacket {
+    /// Packet header.
+    pub header: CitpHeader,
+    /// Packet content.
+    pub message: CitpMessage,
+}
+
+/// Parse a CITP packet from a UDP payload.
+///
+/// Returns `Ok(None)` when the payload does not start with the `CITP`
+/// cookie.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::{CitpMessage, parse_citp};
+///
+/// let mut payload = b"CITP\x01\x00\x00\x00".to_vec();
+/// payload.extend_from_slice(&0u32.to_le_bytes()); // size, set below
+/// payload.extend_from_slice(&[1, 0, 0, 0]); // one part
+/// payload.extend_from_slice(b"PINFPLoc");
+/// payload.extend_from_slice(&4811u16.to_le_bytes());
+/// payload.extend_from_slice(b"MediaServer\0Server A\0Running\0");
+/// let size = payload.len() as u32;
+/// payload[8..12].copy_from_slice(&size.to_le_bytes());
+///
+/// let packet = parse_citp(&payload)?.expect("citp");
+/// let CitpMessage::PeerLocation(peer) = packet.message else {
+///     panic!("expected a peer location");
+/// };
+/// assert_eq!(peer.name, "Server A");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// Returns `CitpError` when the packet is truncated, its message size does
+/// not match the payload, or a string is unterminated.
+#[cfg(feature = "citp")]
+pub fn parse_citp(payload: &[u8]) -> Result<Option<CitpPacket>, CitpError> {
+    if !payload.starts_with(layout::COOKIE) {
+        return Ok(None);
+    }
+    let reader = CitpReader::new(payload);
+    let header = CitpHeader {
+        version_major: reader.read_u8(layout::VERSION_MAJOR_OFFSET)?,
+        version_minor: reader.read_u8(layout::VERSION_MINOR_OFFSET)?,
+        message_size: reader.read_u32_le(layout::MESSAGE_SIZE_RANGE)?,
+        message_part_count: reader.read_u16_le(layout::MESSAGE_PART_COUNT_RANGE)?,
+        message_part: reader.read_u16_le(layout::MESSAGE_PART_RANGE)?,
+        layer: reader.read_cookie(layout::LAYER_RANGE)?,
+    };
+    if header.message_size as usize != payload.len() {
+        return Err(CitpError::MessageSize {
+            declared: header.message_size as usize,
+            actual: payload.len(),
+        });
+    }
+    let message = if header.message_part_count > 1 {
+        CitpMessage::Other { content_type: None }
+    } else if &header.layer == layout::LAYER_PINF {
+        read_pinf(&reader)?
+    } else if &header.layer == layout::LAYER_MSEX {
+        read_msex(&reader)?
+    } else {
+        CitpMessage::Other {
+            content_type: reader.read_cookie(layout::PINF_CONTENT_TYPE_RANGE).ok(),
+        }
+    };
+    Ok(Some(CitpPacket { header, message }))
+}
+
+#[cfg(feature = "citp")]
+fn read_pinf(reader: &CitpReader<'_>) -> Result<CitpMessage, CitpError> {
+    let content_type = reader.read_cookie(layout::PINF_CONTENT_TYPE_RANGE)?;
+    if &content_type != layout::PINF_PLOC {
+        return Ok(CitpMessage::Other {
+            content_type: Some(content_type),
+        });
+    }
+    let listening_port = reader.read_u16_le(layout::PLOC_LISTENING_PORT_RANGE)?;
+    let (peer_type, next) = reader.read_ucs1(layout::PLOC_STRINGS_OFFSET)?;
+    let (name, next) = reader.read_ucs1(next)?;
+    let (state, _) = reader.read_ucs1(next)?;
+    Ok(CitpMessage::PeerLocation(CitpPeer {
+        listening_port,
+        peer_type,
+        name,
+        state,
+    }))
+}
+
+#[cfg(feature = "citp")]
+fn read_msex(reader: &CitpReader<'_>) -> Result<CitpMessage, CitpError> {
+    let version = (
+        reader.read_u8(layout::MSEX_VERSION_MAJOR_OFFSET)?,
+        reader.read_u8(layout::MSEX_VERSION_MINOR_OFFSET)?,
+    );
+    let content_type = reader.read_cookie(layout::MSEX_CONTENT_TYPE_RANGE)?;
+    if &content_type == layout::MSEX_LSTA {
+        read_layer_status(reader, version)
+    } else if &content_type == layout::MSEX_STFR {
+        read_stream_frame(reader, version)
+    } else {
+        Ok(CitpMessage::Other {
+            content_type: Some(content_type),
+        })
+    }
+}
+
+#[cfg(feature = "citp")]
+fn read_layer_status(reader: &CitpReader<'_>, version: (u8, u8)) -> Result<CitpMessage, CitpError> {
+    let prefix_len = if version >= (1, 2) {
+        layout::LSTA_LAYER_PREFIX_LEN_V1_2
+    } else {
+        layout::LSTA_LAYER_PREFIX_LEN_V1_0
+    };
+    let count = reader.read_u8(layout::MSEX_BODY_OFFSET)?;
+    let mut offset = layout::MSEX_BODY_OFFSET + 1;
+    let mut layers = Vec::with_capacity(usize::from(count));
+    for _ in 0..count {
+        let layer = reader.read_u8(offset)?;
+        let physical_output = reader.read_u8(offset + 1)?;
+        let (media_name, next) = reader.read_ucs2(offset + prefix_len)?;
+        let media_position = reader.read_u32_le(next..next + 4)?;
+        let media_length = reader.read_u32_le(next + 4..next + 8)?;
+        // Frame rate and status flags are not reported.
+        reader.read_slice(next..next + layout::LSTA_LAYER_SUFFIX_LEN)?;
+        layers.push(CitpLayerStatus {
+            layer,
+            physical_output,
+            media_name,
+            media_position,
+            media_length,
+        });
+        offset = next + layout::LSTA_LAYER_SUFFIX_LEN;
+    }
+    Ok(CitpMessage::LayerStatus(layers))
+}
+
+#[cfg(feature = "citp")]
+fn read_stream_frame(reader: &CitpReader<'_>, version: (u8, u8)) -> Result<CitpMessage, CitpError> {
+    let mut offset = layout::MSEX_BODY_OFFSET;
+    if version >= (1, 2) {
+        offset += layout::STFR_UUID_LEN;
+    }
+    reader.read_slice(offset..offset + layout::STFR_FIELDS_LEN)?;
+    Ok(CitpMessage::StreamFrame(CitpStreamFrame {
+        source_id: reader.read_u16_le(offset..offset + 2)?,
+        format: reader.read_cookie(offset + 2..offset + 6)?,
+        width: reader.read_u16_le(offset + 6..offset + 8)?,
+        height: reader.read_u16_le(offset + 8..offset + 10)?,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CitpMessage, parse_citp};
+    use crate::citp::error::CitpError;
+
+    fn packet(layer: &[u8; 4], body: &[u8]) -> Vec<u8> {
+        let mut out = b"CITP\x01\x00\x00\x00".to_vec();
+        out.extend_from_slice(&((20 + body.len()) as u32).to_le_bytes());
+        out.extend_from_slice(&[1, 0, 0, 0]);
+        out.extend_from_slice(layer);
+        out.extend_from_slice(body);
+        out
+    }
+
+    fn ucs2(text: &str) -> Vec<u8> {
+        text.encode_utf16()
+            .chain([0])
+            .flat_map(u16::to_le_bytes)
+            .collect()
+    }
+
+    #[test]
+    fn parse_reads_msex_layer_status_of_both_layouts() {
+        let mut body = vec![1, 0, b'L', b'S', b't', b'a', 2];
+        for layer in [1u8, 2] {
+            body.extend_from_slice(&[layer, 0, 0, 5]);
+            body.extend(ucs2("clip.mov"));
+            body.extend_from_slice(&10u32.to_le_bytes());
+            body.extend_from_slice(&250u32.to_le_bytes());
+            body.push(25);
+            body.extend_from_slice(&0u32.to_le_bytes());
+        }
+        let parsed = parse_citp(&packet(b"MSEX", &body)).unwrap().expect("citp");
+        let CitpMessage::LayerStatus(layers) = parsed.message else {
+            panic!("expected layer status");
+        };
+        assert_eq!(layers.len(), 2);
+        assert_eq!(layers[1].layer, 2);
+        assert_eq!(layers[1].media_name, "clip.mov");
+        assert_eq!(
+            (layers[1].media_position, layers[1].media_length),
+            (10, 250)
+        );
+
+        let mut body = vec![1, 2, b'L', b'S', b't', b'a', 1, 3, 0, 1, 0, 0, 0, 0, 7];
+        body.extend(ucs2("x"));
+        body.extend_from_slice(&[0; 13]);
+        let parsed = parse_citp(&packet(b"MSEX", &body)).unwrap().expect("citp");
+        let CitpMessage::LayerStatus(layers) = parsed.message else {
+            panic!("expected layer status");
+        };
+        assert_eq!((layers[0].layer, layers[0].media_name.as_str()), (3, "x"));
+    }
+
+    #[test]
+    fn parse_reads_msex_stream_frames() {
+        let mut body = vec![1, 2, b'S', b't', b'F', b'r'];
+        body.extend_from_slice(&[b'0'; 36]);
+        body.push(0);
+        body.extend_from_slice(&3u16.to_le_bytes());
+        body.extend_from_slice(b"JPEG");
+        body.extend_from_slice(&160u16.to_le_bytes());
+        body.extend_from_slice(&90u16.to_le_bytes());
+        body.extend_from_slice(&4u16.to_le_bytes());
+        body.extend_from_slice(&[0xff; 4]);
+        let parsed = parse_citp(&packet(b"MSEX", &body)).unwrap().expect("citp");
+        let CitpMessage::StreamFrame(frame) = parsed.message else {
+            panic!("expected a stream frame");
+        };
+        assert_eq!(frame.source_id, 3);
+        assert_eq!(&frame.format, b"JPEG");
+        assert_eq!((frame.width, frame.height), (160, 90));
+    }
+
+    #[test]
+    fn parse_rejects_bad_sizes_and_skips_other_payloads() {
+        assert!(parse_citp(b"Art-Net\0").unwrap().is_none());
+        let mut payload = packet(b"PINF", b"PLoc\x00\x00Console\0Desk\0");
+        assert!(matches!(
+            parse_citp(&payload),
+            Err(CitpError::UnterminatedString { offset: 39 })
+        ));
+        payload.push(0);
+        assert!(matches!(
+            parse_citp(&payload),
+            Err(CitpError::MessageSize {
+                declared: 39,
+                actual: 40
+            })
+        ));
+        let parsed = parse_citp(&packet(b"SDMX", b"ChBk"))
+            .unwrap()
+            .expect("citp");
+        assert_eq!(
+            parsed.message,
+            CitpMessage::Other {
+                content_type: Some(*b"ChBk")
+            }
+        );
+    }
+}
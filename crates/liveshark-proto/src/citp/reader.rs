@@ -0,0 +1,126 @@
+use alloc::string::String;
+
+use super::error::CitpError;
+
+/// Safe byte reader for CITP payloads.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::citp::reader::CitpReader;
+///
+/// let reader = CitpReader::new(b"\x41\x12Console\0");
+/// assert_eq!(reader.read_u16_le(0..2).unwrap(), 0x1241);
+/// assert_eq!(reader.read_ucs1(2).unwrap(), ("Console".to_string(), 10));
+/// ```
+pub struct CitpReader<'a> {
+    payload: &'a [u8],
+}
+
+impl<'a> CitpReader<'a> {
+    pub fn new(payload: &'a [u8]) -> Self {
+        Self { payload }
+    }
+
+    /// Read a single byte at the given offset.
+    pub fn read_u8(&self, offset: usize) -> Result<u8, CitpError> {
+        self.payload
+            .get(offset)
+            .copied()
+            .ok_or(CitpError::TooShort {
+                needed: offset + 1,
+                actual: self.payload.len(),
+            })
+    }
+
+    /// Read a little-endian `u16` from the given range.
+    pub fn read_u16_le(&self, range: core::ops::Range<usize>) -> Result<u16, CitpError> {
+        Ok(u16::from_le_bytes(self.read_array::<2>(range)?))
+    }
+
+    /// Read a little-endian `u32` from the given range.
+    pub fn read_u32_le(&self, range: core::ops::Range<usize>) -> Result<u32, CitpError> {
+        Ok(u32::from_le_bytes(self.read_array::<4>(range)?))
+    }
+
+    /// Read a four-byte cookie from the given range.
+    pub fn read_cookie(&self, range: core::ops::Range<usize>) -> Result<[u8; 4], CitpError> {
+        self.read_array::<4>(range)
+    }
+
+    /// Read a byte slice from the given range.
+    pub fn read_slice(&self, range: core::ops::Range<usize>) -> Result<&'a [u8], CitpError> {
+        self.payload.get(range.clone()).ok_or(CitpError::TooShort {
+            needed: range.end,
+            actual: self.payload.len(),
+        })
+    }
+
+    /// Read a null-terminated ASCII string at `offset`; invalid sequences
+    /// are replaced. Returns the string and the offset after the null byte.
+    pub fn read_ucs1(&self, offset: usize) -> Result<(String, usize), CitpError> {
+        let bytes = self.payload.get(offset..).unwrap_or_default();
+        let len = bytes
+            .iter()
+            .position(|byte| *byte == 0)
+            .ok_or(CitpError::UnterminatedString { offset })?;
+        let text = String::from_utf8_lossy(&bytes[..len]).into_owned();
+        Ok((text, offset + len + 1))
+    }
+
+    /// Read a null-terminated little-endian UCS-2 string at `offset`;
+    /// invalid code units are replaced. Returns the string and the offset
+    /// after the null code unit.
+    pub fn read_ucs2(&self, offset: usize) -> Result<(String, usize), CitpError> {
+        let bytes = self.payload.get(offset..).unwrap_or_default();
+        let units = bytes
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]));
+        let mut text = String::new();
+        let mut end = None;
+        for (index, unit) in units.enumerate() {
+            if unit == 0 {
+                end = Some(offset + 2 * (index + 1));
+                break;
+            }
+            text.push(char::from_u32(u32::from(unit)).unwrap_or(char::REPLACEMENT_CHARACTER));
+        }
+        let end = end.ok_or(CitpError::UnterminatedString { offset })?;
+        Ok((text, end))
+    }
+
+    fn read_array<const N: usize>(
+        &self,
+        range: core::ops::Range<usize>,
+    ) -> Result<[u8; N], CitpError> {
+        let bytes = self.read_slice(range)?;
+        bytes.try_into().map_err(|_| CitpError::TooShort {
+            needed: N,
+            actual: bytes.len(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CitpReader;
+    use crate::citp::error::CitpError;
+
+    #[test]
+    fn read_ucs2_stops_at_the_null_unit() {
+        let reader = CitpReader::new(&[b'C', 0, b'1', 0, 0, 0, 0xff]);
+        assert_eq!(reader.read_ucs2(0).unwrap(), ("C1".to_string(), 6));
+    }
+
+    #[test]
+    fn unterminated_strings_are_rejected() {
+        let reader = CitpReader::new(b"abc");
+        assert!(matches!(
+            reader.read_ucs1(1),
+            Err(CitpError::UnterminatedString { offset: 1 })
+        ));
+        assert!(matches!(
+            reader.read_ucs2(0),
+            Err(CitpError::UnterminatedString { offset: 0 })
+        ));
+    }
+}
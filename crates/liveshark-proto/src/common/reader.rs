@@ -0,0 +1,30 @@
+#[cfg(feature = "artnet")]
+pub(crate) fn optional_nonzero_u8(value: u8) -> Option<u8> {
+    if value == 0 { None } else { Some(value) }
+}
+
+#[cfg(feature = "sacn")]
+pub(crate) fn optional_nonzero_u16(value: u16) -> Option<u16> {
+    if value == 0 { None } else { Some(value) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{optional_nonzero_u8, optional_nonzero_u16};
+
+    #[test]
+    fn optional_nonzero_u8_zero() {
+        assert_eq!(optional_nonzero_u8(0), None);
+    }
+
+    #[test]
+    fn optional_nonzero_u8_value() {
+        assert_eq!(optional_nonzero_u8(12), Some(12));
+    }
+
+    #[test]
+    fn optional_nonzero_u16_zero_and_value() {
+        assert_eq!(optional_nonzero_u16(0), None);
+        assert_eq!(optional_nonzero_u16(7000), Some(7000));
+    }
+}
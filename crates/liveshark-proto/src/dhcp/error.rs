@@ -0,0 +1,43 @@
+use core::fmt;
+
+/// Errors returned by DHCP parsing and reading.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::DhcpError;
+///
+/// let err = DhcpError::OptionOverflow { offset: 243 };
+/// assert!(err.to_string().contains("overflows"));
+/// assert_eq!(err.offset(), 243);
+/// ```
+#[derive(Debug)]
+pub enum DhcpError {
+    TooShort { needed: usize, actual: usize },
+    OptionOverflow { offset: usize },
+}
+
+impl DhcpError {
+    /// Payload offset of the field the error is about; 0 for truncated
+    /// packets.
+    pub fn offset(&self) -> usize {
+        match self {
+            Self::TooShort { .. } => 0,
+            Self::OptionOverflow { offset } => *offset,
+        }
+    }
+}
+
+impl fmt::Display for DhcpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooShort { needed, actual } => {
+                write!(f, "payload too short: need {needed} bytes, got {actual}")
+            }
+            Self::OptionOverflow { offset } => {
+                write!(f, "option at offset {offset} overflows the payload")
+            }
+        }
+    }
+}
+
+impl core::error::Error for DhcpError {}
@@ -0,0 +1,33 @@
+/// UDP port of DHCP servers.
+pub const SERVER_PORT: u16 = 67;
+/// UDP port of DHCP clients.
+pub const CLIENT_PORT: u16 = 68;
+
+/// Fixed BOOTP fields, big-endian.
+pub const OP_OFFSET: usize = 0;
+pub const CLIENT_IP_RANGE: core::ops::Range<usize> = 12..16;
+pub const YOUR_IP_RANGE: core::ops::Range<usize> = 16..20;
+/// First six bytes of `chaddr`, the Ethernet address of the client.
+pub const CLIENT_MAC_RANGE: core::ops::Range<usize> = 28..34;
+pub const MAGIC_COOKIE_RANGE: core::ops::Range<usize> = 236..240;
+pub const MAGIC_COOKIE: [u8; 4] = [0x63, 0x82, 0x53, 0x63];
+pub const OPTIONS_OFFSET: usize = 240;
+
+pub const OP_REQUEST: u8 = 1;
+pub const OP_REPLY: u8 = 2;
+
+/// Options (RFC 2132).
+pub const OPTION_PAD: u8 = 0;
+pub const OPTION_REQUESTED_IP: u8 = 50;
+pub const OPTION_MESSAGE_TYPE: u8 = 53;
+pub const OPTION_SERVER_ID: u8 = 54;
+pub const OPTION_END: u8 = 255;
+
+/// Values of the message type option.
+pub const MESSAGE_DISCOVER: u8 = 1;
+pub const MESSAGE_OFFER: u8 = 2;
+pub const MESSAGE_REQUEST: u8 = 3;
+pub const MESSAGE_DECLINE: u8 = 4;
+pub const MESSAGE_ACK: u8 = 5;
+pub const MESSAGE_NAK: u8 = 6;
+pub const MESSAGE_RELEASE: u8 = 7;
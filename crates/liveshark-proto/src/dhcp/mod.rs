@@ -0,0 +1,23 @@
+//! DHCP (RFC 2131, RFC 2132) message decoding.
+//!
+//! Clients obtain their IPv4 address from a DHCP server over UDP ports 67
+//! (server) and 68 (client). The parser reads the fixed BOOTP fields the
+//! lease is about (client hardware address, client and assigned address)
+//! and the options naming the message type, the requested address and the
+//! server. Plain BOOTP packets without the DHCP magic cookie are not DHCP.
+//!
+//! Errors report packets shorter than the fixed fields and options that
+//! overflow the payload.
+//!
+//! Decoding (`reader` and `parse_dhcp`) is compiled with the `dhcp` feature;
+//! message types and `layout` are always available.
+//!
+pub mod error;
+pub mod layout;
+pub mod parser;
+#[cfg(feature = "dhcp")]
+pub mod reader;
+
+pub use parser::DhcpMessage;
+#[cfg(feature = "dhcp")]
+pub use parser::parse_dhcp;
@@ -0,0 +1,179 @@
+use core::net::Ipv4Addr;
+
+#[cfg(feature = "dhcp")]
+use super::error::DhcpError;
+#[cfg(feature = "dhcp")]
+use super::layout;
+#[cfg(feature = "dhcp")]
+use super::reader::DhcpReader;
+use crate::arp::MacAddress;
+
+/// DHCP message, with the fields that identify a lease.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::{DhcpMessage, MacAddress};
+///
+/// let ack = DhcpMessage {
+///     op: 2,
+///     message_type: Some(5),
+///     client_ip: "0.0.0.0".parse().unwrap(),
+///     your_ip: "10.0.0.20".parse().unwrap(),
+///     client_mac: MacAddress([2, 0, 0, 0, 0, 20]),
+///     requested_ip: None,
+///     server_id: Some("10.0.0.1".parse().unwrap()),
+/// };
+/// assert!(ack.is_ack());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DhcpMessage {
+    /// BOOTP operation (1 request, 2 reply).
+    pub op: u8,
+    /// DHCP message type (option 53), e.g. 5 for DHCPACK.
+    pub message_type: Option<u8>,
+    /// Address the client already holds (`ciaddr`).
+    pub client_ip: Ipv4Addr,
+    /// Address the server assigns (`yiaddr`).
+    pub your_ip: Ipv4Addr,
+    /// Ethernet address of the client (first six bytes of `chaddr`).
+    pub client_mac: MacAddress,
+    /// Address the client asks for (option 50).
+    pub requested_ip: Option<Ipv4Addr>,
+    /// Server identifier (option 54).
+    pub server_id: Option<Ipv4Addr>,
+}
+
+impl DhcpMessage {
+    /// Whether the message is a DHCPACK confirming a lease.
+    pub fn is_ack(&self) -> bool {
+        self.message_type == Some(super::layout::MESSAGE_ACK)
+    }
+}
+
+/// Parse a DHCP message from a UDP payload on port 67 or 68.
+///
+/// Returns `Ok(None)` when the payload is BOOTP without the DHCP magic
+/// cookie. Options after the end option are ignored.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::parse_dhcp;
+///
+/// let mut payload = vec![0u8; 240];
+/// payload[0] = 2;
+/// payload[16..20].copy_from_slice(&[10, 0, 0, 20]);
+/// payload[28..34].copy_from_slice(&[2, 0, 0, 0, 0, 20]);
+/// payload[236..240].copy_from_slice(&[0x63, 0x82, 0x53, 0x63]);
+/// payload.extend_from_slice(&[53, 1, 5, 255]);
+///
+/// let message = parse_dhcp(&payload)?.expect("dhcp");
+/// assert!(message.is_ack());
+/// assert_eq!(message.your_ip.to_string(), "10.0.0.20");
+/// assert_eq!(message.client_mac.to_string(), "02:00:00:00:00:14");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// Returns `DhcpError` when the fixed fields are truncated or an option
+/// overflows the payload.
+#[cfg(feature = "dhcp")]
+pub fn parse_dhcp(payload: &[u8]) -> Result<Option<DhcpMessage>, DhcpError> {
+    let reader = DhcpReader::new(payload);
+    let op = reader.read_u8(layout::OP_OFFSET)?;
+    let client_ip = reader.read_ipv4(layout::CLIENT_IP_RANGE)?;
+    let your_ip = reader.read_ipv4(layout::YOUR_IP_RANGE)?;
+    let client_mac = reader.read_mac(layout::CLIENT_MAC_RANGE)?;
+    if reader.read_bytes(layout::MAGIC_COOKIE_RANGE)? != layout::MAGIC_COOKIE {
+        return Ok(None);
+    }
+
+    let mut message = DhcpMessage {
+        op,
+        message_type: None,
+        client_ip,
+        your_ip,
+        client_mac,
+        requested_ip: None,
+        server_id: None,
+    };
+    let mut offset = layout::OPTIONS_OFFSET;
+    while offset < reader.len() {
+        let code = reader.read_u8(offset)?;
+        match code {
+            layout::OPTION_PAD => {
+                offset += 1;
+                continue;
+            }
+            layout::OPTION_END => break,
+            _ => {}
+        }
+        let len = reader
+            .read_u8(offset + 1)
+            .map_err(|_| DhcpError::OptionOverflow { offset })?;
+        let value = reader
+            .read_bytes(offset + 2..offset + 2 + usize::from(len))
+            .map_err(|_| DhcpError::OptionOverflow { offset })?;
+        match (code, value) {
+            (layout::OPTION_MESSAGE_TYPE, [kind]) => message.message_type = Some(*kind),
+            (layout::OPTION_REQUESTED_IP, &[a, b, c, d]) => {
+                message.requested_ip = Some(Ipv4Addr::new(a, b, c, d));
+            }
+            (layout::OPTION_SERVER_ID, &[a, b, c, d]) => {
+                message.server_id = Some(Ipv4Addr::new(a, b, c, d));
+            }
+            _ => {}
+        }
+        offset += 2 + usize::from(len);
+    }
+    Ok(Some(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use core::net::Ipv4Addr;
+
+    use super::parse_dhcp;
+    use crate::dhcp::error::DhcpError;
+
+    fn bootp() -> Vec<u8> {
+        let mut payload = vec![0u8; 240];
+        payload[0] = 1;
+        payload[28..34].copy_from_slice(&[2, 0, 0, 0, 0, 20]);
+        payload
+    }
+
+    #[test]
+    fn parse_reads_options_and_skips_plain_bootp() {
+        let mut payload = bootp();
+        assert_eq!(parse_dhcp(&payload).unwrap(), None);
+        payload[236..240].copy_from_slice(&[0x63, 0x82, 0x53, 0x63]);
+        payload.extend_from_slice(&[0, 53, 1, 3, 50, 4, 10, 0, 0, 20]);
+        payload.extend_from_slice(&[54, 4, 10, 0, 0, 1, 255, 53, 9]);
+
+        let message = parse_dhcp(&payload).unwrap().expect("dhcp");
+        assert_eq!(message.op, 1);
+        assert_eq!(message.message_type, Some(3));
+        assert!(!message.is_ack());
+        assert_eq!(message.requested_ip, Some(Ipv4Addr::new(10, 0, 0, 20)));
+        assert_eq!(message.server_id, Some(Ipv4Addr::new(10, 0, 0, 1)));
+        assert_eq!(message.your_ip, Ipv4Addr::UNSPECIFIED);
+    }
+
+    #[test]
+    fn parse_rejects_truncated_packets_and_options() {
+        let mut payload = bootp();
+        assert!(matches!(
+            parse_dhcp(&payload[..30]),
+            Err(DhcpError::TooShort {
+                needed: 34,
+                actual: 30
+            })
+        ));
+        payload[236..240].copy_from_slice(&[0x63, 0x82, 0x53, 0x63]);
+        payload.extend_from_slice(&[53, 1, 5, 50, 4, 10]);
+        assert!(matches!(
+            parse_dhcp(&payload),
+            Err(DhcpError::OptionOverflow { offset: 243 })
+        ));
+    }
+}
@@ -0,0 +1,74 @@
+use core::net::Ipv4Addr;
+
+use super::error::DhcpError;
+use crate::arp::MacAddress;
+
+/// Safe byte reader for DHCP messages.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::dhcp::reader::DhcpReader;
+///
+/// let reader = DhcpReader::new(&[2, 1, 6, 0, 10, 0, 0, 1]);
+/// assert_eq!(reader.read_u8(0).unwrap(), 2);
+/// assert_eq!(reader.read_ipv4(4..8).unwrap().to_string(), "10.0.0.1");
+/// ```
+pub struct DhcpReader<'a> {
+    payload: &'a [u8],
+}
+
+impl<'a> DhcpReader<'a> {
+    pub fn new(payload: &'a [u8]) -> Self {
+        Self { payload }
+    }
+
+    /// Payload length in bytes.
+    pub fn len(&self) -> usize {
+        self.payload.len()
+    }
+
+    /// Whether the payload is empty.
+    pub fn is_empty(&self) -> bool {
+        self.payload.is_empty()
+    }
+
+    /// Read a single byte at the given offset.
+    pub fn read_u8(&self, offset: usize) -> Result<u8, DhcpError> {
+        self.payload
+            .get(offset)
+            .copied()
+            .ok_or(DhcpError::TooShort {
+                needed: offset + 1,
+                actual: self.payload.len(),
+            })
+    }
+
+    /// Read the bytes of the given range.
+    pub fn read_bytes(&self, range: core::ops::Range<usize>) -> Result<&'a [u8], DhcpError> {
+        self.payload.get(range.clone()).ok_or(DhcpError::TooShort {
+            needed: range.end,
+            actual: self.payload.len(),
+        })
+    }
+
+    /// Read an IPv4 address from the given range.
+    pub fn read_ipv4(&self, range: core::ops::Range<usize>) -> Result<Ipv4Addr, DhcpError> {
+        Ok(Ipv4Addr::from(self.read_array::<4>(range)?))
+    }
+
+    /// Read a hardware address from the given range.
+    pub fn read_mac(&self, range: core::ops::Range<usize>) -> Result<MacAddress, DhcpError> {
+        Ok(MacAddress(self.read_array(range)?))
+    }
+
+    fn read_array<const N: usize>(
+        &self,
+        range: core::ops::Range<usize>,
+    ) -> Result<[u8; N], DhcpError> {
+        let bytes = self.read_bytes(range)?;
+        bytes.try_into().map_err(|_| DhcpError::TooShort {
+            needed: N,
+            actual: bytes.len(),
+        })
+    }
+}
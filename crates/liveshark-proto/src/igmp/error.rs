@@ -0,0 +1,43 @@
+use core::fmt;
+
+/// Errors returned by IGMP parsing and reading.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::IgmpError;
+///
+/// let err = IgmpError::RecordOverflow { offset: 16 };
+/// assert!(err.to_string().contains("overflows"));
+/// assert_eq!(err.offset(), 16);
+/// ```
+#[derive(Debug)]
+pub enum IgmpError {
+    TooShort { needed: usize, actual: usize },
+    RecordOverflow { offset: usize },
+}
+
+impl IgmpError {
+    /// Payload offset of the field the error is about; 0 for truncated
+    /// messages.
+    pub fn offset(&self) -> usize {
+        match self {
+            Self::TooShort { .. } => 0,
+            Self::RecordOverflow { offset } => *offset,
+        }
+    }
+}
+
+impl fmt::Display for IgmpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooShort { needed, actual } => {
+                write!(f, "payload too short: need {needed} bytes, got {actual}")
+            }
+            Self::RecordOverflow { offset } => {
+                write!(f, "group record at offset {offset} overflows the message")
+            }
+        }
+    }
+}
+
+impl core::error::Error for IgmpError {}
@@ -0,0 +1,32 @@
+/// IP protocol number of IGMP.
+pub const IP_PROTOCOL: u8 = 2;
+
+/// Type, max response time (v2+) or reserved, checksum, group address.
+pub const MESSAGE_LEN: usize = 8;
+pub const TYPE_OFFSET: usize = 0;
+pub const GROUP_RANGE: core::ops::Range<usize> = 4..8;
+
+pub const TYPE_QUERY: u8 = 0x11;
+pub const TYPE_V1_REPORT: u8 = 0x12;
+pub const TYPE_V2_REPORT: u8 = 0x16;
+pub const TYPE_V2_LEAVE: u8 = 0x17;
+pub const TYPE_V3_REPORT: u8 = 0x22;
+
+/// Version 3 report: number of group records after two reserved bytes.
+pub const V3_RECORD_COUNT_RANGE: core::ops::Range<usize> = 6..8;
+pub const V3_RECORDS_OFFSET: usize = 8;
+/// Group record: type, auxiliary data length (32-bit words), number of
+/// sources, multicast address; then the sources and auxiliary data.
+pub const RECORD_HEADER_LEN: usize = 8;
+pub const RECORD_TYPE_OFFSET: usize = 0;
+pub const RECORD_AUX_WORDS_OFFSET: usize = 1;
+pub const RECORD_SOURCE_COUNT_RANGE: core::ops::Range<usize> = 2..4;
+pub const RECORD_GROUP_RANGE: core::ops::Range<usize> = 4..8;
+pub const RECORD_SOURCE_LEN: usize = 4;
+
+pub const RECORD_MODE_IS_INCLUDE: u8 = 1;
+pub const RECORD_MODE_IS_EXCLUDE: u8 = 2;
+pub const RECORD_CHANGE_TO_INCLUDE: u8 = 3;
+pub const RECORD_CHANGE_TO_EXCLUDE: u8 = 4;
+pub const RECORD_ALLOW_NEW_SOURCES: u8 = 5;
+pub const RECORD_BLOCK_OLD_SOURCES: u8 = 6;
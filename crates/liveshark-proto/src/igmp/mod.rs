@@ -0,0 +1,24 @@
+//! IGMP (RFC 1112, RFC 2236, RFC 3376) membership decoding.
+//!
+//! Receivers join and leave IPv4 multicast groups with IGMP, carried
+//! directly over IP (protocol number 2). The parser reads queries,
+//! version 1 and 2 membership reports and leaves, and version 3 reports,
+//! turning each v3 group record into a join or a leave: a record excluding
+//! sources (or including some) keeps the receiver in the group, and a
+//! record including no source takes it out. Other IGMP types (multicast
+//! router discovery, mtrace, DVMRP) are not membership messages.
+//!
+//! Errors report messages or group records that overflow the payload.
+//!
+//! Decoding (`reader` and `parse_igmp`) is compiled with the `igmp` feature;
+//! message types and `layout` are always available.
+//!
+pub mod error;
+pub mod layout;
+pub mod parser;
+#[cfg(feature = "igmp")]
+pub mod reader;
+
+#[cfg(feature = "igmp")]
+pub use parser::parse_igmp;
+pub use parser::{IgmpGroupChange, IgmpMessage};
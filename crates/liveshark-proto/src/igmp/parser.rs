@@ -0,0 +1,223 @@
+use alloc::vec::Vec;
+use core::net::Ipv4Addr;
+
+#[cfg(feature = "igmp")]
+use super::error::IgmpError;
+#[cfg(feature = "igmp")]
+use super::layout;
+#[cfg(feature = "igmp")]
+use super::reader::IgmpReader;
+
+/// A receiver joining or leaving one multicast group.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::IgmpGroupChange;
+///
+/// let change = IgmpGroupChange {
+///     group: "239.255.0.1".parse().unwrap(),
+///     joined: true,
+/// };
+/// assert!(change.joined);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IgmpGroupChange {
+    /// Multicast group address.
+    pub group: Ipv4Addr,
+    /// `true` for a join (or a report of continued membership), `false` for
+    /// a leave.
+    pub joined: bool,
+}
+
+/// Decoded IGMP membership message.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::{IgmpGroupChange, IgmpMessage};
+///
+/// let message = IgmpMessage::Membership {
+///     version: 2,
+///     changes: vec![IgmpGroupChange {
+///         group: "239.255.0.1".parse().unwrap(),
+///         joined: false,
+///     }],
+/// };
+/// assert!(matches!(message, IgmpMessage::Membership { version: 2, .. }));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IgmpMessage {
+    /// Membership query from a router or snooping querier; the group is
+    /// `0.0.0.0` for a general query.
+    Query { group: Ipv4Addr },
+    /// Membership report or leave, with one change per group.
+    Membership {
+        /// IGMP version of the message (1, 2, or 3).
+        version: u8,
+        changes: Vec<IgmpGroupChange>,
+    },
+}
+
+/// Parse an IGMP message from the payload of an IPv4 packet with protocol
+/// number 2.
+///
+/// Returns `Ok(None)` for IGMP types that are not membership messages.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::{IgmpMessage, parse_igmp};
+///
+/// // IGMPv2 membership report for the group of sACN universe 1.
+/// let payload = [0x16, 0, 0, 0, 239, 255, 0, 1];
+/// let message = parse_igmp(&payload)?.expect("membership");
+/// let IgmpMessage::Membership { version, changes } = message else {
+///     panic!("expected a report");
+/// };
+/// assert_eq!(version, 2);
+/// assert!(changes[0].joined);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// Returns `IgmpError` when the message, or one of its v3 group records, is
+/// truncated.
+#[cfg(feature = "igmp")]
+pub fn parse_igmp(payload: &[u8]) -> Result<Option<IgmpMessage>, IgmpError> {
+    let reader = IgmpReader::new(payload);
+    let message_type = reader.read_u8(layout::TYPE_OFFSET)?;
+    let (version, joined) = match message_type {
+        layout::TYPE_QUERY => {
+            return Ok(Some(IgmpMessage::Query {
+                group: reader.read_ipv4(layout::GROUP_RANGE)?,
+            }));
+        }
+        layout::TYPE_V1_REPORT => (1, true),
+        layout::TYPE_V2_REPORT => (2, true),
+        layout::TYPE_V2_LEAVE => (2, false),
+        layout::TYPE_V3_REPORT => return read_v3_report(&reader).map(Some),
+        _ => return Ok(None),
+    };
+    let group = reader.read_ipv4(layout::GROUP_RANGE)?;
+    Ok(Some(IgmpMessage::Membership {
+        version,
+        changes: alloc::vec![IgmpGroupChange { group, joined }],
+    }))
+}
+
+#[cfg(feature = "igmp")]
+fn read_v3_report(reader: &IgmpReader<'_>) -> Result<IgmpMessage, IgmpError> {
+    let count = reader.read_u16_be(layout::V3_RECORD_COUNT_RANGE)?;
+    let mut offset = layout::V3_RECORDS_OFFSET;
+    let mut changes = Vec::new();
+    for _ in 0..count {
+        let record_type = reader.read_u8(offset + layout::RECORD_TYPE_OFFSET)?;
+        let aux_words = reader.read_u8(offset + layout::RECORD_AUX_WORDS_OFFSET)?;
+        let sources = reader.read_u16_be(
+            offset + layout::RECORD_SOURCE_COUNT_RANGE.start
+                ..offset + layout::RECORD_SOURCE_COUNT_RANGE.end,
+        )?;
+        let group = reader.read_ipv4(
+            offset + layout::RECORD_GROUP_RANGE.start..offset + layout::RECORD_GROUP_RANGE.end,
+        )?;
+        let end = offset
+            + layout::RECORD_HEADER_LEN
+            + usize::from(sources) * layout::RECORD_SOURCE_LEN
+            + usize::from(aux_words) * 4;
+        if end > reader.len() {
+            return Err(IgmpError::RecordOverflow { offset });
+        }
+        let joined = match record_type {
+            layout::RECORD_MODE_IS_EXCLUDE | layout::RECORD_CHANGE_TO_EXCLUDE => Some(true),
+            layout::RECORD_MODE_IS_INCLUDE
+            | layout::RECORD_CHANGE_TO_INCLUDE
+            | layout::RECORD_ALLOW_NEW_SOURCES => Some(sources > 0),
+            // Blocking some sources (RECORD_BLOCK_OLD_SOURCES) says nothing
+            // about membership; unknown record types are skipped.
+            _ => None,
+        };
+        if let Some(joined) = joined {
+            changes.push(IgmpGroupChange { group, joined });
+        }
+        offset = end;
+    }
+    Ok(IgmpMessage::Membership {
+        version: 3,
+        changes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IgmpGroupChange, IgmpMessage, parse_igmp};
+    use crate::igmp::error::IgmpError;
+
+    fn record(record_type: u8, group: [u8; 4], sources: &[[u8; 4]]) -> Vec<u8> {
+        let mut out = vec![record_type, 0];
+        out.extend_from_slice(&(sources.len() as u16).to_be_bytes());
+        out.extend_from_slice(&group);
+        for source in sources {
+            out.extend_from_slice(source);
+        }
+        out
+    }
+
+    fn v3_report(records: &[Vec<u8>]) -> Vec<u8> {
+        let mut out = vec![0x22, 0, 0, 0, 0, 0];
+        out.extend_from_slice(&(records.len() as u16).to_be_bytes());
+        for record in records {
+            out.extend_from_slice(record);
+        }
+        out
+    }
+
+    #[test]
+    fn parse_reads_v2_leave_and_query() {
+        let leave = parse_igmp(&[0x17, 0, 0, 0, 239, 255, 0, 7]).unwrap();
+        assert_eq!(
+            leave,
+            Some(IgmpMessage::Membership {
+                version: 2,
+                changes: vec![IgmpGroupChange {
+                    group: "239.255.0.7".parse().unwrap(),
+                    joined: false,
+                }],
+            })
+        );
+        let query = parse_igmp(&[0x11, 100, 0, 0, 0, 0, 0, 0]).unwrap();
+        assert!(matches!(query, Some(IgmpMessage::Query { group }) if group.is_unspecified()));
+        assert_eq!(parse_igmp(&[0x13, 0, 0, 0, 0, 0, 0, 0]).unwrap(), None);
+    }
+
+    #[test]
+    fn parse_maps_v3_records_to_joins_and_leaves() {
+        let payload = v3_report(&[
+            record(4, [239, 255, 0, 1], &[]),
+            record(3, [239, 255, 0, 2], &[]),
+            record(5, [239, 255, 0, 3], &[[10, 0, 0, 1]]),
+            record(6, [239, 255, 0, 4], &[[10, 0, 0, 1]]),
+        ]);
+        let Some(IgmpMessage::Membership { version, changes }) = parse_igmp(&payload).unwrap()
+        else {
+            panic!("expected a report");
+        };
+        assert_eq!(version, 3);
+        let changes: Vec<(u8, bool)> = changes
+            .iter()
+            .map(|change| (change.group.octets()[3], change.joined))
+            .collect();
+        assert_eq!(changes, vec![(1, true), (2, false), (3, true)]);
+    }
+
+    #[test]
+    fn parse_rejects_truncated_records() {
+        let mut payload = v3_report(&[record(2, [239, 255, 0, 1], &[[10, 0, 0, 1]])]);
+        payload.truncate(payload.len() - 2);
+        assert!(matches!(
+            parse_igmp(&payload),
+            Err(IgmpError::RecordOverflow { offset: 8 })
+        ));
+        assert!(matches!(
+            parse_igmp(&[0x16, 0, 0]),
+            Err(IgmpError::TooShort { .. })
+        ));
+    }
+}
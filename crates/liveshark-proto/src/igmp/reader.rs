@@ -0,0 +1,68 @@
+use core::net::Ipv4Addr;
+
+use super::error::IgmpError;
+
+/// Safe byte reader for IGMP messages.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::igmp::reader::IgmpReader;
+///
+/// let reader = IgmpReader::new(&[0x16, 0, 0, 0, 239, 255, 0, 1]);
+/// assert_eq!(reader.read_u8(0).unwrap(), 0x16);
+/// assert_eq!(reader.read_ipv4(4..8).unwrap().to_string(), "239.255.0.1");
+/// ```
+pub struct IgmpReader<'a> {
+    payload: &'a [u8],
+}
+
+impl<'a> IgmpReader<'a> {
+    pub fn new(payload: &'a [u8]) -> Self {
+        Self { payload }
+    }
+
+    /// Length of the message.
+    pub fn len(&self) -> usize {
+        self.payload.len()
+    }
+
+    /// Whether the message is empty.
+    pub fn is_empty(&self) -> bool {
+        self.payload.is_empty()
+    }
+
+    /// Read a single byte at the given offset.
+    pub fn read_u8(&self, offset: usize) -> Result<u8, IgmpError> {
+        self.payload
+            .get(offset)
+            .copied()
+            .ok_or(IgmpError::TooShort {
+                needed: offset + 1,
+                actual: self.payload.len(),
+            })
+    }
+
+    /// Read a big-endian `u16` from the given range.
+    pub fn read_u16_be(&self, range: core::ops::Range<usize>) -> Result<u16, IgmpError> {
+        Ok(u16::from_be_bytes(self.read_array(range)?))
+    }
+
+    /// Read an IPv4 address from the given range.
+    pub fn read_ipv4(&self, range: core::ops::Range<usize>) -> Result<Ipv4Addr, IgmpError> {
+        Ok(Ipv4Addr::from(self.read_array::<4>(range)?))
+    }
+
+    fn read_array<const N: usize>(
+        &self,
+        range: core::ops::Range<usize>,
+    ) -> Result<[u8; N], IgmpError> {
+        let bytes = self.payload.get(range.clone()).ok_or(IgmpError::TooShort {
+            needed: range.end,
+            actual: self.payload.len(),
+        })?;
+        bytes.try_into().map_err(|_| IgmpError::TooShort {
+            needed: N,
+            actual: bytes.len(),
+        })
+    }
+}
@@ -0,0 +1,85 @@
+//! LiveShark wire-format decoders.
+//!
+//! The pure byte parsers behind `liveshark-core`: UDP extraction from
+//! Ethernet, raw IP, loopback, or 802.11 frames (optionally unwrapped from
+//! VXLAN, GRE, or ERSPAN tunnels), Art-Net (ArtDmx, ArtNzs, ArtPollReply,
+//! ArtSync, ArtTimeCode, ArtTrigger, ArtAddress, and RDM over Art-Net with
+//! ArtTodRequest, ArtTodData and ArtRdm), sACN (E1.31 data,
+//! synchronization, and universe discovery), PosiStageNet (tracker data and
+//! info packets), OSC (address patterns of messages and bundles), CITP
+//! (peer location and media-server layers), PTP (IEEE 1588 message
+//! headers and Announce), IGMP (multicast group joins and leaves), ARP
+//! (Ethernet/IPv4 requests and replies), and DHCP (lease fields and
+//! options). The crate is `no_std` and only needs `alloc`, so firmware and
+//! embedded gateways decode packets with the same validation as the desktop
+//! analyzer.
+//!
+//! Each protocol follows a layered structure:
+//! - `layout`: byte offsets and ranges (source of truth)
+//! - `reader`: safe byte access and protocol conventions
+//! - `parser`: domain-level decoding (no direct byte indexing)
+//! - `error`: explicit, actionable errors
+//!
+//! Parsers contain no I/O. The Art-Net, sACN, PSN, OSC, CITP, PTP, IGMP,
+//! ARP and DHCP decoders sit behind the `artnet`, `sacn`, `psn`, `osc`,
+//! `citp`, `ptp`, `igmp`, `arp` and `dhcp` features (all on by default).
+//!
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+pub mod arp;
+pub mod artnet;
+pub mod citp;
+mod common;
+pub mod dhcp;
+pub mod igmp;
+pub mod osc;
+pub mod psn;
+pub mod ptp;
+pub mod sacn;
+pub mod udp;
+
+#[cfg(feature = "arp")]
+pub use arp::parse_arp;
+pub use arp::{ArpPacket, MacAddress, error::ArpError};
+pub use artnet::{
+    ArtAddress, ArtDmx, ArtNzs, ArtPollReply, ArtRdm, ArtSync, ArtTimeCode, ArtTodData,
+    ArtTodRequest, ArtTrigger, PortAddress, RdmMessage, RdmUid,
+    error::{ArtNetError, PortAddressError},
+};
+#[cfg(feature = "artnet")]
+pub use artnet::{
+    parse_artaddress, parse_artdmx, parse_artnzs, parse_artpollreply, parse_artrdm, parse_artsync,
+    parse_arttimecode, parse_arttoddata, parse_arttodrequest, parse_arttrigger,
+};
+#[cfg(feature = "citp")]
+pub use citp::parse_citp;
+pub use citp::{
+    CitpHeader, CitpLayerStatus, CitpMessage, CitpPacket, CitpPeer, CitpStreamFrame,
+    error::CitpError,
+};
+#[cfg(feature = "dhcp")]
+pub use dhcp::parse_dhcp;
+pub use dhcp::{DhcpMessage, error::DhcpError};
+#[cfg(feature = "igmp")]
+pub use igmp::parse_igmp;
+pub use igmp::{IgmpGroupChange, IgmpMessage, error::IgmpError};
+#[cfg(feature = "osc")]
+pub use osc::parse_osc;
+pub use osc::{OscMessage, OscPacket, error::OscError};
+pub use psn::{PsnData, PsnInfo, PsnPacketHeader, PsnTracker, PsnTrackerName, error::PsnError};
+#[cfg(feature = "psn")]
+pub use psn::{parse_psn_data, parse_psn_info};
+#[cfg(feature = "ptp")]
+pub use ptp::parse_ptp;
+pub use ptp::{PtpAnnounce, PtpClockIdentity, PtpHeader, PtpMessage, error::PtpError};
+pub use sacn::{SacnDiscovery, SacnDmx, SacnSync, SourceNameIssue, error::SacnError};
+#[cfg(feature = "sacn")]
+pub use sacn::{
+    parse_sacn_address_priority, parse_sacn_discovery, parse_sacn_dmx, parse_sacn_sync,
+};
+pub use udp::{
+    ChecksumMismatch, LinkLayer, Tunnel, TunnelKind, UdpPacket, decapsulate, error::UdpError,
+    parse_truncated_udp_packet, parse_udp_packet,
+};
@@ -0,0 +1,63 @@
+use core::fmt;
+
+/// Errors returned by OSC parsing and reading.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::OscError;
+///
+/// let err = OscError::Misaligned { offset: 0, length: 6 };
+/// assert!(err.to_string().contains("multiple of 4"));
+/// assert_eq!(err.offset(), 0);
+/// ```
+#[derive(Debug)]
+pub enum OscError {
+    TooShort { needed: usize, actual: usize },
+    Misaligned { offset: usize, length: usize },
+    InvalidString { offset: usize },
+    InvalidTypeTags { offset: usize },
+    ElementOverflow { offset: usize, size: usize },
+}
+
+impl OscError {
+    /// Payload offset of the element the error is about; 0 for truncated
+    /// payloads.
+    pub fn offset(&self) -> usize {
+        match self {
+            Self::TooShort { .. } => 0,
+            Self::Misaligned { offset, .. }
+            | Self::InvalidString { offset }
+            | Self::InvalidTypeTags { offset }
+            | Self::ElementOverflow { offset, .. } => *offset,
+        }
+    }
+}
+
+impl fmt::Display for OscError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooShort { needed, actual } => {
+                write!(f, "payload too short: need {needed} bytes, got {actual}")
+            }
+            Self::Misaligned { offset, length } => write!(
+                f,
+                "element at offset {offset} has length {length}, not a multiple of 4"
+            ),
+            Self::InvalidString { offset } => {
+                write!(
+                    f,
+                    "string at offset {offset} is not a null-padded OSC string"
+                )
+            }
+            Self::InvalidTypeTags { offset } => {
+                write!(f, "type tags at offset {offset} do not start with ','")
+            }
+            Self::ElementOverflow { offset, size } => write!(
+                f,
+                "bundle element at offset {offset} of {size} bytes overflows the bundle"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for OscError {}
@@ -0,0 +1,17 @@
+/// Every OSC element (string, argument, bundle element) is padded to a
+/// multiple of this size.
+pub const ALIGNMENT: usize = 4;
+
+/// First byte of a message address pattern.
+pub const ADDRESS_PREFIX: u8 = b'/';
+/// First byte of a type tag string.
+pub const TYPE_TAGS_PREFIX: u8 = b',';
+
+/// Bundle identifier, null-terminated.
+pub const BUNDLE_ID: &[u8; 8] = b"#bundle\0";
+/// Time tag (NTP format, big-endian) after the bundle identifier.
+pub const BUNDLE_TIME_TAG_RANGE: core::ops::Range<usize> = 8..16;
+/// Bundle header: identifier and time tag.
+pub const BUNDLE_HEADER_LEN: usize = 16;
+/// Big-endian `i32` size before each bundle element.
+pub const BUNDLE_ELEMENT_SIZE_LEN: usize = 4;
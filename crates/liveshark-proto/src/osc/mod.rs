@@ -0,0 +1,24 @@
+//! Open Sound Control (OSC) packet detection.
+//!
+//! OSC has no fixed port or magic number: a packet is either a message,
+//! starting with its address pattern (`/...`), or a bundle, starting with
+//! `#bundle` and holding size-prefixed messages and bundles. Every element
+//! is padded to a multiple of four bytes. The parser reads the address
+//! patterns and the type tag strings only; arguments are left undecoded.
+//!
+//! A payload starting with neither prefix is not OSC. Errors report
+//! misaligned packets, address patterns or type tags that are not
+//! null-padded strings, and bundle elements that overflow the packet.
+//!
+//! Decoding (`reader` and `parse_osc`) is compiled with the `osc` feature;
+//! packet types and `layout` are always available.
+//!
+pub mod error;
+pub mod layout;
+pub mod parser;
+#[cfg(feature = "osc")]
+pub mod reader;
+
+#[cfg(feature = "osc")]
+pub use parser::parse_osc;
+pub use parser::{OscMessage, OscPacket};
@@ -0,0 +1,243 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "osc")]
+use super::error::OscError;
+#[cfg(feature = "osc")]
+use super::layout;
+#[cfg(feature = "osc")]
+use super::reader::OscReader;
+
+/// OSC message: its address pattern and type tags; arguments are not
+/// decoded.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::OscMessage;
+///
+/// let message = OscMessage {
+///     address: "/cue/12/go".to_string(),
+///     type_tags: Some("f".to_string()),
+/// };
+/// assert!(message.address.starts_with("/cue"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OscMessage {
+    /// Address pattern (`/...`).
+    pub address: String,
+    /// Argument types after the `,` of the type tag string; `None` when the
+    /// sender omitted it, as early OSC implementations do.
+    pub type_tags: Option<String>,
+}
+
+/// Parsed OSC packet: a lone message, or the messages of a bundle.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::{OscMessage, OscPacket};
+///
+/// let packet = OscPacket {
+///     messages: vec![OscMessage { address: "/go".to_string(), type_tags: None }],
+///     bundles: 0,
+/// };
+/// assert_eq!(packet.messages.len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OscPacket {
+    /// Messages, in packet order, nested bundles flattened.
+    pub messages: Vec<OscMessage>,
+    /// Bundles in the packet, nested ones included; 0 for a lone message.
+    pub bundles: usize,
+}
+
+/// Parse an OSC packet from a UDP payload.
+///
+/// Returns `Ok(None)` when the payload starts with neither an address
+/// pattern (`/`) nor `#bundle`.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::parse_osc;
+///
+/// let message = b"/fader/1\0\0\0\0,f\0\0\x3f\x00\x00\x00";
+/// let mut bundle = b"#bundle\0\0\0\0\0\0\0\0\x01".to_vec();
+/// bundle.extend_from_slice(&(message.len() as u32).to_be_bytes());
+/// bundle.extend_from_slice(message);
+///
+/// let packet = parse_osc(&bundle)?.expect("osc");
+/// assert_eq!(packet.bundles, 1);
+/// assert_eq!(packet.messages[0].address, "/fader/1");
+/// assert_eq!(packet.messages[0].type_tags.as_deref(), Some("f"));
+/// assert!(parse_osc(b"hello")?.is_none());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// Returns `OscError` when the packet or a bundle element is not a multiple
+/// of four bytes, a string is not null-padded printable ASCII, the type
+/// tags do not start with `,`, or a bundle element overflows its bundle.
+#[cfg(feature = "osc")]
+pub fn parse_osc(payload: &[u8]) -> Result<Option<OscPacket>, OscError> {
+    if !is_message(payload) && !payload.starts_with(layout::BUNDLE_ID) {
+        return Ok(None);
+    }
+    let reader = OscReader::new(payload);
+    let mut packet = OscPacket {
+        messages: Vec::new(),
+        bundles: 0,
+    };
+    // Elements still to read, last first, so messages keep packet order.
+    let mut pending = alloc::vec![0..payload.len()];
+    while let Some(range) = pending.pop() {
+        if range.len() % layout::ALIGNMENT != 0 {
+            return Err(OscError::Misaligned {
+                offset: range.start,
+                length: range.len(),
+            });
+        }
+        let element = reader.read_slice(range.clone())?;
+        if element.starts_with(layout::BUNDLE_ID) {
+            packet.bundles += 1;
+            let mut elements = read_bundle(&reader, range)?;
+            elements.reverse();
+            pending.extend(elements);
+        } else if is_message(element) {
+            packet.messages.push(read_message(&reader, range)?);
+        } else {
+            return Err(OscError::InvalidString {
+                offset: range.start,
+            });
+        }
+    }
+    Ok(Some(packet))
+}
+
+#[cfg(feature = "osc")]
+fn is_message(element: &[u8]) -> bool {
+    element.first() == Some(&layout::ADDRESS_PREFIX)
+}
+
+/// Payload ranges of the elements of the bundle filling `range`.
+#[cfg(feature = "osc")]
+fn read_bundle(
+    reader: &OscReader<'_>,
+    range: core::ops::Range<usize>,
+) -> Result<Vec<core::ops::Range<usize>>, OscError> {
+    if range.len() < layout::BUNDLE_HEADER_LEN {
+        return Err(OscError::TooShort {
+            needed: range.start + layout::BUNDLE_HEADER_LEN,
+            actual: range.end,
+        });
+    }
+    let mut elements = Vec::new();
+    let mut offset = range.start + layout::BUNDLE_HEADER_LEN;
+    while offset < range.end {
+        let size = reader.read_u32_be(offset)? as usize;
+        let start = offset + layout::BUNDLE_ELEMENT_SIZE_LEN;
+        if size > range.end.saturating_sub(start) {
+            return Err(OscError::ElementOverflow { offset, size });
+        }
+        if size == 0 {
+            return Err(OscError::Misaligned {
+                offset: start,
+                length: size,
+            });
+        }
+        elements.push(start..start + size);
+        offset = start + size;
+    }
+    Ok(elements)
+}
+
+#[cfg(feature = "osc")]
+fn read_message(
+    reader: &OscReader<'_>,
+    range: core::ops::Range<usize>,
+) -> Result<OscMessage, OscError> {
+    let (address, next) = reader.read_string(range.clone())?;
+    let type_tags = if next < range.end {
+        let (tags, _) = reader.read_string(next..range.end)?;
+        let Some(tags) = tags.strip_prefix(char::from(layout::TYPE_TAGS_PREFIX)) else {
+            return Err(OscError::InvalidTypeTags { offset: next });
+        };
+        Some(String::from(tags))
+    } else {
+        None
+    };
+    Ok(OscMessage {
+        address: String::from(address),
+        type_tags,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_osc;
+    use crate::osc::error::OscError;
+
+    fn bundle(elements: &[&[u8]]) -> Vec<u8> {
+        let mut out = b"#bundle\0".to_vec();
+        out.extend_from_slice(&1u64.to_be_bytes());
+        for element in elements {
+            out.extend_from_slice(&(element.len() as u32).to_be_bytes());
+            out.extend_from_slice(element);
+        }
+        out
+    }
+
+    #[test]
+    fn parse_message_reads_address_and_type_tags() {
+        let packet = parse_osc(b"/cue/1/go\0\0\0,\0\0\0").unwrap().expect("osc");
+        assert_eq!(packet.bundles, 0);
+        assert_eq!(packet.messages.len(), 1);
+        assert_eq!(packet.messages[0].address, "/cue/1/go");
+        assert_eq!(packet.messages[0].type_tags.as_deref(), Some(""));
+
+        let packet = parse_osc(b"/go\0").unwrap().expect("osc");
+        assert_eq!(packet.messages[0].type_tags, None);
+    }
+
+    #[test]
+    fn parse_bundle_flattens_nested_bundles_in_order() {
+        let inner = bundle(&[b"/b\0\0", b"/c\0\0"]);
+        let payload = bundle(&[b"/a\0\0", &inner, b"/d\0\0,\0\0\0"]);
+        let packet = parse_osc(&payload).unwrap().expect("osc");
+        assert_eq!(packet.bundles, 2);
+        let addresses: Vec<&str> = packet
+            .messages
+            .iter()
+            .map(|message| message.address.as_str())
+            .collect();
+        assert_eq!(addresses, ["/a", "/b", "/c", "/d"]);
+    }
+
+    #[test]
+    fn parse_rejects_malformed_packets() {
+        assert!(parse_osc(b"").unwrap().is_none());
+        assert!(parse_osc(b"GET / HTTP/1.1\r\n").unwrap().is_none());
+        assert!(matches!(
+            parse_osc(b"/go\0\0\0"),
+            Err(OscError::Misaligned {
+                offset: 0,
+                length: 6
+            })
+        ));
+        assert!(matches!(
+            parse_osc(b"/go\0i\0\0\0"),
+            Err(OscError::InvalidTypeTags { offset: 4 })
+        ));
+        let mut payload = bundle(&[b"/a\0\0"]);
+        payload[19] = 8;
+        assert!(matches!(
+            parse_osc(&payload),
+            Err(OscError::ElementOverflow {
+                offset: 16,
+                size: 8
+            })
+        ));
+        assert!(matches!(
+            parse_osc(&bundle(&[b"xy\0\0"])),
+            Err(OscError::InvalidString { offset: 20 })
+        ));
+    }
+}
@@ -0,0 +1,100 @@
+use super::error::OscError;
+use super::layout;
+
+/// Safe byte reader for OSC payloads.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::osc::reader::OscReader;
+///
+/// let payload = b"/go\0,i\0\0\0\0\0\x01";
+/// let reader = OscReader::new(payload);
+/// let (address, next) = reader.read_string(0..payload.len()).unwrap();
+/// assert_eq!((address, next), ("/go", 4));
+/// assert_eq!(reader.read_u32_be(8).unwrap(), 1);
+/// ```
+pub struct OscReader<'a> {
+    payload: &'a [u8],
+}
+
+impl<'a> OscReader<'a> {
+    pub fn new(payload: &'a [u8]) -> Self {
+        Self { payload }
+    }
+
+    /// Read a big-endian `u32` at the given offset.
+    pub fn read_u32_be(&self, offset: usize) -> Result<u32, OscError> {
+        let bytes = self.read_slice(offset..offset + 4)?;
+        let bytes: [u8; 4] = bytes.try_into().map_err(|_| OscError::TooShort {
+            needed: offset + 4,
+            actual: self.payload.len(),
+        })?;
+        Ok(u32::from_be_bytes(bytes))
+    }
+
+    /// Read a byte slice from the given range.
+    pub fn read_slice(&self, range: core::ops::Range<usize>) -> Result<&'a [u8], OscError> {
+        self.payload.get(range.clone()).ok_or(OscError::TooShort {
+            needed: range.end,
+            actual: self.payload.len(),
+        })
+    }
+
+    /// Read the OSC string starting `range`: printable ASCII, then one to
+    /// four null bytes up to the next multiple of four. Returns the string
+    /// and the offset after its padding.
+    pub fn read_string(
+        &self,
+        range: core::ops::Range<usize>,
+    ) -> Result<(&'a str, usize), OscError> {
+        let invalid = OscError::InvalidString {
+            offset: range.start,
+        };
+        let bytes = self.read_slice(range.clone())?;
+        let Some(len) = bytes.iter().position(|byte| *byte == 0) else {
+            return Err(invalid);
+        };
+        let padded = (len / layout::ALIGNMENT + 1) * layout::ALIGNMENT;
+        if padded > bytes.len()
+            || bytes[len..padded].iter().any(|byte| *byte != 0)
+            || !bytes[..len].iter().all(u8::is_ascii_graphic)
+        {
+            return Err(invalid);
+        }
+        // Checked ASCII above.
+        let text = core::str::from_utf8(&bytes[..len]).map_err(|_| OscError::InvalidString {
+            offset: range.start,
+        })?;
+        Ok((text, range.start + padded))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OscReader;
+    use crate::osc::error::OscError;
+
+    #[test]
+    fn read_string_skips_padding() {
+        let payload = b"/a/b\0\0\0\0,\0\0\0";
+        let reader = OscReader::new(payload);
+        assert_eq!(reader.read_string(0..12).unwrap(), ("/a/b", 8));
+        assert_eq!(reader.read_string(8..12).unwrap(), (",", 12));
+    }
+
+    #[test]
+    fn read_string_rejects_unterminated_and_dirty_padding() {
+        let reader = OscReader::new(b"/abcd\0\0");
+        assert!(matches!(
+            reader.read_string(0..7),
+            Err(OscError::InvalidString { offset: 0 })
+        ));
+        let reader = OscReader::new(b"/a\0x");
+        assert!(matches!(
+            reader.read_string(0..4),
+            Err(OscError::InvalidString { offset: 0 })
+        ));
+        let reader = OscReader::new(b"/a b\0\0\0\0");
+        assert!(reader.read_string(0..8).is_err());
+    }
+}
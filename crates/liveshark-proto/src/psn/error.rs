@@ -0,0 +1,62 @@
+use core::fmt;
+
+/// Errors returned by PSN parsing and reading.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::PsnError;
+///
+/// let err = PsnError::ChunkOverflow { id: 1, offset: 20 };
+/// assert!(err.to_string().contains("overflows"));
+/// assert_eq!(err.offset(), 20);
+/// ```
+#[derive(Debug)]
+pub enum PsnError {
+    TooShort {
+        needed: usize,
+        actual: usize,
+    },
+    ChunkOverflow {
+        id: u16,
+        offset: usize,
+    },
+    InvalidChunkLength {
+        id: u16,
+        offset: usize,
+        length: usize,
+    },
+    MissingHeader,
+}
+
+impl PsnError {
+    /// Payload offset of the chunk the error is about; 0 for truncated
+    /// payloads and missing headers.
+    pub fn offset(&self) -> usize {
+        match self {
+            Self::TooShort { .. } | Self::MissingHeader => 0,
+            Self::ChunkOverflow { offset, .. } | Self::InvalidChunkLength { offset, .. } => *offset,
+        }
+    }
+}
+
+impl fmt::Display for PsnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooShort { needed, actual } => {
+                write!(f, "payload too short: need {needed} bytes, got {actual}")
+            }
+            Self::ChunkOverflow { id, offset } => {
+                write!(f, "chunk {id:#06x} at offset {offset} overflows its parent")
+            }
+            Self::InvalidChunkLength { id, offset, length } => {
+                write!(
+                    f,
+                    "chunk {id:#06x} at offset {offset} has invalid length {length}"
+                )
+            }
+            Self::MissingHeader => f.write_str("missing packet header chunk"),
+        }
+    }
+}
+
+impl core::error::Error for PsnError {}
@@ -0,0 +1,54 @@
+/// UDP port PSN streams are sent to by default.
+pub const DEFAULT_PORT: u16 = 56565;
+/// Multicast group PSN streams are sent to by default.
+pub const DEFAULT_MULTICAST_GROUP: [u8; 4] = [236, 10, 10, 10];
+
+/// Chunk header: id (`u16`), then data length (15 bits) and the
+/// sub-chunk flag (most significant bit), little-endian.
+pub const CHUNK_HEADER_LEN: usize = 4;
+pub const CHUNK_ID_RANGE: core::ops::Range<usize> = 0..2;
+pub const CHUNK_LENGTH_RANGE: core::ops::Range<usize> = 2..4;
+pub const CHUNK_LENGTH_MASK: u16 = 0x7fff;
+pub const CHUNK_HAS_SUBCHUNKS: u16 = 0x8000;
+
+/// Root chunk of data packets (tracker state).
+pub const DATA_PACKET: u16 = 0x6755;
+/// Root chunk of info packets (system and tracker names).
+pub const INFO_PACKET: u16 = 0x6756;
+
+/// Packet header chunk, first in data and info packets.
+pub const PACKET_HEADER: u16 = 0x0000;
+pub const PACKET_HEADER_LEN: usize = 12;
+pub const HEADER_TIMESTAMP_RANGE: core::ops::Range<usize> = 0..8;
+pub const HEADER_VERSION_HIGH_OFFSET: usize = 8;
+pub const HEADER_VERSION_LOW_OFFSET: usize = 9;
+pub const HEADER_FRAME_ID_OFFSET: usize = 10;
+pub const HEADER_FRAME_PACKET_COUNT_OFFSET: usize = 11;
+
+/// Data packet chunk listing trackers; each sub-chunk id is a tracker id.
+pub const DATA_TRACKER_LIST: u16 = 0x0001;
+/// Tracker position (three `f32`, metres).
+pub const TRACKER_POSITION: u16 = 0x0000;
+/// Tracker speed (three `f32`, metres per second).
+pub const TRACKER_SPEED: u16 = 0x0001;
+/// Tracker orientation (three `f32`, rotation axis scaled by the angle in
+/// radians).
+pub const TRACKER_ORIENTATION: u16 = 0x0002;
+/// Tracker validity (one `f32`).
+pub const TRACKER_STATUS: u16 = 0x0003;
+/// Tracker acceleration (three `f32`, metres per second squared).
+pub const TRACKER_ACCELERATION: u16 = 0x0004;
+/// Tracker target position (three `f32`, metres).
+pub const TRACKER_TARGET_POSITION: u16 = 0x0005;
+/// Tracker timestamp (`u64`, microseconds).
+pub const TRACKER_TIMESTAMP: u16 = 0x0006;
+pub const VECTOR_LEN: usize = 12;
+pub const STATUS_LEN: usize = 4;
+pub const TIMESTAMP_LEN: usize = 8;
+
+/// Info packet chunk holding the system name.
+pub const INFO_SYSTEM_NAME: u16 = 0x0001;
+/// Info packet chunk listing trackers; each sub-chunk id is a tracker id.
+pub const INFO_TRACKER_LIST: u16 = 0x0002;
+/// Tracker name, inside an info tracker chunk.
+pub const INFO_TRACKER_NAME: u16 = 0x0000;
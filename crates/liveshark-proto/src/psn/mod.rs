@@ -0,0 +1,24 @@
+//! PosiStageNet (PSN) protocol decoding.
+//!
+//! PSN packets are trees of little-endian chunks: each chunk header packs a
+//! 16-bit id, a 15-bit data length, and a flag telling whether the data is
+//! made of sub-chunks. The parser walks data packets (tracker positions,
+//! speeds, orientations) and info packets (system and tracker names), and
+//! skips chunk ids it does not know, as the protocol requires.
+//!
+//! A payload whose root chunk is not a PSN packet, or whose length does not
+//! match the datagram, is not PSN. Errors report chunks that overflow their
+//! parent, fields of the wrong size, and packets without their header.
+//!
+//! Decoding (`reader` and the `parse_*` functions) is compiled with the
+//! `psn` feature; packet types and `layout` are always available.
+//!
+pub mod error;
+pub mod layout;
+pub mod parser;
+#[cfg(feature = "psn")]
+pub mod reader;
+
+pub use parser::{PsnData, PsnInfo, PsnPacketHeader, PsnTracker, PsnTrackerName};
+#[cfg(feature = "psn")]
+pub use parser::{parse_psn_data, parse_psn_info};
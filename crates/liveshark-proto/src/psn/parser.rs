@@ -0,0 +1,485 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "psn")]
+use super::error::PsnError;
+#[cfg(feature = "psn")]
+use super::layout;
+#[cfg(feature = "psn")]
+use super::reader::{PsnChunk, PsnReader};
+
+/// Header chunk shared by PSN data and info packets.
+///
+/// A frame too large for one datagram is split into `frame_packet_count`
+/// packets carrying the same `frame_id`.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::PsnPacketHeader;
+///
+/// let header = PsnPacketHeader {
+///     timestamp_us: 1_000_000,
+///     version_high: 2,
+///     version_low: 3,
+///     frame_id: 7,
+///     frame_packet_count: 1,
+/// };
+/// assert_eq!(header.frame_id, 7);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PsnPacketHeader {
+    /// Sender clock when the packet was built (microseconds).
+    pub timestamp_us: u64,
+    /// Protocol version, major part.
+    pub version_high: u8,
+    /// Protocol version, minor part.
+    pub version_low: u8,
+    /// Frame the packet belongs to (wraps at 255).
+    pub frame_id: u8,
+    /// Packets making up the frame.
+    pub frame_packet_count: u8,
+}
+
+/// State of one tracker in a PSN data packet; fields the sender left out
+/// are `None`.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::PsnTracker;
+///
+/// let tracker = PsnTracker {
+///     id: 1,
+///     position: Some([1.0, 2.0, 0.5]),
+///     speed: Some([0.0, 0.5, 0.0]),
+///     ..PsnTracker::default()
+/// };
+/// assert_eq!(tracker.position.unwrap()[1], 2.0);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PsnTracker {
+    /// Tracker id.
+    pub id: u16,
+    /// Position (x, y, z metres).
+    pub position: Option<[f32; 3]>,
+    /// Speed (x, y, z metres per second).
+    pub speed: Option<[f32; 3]>,
+    /// Orientation (rotation axis scaled by the angle in radians).
+    pub orientation: Option<[f32; 3]>,
+    /// Validity reported by the tracking system.
+    pub validity: Option<f32>,
+    /// Acceleration (x, y, z metres per second squared).
+    pub acceleration: Option<[f32; 3]>,
+    /// Target position (x, y, z metres).
+    pub target_position: Option<[f32; 3]>,
+    /// Tracker clock when the state was measured (microseconds).
+    pub timestamp_us: Option<u64>,
+}
+
+/// Parsed PSN data packet: tracker states, in packet order.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::{PsnData, PsnPacketHeader, PsnTracker};
+///
+/// let data = PsnData {
+///     header: PsnPacketHeader {
+///         timestamp_us: 0,
+///         version_high: 2,
+///         version_low: 3,
+///         frame_id: 0,
+///         frame_packet_count: 1,
+///     },
+///     trackers: vec![PsnTracker { id: 4, ..PsnTracker::default() }],
+/// };
+/// assert_eq!(data.trackers[0].id, 4);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PsnData {
+    /// Packet header.
+    pub header: PsnPacketHeader,
+    /// Trackers listed in the packet.
+    pub trackers: Vec<PsnTracker>,
+}
+
+/// Name of one tracker in a PSN info packet.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::PsnTrackerName;
+///
+/// let name = PsnTrackerName { id: 1, name: "Lead vocal".to_string() };
+/// assert_eq!(name.name, "Lead vocal");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PsnTrackerName {
+    /// Tracker id.
+    pub id: u16,
+    /// Name given in the tracking system.
+    pub name: String,
+}
+
+/// Parsed PSN info packet: the tracking system and its tracker names.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::{PsnInfo, PsnPacketHeader};
+///
+/// let info = PsnInfo {
+///     header: PsnPacketHeader {
+///         timestamp_us: 0,
+///         version_high: 2,
+///         version_low: 3,
+///         frame_id: 0,
+///         frame_packet_count: 1,
+///     },
+///     system_name: Some("stage tracking".to_string()),
+///     trackers: Vec::new(),
+/// };
+/// assert_eq!(info.system_name.as_deref(), Some("stage tracking"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PsnInfo {
+    /// Packet header.
+    pub header: PsnPacketHeader,
+    /// Name of the tracking system, when sent.
+    pub system_name: Option<String>,
+    /// Tracker names, in packet order.
+    pub trackers: Vec<PsnTrackerName>,
+}
+
+/// Parse a PSN data packet from a UDP payload.
+///
+/// Returns `Ok(None)` when the payload is not a PSN data packet: the root
+/// chunk id differs or its length does not match the payload. Unknown
+/// chunks are skipped.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::parse_psn_data;
+///
+/// fn chunk(id: u16, subchunks: bool, data: &[u8]) -> Vec<u8> {
+///     let length = data.len() as u16 | if subchunks { 0x8000 } else { 0 };
+///     let mut out = id.to_le_bytes().to_vec();
+///     out.extend_from_slice(&length.to_le_bytes());
+///     out.extend_from_slice(data);
+///     out
+/// }
+///
+/// let header = [0, 0, 0, 0, 0, 0, 0, 0, 2, 3, 0, 1]; // timestamp, v2.03, frame 0 of 1
+/// let position: Vec<u8> = [1.0f32, 2.0, 0.5].iter().flat_map(|v| v.to_le_bytes()).collect();
+/// let tracker = chunk(7, true, &chunk(0x0000, false, &position));
+/// let mut body = chunk(0x0000, false, &header);
+/// body.extend(chunk(0x0001, true, &tracker));
+/// let payload = chunk(0x6755, true, &body);
+///
+/// let data = parse_psn_data(&payload)?.expect("psn data");
+/// assert_eq!(data.trackers[0].id, 7);
+/// assert_eq!(data.trackers[0].position, Some([1.0, 2.0, 0.5]));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// Returns `PsnError` when a chunk overflows its parent, a field has the
+/// wrong size, or the packet header is missing.
+#[cfg(feature = "psn")]
+pub fn parse_psn_data(payload: &[u8]) -> Result<Option<PsnData>, PsnError> {
+    let reader = PsnReader::new(payload);
+    let Some(chunks) = read_root(&reader, payload.len(), layout::DATA_PACKET)? else {
+        return Ok(None);
+    };
+    let header = read_header(&reader, &chunks)?;
+    let mut trackers = Vec::new();
+    for list in chunks.iter().filter(|c| c.id == layout::DATA_TRACKER_LIST) {
+        for chunk in reader.read_chunks(list.data.clone())? {
+            trackers.push(read_tracker(&reader, &chunk)?);
+        }
+    }
+    Ok(Some(PsnData { header, trackers }))
+}
+
+/// Parse a PSN info packet from a UDP payload.
+///
+/// Returns `Ok(None)` when the payload is not a PSN info packet. Unknown
+/// chunks are skipped.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::parse_psn_info;
+///
+/// fn chunk(id: u16, subchunks: bool, data: &[u8]) -> Vec<u8> {
+///     let length = data.len() as u16 | if subchunks { 0x8000 } else { 0 };
+///     let mut out = id.to_le_bytes().to_vec();
+///     out.extend_from_slice(&length.to_le_bytes());
+///     out.extend_from_slice(data);
+///     out
+/// }
+///
+/// let header = [0, 0, 0, 0, 0, 0, 0, 0, 2, 3, 0, 1];
+/// let tracker = chunk(7, true, &chunk(0x0000, false, b"Lead vocal"));
+/// let mut body = chunk(0x0000, false, &header);
+/// body.extend(chunk(0x0001, false, b"stage tracking"));
+/// body.extend(chunk(0x0002, true, &tracker));
+/// let payload = chunk(0x6756, true, &body);
+///
+/// let info = parse_psn_info(&payload)?.expect("psn info");
+/// assert_eq!(info.system_name.as_deref(), Some("stage tracking"));
+/// assert_eq!(info.trackers[0].name, "Lead vocal");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// Returns `PsnError` when a chunk overflows its parent or the packet
+/// header is missing or malformed.
+#[cfg(feature = "psn")]
+pub fn parse_psn_info(payload: &[u8]) -> Result<Option<PsnInfo>, PsnError> {
+    let reader = PsnReader::new(payload);
+    let Some(chunks) = read_root(&reader, payload.len(), layout::INFO_PACKET)? else {
+        return Ok(None);
+    };
+    let header = read_header(&reader, &chunks)?;
+    let mut system_name = None;
+    let mut trackers = Vec::new();
+    for chunk in &chunks {
+        match chunk.id {
+            layout::INFO_SYSTEM_NAME => system_name = Some(reader.read_name(chunk)?),
+            layout::INFO_TRACKER_LIST => {
+                for tracker in reader.read_chunks(chunk.data.clone())? {
+                    for field in reader.read_chunks(tracker.data.clone())? {
+                        if field.id == layout::INFO_TRACKER_NAME {
+                            trackers.push(PsnTrackerName {
+                                id: tracker.id,
+                                name: reader.read_name(&field)?,
+                            });
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(Some(PsnInfo {
+        header,
+        system_name,
+        trackers,
+    }))
+}
+
+/// Chunks of the root chunk `id`, or `None` when the payload is not a PSN
+/// packet of that kind (the root chunk must span the whole payload).
+#[cfg(feature = "psn")]
+fn read_root(
+    reader: &PsnReader<'_>,
+    payload_len: usize,
+    id: u16,
+) -> Result<Option<Vec<PsnChunk>>, PsnError> {
+    let Ok(root) = reader.read_chunk(0) else {
+        return Ok(None);
+    };
+    if root.id != id || !root.has_subchunks || root.data.end != payload_len {
+        return Ok(None);
+    }
+    reader.read_chunks(root.data).map(Some)
+}
+
+#[cfg(feature = "psn")]
+fn read_header(reader: &PsnReader<'_>, chunks: &[PsnChunk]) -> Result<PsnPacketHeader, PsnError> {
+    let chunk = chunks
+        .iter()
+        .find(|chunk| chunk.id == layout::PACKET_HEADER)
+        .ok_or(PsnError::MissingHeader)?;
+    reader.require_chunk_len(chunk, layout::PACKET_HEADER_LEN)?;
+    let at = |offset: usize| chunk.data.start + offset;
+    Ok(PsnPacketHeader {
+        timestamp_us: reader.read_u64_le(
+            at(layout::HEADER_TIMESTAMP_RANGE.start)..at(layout::HEADER_TIMESTAMP_RANGE.end),
+        )?,
+        version_high: reader.read_u8(at(layout::HEADER_VERSION_HIGH_OFFSET))?,
+        version_low: reader.read_u8(at(layout::HEADER_VERSION_LOW_OFFSET))?,
+        frame_id: reader.read_u8(at(layout::HEADER_FRAME_ID_OFFSET))?,
+        frame_packet_count: reader.read_u8(at(layout::HEADER_FRAME_PACKET_COUNT_OFFSET))?,
+    })
+}
+
+#[cfg(feature = "psn")]
+fn read_tracker(reader: &PsnReader<'_>, chunk: &PsnChunk) -> Result<PsnTracker, PsnError> {
+    let mut tracker = PsnTracker {
+        id: chunk.id,
+        ..PsnTracker::default()
+    };
+    for field in reader.read_chunks(chunk.data.clone())? {
+        match field.id {
+            layout::TRACKER_POSITION => tracker.position = Some(reader.read_vector(&field)?),
+            layout::TRACKER_SPEED => tracker.speed = Some(reader.read_vector(&field)?),
+            layout::TRACKER_ORIENTATION => tracker.orientation = Some(reader.read_vector(&field)?),
+            layout::TRACKER_STATUS => {
+                reader.require_chunk_len(&field, layout::STATUS_LEN)?;
+                tracker.validity = Some(reader.read_f32_le(field.data.start)?);
+            }
+            layout::TRACKER_ACCELERATION => {
+                tracker.acceleration = Some(reader.read_vector(&field)?);
+            }
+            layout::TRACKER_TARGET_POSITION => {
+                tracker.target_position = Some(reader.read_vector(&field)?);
+            }
+            layout::TRACKER_TIMESTAMP => {
+                reader.require_chunk_len(&field, layout::TIMESTAMP_LEN)?;
+                tracker.timestamp_us = Some(reader.read_u64_le(field.data.clone())?);
+            }
+            _ => {}
+        }
+    }
+    Ok(tracker)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_psn_data, parse_psn_info};
+    use crate::psn::error::PsnError;
+    use crate::psn::layout;
+
+    fn chunk(id: u16, subchunks: bool, data: &[u8]) -> Vec<u8> {
+        let flag = if subchunks {
+            layout::CHUNK_HAS_SUBCHUNKS
+        } else {
+            0
+        };
+        let mut out = id.to_le_bytes().to_vec();
+        out.extend_from_slice(&(data.len() as u16 | flag).to_le_bytes());
+        out.extend_from_slice(data);
+        out
+    }
+
+    fn vector(values: [f32; 3]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect()
+    }
+
+    fn header(frame_id: u8) -> Vec<u8> {
+        let mut data = 5_000_000u64.to_le_bytes().to_vec();
+        data.extend_from_slice(&[2, 3, frame_id, 1]);
+        chunk(layout::PACKET_HEADER, false, &data)
+    }
+
+    fn packet(root: u16, chunks: &[Vec<u8>]) -> Vec<u8> {
+        chunk(root, true, &chunks.concat())
+    }
+
+    #[test]
+    fn parse_data_reads_every_tracker_field() {
+        let first = [
+            chunk(layout::TRACKER_POSITION, false, &vector([1.0, 2.0, 0.5])),
+            chunk(layout::TRACKER_SPEED, false, &vector([0.0, 0.5, 0.0])),
+            chunk(layout::TRACKER_ORIENTATION, false, &vector([0.0, 0.0, 1.5])),
+            chunk(layout::TRACKER_STATUS, false, &0.75f32.to_le_bytes()),
+            chunk(
+                layout::TRACKER_ACCELERATION,
+                false,
+                &vector([0.1, 0.0, 0.0]),
+            ),
+            chunk(
+                layout::TRACKER_TARGET_POSITION,
+                false,
+                &vector([3.0, 2.0, 0.5]),
+            ),
+            chunk(layout::TRACKER_TIMESTAMP, false, &42u64.to_le_bytes()),
+            chunk(0x0100, false, &[1, 2, 3]),
+        ]
+        .concat();
+        let second = chunk(layout::TRACKER_POSITION, false, &vector([4.0, 0.0, 0.0]));
+        let trackers = [chunk(1, true, &first), chunk(9, true, &second)].concat();
+        let payload = packet(
+            layout::DATA_PACKET,
+            &[header(3), chunk(layout::DATA_TRACKER_LIST, true, &trackers)],
+        );
+
+        let data = parse_psn_data(&payload).unwrap().expect("psn data");
+        assert_eq!(data.header.timestamp_us, 5_000_000);
+        assert_eq!((data.header.version_high, data.header.version_low), (2, 3));
+        assert_eq!(data.header.frame_id, 3);
+        assert_eq!(data.trackers.len(), 2);
+        let tracker = &data.trackers[0];
+        assert_eq!(tracker.id, 1);
+        assert_eq!(tracker.position, Some([1.0, 2.0, 0.5]));
+        assert_eq!(tracker.speed, Some([0.0, 0.5, 0.0]));
+        assert_eq!(tracker.orientation, Some([0.0, 0.0, 1.5]));
+        assert_eq!(tracker.validity, Some(0.75));
+        assert_eq!(tracker.acceleration, Some([0.1, 0.0, 0.0]));
+        assert_eq!(tracker.target_position, Some([3.0, 2.0, 0.5]));
+        assert_eq!(tracker.timestamp_us, Some(42));
+        assert_eq!(data.trackers[1].id, 9);
+        assert_eq!(data.trackers[1].speed, None);
+    }
+
+    #[test]
+    fn parse_info_reads_system_and_tracker_names() {
+        let trackers = [
+            chunk(1, true, &chunk(layout::INFO_TRACKER_NAME, false, b"Lead")),
+            chunk(2, true, &chunk(layout::INFO_TRACKER_NAME, false, b"Bass")),
+        ]
+        .concat();
+        let payload = packet(
+            layout::INFO_PACKET,
+            &[
+                header(0),
+                chunk(layout::INFO_SYSTEM_NAME, false, b"stage"),
+                chunk(layout::INFO_TRACKER_LIST, true, &trackers),
+            ],
+        );
+
+        let info = parse_psn_info(&payload).unwrap().expect("psn info");
+        assert_eq!(info.system_name.as_deref(), Some("stage"));
+        assert_eq!(info.trackers.len(), 2);
+        assert_eq!(info.trackers[1].id, 2);
+        assert_eq!(info.trackers[1].name, "Bass");
+        assert!(parse_psn_data(&payload).unwrap().is_none());
+    }
+
+    #[test]
+    fn other_payloads_are_not_psn() {
+        assert!(parse_psn_data(&[]).unwrap().is_none());
+        assert!(parse_psn_data(b"Art-Net\0").unwrap().is_none());
+        let mut payload = packet(layout::DATA_PACKET, &[header(0)]);
+        payload.push(0);
+        assert!(parse_psn_data(&payload).unwrap().is_none());
+    }
+
+    #[test]
+    fn malformed_packets_are_rejected() {
+        let payload = packet(
+            layout::DATA_PACKET,
+            &[chunk(layout::DATA_TRACKER_LIST, true, &[])],
+        );
+        assert!(matches!(
+            parse_psn_data(&payload).unwrap_err(),
+            PsnError::MissingHeader
+        ));
+
+        let position = chunk(layout::TRACKER_POSITION, false, &[0; 8]);
+        let payload = packet(
+            layout::DATA_PACKET,
+            &[
+                header(0),
+                chunk(layout::DATA_TRACKER_LIST, true, &chunk(1, true, &position)),
+            ],
+        );
+        assert!(matches!(
+            parse_psn_data(&payload).unwrap_err(),
+            PsnError::InvalidChunkLength {
+                id: 0,
+                length: 8,
+                ..
+            }
+        ));
+
+        let mut list = chunk(layout::DATA_TRACKER_LIST, true, &[0; 4]);
+        list[4..6].copy_from_slice(&1u16.to_le_bytes());
+        list[6..8].copy_from_slice(&0x8010u16.to_le_bytes());
+        let payload = packet(layout::DATA_PACKET, &[header(0), list]);
+        assert!(matches!(
+            parse_psn_data(&payload).unwrap_err(),
+            PsnError::ChunkOverflow { id: 1, .. }
+        ));
+    }
+}
@@ -0,0 +1,209 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::error::PsnError;
+use super::layout;
+
+/// One chunk of a PSN packet: its id and the payload range of its data.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::psn::reader::PsnChunk;
+///
+/// let chunk = PsnChunk { id: 1, has_subchunks: true, offset: 4, data: 8..20 };
+/// assert_eq!(chunk.data.len(), 12);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PsnChunk {
+    /// Chunk id; meaning depends on the parent chunk.
+    pub id: u16,
+    /// Whether the data is made of sub-chunks.
+    pub has_subchunks: bool,
+    /// Payload offset of the chunk header.
+    pub offset: usize,
+    /// Payload range of the chunk data (header excluded).
+    pub data: core::ops::Range<usize>,
+}
+
+/// Safe byte reader for PSN payloads.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::psn::reader::PsnReader;
+///
+/// // Chunk 0x0003 holding one little-endian f32.
+/// let mut payload = vec![0x03, 0x00, 0x04, 0x00];
+/// payload.extend_from_slice(&1.0f32.to_le_bytes());
+/// let reader = PsnReader::new(&payload);
+/// let chunks = reader.read_chunks(0..payload.len()).unwrap();
+/// assert_eq!(chunks[0].id, 3);
+/// assert_eq!(reader.read_f32_le(chunks[0].data.start).unwrap(), 1.0);
+/// ```
+pub struct PsnReader<'a> {
+    payload: &'a [u8],
+}
+
+impl<'a> PsnReader<'a> {
+    pub fn new(payload: &'a [u8]) -> Self {
+        Self { payload }
+    }
+
+    /// Read a little-endian `u16` from the given range.
+    pub fn read_u16_le(&self, range: core::ops::Range<usize>) -> Result<u16, PsnError> {
+        let bytes = self.read_array::<2>(range)?;
+        Ok(u16::from_le_bytes(bytes))
+    }
+
+    /// Read a little-endian `u64` from the given range.
+    pub fn read_u64_le(&self, range: core::ops::Range<usize>) -> Result<u64, PsnError> {
+        let bytes = self.read_array::<8>(range)?;
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    /// Read a little-endian `f32` at the given offset.
+    pub fn read_f32_le(&self, offset: usize) -> Result<f32, PsnError> {
+        let bytes = self.read_array::<4>(offset..offset + 4)?;
+        Ok(f32::from_le_bytes(bytes))
+    }
+
+    /// Read a single byte at the given offset.
+    pub fn read_u8(&self, offset: usize) -> Result<u8, PsnError> {
+        self.payload.get(offset).copied().ok_or(PsnError::TooShort {
+            needed: offset + 1,
+            actual: self.payload.len(),
+        })
+    }
+
+    /// Read a byte slice from the given range.
+    pub fn read_slice(&self, range: core::ops::Range<usize>) -> Result<&'a [u8], PsnError> {
+        self.payload.get(range.clone()).ok_or(PsnError::TooShort {
+            needed: range.end,
+            actual: self.payload.len(),
+        })
+    }
+
+    /// Read the header of the chunk at `offset`.
+    pub fn read_chunk(&self, offset: usize) -> Result<PsnChunk, PsnError> {
+        let id = self.read_u16_le(
+            offset + layout::CHUNK_ID_RANGE.start..offset + layout::CHUNK_ID_RANGE.end,
+        )?;
+        let length = self.read_u16_le(
+            offset + layout::CHUNK_LENGTH_RANGE.start..offset + layout::CHUNK_LENGTH_RANGE.end,
+        )?;
+        let start = offset + layout::CHUNK_HEADER_LEN;
+        Ok(PsnChunk {
+            id,
+            has_subchunks: length & layout::CHUNK_HAS_SUBCHUNKS != 0,
+            offset,
+            data: start..start + usize::from(length & layout::CHUNK_LENGTH_MASK),
+        })
+    }
+
+    /// Read the chunks filling `range`, in order.
+    pub fn read_chunks(&self, range: core::ops::Range<usize>) -> Result<Vec<PsnChunk>, PsnError> {
+        let mut chunks = Vec::new();
+        let mut offset = range.start;
+        while offset < range.end {
+            if range.end - offset < layout::CHUNK_HEADER_LEN {
+                return Err(PsnError::TooShort {
+                    needed: offset + layout::CHUNK_HEADER_LEN,
+                    actual: range.end,
+                });
+            }
+            let chunk = self.read_chunk(offset)?;
+            if chunk.data.end > range.end {
+                return Err(PsnError::ChunkOverflow {
+                    id: chunk.id,
+                    offset,
+                });
+            }
+            offset = chunk.data.end;
+            chunks.push(chunk);
+        }
+        Ok(chunks)
+    }
+
+    /// Read three little-endian `f32` filling a chunk.
+    pub fn read_vector(&self, chunk: &PsnChunk) -> Result<[f32; 3], PsnError> {
+        self.require_chunk_len(chunk, layout::VECTOR_LEN)?;
+        let start = chunk.data.start;
+        Ok([
+            self.read_f32_le(start)?,
+            self.read_f32_le(start + 4)?,
+            self.read_f32_le(start + 8)?,
+        ])
+    }
+
+    /// Read a UTF-8 name filling a chunk (not null-terminated); invalid
+    /// sequences are replaced.
+    pub fn read_name(&self, chunk: &PsnChunk) -> Result<String, PsnError> {
+        let bytes = self.read_slice(chunk.data.clone())?;
+        Ok(String::from_utf8_lossy(bytes).into_owned())
+    }
+
+    /// Ensure a chunk holds exactly `length` bytes of data.
+    pub fn require_chunk_len(&self, chunk: &PsnChunk, length: usize) -> Result<(), PsnError> {
+        if chunk.data.len() != length {
+            return Err(PsnError::InvalidChunkLength {
+                id: chunk.id,
+                offset: chunk.offset,
+                length: chunk.data.len(),
+            });
+        }
+        Ok(())
+    }
+
+    fn read_array<const N: usize>(
+        &self,
+        range: core::ops::Range<usize>,
+    ) -> Result<[u8; N], PsnError> {
+        let bytes = self.read_slice(range)?;
+        bytes.try_into().map_err(|_| PsnError::TooShort {
+            needed: N,
+            actual: bytes.len(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PsnReader;
+    use crate::psn::error::PsnError;
+
+    #[test]
+    fn read_chunks_splits_siblings() {
+        let payload = [0x01, 0x00, 0x01, 0x80, 0xaa, 0x02, 0x00, 0x00, 0x00];
+        let reader = PsnReader::new(&payload);
+        let chunks = reader.read_chunks(0..payload.len()).unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].id, 1);
+        assert!(chunks[0].has_subchunks);
+        assert_eq!(chunks[0].data, 4..5);
+        assert_eq!(chunks[1].id, 2);
+        assert!(chunks[1].data.is_empty());
+    }
+
+    #[test]
+    fn read_chunks_rejects_overflow_and_trailing_bytes() {
+        let reader = PsnReader::new(&[0x01, 0x00, 0x08, 0x00, 0x00]);
+        let err = reader.read_chunks(0..5).unwrap_err();
+        assert!(matches!(err, PsnError::ChunkOverflow { id: 1, offset: 0 }));
+
+        let reader = PsnReader::new(&[0x01, 0x00, 0x00, 0x00, 0x00, 0x00]);
+        let err = reader.read_chunks(0..6).unwrap_err();
+        assert!(matches!(err, PsnError::TooShort { .. }));
+    }
+
+    #[test]
+    fn read_vector_requires_twelve_bytes() {
+        let mut payload = vec![0x00, 0x00, 0x08, 0x00];
+        payload.extend_from_slice(&[0u8; 8]);
+        let reader = PsnReader::new(&payload);
+        let chunk = reader.read_chunk(0).unwrap();
+        let err = reader.read_vector(&chunk).unwrap_err();
+        assert!(matches!(
+            err,
+            PsnError::InvalidChunkLength { length: 8, .. }
+        ));
+    }
+}
@@ -0,0 +1,49 @@
+use core::fmt;
+
+/// Errors returned by PTP parsing and reading.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::PtpError;
+///
+/// let err = PtpError::UnsupportedVersion { version: 1 };
+/// assert!(err.to_string().contains("version 1"));
+/// assert_eq!(err.offset(), 1);
+/// ```
+#[derive(Debug)]
+pub enum PtpError {
+    TooShort { needed: usize, actual: usize },
+    UnsupportedVersion { version: u8 },
+    InvalidLength { length: usize, actual: usize },
+}
+
+impl PtpError {
+    /// Payload offset of the field the error is about; 0 for truncated
+    /// payloads.
+    pub fn offset(&self) -> usize {
+        match self {
+            Self::TooShort { .. } => 0,
+            Self::UnsupportedVersion { .. } => super::layout::VERSION_OFFSET,
+            Self::InvalidLength { .. } => super::layout::MESSAGE_LENGTH_RANGE.start,
+        }
+    }
+}
+
+impl fmt::Display for PtpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooShort { needed, actual } => {
+                write!(f, "payload too short: need {needed} bytes, got {actual}")
+            }
+            Self::UnsupportedVersion { version } => {
+                write!(f, "unsupported PTP version {version} (expected 2)")
+            }
+            Self::InvalidLength { length, actual } => write!(
+                f,
+                "message length {length} does not fit the {actual}-byte payload"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for PtpError {}
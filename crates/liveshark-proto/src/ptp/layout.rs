@@ -0,0 +1,46 @@
+/// UDP port of event messages (Sync, Delay_Req, Pdelay_Req, Pdelay_Resp).
+pub const EVENT_PORT: u16 = 319;
+/// UDP port of general messages (Announce, Follow_Up, Delay_Resp, ...).
+pub const GENERAL_PORT: u16 = 320;
+
+/// Common header, big-endian.
+pub const HEADER_LEN: usize = 34;
+/// Transport-specific nibble (high) and message type (low).
+pub const MESSAGE_TYPE_OFFSET: usize = 0;
+pub const MESSAGE_TYPE_MASK: u8 = 0x0f;
+/// PTP version in the low nibble.
+pub const VERSION_OFFSET: usize = 1;
+pub const VERSION_MASK: u8 = 0x0f;
+pub const VERSION: u8 = 2;
+pub const MESSAGE_LENGTH_RANGE: core::ops::Range<usize> = 2..4;
+pub const DOMAIN_OFFSET: usize = 4;
+pub const FLAGS_RANGE: core::ops::Range<usize> = 6..8;
+pub const SOURCE_CLOCK_IDENTITY_RANGE: core::ops::Range<usize> = 20..28;
+pub const SOURCE_PORT_NUMBER_RANGE: core::ops::Range<usize> = 28..30;
+pub const SEQUENCE_ID_RANGE: core::ops::Range<usize> = 30..32;
+pub const CONTROL_OFFSET: usize = 32;
+/// Logarithm (base 2) of the sender's interval for this message type.
+pub const LOG_MESSAGE_INTERVAL_OFFSET: usize = 33;
+
+pub const MESSAGE_SYNC: u8 = 0x0;
+pub const MESSAGE_DELAY_REQ: u8 = 0x1;
+pub const MESSAGE_PDELAY_REQ: u8 = 0x2;
+pub const MESSAGE_PDELAY_RESP: u8 = 0x3;
+pub const MESSAGE_FOLLOW_UP: u8 = 0x8;
+pub const MESSAGE_DELAY_RESP: u8 = 0x9;
+pub const MESSAGE_PDELAY_RESP_FOLLOW_UP: u8 = 0xa;
+pub const MESSAGE_ANNOUNCE: u8 = 0xb;
+pub const MESSAGE_SIGNALING: u8 = 0xc;
+pub const MESSAGE_MANAGEMENT: u8 = 0xd;
+
+/// Announce body, after the header and the 10-byte origin timestamp.
+pub const ANNOUNCE_LEN: usize = 64;
+pub const ANNOUNCE_UTC_OFFSET_RANGE: core::ops::Range<usize> = 44..46;
+pub const ANNOUNCE_PRIORITY1_OFFSET: usize = 47;
+pub const ANNOUNCE_CLOCK_CLASS_OFFSET: usize = 48;
+pub const ANNOUNCE_CLOCK_ACCURACY_OFFSET: usize = 49;
+pub const ANNOUNCE_VARIANCE_RANGE: core::ops::Range<usize> = 50..52;
+pub const ANNOUNCE_PRIORITY2_OFFSET: usize = 52;
+pub const ANNOUNCE_GRANDMASTER_RANGE: core::ops::Range<usize> = 53..61;
+pub const ANNOUNCE_STEPS_REMOVED_RANGE: core::ops::Range<usize> = 61..63;
+pub const ANNOUNCE_TIME_SOURCE_OFFSET: usize = 63;
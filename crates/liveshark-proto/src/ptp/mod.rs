@@ -0,0 +1,24 @@
+//! Precision Time Protocol (IEEE 1588-2008, PTPv2) decoding.
+//!
+//! PTP messages travel over UDP ports 319 (event messages: Sync, delay
+//! requests) and 320 (general messages: Announce, Follow_Up, ...). Every
+//! message opens with a 34-byte big-endian header naming its type, domain,
+//! sending port, and logged message interval; Announce messages add the
+//! grandmaster the sender follows and the clock quality used by the best
+//! master clock algorithm. Other message bodies are not decoded.
+//!
+//! Errors report truncated messages, a message length beyond the datagram,
+//! and PTP versions other than 2.
+//!
+//! Decoding (`reader` and `parse_ptp`) is compiled with the `ptp` feature;
+//! message types and `layout` are always available.
+//!
+pub mod error;
+pub mod layout;
+pub mod parser;
+#[cfg(feature = "ptp")]
+pub mod reader;
+
+#[cfg(feature = "ptp")]
+pub use parser::parse_ptp;
+pub use parser::{PtpAnnounce, PtpClockIdentity, PtpHeader, PtpMessage};
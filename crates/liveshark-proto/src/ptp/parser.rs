@@ -0,0 +1,305 @@
+use core::fmt;
+
+#[cfg(feature = "ptp")]
+use super::error::PtpError;
+#[cfg(feature = "ptp")]
+use super::layout;
+#[cfg(feature = "ptp")]
+use super::reader::PtpReader;
+
+/// EUI-64 identity of a PTP clock.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::PtpClockIdentity;
+///
+/// let identity = PtpClockIdentity([0x00, 0x1b, 0x21, 0xff, 0xfe, 0x12, 0x34, 0x56]);
+/// assert_eq!(identity.to_string(), "00:1b:21:ff:fe:12:34:56");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PtpClockIdentity(pub [u8; 8]);
+
+impl fmt::Display for PtpClockIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, byte) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(":")?;
+            }
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Header shared by every PTPv2 message.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::{PtpClockIdentity, PtpHeader};
+///
+/// let header = PtpHeader {
+///     message_type: 0xb,
+///     message_length: 64,
+///     domain: 0,
+///     flags: 0,
+///     source_clock: PtpClockIdentity([1; 8]),
+///     source_port: 1,
+///     sequence_id: 7,
+///     log_message_interval: 1,
+/// };
+/// assert!(header.is_announce());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PtpHeader {
+    /// Message type (0 Sync, 8 Follow_Up, 11 Announce, ...).
+    pub message_type: u8,
+    /// Length of the message, header included.
+    pub message_length: u16,
+    /// PTP domain the message belongs to.
+    pub domain: u8,
+    /// Flag field (two-step, unicast, leap seconds, ...).
+    pub flags: u16,
+    /// Clock of the sending port.
+    pub source_clock: PtpClockIdentity,
+    /// Number of the sending port on its clock.
+    pub source_port: u16,
+    /// Sequence number, per message type and sender.
+    pub sequence_id: u16,
+    /// Sender's interval for this message type, as log2 seconds.
+    pub log_message_interval: i8,
+}
+
+impl PtpHeader {
+    /// Whether the message is an Announce.
+    pub fn is_announce(&self) -> bool {
+        self.message_type == super::layout::MESSAGE_ANNOUNCE
+    }
+}
+
+/// Grandmaster advertised by an Announce message.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::{PtpAnnounce, PtpClockIdentity};
+///
+/// let announce = PtpAnnounce {
+///     grandmaster: PtpClockIdentity([2; 8]),
+///     priority1: 128,
+///     clock_class: 6,
+///     clock_accuracy: 0x21,
+///     offset_scaled_log_variance: 0x4e5d,
+///     priority2: 128,
+///     steps_removed: 0,
+///     time_source: 0x20,
+///     current_utc_offset: 37,
+/// };
+/// assert_eq!(announce.clock_class, 6);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PtpAnnounce {
+    /// Identity of the grandmaster the sender is synchronized to.
+    pub grandmaster: PtpClockIdentity,
+    /// First priority of the best master clock algorithm; lower wins.
+    pub priority1: u8,
+    /// Clock class (6 for a GNSS-locked primary reference, 248 default).
+    pub clock_class: u8,
+    /// Clock accuracy enumeration.
+    pub clock_accuracy: u8,
+    /// Scaled log variance of the clock.
+    pub offset_scaled_log_variance: u16,
+    /// Second priority, compared after clock quality; lower wins.
+    pub priority2: u8,
+    /// Hops between the sender and the grandmaster.
+    pub steps_removed: u16,
+    /// Time source enumeration (GNSS, NTP, internal oscillator, ...).
+    pub time_source: u8,
+    /// Offset between TAI and UTC, in seconds.
+    pub current_utc_offset: i16,
+}
+
+/// Decoded PTPv2 message.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::{PtpClockIdentity, PtpHeader, PtpMessage};
+///
+/// let message = PtpMessage {
+///     header: PtpHeader {
+///         message_type: 0,
+///         message_length: 44,
+///         domain: 0,
+///         flags: 0x0200,
+///         source_clock: PtpClockIdentity([1; 8]),
+///         source_port: 1,
+///         sequence_id: 1,
+///         log_message_interval: -3,
+///     },
+///     announce: None,
+/// };
+/// assert!(message.announce.is_none());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PtpMessage {
+    pub header: PtpHeader,
+    /// Announce body; `None` for every other message type.
+    pub announce: Option<PtpAnnounce>,
+}
+
+/// Parse a PTPv2 message from a UDP payload on port 319 or 320.
+///
+/// PTP has no magic number; callers select it by port.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::parse_ptp;
+///
+/// let mut payload = vec![0u8; 64];
+/// payload[0] = 0x0b;
+/// payload[1] = 0x02;
+/// payload[2..4].copy_from_slice(&64u16.to_be_bytes());
+/// payload[47] = 128;
+/// payload[48] = 6;
+/// payload[53..61].copy_from_slice(&[0xaa; 8]);
+///
+/// let message = parse_ptp(&payload)?;
+/// let announce = message.announce.expect("announce");
+/// assert_eq!(announce.grandmaster.to_string(), "aa:aa:aa:aa:aa:aa:aa:aa");
+/// assert_eq!(announce.clock_class, 6);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// Returns `PtpError` when the message is truncated, is not PTP version 2,
+/// or declares a length outside the payload.
+#[cfg(feature = "ptp")]
+pub fn parse_ptp(payload: &[u8]) -> Result<PtpMessage, PtpError> {
+    let reader = PtpReader::new(payload);
+    if payload.len() < layout::HEADER_LEN {
+        return Err(PtpError::TooShort {
+            needed: layout::HEADER_LEN,
+            actual: payload.len(),
+        });
+    }
+    let version = reader.read_u8(layout::VERSION_OFFSET)? & layout::VERSION_MASK;
+    if version != layout::VERSION {
+        return Err(PtpError::UnsupportedVersion { version });
+    }
+    let message_length = reader.read_u16_be(layout::MESSAGE_LENGTH_RANGE)?;
+    if usize::from(message_length) < layout::HEADER_LEN
+        || usize::from(message_length) > payload.len()
+    {
+        return Err(PtpError::InvalidLength {
+            length: usize::from(message_length),
+            actual: payload.len(),
+        });
+    }
+    // Padding after the declared length (short Ethernet frames) is ignored.
+    let reader = PtpReader::new(&payload[..usize::from(message_length)]);
+    let header = PtpHeader {
+        message_type: reader.read_u8(layout::MESSAGE_TYPE_OFFSET)? & layout::MESSAGE_TYPE_MASK,
+        message_length,
+        domain: reader.read_u8(layout::DOMAIN_OFFSET)?,
+        flags: reader.read_u16_be(layout::FLAGS_RANGE)?,
+        source_clock: PtpClockIdentity(reader.read_array(layout::SOURCE_CLOCK_IDENTITY_RANGE)?),
+        source_port: reader.read_u16_be(layout::SOURCE_PORT_NUMBER_RANGE)?,
+        sequence_id: reader.read_u16_be(layout::SEQUENCE_ID_RANGE)?,
+        log_message_interval: reader.read_u8(layout::LOG_MESSAGE_INTERVAL_OFFSET)? as i8,
+    };
+    let announce = if header.message_type == layout::MESSAGE_ANNOUNCE {
+        Some(read_announce(&reader)?)
+    } else {
+        None
+    };
+    Ok(PtpMessage { header, announce })
+}
+
+#[cfg(feature = "ptp")]
+fn read_announce(reader: &PtpReader<'_>) -> Result<PtpAnnounce, PtpError> {
+    Ok(PtpAnnounce {
+        grandmaster: PtpClockIdentity(reader.read_array(layout::ANNOUNCE_GRANDMASTER_RANGE)?),
+        priority1: reader.read_u8(layout::ANNOUNCE_PRIORITY1_OFFSET)?,
+        clock_class: reader.read_u8(layout::ANNOUNCE_CLOCK_CLASS_OFFSET)?,
+        clock_accuracy: reader.read_u8(layout::ANNOUNCE_CLOCK_ACCURACY_OFFSET)?,
+        offset_scaled_log_variance: reader.read_u16_be(layout::ANNOUNCE_VARIANCE_RANGE)?,
+        priority2: reader.read_u8(layout::ANNOUNCE_PRIORITY2_OFFSET)?,
+        steps_removed: reader.read_u16_be(layout::ANNOUNCE_STEPS_REMOVED_RANGE)?,
+        time_source: reader.read_u8(layout::ANNOUNCE_TIME_SOURCE_OFFSET)?,
+        current_utc_offset: reader.read_u16_be(layout::ANNOUNCE_UTC_OFFSET_RANGE)? as i16,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_ptp;
+    use crate::ptp::error::PtpError;
+
+    fn message(message_type: u8, length: usize) -> Vec<u8> {
+        let mut out = vec![0u8; length];
+        out[0] = message_type;
+        out[1] = 0x02;
+        out[2..4].copy_from_slice(&(length as u16).to_be_bytes());
+        out[4] = 3;
+        out[20..28].copy_from_slice(&[0x11; 8]);
+        out[28..30].copy_from_slice(&1u16.to_be_bytes());
+        out[30..32].copy_from_slice(&42u16.to_be_bytes());
+        out[33] = 0xfd;
+        out
+    }
+
+    #[test]
+    fn parse_reads_header_of_sync_and_ignores_padding() {
+        let mut payload = message(0x10, 44);
+        payload.extend_from_slice(&[0; 16]);
+        let parsed = parse_ptp(&payload).unwrap();
+        assert_eq!(parsed.header.message_type, 0);
+        assert_eq!(parsed.header.domain, 3);
+        assert_eq!(parsed.header.sequence_id, 42);
+        assert_eq!(parsed.header.log_message_interval, -3);
+        assert_eq!(parsed.header.source_clock.0, [0x11; 8]);
+        assert!(parsed.announce.is_none());
+    }
+
+    #[test]
+    fn parse_reads_announce_body() {
+        let mut payload = message(0x0b, 64);
+        payload[44..46].copy_from_slice(&37i16.to_be_bytes());
+        payload[47] = 100;
+        payload[48] = 6;
+        payload[52] = 120;
+        payload[53..61].copy_from_slice(&[0x22; 8]);
+        payload[61..63].copy_from_slice(&1u16.to_be_bytes());
+        let announce = parse_ptp(&payload).unwrap().announce.expect("announce");
+        assert_eq!((announce.priority1, announce.priority2), (100, 120));
+        assert_eq!(announce.clock_class, 6);
+        assert_eq!(announce.grandmaster.0, [0x22; 8]);
+        assert_eq!(announce.steps_removed, 1);
+        assert_eq!(announce.current_utc_offset, 37);
+    }
+
+    #[test]
+    fn parse_rejects_other_versions_and_bad_lengths() {
+        let mut v1 = message(0, 44);
+        v1[1] = 0x01;
+        assert!(matches!(
+            parse_ptp(&v1),
+            Err(PtpError::UnsupportedVersion { version: 1 })
+        ));
+        let mut long = message(0, 44);
+        long[2..4].copy_from_slice(&80u16.to_be_bytes());
+        assert!(matches!(
+            parse_ptp(&long),
+            Err(PtpError::InvalidLength {
+                length: 80,
+                actual: 44
+            })
+        ));
+        assert!(matches!(
+            parse_ptp(&message(0x0b, 44)),
+            Err(PtpError::TooShort { .. })
+        ));
+        assert!(matches!(
+            parse_ptp(&[0; 10]),
+            Err(PtpError::TooShort { needed: 34, .. })
+        ));
+    }
+}
@@ -0,0 +1,68 @@
+use super::error::PtpError;
+
+/// Safe byte reader for PTP payloads.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::ptp::reader::PtpReader;
+///
+/// let reader = PtpReader::new(&[0x0b, 0x02, 0x00, 0x40]);
+/// assert_eq!(reader.read_u8(0).unwrap(), 0x0b);
+/// assert_eq!(reader.read_u16_be(2..4).unwrap(), 64);
+/// ```
+pub struct PtpReader<'a> {
+    payload: &'a [u8],
+}
+
+impl<'a> PtpReader<'a> {
+    pub fn new(payload: &'a [u8]) -> Self {
+        Self { payload }
+    }
+
+    /// Read a single byte at the given offset.
+    pub fn read_u8(&self, offset: usize) -> Result<u8, PtpError> {
+        self.payload.get(offset).copied().ok_or(PtpError::TooShort {
+            needed: offset + 1,
+            actual: self.payload.len(),
+        })
+    }
+
+    /// Read a big-endian `u16` from the given range.
+    pub fn read_u16_be(&self, range: core::ops::Range<usize>) -> Result<u16, PtpError> {
+        Ok(u16::from_be_bytes(self.read_array::<2>(range)?))
+    }
+
+    /// Read a fixed-size byte array from the given range.
+    pub fn read_array<const N: usize>(
+        &self,
+        range: core::ops::Range<usize>,
+    ) -> Result<[u8; N], PtpError> {
+        let bytes = self.payload.get(range.clone()).ok_or(PtpError::TooShort {
+            needed: range.end,
+            actual: self.payload.len(),
+        })?;
+        bytes.try_into().map_err(|_| PtpError::TooShort {
+            needed: N,
+            actual: bytes.len(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PtpReader;
+    use crate::ptp::error::PtpError;
+
+    #[test]
+    fn reads_past_the_end_are_rejected() {
+        let reader = PtpReader::new(&[0, 1]);
+        assert_eq!(reader.read_u16_be(0..2).unwrap(), 1);
+        assert!(matches!(
+            reader.read_u8(2),
+            Err(PtpError::TooShort {
+                needed: 3,
+                actual: 2
+            })
+        ));
+    }
+}
@@ -0,0 +1,64 @@
+use core::fmt;
+
+use super::layout;
+
+/// Errors returned by sACN parsing and reading.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::SacnError;
+///
+/// let err = SacnError::InvalidStartCode { value: 1 };
+/// assert!(err.to_string().contains("invalid start code"));
+/// assert_eq!(err.offset(), 125);
+/// ```
+#[derive(Debug)]
+pub enum SacnError {
+    TooShort { needed: usize, actual: usize },
+    InvalidStartCode { value: u8 },
+    InvalidPropertyValueCount { count: u16 },
+    InvalidDmxLength { length: u16 },
+    InvalidAcnPid,
+    InvalidRootVector { value: u32 },
+    InvalidFramingVector { value: u32 },
+    InvalidDmpVector { value: u8 },
+}
+
+impl SacnError {
+    /// Payload offset of the field the error is about; 0 for truncated
+    /// payloads.
+    pub fn offset(&self) -> usize {
+        match self {
+            Self::TooShort { .. } => 0,
+            Self::InvalidStartCode { .. } => layout::START_CODE_OFFSET,
+            Self::InvalidPropertyValueCount { .. } | Self::InvalidDmxLength { .. } => {
+                layout::DMP_PROPERTY_VALUE_COUNT_RANGE.start
+            }
+            Self::InvalidAcnPid => layout::ACN_PID_RANGE.start,
+            Self::InvalidRootVector { .. } => layout::ROOT_VECTOR_RANGE.start,
+            Self::InvalidFramingVector { .. } => layout::FRAMING_VECTOR_RANGE.start,
+            Self::InvalidDmpVector { .. } => layout::DMP_VECTOR_OFFSET,
+        }
+    }
+}
+
+impl fmt::Display for SacnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooShort { needed, actual } => {
+                write!(f, "payload too short: need {needed} bytes, got {actual}")
+            }
+            Self::InvalidStartCode { value } => write!(f, "invalid start code: {value}"),
+            Self::InvalidPropertyValueCount { count } => {
+                write!(f, "invalid property value count: {count}")
+            }
+            Self::InvalidDmxLength { length } => write!(f, "invalid DMX data length: {length}"),
+            Self::InvalidAcnPid => f.write_str("invalid ACN PID"),
+            Self::InvalidRootVector { value } => write!(f, "invalid root vector: {value}"),
+            Self::InvalidFramingVector { value } => write!(f, "invalid framing vector: {value}"),
+            Self::InvalidDmpVector { value } => write!(f, "invalid DMP vector: {value}"),
+        }
+    }
+}
+
+impl core::error::Error for SacnError {}
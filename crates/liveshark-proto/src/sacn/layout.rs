@@ -0,0 +1,63 @@
+pub const PREAMBLE_SIZE_RANGE: core::ops::Range<usize> = 0..2;
+pub const POSTAMBLE_SIZE_RANGE: core::ops::Range<usize> = 2..4;
+pub const ACN_PID_RANGE: core::ops::Range<usize> = 4..16;
+
+pub const ROOT_VECTOR_RANGE: core::ops::Range<usize> = 18..22;
+pub const CID_RANGE: core::ops::Range<usize> = 22..38;
+
+pub const FRAMING_VECTOR_RANGE: core::ops::Range<usize> = 40..44;
+pub const SOURCE_NAME_RANGE: core::ops::Range<usize> = 44..108;
+pub const PRIORITY_OFFSET: usize = 108;
+pub const SYNC_ADDRESS_RANGE: core::ops::Range<usize> = 109..111;
+pub const SEQUENCE_OFFSET: usize = 111;
+pub const OPTIONS_OFFSET: usize = 112;
+pub const UNIVERSE_RANGE: core::ops::Range<usize> = 113..115;
+
+pub const DMP_VECTOR_OFFSET: usize = 117;
+pub const DMP_ADDRESS_TYPE_OFFSET: usize = 118;
+pub const DMP_FIRST_PROPERTY_ADDRESS_RANGE: core::ops::Range<usize> = 119..121;
+pub const DMP_ADDRESS_INCREMENT_RANGE: core::ops::Range<usize> = 121..123;
+pub const DMP_PROPERTY_VALUE_COUNT_RANGE: core::ops::Range<usize> = 123..125;
+pub const START_CODE_OFFSET: usize = 125;
+/// Start code of per-address priority data (one priority per slot).
+pub const START_CODE_PER_ADDRESS_PRIORITY: u8 = 0xdd;
+pub const DMX_DATA_OFFSET: usize = 126;
+pub const DMX_MAX_SLOTS: usize = 512;
+/// Highest valid data priority; 201..=255 are reserved.
+pub const MAX_PRIORITY: u8 = 200;
+
+pub const ACN_PID: &[u8; 12] = b"ASC-E1.17\0\0\0";
+pub const PREAMBLE_SIZE: u16 = 0x0010;
+pub const POSTAMBLE_SIZE: u16 = 0x0000;
+pub const ROOT_VECTOR_DATA: u32 = 0x0000_0004;
+pub const FRAMING_VECTOR_DMX: u32 = 0x0000_0002;
+pub const DMP_VECTOR_SET_PROPERTY: u8 = 0x02;
+
+pub const OPTION_PREVIEW_DATA: u8 = 0x80;
+pub const OPTION_STREAM_TERMINATED: u8 = 0x40;
+pub const OPTION_FORCE_SYNCHRONIZATION: u8 = 0x20;
+
+pub const MIN_LEN: usize = DMP_VECTOR_OFFSET + 1;
+
+/// Root vector of extended packets (synchronization and discovery).
+pub const ROOT_VECTOR_EXTENDED: u32 = 0x0000_0008;
+/// Framing vector of synchronization packets.
+pub const FRAMING_VECTOR_SYNC: u32 = 0x0000_0001;
+pub const SYNC_SEQUENCE_OFFSET: usize = 44;
+pub const SYNC_UNIVERSE_RANGE: core::ops::Range<usize> = 45..47;
+/// Length of a synchronization packet (two reserved bytes end it).
+pub const SYNC_LEN: usize = 49;
+
+/// Framing vector of universe discovery packets.
+pub const FRAMING_VECTOR_DISCOVERY: u32 = 0x0000_0002;
+pub const DISCOVERY_SOURCE_NAME_RANGE: core::ops::Range<usize> = 44..108;
+/// Flags and length of the universe discovery layer.
+pub const DISCOVERY_LAYER_FLAGS_LENGTH_RANGE: core::ops::Range<usize> = 112..114;
+pub const DISCOVERY_LAYER_VECTOR_RANGE: core::ops::Range<usize> = 114..118;
+/// Universe discovery layer vector: universe list.
+pub const DISCOVERY_VECTOR_UNIVERSE_LIST: u32 = 0x0000_0001;
+pub const DISCOVERY_PAGE_OFFSET: usize = 118;
+pub const DISCOVERY_LAST_PAGE_OFFSET: usize = 119;
+pub const DISCOVERY_UNIVERSES_OFFSET: usize = 120;
+/// Universes listed per discovery page at most.
+pub const DISCOVERY_MAX_UNIVERSES: usize = 512;
@@ -8,9 +8,17 @@
 //! details are defined in `layout`, while conventions and safe reads live in
 //! `reader`.
 //!
+//! Decoding (`reader` and the `parse_*` functions) is compiled with the
+//! `sacn` feature; frame types and `layout` are always available.
+//!
 pub mod error;
 pub mod layout;
 pub mod parser;
+#[cfg(feature = "sacn")]
 pub mod reader;
 
-pub use parser::parse_sacn_dmx;
+pub use parser::{SacnDiscovery, SacnDmx, SacnSync, SourceNameIssue};
+#[cfg(feature = "sacn")]
+pub use parser::{
+    parse_sacn_address_priority, parse_sacn_discovery, parse_sacn_dmx, parse_sacn_sync,
+};
@@ -0,0 +1,766 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "sacn")]
+use super::error::SacnError;
+use super::layout;
+#[cfg(feature = "sacn")]
+use super::reader::SacnReader;
+
+/// Parsed sACN (E1.31) data packet: header fields and raw slot data.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::SacnDmx;
+///
+/// let frame = SacnDmx {
+///     cid: "00112233445566778899aabbccddeeff".to_string(),
+///     source_name: Some("console".to_string()),
+///     source_name_issue: None,
+///     priority: 100,
+///     sync_address: None,
+///     sequence: Some(1),
+///     options: 0,
+///     universe: 1,
+///     address_type: 0xa1,
+///     first_property_address: 0,
+///     address_increment: 1,
+///     start_code: 0,
+///     slots: vec![1, 2, 3],
+/// };
+/// assert!(!frame.stream_terminated());
+/// assert_eq!(frame.slots.len(), 3);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SacnDmx {
+    /// Component identifier (root layer CID) as lowercase hex.
+    pub cid: String,
+    /// User-assigned source name; `None` when empty or not valid UTF-8.
+    pub source_name: Option<String>,
+    /// Why the source name field is malformed, if it is.
+    pub source_name_issue: Option<SourceNameIssue>,
+    /// Data priority (0..=200, default 100).
+    pub priority: u8,
+    /// Synchronization universe; `None` when the packet is not synchronized.
+    pub sync_address: Option<u16>,
+    /// Sequence number.
+    pub sequence: Option<u8>,
+    /// Raw framing options byte (see the flag accessors).
+    pub options: u8,
+    /// Universe number.
+    pub universe: u16,
+    /// DMP address type and data type (0xa1 for E1.31 data).
+    pub address_type: u8,
+    /// DMP first property address (0 for E1.31 data).
+    pub first_property_address: u16,
+    /// DMP address increment (1 for E1.31 data).
+    pub address_increment: u16,
+    /// DMX start code: 0 from `parse_sacn_dmx`, 0xdd from
+    /// `parse_sacn_address_priority`.
+    pub start_code: u8,
+    /// DMX slot values, or per-address priorities for start code 0xdd
+    /// (start code excluded), 0..=512 bytes.
+    pub slots: Vec<u8>,
+}
+
+/// Parsed sACN (E1.31) synchronization packet.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::SacnSync;
+///
+/// let sync = SacnSync {
+///     cid: "00112233445566778899aabbccddeeff".to_string(),
+///     sequence: 7,
+///     sync_address: 7000,
+/// };
+/// assert_eq!(sync.sync_address, 7000);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SacnSync {
+    /// Component identifier (root layer CID) as lowercase hex.
+    pub cid: String,
+    /// Sequence number.
+    pub sequence: u8,
+    /// Synchronization universe the packet releases.
+    pub sync_address: u16,
+}
+
+/// Parsed sACN (E1.31) universe discovery packet: one page of the
+/// universes a source transmits.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::SacnDiscovery;
+///
+/// let page = SacnDiscovery {
+///     cid: "00112233445566778899aabbccddeeff".to_string(),
+///     source_name: Some("console".to_string()),
+///     page: 0,
+///     last_page: 0,
+///     universes: vec![1, 2, 3],
+/// };
+/// assert_eq!(page.universes.len(), 3);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SacnDiscovery {
+    /// Component identifier (root layer CID) as lowercase hex.
+    pub cid: String,
+    /// User-assigned source name; `None` when empty or malformed.
+    pub source_name: Option<String>,
+    /// Page number of this packet, from 0.
+    pub page: u8,
+    /// Number of the source's last page.
+    pub last_page: u8,
+    /// Universes listed on this page, in packet order (sorted by senders).
+    pub universes: Vec<u16>,
+}
+
+/// Defect in the E1.31 source name field (64 bytes, null-terminated UTF-8).
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::SourceNameIssue;
+///
+/// assert_eq!(SourceNameIssue::NotTerminated.as_str(), "not-terminated");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceNameIssue {
+    /// Bytes before the terminator are not valid UTF-8.
+    InvalidUtf8,
+    /// No null terminator within the field.
+    NotTerminated,
+    /// Name is empty or whitespace only.
+    Empty,
+}
+
+impl SourceNameIssue {
+    /// Label used in violation examples.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SourceNameIssue::InvalidUtf8 => "invalid-utf8",
+            SourceNameIssue::NotTerminated => "not-terminated",
+            SourceNameIssue::Empty => "empty",
+        }
+    }
+}
+
+impl SacnDmx {
+    /// Preview data flag: the data is not intended for live output.
+    pub fn preview_data(&self) -> bool {
+        self.options & layout::OPTION_PREVIEW_DATA != 0
+    }
+
+    /// Stream terminated flag: the source is ceasing transmission.
+    pub fn stream_terminated(&self) -> bool {
+        self.options & layout::OPTION_STREAM_TERMINATED != 0
+    }
+
+    /// Force synchronization flag.
+    pub fn force_synchronization(&self) -> bool {
+        self.options & layout::OPTION_FORCE_SYNCHRONIZATION != 0
+    }
+}
+
+/// Parse an sACN DMX payload from a UDP payload.
+///
+/// Returns `Ok(None)` when the payload is not sACN. Returns `Err` for
+/// malformed sACN packets.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::parse_sacn_dmx;
+///
+/// let mut payload = vec![0u8; 128];
+/// payload[0..2].copy_from_slice(&0x0010u16.to_be_bytes()); // preamble size
+/// payload[4..16].copy_from_slice(b"ASC-E1.17\0\0\0"); // ACN packet identifier
+/// payload[18..22].copy_from_slice(&4u32.to_be_bytes()); // root vector: data
+/// payload[40..44].copy_from_slice(&2u32.to_be_bytes()); // framing vector: DMX
+/// payload[108] = 100; // priority
+/// payload[113..115].copy_from_slice(&1u16.to_be_bytes()); // universe
+/// payload[117] = 0x02; // DMP vector: set property
+/// payload[123..125].copy_from_slice(&3u16.to_be_bytes()); // start code + 2 slots
+/// payload[126..128].copy_from_slice(&[1, 2]);
+///
+/// let parsed = parse_sacn_dmx(&payload)?.expect("sacn dmx");
+/// assert_eq!(parsed.universe, 1);
+/// assert_eq!(parsed.priority, 100);
+/// assert_eq!(parsed.slots, vec![1, 2]);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// Returns `SacnError` when the payload is too short or any sACN framing,
+/// vector, or DMX length validation fails.
+#[cfg(feature = "sacn")]
+pub fn parse_sacn_dmx(payload: &[u8]) -> Result<Option<SacnDmx>, SacnError> {
+    parse_data_packet(payload, false)
+}
+
+/// Parse an sACN per-address priority packet (start code 0xdd) from a UDP
+/// payload; its slots are the priorities of the source's DMX slots, 0
+/// meaning the source does not drive the slot.
+///
+/// Returns `Ok(None)` when the payload is not an sACN data packet with
+/// start code 0xdd.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::parse_sacn_address_priority;
+///
+/// let mut payload = vec![0u8; 128];
+/// payload[0..2].copy_from_slice(&0x0010u16.to_be_bytes()); // preamble size
+/// payload[4..16].copy_from_slice(b"ASC-E1.17\0\0\0"); // ACN packet identifier
+/// payload[18..22].copy_from_slice(&4u32.to_be_bytes()); // root vector: data
+/// payload[40..44].copy_from_slice(&2u32.to_be_bytes()); // framing vector: DMX
+/// payload[113..115].copy_from_slice(&1u16.to_be_bytes()); // universe
+/// payload[117] = 0x02; // DMP vector: set property
+/// payload[123..125].copy_from_slice(&3u16.to_be_bytes()); // start code + 2 slots
+/// payload[125] = 0xdd; // start code: per-address priority
+/// payload[126..128].copy_from_slice(&[100, 0]);
+///
+/// let parsed = parse_sacn_address_priority(&payload)?.expect("priorities");
+/// assert_eq!(parsed.start_code, 0xdd);
+/// assert_eq!(parsed.slots, vec![100, 0]);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// Returns `SacnError` when a per-address priority packet fails the same
+/// validation as DMX data.
+#[cfg(feature = "sacn")]
+pub fn parse_sacn_address_priority(payload: &[u8]) -> Result<Option<SacnDmx>, SacnError> {
+    let reader = SacnReader::new(payload);
+    if reader
+        .read_u8(layout::START_CODE_OFFSET)
+        .is_ok_and(|value| value == layout::START_CODE_PER_ADDRESS_PRIORITY)
+    {
+        parse_data_packet(payload, true)
+    } else {
+        Ok(None)
+    }
+}
+
+/// Decode a data packet: DMX (start code 0) or, with
+/// `address_priority`, per-address priorities (start code 0xdd).
+#[cfg(feature = "sacn")]
+fn parse_data_packet(payload: &[u8], address_priority: bool) -> Result<Option<SacnDmx>, SacnError> {
+    let reader = SacnReader::new(payload);
+    reader.require_len(layout::MIN_LEN)?;
+
+    let preamble = reader.read_u16_be(layout::PREAMBLE_SIZE_RANGE.clone())?;
+    let postamble = reader.read_u16_be(layout::POSTAMBLE_SIZE_RANGE.clone())?;
+    if preamble != layout::PREAMBLE_SIZE || postamble != layout::POSTAMBLE_SIZE {
+        return Ok(None);
+    }
+
+    let acn_pid = reader.read_slice(layout::ACN_PID_RANGE.clone())?;
+    if acn_pid != layout::ACN_PID {
+        return Err(SacnError::InvalidAcnPid);
+    }
+
+    let root_vector = reader.read_u32_be(layout::ROOT_VECTOR_RANGE.clone())?;
+    if root_vector != layout::ROOT_VECTOR_DATA {
+        return Err(SacnError::InvalidRootVector { value: root_vector });
+    }
+
+    let framing_vector = reader.read_u32_be(layout::FRAMING_VECTOR_RANGE.clone())?;
+    if framing_vector != layout::FRAMING_VECTOR_DMX {
+        return Err(SacnError::InvalidFramingVector {
+            value: framing_vector,
+        });
+    }
+
+    let dmp_vector = reader.read_u8(layout::DMP_VECTOR_OFFSET)?;
+    if dmp_vector != layout::DMP_VECTOR_SET_PROPERTY {
+        return Err(SacnError::InvalidDmpVector { value: dmp_vector });
+    }
+
+    let start_code = if address_priority {
+        reader.read_u8(layout::START_CODE_OFFSET)?
+    } else {
+        reader.read_start_code()?
+    };
+
+    let universe = reader.read_u16_be(layout::UNIVERSE_RANGE.clone())?;
+    let cid = reader.read_cid_hex()?;
+    let (source_name, source_name_issue) =
+        reader.read_source_name(layout::SOURCE_NAME_RANGE.clone())?;
+    let priority = reader.read_u8(layout::PRIORITY_OFFSET)?;
+    let sync_address = reader.read_sync_address()?;
+    let sequence = Some(reader.read_u8(layout::SEQUENCE_OFFSET)?);
+    let options = reader.read_u8(layout::OPTIONS_OFFSET)?;
+    let address_type = reader.read_u8(layout::DMP_ADDRESS_TYPE_OFFSET)?;
+    let first_property_address =
+        reader.read_u16_be(layout::DMP_FIRST_PROPERTY_ADDRESS_RANGE.clone())?;
+    let address_increment = reader.read_u16_be(layout::DMP_ADDRESS_INCREMENT_RANGE.clone())?;
+    let data_len = reader.read_dmx_data_len()?;
+    let slots = if data_len > 0 {
+        let needed = layout::DMX_DATA_OFFSET
+            .checked_add(data_len)
+            .ok_or(SacnError::InvalidDmxLength { length: 0 })?;
+        let data = reader.read_slice(layout::DMX_DATA_OFFSET..needed)?;
+        data.to_vec()
+    } else {
+        Vec::new()
+    };
+
+    Ok(Some(SacnDmx {
+        cid,
+        source_name,
+        source_name_issue,
+        priority,
+        sync_address,
+        sequence,
+        options,
+        universe,
+        address_type,
+        first_property_address,
+        address_increment,
+        start_code,
+        slots,
+    }))
+}
+
+/// Parse an sACN synchronization packet from a UDP payload.
+///
+/// Returns `Ok(None)` when the payload is not an sACN synchronization packet
+/// (including data and discovery packets).
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::parse_sacn_sync;
+///
+/// let mut payload = vec![0u8; 49];
+/// payload[0..2].copy_from_slice(&0x0010u16.to_be_bytes()); // preamble size
+/// payload[4..16].copy_from_slice(b"ASC-E1.17\0\0\0"); // ACN packet identifier
+/// payload[18..22].copy_from_slice(&8u32.to_be_bytes()); // root vector: extended
+/// payload[40..44].copy_from_slice(&1u32.to_be_bytes()); // framing vector: sync
+/// payload[44] = 3; // sequence
+/// payload[45..47].copy_from_slice(&7000u16.to_be_bytes()); // sync address
+///
+/// let sync = parse_sacn_sync(&payload)?.expect("sacn sync");
+/// assert_eq!(sync.sync_address, 7000);
+/// assert_eq!(sync.sequence, 3);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// Returns `SacnError::TooShort` when a synchronization packet is truncated.
+#[cfg(feature = "sacn")]
+pub fn parse_sacn_sync(payload: &[u8]) -> Result<Option<SacnSync>, SacnError> {
+    let reader = SacnReader::new(payload);
+    let is_sync = reader
+        .read_u16_be(layout::PREAMBLE_SIZE_RANGE.clone())
+        .is_ok_and(|value| value == layout::PREAMBLE_SIZE)
+        && reader
+            .read_u16_be(layout::POSTAMBLE_SIZE_RANGE.clone())
+            .is_ok_and(|value| value == layout::POSTAMBLE_SIZE)
+        && reader
+            .read_slice(layout::ACN_PID_RANGE.clone())
+            .is_ok_and(|value| value == layout::ACN_PID)
+        && reader
+            .read_u32_be(layout::ROOT_VECTOR_RANGE.clone())
+            .is_ok_and(|value| value == layout::ROOT_VECTOR_EXTENDED)
+        && reader
+            .read_u32_be(layout::FRAMING_VECTOR_RANGE.clone())
+            .is_ok_and(|value| value == layout::FRAMING_VECTOR_SYNC);
+    if !is_sync {
+        return Ok(None);
+    }
+    reader.require_len(layout::SYNC_LEN)?;
+    Ok(Some(SacnSync {
+        cid: reader.read_cid_hex()?,
+        sequence: reader.read_u8(layout::SYNC_SEQUENCE_OFFSET)?,
+        sync_address: reader.read_u16_be(layout::SYNC_UNIVERSE_RANGE.clone())?,
+    }))
+}
+
+/// Parse an sACN universe discovery packet from a UDP payload.
+///
+/// Returns `Ok(None)` when the payload is not an sACN universe discovery
+/// packet (including data and synchronization packets).
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::parse_sacn_discovery;
+///
+/// let mut payload = vec![0u8; 124];
+/// payload[0..2].copy_from_slice(&0x0010u16.to_be_bytes()); // preamble size
+/// payload[4..16].copy_from_slice(b"ASC-E1.17\0\0\0"); // ACN packet identifier
+/// payload[18..22].copy_from_slice(&8u32.to_be_bytes()); // root vector: extended
+/// payload[40..44].copy_from_slice(&2u32.to_be_bytes()); // framing vector: discovery
+/// payload[112..114].copy_from_slice(&(0x7000u16 | 12).to_be_bytes()); // layer length
+/// payload[114..118].copy_from_slice(&1u32.to_be_bytes()); // universe list
+/// payload[120..124].copy_from_slice(&[0, 1, 0, 2]); // universes 1 and 2
+///
+/// let discovery = parse_sacn_discovery(&payload)?.expect("sacn discovery");
+/// assert_eq!(discovery.universes, vec![1, 2]);
+/// assert_eq!((discovery.page, discovery.last_page), (0, 0));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// Returns `SacnError::TooShort` when a discovery packet is truncated.
+#[cfg(feature = "sacn")]
+pub fn parse_sacn_discovery(payload: &[u8]) -> Result<Option<SacnDiscovery>, SacnError> {
+    let reader = SacnReader::new(payload);
+    let is_discovery = reader
+        .read_u16_be(layout::PREAMBLE_SIZE_RANGE.clone())
+        .is_ok_and(|value| value == layout::PREAMBLE_SIZE)
+        && reader
+            .read_u16_be(layout::POSTAMBLE_SIZE_RANGE.clone())
+            .is_ok_and(|value| value == layout::POSTAMBLE_SIZE)
+        && reader
+            .read_slice(layout::ACN_PID_RANGE.clone())
+            .is_ok_and(|value| value == layout::ACN_PID)
+        && reader
+            .read_u32_be(layout::ROOT_VECTOR_RANGE.clone())
+            .is_ok_and(|value| value == layout::ROOT_VECTOR_EXTENDED)
+        && reader
+            .read_u32_be(layout::FRAMING_VECTOR_RANGE.clone())
+            .is_ok_and(|value| value == layout::FRAMING_VECTOR_DISCOVERY);
+    if !is_discovery {
+        return Ok(None);
+    }
+    reader.require_len(layout::DISCOVERY_UNIVERSES_OFFSET)?;
+    let vector = reader.read_u32_be(layout::DISCOVERY_LAYER_VECTOR_RANGE.clone())?;
+    if vector != layout::DISCOVERY_VECTOR_UNIVERSE_LIST {
+        return Ok(None);
+    }
+    // The layer length covers its own header (flags and length, vector,
+    // page, last page) and two bytes per universe.
+    let layer_len = usize::from(
+        reader.read_u16_be(layout::DISCOVERY_LAYER_FLAGS_LENGTH_RANGE.clone())? & 0x0fff,
+    );
+    let header_len =
+        layout::DISCOVERY_UNIVERSES_OFFSET - layout::DISCOVERY_LAYER_FLAGS_LENGTH_RANGE.start;
+    let count = (layer_len.saturating_sub(header_len) / 2).min(layout::DISCOVERY_MAX_UNIVERSES);
+    let end = layout::DISCOVERY_UNIVERSES_OFFSET + 2 * count;
+    let universes = reader
+        .read_slice(layout::DISCOVERY_UNIVERSES_OFFSET..end)?
+        .chunks_exact(2)
+        .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+        .collect();
+    let (source_name, _) = reader.read_source_name(layout::DISCOVERY_SOURCE_NAME_RANGE.clone())?;
+    Ok(Some(SacnDiscovery {
+        cid: reader.read_cid_hex()?,
+        source_name,
+        page: reader.read_u8(layout::DISCOVERY_PAGE_OFFSET)?,
+        last_page: reader.read_u8(layout::DISCOVERY_LAST_PAGE_OFFSET)?,
+        universes,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        SourceNameIssue, parse_sacn_address_priority, parse_sacn_discovery, parse_sacn_dmx,
+        parse_sacn_sync,
+    };
+    use crate::sacn::error::SacnError;
+    use crate::sacn::layout;
+
+    #[test]
+    fn parse_valid_sacn() {
+        let count = 3u16;
+        let mut payload = vec![0u8; layout::DMX_DATA_OFFSET + (count - 1) as usize];
+        payload[layout::PREAMBLE_SIZE_RANGE.clone()]
+            .copy_from_slice(&layout::PREAMBLE_SIZE.to_be_bytes());
+        payload[layout::POSTAMBLE_SIZE_RANGE.clone()]
+            .copy_from_slice(&layout::POSTAMBLE_SIZE.to_be_bytes());
+        payload[layout::ACN_PID_RANGE.clone()].copy_from_slice(layout::ACN_PID);
+        payload[layout::ROOT_VECTOR_RANGE.clone()]
+            .copy_from_slice(&layout::ROOT_VECTOR_DATA.to_be_bytes());
+        payload[layout::FRAMING_VECTOR_RANGE.clone()]
+            .copy_from_slice(&layout::FRAMING_VECTOR_DMX.to_be_bytes());
+        payload[layout::DMP_VECTOR_OFFSET] = layout::DMP_VECTOR_SET_PROPERTY;
+        payload[layout::UNIVERSE_RANGE.clone()].copy_from_slice(&1u16.to_be_bytes());
+        payload[layout::START_CODE_OFFSET] = 0x00;
+        payload[layout::SEQUENCE_OFFSET] = 0x01;
+        payload[layout::PRIORITY_OFFSET] = 150;
+        payload[layout::SYNC_ADDRESS_RANGE.clone()].copy_from_slice(&7000u16.to_be_bytes());
+        payload[layout::OPTIONS_OFFSET] = layout::OPTION_PREVIEW_DATA;
+        payload[layout::DMP_ADDRESS_TYPE_OFFSET] = 0xa1;
+        payload[layout::DMP_ADDRESS_INCREMENT_RANGE.clone()].copy_from_slice(&1u16.to_be_bytes());
+        payload[layout::DMP_PROPERTY_VALUE_COUNT_RANGE.clone()]
+            .copy_from_slice(&count.to_be_bytes());
+        payload[layout::START_CODE_OFFSET] = 0x00;
+        payload[layout::DMX_DATA_OFFSET..layout::DMX_DATA_OFFSET + 2].copy_from_slice(&[1, 2]);
+
+        let parsed = parse_sacn_dmx(&payload).unwrap();
+        assert!(parsed.is_some());
+        let parsed = parsed.unwrap();
+        assert_eq!(parsed.universe, 1);
+        assert_eq!(parsed.sequence, Some(0x01));
+        assert_eq!(parsed.source_name, None);
+        assert_eq!(parsed.source_name_issue, Some(SourceNameIssue::Empty));
+        assert_eq!(parsed.priority, 150);
+        assert_eq!(parsed.sync_address, Some(7000));
+        assert!(parsed.preview_data());
+        assert!(!parsed.stream_terminated());
+        assert!(!parsed.force_synchronization());
+        assert_eq!(parsed.address_type, 0xa1);
+        assert_eq!(parsed.first_property_address, 0);
+        assert_eq!(parsed.address_increment, 1);
+        assert_eq!(parsed.start_code, 0);
+        assert_eq!(&parsed.slots[..2], &[1, 2]);
+        assert_eq!(parsed.slots.len(), 2);
+    }
+
+    #[test]
+    fn parse_sync_packet_only() {
+        let mut payload = vec![0u8; layout::SYNC_LEN];
+        payload[layout::PREAMBLE_SIZE_RANGE.clone()]
+            .copy_from_slice(&layout::PREAMBLE_SIZE.to_be_bytes());
+        payload[layout::ACN_PID_RANGE.clone()].copy_from_slice(layout::ACN_PID);
+        payload[layout::ROOT_VECTOR_RANGE.clone()]
+            .copy_from_slice(&layout::ROOT_VECTOR_EXTENDED.to_be_bytes());
+        payload[layout::CID_RANGE.clone()].copy_from_slice(&[0xab; 16]);
+        payload[layout::FRAMING_VECTOR_RANGE.clone()]
+            .copy_from_slice(&layout::FRAMING_VECTOR_SYNC.to_be_bytes());
+        payload[layout::SYNC_SEQUENCE_OFFSET] = 9;
+        payload[layout::SYNC_UNIVERSE_RANGE.clone()].copy_from_slice(&7000u16.to_be_bytes());
+
+        let sync = parse_sacn_sync(&payload).unwrap().expect("sync");
+        assert_eq!(sync.cid, "ab".repeat(16));
+        assert_eq!(sync.sequence, 9);
+        assert_eq!(sync.sync_address, 7000);
+        assert!(matches!(
+            parse_sacn_sync(&payload[..layout::SYNC_LEN - 1]),
+            Err(SacnError::TooShort { .. })
+        ));
+
+        payload[layout::ROOT_VECTOR_RANGE.clone()]
+            .copy_from_slice(&layout::ROOT_VECTOR_DATA.to_be_bytes());
+        assert!(parse_sacn_sync(&payload).unwrap().is_none());
+    }
+
+    #[test]
+    fn parse_discovery_packet_only() {
+        let universes = [1u16, 2, 7];
+        let layer_len = 8 + 2 * universes.len();
+        let mut payload = vec![0u8; layout::DISCOVERY_UNIVERSES_OFFSET + 2 * universes.len()];
+        payload[layout::PREAMBLE_SIZE_RANGE.clone()]
+            .copy_from_slice(&layout::PREAMBLE_SIZE.to_be_bytes());
+        payload[layout::ACN_PID_RANGE.clone()].copy_from_slice(layout::ACN_PID);
+        payload[layout::ROOT_VECTOR_RANGE.clone()]
+            .copy_from_slice(&layout::ROOT_VECTOR_EXTENDED.to_be_bytes());
+        payload[layout::CID_RANGE.clone()].copy_from_slice(&[0xab; 16]);
+        payload[layout::FRAMING_VECTOR_RANGE.clone()]
+            .copy_from_slice(&layout::FRAMING_VECTOR_DISCOVERY.to_be_bytes());
+        payload[layout::DISCOVERY_SOURCE_NAME_RANGE.start..][..7].copy_from_slice(b"console");
+        payload[layout::DISCOVERY_LAYER_FLAGS_LENGTH_RANGE.clone()]
+            .copy_from_slice(&(0x7000 | layer_len as u16).to_be_bytes());
+        payload[layout::DISCOVERY_LAYER_VECTOR_RANGE.clone()]
+            .copy_from_slice(&layout::DISCOVERY_VECTOR_UNIVERSE_LIST.to_be_bytes());
+        payload[layout::DISCOVERY_PAGE_OFFSET] = 1;
+        payload[layout::DISCOVERY_LAST_PAGE_OFFSET] = 2;
+        for (index, universe) in universes.iter().enumerate() {
+            let offset = layout::DISCOVERY_UNIVERSES_OFFSET + 2 * index;
+            payload[offset..offset + 2].copy_from_slice(&universe.to_be_bytes());
+        }
+
+        let discovery = parse_sacn_discovery(&payload).unwrap().expect("discovery");
+        assert_eq!(discovery.cid, "ab".repeat(16));
+        assert_eq!(discovery.source_name.as_deref(), Some("console"));
+        assert_eq!((discovery.page, discovery.last_page), (1, 2));
+        assert_eq!(discovery.universes, universes.to_vec());
+        assert!(parse_sacn_sync(&payload).unwrap().is_none());
+        assert!(matches!(
+            parse_sacn_discovery(&payload[..payload.len() - 1]),
+            Err(SacnError::TooShort { .. })
+        ));
+
+        payload[layout::FRAMING_VECTOR_RANGE.clone()]
+            .copy_from_slice(&layout::FRAMING_VECTOR_SYNC.to_be_bytes());
+        assert!(parse_sacn_discovery(&payload).unwrap().is_none());
+    }
+
+    #[test]
+    fn parse_non_sacn() {
+        let payload = vec![0u8; layout::MIN_LEN];
+        let parsed = parse_sacn_dmx(&payload).unwrap();
+        assert!(parsed.is_none());
+    }
+
+    #[test]
+    fn parse_short_payload() {
+        let payload = vec![0u8; layout::MIN_LEN - 1];
+        let err = parse_sacn_dmx(&payload).unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("payload too short"));
+    }
+
+    #[test]
+    fn parse_invalid_property_value_count() {
+        let mut payload = vec![0u8; layout::DMX_DATA_OFFSET];
+        payload[layout::PREAMBLE_SIZE_RANGE.clone()]
+            .copy_from_slice(&layout::PREAMBLE_SIZE.to_be_bytes());
+        payload[layout::POSTAMBLE_SIZE_RANGE.clone()]
+            .copy_from_slice(&layout::POSTAMBLE_SIZE.to_be_bytes());
+        payload[layout::ACN_PID_RANGE.clone()].copy_from_slice(layout::ACN_PID);
+        payload[layout::ROOT_VECTOR_RANGE.clone()]
+            .copy_from_slice(&layout::ROOT_VECTOR_DATA.to_be_bytes());
+        payload[layout::FRAMING_VECTOR_RANGE.clone()]
+            .copy_from_slice(&layout::FRAMING_VECTOR_DMX.to_be_bytes());
+        payload[layout::DMP_VECTOR_OFFSET] = layout::DMP_VECTOR_SET_PROPERTY;
+        payload[layout::UNIVERSE_RANGE.clone()].copy_from_slice(&1u16.to_be_bytes());
+        payload[layout::START_CODE_OFFSET] = 0x00;
+        payload[layout::DMP_PROPERTY_VALUE_COUNT_RANGE.clone()]
+            .copy_from_slice(&0u16.to_be_bytes());
+
+        let err = parse_sacn_dmx(&payload).unwrap_err();
+        assert!(matches!(
+            err,
+            SacnError::InvalidPropertyValueCount { count: 0 }
+        ));
+    }
+
+    #[test]
+    fn parse_invalid_start_code() {
+        let mut payload = vec![0u8; layout::DMX_DATA_OFFSET];
+        payload[layout::PREAMBLE_SIZE_RANGE.clone()]
+            .copy_from_slice(&layout::PREAMBLE_SIZE.to_be_bytes());
+        payload[layout::POSTAMBLE_SIZE_RANGE.clone()]
+            .copy_from_slice(&layout::POSTAMBLE_SIZE.to_be_bytes());
+        payload[layout::ACN_PID_RANGE.clone()].copy_from_slice(layout::ACN_PID);
+        payload[layout::ROOT_VECTOR_RANGE.clone()]
+            .copy_from_slice(&layout::ROOT_VECTOR_DATA.to_be_bytes());
+        payload[layout::FRAMING_VECTOR_RANGE.clone()]
+            .copy_from_slice(&layout::FRAMING_VECTOR_DMX.to_be_bytes());
+        payload[layout::DMP_VECTOR_OFFSET] = layout::DMP_VECTOR_SET_PROPERTY;
+        payload[layout::UNIVERSE_RANGE.clone()].copy_from_slice(&1u16.to_be_bytes());
+        payload[layout::START_CODE_OFFSET] = 0x01;
+        payload[layout::DMP_PROPERTY_VALUE_COUNT_RANGE.clone()]
+            .copy_from_slice(&1u16.to_be_bytes());
+
+        let err = parse_sacn_dmx(&payload).unwrap_err();
+        assert!(matches!(err, SacnError::InvalidStartCode { value: 0x01 }));
+    }
+
+    #[test]
+    fn parse_address_priority_only() {
+        let count = 4u16;
+        let mut payload = vec![0u8; layout::DMX_DATA_OFFSET + (count - 1) as usize];
+        payload[layout::PREAMBLE_SIZE_RANGE.clone()]
+            .copy_from_slice(&layout::PREAMBLE_SIZE.to_be_bytes());
+        payload[layout::ACN_PID_RANGE.clone()].copy_from_slice(layout::ACN_PID);
+        payload[layout::ROOT_VECTOR_RANGE.clone()]
+            .copy_from_slice(&layout::ROOT_VECTOR_DATA.to_be_bytes());
+        payload[layout::FRAMING_VECTOR_RANGE.clone()]
+            .copy_from_slice(&layout::FRAMING_VECTOR_DMX.to_be_bytes());
+        payload[layout::DMP_VECTOR_OFFSET] = layout::DMP_VECTOR_SET_PROPERTY;
+        payload[layout::UNIVERSE_RANGE.clone()].copy_from_slice(&5u16.to_be_bytes());
+        payload[layout::DMP_PROPERTY_VALUE_COUNT_RANGE.clone()]
+            .copy_from_slice(&count.to_be_bytes());
+        payload[layout::START_CODE_OFFSET] = layout::START_CODE_PER_ADDRESS_PRIORITY;
+        payload[layout::DMX_DATA_OFFSET..].copy_from_slice(&[150, 150, 0]);
+
+        let parsed = parse_sacn_address_priority(&payload)
+            .unwrap()
+            .expect("priority");
+        assert_eq!(parsed.universe, 5);
+        assert_eq!(parsed.start_code, 0xdd);
+        assert_eq!(parsed.slots, vec![150, 150, 0]);
+        // Not DMX data.
+        assert!(matches!(
+            parse_sacn_dmx(&payload),
+            Err(SacnError::InvalidStartCode { value: 0xdd })
+        ));
+
+        payload[layout::START_CODE_OFFSET] = 0;
+        assert!(parse_sacn_address_priority(&payload).unwrap().is_none());
+        payload[layout::START_CODE_OFFSET] = layout::START_CODE_PER_ADDRESS_PRIORITY;
+        payload[layout::DMP_PROPERTY_VALUE_COUNT_RANGE.clone()]
+            .copy_from_slice(&0u16.to_be_bytes());
+        assert!(parse_sacn_address_priority(&payload).is_err());
+    }
+
+    #[test]
+    fn parse_invalid_acn_pid() {
+        let mut payload = vec![0u8; layout::MIN_LEN];
+        payload[layout::PREAMBLE_SIZE_RANGE.clone()]
+            .copy_from_slice(&layout::PREAMBLE_SIZE.to_be_bytes());
+        payload[layout::POSTAMBLE_SIZE_RANGE.clone()]
+            .copy_from_slice(&layout::POSTAMBLE_SIZE.to_be_bytes());
+        payload[layout::ACN_PID_RANGE.clone()].copy_from_slice(b"ASC-E1.17\x01\x02\x03");
+
+        let err = parse_sacn_dmx(&payload).unwrap_err();
+        assert!(matches!(err, SacnError::InvalidAcnPid));
+    }
+
+    #[test]
+    fn parse_invalid_root_vector() {
+        let mut payload = vec![0u8; layout::MIN_LEN];
+        payload[layout::PREAMBLE_SIZE_RANGE.clone()]
+            .copy_from_slice(&layout::PREAMBLE_SIZE.to_be_bytes());
+        payload[layout::POSTAMBLE_SIZE_RANGE.clone()]
+            .copy_from_slice(&layout::POSTAMBLE_SIZE.to_be_bytes());
+        payload[layout::ACN_PID_RANGE.clone()].copy_from_slice(layout::ACN_PID);
+        payload[layout::ROOT_VECTOR_RANGE.clone()].copy_from_slice(&0x0000_0001u32.to_be_bytes());
+
+        let err = parse_sacn_dmx(&payload).unwrap_err();
+        assert!(matches!(err, SacnError::InvalidRootVector { value: 1 }));
+    }
+
+    #[test]
+    fn parse_invalid_framing_vector() {
+        let mut payload = vec![0u8; layout::MIN_LEN];
+        payload[layout::PREAMBLE_SIZE_RANGE.clone()]
+            .copy_from_slice(&layout::PREAMBLE_SIZE.to_be_bytes());
+        payload[layout::POSTAMBLE_SIZE_RANGE.clone()]
+            .copy_from_slice(&layout::POSTAMBLE_SIZE.to_be_bytes());
+        payload[layout::ACN_PID_RANGE.clone()].copy_from_slice(layout::ACN_PID);
+        payload[layout::ROOT_VECTOR_RANGE.clone()]
+            .copy_from_slice(&layout::ROOT_VECTOR_DATA.to_be_bytes());
+        payload[layout::FRAMING_VECTOR_RANGE.clone()]
+            .copy_from_slice(&0x0000_0001u32.to_be_bytes());
+
+        let err = parse_sacn_dmx(&payload).unwrap_err();
+        assert!(matches!(err, SacnError::InvalidFramingVector { value: 1 }));
+    }
+
+    #[test]
+    fn parse_invalid_dmp_vector() {
+        let mut payload = vec![0u8; layout::MIN_LEN];
+        payload[layout::PREAMBLE_SIZE_RANGE.clone()]
+            .copy_from_slice(&layout::PREAMBLE_SIZE.to_be_bytes());
+        payload[layout::POSTAMBLE_SIZE_RANGE.clone()]
+            .copy_from_slice(&layout::POSTAMBLE_SIZE.to_be_bytes());
+        payload[layout::ACN_PID_RANGE.clone()].copy_from_slice(layout::ACN_PID);
+        payload[layout::ROOT_VECTOR_RANGE.clone()]
+            .copy_from_slice(&layout::ROOT_VECTOR_DATA.to_be_bytes());
+        payload[layout::FRAMING_VECTOR_RANGE.clone()]
+            .copy_from_slice(&layout::FRAMING_VECTOR_DMX.to_be_bytes());
+        payload[layout::DMP_VECTOR_OFFSET] = 0x00;
+
+        let err = parse_sacn_dmx(&payload).unwrap_err();
+        assert!(matches!(err, SacnError::InvalidDmpVector { value: 0 }));
+    }
+
+    #[test]
+    fn parse_property_value_count_too_large() {
+        let mut payload = vec![0u8; layout::DMX_DATA_OFFSET];
+        payload[layout::PREAMBLE_SIZE_RANGE.clone()]
+            .copy_from_slice(&layout::PREAMBLE_SIZE.to_be_bytes());
+        payload[layout::POSTAMBLE_SIZE_RANGE.clone()]
+            .copy_from_slice(&layout::POSTAMBLE_SIZE.to_be_bytes());
+        payload[layout::ACN_PID_RANGE.clone()].copy_from_slice(layout::ACN_PID);
+        payload[layout::ROOT_VECTOR_RANGE.clone()]
+            .copy_from_slice(&layout::ROOT_VECTOR_DATA.to_be_bytes());
+        payload[layout::FRAMING_VECTOR_RANGE.clone()]
+            .copy_from_slice(&layout::FRAMING_VECTOR_DMX.to_be_bytes());
+        payload[layout::DMP_VECTOR_OFFSET] = layout::DMP_VECTOR_SET_PROPERTY;
+        payload[layout::UNIVERSE_RANGE.clone()].copy_from_slice(&1u16.to_be_bytes());
+        payload[layout::START_CODE_OFFSET] = 0x00;
+        payload[layout::DMP_PROPERTY_VALUE_COUNT_RANGE.clone()]
+            .copy_from_slice(&(layout::DMX_MAX_SLOTS as u16 + 2).to_be_bytes());
+
+        let err = parse_sacn_dmx(&payload).unwrap_err();
+        assert!(matches!(
+            err,
+            SacnError::InvalidPropertyValueCount { count: 514 }
+        ));
+    }
+}
@@ -1,14 +1,15 @@
+use alloc::string::{String, ToString};
+
 use super::error::SacnError;
 use super::layout;
+use super::parser::SourceNameIssue;
+use crate::common::reader::optional_nonzero_u16;
 
 /// Safe byte reader for sACN payloads.
 ///
-/// Note: this reader lives in an internal module; the example is illustrative
-/// and not compiled as a public doctest.
-///
 /// # Examples
-/// ```text
-/// use liveshark_core::protocols::sacn::{layout, reader::SacnReader};
+/// ```
+/// use liveshark_proto::sacn::{layout, reader::SacnReader};
 ///
 /// let mut payload = vec![0u8; layout::START_CODE_OFFSET + 1];
 /// payload[layout::START_CODE_OFFSET] = 0x00;
@@ -47,7 +48,7 @@ impl<'a> SacnReader<'a> {
     }
 
     /// Read a big-endian `u16` from the given range.
-    pub fn read_u16_be(&self, range: std::ops::Range<usize>) -> Result<u16, SacnError> {
+    pub fn read_u16_be(&self, range: core::ops::Range<usize>) -> Result<u16, SacnError> {
         let bytes = self.read_slice(range)?;
         if bytes.len() != 2 {
             return Err(SacnError::TooShort {
@@ -59,7 +60,7 @@ impl<'a> SacnReader<'a> {
     }
 
     /// Read a big-endian `u32` from the given range.
-    pub fn read_u32_be(&self, range: std::ops::Range<usize>) -> Result<u32, SacnError> {
+    pub fn read_u32_be(&self, range: core::ops::Range<usize>) -> Result<u32, SacnError> {
         let bytes = self.read_slice(range)?;
         if bytes.len() != 4 {
             return Err(SacnError::TooShort {
@@ -71,18 +72,38 @@ impl<'a> SacnReader<'a> {
     }
 
     /// Read a byte slice from the given range.
-    pub fn read_slice(&self, range: std::ops::Range<usize>) -> Result<&'a [u8], SacnError> {
+    pub fn read_slice(&self, range: core::ops::Range<usize>) -> Result<&'a [u8], SacnError> {
         self.payload.get(range.clone()).ok_or(SacnError::TooShort {
             needed: range.end,
             actual: self.payload.len(),
         })
     }
 
-    /// Read a null-terminated ASCII string and trim whitespace.
-    pub fn read_ascii_string(&self, range: std::ops::Range<usize>) -> Result<String, SacnError> {
+    /// Read the E1.31 source name: null-terminated UTF-8, trimmed.
+    ///
+    /// Malformed names are reported as a `SourceNameIssue` instead of being
+    /// converted lossily; invalid UTF-8 yields no name.
+    pub fn read_source_name(
+        &self,
+        range: core::ops::Range<usize>,
+    ) -> Result<(Option<String>, Option<SourceNameIssue>), SacnError> {
         let bytes = self.read_slice(range)?;
-        let raw = String::from_utf8_lossy(bytes);
-        Ok(raw.trim_end_matches('\0').trim().to_string())
+        let terminator = bytes.iter().position(|&byte| byte == 0);
+        let content = &bytes[..terminator.unwrap_or(bytes.len())];
+        let Ok(name) = core::str::from_utf8(content) else {
+            return Ok((None, Some(SourceNameIssue::InvalidUtf8)));
+        };
+        let name = name.trim();
+        if terminator.is_none() {
+            return Ok((
+                Some(name.to_string()).filter(|name| !name.is_empty()),
+                Some(SourceNameIssue::NotTerminated),
+            ));
+        }
+        if name.is_empty() {
+            return Ok((None, Some(SourceNameIssue::Empty)));
+        }
+        Ok((Some(name.to_string()), None))
     }
 
     /// Read the CID and return a canonical lowercase hex string.
@@ -123,17 +144,10 @@ impl<'a> SacnReader<'a> {
         Ok(data_len)
     }
 
-    /// Read an ASCII string, returning `None` when empty.
-    pub fn read_optional_ascii_string(
-        &self,
-        range: std::ops::Range<usize>,
-    ) -> Result<Option<String>, SacnError> {
-        let value = self.read_ascii_string(range)?;
-        if value.is_empty() {
-            Ok(None)
-        } else {
-            Ok(Some(value))
-        }
+    /// Read the synchronization universe, returning `None` when zero (no sync).
+    pub fn read_sync_address(&self) -> Result<Option<u16>, SacnError> {
+        let value = self.read_u16_be(layout::SYNC_ADDRESS_RANGE.clone())?;
+        Ok(optional_nonzero_u16(value))
     }
 }
 
@@ -147,28 +161,15 @@ fn hex_char(value: u8) -> char {
 
 #[cfg(test)]
 mod tests {
-    use super::SacnReader;
-    use crate::protocols::sacn::error::SacnError;
-    use crate::protocols::sacn::layout;
+    use super::{SacnReader, SourceNameIssue};
+    use crate::sacn::error::SacnError;
+    use crate::sacn::layout;
 
     #[test]
-    fn read_optional_ascii_string() {
-        let payload = [b't', b'e', b's', b't', 0x00];
-        let reader = SacnReader::new(&payload);
-        let value = reader.read_optional_ascii_string(0..payload.len()).unwrap();
-        assert_eq!(value.as_deref(), Some("test"));
-
-        let empty = [0x00u8; 4];
-        let reader = SacnReader::new(&empty);
-        let value = reader.read_optional_ascii_string(0..empty.len()).unwrap();
-        assert!(value.is_none());
-    }
-
-    #[test]
-    fn read_optional_ascii_string_too_short() {
+    fn read_source_name_too_short() {
         let payload = [];
         let reader = SacnReader::new(&payload);
-        let err = reader.read_optional_ascii_string(0..1).unwrap_err();
+        let err = reader.read_source_name(0..1).unwrap_err();
         assert!(matches!(err, SacnError::TooShort { .. }));
     }
 
@@ -229,4 +230,34 @@ mod tests {
         let err = reader.read_dmx_data_len().unwrap_err();
         assert!(matches!(err, SacnError::InvalidPropertyValueCount { count } if count == value));
     }
+
+    #[test]
+    fn read_source_name_reports_issues() {
+        let reader = SacnReader::new(b"desk\0\0");
+        assert_eq!(
+            reader.read_source_name(0..6).unwrap(),
+            (Some("desk".to_string()), None)
+        );
+
+        let reader = SacnReader::new(b"desk");
+        assert_eq!(
+            reader.read_source_name(0..4).unwrap(),
+            (
+                Some("desk".to_string()),
+                Some(SourceNameIssue::NotTerminated)
+            )
+        );
+
+        let reader = SacnReader::new(&[0xff, 0xfe, 0x00]);
+        assert_eq!(
+            reader.read_source_name(0..3).unwrap(),
+            (None, Some(SourceNameIssue::InvalidUtf8))
+        );
+
+        let reader = SacnReader::new(b" \0");
+        assert_eq!(
+            reader.read_source_name(0..2).unwrap(),
+            (None, Some(SourceNameIssue::Empty))
+        );
+    }
 }
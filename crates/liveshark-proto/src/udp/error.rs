@@ -0,0 +1,49 @@
+use alloc::string::String;
+use core::fmt;
+use core::net::IpAddr;
+
+/// Errors returned by UDP decoding.
+///
+/// # Examples
+/// ```
+/// use liveshark_proto::UdpError;
+///
+/// let err = UdpError::MissingNetworkLayer;
+/// assert!(err.to_string().contains("missing network layer"));
+/// ```
+#[derive(Debug)]
+pub enum UdpError {
+    Slice(String),
+    MissingNetworkLayer,
+    MissingIpPayload,
+    TooShort {
+        needed: usize,
+        actual: usize,
+    },
+    Fragmented {
+        src: IpAddr,
+        dst: IpAddr,
+        ip_len: usize,
+    },
+}
+
+impl fmt::Display for UdpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Slice(err) => write!(f, "packet slice error: {err}"),
+            Self::MissingNetworkLayer => f.write_str("missing network layer in packet"),
+            Self::MissingIpPayload => f.write_str("missing IP payload in packet"),
+            Self::TooShort { needed, actual } => {
+                write!(f, "payload too short: need {needed} bytes, got {actual}")
+            }
+            Self::Fragmented { src, dst, ip_len } => {
+                write!(
+                    f,
+                    "fragmented UDP datagram from {src} to {dst} ({ip_len} bytes)"
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for UdpError {}
@@ -0,0 +1,86 @@
+pub const UDP_HEADER_LEN: usize = 8;
+/// Fixed IPv6 header length; `payload_length` excludes it.
+pub const IPV6_HEADER_LEN: usize = 40;
+
+/// Ethernet II header, before any VLAN tag.
+pub const ETHER_TYPE_OFFSET: usize = 12;
+/// 802.1Q tag: TPID (in place of the EtherType), then TCI.
+pub const VLAN_TAG_LEN: usize = 4;
+/// Tag protocol identifiers: 802.1Q, 802.1ad (QinQ service tag), and the
+/// pre-standard QinQ values some switches still send.
+pub const VLAN_TPIDS: [u16; 5] = [0x8100, 0x88a8, 0x9100, 0x9200, 0x9300];
+/// VLAN identifier bits of the TCI.
+pub const VLAN_ID_MASK: u16 = 0x0fff;
+/// Tags read in front of the network layer (single or QinQ).
+pub const MAX_VLAN_TAGS: usize = 2;
+
+/// BSD loopback header: the address family as a 32-bit integer, in the
+/// capturing host's byte order (`NULL`) or big-endian (`LOOP`).
+pub const LOOPBACK_HEADER_LEN: usize = 4;
+pub const AF_INET: u32 = 2;
+/// `AF_INET6` differs between systems: Linux, NetBSD/OpenBSD, FreeBSD,
+/// macOS.
+pub const AF_INET6: [u32; 4] = [10, 24, 28, 30];
+
+/// VXLAN (RFC 7348): an 8-byte header, then the inner Ethernet frame.
+pub const VXLAN_PORT: u16 = 4789;
+pub const VXLAN_HEADER_LEN: usize = 8;
+/// Flag set when the header carries a valid VXLAN network identifier.
+pub const VXLAN_FLAG_VNI: u8 = 0x08;
+
+/// GRE (RFC 2784/2890): flags and version, protocol type, then one 4-byte
+/// word for each optional field present.
+pub const GRE_HEADER_LEN: usize = 4;
+pub const GRE_OPTIONAL_FIELD_LEN: usize = 4;
+pub const GRE_FLAG_CHECKSUM: u16 = 0x8000;
+pub const GRE_FLAG_KEY: u16 = 0x2000;
+pub const GRE_FLAG_SEQUENCE: u16 = 0x1000;
+pub const GRE_VERSION_MASK: u16 = 0x0007;
+/// Transparent Ethernet bridging: the payload is an Ethernet frame.
+pub const GRE_PROTO_ETHERNET: u16 = 0x6558;
+pub const GRE_PROTO_IPV4: u16 = 0x0800;
+pub const GRE_PROTO_IPV6: u16 = 0x86dd;
+/// ERSPAN type I and II; type I has no sequence number and no ERSPAN header.
+pub const GRE_PROTO_ERSPAN_II: u16 = 0x88be;
+pub const GRE_PROTO_ERSPAN_III: u16 = 0x22eb;
+pub const ERSPAN_II_HEADER_LEN: usize = 8;
+pub const ERSPAN_III_HEADER_LEN: usize = 12;
+/// Optional platform-specific subheader of ERSPAN type III, flagged by the
+/// lowest bit of its header.
+pub const ERSPAN_III_SUBHEADER_LEN: usize = 8;
+pub const ERSPAN_III_FLAG_SUBHEADER: u8 = 0x01;
+
+/// Radiotap header: version, padding, little-endian length, then one or more
+/// little-endian `present` words (bit 31 chains another word).
+pub const RADIOTAP_LEN_OFFSET: usize = 2;
+pub const RADIOTAP_PRESENT_OFFSET: usize = 4;
+pub const RADIOTAP_PRESENT_EXT: u32 = 1 << 31;
+/// Fields before `Flags`: TSFT, a 64-bit timestamp aligned to 8 bytes.
+pub const RADIOTAP_PRESENT_TSFT: u32 = 1 << 0;
+pub const RADIOTAP_TSFT_LEN: usize = 8;
+pub const RADIOTAP_PRESENT_FLAGS: u32 = 1 << 1;
+/// `Flags` bit set when the 802.11 frame ends with its 4-byte FCS.
+pub const RADIOTAP_FLAG_FCS: u8 = 0x10;
+pub const IEEE80211_FCS_LEN: usize = 4;
+
+/// 802.11 MAC header: frame control, duration, three addresses, sequence
+/// control; a fourth address when both DS bits are set (WDS).
+pub const IEEE80211_HEADER_LEN: usize = 24;
+pub const IEEE80211_ADDR4_LEN: usize = 6;
+pub const IEEE80211_QOS_CONTROL_LEN: usize = 2;
+pub const IEEE80211_HT_CONTROL_LEN: usize = 4;
+pub const IEEE80211_TYPE_MASK: u8 = 0x0c;
+pub const IEEE80211_TYPE_DATA: u8 = 0x08;
+/// Subtype bits of data frames: QoS, and no frame body (null data).
+pub const IEEE80211_SUBTYPE_QOS: u8 = 0x80;
+pub const IEEE80211_SUBTYPE_NO_DATA: u8 = 0x40;
+/// Second frame control byte.
+pub const IEEE80211_FLAGS_DS: u8 = 0x03;
+pub const IEEE80211_FLAG_RETRY: u8 = 0x08;
+pub const IEEE80211_FLAG_PROTECTED: u8 = 0x40;
+pub const IEEE80211_FLAG_ORDER: u8 = 0x80;
+/// QoS control bit marking an aggregated (A-MSDU) frame body.
+pub const IEEE80211_QOS_AMSDU: u8 = 0x80;
+/// LLC/SNAP header in front of the EtherType of the frame body.
+pub const LLC_SNAP_HEADER: [u8; 6] = [0xaa, 0xaa, 0x03, 0x00, 0x00, 0x00];
+pub const LLC_SNAP_LEN: usize = 8;
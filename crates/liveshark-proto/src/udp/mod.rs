@@ -0,0 +1,16 @@
+//! UDP datagram extraction from Ethernet, raw IP, BSD loopback, or 802.11
+//! (radiotap) frames, and unwrapping of the VXLAN, GRE, and ERSPAN tunnels
+//! mirrored traffic arrives in.
+//!
+//! Fragments are reported rather than reassembled, and checksum mismatches
+//! are surfaced without rejecting the datagram.
+//!
+pub mod error;
+pub mod layout;
+pub mod parser;
+pub mod reader;
+
+pub use parser::{
+    ChecksumMismatch, LinkLayer, Tunnel, TunnelKind, UdpPacket, decapsulate,
+    parse_truncated_udp_packet, parse_udp_packet,
+};
@@ -0,0 +1,949 @@
+use alloc::string::ToString;
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use etherparse::{
+    EtherType, IpNumber, Ipv4HeaderSlice, Ipv4Slice, Ipv6HeaderSlice, LaxNetSlice, LaxSlicedPacket,
+    NetSlice, SlicedPacket, TransportSlice, UdpSlice,
+};
+
+use super::error::UdpError;
+use super::layout::{self, IPV6_HEADER_LEN};
+use super::reader::UdpReader;
+
+/// Parsed UDP packet with source/destination endpoints.
+///
+/// `ip_len` is the full IP datagram length (header included) as declared by
+/// the network layer; `ttl` is the IPv4 TTL or IPv6 hop limit. Checksum
+/// mismatches are reported, not rejected: captures taken on the sending host
+/// often carry unfilled checksums because of NIC offload. `vlan` is the
+/// innermost 802.1Q VLAN ID of a tagged Ethernet frame; `outer_vlan` the
+/// service VLAN ID of a double-tagged (QinQ) one. `wifi_retry` is the retry
+/// flag of an 802.11 data frame (`None` on other links).
+///
+/// # Examples
+/// ```
+/// use std::net::IpAddr;
+///
+/// use liveshark_proto::UdpPacket;
+///
+/// let packet = UdpPacket {
+///     src_ip: IpAddr::V4("192.168.0.1".parse().unwrap()),
+///     src_port: 6454,
+///     dst_ip: IpAddr::V4("192.168.0.2".parse().unwrap()),
+///     dst_port: 6454,
+///     payload: &[1, 2, 3],
+///     ip_len: 31,
+///     ttl: 64,
+///     ip_checksum: None,
+///     udp_checksum: None,
+///     vlan: Some(10),
+///     outer_vlan: None,
+///     wifi_retry: None,
+/// };
+/// assert_eq!(packet.payload.len(), 3);
+/// ```
+pub struct UdpPacket<'a> {
+    pub src_ip: IpAddr,
+    pub src_port: u16,
+    pub dst_ip: IpAddr,
+    pub dst_port: u16,
+    pub payload: &'a [u8],
+    pub ip_len: usize,
+    pub ttl: u8,
+    /// IPv4 header checksum mismatch (always `None` for IPv6).
+    pub ip_checksum: Option<ChecksumMismatch>,
+    /// UDP checksum mismatch (`None` when valid or, over IPv4, not computed).
+    pub udp_checksum: Option<ChecksumMismatch>,
+    pub vlan: Option<u16>,
+    pub outer_vlan: Option<u16>,
+    pub wifi_retry: Option<bool>,
+}
+
+/// Checksum found on the wire that differs from the recomputed one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChecksumMismatch {
+    pub expected: u16,
+    pub actual: u16,
+}
+
+/// Link layers `parse_udp_packet` can decode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkLayer {
+    /// Ethernet II frames, untagged, 802.1Q tagged, or double tagged
+    /// (QinQ).
+    Ethernet,
+    /// Raw IPv4 or IPv6 datagrams.
+    RawIp,
+    /// BSD loopback (`LINKTYPE_NULL`): a 4-byte address family in the
+    /// capturing host's byte order, then the IP datagram.
+    Null,
+    /// OpenBSD loopback (`LINKTYPE_LOOP`): as `Null`, with the address
+    /// family big-endian.
+    Loop,
+    /// IEEE 802.11 frames (`LINKTYPE_IEEE802_11`); only unprotected data
+    /// frames carry IP, so encrypted captures must be decrypted first.
+    Ieee80211,
+    /// 802.11 frames behind a radiotap header, as captured by monitor-mode
+    /// adapters (`LINKTYPE_IEEE802_11_RADIOTAP`).
+    Radiotap,
+}
+
+/// Parse a UDP packet from a link-layer frame.
+///
+/// Returns `Ok(None)` when the payload is not UDP, including loopback
+/// frames of another address family than IPv4 or IPv6, and 802.11 frames
+/// other than unprotected data frames.
+///
+/// # Examples
+/// ```
+/// use etherparse::PacketBuilder;
+/// use liveshark_proto::{LinkLayer, parse_udp_packet};
+///
+/// let builder = PacketBuilder::ethernet2([1, 2, 3, 4, 5, 6], [7, 8, 9, 10, 11, 12])
+///     .ipv4([192, 168, 0, 1], [192, 168, 0, 2], 64)
+///     .udp(6454, 6454);
+/// let payload = [1, 2, 3, 4];
+/// let mut packet = Vec::<u8>::with_capacity(builder.size(payload.len()));
+/// builder.write(&mut packet, &payload).unwrap();
+///
+/// let parsed = parse_udp_packet(LinkLayer::Ethernet, &packet)?.expect("udp");
+/// assert_eq!(parsed.src_port, 6454);
+/// assert_eq!(parsed.payload, payload);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// Returns `UdpError` when the packet cannot be sliced, when required network
+/// layers are missing, or when the UDP datagram arrives as an IP fragment
+/// (fragments are not reassembled).
+pub fn parse_udp_packet(link: LinkLayer, data: &[u8]) -> Result<Option<UdpPacket<'_>>, UdpError> {
+    let Some((sliced, link_info)) = slice(link, data)? else {
+        return Ok(None);
+    };
+    let (vlan, outer_vlan) = link_info.vlan_ids();
+
+    let net = sliced.net.ok_or(UdpError::MissingNetworkLayer)?;
+    let (src_ip, dst_ip, ip_len, ttl) = match net {
+        NetSlice::Ipv4(ref ipv4) => ipv4_fields(&ipv4.header()),
+        NetSlice::Ipv6(ref ipv6) => ipv6_fields(&ipv6.header()),
+    };
+
+    let ip_payload = net.ip_payload_ref().ok_or(UdpError::MissingIpPayload)?;
+    if ip_payload.fragmented && ip_payload.ip_number == IpNumber::UDP {
+        return Err(UdpError::Fragmented {
+            src: src_ip,
+            dst: dst_ip,
+            ip_len,
+        });
+    }
+    let udp = match sliced.transport {
+        Some(TransportSlice::Udp(udp)) => udp,
+        _ => return Ok(None),
+    };
+
+    let reader = UdpReader::new(ip_payload.payload);
+    let payload = reader.payload_without_header()?;
+    let (ip_checksum, udp_checksum) = match net {
+        NetSlice::Ipv4(ref ipv4) => (ipv4_header_checksum(ipv4), ipv4_udp_checksum(ipv4, &udp)),
+        NetSlice::Ipv6(ref ipv6) => {
+            let header = ipv6.header();
+            let expected = udp
+                .to_header()
+                .calc_checksum_ipv6_raw(header.source(), header.destination(), udp.payload())
+                .ok();
+            (None, mismatch(expected, udp.checksum()))
+        }
+    };
+
+    Ok(Some(UdpPacket {
+        src_ip,
+        src_port: udp.source_port(),
+        dst_ip,
+        dst_port: udp.destination_port(),
+        payload,
+        ip_len,
+        ttl,
+        ip_checksum,
+        udp_checksum,
+        vlan,
+        outer_vlan,
+        wifi_retry: link_info.wifi_retry,
+    }))
+}
+
+/// Parse the headers of a UDP packet the capture cut short (snaplen).
+///
+/// Link, IP, and UDP headers are read as in [`parse_udp_packet`], with the
+/// lengths they declare (`ip_len`) rather than the bytes captured; `payload`
+/// is the part of the datagram that was captured, and checksums are not
+/// verified since they cover bytes that are missing.
+///
+/// # Examples
+/// ```
+/// use etherparse::PacketBuilder;
+/// use liveshark_proto::{LinkLayer, parse_truncated_udp_packet};
+///
+/// let builder = PacketBuilder::ethernet2([1, 2, 3, 4, 5, 6], [7, 8, 9, 10, 11, 12])
+///     .ipv4([192, 168, 0, 1], [192, 168, 0, 2], 64)
+///     .udp(6454, 6454);
+/// let mut packet = Vec::new();
+/// builder.write(&mut packet, &[0u8; 530]).unwrap();
+/// packet.truncate(96);
+///
+/// let parsed = parse_truncated_udp_packet(LinkLayer::Ethernet, &packet)?.expect("udp");
+/// assert_eq!(parsed.ip_len, 558);
+/// assert_eq!(parsed.payload.len(), 96 - 42);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// Returns `UdpError` when the headers themselves were cut or cannot be
+/// decoded, or when the datagram is an IP fragment.
+pub fn parse_truncated_udp_packet(
+    link: LinkLayer,
+    data: &[u8],
+) -> Result<Option<UdpPacket<'_>>, UdpError> {
+    let Some((frame, link_info)) = link_frame(link, data)? else {
+        return Ok(None);
+    };
+    let sliced = match frame {
+        Frame::Ethernet(data) => {
+            LaxSlicedPacket::from_ethernet(data).map_err(|e| UdpError::Slice(e.to_string()))?
+        }
+        Frame::EtherType(ether_type, data) => LaxSlicedPacket::from_ether_type(ether_type, data),
+        Frame::Ip(data) => {
+            LaxSlicedPacket::from_ip(data).map_err(|e| UdpError::Slice(e.to_string()))?
+        }
+    };
+    let (vlan, outer_vlan) = link_info.vlan_ids();
+    // The error the lax slicer stopped at, if the cut fell inside a header.
+    let stop_err = |fallback| {
+        sliced
+            .stop_err
+            .as_ref()
+            .map_or(fallback, |(err, _)| UdpError::Slice(err.to_string()))
+    };
+
+    let Some(net) = sliced.net.as_ref() else {
+        return Err(stop_err(UdpError::MissingNetworkLayer));
+    };
+    let (src_ip, dst_ip, ip_len, ttl) = match net {
+        LaxNetSlice::Ipv4(ipv4) => ipv4_fields(&ipv4.header()),
+        LaxNetSlice::Ipv6(ipv6) => ipv6_fields(&ipv6.header()),
+    };
+    let ip_payload = net.ip_payload_ref().ok_or(UdpError::MissingIpPayload)?;
+    if ip_payload.fragmented && ip_payload.ip_number == IpNumber::UDP {
+        return Err(UdpError::Fragmented {
+            src: src_ip,
+            dst: dst_ip,
+            ip_len,
+        });
+    }
+    let udp = match &sliced.transport {
+        Some(TransportSlice::Udp(udp)) => udp,
+        _ if ip_payload.ip_number == IpNumber::UDP => {
+            return Err(stop_err(UdpError::MissingIpPayload));
+        }
+        _ => return Ok(None),
+    };
+
+    Ok(Some(UdpPacket {
+        src_ip,
+        src_port: udp.source_port(),
+        dst_ip,
+        dst_port: udp.destination_port(),
+        payload: udp.payload(),
+        ip_len,
+        ttl,
+        ip_checksum: None,
+        udp_checksum: None,
+        vlan,
+        outer_vlan,
+        wifi_retry: link_info.wifi_retry,
+    }))
+}
+
+/// Source, destination, datagram length, and TTL of an IPv4 header.
+fn ipv4_fields(header: &Ipv4HeaderSlice<'_>) -> (IpAddr, IpAddr, usize, u8) {
+    (
+        IpAddr::V4(Ipv4Addr::from(header.source())),
+        IpAddr::V4(Ipv4Addr::from(header.destination())),
+        usize::from(header.total_len()),
+        header.ttl(),
+    )
+}
+
+/// Source, destination, datagram length, and hop limit of an IPv6 header.
+fn ipv6_fields(header: &Ipv6HeaderSlice<'_>) -> (IpAddr, IpAddr, usize, u8) {
+    (
+        IpAddr::V6(Ipv6Addr::from(header.source())),
+        IpAddr::V6(Ipv6Addr::from(header.destination())),
+        usize::from(header.payload_length()) + IPV6_HEADER_LEN,
+        header.hop_limit(),
+    )
+}
+
+/// Tunnels `decapsulate` unwraps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TunnelKind {
+    /// VXLAN over UDP port 4789.
+    Vxlan,
+    /// GRE carrying Ethernet (transparent bridging) or IP.
+    Gre,
+    /// ERSPAN type I, II, or III mirror sessions over GRE.
+    Erspan,
+}
+
+/// Frame carried inside a tunnel, with the link layer it starts with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tunnel<'a> {
+    pub kind: TunnelKind,
+    pub link: LinkLayer,
+    pub frame: &'a [u8],
+}
+
+/// Unwrap the frame carried by a VXLAN, GRE, or ERSPAN tunnel.
+///
+/// Switches mirror traffic to a remote analyzer this way; the inner frame
+/// can then be passed to `parse_udp_packet`. Returns `None` when the frame
+/// is not a tunnel, or is one that cannot be read (truncated, fragmented,
+/// unknown GRE protocol). Only the outermost tunnel is unwrapped.
+///
+/// # Examples
+/// ```
+/// use etherparse::PacketBuilder;
+/// use liveshark_proto::{LinkLayer, TunnelKind, decapsulate, parse_udp_packet};
+///
+/// let inner = PacketBuilder::ethernet2([1, 2, 3, 4, 5, 6], [7, 8, 9, 10, 11, 12])
+///     .ipv4([192, 168, 0, 1], [192, 168, 0, 2], 64)
+///     .udp(6454, 6454);
+/// let mut vxlan = vec![0x08, 0, 0, 0, 0, 0, 0x10, 0];
+/// inner.write(&mut vxlan, &[1, 2, 3]).unwrap();
+///
+/// let outer = PacketBuilder::ethernet2([1, 2, 3, 4, 5, 6], [7, 8, 9, 10, 11, 12])
+///     .ipv4([10, 0, 0, 1], [10, 0, 0, 2], 64)
+///     .udp(49152, 4789);
+/// let mut packet = Vec::new();
+/// outer.write(&mut packet, &vxlan).unwrap();
+///
+/// let tunnel = decapsulate(LinkLayer::Ethernet, &packet).expect("vxlan");
+/// assert_eq!(tunnel.kind, TunnelKind::Vxlan);
+/// let parsed = parse_udp_packet(tunnel.link, tunnel.frame)?.expect("udp");
+/// assert_eq!(parsed.dst_port, 6454);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn decapsulate(link: LinkLayer, data: &[u8]) -> Option<Tunnel<'_>> {
+    let (sliced, _) = slice(link, data).ok()??;
+    let ip_payload = sliced.net.as_ref()?.ip_payload_ref()?;
+    if ip_payload.fragmented {
+        return None;
+    }
+    match &sliced.transport {
+        Some(TransportSlice::Udp(udp)) if udp.destination_port() == layout::VXLAN_PORT => {
+            vxlan_frame(udp.payload())
+        }
+        _ if ip_payload.ip_number == IpNumber::GRE => gre_frame(ip_payload.payload),
+        _ => None,
+    }
+}
+
+fn vxlan_frame(payload: &[u8]) -> Option<Tunnel<'_>> {
+    let flags = *payload.first()?;
+    if flags & layout::VXLAN_FLAG_VNI == 0 {
+        return None;
+    }
+    Some(Tunnel {
+        kind: TunnelKind::Vxlan,
+        link: LinkLayer::Ethernet,
+        frame: payload.get(layout::VXLAN_HEADER_LEN..)?,
+    })
+}
+
+fn gre_frame(payload: &[u8]) -> Option<Tunnel<'_>> {
+    let reader = UdpReader::new(payload);
+    let flags = reader.read_u16_be(0).ok()?;
+    if flags & layout::GRE_VERSION_MASK != 0 {
+        return None;
+    }
+    let optional_fields = [
+        layout::GRE_FLAG_CHECKSUM,
+        layout::GRE_FLAG_KEY,
+        layout::GRE_FLAG_SEQUENCE,
+    ]
+    .iter()
+    .filter(|flag| flags & **flag != 0)
+    .count();
+    let offset = layout::GRE_HEADER_LEN + optional_fields * layout::GRE_OPTIONAL_FIELD_LEN;
+    let (kind, link, offset) = match reader.read_u16_be(2).ok()? {
+        layout::GRE_PROTO_ETHERNET => (TunnelKind::Gre, LinkLayer::Ethernet, offset),
+        layout::GRE_PROTO_IPV4 | layout::GRE_PROTO_IPV6 => {
+            (TunnelKind::Gre, LinkLayer::RawIp, offset)
+        }
+        // Type II is told apart from type I by its sequence number.
+        layout::GRE_PROTO_ERSPAN_II if flags & layout::GRE_FLAG_SEQUENCE != 0 => (
+            TunnelKind::Erspan,
+            LinkLayer::Ethernet,
+            offset + layout::ERSPAN_II_HEADER_LEN,
+        ),
+        layout::GRE_PROTO_ERSPAN_II => (TunnelKind::Erspan, LinkLayer::Ethernet, offset),
+        layout::GRE_PROTO_ERSPAN_III => {
+            let header_end = offset + layout::ERSPAN_III_HEADER_LEN;
+            let subheader = payload.get(header_end - 1)? & layout::ERSPAN_III_FLAG_SUBHEADER != 0;
+            let subheader_len = if subheader {
+                layout::ERSPAN_III_SUBHEADER_LEN
+            } else {
+                0
+            };
+            (
+                TunnelKind::Erspan,
+                LinkLayer::Ethernet,
+                header_end + subheader_len,
+            )
+        }
+        _ => return None,
+    };
+    Some(Tunnel {
+        kind,
+        link,
+        frame: payload.get(offset..)?,
+    })
+}
+
+/// Link-layer details kept next to the sliced packet.
+#[derive(Default)]
+struct LinkInfo {
+    vlan_tags: Option<VlanTags>,
+    wifi_retry: Option<bool>,
+}
+
+impl LinkInfo {
+    /// Innermost and, for QinQ, outer VLAN IDs.
+    fn vlan_ids(&self) -> (Option<u16>, Option<u16>) {
+        match self.vlan_tags.as_ref().map(|tags| tags.ids) {
+            Some([outer, Some(inner)]) => (Some(inner), outer),
+            Some([id, None]) => (id, None),
+            None => (None, None),
+        }
+    }
+}
+
+/// What remains of a frame once its link layer is understood.
+enum Frame<'a> {
+    /// An untagged Ethernet frame, header included.
+    Ethernet(&'a [u8]),
+    /// The payload of a tagged Ethernet or 802.11 frame, and its EtherType.
+    EtherType(EtherType, &'a [u8]),
+    /// An IP datagram.
+    Ip(&'a [u8]),
+}
+
+/// Strip the link layer a slicer cannot read itself, with the VLAN tags of
+/// a tagged Ethernet frame or the retry flag of an 802.11 one; `Ok(None)`
+/// for loopback frames of another address family than IP and 802.11 frames
+/// that carry no IP.
+fn link_frame(link: LinkLayer, data: &[u8]) -> Result<Option<(Frame<'_>, LinkInfo)>, UdpError> {
+    let mut info = LinkInfo::default();
+    let frame = match link {
+        LinkLayer::Ethernet => {
+            info.vlan_tags = vlan_tags(data);
+            match &info.vlan_tags {
+                Some(tags) => Frame::EtherType(EtherType(tags.ether_type), &data[tags.offset..]),
+                None => Frame::Ethernet(data),
+            }
+        }
+        LinkLayer::RawIp => Frame::Ip(data),
+        LinkLayer::Null | LinkLayer::Loop => {
+            if !is_ip_family(loopback_family(link, data)?) {
+                return Ok(None);
+            }
+            Frame::Ip(&data[layout::LOOPBACK_HEADER_LEN..])
+        }
+        LinkLayer::Ieee80211 | LinkLayer::Radiotap => {
+            let frame = match link {
+                LinkLayer::Radiotap => radiotap_frame(data)?,
+                _ => data,
+            };
+            let Some(body) = wifi_data(frame)? else {
+                return Ok(None);
+            };
+            info.wifi_retry = Some(body.retry);
+            Frame::EtherType(EtherType(body.ether_type), body.payload)
+        }
+    };
+    Ok(Some((frame, info)))
+}
+
+/// Slice a frame down to its transport layer (see [`link_frame`]).
+fn slice(link: LinkLayer, data: &[u8]) -> Result<Option<(SlicedPacket<'_>, LinkInfo)>, UdpError> {
+    let Some((frame, info)) = link_frame(link, data)? else {
+        return Ok(None);
+    };
+    let sliced = match frame {
+        Frame::Ethernet(data) => SlicedPacket::from_ethernet(data),
+        Frame::EtherType(ether_type, data) => SlicedPacket::from_ether_type(ether_type, data),
+        Frame::Ip(data) => SlicedPacket::from_ip(data),
+    }
+    .map_err(|e| UdpError::Slice(e.to_string()))?;
+    Ok(Some((sliced, info)))
+}
+
+/// The 802.11 frame behind a radiotap header, without its FCS.
+fn radiotap_frame(data: &[u8]) -> Result<&[u8], UdpError> {
+    let reader = UdpReader::new(data);
+    let len = usize::from(reader.read_u16_le(layout::RADIOTAP_LEN_OFFSET)?);
+    reader.require_len(len)?;
+    let present = reader.read_u32(layout::RADIOTAP_PRESENT_OFFSET, false)?;
+    let mut offset = layout::RADIOTAP_PRESENT_OFFSET + 4;
+    let mut word = present;
+    while word & layout::RADIOTAP_PRESENT_EXT != 0 {
+        word = reader.read_u32(offset, false)?;
+        offset += 4;
+    }
+    // Fields follow the present words in bit order, each aligned to its size.
+    if present & layout::RADIOTAP_PRESENT_TSFT != 0 {
+        offset = offset.next_multiple_of(layout::RADIOTAP_TSFT_LEN) + layout::RADIOTAP_TSFT_LEN;
+    }
+    let mut frame = &data[len..];
+    if present & layout::RADIOTAP_PRESENT_FLAGS != 0
+        && offset < len
+        && reader.read_u8(offset)? & layout::RADIOTAP_FLAG_FCS != 0
+    {
+        frame = &frame[..frame.len().saturating_sub(layout::IEEE80211_FCS_LEN)];
+    }
+    Ok(frame)
+}
+
+/// Body of an 802.11 data frame after its LLC/SNAP header.
+struct WifiData<'a> {
+    ether_type: u16,
+    payload: &'a [u8],
+    retry: bool,
+}
+
+/// Read an unprotected 802.11 data frame carrying one LLC/SNAP packet;
+/// `Ok(None)` for management, control, null, encrypted, and aggregated
+/// (A-MSDU) frames.
+fn wifi_data(frame: &[u8]) -> Result<Option<WifiData<'_>>, UdpError> {
+    let reader = UdpReader::new(frame);
+    let (kind, flags) = (reader.read_u8(0)?, reader.read_u8(1)?);
+    if kind & layout::IEEE80211_TYPE_MASK != layout::IEEE80211_TYPE_DATA
+        || kind & layout::IEEE80211_SUBTYPE_NO_DATA != 0
+        || flags & layout::IEEE80211_FLAG_PROTECTED != 0
+    {
+        return Ok(None);
+    }
+    let mut offset = layout::IEEE80211_HEADER_LEN;
+    if flags & layout::IEEE80211_FLAGS_DS == layout::IEEE80211_FLAGS_DS {
+        offset += layout::IEEE80211_ADDR4_LEN;
+    }
+    if kind & layout::IEEE80211_SUBTYPE_QOS != 0 {
+        if reader.read_u8(offset)? & layout::IEEE80211_QOS_AMSDU != 0 {
+            return Ok(None);
+        }
+        offset += layout::IEEE80211_QOS_CONTROL_LEN;
+        if flags & layout::IEEE80211_FLAG_ORDER != 0 {
+            offset += layout::IEEE80211_HT_CONTROL_LEN;
+        }
+    }
+    reader.require_len(offset + layout::LLC_SNAP_LEN)?;
+    if frame[offset..offset + layout::LLC_SNAP_HEADER.len()] != layout::LLC_SNAP_HEADER {
+        return Ok(None);
+    }
+    Ok(Some(WifiData {
+        ether_type: reader.read_u16_be(offset + layout::LLC_SNAP_HEADER.len())?,
+        payload: &frame[offset + layout::LLC_SNAP_LEN..],
+        retry: flags & layout::IEEE80211_FLAG_RETRY != 0,
+    }))
+}
+
+/// Address family of a loopback frame. A `NULL` header is in the capturing
+/// host's byte order: families are small, so a value with its low bytes
+/// zero was written big-endian.
+fn loopback_family(link: LinkLayer, data: &[u8]) -> Result<u32, UdpError> {
+    let reader = UdpReader::new(data);
+    let big_endian = link == LinkLayer::Loop || reader.read_u16_be(0)? == 0;
+    reader.read_u32(0, big_endian)
+}
+
+fn is_ip_family(family: u32) -> bool {
+    family == layout::AF_INET || layout::AF_INET6.contains(&family)
+}
+
+/// VLAN tags in front of the network layer of an Ethernet frame.
+struct VlanTags {
+    /// VLAN IDs, outermost first.
+    ids: [Option<u16>; layout::MAX_VLAN_TAGS],
+    /// EtherType after the last tag.
+    ether_type: u16,
+    /// Offset of the network layer.
+    offset: usize,
+}
+
+/// Read the VLAN tags of an Ethernet frame; `None` when it is untagged or
+/// truncated inside a tag, so the frame is sliced (and rejected) as plain
+/// Ethernet.
+fn vlan_tags(data: &[u8]) -> Option<VlanTags> {
+    let reader = UdpReader::new(data);
+    let mut tags = VlanTags {
+        ids: [None; layout::MAX_VLAN_TAGS],
+        ether_type: reader.read_u16_be(layout::ETHER_TYPE_OFFSET).ok()?,
+        offset: layout::ETHER_TYPE_OFFSET + 2,
+    };
+    for id in &mut tags.ids {
+        if !layout::VLAN_TPIDS.contains(&tags.ether_type) {
+            break;
+        }
+        *id = Some(reader.read_u16_be(tags.offset).ok()? & layout::VLAN_ID_MASK);
+        tags.ether_type = reader.read_u16_be(tags.offset + 2).ok()?;
+        tags.offset += layout::VLAN_TAG_LEN;
+    }
+    tags.ids[0].map(|_| tags)
+}
+
+fn ipv4_header_checksum(ipv4: &Ipv4Slice<'_>) -> Option<ChecksumMismatch> {
+    let header = ipv4.header();
+    mismatch(
+        Some(header.to_header().calc_header_checksum()),
+        header.header_checksum(),
+    )
+}
+
+/// A zero UDP checksum over IPv4 means the sender did not compute one.
+fn ipv4_udp_checksum(ipv4: &Ipv4Slice<'_>, udp: &UdpSlice<'_>) -> Option<ChecksumMismatch> {
+    if udp.checksum() == 0 {
+        return None;
+    }
+    let header = ipv4.header();
+    let expected = udp
+        .to_header()
+        .calc_checksum_ipv4_raw(header.source(), header.destination(), udp.payload())
+        .ok();
+    mismatch(expected, udp.checksum())
+}
+
+fn mismatch(expected: Option<u16>, actual: u16) -> Option<ChecksumMismatch> {
+    expected
+        .filter(|expected| *expected != actual)
+        .map(|expected| ChecksumMismatch { expected, actual })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LinkLayer, TunnelKind, decapsulate, parse_truncated_udp_packet, parse_udp_packet};
+    use crate::udp::error::UdpError;
+    use etherparse::{IpHeaders, IpNumber, Ipv4Header, PacketBuilder};
+
+    #[test]
+    fn parse_udp_ok() {
+        let builder = PacketBuilder::ethernet2([1, 2, 3, 4, 5, 6], [7, 8, 9, 10, 11, 12])
+            .ipv4([192, 168, 0, 1], [192, 168, 0, 2], 64)
+            .udp(6454, 6454);
+        let payload = [1, 2, 3, 4];
+        let mut packet = Vec::<u8>::with_capacity(builder.size(payload.len()));
+        builder.write(&mut packet, &payload).unwrap();
+
+        let parsed = parse_udp_packet(LinkLayer::Ethernet, &packet).unwrap();
+        assert!(parsed.is_some());
+        let parsed = parsed.unwrap();
+        assert_eq!(parsed.src_port, 6454);
+        assert_eq!(parsed.dst_port, 6454);
+        assert_eq!(parsed.payload, payload);
+        assert_eq!(parsed.ip_len, 20 + 8 + payload.len());
+        assert_eq!(parsed.ttl, 64);
+    }
+
+    #[test]
+    fn parse_fragmented_udp_is_reported() {
+        let mut header =
+            Ipv4Header::new(0, 64, IpNumber::UDP, [10, 0, 0, 1], [239, 255, 0, 1]).unwrap();
+        header.more_fragments = true;
+        let builder = PacketBuilder::ethernet2([1, 2, 3, 4, 5, 6], [7, 8, 9, 10, 11, 12])
+            .ip(IpHeaders::Ipv4(header, Default::default()))
+            .udp(5568, 5568);
+        let payload = [0u8; 16];
+        let mut packet = Vec::<u8>::with_capacity(builder.size(payload.len()));
+        builder.write(&mut packet, &payload).unwrap();
+
+        let result = parse_udp_packet(LinkLayer::Ethernet, &packet);
+        assert!(matches!(
+            result,
+            Err(UdpError::Fragmented { ip_len: 44, .. })
+        ));
+    }
+
+    #[test]
+    fn parse_truncated_reads_headers_of_cut_packets() {
+        let builder = PacketBuilder::ethernet2([1, 2, 3, 4, 5, 6], [7, 8, 9, 10, 11, 12])
+            .vlan(etherparse::VlanHeader::Single(
+                etherparse::SingleVlanHeader {
+                    vlan_id: 10.try_into().unwrap(),
+                    ..Default::default()
+                },
+            ))
+            .ipv4([192, 168, 0, 1], [192, 168, 0, 2], 64)
+            .udp(6454, 6454);
+        let payload = [7u8; 530];
+        let mut packet = Vec::<u8>::with_capacity(builder.size(payload.len()));
+        builder.write(&mut packet, &payload).unwrap();
+        packet.truncate(100);
+
+        assert!(parse_udp_packet(LinkLayer::Ethernet, &packet).is_err());
+        let parsed = parse_truncated_udp_packet(LinkLayer::Ethernet, &packet)
+            .unwrap()
+            .unwrap();
+        assert_eq!(parsed.dst_port, 6454);
+        assert_eq!(parsed.vlan, Some(10));
+        assert_eq!(parsed.ip_len, 20 + 8 + payload.len());
+        assert_eq!(parsed.payload, &payload[..100 - 46]);
+        assert_eq!(parsed.udp_checksum, None);
+
+        // Headers cut in the middle cannot be read either way.
+        for len in [30, 42] {
+            assert!(matches!(
+                parse_truncated_udp_packet(LinkLayer::Ethernet, &packet[..len]),
+                Err(UdpError::Slice(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn parse_reports_checksum_mismatches() {
+        let builder = PacketBuilder::ethernet2([1, 2, 3, 4, 5, 6], [7, 8, 9, 10, 11, 12])
+            .ipv4([192, 168, 0, 1], [192, 168, 0, 2], 64)
+            .udp(6454, 6454);
+        let payload = [1, 2, 3, 4];
+        let mut packet = Vec::<u8>::with_capacity(builder.size(payload.len()));
+        builder.write(&mut packet, &payload).unwrap();
+
+        let parsed = parse_udp_packet(LinkLayer::Ethernet, &packet)
+            .unwrap()
+            .unwrap();
+        assert_eq!(parsed.ip_checksum, None);
+        assert_eq!(parsed.udp_checksum, None);
+
+        // Ethernet (14) + IPv4 checksum at 10..12, UDP checksum at 20 + 6..8.
+        let mut corrupted = packet.clone();
+        corrupted[14 + 10] ^= 0xff;
+        corrupted[14 + 20 + 6] ^= 0xff;
+        let parsed = parse_udp_packet(LinkLayer::Ethernet, &corrupted)
+            .unwrap()
+            .unwrap();
+        let ip = parsed.ip_checksum.expect("ip checksum mismatch");
+        assert_eq!(ip.expected, u16::from_be_bytes([packet[24], packet[25]]));
+        assert!(parsed.udp_checksum.is_some());
+
+        // A zero UDP checksum over IPv4 is "not computed".
+        corrupted[14 + 20 + 6] = 0;
+        corrupted[14 + 20 + 7] = 0;
+        let parsed = parse_udp_packet(LinkLayer::Ethernet, &corrupted)
+            .unwrap()
+            .unwrap();
+        assert_eq!(parsed.udp_checksum, None);
+    }
+
+    #[test]
+    fn parse_reads_single_and_double_vlan_tags() {
+        let builder = PacketBuilder::ethernet2([1, 2, 3, 4, 5, 6], [7, 8, 9, 10, 11, 12])
+            .ipv4([10, 0, 0, 1], [239, 255, 0, 1], 64)
+            .udp(5568, 5568);
+        let payload = [1, 2, 3, 4];
+        let mut untagged = Vec::<u8>::with_capacity(builder.size(payload.len()));
+        builder.write(&mut untagged, &payload).unwrap();
+        let parsed = parse_udp_packet(LinkLayer::Ethernet, &untagged)
+            .unwrap()
+            .unwrap();
+        assert_eq!((parsed.vlan, parsed.outer_vlan), (None, None));
+
+        let tag = |tpid: u16, tci: u16| [tpid.to_be_bytes(), tci.to_be_bytes()].concat();
+        let tagged = |tags: &[Vec<u8>]| {
+            let mut frame = untagged[..12].to_vec();
+            for tag in tags {
+                frame.extend_from_slice(tag);
+            }
+            frame.extend_from_slice(&untagged[12..]);
+            frame
+        };
+        // Priority bits (0x6000) are not part of the VLAN ID.
+        let single = tagged(&[tag(0x8100, 0x6000 | 10)]);
+        let parsed = parse_udp_packet(LinkLayer::Ethernet, &single)
+            .unwrap()
+            .unwrap();
+        assert_eq!((parsed.vlan, parsed.outer_vlan), (Some(10), None));
+        assert_eq!(parsed.payload, payload);
+
+        for outer_tpid in [0x88a8, 0x9100, 0x9200] {
+            let double = tagged(&[tag(outer_tpid, 200), tag(0x8100, 10)]);
+            let parsed = parse_udp_packet(LinkLayer::Ethernet, &double)
+                .unwrap()
+                .unwrap();
+            assert_eq!((parsed.vlan, parsed.outer_vlan), (Some(10), Some(200)));
+            assert_eq!(parsed.dst_port, 5568);
+        }
+
+        let truncated = &single[..15];
+        assert!(matches!(
+            parse_udp_packet(LinkLayer::Ethernet, truncated),
+            Err(UdpError::Slice(_))
+        ));
+    }
+
+    #[test]
+    fn parse_reads_loopback_frames_in_either_byte_order() {
+        let builder = PacketBuilder::ipv4([127, 0, 0, 1], [127, 0, 0, 1], 64).udp(6454, 6454);
+        let payload = [1, 2, 3, 4];
+        let mut datagram = Vec::<u8>::with_capacity(builder.size(payload.len()));
+        builder.write(&mut datagram, &payload).unwrap();
+        let frame = |family: [u8; 4]| [&family[..], &datagram].concat();
+
+        for (link, family) in [
+            (LinkLayer::Null, 2u32.to_le_bytes()),
+            (LinkLayer::Null, 2u32.to_be_bytes()),
+            (LinkLayer::Loop, 2u32.to_be_bytes()),
+        ] {
+            let frame = frame(family);
+            let parsed = parse_udp_packet(link, &frame).unwrap().unwrap();
+            assert_eq!(parsed.dst_port, 6454);
+            assert_eq!(parsed.payload, payload);
+        }
+        // AF_INET little-endian is not AF_INET for OpenBSD's LOOP.
+        let other_family = frame(2u32.to_le_bytes());
+        assert!(
+            parse_udp_packet(LinkLayer::Loop, &other_family)
+                .unwrap()
+                .is_none()
+        );
+        assert!(matches!(
+            parse_udp_packet(LinkLayer::Null, &[2, 0]),
+            Err(UdpError::TooShort {
+                needed: 4,
+                actual: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn decapsulate_unwraps_gre_and_erspan_tunnels() {
+        let builder = PacketBuilder::ethernet2([1, 2, 3, 4, 5, 6], [7, 8, 9, 10, 11, 12])
+            .ipv4([192, 168, 0, 1], [192, 168, 0, 2], 64)
+            .udp(6454, 6454);
+        let payload = [1, 2, 3, 4];
+        let mut inner = Vec::<u8>::with_capacity(builder.size(payload.len()));
+        builder.write(&mut inner, &payload).unwrap();
+        let gre = |header: &[u8], inner: &[u8]| {
+            let len = u16::try_from(header.len() + inner.len()).unwrap();
+            let ip = Ipv4Header::new(len, 64, IpNumber::GRE, [10, 0, 0, 1], [10, 0, 0, 2]);
+            let mut packet = vec![7, 8, 9, 10, 11, 12, 1, 2, 3, 4, 5, 6, 0x08, 0x00];
+            ip.unwrap().write(&mut packet).unwrap();
+            [&packet[..], header, inner].concat()
+        };
+
+        let erspan_i = [0x00, 0x00, 0x88, 0xbe];
+        let erspan_ii = [
+            &[0x10, 0x00, 0x88, 0xbe, 0, 0, 0, 1][..],
+            &[0x10, 0, 0, 1, 0, 0, 0, 0],
+        ]
+        .concat();
+        let erspan_iii = [
+            &[0x10, 0x00, 0x22, 0xeb, 0, 0, 0, 1][..],
+            &[0x20, 0, 0, 1],
+            &[0; 7],
+            &[0x01],
+            &[0; 8],
+        ]
+        .concat();
+        let bridged = [0x20, 0x00, 0x65, 0x58, 0, 0, 0, 42];
+        for (header, kind) in [
+            (&erspan_i[..], TunnelKind::Erspan),
+            (&erspan_ii, TunnelKind::Erspan),
+            (&erspan_iii, TunnelKind::Erspan),
+            (&bridged, TunnelKind::Gre),
+        ] {
+            let packet = gre(header, &inner);
+            let tunnel = decapsulate(LinkLayer::Ethernet, &packet).unwrap();
+            assert_eq!((tunnel.kind, tunnel.link), (kind, LinkLayer::Ethernet));
+            assert_eq!(tunnel.frame, inner);
+        }
+
+        let packet = gre(&[0x00, 0x00, 0x08, 0x00], &inner[14..]);
+        let tunnel = decapsulate(LinkLayer::Ethernet, &packet).unwrap();
+        assert_eq!(tunnel.link, LinkLayer::RawIp);
+        let parsed = parse_udp_packet(tunnel.link, tunnel.frame)
+            .unwrap()
+            .unwrap();
+        assert_eq!(parsed.payload, payload);
+
+        // Unknown GRE protocols and frames outside a tunnel are left alone.
+        assert!(decapsulate(LinkLayer::Ethernet, &gre(&[0, 0, 0x88, 0x0b], &inner)).is_none());
+        assert!(decapsulate(LinkLayer::Ethernet, &inner).is_none());
+    }
+
+    #[test]
+    fn parse_reads_radiotap_and_802_11_data_frames() {
+        let builder = PacketBuilder::ipv4([10, 0, 0, 1], [10, 0, 0, 255], 64).udp(6454, 6454);
+        let payload = [1, 2, 3, 4];
+        let mut datagram = Vec::<u8>::with_capacity(builder.size(payload.len()));
+        builder.write(&mut datagram, &payload).unwrap();
+        let wifi = |kind: u8, flags: u8| {
+            let mut frame = vec![kind, flags, 0, 0];
+            frame.extend_from_slice(&[0xff; 18]);
+            frame.extend_from_slice(&[0x10, 0x00]);
+            if kind & 0x80 != 0 {
+                frame.extend_from_slice(&[0x00, 0x00]);
+            }
+            frame.extend_from_slice(&[0xaa, 0xaa, 0x03, 0, 0, 0, 0x08, 0x00]);
+            frame.extend_from_slice(&datagram);
+            frame
+        };
+        // Radiotap with TSFT and Flags (FCS at the end of the frame).
+        let mut radiotap = vec![0, 0, 17, 0, 0x03, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x10];
+        // QoS data frame, from the access point, retried.
+        radiotap.extend_from_slice(&wifi(0x88, 0x0a));
+        radiotap.extend_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+
+        let parsed = parse_udp_packet(LinkLayer::Radiotap, &radiotap)
+            .unwrap()
+            .unwrap();
+        assert_eq!(parsed.payload, payload);
+        assert_eq!(parsed.wifi_retry, Some(true));
+        let frame = wifi(0x08, 0x02);
+        let parsed = parse_udp_packet(LinkLayer::Ieee80211, &frame)
+            .unwrap()
+            .unwrap();
+        assert_eq!(parsed.wifi_retry, Some(false));
+        assert_eq!(parsed.src_port, 6454);
+
+        // Encrypted data and beacons carry no readable IP.
+        for (kind, flags) in [(0x88, 0x42), (0x80, 0x00)] {
+            let frame = wifi(kind, flags);
+            assert!(
+                parse_udp_packet(LinkLayer::Ieee80211, &frame)
+                    .unwrap()
+                    .is_none()
+            );
+        }
+        assert!(matches!(
+            parse_udp_packet(LinkLayer::Radiotap, &[0, 0, 8, 0]),
+            Err(UdpError::TooShort { .. })
+        ));
+    }
+
+    #[test]
+    fn parse_non_udp() {
+        let builder = PacketBuilder::ethernet2([1, 1, 1, 1, 1, 1], [2, 2, 2, 2, 2, 2])
+            .ipv4([10, 0, 0, 1], [10, 0, 0, 2], 64)
+            .tcp(1000, 1001, 0, 0);
+        let payload = [0u8; 4];
+        let mut packet = Vec::<u8>::with_capacity(builder.size(payload.len()));
+        builder.write(&mut packet, &payload).unwrap();
+
+        let parsed = parse_udp_packet(LinkLayer::Ethernet, &packet).unwrap();
+        assert!(parsed.is_none());
+    }
+
+    #[test]
+    fn parse_slice_error() {
+        let data = [];
+        let result = parse_udp_packet(LinkLayer::Ethernet, &data);
+        assert!(matches!(result, Err(UdpError::Slice(_))));
+    }
+}
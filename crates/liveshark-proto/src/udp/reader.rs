@@ -3,12 +3,9 @@ use super::layout;
 
 /// Safe byte reader for UDP payloads.
 ///
-/// Note: this reader lives in an internal module; the example is illustrative
-/// and not compiled as a public doctest.
-///
 /// # Examples
-/// ```text
-/// use liveshark_core::analysis::udp::reader::UdpReader;
+/// ```
+/// use liveshark_proto::udp::reader::UdpReader;
 ///
 /// let payload = [0u8; 12];
 /// let reader = UdpReader::new(&payload);
@@ -35,6 +32,46 @@ impl<'a> UdpReader<'a> {
         Ok(())
     }
 
+    /// Read a byte at the given offset.
+    pub fn read_u8(&self, offset: usize) -> Result<u8, UdpError> {
+        self.require_len(offset + 1)?;
+        Ok(self.payload[offset])
+    }
+
+    /// Read a little-endian `u16` at the given offset.
+    pub fn read_u16_le(&self, offset: usize) -> Result<u16, UdpError> {
+        self.require_len(offset + 2)?;
+        Ok(u16::from_le_bytes([
+            self.payload[offset],
+            self.payload[offset + 1],
+        ]))
+    }
+
+    /// Read a big-endian `u16` at the given offset.
+    pub fn read_u16_be(&self, offset: usize) -> Result<u16, UdpError> {
+        self.require_len(offset + 2)?;
+        Ok(u16::from_be_bytes([
+            self.payload[offset],
+            self.payload[offset + 1],
+        ]))
+    }
+
+    /// Read a 32-bit integer at the given offset, big- or little-endian.
+    pub fn read_u32(&self, offset: usize, big_endian: bool) -> Result<u32, UdpError> {
+        self.require_len(offset + 4)?;
+        let bytes = [
+            self.payload[offset],
+            self.payload[offset + 1],
+            self.payload[offset + 2],
+            self.payload[offset + 3],
+        ];
+        Ok(if big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    }
+
     /// Return the UDP payload without the header.
     pub fn payload_without_header(&self) -> Result<&'a [u8], UdpError> {
         self.require_len(layout::UDP_HEADER_LEN)?;
@@ -50,7 +87,7 @@ impl<'a> UdpReader<'a> {
 #[cfg(test)]
 mod tests {
     use super::UdpReader;
-    use crate::analysis::udp::error::UdpError;
+    use crate::udp::error::UdpError;
 
     #[test]
     fn payload_without_header_ok() {
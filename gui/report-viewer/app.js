@@ -64,10 +64,13 @@ const copyJsonBtn = document.getElementById("copyJsonBtn");
 
 const summaryVersion = document.getElementById("summaryVersion");
 const summaryDuration = document.getElementById("summaryDuration");
+const summaryHealth = document.getElementById("summaryHealth");
 const summaryUniverses = document.getElementById("summaryUniverses");
 const summaryFlows = document.getElementById("summaryFlows");
 const summaryConflicts = document.getElementById("summaryConflicts");
 const summaryViolations = document.getElementById("summaryViolations");
+const notesSection = document.getElementById("notesSection");
+const notesList = document.getElementById("notesList");
 
 openBtn.addEventListener("click", () => fileInput.click());
 fileInput.addEventListener("change", (event) => {
@@ -198,6 +201,8 @@ function updateSummary() {
   summaryVersion.textContent = fmtOptional(report.report_version);
   const duration = report.capture_summary?.duration_s;
   summaryDuration.textContent = fmtOptional(fmtNumber(duration, 2));
+  const health = fmtNumber(report.health?.score, 1);
+  summaryHealth.textContent = health === null ? "N/A" : `${health}/100`;
 
   summaryUniverses.textContent = Array.isArray(report.universes)
     ? report.universes.length
@@ -208,6 +213,18 @@ function updateSummary() {
     : 0;
 
   summaryViolations.textContent = countViolations(report.compliance || []);
+  renderNotes(report.notes);
+}
+
+function renderNotes(notes) {
+  notesList.innerHTML = "";
+  const items = Array.isArray(notes) ? notes : [];
+  items.forEach((note) => {
+    const li = document.createElement("li");
+    li.textContent = String(note);
+    notesList.appendChild(li);
+  });
+  notesSection.hidden = items.length === 0;
 }
 
 function countViolations(compliance) {
@@ -453,7 +470,7 @@ function buildRows(report, tab) {
             id: v.id,
             severity: v.severity,
             count: v.count,
-            examples: examples.slice(0, 3).join("; "),
+            examples: examples.slice(0, 3).map(fmtExample).join(" | "),
             raw: v,
           };
           row._details = {
@@ -461,6 +478,7 @@ function buildRows(report, tab) {
             id: v.id,
             severity: v.severity,
             occurrences: v.count,
+            reference: v.spec_reference,
             examples,
           };
           row._filterValues = collectFilterValues(tab, row);
@@ -585,6 +603,7 @@ function renderComplianceDetails(row) {
     ["id", details.id],
     ["severity", details.severity],
     ["occurrences", details.occurrences],
+    ["reference", details.reference],
   ];
   overviewPairs.forEach(([key, value]) => {
     const keyEl = document.createElement("div");
@@ -612,7 +631,7 @@ function renderComplianceDetails(row) {
   } else {
     examples.forEach((example) => {
       const item = document.createElement("li");
-      item.textContent = String(example);
+      item.textContent = fmtExample(example);
       examplesList.appendChild(item);
     });
   }
@@ -645,6 +664,23 @@ function fmtOptional(value) {
   return value;
 }
 
+// report_version 1 examples are strings; version 2 examples are objects.
+function fmtExample(example) {
+  if (example === null || typeof example !== "object") {
+    return String(example);
+  }
+  const ip = example.source_ip ?? "unknown";
+  const source = example.source_port === undefined ? ip : `${ip}:${example.source_port}`;
+  let text = `source ${source} @ ${example.timestamp ?? "unknown"}`;
+  if (example.detail) {
+    text = `${text}; ${example.detail}`;
+  }
+  if (example.bytes) {
+    text = `${text}; bytes@${example.bytes.offset}: ${example.bytes.hex}`;
+  }
+  return text;
+}
+
 function fmtNumber(value, digits) {
   if (value === null || value === undefined || Number.isNaN(value)) {
     return null;